@@ -15,7 +15,7 @@ use common::generate_diff_patch;
 use octorus::github::ChangedFile;
 use octorus::symbol::{
     extract_all_identifiers, find_definition_in_patches, find_definition_in_repo,
-    is_definition_line, is_import_line,
+    is_definition_line, is_import_line, PatchSymbolTable,
 };
 
 // ---------------------------------------------------------------------------
@@ -153,10 +153,13 @@ fn bench_find_definition_in_patches_miss(c: &mut Criterion) {
             BenchmarkId::from_parameter(format!("{}_files", file_count)),
             &files,
             |b, files| {
+                // The app builds the table once per PR data load, not per lookup
+                let table = PatchSymbolTable::build(files);
                 b.iter(|| {
                     black_box(find_definition_in_patches(
                         black_box("nonexistent_symbol_xyz"),
                         black_box(files),
+                        black_box(&table),
                         black_box(0),
                     ))
                 });
@@ -185,10 +188,13 @@ fn bench_find_definition_in_patches_hit(c: &mut Criterion) {
             BenchmarkId::from_parameter(format!("{}_files", file_count)),
             &files,
             |b, files| {
+                // The app builds the table once per PR data load, not per lookup
+                let table = PatchSymbolTable::build(files);
                 b.iter(|| {
                     black_box(find_definition_in_patches(
                         black_box("target_symbol"),
                         black_box(files),
+                        black_box(&table),
                         black_box(0),
                     ))
                 });
@@ -25,7 +25,7 @@ use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 
 use common::{generate_diff_patch, generate_haskell_diff_patch, generate_vue_diff_patch};
-use octorus::{build_diff_cache, render_cached_lines, ParserPool};
+use octorus::{build_diff_cache, render_cached_lines, LineRenderOptions, ParserPool};
 
 /// Benchmark diff cache building with syntax highlighting.
 ///
@@ -175,7 +175,7 @@ fn bench_selected_line_rendering(c: &mut Criterion) {
                         0..cache.lines.len(),
                         selected,
                         comments,
-                        false,
+                        LineRenderOptions::default(),
                         None,
                         120,
                     ))
@@ -258,7 +258,7 @@ fn bench_visible_range_processing(c: &mut Criterion) {
                         visible_start..visible_end,
                         scroll_offset,
                         comments,
-                        false,
+                        LineRenderOptions::default(),
                         None,
                         120,
                     ))
@@ -218,7 +218,7 @@ pub trait AgentAdapter: Send + Sync {
 pub enum SupportedAgent {
     Claude,
     Codex,
-    // Gemini, // Future
+    Gemini,
 }
 
 impl SupportedAgent {
@@ -226,7 +226,7 @@ impl SupportedAgent {
         match name.to_lowercase().as_str() {
             "claude" => Some(Self::Claude),
             "codex" => Some(Self::Codex),
-            // "gemini" => Some(Self::Gemini),
+            "gemini" => Some(Self::Gemini),
             _ => None,
         }
     }
@@ -237,7 +237,7 @@ impl SupportedAgent {
         match self {
             Self::Claude => "claude",
             Self::Codex => "codex",
-            // Self::Gemini => "gemini",
+            Self::Gemini => "gemini",
         }
     }
 }
@@ -49,7 +49,7 @@ pub(crate) struct RawPermissionRequest {
 
 /// Parse reviewer output from a JSON result value.
 ///
-/// `agent_name` is used in error messages (e.g., "claude", "codex").
+/// `agent_name` is used in error messages (e.g., "claude", "codex", "gemini").
 pub(crate) fn parse_reviewer_output(
     result: Option<&serde_json::Value>,
     agent_name: &str,
@@ -96,7 +96,7 @@ pub(crate) fn parse_reviewer_output(
 
 /// Parse reviewee output from a JSON result value.
 ///
-/// `agent_name` is used in error messages (e.g., "claude", "codex").
+/// `agent_name` is used in error messages (e.g., "claude", "codex", "gemini").
 pub(crate) fn parse_reviewee_output(
     result: Option<&serde_json::Value>,
     agent_name: &str,
@@ -0,0 +1,272 @@
+use anyhow::{anyhow, Context as AnyhowContext, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::process::Stdio;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::ai::adapter::{AgentAdapter, Context, RevieweeOutput, RevieweeProposal, ReviewerOutput};
+use crate::ai::orchestrator::RallyEvent;
+
+use super::common::{parse_reviewee_output, parse_reviewee_proposal, parse_reviewer_output};
+
+const REVIEWER_SCHEMA: &str = include_str!("../schemas/reviewer.json");
+const REVIEWEE_SCHEMA: &str = include_str!("../schemas/reviewee.json");
+const REVIEWEE_PROPOSAL_SCHEMA: &str = include_str!("../schemas/reviewee_proposal.json");
+
+/// Gemini-specific errors
+#[derive(Debug, Error)]
+pub enum GeminiError {
+    #[error("Gemini CLI not found. Install it with: npm install -g @google/gemini-cli")]
+    CliNotFound,
+    #[error("Gemini returned an error: {0}")]
+    ResponseError(String),
+}
+
+/// Prompt prefix for local mode git write prohibition (best-effort, same as Codex)
+const LOCAL_MODE_GIT_CONSTRAINT: &str = "\
+CRITICAL CONSTRAINT: This is a LOCAL-ONLY session. The following git commands are ABSOLUTELY \
+FORBIDDEN and must NEVER be executed: git add, git commit, git push, git stash, git switch, \
+git branch, git merge, git rebase, git reset, git cherry-pick, git revert, git checkout, \
+git restore, git tag, git rm, git clean. Only read-only git commands (status, diff, log, show) \
+are permitted. Edit files directly without staging or committing.\n\n";
+
+/// Google Gemini CLI adapter
+///
+/// The `gemini` CLI has no structured-output option, so the JSON schema is appended to
+/// the prompt and the JSON object is extracted from the final response text.
+pub struct GeminiAdapter {
+    event_sender: Option<mpsc::Sender<RallyEvent>>,
+    /// When true, git write prohibition is prepended to prompts (best-effort)
+    local_mode: bool,
+}
+
+impl GeminiAdapter {
+    pub fn new() -> Self {
+        Self {
+            event_sender: None,
+            local_mode: false,
+        }
+    }
+
+    async fn send_event(&self, event: RallyEvent) {
+        if let Some(ref sender) = self.event_sender {
+            let _ = sender.send(event).await;
+        }
+    }
+
+    fn effective_prompt(&self, prompt: &str) -> String {
+        if self.local_mode {
+            format!("{}{}", LOCAL_MODE_GIT_CONSTRAINT, prompt)
+        } else {
+            prompt.to_string()
+        }
+    }
+
+    /// Run Gemini CLI in non-interactive mode and return the parsed JSON result
+    ///
+    /// - Reviewer / proposal: default approval mode (tools that need confirmation are unavailable)
+    /// - Reviewee: `--yolo` (auto-approve edits and shell commands)
+    async fn run_gemini(
+        &self,
+        prompt: &str,
+        schema: &str,
+        auto_approve: bool,
+        working_dir: Option<&str>,
+    ) -> Result<serde_json::Value> {
+        let mut cmd = Command::new("gemini");
+        cmd.arg("--output-format").arg("json");
+        if auto_approve {
+            cmd.arg("--yolo");
+        }
+        if let Some(dir) = working_dir {
+            cmd.current_dir(dir);
+        }
+        cmd.stdin(Stdio::piped());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+
+        let mut child = cmd.spawn().map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow::Error::from(GeminiError::CliNotFound)
+            } else {
+                anyhow!("Failed to spawn gemini process: {}", e)
+            }
+        })?;
+
+        self.send_event(RallyEvent::AgentThinking("Processing...".to_string()))
+            .await;
+
+        // Write prompt to stdin to avoid ARG_MAX limit
+        if let Some(mut stdin) = child.stdin.take() {
+            let full_prompt = format!(
+                "{}\n\nRespond with only a JSON object that matches this JSON schema, \
+                 without any surrounding text or code fences:\n{}",
+                prompt, schema
+            );
+            stdin
+                .write_all(full_prompt.as_bytes())
+                .await
+                .context("Failed to write prompt to gemini stdin")?;
+            drop(stdin); // Close stdin to signal EOF
+        }
+
+        let output = child
+            .wait_with_output()
+            .await
+            .context("Failed to wait for gemini process")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Gemini process failed with status {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let result = parse_gemini_output(&stdout)?;
+        self.send_event(RallyEvent::AgentText("Review completed.".to_string()))
+            .await;
+        Ok(result)
+    }
+}
+
+impl Default for GeminiAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `gemini --output-format json` output
+#[derive(Debug, Deserialize)]
+struct GeminiOutput {
+    #[serde(default)]
+    response: Option<String>,
+    #[serde(default)]
+    error: Option<GeminiErrorInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiErrorInfo {
+    #[serde(default)]
+    message: String,
+}
+
+/// Extract the structured result from `gemini --output-format json` output
+fn parse_gemini_output(stdout: &str) -> Result<serde_json::Value> {
+    let output: GeminiOutput =
+        serde_json::from_str(stdout.trim()).context("Failed to parse gemini output")?;
+    if let Some(error) = output.error {
+        return Err(GeminiError::ResponseError(error.message).into());
+    }
+    let response = output
+        .response
+        .ok_or_else(|| anyhow!("No response received from gemini"))?;
+    extract_json_object(&response)
+        .ok_or_else(|| anyhow!("Gemini response did not contain a JSON object"))
+}
+
+/// Parse the outermost `{...}` in `text`, tolerating code fences or prose around it
+fn extract_json_object(text: &str) -> Option<serde_json::Value> {
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    serde_json::from_str(&text[start..=end]).ok()
+}
+
+#[async_trait]
+impl AgentAdapter for GeminiAdapter {
+    fn name(&self) -> &str {
+        "gemini"
+    }
+
+    fn set_event_sender(&mut self, sender: mpsc::Sender<RallyEvent>) {
+        self.event_sender = Some(sender);
+    }
+
+    async fn run_reviewer(&mut self, prompt: &str, context: &Context) -> Result<ReviewerOutput> {
+        let result = self
+            .run_gemini(
+                prompt,
+                REVIEWER_SCHEMA,
+                false,
+                context.working_dir.as_deref(),
+            )
+            .await?;
+        parse_reviewer_output(Some(&result), "gemini")
+    }
+
+    async fn run_reviewee(&mut self, prompt: &str, context: &Context) -> Result<RevieweeOutput> {
+        // NOTE: --yolo allows git push, but the prompt explicitly prohibits it
+        let prompt = self.effective_prompt(prompt);
+        let result = self
+            .run_gemini(
+                &prompt,
+                REVIEWEE_SCHEMA,
+                true,
+                context.working_dir.as_deref(),
+            )
+            .await?;
+        parse_reviewee_output(Some(&result), "gemini")
+    }
+
+    async fn run_reviewee_proposal(
+        &mut self,
+        prompt: &str,
+        context: &Context,
+    ) -> Result<RevieweeProposal> {
+        // Without --yolo, tools that modify files or run shell commands need
+        // confirmation and are unavailable in non-interactive mode.
+        let prompt = self.effective_prompt(prompt);
+        let result = self
+            .run_gemini(
+                &prompt,
+                REVIEWEE_PROPOSAL_SCHEMA,
+                false,
+                context.working_dir.as_deref(),
+            )
+            .await?;
+        parse_reviewee_proposal(Some(&result), "gemini")
+    }
+
+    async fn continue_reviewer(&mut self, _message: &str) -> Result<ReviewerOutput> {
+        Err(anyhow!("Gemini adapter does not support resuming sessions"))
+    }
+
+    async fn continue_reviewee(&mut self, _message: &str) -> Result<RevieweeOutput> {
+        Err(anyhow!("Gemini adapter does not support resuming sessions"))
+    }
+
+    fn add_reviewee_allowed_tool(&mut self, _tool: &str) {
+        // Gemini CLI only has approval modes (default / --yolo), not per-tool permissions.
+    }
+
+    fn set_local_mode(&mut self, local_mode: bool) {
+        self.local_mode = local_mode;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gemini_output_extracts_fenced_json() {
+        let stdout = r#"{"response": "```json\n{\"action\": \"approve\", \"summary\": \"LGTM\"}\n```", "stats": {}}"#;
+        let result = parse_gemini_output(stdout).unwrap();
+        assert_eq!(result["action"], "approve");
+    }
+
+    #[test]
+    fn test_parse_gemini_output_errors() {
+        let err = parse_gemini_output(r#"{"error": {"message": "quota exceeded"}}"#).unwrap_err();
+        assert!(err.to_string().contains("quota exceeded"));
+
+        let err = parse_gemini_output(r#"{"response": "no json here"}"#).unwrap_err();
+        assert!(err.to_string().contains("did not contain a JSON object"));
+    }
+}
@@ -1,9 +1,11 @@
 mod claude;
 mod codex;
 mod common;
+mod gemini;
 
 pub use claude::ClaudeAdapter;
 pub use codex::CodexAdapter;
+pub use gemini::GeminiAdapter;
 
 use anyhow::{anyhow, Result};
 
@@ -13,17 +15,45 @@ use crate::config::AiConfig;
 /// Create an adapter from agent name.
 ///
 /// # Arguments
-/// * `name` - Agent name ("claude" or "codex")
-/// * `config` - AI configuration (used by Claude adapter for additional tools, ignored by Codex)
+/// * `name` - Agent name ("claude", "codex" or "gemini")
+/// * `config` - AI configuration (used by Claude adapter for additional tools, ignored by others)
 pub fn create_adapter(name: &str, config: &AiConfig) -> Result<Box<dyn AgentAdapter>> {
-    let agent = SupportedAgent::from_name(name)
-        .ok_or_else(|| anyhow!("Unsupported agent: {}. Supported: claude, codex", name))?;
+    let agent = SupportedAgent::from_name(name).ok_or_else(|| {
+        anyhow!(
+            "Unsupported agent: {}. Supported: claude, codex, gemini",
+            name
+        )
+    })?;
 
     match agent {
         // Claude adapter uses config for additional tools
         SupportedAgent::Claude => Ok(Box::new(ClaudeAdapter::new(config)?)),
         // Codex adapter does not support fine-grained tool control
         SupportedAgent::Codex => Ok(Box::new(CodexAdapter::new())),
-        // SupportedAgent::Gemini => Ok(Box::new(GeminiAdapter::new())),
+        // Gemini CLI only has approval modes, not per-tool permissions
+        SupportedAgent::Gemini => Ok(Box::new(GeminiAdapter::new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_gemini_adapter() {
+        assert_eq!(
+            SupportedAgent::from_name("gemini"),
+            Some(SupportedAgent::Gemini)
+        );
+        assert_eq!(
+            SupportedAgent::from_name("Gemini"),
+            Some(SupportedAgent::Gemini)
+        );
+
+        let adapter = create_adapter("gemini", &AiConfig::default()).unwrap();
+        assert_eq!(adapter.name(), "gemini");
+
+        let err = create_adapter("bard", &AiConfig::default()).err().unwrap();
+        assert!(err.to_string().contains("claude, codex, gemini"));
     }
 }
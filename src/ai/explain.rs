@@ -0,0 +1,189 @@
+//! One-off "explain this hunk" requests.
+//!
+//! Unlike a rally, this runs a single reviewer call with a focused prompt and
+//! returns the agent's summary as plain text. Nothing is posted to GitHub.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use tokio::time::timeout;
+
+use super::adapter::{AgentAdapter, Context};
+use super::adapters::create_adapter;
+use crate::config::AiConfig;
+
+/// Build the prompt asking the agent to explain a single hunk of `path`.
+pub fn build_explain_hunk_prompt(context: &Context, path: &str, hunk: &str) -> String {
+    let (target, source) = if context.local_mode {
+        ("local changes".to_string(), "the working tree".to_string())
+    } else {
+        (
+            format!("PR #{}: {}", context.pr_number, context.pr_title),
+            format!("head commit {}", context.head_sha),
+        )
+    };
+    format!(
+        r#"You are helping a reviewer understand one hunk of a code change ({target}).
+
+## File
+{path}
+
+## Hunk
+```diff
+{hunk}
+```
+
+## Your Task
+
+Explain in plain language what this hunk changes and why it likely matters:
+1. What the code did before and what it does now
+2. The intent behind the change, as far as it can be inferred
+3. Anything subtle a reviewer should double-check (edge cases, behavior changes)
+
+If you need more surrounding code, read `{path}` ({source}). Do not modify files and do not post anything to GitHub.
+
+Respond with action "comment", put the whole explanation in `summary` (Markdown is fine), and leave `comments` and `blocking_issues` empty."#,
+        target = target,
+        path = path,
+        hunk = hunk.trim_end(),
+        source = source,
+    )
+}
+
+/// Ask the configured reviewer agent to explain a hunk.
+///
+/// The reviewer's additional tools are dropped so the agent stays read-only.
+pub async fn explain_hunk(config: &AiConfig, prompt: &str, context: &Context) -> Result<String> {
+    let config = AiConfig {
+        reviewer_additional_tools: Vec::new(),
+        ..config.clone()
+    };
+    let mut adapter = create_adapter(&config.reviewer, &config)?;
+    adapter.set_local_mode(context.local_mode);
+    run_explanation(adapter.as_mut(), prompt, context, config.timeout_secs).await
+}
+
+async fn run_explanation(
+    adapter: &mut dyn AgentAdapter,
+    prompt: &str,
+    context: &Context,
+    timeout_secs: u64,
+) -> Result<String> {
+    let output = timeout(
+        Duration::from_secs(timeout_secs),
+        adapter.run_reviewer(prompt, context),
+    )
+    .await
+    .map_err(|_| anyhow!("Explanation timeout after {} seconds", timeout_secs))??;
+    Ok(output.summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::adapter::{ReviewAction, RevieweeOutput, RevieweeProposal, ReviewerOutput};
+    use crate::ai::orchestrator::RallyEvent;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+    use tokio::sync::mpsc;
+
+    /// Records the reviewer prompt and answers with a canned explanation.
+    struct ExplainMock {
+        prompts: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl AgentAdapter for ExplainMock {
+        fn name(&self) -> &str {
+            "mock"
+        }
+        fn set_event_sender(&mut self, _sender: mpsc::Sender<RallyEvent>) {}
+        async fn run_reviewer(
+            &mut self,
+            prompt: &str,
+            _context: &Context,
+        ) -> Result<ReviewerOutput> {
+            self.prompts.lock().unwrap().push(prompt.to_string());
+            Ok(ReviewerOutput {
+                action: ReviewAction::Comment,
+                summary: "Adds an early return for empty input.".to_string(),
+                comments: vec![],
+                blocking_issues: vec![],
+            })
+        }
+        async fn run_reviewee(
+            &mut self,
+            _prompt: &str,
+            _context: &Context,
+        ) -> Result<RevieweeOutput> {
+            unreachable!("explain only runs the reviewer")
+        }
+        async fn run_reviewee_proposal(
+            &mut self,
+            _prompt: &str,
+            _context: &Context,
+        ) -> Result<RevieweeProposal> {
+            unreachable!("explain only runs the reviewer")
+        }
+        async fn continue_reviewer(&mut self, _message: &str) -> Result<ReviewerOutput> {
+            unreachable!()
+        }
+        async fn continue_reviewee(&mut self, _message: &str) -> Result<RevieweeOutput> {
+            unreachable!()
+        }
+        fn add_reviewee_allowed_tool(&mut self, _tool: &str) {}
+        fn set_local_mode(&mut self, _local_mode: bool) {}
+    }
+
+    const HUNK: &str = "@@ -1,3 +1,5 @@\n fn parse(input: &str) {\n+    if input.is_empty() {\n+        return;\n+    }\n     run(input);";
+
+    fn context(local_mode: bool) -> Context {
+        Context {
+            repo: "owner/repo".to_string(),
+            pr_number: 12,
+            pr_title: "Handle empty input".to_string(),
+            pr_body: None,
+            diff: HUNK.to_string(),
+            working_dir: None,
+            head_sha: "abc123".to_string(),
+            base_branch: "main".to_string(),
+            external_comments: Vec::new(),
+            local_mode,
+            file_patches: vec![("src/parse.rs".to_string(), HUNK.to_string())],
+        }
+    }
+
+    #[test]
+    fn test_explain_prompt_includes_hunk_and_path() {
+        let prompt = build_explain_hunk_prompt(&context(false), "src/parse.rs", HUNK);
+        assert!(prompt.contains("PR #12: Handle empty input"));
+        assert!(prompt.contains("## File\nsrc/parse.rs"));
+        assert!(prompt.contains(&format!("```diff\n{}\n```", HUNK)));
+        assert!(prompt.contains("read `src/parse.rs` (head commit abc123)"));
+
+        let local = build_explain_hunk_prompt(&context(true), "src/parse.rs", HUNK);
+        assert!(local.contains("(local changes)"));
+        assert!(!local.contains("PR #12"));
+        assert!(local.contains("read `src/parse.rs` (the working tree)"));
+    }
+
+    #[tokio::test]
+    async fn test_run_explanation_returns_summary_for_prompt() {
+        let prompts = Arc::new(Mutex::new(Vec::new()));
+        let mut adapter = ExplainMock {
+            prompts: prompts.clone(),
+        };
+        let context = context(false);
+        let prompt = build_explain_hunk_prompt(&context, "src/parse.rs", HUNK);
+
+        let explanation = run_explanation(&mut adapter, &prompt, &context, 5)
+            .await
+            .unwrap();
+
+        assert_eq!(explanation, "Adds an early return for empty input.");
+        let sent = prompts.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains("src/parse.rs"));
+        assert!(sent[0].contains("+    if input.is_empty() {"));
+    }
+}
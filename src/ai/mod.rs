@@ -1,6 +1,8 @@
 pub mod adapter;
 pub mod adapters;
+pub mod explain;
 pub mod orchestrator;
+pub mod pending;
 pub mod prompt_loader;
 pub mod prompts;
 pub mod session;
@@ -9,5 +11,6 @@ pub use adapter::{
     Context, ProposalItem, ReviewAction, RevieweeOutput, RevieweeProposal, RevieweeProposalStatus,
     RevieweeStatus, ReviewerOutput,
 };
-pub use orchestrator::{Orchestrator, RallyState};
+pub use orchestrator::{Orchestrator, RallyState, StepInput};
+pub use pending::PendingComment;
 pub use prompt_loader::{PromptLoader, PromptSource};
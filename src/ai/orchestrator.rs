@@ -1,19 +1,24 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::timeout;
 use tracing::warn;
 
-use crate::config::{AiConfig, ProposalPostStrategy};
-use crate::github;
+use crate::config::{AiConfig, ProposalPostStrategy, RallyMode};
 use crate::github::comment::{fetch_discussion_comments, fetch_review_comments};
+use crate::github::{self, GitHubBackend};
 
 use super::adapter::{
     AgentAdapter, Context, ExternalComment, ReviewAction, RevieweeOutput, RevieweeProposal,
     RevieweeStatus, ReviewerOutput,
 };
 use super::adapters::create_adapter;
+use super::pending::{
+    build_pending_comments, commit_pending_comments, GithubCommentPoster, PendingComment,
+    POST_INTERVAL,
+};
 use super::prompt_loader::PromptLoader;
 use super::prompts::{
     build_clarification_prompt, build_clarification_skipped_prompt, build_permission_denied_prompt,
@@ -70,8 +75,6 @@ impl RallyState {
     }
 
     /// Rally が完了、中断、またはエラーで終了したかどうか
-    // Kept for state inspection in tests and future multi-agent coordination
-    #[allow(dead_code)]
     pub fn is_finished(&self) -> bool {
         match self {
             Self::Completed | Self::Aborted | Self::Error => true,
@@ -117,6 +120,17 @@ pub enum RallyEvent {
     FixPostConfirmNeeded(FixPostInfo),
     /// Post confirmation needed before posting a reviewee proposal to the PR.
     ProposalPostConfirmNeeded(ProposalPostInfo),
+    /// Inline comments from a review, held back in `RallyMode::DryRun`
+    /// until the user commits them.
+    PendingComments(Vec<PendingComment>),
+    /// An agent call is about to run with these inputs. Recorded by the TUI
+    /// so the step can be replayed later (`retry_iteration`).
+    StepStarted(StepInput),
+    /// A replayed step finished; `output` replaces the step's earlier result.
+    StepReplayed {
+        iteration: u32,
+        output: StepOutput,
+    },
     Error(String),
     Log(String),
     /// Orchestrator has paused at a checkpoint
@@ -187,6 +201,113 @@ pub struct ProposalPostInfo {
     pub plan_item_count: usize,
 }
 
+/// Which agent call of an iteration a [`StepInput`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentStep {
+    Review,
+    Fix,
+    Proposal,
+}
+
+impl AgentStep {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Review => "review",
+            Self::Fix => "fix",
+            Self::Proposal => "proposal",
+        }
+    }
+}
+
+/// Inputs of a single agent call: enough to run the same step again without
+/// replaying the iterations before it.
+#[derive(Debug, Clone)]
+pub struct StepInput {
+    pub iteration: u32,
+    pub step: AgentStep,
+    pub prompt: String,
+    pub context: Context,
+}
+
+/// Result of a replayed agent call.
+#[derive(Debug, Clone)]
+pub enum StepOutput {
+    Review(ReviewerOutput),
+    Fix(RevieweeOutput),
+    Proposal(RevieweeProposal),
+}
+
+impl StepOutput {
+    /// The event the rally emits when the step completes normally.
+    pub fn into_event(self) -> RallyEvent {
+        match self {
+            Self::Review(review) => RallyEvent::ReviewCompleted(review),
+            Self::Fix(fix) => RallyEvent::FixCompleted(fix),
+            Self::Proposal(proposal) => RallyEvent::ProposalCompleted(proposal),
+        }
+    }
+
+    pub fn summary(&self) -> &str {
+        match self {
+            Self::Review(review) => &review.summary,
+            Self::Fix(fix) => &fix.summary,
+            Self::Proposal(proposal) => &proposal.summary,
+        }
+    }
+}
+
+/// Re-run a recorded agent step with the same prompt and context.
+///
+/// Nothing is posted and the rally session is left untouched; the caller
+/// swaps the output in for the step's previous result.
+pub async fn replay_step(
+    config: &AiConfig,
+    input: &StepInput,
+    event_sender: mpsc::Sender<RallyEvent>,
+) -> Result<StepOutput> {
+    let agent = match input.step {
+        AgentStep::Review => &config.reviewer,
+        AgentStep::Fix | AgentStep::Proposal => &config.reviewee,
+    };
+    let mut adapter = create_adapter(agent, config)?;
+    adapter.set_event_sender(event_sender);
+    adapter.set_local_mode(input.context.local_mode);
+    run_step(adapter.as_mut(), input, config.timeout_secs).await
+}
+
+async fn run_step(
+    adapter: &mut dyn AgentAdapter,
+    input: &StepInput,
+    timeout_secs: u64,
+) -> Result<StepOutput> {
+    let (prompt, context) = (input.prompt.as_str(), &input.context);
+    let run = async {
+        match input.step {
+            AgentStep::Review => adapter
+                .run_reviewer(prompt, context)
+                .await
+                .map(StepOutput::Review),
+            AgentStep::Fix => adapter
+                .run_reviewee(prompt, context)
+                .await
+                .map(StepOutput::Fix),
+            AgentStep::Proposal => adapter
+                .run_reviewee_proposal(prompt, context)
+                .await
+                .map(StepOutput::Proposal),
+        }
+    };
+    timeout(Duration::from_secs(timeout_secs), run)
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "Replayed {} timeout after {} seconds",
+                input.step.label(),
+                timeout_secs
+            )
+        })?
+}
+
 /// Command sent from TUI to Orchestrator
 #[derive(Debug)]
 pub enum OrchestratorCommand {
@@ -210,6 +331,7 @@ pub enum OrchestratorCommand {
 pub struct Orchestrator {
     repo: String,
     pr_number: u32,
+    github: Arc<dyn GitHubBackend>,
     config: AiConfig,
     reviewer_adapter: Box<dyn AgentAdapter>,
     reviewee_adapter: Box<dyn AgentAdapter>,
@@ -245,6 +367,7 @@ impl Orchestrator {
     pub fn new(
         repo: &str,
         pr_number: u32,
+        github: Arc<dyn GitHubBackend>,
         config: AiConfig,
         event_sender: mpsc::Sender<RallyEvent>,
         command_receiver: Option<mpsc::Receiver<OrchestratorCommand>>,
@@ -262,6 +385,7 @@ impl Orchestrator {
         Ok(Self {
             repo: repo.to_string(),
             pr_number,
+            github,
             config,
             reviewer_adapter,
             reviewee_adapter,
@@ -1067,6 +1191,8 @@ impl Orchestrator {
                 &updated_diff,
             )
         };
+        self.record_step(iteration, AgentStep::Review, &prompt, context)
+            .await;
 
         let duration = Duration::from_secs(self.config.timeout_secs);
 
@@ -1092,6 +1218,8 @@ impl Orchestrator {
         let prompt = self
             .prompt_loader
             .load_reviewee_proposal_prompt(context, review, iteration);
+        self.record_step(iteration, AgentStep::Proposal, &prompt, context)
+            .await;
         let duration = Duration::from_secs(self.config.timeout_secs);
 
         timeout(
@@ -1309,6 +1437,8 @@ impl Orchestrator {
         let prompt = self
             .prompt_loader
             .load_reviewee_prompt(context, review, iteration);
+        self.record_step(iteration, AgentStep::Fix, &prompt, context)
+            .await;
         let duration = Duration::from_secs(self.config.timeout_secs);
 
         timeout(
@@ -1328,6 +1458,17 @@ impl Orchestrator {
         let _ = self.event_sender.send(event).await;
     }
 
+    /// Emit the inputs of the agent call about to run so the TUI can replay it.
+    async fn record_step(&self, iteration: u32, step: AgentStep, prompt: &str, context: &Context) {
+        self.send_event(RallyEvent::StepStarted(StepInput {
+            iteration,
+            step,
+            prompt: prompt.to_string(),
+            context: context.clone(),
+        }))
+        .await;
+    }
+
     /// Wrapper that optionally asks for user confirmation before posting review.
     /// - local_mode: skip posting entirely
     /// - auto_post: post directly without confirmation
@@ -1478,64 +1619,50 @@ impl Orchestrator {
 
         // Post summary comment using gh pr review
         // If approve fails (e.g., can't approve own PR), fall back to comment
-        let result =
-            github::submit_review(&self.repo, self.pr_number, app_action, &summary_with_prefix)
-                .await;
+        let result = github::submit_review(
+            &*self.github,
+            &self.repo,
+            self.pr_number,
+            app_action,
+            &summary_with_prefix,
+            &[],
+        )
+        .await;
 
         if result.is_err() && matches!(app_action_for_fallback, crate::app::ReviewAction::Approve) {
             warn!("Approve failed, falling back to comment");
             github::submit_review(
+                &*self.github,
                 &self.repo,
                 self.pr_number,
                 crate::app::ReviewAction::Comment,
                 &summary_with_prefix,
+                &[],
             )
             .await?;
         } else {
             result?;
         }
 
-        // Post inline comments with rate limit handling
-        for comment in &review.comments {
-            // Convert line number to patch position
-            let patch = context
-                .file_patches
-                .iter()
-                .find(|(name, _)| name == &comment.path)
-                .map(|(_, p)| p.as_str());
-
-            let Some(patch) = patch else {
-                warn!("No patch found for {}, skipping comment", comment.path);
-                continue;
-            };
-
-            let Some(position) = crate::diff::line_number_to_position(patch, comment.line) else {
-                warn!(
-                    "Could not convert line {} to position for {}, skipping comment",
-                    comment.line, comment.path
-                );
-                continue;
-            };
+        let pending =
+            build_pending_comments(&review.comments, &context.file_patches, &context.head_sha);
+        if pending.is_empty() {
+            return Ok(());
+        }
 
-            // Add prefix to inline comment
-            let body_with_prefix = format!("[AI Rally - Reviewer]\n\n{}", comment.body);
-            if let Err(e) = github::create_review_comment(
-                &self.repo,
-                self.pr_number,
-                &context.head_sha,
-                &comment.path,
-                position,
-                &body_with_prefix,
-            )
-            .await
-            {
-                warn!(
-                    "Failed to post inline comment on {}:{} (position {}): {}",
-                    comment.path, comment.line, position, e
-                );
+        match self.config.rally_mode {
+            RallyMode::DryRun => {
+                // Hand inline comments to the TUI; they are posted on `commit_rally`
+                self.send_event(RallyEvent::PendingComments(pending)).await;
+            }
+            RallyMode::Post => {
+                let poster = GithubCommentPoster {
+                    github: self.github.clone(),
+                    repo: self.repo.clone(),
+                    pr_number: self.pr_number,
+                };
+                commit_pending_comments(&poster, pending, POST_INTERVAL).await;
             }
-            // Rate limit mitigation: small delay between API calls
-            tokio::time::sleep(Duration::from_millis(100)).await;
         }
 
         Ok(())
@@ -1651,10 +1778,12 @@ impl Orchestrator {
         );
 
         github::submit_review(
+            &*self.github,
             &self.repo,
             self.pr_number,
             crate::app::ReviewAction::Comment,
             &body,
+            &[],
         )
         .await?;
 
@@ -1696,10 +1825,12 @@ impl Orchestrator {
 
         // Post as a comment (not a review)
         github::submit_review(
+            &*self.github,
             &self.repo,
             self.pr_number,
             crate::app::ReviewAction::Comment,
             &comment_body,
+            &[],
         )
         .await?;
 
@@ -1715,7 +1846,9 @@ impl Orchestrator {
         let mut comments = Vec::new();
 
         // Fetch review comments (inline comments on diff)
-        if let Ok(review_comments) = fetch_review_comments(&self.repo, self.pr_number).await {
+        if let Ok(review_comments) =
+            fetch_review_comments(&*self.github, &self.repo, self.pr_number).await
+        {
             for c in review_comments {
                 if is_bot_user(&c.user.login) {
                     comments.push(ExternalComment {
@@ -1729,7 +1862,9 @@ impl Orchestrator {
         }
 
         // Fetch discussion comments (general PR comments)
-        if let Ok(discussion) = fetch_discussion_comments(&self.repo, self.pr_number).await {
+        if let Ok(discussion) =
+            fetch_discussion_comments(&*self.github, &self.repo, self.pr_number).await
+        {
             for c in discussion {
                 if is_bot_user(&c.user.login) {
                     comments.push(ExternalComment {
@@ -1757,7 +1892,7 @@ impl Orchestrator {
             return Ok(());
         }
 
-        let pr = github::fetch_pr(&self.repo, self.pr_number).await?;
+        let pr = github::fetch_pr(&*self.github, &self.repo, self.pr_number).await?;
         if let Some(ref mut ctx) = self.context {
             ctx.head_sha = pr.head.sha.clone();
         }
@@ -1853,7 +1988,7 @@ impl Orchestrator {
         }
 
         // Fallback to GitHub API
-        github::fetch_pr_diff(&self.repo, self.pr_number).await
+        github::fetch_pr_diff(&*self.github, &self.repo, self.pr_number).await
     }
 
     /// ローカルモード専用の diff 取得
@@ -2496,6 +2631,7 @@ mod tests {
         let orchestrator = Orchestrator {
             repo: "owner/repo".to_string(),
             pr_number: 1,
+            github: Arc::new(crate::github::backend::mock::RecordingBackend::default()),
             config,
             reviewer_adapter: Box::new(reviewer),
             reviewee_adapter: Box::new(reviewee),
@@ -2585,6 +2721,60 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_step_inputs_are_emitted_and_replayable() {
+        // Every agent call announces its inputs, and replaying a recorded
+        // input runs only that step again.
+        let (mut orchestrator, _reviewer_calls, _reviewee_calls, mut event_rx) =
+            make_orchestrator_with_mocks(false, ReviewAction::RequestChanges);
+        orchestrator.set_context(make_local_context());
+        let _ = orchestrator.run().await.unwrap();
+
+        let mut inputs = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            if let RallyEvent::StepStarted(input) = event {
+                inputs.push(input);
+            }
+        }
+        let steps: Vec<(u32, AgentStep)> = inputs.iter().map(|i| (i.iteration, i.step)).collect();
+        assert_eq!(
+            &steps[..4],
+            &[
+                (1, AgentStep::Review),
+                (1, AgentStep::Fix),
+                (2, AgentStep::Review),
+                (2, AgentStep::Fix),
+            ]
+        );
+        assert!(inputs.iter().all(|i| !i.prompt.is_empty()));
+
+        let (mut adapter, counters) = {
+            let (orch, counters, _rx) =
+                make_orchestrator_with_mocks_full(false, vec![ReviewAction::Approve], vec![], 1);
+            (orch.reviewer_adapter, counters)
+        };
+        let output = run_step(adapter.as_mut(), &inputs[2], 60).await.unwrap();
+        assert!(matches!(
+            output,
+            StepOutput::Review(ReviewerOutput {
+                action: ReviewAction::Approve,
+                ..
+            })
+        ));
+        assert_eq!(
+            counters
+                .reviewer_calls
+                .load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+        assert_eq!(
+            counters
+                .reviewee_calls
+                .load(std::sync::atomic::Ordering::SeqCst),
+            0
+        );
+    }
+
     #[tokio::test]
     async fn test_review_only_skips_reviewee_on_request_changes() {
         // Safety invariant: in review_only mode the reviewee fix phase MUST
@@ -3064,6 +3254,7 @@ mod tests {
         let orchestrator = Orchestrator {
             repo: "owner/repo".to_string(),
             pr_number: 1,
+            github: Arc::new(crate::github::backend::mock::RecordingBackend::default()),
             config,
             reviewer_adapter: Box::new(reviewer),
             reviewee_adapter: Box::new(reviewee),
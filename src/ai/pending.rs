@@ -0,0 +1,262 @@
+//! Inline comments held back by AI Rally in dry-run mode.
+//!
+//! The orchestrator turns reviewer comments into [`PendingComment`]s (resolving
+//! diff positions up front) and either posts them right away or hands them to
+//! the TUI, which posts whatever is left on `commit_rally`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::diff::CommentSide;
+use crate::github::{self, GitHubBackend};
+
+use super::adapter::ReviewComment;
+
+/// Delay between consecutive comment posts (rate limit mitigation)
+pub const POST_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Prefix marking inline comments written by the AI reviewer
+const REVIEWER_PREFIX: &str = "[AI Rally - Reviewer]\n\n";
+
+/// A generated inline comment that has not been posted yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingComment {
+    pub path: String,
+    /// Line number in the new file
+    pub line: u32,
+    /// Diff position passed to the review comment API
+    pub position: u32,
+    /// Head commit the position was resolved against
+    pub commit_id: String,
+    pub body: String,
+}
+
+/// Resolve reviewer comments against the PR patches.
+///
+/// Comments whose file or line is not part of the diff cannot be posted
+/// inline and are dropped with a warning.
+pub fn build_pending_comments(
+    comments: &[ReviewComment],
+    file_patches: &[(String, String)],
+    head_sha: &str,
+) -> Vec<PendingComment> {
+    comments
+        .iter()
+        .filter_map(|comment| {
+            let Some((_, patch)) = file_patches.iter().find(|(name, _)| name == &comment.path)
+            else {
+                warn!("No patch found for {}, skipping comment", comment.path);
+                return None;
+            };
+            let Some(position) = crate::diff::line_number_to_position(patch, comment.line) else {
+                warn!(
+                    "Could not convert line {} to position for {}, skipping comment",
+                    comment.line, comment.path
+                );
+                return None;
+            };
+            Some(PendingComment {
+                path: comment.path.clone(),
+                line: comment.line,
+                position,
+                commit_id: head_sha.to_string(),
+                body: comment.body.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Destination for pending comments. Abstracted so the commit flow can be
+/// tested without calling GitHub.
+#[async_trait]
+pub trait CommentPoster: Send + Sync {
+    async fn post(&self, comment: &PendingComment) -> Result<()>;
+}
+
+/// Posts pending comments as PR review comments via `create_review_comment`
+pub struct GithubCommentPoster {
+    pub github: Arc<dyn GitHubBackend>,
+    pub repo: String,
+    pub pr_number: u32,
+}
+
+#[async_trait]
+impl CommentPoster for GithubCommentPoster {
+    async fn post(&self, comment: &PendingComment) -> Result<()> {
+        let body = format!("{}{}", REVIEWER_PREFIX, comment.body);
+        github::create_review_comment(
+            &*self.github,
+            &self.repo,
+            self.pr_number,
+            &comment.commit_id,
+            &comment.path,
+            comment.position,
+            CommentSide::Right,
+            &body,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Result of [`commit_pending_comments`]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CommitOutcome {
+    pub posted: usize,
+    /// Comments that failed to post, in their original order
+    pub failed: Vec<PendingComment>,
+}
+
+/// Post `comments` in order, waiting `interval` between posts.
+/// A failed post does not stop the remaining ones.
+pub async fn commit_pending_comments(
+    poster: &dyn CommentPoster,
+    comments: Vec<PendingComment>,
+    interval: Duration,
+) -> CommitOutcome {
+    let mut outcome = CommitOutcome::default();
+    for (i, comment) in comments.into_iter().enumerate() {
+        if i > 0 && !interval.is_zero() {
+            tokio::time::sleep(interval).await;
+        }
+        match poster.post(&comment).await {
+            Ok(()) => outcome.posted += 1,
+            Err(e) => {
+                warn!(
+                    "Failed to post inline comment on {}:{} (position {}): {}",
+                    comment.path, comment.line, comment.position, e
+                );
+                outcome.failed.push(comment);
+            }
+        }
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::adapter::CommentSeverity;
+    use anyhow::anyhow;
+    use std::sync::Mutex;
+
+    /// Records posted comments; fails for paths listed in `fail_paths`
+    #[derive(Default)]
+    struct MockPoster {
+        posted: Mutex<Vec<PendingComment>>,
+        fail_paths: Vec<String>,
+    }
+
+    #[async_trait]
+    impl CommentPoster for MockPoster {
+        async fn post(&self, comment: &PendingComment) -> Result<()> {
+            if self.fail_paths.contains(&comment.path) {
+                return Err(anyhow!("rejected"));
+            }
+            self.posted.lock().unwrap().push(comment.clone());
+            Ok(())
+        }
+    }
+
+    fn review_comment(path: &str, line: u32, body: &str) -> ReviewComment {
+        ReviewComment {
+            path: path.to_string(),
+            line,
+            body: body.to_string(),
+            severity: CommentSeverity::Minor,
+        }
+    }
+
+    fn patches() -> Vec<(String, String)> {
+        vec![
+            (
+                "src/a.rs".to_string(),
+                "@@ -1,2 +1,3 @@\n fn a() {}\n+fn b() {}\n fn c() {}".to_string(),
+            ),
+            (
+                "src/b.rs".to_string(),
+                "@@ -10,1 +10,2 @@\n x\n+y".to_string(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_build_pending_comments_resolves_positions() {
+        let comments = vec![
+            review_comment("src/a.rs", 2, "rename b"),
+            review_comment("src/missing.rs", 1, "no patch"),
+            review_comment("src/a.rs", 99, "outside the diff"),
+            review_comment("src/b.rs", 11, "check y"),
+        ];
+        let pending = build_pending_comments(&comments, &patches(), "abc123");
+        assert_eq!(
+            pending,
+            vec![
+                PendingComment {
+                    path: "src/a.rs".to_string(),
+                    line: 2,
+                    position: 2,
+                    commit_id: "abc123".to_string(),
+                    body: "rename b".to_string(),
+                },
+                PendingComment {
+                    path: "src/b.rs".to_string(),
+                    line: 11,
+                    position: 2,
+                    commit_id: "abc123".to_string(),
+                    body: "check y".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_accumulate_drop_then_commit() {
+        let comments = vec![
+            review_comment("src/a.rs", 1, "first"),
+            review_comment("src/a.rs", 2, "second"),
+            review_comment("src/b.rs", 11, "third"),
+        ];
+        let mut pending = build_pending_comments(&comments, &patches(), "abc123");
+        assert_eq!(pending.len(), 3);
+
+        // Nothing is posted until commit; the user drops one item first
+        let poster = MockPoster::default();
+        pending.remove(1);
+        assert!(poster.posted.lock().unwrap().is_empty());
+
+        let outcome = commit_pending_comments(&poster, pending, Duration::ZERO).await;
+        assert_eq!(outcome.posted, 2);
+        assert!(outcome.failed.is_empty());
+        let bodies: Vec<String> = poster
+            .posted
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| c.body.clone())
+            .collect();
+        assert_eq!(bodies, ["first", "third"]);
+    }
+
+    #[tokio::test]
+    async fn test_commit_keeps_failed_comments() {
+        let comments = vec![
+            review_comment("src/a.rs", 2, "ok"),
+            review_comment("src/b.rs", 11, "rejected"),
+        ];
+        let pending = build_pending_comments(&comments, &patches(), "abc123");
+        let poster = MockPoster {
+            fail_paths: vec!["src/b.rs".to_string()],
+            ..Default::default()
+        };
+
+        let outcome = commit_pending_comments(&poster, pending, Duration::ZERO).await;
+        assert_eq!(outcome.posted, 1);
+        assert_eq!(outcome.failed.len(), 1);
+        assert_eq!(outcome.failed[0].body, "rejected");
+    }
+}
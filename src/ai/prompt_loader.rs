@@ -19,6 +19,9 @@ pub enum PromptSource {
     Embedded,
 }
 
+/// Builds the [`PromptSource`] for a file found in one prompt directory level
+type PromptLevel = fn(PathBuf) -> PromptSource;
+
 /// Default prompt templates embedded in the binary
 mod defaults {
     pub const REVIEWER: &str = include_str!("defaults/reviewer.md");
@@ -93,31 +96,48 @@ impl PromptLoader {
     /// readable, so the Help display matches what `load_template()` would
     /// actually load.
     pub fn resolve_source(&self, filename: &str) -> PromptSource {
-        if let Some(ref dir) = self.local_prompts_dir {
+        self.candidate_sources(filename)
+            .next()
+            .unwrap_or(PromptSource::Embedded)
+    }
+
+    /// Prompt directories paired with the source they resolve to, highest
+    /// priority first. This is the single definition of the resolution order.
+    fn levels(&self) -> [(Option<&Path>, PromptLevel); 3] {
+        [
+            (self.local_prompts_dir.as_deref(), PromptSource::Local),
+            (self.prompt_dir.as_deref(), PromptSource::PromptDir),
+            (self.global_prompts_dir.as_deref(), PromptSource::Global),
+        ]
+    }
+
+    /// Sources that provide a readable `filename`, in resolution order.
+    fn candidate_sources<'a>(
+        &'a self,
+        filename: &'a str,
+    ) -> impl Iterator<Item = PromptSource> + 'a {
+        self.levels()
+            .into_iter()
+            .filter_map(move |(dir, source)| dir.map(|dir| source(dir.join(filename))))
+            .filter(|source| self.is_usable_source(source))
+    }
+
+    fn is_usable_source(&self, source: &PromptSource) -> bool {
+        match source {
             // Re-validate directory on every access to guard against symlink swap
-            // (e.g. reviewee agent running `git switch` to a branch with symlinked dir)
-            if Self::is_safe_local_dir(dir, &self.project_root) {
-                let path = dir.join(filename);
-                // Reject symlinks for local prompts to prevent path traversal
-                if Self::is_readable_file_no_symlink(&path) {
-                    return PromptSource::Local(path);
-                }
+            // (e.g. reviewee agent running `git switch` to a branch with symlinked dir),
+            // and reject symlinks for local prompts to prevent path traversal
+            PromptSource::Local(path) => {
+                self.local_prompts_dir
+                    .as_deref()
+                    .is_some_and(|dir| Self::is_safe_local_dir(dir, &self.project_root))
+                    && Self::is_readable_file_no_symlink(path)
             }
-        }
-        if let Some(ref dir) = self.prompt_dir {
-            let path = dir.join(filename);
             // Reject symlinks for prompt_dir files (may originate from local config)
-            if Self::is_readable_file_no_symlink(&path) {
-                return PromptSource::PromptDir(path);
-            }
-        }
-        if let Some(ref dir) = self.global_prompts_dir {
-            let path = dir.join(filename);
-            if Self::is_readable_file(&path) {
-                return PromptSource::Global(path);
-            }
+            PromptSource::PromptDir(path) => Self::is_readable_file_no_symlink(path),
+            PromptSource::Global(path) => Self::is_readable_file(path),
+            PromptSource::Embedded => true,
         }
-        PromptSource::Embedded
     }
 
     /// Check that a local prompts directory is safe:
@@ -513,55 +533,65 @@ Note: Address these comments if they are relevant and valid. Don't wait for more
     /// Load a template with multi-level resolution.
     ///
     /// Order: local .octorus/prompts/ → config.prompt_dir → global prompts → embedded default
+    /// The chosen source is logged under `OR_DEBUG` together with the search order.
     fn load_template(&self, filename: &str, default: &str) -> String {
-        // 1. Project-local .octorus/prompts/ (highest priority, reject symlinks)
-        if let Some(ref dir) = self.local_prompts_dir {
-            // Re-validate directory on every access to guard against symlink swap
-            if Self::is_safe_local_dir(dir, &self.project_root) {
-                let path = dir.join(filename);
-                if Self::is_readable_file_no_symlink(&path) {
-                    if let Some(content) = Self::try_load_from(&self.local_prompts_dir, filename) {
-                        return content;
-                    }
-                }
-            }
-        }
-        // 2. config.prompt_dir (explicit path, reject symlinked files)
-        if let Some(ref dir) = self.prompt_dir {
-            let path = dir.join(filename);
-            if Self::is_readable_file_no_symlink(&path) {
-                if let Some(content) = Self::try_load_from(&self.prompt_dir, filename) {
-                    return content;
-                }
-            }
-        }
-        // 3. Global ~/.config/octorus/prompts/
-        if let Some(content) = Self::try_load_from(&self.global_prompts_dir, filename) {
-            return content;
-        }
-        // 4. Binary-embedded default
-        default.to_string()
+        tracing::debug!(
+            "prompt '{}' search order: {}",
+            filename,
+            self.search_order()
+                .iter()
+                .map(|dir| dir.display().to_string())
+                .chain(std::iter::once("<embedded>".to_string()))
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        );
+        let (content, source) = self
+            .find_template(filename)
+            .unwrap_or_else(|| (default.to_string(), PromptSource::Embedded));
+        tracing::debug!("prompt '{}' resolved to {:?}", filename, source);
+        content
+    }
+
+    /// Directories searched for prompt templates, highest priority first.
+    fn search_order(&self) -> Vec<&Path> {
+        self.levels()
+            .into_iter()
+            .filter_map(|(dir, _)| dir)
+            .collect()
     }
 
-    /// Try to load a file from an optional directory.
+    /// Load a template from the first directory that provides it.
+    /// Missing files fall through to the next level, so a partial local
+    /// override set is merged with the global templates. The binary-embedded
+    /// default is handled by the caller.
+    fn find_template(&self, filename: &str) -> Option<(String, PromptSource)> {
+        self.candidate_sources(filename).find_map(|source| {
+            let content = match &source {
+                PromptSource::Local(path)
+                | PromptSource::PromptDir(path)
+                | PromptSource::Global(path) => Self::try_load(path, filename)?,
+                PromptSource::Embedded => return None,
+            };
+            Some((content, source))
+        })
+    }
+
+    /// Try to load a prompt file.
     /// Returns None for NotFound; logs a warning and returns None for other errors.
-    fn try_load_from(dir: &Option<PathBuf>, filename: &str) -> Option<String> {
-        dir.as_ref().and_then(|d| {
-            let path = d.join(filename);
-            match fs::read_to_string(&path) {
-                Ok(content) => Some(content),
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
-                Err(e) => {
-                    eprintln!(
-                        "Warning: Failed to read prompt '{}' from {}: {}",
-                        filename,
-                        path.display(),
-                        e
-                    );
-                    None
-                }
+    fn try_load(path: &Path, filename: &str) -> Option<String> {
+        match fs::read_to_string(path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                eprintln!(
+                    "Warning: Failed to read prompt '{}' from {}: {}",
+                    filename,
+                    path.display(),
+                    e
+                );
+                None
             }
-        })
+        }
     }
 }
 
@@ -1068,6 +1098,55 @@ mod tests {
         assert_eq!(source, PromptSource::Local(prompts_dir.join("reviewer.md")));
     }
 
+    #[test]
+    fn test_partial_local_override_merges_with_global() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_root = dir.path().join("project");
+        let local_dir = project_root.join(".octorus/prompts");
+        std::fs::create_dir_all(&local_dir).unwrap();
+        std::fs::write(local_dir.join("reviewer.md"), "local reviewer").unwrap();
+        let global_dir = dir.path().join("global_prompts");
+        std::fs::create_dir_all(&global_dir).unwrap();
+        std::fs::write(global_dir.join("reviewer.md"), "global reviewer").unwrap();
+        std::fs::write(global_dir.join("reviewee.md"), "global reviewee").unwrap();
+
+        let mut loader = PromptLoader::new(&AiConfig::default(), &project_root);
+        loader.global_prompts_dir = Some(global_dir.clone());
+
+        assert_eq!(
+            loader.search_order(),
+            vec![local_dir.as_path(), global_dir.as_path()]
+        );
+        // Local override wins, missing ones come from the global set
+        assert_eq!(
+            loader.find_template("reviewer.md"),
+            Some((
+                "local reviewer".to_string(),
+                PromptSource::Local(local_dir.join("reviewer.md"))
+            ))
+        );
+        assert_eq!(
+            loader.find_template("reviewee.md"),
+            Some((
+                "global reviewee".to_string(),
+                PromptSource::Global(global_dir.join("reviewee.md"))
+            ))
+        );
+        assert_eq!(loader.find_template("rereview.md"), None);
+        // Help display resolves through the same order
+        for file in ["reviewer.md", "reviewee.md"] {
+            assert_eq!(
+                Some(loader.resolve_source(file)),
+                loader.find_template(file).map(|(_, source)| source)
+            );
+        }
+        assert_eq!(loader.resolve_source("rereview.md"), PromptSource::Embedded);
+        assert_eq!(
+            loader.load_template("rereview.md", defaults::REREVIEW),
+            defaults::REREVIEW
+        );
+    }
+
     #[cfg(unix)]
     #[test]
     fn test_symlink_allowed_for_global_prompts() {
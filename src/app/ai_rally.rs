@@ -5,7 +5,8 @@ use std::io::Stdout;
 use tokio::sync::mpsc;
 
 use crate::ai::adapter::{CommentSeverity, ReviewComment as AiReviewComment};
-use crate::ai::orchestrator::{OrchestratorCommand, RallyEvent};
+use crate::ai::orchestrator::{replay_step, OrchestratorCommand, RallyEvent};
+use crate::ai::pending::{commit_pending_comments, GithubCommentPoster, POST_INTERVAL};
 use crate::ai::prompt_loader::{PromptLoader, PromptSource};
 use crate::ai::{
     Context, Orchestrator, RallyState, ReviewAction as AiReviewAction, ReviewerOutput,
@@ -14,6 +15,7 @@ use crate::cache::load_local_review_comments;
 use crate::keybinding::{event_to_keybinding, SequenceMatch};
 use crate::ui;
 
+use super::read_only::WriteAction;
 use super::types::*;
 use super::{App, AppState};
 
@@ -205,7 +207,21 @@ impl App {
                     self.start_ai_rally();
                 }
             }
+        } else if self.matches_single_key(&key, &kb.retry_iteration) {
+            self.retry_last_rally_step();
+        } else if self.matches_single_key(&key, &kb.commit_rally) {
+            self.commit_rally_comments();
+        } else if self.matches_single_key(&key, &kb.rally_drop_comment) {
+            self.drop_selected_pending_comment();
+        } else if self.matches_single_key(&key, &kb.tab_switch) {
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                rally_state.pending_comments_focused = !rally_state.pending_comments_focused
+                    && !rally_state.pending_comments.is_empty();
+            }
         } else if self.matches_single_key(&key, &kb.move_down) {
+            if self.move_pending_comment_selection(1) {
+                return Ok(());
+            }
             if let Some(ref mut rally_state) = self.ai_rally_state {
                 let total_logs = rally_state.logs.len();
                 if total_logs == 0 {
@@ -217,6 +233,9 @@ impl App {
                 self.adjust_log_scroll_to_selection();
             }
         } else if self.matches_single_key(&key, &kb.move_up) {
+            if self.move_pending_comment_selection(-1) {
+                return Ok(());
+            }
             if let Some(ref mut rally_state) = self.ai_rally_state {
                 let total_logs = rally_state.logs.len();
                 if total_logs == 0 {
@@ -363,6 +382,162 @@ impl App {
         }
     }
 
+    /// 保留コメント一覧にフォーカスがあれば選択を `delta` 動かして true を返す
+    fn move_pending_comment_selection(&mut self, delta: isize) -> bool {
+        let Some(ref mut rally_state) = self.ai_rally_state else {
+            return false;
+        };
+        if !rally_state.pending_comments_focused || rally_state.pending_comments.is_empty() {
+            return false;
+        }
+        let last = rally_state.pending_comments.len() - 1;
+        rally_state.selected_pending_comment = rally_state
+            .selected_pending_comment
+            .saturating_add_signed(delta)
+            .min(last);
+        true
+    }
+
+    /// 選択中の保留コメントを投稿せずに破棄する
+    pub(crate) fn drop_selected_pending_comment(&mut self) {
+        let Some(ref mut rally_state) = self.ai_rally_state else {
+            return;
+        };
+        if rally_state.pending_comments.is_empty() {
+            return;
+        }
+        let index = rally_state
+            .selected_pending_comment
+            .min(rally_state.pending_comments.len() - 1);
+        let dropped = rally_state.pending_comments.remove(index);
+        rally_state.selected_pending_comment =
+            index.min(rally_state.pending_comments.len().saturating_sub(1));
+        if rally_state.pending_comments.is_empty() {
+            rally_state.pending_comments_focused = false;
+        }
+        rally_state.push_log(LogEntry::new(
+            LogEventType::Info,
+            format!("Dropped comment on {}:{}", dropped.path, dropped.line),
+        ));
+    }
+
+    /// 保留中のインラインコメントをまとめて PR に投稿する。
+    /// 投稿に失敗したものは一覧に戻す（`poll_rally_commit`）。
+    pub(crate) fn commit_rally_comments(&mut self) {
+        if self.deny_write(WriteAction::AiRally) {
+            return;
+        }
+        if self.rally_commit_receiver.is_some() {
+            return;
+        }
+        let Some(ref mut rally_state) = self.ai_rally_state else {
+            return;
+        };
+        if rally_state.pending_comments.is_empty() {
+            return;
+        }
+        let comments = std::mem::take(&mut rally_state.pending_comments);
+        rally_state.selected_pending_comment = 0;
+        rally_state.pending_comments_focused = false;
+        rally_state.push_log(LogEntry::new(
+            LogEventType::Info,
+            format!("Posting {} pending comment(s) to PR...", comments.len()),
+        ));
+
+        let (tx, rx) = mpsc::channel(1);
+        self.rally_commit_receiver = Some(rx);
+        let poster = GithubCommentPoster {
+            github: self.github.clone(),
+            repo: self.repo.clone(),
+            pr_number: self.pr_number(),
+        };
+        tokio::spawn(async move {
+            let outcome = commit_pending_comments(&poster, comments, POST_INTERVAL).await;
+            let _ = tx.send(outcome).await;
+        });
+    }
+
+    pub(crate) fn poll_rally_commit(&mut self) {
+        let Some(ref mut rx) = self.rally_commit_receiver else {
+            return;
+        };
+        let outcome = match rx.try_recv() {
+            Ok(outcome) => outcome,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.rally_commit_receiver = None;
+                return;
+            }
+        };
+        self.rally_commit_receiver = None;
+        let Some(ref mut rally_state) = self.ai_rally_state else {
+            return;
+        };
+        rally_state.push_log(LogEntry::new(
+            LogEventType::Info,
+            format!("Posted {} comment(s) to PR", outcome.posted),
+        ));
+        if !outcome.failed.is_empty() {
+            rally_state.push_log(LogEntry::new(
+                LogEventType::Error,
+                format!(
+                    "{} comment(s) failed to post and were kept for retry",
+                    outcome.failed.len()
+                ),
+            ));
+            // 投稿中に届いた分より前に戻す
+            let newer = std::mem::replace(&mut rally_state.pending_comments, outcome.failed);
+            rally_state.pending_comments.extend(newer);
+        }
+    }
+
+    /// 終了した Rally の最後のエージェント呼び出しを同じ入力で再実行する。
+    /// 結果はそのイテレーションの元の結果と置き換え、それ以前のイテレーションは残す。
+    /// 再実行した結果は PR に投稿しない。
+    pub(crate) fn retry_last_rally_step(&mut self) {
+        if self.deny_write(WriteAction::AiRally) {
+            return;
+        }
+        if self.rally_event_receiver.is_some() {
+            return;
+        }
+        let Some(ref mut rally_state) = self.ai_rally_state else {
+            return;
+        };
+        if !rally_state.state.is_finished() {
+            return;
+        }
+        let Some(input) = rally_state.step_inputs.last().cloned() else {
+            return;
+        };
+        rally_state.push_log(LogEntry::new(
+            LogEventType::Info,
+            format!(
+                "Re-running iteration {} ({})...",
+                input.iteration,
+                input.step.label()
+            ),
+        ));
+
+        let (event_tx, event_rx) = mpsc::channel(100);
+        self.rally_event_receiver = Some(event_rx);
+        let config = self.config.ai.clone();
+        let handle = tokio::spawn(async move {
+            let event = match replay_step(&config, &input, event_tx.clone()).await {
+                Ok(output) => RallyEvent::StepReplayed {
+                    iteration: input.iteration,
+                    output,
+                },
+                Err(e) => RallyEvent::Error(format!(
+                    "Re-running iteration {} failed: {}",
+                    input.iteration, e
+                )),
+            };
+            let _ = event_tx.send(event).await;
+        });
+        self.rally_abort_handle = Some(handle.abort_handle());
+    }
+
     pub(crate) fn send_rally_command(&mut self, cmd: OrchestratorCommand) {
         if let Some(ref sender) = self.rally_command_sender {
             // Use try_send since we're not in an async context
@@ -391,6 +566,7 @@ impl App {
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<()> {
         // Restore terminal before opening editor
+        self.pause_review_timer();
         ui::restore_terminal(terminal)?;
 
         // Open editor (blocking)
@@ -399,6 +575,7 @@ impl App {
 
         // Re-setup terminal after editor closes
         *terminal = ui::setup_terminal()?;
+        self.resume_review_timer();
 
         // Process result
         if let Some(ref mut rally_state) = self.ai_rally_state {
@@ -558,6 +735,12 @@ impl App {
                 Some(warnings)
             },
             pause_state: PauseState::Running,
+            agent_started_at: None,
+            spinner_frame: 0,
+            pending_comments: Vec::new(),
+            selected_pending_comment: 0,
+            pending_comments_focused: false,
+            step_inputs: Vec::new(),
         });
 
         self.state = AppState::AiRally;
@@ -639,11 +822,13 @@ impl App {
         let config = self.config.ai.clone();
         let repo = self.repo.clone();
         let pr_number = self.pr_number();
+        let github = self.github.clone();
 
         let handle = tokio::spawn(async move {
             let orchestrator_result = Orchestrator::new(
                 &repo,
                 pr_number,
+                github,
                 config,
                 event_tx.clone(),
                 Some(cmd_rx),
@@ -683,6 +868,7 @@ impl App {
             }
             "ai.auto_post" => format!("{}", self.config.ai.auto_post),
             "ai.review_only" => format!("{}", self.config.ai.review_only),
+            "ai.rally_mode" => self.config.ai.rally_mode.label().to_string(),
             "ai.reviewer" => self.config.ai.reviewer.clone(),
             "ai.reviewee" => self.config.ai.reviewee.clone(),
             "ai.prompt_dir" => self
@@ -0,0 +1,138 @@
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::time::{interval_at, Instant, MissedTickBehavior};
+
+use super::types::{AutoRefreshTarget, DataState, RefreshRequest};
+use super::{App, AppState};
+
+impl App {
+    /// 自動更新タイマーと共有する「更新対象の PR 番号」のハンドル（0 なら更新しない）
+    pub fn auto_refresh_handle(&self) -> AutoRefreshTarget {
+        self.auto_refresh_target.clone()
+    }
+
+    /// リモート PR の閲覧画面を表示中なら、その PR 番号を自動更新の対象にする。
+    /// ローカルモード・オフライン・キャッシュ表示中・入力中などは対象外（0）。
+    pub(crate) fn sync_auto_refresh_target(&self) {
+        let viewing_pr = !self.local_mode
+            && !self.offline
+            && !self.showing_stale_data
+            && matches!(self.data_state, DataState::Loaded { .. })
+            && matches!(
+                self.state,
+                AppState::FileList
+                    | AppState::DiffView
+                    | AppState::SplitViewFileList
+                    | AppState::SplitViewDiff
+                    | AppState::CommentList
+                    | AppState::PrDescription
+                    | AppState::SymbolDigest
+            );
+        let pr_number = if viewing_pr {
+            self.pr_number.unwrap_or(0)
+        } else {
+            0
+        };
+        self.auto_refresh_target.store(pr_number, Ordering::Relaxed);
+    }
+}
+
+/// `period` ごとに対象 PR の `RefreshRequest::PrRefresh` を retry ループへ送る。
+///
+/// 対象がない（0）間は送らない。前回の要求がまだ処理待ちなら、その tick は捨てる。
+/// retry ループが終了したら（チャネルが閉じたら）戻る。
+pub async fn run_auto_refresh(
+    period: Duration,
+    target: AutoRefreshTarget,
+    tx: mpsc::Sender<RefreshRequest>,
+) {
+    let mut ticker = interval_at(Instant::now() + period, period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        let pr_number = target.load(Ordering::Relaxed);
+        if pr_number == 0 {
+            if tx.is_closed() {
+                return;
+            }
+            continue;
+        }
+        if let Err(mpsc::error::TrySendError::Closed(_)) =
+            tx.try_send(RefreshRequest::PrRefresh { pr_number })
+        {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERIOD: Duration = Duration::from_secs(30);
+
+    fn spawn_timer(pr_number: u32) -> (AutoRefreshTarget, mpsc::Receiver<RefreshRequest>) {
+        let target = AutoRefreshTarget::default();
+        target.store(pr_number, Ordering::Relaxed);
+        let (tx, rx) = mpsc::channel(1);
+        tokio::spawn(run_auto_refresh(PERIOD, target.clone(), tx));
+        (target, rx)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_auto_refresh_emits_at_configured_cadence() {
+        let (_target, mut rx) = spawn_timer(42);
+        let start = Instant::now();
+
+        for n in 1..=3 {
+            let request = rx.recv().await.unwrap();
+            assert!(matches!(
+                request,
+                RefreshRequest::PrRefresh { pr_number: 42 }
+            ));
+            assert_eq!(start.elapsed(), PERIOD * n);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_auto_refresh_skips_ticks_without_target() {
+        let (target, mut rx) = spawn_timer(0);
+        let start = Instant::now();
+
+        tokio::time::sleep(PERIOD * 2 + Duration::from_secs(1)).await;
+        assert!(rx.try_recv().is_err());
+
+        target.store(7, Ordering::Relaxed);
+        let request = rx.recv().await.unwrap();
+        assert!(matches!(
+            request,
+            RefreshRequest::PrRefresh { pr_number: 7 }
+        ));
+        assert_eq!(start.elapsed(), PERIOD * 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_auto_refresh_drops_ticks_while_request_pending() {
+        let (_target, mut rx) = spawn_timer(42);
+
+        // 取り出さない間は 1 件だけ溜まる
+        tokio::time::sleep(PERIOD * 3 + Duration::from_secs(1)).await;
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_auto_refresh_stops_when_channel_closed() {
+        let target = AutoRefreshTarget::default();
+        target.store(42, Ordering::Relaxed);
+        let (tx, rx) = mpsc::channel(1);
+        drop(rx);
+        let handle = tokio::spawn(run_auto_refresh(PERIOD, target, tx));
+        tokio::time::timeout(PERIOD * 2, handle)
+            .await
+            .expect("timer should stop once the retry loop is gone")
+            .unwrap();
+    }
+}
@@ -0,0 +1,99 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+use crate::blame::fetch_blame;
+use crate::diff::LineType;
+
+use super::types::{BlamePopupState, LoadState};
+use super::App;
+
+impl App {
+    /// カーソル行の周辺を `git blame` してポップアップで表示する（local mode 専用）
+    pub(crate) fn open_blame_popup(&mut self) {
+        if !self.local_mode {
+            self.cmt.submission_result =
+                Some((false, "Blame is only available in local mode".to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        }
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        let info = file
+            .patch
+            .as_deref()
+            .and_then(|patch| crate::diff::get_line_info(patch, self.diff_scroll.selected_line));
+        // 削除行は作業ツリーに無いので HEAD 側の行を blame する
+        let target = info.and_then(|info| match info.line_type {
+            LineType::Removed => info.old_line_number.map(|n| (Some("HEAD"), n)),
+            _ => info.new_line_number.map(|n| (None, n)),
+        });
+        let Some((revision, line)) = target else {
+            self.cmt.submission_result = Some((false, "No line to blame".to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        };
+
+        let filename = file.filename.clone();
+        self.cancel_blame();
+        self.blame_popup = Some(BlamePopupState {
+            filename: filename.clone(),
+            line,
+            lines: LoadState::Loading,
+        });
+
+        let (tx, rx) = mpsc::channel(1);
+        self.blame_receiver = Some(rx);
+        let working_dir = self.working_dir.clone();
+        let handle = tokio::spawn(async move {
+            let result = fetch_blame(working_dir.as_deref(), &filename, revision, line)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result).await;
+        });
+        self.blame_abort_handle = Some(handle.abort_handle());
+    }
+
+    /// 実行中の blame を中断する（`kill_on_drop` で git も終了する）
+    pub(crate) fn cancel_blame(&mut self) {
+        if let Some(handle) = self.blame_abort_handle.take() {
+            handle.abort();
+        }
+        self.blame_receiver = None;
+    }
+
+    pub(crate) fn poll_blame_updates(&mut self) {
+        let Some(ref mut rx) = self.blame_receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                Err("Blame task ended unexpectedly".to_string())
+            }
+        };
+        self.blame_receiver = None;
+        self.blame_abort_handle = None;
+        if let Some(popup) = self.blame_popup.as_mut() {
+            popup.lines = match result {
+                Ok(lines) => LoadState::Loaded(lines),
+                Err(e) => LoadState::Error(e),
+            };
+        }
+    }
+
+    /// ポップアップ表示中のキー処理。表示中は全キーを消費し、閉じると blame も中断する。
+    pub(crate) fn handle_blame_popup_input(&mut self, key: &KeyEvent) -> bool {
+        if self.blame_popup.is_none() {
+            return false;
+        }
+        let kb = &self.config.keybindings;
+        if key.code == KeyCode::Esc || self.matches_single_key(key, &kb.quit) {
+            self.cancel_blame();
+            self.blame_popup = None;
+        }
+        true
+    }
+}
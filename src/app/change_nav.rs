@@ -0,0 +1,116 @@
+use std::time::Instant;
+
+use crate::diff::{
+    change_block_starts, hunk_header_lines, next_change_block, next_change_stop, next_conflict_line,
+};
+
+use super::App;
+
+impl App {
+    /// PR 全体の変更（hunk ヘッダ）を順にたどる
+    ///
+    /// ファイル内の次（前）の hunk へ移動し、端の hunk からは隣のファイルの
+    /// 最初（最後）の hunk へ進む。PR の端で折り返すかは `diff.wrap_change_navigation`。
+    pub(crate) fn jump_to_change(&mut self, forward: bool) {
+        let stops: Vec<Vec<usize>> = self
+            .files()
+            .iter()
+            .enumerate()
+            .map(|(i, file)| match file.patch.as_deref() {
+                Some(patch) => {
+                    let mut lines = hunk_header_lines(patch);
+                    if i == self.selected_file {
+                        // hunk フィルタで隠れている hunk には止まらない
+                        lines.retain(|&line| self.collapsed_lines_at(line).is_none());
+                    }
+                    lines
+                }
+                // patch 未取得（lazy ロード前）やバイナリはファイル先頭に 1 回止まる
+                None => vec![0],
+            })
+            .collect();
+
+        let Some((file, line)) = next_change_stop(
+            &stops,
+            self.selected_file,
+            self.diff_scroll.selected_line,
+            forward,
+            self.config.diff.wrap_change_navigation,
+        ) else {
+            let edge = if forward { "last" } else { "first" };
+            self.cmt.submission_result = Some((true, format!("Already at the {} change", edge)));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        };
+
+        if file != self.selected_file {
+            self.selected_file = file;
+            self.sync_diff_to_selected_file();
+        }
+        self.diff_scroll.selected_line = line;
+        self.diff_scroll.scroll_offset = line;
+    }
+
+    /// 表示中ファイル内の次（前）の hunk ヘッダ・変更ブロックへ画面上端を移動する
+    ///
+    /// 基準は画面上端の行。ファイル端で折り返すかは `diff.wrap_change_navigation`。
+    pub(crate) fn jump_to_change_block(&mut self, forward: bool) {
+        let Some(cache) = self.diff_store.current.as_ref() else {
+            return;
+        };
+        let key = (cache.file_index, cache.patch_hash);
+        if self
+            .change_block_stops
+            .as_ref()
+            .is_none_or(|(file, hash, _)| (*file, *hash) != key)
+        {
+            let starts = change_block_starts(cache.lines.iter().map(|line| line.line_type));
+            self.change_block_stops = Some((key.0, key.1, starts));
+        }
+
+        let mut stops = self
+            .change_block_stops
+            .as_ref()
+            .map(|(_, _, starts)| starts.clone())
+            .unwrap_or_default();
+        // hunk フィルタ・折りたたみで隠れている行には止まらない
+        stops.retain(|&line| self.collapsed_lines_at(line).is_none());
+
+        let Some(line) = next_change_block(
+            &stops,
+            self.diff_scroll.scroll_offset,
+            forward,
+            self.config.diff.wrap_change_navigation,
+        ) else {
+            let edge = if forward { "last" } else { "first" };
+            self.cmt.submission_result =
+                Some((true, format!("Already at the {} change in this file", edge)));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        };
+        self.diff_scroll.selected_line = line;
+        self.diff_scroll.scroll_offset = line;
+    }
+
+    /// 表示中ファイル内の次の衝突マーカー行へカーソルを移動する（末尾からは先頭へ折り返す）
+    pub(crate) fn jump_to_next_conflict(&mut self) {
+        let Some(cache) = self.diff_store.current.as_ref() else {
+            return;
+        };
+        // hunk フィルタ・折りたたみで隠れている行には止まらない
+        let conflicts: Vec<usize> = cache
+            .conflict_lines
+            .iter()
+            .copied()
+            .filter(|&line| self.collapsed_lines_at(line).is_none())
+            .collect();
+        let Some(line) = next_conflict_line(&conflicts, self.diff_scroll.selected_line) else {
+            self.cmt.submission_result =
+                Some((true, "No conflict markers in this file".to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        };
+        self.diff_scroll.selected_line = line;
+        self.diff_scroll.scroll_offset = line;
+    }
+}
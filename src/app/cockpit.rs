@@ -116,8 +116,9 @@ impl App {
         cockpit.mentioned_receiver = Some(mention_rx);
 
         let repo_for_mention = repo.clone();
+        let github = self.github.clone();
         tokio::spawn(async move {
-            let result = github::fetch_mentioned_issues_count(&repo_for_mention)
+            let result = github::fetch_mentioned_issues_count(&*github, &repo_for_mention)
                 .await
                 .map_err(|e| e.to_string());
             let _ = mention_tx.send(result).await;
@@ -126,8 +127,9 @@ impl App {
         let (review_tx, review_rx) = mpsc::channel(1);
         cockpit.review_receiver = Some(review_rx);
 
+        let github = self.github.clone();
         tokio::spawn(async move {
-            let result = github::fetch_review_requested_prs_count(&repo)
+            let result = github::fetch_review_requested_prs_count(&*github, &repo)
                 .await
                 .map_err(|e| e.to_string());
             let _ = review_tx.send(result).await;
@@ -0,0 +1,161 @@
+use std::time::Instant;
+
+use super::types::CommentThread;
+use super::App;
+
+impl App {
+    /// コメントを含むスレッド
+    pub(crate) fn thread_of_comment(&self, comment_index: usize) -> Option<&CommentThread> {
+        self.cmt
+            .review_threads
+            .iter()
+            .find(|t| t.root == comment_index || t.replies.contains(&comment_index))
+    }
+
+    /// スレッドのルートコメント ID（折りたたみ状態のキー。リロードで index が変わっても保持される）
+    fn thread_root_id(&self, comment_index: usize) -> Option<u64> {
+        let root = self
+            .thread_of_comment(comment_index)
+            .map_or(comment_index, |t| t.root);
+        self.cmt.review_comments.as_ref()?.get(root).map(|c| c.id)
+    }
+
+    /// コメントを含むスレッドが折りたたまれているか
+    pub fn is_thread_collapsed(&self, comment_index: usize) -> bool {
+        let overridden = self
+            .thread_root_id(comment_index)
+            .is_some_and(|id| self.cmt.thread_fold_overrides.contains(&id));
+        self.cmt.threads_collapsed != overridden
+    }
+
+    /// スレッドの返信数
+    pub fn thread_reply_count(&self, comment_index: usize) -> usize {
+        self.thread_of_comment(comment_index)
+            .map_or(0, |t| t.replies.len())
+    }
+
+    /// 折りたたまれたスレッドの 1 行要約（作成者・本文の 1 行目・返信数）
+    pub fn collapsed_thread_summary(&self, comment_index: usize) -> String {
+        let root_index = self
+            .thread_of_comment(comment_index)
+            .map_or(comment_index, |t| t.root);
+        let Some(root) = self
+            .cmt
+            .review_comments
+            .as_ref()
+            .and_then(|c| c.get(root_index))
+        else {
+            return String::new();
+        };
+        let first_line = root.body.lines().next().unwrap_or("").trim();
+        let replies = self.thread_reply_count(comment_index);
+        format!(
+            "▸ @{}: {} ({} {})",
+            root.user.login,
+            first_line,
+            replies,
+            if replies == 1 { "reply" } else { "replies" }
+        )
+    }
+
+    /// コメントパネルに表示する現在行のコメント
+    ///
+    /// 折りたたまれたスレッドは最初のコメント 1 件（1 行の要約として表示）に畳む。
+    pub fn visible_inline_comment_indices(&self) -> Vec<usize> {
+        let mut seen_collapsed: Vec<u64> = Vec::new();
+        self.get_comment_indices_at_current_line()
+            .into_iter()
+            .filter(|&idx| {
+                if !self.is_thread_collapsed(idx) {
+                    return true;
+                }
+                let Some(root_id) = self.thread_root_id(idx) else {
+                    return true;
+                };
+                if seen_collapsed.contains(&root_id) {
+                    return false;
+                }
+                seen_collapsed.push(root_id);
+                true
+            })
+            .collect()
+    }
+
+    /// 全スレッドを折りたたむ
+    pub(crate) fn collapse_all_threads(&mut self) {
+        self.set_all_threads_collapsed(true);
+    }
+
+    /// 全スレッドを展開する
+    pub(crate) fn expand_all_threads(&mut self) {
+        self.set_all_threads_collapsed(false);
+    }
+
+    fn set_all_threads_collapsed(&mut self, collapsed: bool) {
+        self.cmt.threads_collapsed = collapsed;
+        self.cmt.thread_fold_overrides.clear();
+        self.refresh_collapsed_comment_lines();
+        self.clamp_inline_comment_selection();
+        let message = if collapsed {
+            "All comment threads collapsed"
+        } else {
+            "All comment threads expanded"
+        };
+        self.cmt.submission_result = Some((true, message.to_string()));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// コメントパネルで選択中のコメントのスレッドを折りたたむ / 展開する
+    pub(crate) fn toggle_selected_thread(&mut self) {
+        let indices = self.visible_inline_comment_indices();
+        let Some(&comment_index) = indices.get(
+            self.cmt
+                .selected_inline_comment
+                .min(indices.len().saturating_sub(1)),
+        ) else {
+            return;
+        };
+        let Some(root_id) = self.thread_root_id(comment_index) else {
+            return;
+        };
+        if !self.cmt.thread_fold_overrides.remove(&root_id) {
+            self.cmt.thread_fold_overrides.insert(root_id);
+        }
+        self.refresh_collapsed_comment_lines();
+
+        // 選択は同じスレッドの先頭コメントに留める
+        let indices = self.visible_inline_comment_indices();
+        self.cmt.selected_inline_comment = indices
+            .iter()
+            .position(|&idx| self.thread_root_id(idx) == Some(root_id))
+            .unwrap_or(0);
+        self.cmt.comment_panel_scroll = 0;
+    }
+
+    /// diff 上のコメント行のうち、スレッドがすべて折りたたまれている行を求め直す
+    pub(crate) fn refresh_collapsed_comment_lines(&mut self) {
+        let positions = &self.cmt.file_comment_positions;
+        let collapsed = self
+            .cmt
+            .file_comment_lines
+            .iter()
+            .copied()
+            .filter(|&line| {
+                positions
+                    .iter()
+                    .filter(|pos| pos.diff_line_index == line)
+                    .all(|pos| self.is_thread_collapsed(pos.comment_index))
+            })
+            .collect();
+        self.cmt.file_collapsed_comment_lines = collapsed;
+    }
+
+    fn clamp_inline_comment_selection(&mut self) {
+        let count = self.visible_inline_comment_indices().len();
+        self.cmt.selected_inline_comment = self
+            .cmt
+            .selected_inline_comment
+            .min(count.saturating_sub(1));
+        self.cmt.comment_panel_scroll = 0;
+    }
+}
@@ -7,10 +7,12 @@ use std::time::Instant;
 use tokio::sync::mpsc;
 
 use crate::cache::{load_local_review_comments, LocalCommentMeta, LocalReviewComment, PrCacheKey};
+use crate::diff::CommentSide;
 use crate::github::{self, comment::ReviewComment};
 use crate::keybinding::{event_to_keybinding, SequenceMatch};
 use crate::ui;
 
+use super::read_only::WriteAction;
 use super::types::*;
 use super::{App, AppState, CommentTab};
 
@@ -44,19 +46,30 @@ impl App {
             return;
         };
 
-        // Only allow comments on Added or Context lines (not Removed/Header/Meta)
-        if !matches!(
-            line_info.line_type,
-            crate::diff::LineType::Added | crate::diff::LineType::Context
-        ) {
+        // 削除行へのコメントは旧ファイル側（LEFT）の行番号に付ける（Header/Meta は不可）
+        let (line_number, side) = match line_info.line_type {
+            crate::diff::LineType::Added | crate::diff::LineType::Context => {
+                (line_info.new_line_number, CommentSide::Right)
+            }
+            crate::diff::LineType::Removed => (line_info.old_line_number, CommentSide::Left),
+            _ => return,
+        };
+        let Some(line_number) = line_number else {
+            return;
+        };
+        if side == CommentSide::Left && self.local_mode {
+            self.cmt.submission_result = Some((
+                false,
+                "Comments on removed lines are only supported in PR mode".to_string(),
+            ));
+            self.cmt.submission_result_time = Some(Instant::now());
             return;
         }
 
-        let Some(line_number) = line_info.new_line_number else {
+        let Some(diff_position) = line_info.diff_position else {
             return;
         };
-
-        let Some(diff_position) = line_info.diff_position else {
+        let Some(diff_position) = self.review_position(side, line_number, diff_position) else {
             return;
         };
 
@@ -65,6 +78,7 @@ impl App {
             line_number,
             diff_position,
             start_line_number: None,
+            side,
         }));
         self.input_text_area.clear();
         self.preview_return_state = self.state;
@@ -76,6 +90,10 @@ impl App {
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<()> {
         tracing::debug!(?action, "submit_review: start");
+        if self.deny_write(WriteAction::Review) {
+            return Ok(());
+        }
+        self.pause_review_timer();
         ui::restore_terminal(terminal)?;
 
         let editor_result = crate::editor::open_review_editor(self.config.editor.as_deref());
@@ -83,6 +101,7 @@ impl App {
 
         // エディタの成否に関わらずターミナルを再セットアップ
         *terminal = ui::setup_terminal()?;
+        self.resume_review_timer();
 
         let body = match editor_result {
             Ok(body) => body,
@@ -115,17 +134,41 @@ impl App {
         action: ReviewAction,
         body: &str,
     ) -> Result<()> {
-        tracing::debug!(body_len = body.len(), "submit_review: calling GitHub API");
-        match github::submit_review(&self.repo, self.pr_number(), action, body).await {
+        // pending コメントはレビューの comments[] として同時に作成する（レビューと一括で成否が決まる）
+        let (drafts, sent) = self.pending_review_drafts();
+        let stale = self.cmt.pending_comments.len() - sent.len();
+        tracing::debug!(
+            body_len = body.len(),
+            comments = drafts.len(),
+            "submit_review: calling GitHub API"
+        );
+        match github::submit_review(
+            &*self.github,
+            &self.repo,
+            self.pr_number(),
+            action,
+            body,
+            &drafts,
+        )
+        .await
+        {
             Ok(()) => {
+                self.remove_sent_pending_comments(&sent);
                 let action_str = match action {
                     ReviewAction::Approve => "approved",
                     ReviewAction::RequestChanges => "changes requested",
                     ReviewAction::Comment => "commented",
                 };
                 tracing::debug!(action_str, "submit_review: success");
-                self.cmt.submission_result =
-                    Some((true, format!("Review submitted ({})", action_str)));
+                let message = if stale > 0 {
+                    format!(
+                        "Review submitted ({}); {} pending comment(s) no longer match the diff and were kept",
+                        action_str, stale
+                    )
+                } else {
+                    format!("Review submitted ({})", action_str)
+                };
+                self.cmt.submission_result = Some((stale == 0, message));
                 self.cmt.submission_result_time = Some(Instant::now());
             }
             Err(e) => {
@@ -166,6 +209,11 @@ impl App {
         let Some(diff_position) = line_info.diff_position else {
             return;
         };
+        let Some(diff_position) =
+            self.review_position(CommentSide::Right, line_number, diff_position)
+        else {
+            return;
+        };
 
         let original_code = line_info.line_content.clone();
 
@@ -175,6 +223,7 @@ impl App {
                 line_number,
                 diff_position,
                 start_line_number: None,
+                side: CommentSide::Right,
             },
             original_code: original_code.clone(),
         });
@@ -252,6 +301,18 @@ impl App {
         let Some(start_line_number) = start_info.new_line_number else {
             return;
         };
+        let start_position = start_info.diff_position.unwrap_or(diff_position);
+        let Some(diff_position) =
+            self.review_position(CommentSide::Right, end_line_number, diff_position)
+        else {
+            return;
+        };
+        if self
+            .review_position(CommentSide::Right, start_line_number, start_position)
+            .is_none()
+        {
+            return;
+        }
 
         // 単一行の場合は start_line_number を None にする
         let start_line = if start_line_number < end_line_number {
@@ -268,90 +329,134 @@ impl App {
             line_number: end_line_number,
             diff_position,
             start_line_number: start_line,
+            side: CommentSide::Right,
         }));
         self.input_text_area.clear();
         self.preview_return_state = self.state;
         self.state = AppState::TextInput;
     }
     pub(crate) fn enter_multiline_suggestion_input(&mut self) {
-        let Some(ref selection) = self.multiline_selection else {
+        let Some((context, original_lines)) = self.multiline_suggestion_target() else {
             return;
         };
-        let start = selection.start();
-        let end = selection.end();
+        let original_code = original_lines.join("\n");
 
-        let Some(file) = self.files().get(self.selected_file) else {
-            return;
+        // バリデーション成功後にのみ選択状態をクリア
+        self.multiline_selection = None;
+
+        self.input_mode = Some(InputMode::Suggestion {
+            context,
+            original_code: original_code.clone(),
+        });
+        self.input_text_area.set_content(&original_code);
+        self.update_suggestion_highlight_cache();
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+    /// 複数行選択を外部エディタに流し込んで suggestion を作成する
+    pub(crate) async fn open_multiline_suggestion_editor(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<()> {
+        let Some((context, original_lines)) = self.multiline_suggestion_target() else {
+            return Ok(());
         };
-        let Some(patch) = file.patch.as_ref() else {
-            return;
+        let Some(filename) = self
+            .files()
+            .get(context.file_index)
+            .map(|f| f.filename.clone())
+        else {
+            return Ok(());
         };
+        self.multiline_selection = None;
+
+        let start_line = context.start_line_number.unwrap_or(context.line_number) as usize;
+        let lines: Vec<&str> = original_lines.iter().map(String::as_str).collect();
+
+        self.pause_review_timer();
+        ui::restore_terminal(terminal)?;
+        let editor_result = crate::editor::open_suggestion_editor(
+            self.config.editor.as_deref(),
+            &filename,
+            start_line,
+            &lines,
+        );
+        *terminal = ui::setup_terminal()?;
+        self.resume_review_timer();
+
+        match editor_result {
+            Ok(Some(code)) => {
+                let original_code = original_lines.join("\n");
+                self.submit_suggestion(context, &original_code, code)
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.cmt.submission_result = Some((false, format!("Editor failed: {}", e)));
+                self.cmt.submission_result_time = Some(Instant::now());
+            }
+        }
+        Ok(())
+    }
+    /// 複数行選択から suggestion の対象（`start_line` / `line` のアンカーと元コード）を求める
+    ///
+    /// ハンク境界を跨ぐ・削除行を含む選択は、理由をステータスに表示して `None` を返す
+    /// （選択状態は維持する）。
+    pub(crate) fn multiline_suggestion_target(
+        &mut self,
+    ) -> Option<(LineInputContext, Vec<String>)> {
+        let selection = self.multiline_selection.as_ref()?;
+        let start = selection.start();
+        let end = selection.end();
+
+        let patch = self.files().get(self.selected_file)?.patch.as_ref()?;
 
         // 範囲内の全行が同一ハンク内の new-side 行であることを検証
         if !crate::diff::validate_multiline_range(patch, start, end) {
-            return;
+            let crosses_hunk =
+                patch.lines().take(end + 1).skip(start).any(|line| {
+                    crate::diff::classify_line(line).0 == crate::diff::LineType::Header
+                });
+            let message = if crosses_hunk {
+                "Suggestion range cannot span multiple hunks"
+            } else {
+                "Suggestion range must contain only added or context lines"
+            };
+            self.cmt.submission_result = Some((false, message.to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return None;
         }
         let index = crate::diff::PatchIndex::build(patch);
 
-        // 終了行の情報を取得
-        let Some(end_info) = index.get(end) else {
-            return;
-        };
-        if !matches!(
-            end_info.line_type,
-            crate::diff::LineType::Added | crate::diff::LineType::Context
-        ) {
-            return;
-        }
-        let Some(end_line_number) = end_info.new_line_number else {
-            return;
-        };
-        let Some(diff_position) = end_info.diff_position else {
-            return;
-        };
-
-        // 開始行の情報を取得
-        let Some(start_info) = index.get(start) else {
-            return;
-        };
-        let Some(start_line_number) = start_info.new_line_number else {
-            return;
-        };
-        let mut original_lines = Vec::new();
-        for line_idx in start..=end {
-            if let Some(info) = index.get(line_idx) {
-                if matches!(
-                    info.line_type,
-                    crate::diff::LineType::Added | crate::diff::LineType::Context
-                ) {
-                    original_lines.push(info.content);
-                }
-            }
-        }
-        let original_code = original_lines.join("\n");
+        // 終了行の情報を取得（GitHub API の line パラメータ）
+        let end_info = index.get(end)?;
+        let end_line_number = end_info.new_line_number?;
+        let diff_position = end_info.diff_position?;
 
-        let start_line = if start_line_number < end_line_number {
-            Some(start_line_number)
-        } else {
-            None
-        };
+        // 開始行の情報を取得（GitHub API の start_line パラメータ）
+        let start_info = index.get(start)?;
+        let start_line_number = start_info.new_line_number?;
+        let start_position = start_info.diff_position.unwrap_or(diff_position);
+        let original_lines = (start..=end)
+            .filter_map(|line_idx| index.get(line_idx))
+            .map(|info| info.content.to_string())
+            .collect();
+        let diff_position =
+            self.review_position(CommentSide::Right, end_line_number, diff_position)?;
+        self.review_position(CommentSide::Right, start_line_number, start_position)?;
 
-        // バリデーション成功後にのみ選択状態をクリア
-        self.multiline_selection = None;
+        // 単一行の場合は start_line_number を None にする
+        let start_line = (start_line_number < end_line_number).then_some(start_line_number);
 
-        self.input_mode = Some(InputMode::Suggestion {
-            context: LineInputContext {
+        Some((
+            LineInputContext {
                 file_index: self.selected_file,
                 line_number: end_line_number,
                 diff_position,
                 start_line_number: start_line,
+                side: CommentSide::Right,
             },
-            original_code: original_code.clone(),
-        });
-        self.input_text_area.set_content(&original_code);
-        self.update_suggestion_highlight_cache();
-        self.preview_return_state = self.state;
-        self.state = AppState::TextInput;
+            original_lines,
+        ))
     }
     pub(crate) fn open_comment_list(&mut self) {
         self.state = AppState::CommentList;
@@ -444,6 +549,7 @@ impl App {
     /// (i.e. a background poll returned the same threads, possibly with new
     /// replies). A structural change (new/removed threads) resets selection.
     pub(crate) fn apply_review_comments(&mut self, comments: Vec<ReviewComment>) {
+        let comments = self.merge_pending_comments(comments);
         // Count all comments per file (including replies) so the badge
         // reflects total activity, not just thread count.
         self.cmt.file_comment_counts.clear();
@@ -575,11 +681,26 @@ impl App {
             return;
         }
 
+        self.load_pending_comments();
+        if !self.offline {
+            self.load_review_thread_states();
+        }
         if let Some(comments) = self.session_cache.get_review_comments(&cache_key) {
             self.cmt.local_comment_meta.clear();
             self.apply_review_comments(comments.to_vec());
             return;
         }
+        // オフライン: GitHub には問い合わせず、最後に取得したコメントをディスクキャッシュから表示
+        if self.offline {
+            let comments = crate::cache::load_pr_data_snapshot(&self.repo, self.pr_number())
+                .ok()
+                .flatten()
+                .map(|snapshot| snapshot.comments)
+                .unwrap_or_default();
+            self.cmt.local_comment_meta.clear();
+            self.apply_review_comments(comments);
+            return;
+        }
 
         self.cmt.local_comment_meta.clear();
         self.cmt.comments_loading = true;
@@ -588,12 +709,15 @@ impl App {
         self.cmt.comment_receiver = Some((pr_number, rx));
 
         let repo = self.repo.clone();
+        let github = self.github.clone();
 
         tokio::spawn(async move {
             // Fetch both review comments and reviews
             let review_comments_result =
-                github::comment::fetch_review_comments(&repo, pr_number).await;
-            let reviews_result = github::comment::fetch_reviews(&repo, pr_number).await;
+                github::comment::fetch_review_comments(&*github, &repo, pr_number).await;
+            let reviews_result = github::comment::fetch_reviews(&*github, &repo, pr_number).await;
+            // 片方でも失敗した結果でオフライン用のコメントを上書きしない
+            let complete = review_comments_result.is_ok() && reviews_result.is_ok();
 
             // Combine results
             let mut all_comments: Vec<ReviewComment> = Vec::new();
@@ -617,6 +741,7 @@ impl App {
                                 user: review.user,
                                 created_at: review.submitted_at.unwrap_or_default(),
                                 in_reply_to_id: None,
+                                reactions: Default::default(),
                             });
                         }
                     }
@@ -626,6 +751,14 @@ impl App {
             // Sort by created_at
             all_comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
 
+            if complete {
+                if let Err(e) =
+                    crate::cache::save_pr_comments_snapshot(&repo, pr_number, &all_comments)
+                {
+                    tracing::warn!("Failed to save comments for offline use: {}", e);
+                }
+            }
+
             let _ = tx.send(Ok(all_comments)).await;
         });
     }
@@ -649,9 +782,10 @@ impl App {
         self.cmt.discussion_comment_receiver = Some((pr_number, rx));
 
         let repo = self.repo.clone();
+        let github = self.github.clone();
 
         tokio::spawn(async move {
-            match github::comment::fetch_discussion_comments(&repo, pr_number).await {
+            match github::comment::fetch_discussion_comments(&*github, &repo, pr_number).await {
                 Ok(comments) => {
                     let _ = tx.send(Ok(comments)).await;
                 }
@@ -676,6 +810,11 @@ impl App {
             return self.handle_discussion_detail_input(key, visible_lines);
         }
 
+        if self.reaction_picker.is_some() {
+            self.handle_reaction_picker_key(&key);
+            return Ok(());
+        }
+
         let kb = self.config.keybindings.clone();
 
         if self.matches_single_key(&key, &kb.help) {
@@ -747,6 +886,14 @@ impl App {
                         self.cmt.selected_discussion_comment.saturating_sub(step);
                 }
             }
+        } else if self.cmt.comment_tab == CommentTab::Review
+            && self.matches_single_key(&key, &kb.toggle_resolved)
+        {
+            self.toggle_selected_thread_resolved();
+        } else if self.cmt.comment_tab == CommentTab::Review
+            && self.matches_single_key(&key, &kb.add_reaction)
+        {
+            self.open_reaction_picker();
         } else if self.matches_single_key(&key, &kb.open_panel) {
             match self.cmt.comment_tab {
                 CommentTab::Review => self.review_tab_open_panel(),
@@ -1028,6 +1175,7 @@ impl App {
     pub(crate) fn update_file_comment_positions(&mut self) {
         self.cmt.file_comment_positions.clear();
         self.cmt.file_comment_lines.clear();
+        self.cmt.file_collapsed_comment_lines.clear();
 
         let Some(file) = self.files().get(self.selected_file) else {
             return;
@@ -1061,6 +1209,7 @@ impl App {
         self.cmt
             .file_comment_positions
             .sort_by_key(|pos| pos.diff_line_index);
+        self.refresh_collapsed_comment_lines();
     }
 
     /// Static helper to find diff line index for a given line number
@@ -1131,7 +1280,7 @@ impl App {
 
     /// コメントパネルのコンテンツ行数を計算（スクロール上限算出用）
     pub(crate) fn comment_panel_content_lines(&self, panel_inner_width: usize) -> usize {
-        let indices = self.get_comment_indices_at_current_line();
+        let indices = self.visible_inline_comment_indices();
         if indices.is_empty() {
             return 1; // "No comments..." message
         }
@@ -1146,16 +1295,26 @@ impl App {
             if i > 0 {
                 count += 1; // separator
             }
-            count += 1; // header
-            count += Self::comment_body_wrapped_lines(&comment.body, panel_inner_width);
-            count += 1; // spacing
+            count += self.comment_panel_entry_lines(idx, &comment.body, panel_inner_width);
         }
         count
     }
 
+    /// パネル内 1 エントリの行数（折りたたみスレッドは要約 1 行）
+    fn comment_panel_entry_lines(&self, idx: usize, body: &str, panel_inner_width: usize) -> usize {
+        if self.is_thread_collapsed(idx) {
+            return Self::wrapped_line_count(
+                &self.collapsed_thread_summary(idx),
+                panel_inner_width,
+            );
+        }
+        // header + body + spacing
+        1 + Self::comment_body_wrapped_lines(body, panel_inner_width) + 1
+    }
+
     /// 指定インラインコメントのパネル内行オフセットを計算（スクロール追従用）
     pub(crate) fn comment_panel_offset_for(&self, target: usize, panel_inner_width: usize) -> u16 {
-        let indices = self.get_comment_indices_at_current_line();
+        let indices = self.visible_inline_comment_indices();
         let Some(ref comments) = self.cmt.review_comments else {
             return 0;
         };
@@ -1170,9 +1329,7 @@ impl App {
             if i > 0 {
                 offset += 1; // separator
             }
-            offset += 1; // header
-            offset += Self::comment_body_wrapped_lines(&comment.body, panel_inner_width);
-            offset += 1; // spacing
+            offset += self.comment_panel_entry_lines(idx, &comment.body, panel_inner_width);
         }
         if target > 0 {
             offset += 1; // separator before target
@@ -1200,35 +1357,77 @@ impl App {
         let panel_inner_height = (terminal_height.saturating_sub(8) * 40 / 100).max(1);
         content_lines.saturating_sub(panel_inner_height) as u16
     }
-    pub(crate) fn jump_to_next_comment(&mut self) {
-        let next = self
+    /// 現在のファイルでジャンプ先になるコメント行（畳まれた範囲内の行は除く）
+    fn comment_jump_targets(&self) -> Vec<usize> {
+        let mut lines: Vec<usize> = self
             .cmt
             .file_comment_positions
             .iter()
-            .find(|pos| pos.diff_line_index > self.diff_scroll.selected_line);
+            .map(|pos| pos.diff_line_index)
+            .filter(|&line| self.collapsed_lines_at(line).is_none())
+            .collect();
+        lines.dedup();
+        lines
+    }
 
-        if let Some(pos) = next {
-            self.diff_scroll.selected_line = pos.diff_line_index;
-            self.diff_scroll.scroll_offset = self.diff_scroll.selected_line;
+    fn jump_to_comment_line(&mut self, line: Option<usize>) {
+        if let Some(line) = line {
+            self.diff_scroll.selected_line = line;
+            self.diff_scroll.scroll_offset = line;
         }
     }
 
-    /// Jump to previous comment in the diff (no wrap-around, scroll to top)
+    /// Jump to next comment in the diff (wraps only when `diff.wrap_comment_navigation`)
+    pub(crate) fn jump_to_next_comment(&mut self) {
+        let targets = self.comment_jump_targets();
+        let current = self.diff_scroll.selected_line;
+        let next = targets
+            .iter()
+            .copied()
+            .find(|&line| line > current)
+            .or_else(|| {
+                self.config
+                    .diff
+                    .wrap_comment_navigation
+                    .then(|| targets.first().copied())
+                    .flatten()
+            });
+        self.jump_to_comment_line(next);
+    }
+
+    /// Jump to previous comment in the diff (wraps only when `diff.wrap_comment_navigation`)
     pub(crate) fn jump_to_prev_comment(&mut self) {
-        let prev = self
-            .cmt
-            .file_comment_positions
+        let targets = self.comment_jump_targets();
+        let current = self.diff_scroll.selected_line;
+        let prev = targets
             .iter()
             .rev()
-            .find(|pos| pos.diff_line_index < self.diff_scroll.selected_line);
+            .copied()
+            .find(|&line| line < current)
+            .or_else(|| {
+                self.config
+                    .diff
+                    .wrap_comment_navigation
+                    .then(|| targets.last().copied())
+                    .flatten()
+            });
+        self.jump_to_comment_line(prev);
+    }
 
-        if let Some(pos) = prev {
-            self.diff_scroll.selected_line = pos.diff_line_index;
-            self.diff_scroll.scroll_offset = self.diff_scroll.selected_line;
-        }
+    /// 現在のファイルの最初のコメント行へジャンプ
+    pub(crate) fn jump_to_first_comment(&mut self) {
+        let first = self.comment_jump_targets().first().copied();
+        self.jump_to_comment_line(first);
     }
+
+    /// 現在のファイルの最後のコメント行へジャンプ
+    pub(crate) fn jump_to_last_comment(&mut self) {
+        let last = self.comment_jump_targets().last().copied();
+        self.jump_to_comment_line(last);
+    }
+
     pub(crate) fn enter_reply_input(&mut self) {
-        let indices = self.get_comment_indices_at_current_line();
+        let indices = self.visible_inline_comment_indices();
         if indices.is_empty() {
             return;
         }
@@ -1245,6 +1444,14 @@ impl App {
         let Some(comment) = comments.get(comment_idx) else {
             return;
         };
+        if self.is_pending_comment(comment.id) {
+            self.cmt.submission_result = Some((
+                false,
+                "Cannot reply to a pending comment before the review is submitted".to_string(),
+            ));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        }
 
         self.input_mode = Some(InputMode::Reply {
             comment_id: comment.id,
@@ -0,0 +1,83 @@
+use std::time::Instant;
+
+use crate::config::Config;
+
+use super::types::AppState;
+use super::App;
+
+impl App {
+    /// 設定ファイルを再読み込みして反映する
+    ///
+    /// 読み込み・パースに失敗した場合は現在の設定を維持し、エラーをステータスに表示する。
+    pub(crate) fn reload_config(&mut self) {
+        match self.load_config_for_repo() {
+            Ok(()) => {
+                self.cmt.submission_result = Some((true, "Config reloaded".to_string()));
+            }
+            Err(e) => {
+                self.cmt.submission_result =
+                    Some((false, format!("Config reload failed: {:#}", e)));
+            }
+        }
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// 現在のリポジトリの `[repos."owner/repo"]` セクションを含めて設定を解決し直す
+    pub(crate) fn load_config_for_repo(&mut self) -> anyhow::Result<()> {
+        let repo = self.repo.contains('/').then_some(self.repo.as_str());
+        let project_root = self.config.project_root.clone();
+        let mut config = Config::load_for_dir(&project_root, repo)?;
+        config
+            .diff
+            .apply_terminal_background(crate::syntax::detect_terminal_background());
+        self.apply_reloaded_config(config);
+        Ok(())
+    }
+
+    /// 再読み込みした設定に差し替え、設定から派生する状態を作り直す
+    ///
+    /// `read_only` は CLI フラグ（`--read-only`）でも有効化されるため、有効にはできるが解除はしない。
+    /// AI 関連設定は起動時に確認済みの local override を含むため、現在の値を引き継ぐ。
+    /// `--theme` の指定があれば再読み込みした `diff.theme` より優先する。
+    /// テーマ・タブ幅が変わった場合はハイライト済みキャッシュをすべて破棄して再構築する。
+    pub(crate) fn apply_reloaded_config(&mut self, mut config: Config) {
+        config.read_only |= self.config.read_only;
+        config.ai = self.config.ai.clone();
+        config.local_overrides = std::mem::take(&mut self.config.local_overrides);
+        config.theme_override = self.config.theme_override.clone();
+        if let Some(theme) = &config.theme_override {
+            config.diff.theme = theme.clone();
+        }
+
+        let highlight_changed = config.diff.theme != self.config.diff.theme
+            || config.diff.tab_width != self.config.diff.tab_width;
+
+        self.input_text_area
+            .set_submit_key(config.keybindings.submit.clone());
+        self.config = config;
+
+        if !highlight_changed {
+            return;
+        }
+
+        self.diff_store.clear();
+        self.rebuild_theme_caches();
+        if matches!(self.state, AppState::DiffView | AppState::SplitViewDiff) {
+            self.ensure_diff_cache();
+        }
+    }
+
+    /// diff 以外のハイライト済みキャッシュ（suggestion・PR description・issue 本文）を作り直す
+    pub(crate) fn rebuild_theme_caches(&mut self) {
+        self.suggestion_highlight_cache = None;
+        self.update_suggestion_highlight_cache();
+        if self.pr_description_cache.take().is_some() {
+            self.rebuild_pr_description_cache();
+        }
+        if let Some(state) = self.issue_state.as_mut() {
+            if state.issue_detail_cache.take().is_some() {
+                self.rebuild_issue_detail_cache();
+            }
+        }
+    }
+}
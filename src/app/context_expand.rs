@@ -0,0 +1,255 @@
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+use crate::diff::{
+    expand_hunk_context, hunk_header_lines, resolve_comment_anchor, CommentAnchor, CommentSide,
+    LineType, PatchIndex,
+};
+use crate::github;
+
+use super::types::{hash_string, CommentAnchorCache, LineInputContext};
+use super::{App, DataState};
+
+/// `expand_context` 1 回で hunk の上下それぞれに広げる行数
+pub(crate) const CONTEXT_EXPAND_STEP: usize = 20;
+
+impl App {
+    /// カーソル位置の hunk ヘッダの上下に、ファイル本体から文脈行を足す
+    ///
+    /// 取得元は local mode なら作業ツリー、PR mode なら head コミットのファイル内容。
+    /// 一度読み込んだ内容は再利用するので、2 回目以降の展開は即座に反映される。
+    pub(crate) fn expand_context_at_cursor(&mut self) {
+        let header_line = self.diff_scroll.selected_line;
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        let is_header = file
+            .patch
+            .as_deref()
+            .is_some_and(|patch| hunk_header_lines(patch).contains(&header_line));
+        if !is_header {
+            self.set_context_message(false, "Move the cursor to a hunk header (@@) to expand");
+            return;
+        }
+        if file.status == "removed" {
+            self.set_context_message(false, "Deleted files have no context to expand");
+            return;
+        }
+        let filename = file.filename.clone();
+
+        if let Some(source) = self.context_sources.get(&filename).cloned() {
+            self.apply_context_expansion(&filename, header_line, &source);
+            return;
+        }
+        if self.local_mode {
+            let dir = std::path::Path::new(self.working_dir.as_deref().unwrap_or("."));
+            match std::fs::read_to_string(dir.join(&filename)) {
+                Ok(source) => {
+                    self.context_sources
+                        .insert(filename.clone(), source.clone());
+                    self.apply_context_expansion(&filename, header_line, &source);
+                }
+                Err(e) => self.set_context_message(false, &format!("Failed to read file: {}", e)),
+            }
+            return;
+        }
+
+        let Some(head_sha) = self.pr().map(|pr| pr.head.sha.clone()) else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel(1);
+        self.context_source_receiver = Some(rx);
+        let repo = self.repo.clone();
+        let github = self.github.clone();
+        tokio::spawn(async move {
+            let result = github::fetch_file_content(&*github, &repo, &filename, &head_sha)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send((filename, header_line, result)).await;
+        });
+        self.set_context_message(true, "Fetching file to expand context...");
+    }
+
+    pub(crate) fn poll_context_source_updates(&mut self) {
+        let Some(ref mut rx) = self.context_source_receiver else {
+            return;
+        };
+        let (filename, header_line, result) = match rx.try_recv() {
+            Ok(update) => update,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.context_source_receiver = None;
+                return;
+            }
+        };
+        self.context_source_receiver = None;
+        match result {
+            Ok(source) => {
+                self.context_sources
+                    .insert(filename.clone(), source.clone());
+                self.apply_context_expansion(&filename, header_line, &source);
+            }
+            Err(e) => self.set_context_message(false, &format!("Failed to fetch file: {}", e)),
+        }
+    }
+
+    /// `header_line` の hunk に文脈行を足した patch へ差し替える
+    pub(crate) fn apply_context_expansion(
+        &mut self,
+        filename: &str,
+        header_line: usize,
+        source: &str,
+    ) {
+        let Some(index) = self.files().iter().position(|f| f.filename == filename) else {
+            return;
+        };
+        let Some(patch) = self.files()[index].patch.clone() else {
+            return;
+        };
+        let source_lines: Vec<&str> = source.lines().collect();
+        let Some(expanded) =
+            expand_hunk_context(&patch, header_line, &source_lines, CONTEXT_EXPAND_STEP)
+        else {
+            self.set_context_message(false, "No more context to expand");
+            return;
+        };
+
+        self.original_patches
+            .entry(filename.to_string())
+            .or_insert(patch);
+        self.replace_patch(index, expanded);
+    }
+
+    /// 現在のファイルの文脈行の展開を取り消し、元の patch に戻す
+    pub(crate) fn reset_context(&mut self) {
+        let Some(filename) = self
+            .files()
+            .get(self.selected_file)
+            .map(|f| f.filename.clone())
+        else {
+            return;
+        };
+        let Some(original) = self.original_patches.remove(&filename) else {
+            self.set_context_message(false, "No expanded context to reset");
+            return;
+        };
+        // 空白差分を隠している場合は元の patch に掛け直す
+        self.whitespace_hidden.remove(&filename);
+        self.replace_patch(self.selected_file, original);
+        self.diff_scroll.selected_line = 0;
+        self.set_context_message(true, "Context reset");
+    }
+
+    /// 文脈行の展開・空白差分の非表示・ファイル内容をすべて破棄する（patch の再取得時）
+    pub(crate) fn clear_patch_overrides(&mut self) {
+        self.original_patches.clear();
+        self.whitespace_hidden.clear();
+        self.context_sources.clear();
+        self.context_source_receiver = None;
+    }
+
+    /// コメントの送信先となる patch（表示用に書き換えていれば元のもの）
+    pub(crate) fn review_patch(&self, file_index: usize) -> Option<&str> {
+        let file = self.files().get(file_index)?;
+        self.original_patches
+            .get(&file.filename)
+            .map(String::as_str)
+            .or(file.patch.as_deref())
+    }
+
+    /// コメント入力欄に出す送信先の行（ファイル・位置・patch が変わったときだけ解決し直す）
+    pub(crate) fn comment_anchor_preview(&self, ctx: &LineInputContext) -> Option<CommentAnchor> {
+        // diff_position は文脈行を広げる前の patch 上の位置
+        let patch = self.review_patch(ctx.file_index)?;
+        let patch_hash = hash_string(patch);
+        let mut cache = self.comment_anchor_cache.borrow_mut();
+        if let Some(cached) = cache.as_ref().filter(|c| {
+            c.file_index == ctx.file_index
+                && c.diff_position == ctx.diff_position
+                && c.patch_hash == patch_hash
+        }) {
+            return cached.anchor.clone();
+        }
+        let anchor = resolve_comment_anchor(patch, ctx.diff_position);
+        *cache = Some(CommentAnchorCache {
+            file_index: ctx.file_index,
+            diff_position: ctx.diff_position,
+            patch_hash,
+            anchor: anchor.clone(),
+        });
+        anchor
+    }
+
+    /// 表示中の patch 上の位置を、GitHub に送る元の patch の `position` に読み替える
+    ///
+    /// 展開で足した文脈行は PR の diff に含まれないためコメントできない。
+    /// その場合はメッセージを出して `None` を返す。
+    pub(crate) fn review_position(
+        &mut self,
+        side: CommentSide,
+        line: u32,
+        displayed_position: u32,
+    ) -> Option<u32> {
+        let file = self.files().get(self.selected_file)?;
+        let Some(original) = self.original_patches.get(&file.filename) else {
+            return Some(displayed_position);
+        };
+        let index = PatchIndex::build(original);
+        let position = (0..index.len())
+            .filter_map(|i| index.get(i))
+            .find(|info| match side {
+                CommentSide::Right => {
+                    matches!(info.line_type, LineType::Added | LineType::Context)
+                        && info.new_line_number == Some(line)
+                }
+                CommentSide::Left => {
+                    info.line_type == LineType::Removed && info.old_line_number == Some(line)
+                }
+            })
+            .and_then(|info| info.diff_position);
+        if position.is_none() {
+            self.set_context_message(false, "Expanded context lines are not part of the PR diff");
+        }
+        position
+    }
+
+    /// 表示する patch だけを差し替える（行範囲で持つ状態はずれるので捨てる）
+    pub(crate) fn set_file_patch(&mut self, index: usize, patch: String) -> bool {
+        let filename = match self.data_state {
+            DataState::Loaded { ref mut files, .. }
+            | DataState::PartiallyLoaded { ref mut files, .. } => {
+                let Some(file) = files.get_mut(index) else {
+                    return false;
+                };
+                file.patch = Some(patch);
+                file.filename.clone()
+            }
+            _ => return false,
+        };
+        self.patch_symbol_table = None;
+        self.folded_hunks.remove(&filename);
+        self.expanded_unchanged.remove(&filename);
+        true
+    }
+
+    /// patch を差し替え、行数・キャッシュ・行範囲に依存する状態を作り直す
+    pub(crate) fn replace_patch(&mut self, index: usize, patch: String) {
+        if !self.set_file_patch(index, patch) || index != self.selected_file {
+            return;
+        }
+        self.hunk_filter = None;
+        self.diff_search = None;
+        self.multiline_selection = None;
+        self.update_diff_line_count();
+        self.update_file_comment_positions();
+        self.diff_store.clear_current();
+        if tokio::runtime::Handle::try_current().is_ok() {
+            self.ensure_diff_cache();
+        }
+    }
+
+    fn set_context_message(&mut self, success: bool, message: &str) {
+        self.cmt.submission_result = Some((success, message.to_string()));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+}
@@ -0,0 +1,76 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::Instant;
+
+use crate::loader::is_valid_diff_base;
+
+use super::types::SharedDiffBase;
+use super::App;
+
+impl App {
+    /// 現在の比較元リビジョン（None なら PR 全体 / HEAD との差分）
+    pub fn diff_base(&self) -> Option<String> {
+        self.diff_base.lock().ok().and_then(|base| base.clone())
+    }
+
+    /// retry ループと共有する比較元リビジョンのハンドル
+    pub fn diff_base_handle(&self) -> SharedDiffBase {
+        self.diff_base.clone()
+    }
+
+    /// 比較元リビジョンを設定する（再取得はしない。起動時の `--base` 用）
+    pub fn set_diff_base(&mut self, base: Option<String>) {
+        if let Ok(mut guard) = self.diff_base.lock() {
+            *guard = base;
+        }
+    }
+
+    /// 比較元リビジョンの入力バーを開く（設定中のリビジョンを編集できる）
+    pub(crate) fn open_diff_base_input(&mut self) {
+        self.diff_base_input = Some(self.diff_base().unwrap_or_default());
+    }
+
+    /// 比較元リビジョン入力中のキー処理。処理した場合は true を返す。
+    pub(crate) fn handle_diff_base_input(&mut self, key: &KeyEvent) -> bool {
+        let Some(input) = self.diff_base_input.as_mut() else {
+            return false;
+        };
+        match key.code {
+            KeyCode::Esc => self.diff_base_input = None,
+            KeyCode::Enter => {
+                let rev = self.diff_base_input.take().unwrap_or_default();
+                self.apply_diff_base(&rev);
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => input.clear(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => input.push(c),
+            _ => {}
+        }
+        true
+    }
+
+    /// 比較元リビジョンを切り替えて差分を取り直す。空文字で PR 全体 / HEAD に戻す。
+    pub(crate) fn apply_diff_base(&mut self, rev: &str) {
+        let rev = rev.trim();
+        let base = (!rev.is_empty()).then(|| rev.to_string());
+        if base.as_deref().is_some_and(|rev| !is_valid_diff_base(rev)) {
+            self.cmt.submission_result = Some((false, format!("Invalid revision: {}", rev)));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        }
+        if base == self.diff_base() {
+            return;
+        }
+
+        let message = match (&base, self.local_mode) {
+            (Some(rev), _) => format!("Reviewing changes since {}", rev),
+            (None, true) => "Reviewing changes since HEAD".to_string(),
+            (None, false) => "Reviewing the whole PR".to_string(),
+        };
+        self.set_diff_base(base);
+        self.cmt.submission_result = Some((true, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+        self.retry_load();
+    }
+}
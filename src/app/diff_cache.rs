@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
 use tokio::sync::mpsc;
 
+use crate::diff::{classify_hunk_line, count_added_markers, LineType};
 use crate::github::{ChangedFile, PullRequest};
-use crate::syntax::ParserPool;
+use crate::syntax::{LinguistOverrides, ParserPool};
 
 use super::types::*;
 use super::{App, DataState};
@@ -17,14 +21,62 @@ impl App {
 
     pub fn files(&self) -> &[ChangedFile] {
         match &self.data_state {
-            DataState::Loaded { files, .. } => files,
+            DataState::Loaded { files, .. } | DataState::PartiallyLoaded { files, .. } => files,
             _ => &[],
         }
     }
 
+    /// 追加行に含まれるレビューマーカー数と diffstat を再計算する
+    ///
+    /// 全パッチを走査するため、描画ごとではなくパッチ更新時にのみ呼び出す。
+    /// パッチ内の定義表もここで破棄する。
+    pub(crate) fn refresh_review_marker_count(&mut self) {
+        self.patch_symbol_table = None;
+        self.refresh_diff_stat();
+        let diff = &self.config.diff;
+        let count = if diff.highlight_markers {
+            self.files()
+                .iter()
+                .filter_map(|f| f.patch.as_deref())
+                .map(|patch| count_added_markers(patch, &diff.marker_words))
+                .sum()
+        } else {
+            0
+        };
+        self.review_marker_count = count;
+        let patches_pending = self.batch_diff_receiver.is_some()
+            || matches!(self.data_state, DataState::PartiallyLoaded { .. });
+        self.review_marker_count_partial = diff.highlight_markers
+            && patches_pending
+            && self.files().iter().any(|f| f.patch.is_none());
+    }
+
+    /// ファイル数と追加・削除行数の合計を再計算する
+    ///
+    /// PR mode は API の additions/deletions を合算する。local mode はパッチの行を数える。
+    pub(crate) fn refresh_diff_stat(&mut self) {
+        let local_mode = self.local_mode;
+        let files = self.files();
+        let mut stat = DiffStat {
+            files: files.len(),
+            ..DiffStat::default()
+        };
+        for file in files {
+            let (additions, deletions) = match file.patch.as_deref() {
+                Some(patch) if local_mode => count_patch_changes(patch),
+                _ => (file.additions as u64, file.deletions as u64),
+            };
+            stat.additions += additions;
+            stat.deletions += deletions;
+        }
+        self.diff_stat = stat;
+    }
+
     pub fn pr(&self) -> Option<&PullRequest> {
         match &self.data_state {
-            DataState::Loaded { pr, .. } => Some(pr.as_ref()),
+            DataState::Loaded { pr, .. } | DataState::PartiallyLoaded { pr, .. } => {
+                Some(pr.as_ref())
+            }
             _ => None,
         }
     }
@@ -38,6 +90,9 @@ impl App {
     pub(crate) fn sync_diff_to_selected_file(&mut self) {
         self.diff_scroll.reset();
         self.multiline_selection = None;
+        self.hunk_filter = None;
+        self.diff_search = None;
+        self.line_endings_expanded = false;
         self.cmt.comment_panel_open = false;
         self.cmt.comment_panel_scroll = 0;
         self.clear_pending_keys();
@@ -50,8 +105,105 @@ impl App {
         self.request_lazy_diff();
         self.ensure_diff_cache();
     }
+    /// シンタックスハイライトの有効/無効を切り替える
+    ///
+    /// 現在の diff キャッシュを差し替えるだけで、スクロール・カーソル位置は維持する。
+    /// 無効化中もハイライト済みキャッシュはストアに残し、再有効化時に再利用する。
+    pub(crate) fn toggle_highlighting(&mut self) {
+        self.highlighting_enabled = !self.highlighting_enabled;
+        self.diff_store.drop_prefetch_rx();
+        if self.highlighting_enabled {
+            // current はプレーンキャッシュなので破棄してストア復元 or 再構築させる
+            self.diff_store.clear_current();
+        } else {
+            self.diff_store.drop_highlight_rx();
+        }
+        if tokio::runtime::Handle::try_current().is_ok() {
+            self.ensure_diff_cache();
+            if self.highlighting_enabled {
+                self.start_prefetch_all_files();
+            }
+        }
+
+        let message = if self.highlighting_enabled {
+            "Syntax highlighting: on"
+        } else {
+            "Syntax highlighting: off"
+        };
+        self.cmt.submission_result = Some((true, message.to_string()));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// local mode: 作業ディレクトリの `.gitattributes` から linguist 属性を読み込む
+    pub(crate) fn load_linguist_overrides(&mut self) {
+        let dir = self.working_dir.as_deref().unwrap_or(".");
+        self.linguist = LinguistOverrides::load(std::path::Path::new(dir));
+    }
+
+    /// local mode: ファイル一覧の更新前に、ハイライト済みキャッシュを
+    /// `(filename, patch_hash)` をキーにして `highlight_memo` へ退避する
+    pub(crate) fn stash_highlighted_caches(&mut self) {
+        let named: Vec<(String, DiffCache)> = self
+            .diff_store
+            .drain_highlighted()
+            .into_iter()
+            .filter_map(|(index, cache)| Some((self.files().get(index)?.filename.clone(), cache)))
+            .collect();
+        // patch が判明していてハッシュが異なるエントリは stale
+        let known: HashMap<String, u64> = self
+            .files()
+            .iter()
+            .filter_map(|f| Some((f.filename.clone(), hash_string(f.patch.as_ref()?))))
+            .collect();
+        self.highlight_memo
+            .retain(|name, hash| known.get(name).is_none_or(|h| *h == hash));
+
+        let theme = &self.config.diff.theme;
+        for (filename, cache) in named {
+            self.highlight_memo.insert(filename, theme, cache);
+        }
+    }
+
+    /// `highlight_memo` から patch が変わっていないファイルのキャッシュを取り出す
+    fn take_memoized_cache(&mut self, file_index: usize) -> Option<DiffCache> {
+        if self.highlight_memo.is_empty() {
+            return None;
+        }
+        let file = self.files().get(file_index)?;
+        let patch_hash = hash_string(file.patch.as_ref()?);
+        let filename = file.filename.clone();
+        let mut cache = self
+            .highlight_memo
+            .take(&filename, patch_hash, &self.config.diff.theme)?;
+        let is_md = crate::language::is_markdown_ext_from_filename(&filename);
+        if is_md && cache.markdown_rich != self.markdown_rich {
+            return None;
+        }
+        cache.file_index = file_index;
+        Some(cache)
+    }
+
+    /// 退避済みキャッシュのうち再利用できるものを store に戻す（プリフェッチ前）
+    pub(crate) fn restore_memoized_caches(&mut self) {
+        for index in 0..self.files().len() {
+            if self.highlight_memo.is_empty() {
+                return;
+            }
+            if self.diff_store.store_contains_key(&index)
+                || self.diff_store.current_key() == Some(&index)
+            {
+                continue;
+            }
+            if let Some(cache) = self.take_memoized_cache(index) {
+                self.diff_store.insert_store(index, cache);
+            }
+        }
+    }
+
     pub fn ensure_diff_cache(&mut self) {
         let file_index = self.selected_file;
+        self.apply_hidden_whitespace(file_index);
+        self.record_file_viewed(file_index);
         let markdown_rich = self.markdown_rich;
         // IMPORTANT: markdown_rich フラグはmarkdownファイルのハイライト結果にのみ影響する。
         // 非markdownファイルでは build_diff_cache() の出力が同一になるため、フラグの
@@ -68,7 +220,9 @@ impl App {
         if self.diff_store.current_key() == Some(&file_index) {
             if let Some(ref cache) = self.diff_store.current {
                 let md_ok = !is_md || cache.markdown_rich == markdown_rich;
-                if md_ok {
+                // ハイライト無効時はハイライト済みキャッシュをプレーンに差し替える
+                let highlight_ok = self.highlighting_enabled || !cache.highlighted;
+                if md_ok && highlight_ok {
                     let Some(file) = self.files().get(file_index) else {
                         self.diff_store.clear_current();
                         return;
@@ -93,12 +247,16 @@ impl App {
             self.diff_store.clear_current();
             return;
         };
-        let filename = file.filename.clone();
+        let filename = self
+            .linguist
+            .highlight_filename(&file.filename)
+            .map(|name| name.into_owned());
         let current_hash = hash_string(&patch);
 
         // 2. ストアにハイライト済みキャッシュがあるか確認
         //    try_restore は patch_hash のみチェックするため、markdown_rich は別途確認
-        if self.diff_store.try_restore(&file_index, Some(current_hash)) {
+        if self.highlighting_enabled && self.diff_store.try_restore(&file_index, Some(current_hash))
+        {
             // markdown_rich の一致チェック（非markdownファイルでは常に ok）
             let md_ok = !is_md
                 || self
@@ -114,20 +272,36 @@ impl App {
             self.diff_store.clear_current();
         }
 
+        // 2b. local mode: リフレッシュ前から patch が変わっていなければ再利用
+        if self.highlighting_enabled {
+            if let Some(cache) = self.take_memoized_cache(file_index) {
+                self.diff_store.set_current(file_index, cache);
+                return;
+            }
+        }
+
         // 3. キャッシュミス: プレーンキャッシュを即座に構築（~1ms）
         let tab_width = self.config.diff.tab_width;
         let mut plain_cache = crate::ui::diff_view::build_plain_diff_cache(&patch, tab_width);
         plain_cache.file_index = file_index;
         self.diff_store.set_current(file_index, plain_cache);
+        if !self.highlighting_enabled {
+            return;
+        }
+        // linguist-generated: プレーン表示のままハイライトを省略する
+        let Some(filename) = filename else {
+            return;
+        };
 
         // 完全版キャッシュをバックグラウンドで構築
         let (tx, rx) = mpsc::channel(1);
         self.diff_store.set_highlight_rx(rx);
 
         let theme = self.config.diff.theme.clone();
+        // local mode ではリフレッシュごとに同じファイルを再構築するため、前回のツリーを再利用する
+        let mut parser_pool = self.diff_store.parser_pool(self.local_mode);
 
         tokio::task::spawn_blocking(move || {
-            let mut parser_pool = ParserPool::new();
             let mut cache = crate::ui::diff_view::build_diff_cache(
                 &patch,
                 &filename,
@@ -206,6 +380,18 @@ pub fn build_pr_description_patch(body: &str) -> String {
     patch
 }
 
+/// パッチの追加・削除行数
+fn count_patch_changes(patch: &str) -> (u64, u64) {
+    let mut combined = false;
+    patch.lines().fold((0, 0), |(added, removed), line| {
+        match classify_hunk_line(line, &mut combined).0.two_way() {
+            LineType::Added => (added + 1, removed),
+            LineType::Removed => (added, removed + 1),
+            _ => (added, removed),
+        }
+    })
+}
+
 #[cfg(test)]
 mod patch_tests {
     use super::*;
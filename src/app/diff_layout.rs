@@ -0,0 +1,55 @@
+use std::time::Instant;
+
+use super::types::{DiffLayout, SplitDiffScroll};
+use super::App;
+
+/// `scroll_left` / `scroll_right` 1 回あたりの横スクロール量（桁）
+const SPLIT_SCROLL_STEP: usize = 8;
+
+impl App {
+    /// unified / 左右分割レイアウトを切り替える
+    pub(crate) fn toggle_diff_layout(&mut self) {
+        self.diff_layout = match self.diff_layout {
+            DiffLayout::Unified => DiffLayout::Split,
+            DiffLayout::Split => DiffLayout::Unified,
+        };
+        self.split_scroll = SplitDiffScroll::default();
+        let message = match self.diff_layout {
+            DiffLayout::Unified => "Diff layout: unified",
+            DiffLayout::Split => "Diff layout: side-by-side",
+        };
+        self.cmt.submission_result = Some((true, message.to_string()));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// unified diff の長い行の折り返し（継続行はガターの後ろに揃える）を切り替える
+    pub(crate) fn toggle_diff_wrap(&mut self) {
+        self.diff_wrap = !self.diff_wrap;
+        let message = if self.diff_wrap {
+            "Line wrap: on"
+        } else {
+            "Line wrap: off"
+        };
+        self.cmt.submission_result = Some((true, message.to_string()));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// 左右分割レイアウトで横スクロール対象の列（old / new）を切り替える
+    pub(crate) fn toggle_split_focus(&mut self) {
+        self.split_scroll.focus_new = !self.split_scroll.focus_new;
+    }
+
+    /// 左右分割レイアウトのフォーカス中の列を横スクロールする
+    pub(crate) fn scroll_split_column(&mut self, right: bool) {
+        let offset = if self.split_scroll.focus_new {
+            &mut self.split_scroll.new
+        } else {
+            &mut self.split_scroll.old
+        };
+        *offset = if right {
+            offset.saturating_add(SPLIT_SCROLL_STEP)
+        } else {
+            offset.saturating_sub(SPLIT_SCROLL_STEP)
+        };
+    }
+}
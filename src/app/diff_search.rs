@@ -0,0 +1,152 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::Instant;
+
+use crate::diff::find_search_matches;
+
+use super::types::DiffSearch;
+use super::App;
+
+impl App {
+    /// 現在のファイルに対する diff 内検索
+    pub fn current_diff_search(&self) -> Option<&DiffSearch> {
+        self.diff_search
+            .as_ref()
+            .filter(|s| s.file_index == self.selected_file)
+    }
+
+    /// 検索クエリの入力を開く（既存クエリは引き継ぐ）
+    pub(crate) fn open_diff_search(&mut self) {
+        let origin_line = self.diff_scroll.selected_line;
+        match self.diff_search {
+            Some(ref mut search) if search.file_index == self.selected_file => {
+                search.input_active = true;
+                search.origin_line = origin_line;
+            }
+            _ => self.diff_search = Some(DiffSearch::new(self.selected_file, origin_line)),
+        }
+    }
+
+    /// クエリ入力中のキー処理。処理した場合は true を返す。
+    ///
+    /// 入力のたびに一致を再計算し、検索を開いた行以降の最初の一致へ移動する。
+    pub(crate) fn handle_diff_search_input(&mut self, key: &KeyEvent) -> bool {
+        let Some(search) = self.diff_search.as_mut().filter(|s| s.input_active) else {
+            return false;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                let origin_line = search.origin_line;
+                self.diff_search = None;
+                self.diff_scroll.jump_to(origin_line);
+                return true;
+            }
+            KeyCode::Enter => {
+                if search.query.is_empty() {
+                    self.diff_search = None;
+                } else {
+                    search.input_active = false;
+                    if search.matches.is_empty() {
+                        let message = format!("Pattern not found: {}", search.query);
+                        self.cmt.submission_result = Some((false, message));
+                        self.cmt.submission_result_time = Some(Instant::now());
+                    }
+                }
+                return true;
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                search.query.clear();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                search.query.push(c);
+            }
+            _ => return true,
+        }
+        self.recompute_diff_search();
+        true
+    }
+
+    /// キャッシュ済みの行テキストから一致を再計算し、起点以降の最初の一致へ移動する
+    pub(crate) fn recompute_diff_search(&mut self) {
+        let Some(search) = self.diff_search.as_ref() else {
+            return;
+        };
+        let case_sensitive = self.config.diff.search_case_sensitive;
+        let matches = match self
+            .diff_store
+            .current
+            .as_ref()
+            .filter(|cache| cache.file_index == search.file_index)
+        {
+            // 描画と同じテキスト（タブ展開済み）で位置を求める
+            Some(cache) => find_search_matches(
+                cache.lines.iter().map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|s| cache.resolve(s.content))
+                        .collect::<String>()
+                }),
+                &search.query,
+                case_sensitive,
+            ),
+            None => {
+                let patch = self
+                    .files()
+                    .get(search.file_index)
+                    .and_then(|f| f.patch.as_deref())
+                    .unwrap_or("");
+                find_search_matches(patch.lines(), &search.query, case_sensitive)
+            }
+        };
+
+        let origin_line = search.origin_line;
+        let target = matches
+            .iter()
+            .find(|m| m.line >= origin_line)
+            .or_else(|| matches.first())
+            .map_or(origin_line, |m| m.line);
+        if let Some(ref mut search) = self.diff_search {
+            search.matches = matches;
+        }
+        self.diff_scroll.jump_to(target);
+    }
+
+    /// 次 / 前の一致行へ移動する（末尾・先頭で折り返す）
+    ///
+    /// 検索が確定していなければ false を返し、呼び出し側は通常のキー処理を続ける。
+    pub(crate) fn jump_to_search_match(&mut self, forward: bool) -> bool {
+        let Some(search) = self
+            .current_diff_search()
+            .filter(|s| !s.input_active && !s.query.is_empty())
+        else {
+            return false;
+        };
+        let current = self.diff_scroll.selected_line;
+        let next = if forward {
+            search
+                .matches
+                .iter()
+                .find(|m| m.line > current)
+                .or_else(|| search.matches.first())
+        } else {
+            search
+                .matches
+                .iter()
+                .rev()
+                .find(|m| m.line < current)
+                .or_else(|| search.matches.last())
+        };
+        match next.map(|m| m.line) {
+            Some(line) => self.diff_scroll.jump_to(line),
+            None => {
+                let message = format!("Pattern not found: {}", search.query);
+                self.cmt.submission_result = Some((false, message));
+                self.cmt.submission_result_time = Some(Instant::now());
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::github::ChangedFile;
+
+use super::App;
+
+fn has_comments(
+    files: &[ChangedFile],
+    comment_counts: &HashMap<String, usize>,
+    idx: usize,
+) -> bool {
+    files
+        .get(idx)
+        .and_then(|file| comment_counts.get(&file.filename))
+        .is_some_and(|&count| count > 0)
+}
+
+/// `order`（表示順のファイルインデックス）の中で `current` の次（`forward` でなければ前）にある、
+/// コメントが 1 件以上付いたファイルのインデックス。端で折り返すかは `wrap`。
+/// 折り返すときは `current` 自身も候補になる。`current` が `order` に無ければ先頭（末尾）から探す。
+pub(crate) fn next_file_with_comments(
+    files: &[ChangedFile],
+    comment_counts: &HashMap<String, usize>,
+    order: &[usize],
+    current: usize,
+    forward: bool,
+    wrap: bool,
+) -> Option<usize> {
+    let pos = order.iter().position(|&idx| idx == current);
+    let (after, before): (Vec<usize>, Vec<usize>) = match (pos, forward) {
+        (Some(pos), true) => (order[pos + 1..].to_vec(), order[..=pos].to_vec()),
+        (Some(pos), false) => (
+            order[..pos].iter().rev().copied().collect(),
+            order[pos..].iter().rev().copied().collect(),
+        ),
+        (None, true) => (order.to_vec(), Vec::new()),
+        (None, false) => (order.iter().rev().copied().collect(), Vec::new()),
+    };
+    let wrapped = if wrap { before } else { Vec::new() };
+    after
+        .into_iter()
+        .chain(wrapped)
+        .find(|&idx| has_comments(files, comment_counts, idx))
+}
+
+impl App {
+    /// ファイル一覧の選択を、コメントの付いた次（前）のファイルへ移す。
+    /// 端で折り返すかは `diff.wrap_comment_navigation`。
+    pub(crate) fn jump_to_file_with_comments(&mut self, forward: bool) {
+        if self.is_file_tree_active() {
+            self.set_file_comment_nav_message(
+                "File comment navigation is not available in tree view".to_string(),
+            );
+            return;
+        }
+        let order = match self.file_list_filter.as_ref() {
+            Some(filter) => filter.matched_indices.clone(),
+            None => self.file_display_order(),
+        };
+        let files = self.files();
+        let counts = &self.cmt.file_comment_counts;
+        let next = next_file_with_comments(
+            files,
+            counts,
+            &order,
+            self.selected_file,
+            forward,
+            self.config.diff.wrap_comment_navigation,
+        );
+        let Some(idx) = next else {
+            let message = if !order.iter().any(|&idx| has_comments(files, counts, idx)) {
+                "No files with comments".to_string()
+            } else {
+                let edge = if forward { "last" } else { "first" };
+                format!("Already at the {} file with comments", edge)
+            };
+            self.set_file_comment_nav_message(message);
+            return;
+        };
+        self.selected_file = idx;
+        if let Some(filter) = self.file_list_filter.as_mut() {
+            filter.selected = filter.matched_indices.iter().position(|&i| i == idx);
+        }
+    }
+
+    fn set_file_comment_nav_message(&mut self, message: String) {
+        self.cmt.submission_result = Some((true, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(filename: &str) -> ChangedFile {
+        ChangedFile {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: None,
+            viewed: false,
+        }
+    }
+
+    fn fixture() -> (Vec<ChangedFile>, HashMap<String, usize>) {
+        let files = ["a.rs", "b.rs", "c.rs", "d.rs", "e.rs"]
+            .into_iter()
+            .map(file)
+            .collect();
+        let counts = HashMap::from([
+            ("b.rs".to_string(), 2),
+            ("c.rs".to_string(), 0),
+            ("d.rs".to_string(), 1),
+        ]);
+        (files, counts)
+    }
+
+    #[test]
+    fn test_next_file_with_comments_after_current() {
+        let (files, counts) = fixture();
+        let order = [0, 1, 2, 3, 4];
+        let next = |current, forward| {
+            next_file_with_comments(&files, &counts, &order, current, forward, false)
+        };
+        assert_eq!(next(0, true), Some(1));
+        // 件数 0 のエントリはコメント無しとして飛ばす
+        assert_eq!(next(1, true), Some(3));
+        assert_eq!(next(3, true), None);
+        assert_eq!(next(4, false), Some(3));
+        assert_eq!(next(1, false), None);
+    }
+
+    #[test]
+    fn test_next_file_with_comments_wraps() {
+        let (files, counts) = fixture();
+        let order = [0, 1, 2, 3, 4];
+        assert_eq!(
+            next_file_with_comments(&files, &counts, &order, 3, true, true),
+            Some(1)
+        );
+        assert_eq!(
+            next_file_with_comments(&files, &counts, &order, 1, false, true),
+            Some(3)
+        );
+        // 自分しかコメント付きが無ければ自分に戻る
+        let only_b = HashMap::from([("b.rs".to_string(), 1)]);
+        assert_eq!(
+            next_file_with_comments(&files, &only_b, &order, 1, true, true),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_next_file_with_comments_follows_display_order() {
+        let (files, counts) = fixture();
+        // 並べ替え後の表示順（d, a, b, ...）に沿って進む
+        let order = [3, 0, 1, 4, 2];
+        assert_eq!(
+            next_file_with_comments(&files, &counts, &order, 3, true, false),
+            Some(1)
+        );
+        assert_eq!(
+            next_file_with_comments(&files, &counts, &order, 1, true, false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_next_file_with_comments_none_when_no_comments() {
+        let (files, _) = fixture();
+        let order = [0, 1, 2, 3, 4];
+        assert_eq!(
+            next_file_with_comments(&files, &HashMap::new(), &order, 0, true, true),
+            None
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use std::cmp::{Ordering, Reverse};
+use std::time::Instant;
+
+use crate::diff::{categorize_file, FileCategory};
+use crate::github::ChangedFile;
+
+use super::{App, DataState, FileListRow};
+
+/// ファイル一覧の並び順（`sort_files` で順に切り替える）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileSort {
+    /// API / `git diff` が返した順
+    #[default]
+    Default,
+    /// 変更行数（additions + deletions）の多い順
+    Churn,
+    /// パスのアルファベット順
+    Name,
+    /// 変更種別順（added → modified → deleted → renamed）
+    Status,
+}
+
+impl FileSort {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Default => Self::Churn,
+            Self::Churn => Self::Name,
+            Self::Name => Self::Status,
+            Self::Status => Self::Default,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Churn => "churn",
+            Self::Name => "name",
+            Self::Status => "status",
+        }
+    }
+
+    /// `a` と `b` の並び順。`Default` は常に `Equal`（安定ソートで元の順を保つ）。
+    pub fn compare(self, a: &ChangedFile, b: &ChangedFile) -> Ordering {
+        match self {
+            Self::Default => Ordering::Equal,
+            Self::Churn => Reverse(churn(a)).cmp(&Reverse(churn(b))),
+            Self::Name => a.filename.cmp(&b.filename),
+            Self::Status => status_rank(a).cmp(&status_rank(b)),
+        }
+    }
+
+    /// `files` へのインデックス列をこの並び順に並べ替える（同順位は元の順を保つ）
+    pub fn sort_indices(self, files: &[ChangedFile], indices: &mut [usize]) {
+        if self == Self::Default {
+            indices.sort_unstable();
+            return;
+        }
+        indices.sort_by(|&a, &b| self.compare(&files[a], &files[b]).then(a.cmp(&b)));
+    }
+}
+
+fn churn(file: &ChangedFile) -> u64 {
+    u64::from(file.additions) + u64::from(file.deletions)
+}
+
+fn status_rank(file: &ChangedFile) -> u8 {
+    match categorize_file(&file.status, file.patch.as_deref()) {
+        FileCategory::Added => 0,
+        FileCategory::Modified => 1,
+        FileCategory::Deleted => 2,
+        FileCategory::Renamed => 3,
+    }
+}
+
+impl App {
+    /// フラットなファイル一覧に表示中のファイルの順（`files()` へのインデックス）。
+    /// 折りたたんだ「Generated (N)」グループのファイルは含まない。
+    pub(crate) fn file_display_order(&self) -> Vec<usize> {
+        self.file_list_rows()
+            .into_iter()
+            .filter_map(FileListRow::file_index)
+            .collect()
+    }
+
+    /// 表示順で `delta` 行先を選択する（端で止まる）
+    pub(crate) fn move_file_selection(&mut self, delta: isize) {
+        let rows = self.file_list_rows();
+        let Some(last) = rows.len().checked_sub(1) else {
+            return;
+        };
+        let pos = self.selected_file_list_row(&rows);
+        self.select_file_list_row(rows[pos.saturating_add_signed(delta).min(last)]);
+    }
+
+    /// 表示順で先頭（`last` なら末尾）の行を選択する
+    pub(crate) fn select_edge_file(&mut self, last: bool) {
+        let rows = self.file_list_rows();
+        let edge = if last { rows.last() } else { rows.first() };
+        if let Some(&row) = edge {
+            self.select_file_list_row(row);
+        }
+    }
+
+    /// ファイル一覧の並び順を切り替える。選択中のファイルは並べ替え後も選択したままにする。
+    pub(crate) fn cycle_file_sort(&mut self) {
+        if self.is_file_tree_active() {
+            self.set_file_sort_message("File sort is not available in tree view".to_string());
+            return;
+        }
+        self.file_sort = self.file_sort.next();
+        let current = self.selected_file;
+        let sort = self.file_sort;
+        let files = match &self.data_state {
+            DataState::Loaded { files, .. } | DataState::PartiallyLoaded { files, .. } => {
+                files.as_slice()
+            }
+            _ => &[],
+        };
+        if let Some(filter) = self.file_list_filter.as_mut() {
+            sort.sort_indices(files, &mut filter.matched_indices);
+            filter.selected = filter.matched_indices.iter().position(|&i| i == current);
+        }
+        self.set_file_sort_message(format!("Sort files: {}", sort.label()));
+    }
+
+    fn set_file_sort_message(&mut self, message: String) {
+        self.cmt.submission_result = Some((true, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(filename: &str, status: &str, additions: u32, deletions: u32) -> ChangedFile {
+        ChangedFile {
+            filename: filename.to_string(),
+            status: status.to_string(),
+            additions,
+            deletions,
+            patch: None,
+            viewed: false,
+        }
+    }
+
+    fn files() -> Vec<ChangedFile> {
+        vec![
+            file("src/main.rs", "modified", 3, 1),
+            file("README.md", "added", 10, 0),
+            file("old.rs", "removed", 0, 40),
+            file("src/lib.rs", "renamed", 1, 1),
+            file("Cargo.toml", "modified", 2, 2),
+        ]
+    }
+
+    fn sorted(sort: FileSort) -> Vec<&'static str> {
+        let files = files();
+        let mut indices: Vec<usize> = (0..files.len()).collect();
+        sort.sort_indices(&files, &mut indices);
+        let names = [
+            "src/main.rs",
+            "README.md",
+            "old.rs",
+            "src/lib.rs",
+            "Cargo.toml",
+        ];
+        indices.into_iter().map(|i| names[i]).collect()
+    }
+
+    #[test]
+    fn test_sort_default_keeps_original_order() {
+        assert_eq!(
+            sorted(FileSort::Default),
+            [
+                "src/main.rs",
+                "README.md",
+                "old.rs",
+                "src/lib.rs",
+                "Cargo.toml"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_churn_descending() {
+        // 同じ変更行数（main.rs / Cargo.toml は 4 行）は元の順を保つ
+        assert_eq!(
+            sorted(FileSort::Churn),
+            [
+                "old.rs",
+                "README.md",
+                "src/main.rs",
+                "Cargo.toml",
+                "src/lib.rs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_name() {
+        assert_eq!(
+            sorted(FileSort::Name),
+            [
+                "Cargo.toml",
+                "README.md",
+                "old.rs",
+                "src/lib.rs",
+                "src/main.rs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_status() {
+        assert_eq!(
+            sorted(FileSort::Status),
+            [
+                "README.md",
+                "src/main.rs",
+                "Cargo.toml",
+                "old.rs",
+                "src/lib.rs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_cycle() {
+        let mut sort = FileSort::default();
+        let mut labels = Vec::new();
+        for _ in 0..4 {
+            sort = sort.next();
+            labels.push(sort.label());
+        }
+        assert_eq!(labels, ["churn", "name", "status", "default"]);
+    }
+}
@@ -1,6 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::{App, DataState};
+use crate::config::KeybindingsConfig;
+use crate::diff::{categorize_file, FileCategory};
+use crate::filter::{file_glob_matches, ListFilter};
+use crate::keybinding::{KeySequence, SequenceMatch};
 
 /// issue_state のフィルタに安全にアクセスするヘルパー
 fn get_issue_filter_mut(app: &mut App) -> Option<&mut crate::filter::ListFilter> {
@@ -9,6 +13,16 @@ fn get_issue_filter_mut(app: &mut App) -> Option<&mut crate::filter::ListFilter>
         .and_then(|s| s.issue_list_filter.as_mut())
 }
 
+/// 変更種別フィルタのキーと種別の対応
+fn file_category_bindings(kb: &KeybindingsConfig) -> [(&KeySequence, FileCategory); 4] {
+    [
+        (&kb.filter_added_files, FileCategory::Added),
+        (&kb.filter_deleted_files, FileCategory::Deleted),
+        (&kb.filter_modified_files, FileCategory::Modified),
+        (&kb.filter_renamed_files, FileCategory::Renamed),
+    ]
+}
+
 fn get_issue_filter(app: &App) -> Option<&crate::filter::ListFilter> {
     app.issue_state
         .as_ref()
@@ -36,6 +50,8 @@ impl App {
                     "pr" => self.prs.pr_list_filter = None,
                     "file" => {
                         self.file_list_filter = None;
+                        self.file_category_filter = None;
+                        self.file_glob_filter = None;
                         self.rebuild_file_tree_if_active();
                     }
                     "issue" => {
@@ -48,6 +64,9 @@ impl App {
                 true
             }
             KeyCode::Enter => {
+                // 変更種別 / glob フィルタ中はクエリが空でもフィルタを残す
+                let keep_file_filter = target == "file"
+                    && (self.file_category_filter.is_some() || self.file_glob_filter.is_some());
                 let filter = match target {
                     "pr" => self.prs.pr_list_filter.as_mut(),
                     "file" => self.file_list_filter.as_mut(),
@@ -55,7 +74,7 @@ impl App {
                     _ => return false,
                 };
                 let did_clear_file = if let Some(f) = filter {
-                    if f.query.is_empty() {
+                    if f.query.is_empty() && !keep_file_filter {
                         match target {
                             "pr" => self.prs.pr_list_filter = None,
                             "file" => self.file_list_filter = None,
@@ -197,10 +216,25 @@ impl App {
                     None => return,
                 };
                 let files = match &self.data_state {
-                    DataState::Loaded { files, .. } => files.as_slice(),
+                    DataState::Loaded { files, .. } | DataState::PartiallyLoaded { files, .. } => {
+                        files.as_slice()
+                    }
                     _ => &[],
                 };
                 filter.apply(files, |file, q| file.filename.to_lowercase().contains(q));
+                if let Some(category) = self.file_category_filter {
+                    filter.matched_indices.retain(|&i| {
+                        let file = &files[i];
+                        categorize_file(&file.status, file.patch.as_deref()) == category
+                    });
+                }
+                if let Some(ref pattern) = self.file_glob_filter {
+                    filter
+                        .matched_indices
+                        .retain(|&i| file_glob_matches(pattern, &files[i].filename));
+                }
+                self.file_sort
+                    .sort_indices(files, &mut filter.matched_indices);
                 if let Some(idx) = filter.sync_selection() {
                     self.selected_file = idx;
                 }
@@ -230,6 +264,103 @@ impl App {
         }
     }
 
+    /// pending_keys が変更種別フィルタのシーケンスに一致すれば適用して true を返す
+    pub(crate) fn try_file_category_sequence(&mut self, kb: &KeybindingsConfig) -> bool {
+        let matched = file_category_bindings(kb)
+            .into_iter()
+            .find(|(seq, _)| self.try_match_sequence(seq) == SequenceMatch::Full);
+        let Some((_, category)) = matched else {
+            return false;
+        };
+        self.clear_pending_keys();
+        self.toggle_file_category_filter(category);
+        true
+    }
+
+    pub(crate) fn key_could_start_file_category(
+        &self,
+        key: &KeyEvent,
+        kb: &KeybindingsConfig,
+    ) -> bool {
+        file_category_bindings(kb)
+            .iter()
+            .any(|(seq, _)| self.key_could_match_sequence(key, seq))
+    }
+
+    /// ファイル一覧を変更種別で絞り込む。同じ種別を再度指定すると解除する。
+    pub(crate) fn toggle_file_category_filter(&mut self, category: FileCategory) {
+        if self.file_category_filter == Some(category) {
+            self.file_category_filter = None;
+        } else {
+            self.file_category_filter = Some(category);
+        }
+        self.refresh_file_list_filter();
+    }
+
+    /// glob フィルタの入力バーを開く（適用中のパターンを編集できる）
+    pub(crate) fn open_file_glob_input(&mut self) {
+        self.file_glob_input = Some(self.file_glob_filter.clone().unwrap_or_default());
+    }
+
+    /// glob フィルタ入力中のキー処理。処理した場合は true を返す。
+    pub(crate) fn handle_file_glob_input(&mut self, key: &KeyEvent) -> bool {
+        let Some(input) = self.file_glob_input.as_mut() else {
+            return false;
+        };
+        match key.code {
+            KeyCode::Esc => self.file_glob_input = None,
+            KeyCode::Enter => {
+                let pattern = self.file_glob_input.take().unwrap_or_default();
+                self.set_file_glob_filter(&pattern);
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => input.clear(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => input.push(c),
+            _ => {}
+        }
+        true
+    }
+
+    /// ファイル一覧を glob パターンで絞り込む。空のパターンで解除する。
+    pub(crate) fn set_file_glob_filter(&mut self, pattern: &str) {
+        let pattern = pattern.trim();
+        self.file_glob_filter = (!pattern.is_empty()).then(|| pattern.to_string());
+        self.refresh_file_list_filter();
+    }
+
+    /// 変更種別 / glob フィルタの変更後にファイル一覧のフィルタを作り直す
+    fn refresh_file_list_filter(&mut self) {
+        if self.file_category_filter.is_none() && self.file_glob_filter.is_none() {
+            // キーワードも無ければフィルタごと解除
+            if self
+                .file_list_filter
+                .as_ref()
+                .is_some_and(|f| f.query.is_empty() && !f.input_active)
+            {
+                self.file_list_filter = None;
+                self.rebuild_file_tree_if_active();
+                return;
+            }
+        } else {
+            self.file_list_filter.get_or_insert_with(|| ListFilter {
+                input_active: false,
+                ..ListFilter::new()
+            });
+        }
+
+        // 選択中のファイルが残っていれば維持し、なければ先頭へ
+        let current = self.selected_file;
+        self.reapply_filter("file");
+        if let Some(filter) = self.file_list_filter.as_mut() {
+            filter.selected = filter.matched_indices.iter().position(|&i| i == current);
+            if let Some(idx) = filter.sync_selection() {
+                self.selected_file = idx;
+            }
+        }
+    }
+
     /// フィルタ適用中のナビゲーション（j/k/↑/↓）。処理した場合は true を返す。
     pub(crate) fn handle_filter_navigation(&mut self, target: &str, is_down: bool) -> bool {
         let filter = match target {
@@ -278,6 +409,8 @@ impl App {
                 "pr" => self.prs.pr_list_filter = None,
                 "file" => {
                     self.file_list_filter = None;
+                    self.file_category_filter = None;
+                    self.file_glob_filter = None;
                     // フィルタ解除時にツリーを復元
                     self.rebuild_file_tree_if_active();
                 }
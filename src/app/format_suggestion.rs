@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::io::Stdout;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use ratatui::{backend::CrosstermBackend, Terminal};
+use tokio::io::AsyncWriteExt;
+
+use crate::ui;
+
+use super::App;
+
+/// `filename` の拡張子に対応するフォーマッタコマンドを返す
+///
+/// キーは `rs` / `.rs` のどちらでもよく、大文字小文字は区別しない。
+pub(crate) fn formatter_for<'a>(
+    filename: &str,
+    formatters: &'a HashMap<String, String>,
+) -> Option<&'a str> {
+    let ext = Path::new(filename).extension()?.to_str()?;
+    formatters
+        .iter()
+        .find(|(key, _)| key.trim_start_matches('.').eq_ignore_ascii_case(ext))
+        .map(|(_, command)| command.as_str())
+        .filter(|command| !command.trim().is_empty())
+}
+
+/// `command` に `input` を stdin で渡し、stdout を返す。非ゼロ終了なら stderr をエラーにする。
+async fn run_formatter(
+    command: &str,
+    input: &str,
+    working_dir: &str,
+    timeout_secs: u64,
+) -> Result<String, String> {
+    let parts = shell_words::split(command).map_err(|e| e.to_string())?;
+    let Some((cmd, args)) = parts.split_first() else {
+        return Err("Formatter command is empty".to_string());
+    };
+    let mut child = tokio::process::Command::new(cmd)
+        .args(args)
+        .current_dir(working_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", cmd, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let output = tokio::time::timeout(Duration::from_secs(timeout_secs), child.wait_with_output())
+        .await
+        .map_err(|_| format!("Timed out after {}s", timeout_secs))?
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = stderr.trim();
+        return Err(if stderr.is_empty() {
+            format!("exited with {}", output.status)
+        } else {
+            stderr.lines().collect::<Vec<_>>().join(" ")
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+impl App {
+    /// 複数行選択をフォーマッタに通し、その出力を初期値にして外部エディタで suggestion を書く（local mode）
+    pub(crate) async fn suggest_format(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<()> {
+        if !self.local_mode {
+            self.set_format_message("suggest_format is only available in local mode".to_string());
+            return Ok(());
+        }
+        let Some(filename) = self
+            .files()
+            .get(self.selected_file)
+            .map(|f| f.filename.clone())
+        else {
+            return Ok(());
+        };
+        let Some(command) = formatter_for(&filename, &self.config.formatters).map(str::to_string)
+        else {
+            self.set_format_message(format!("No formatter configured for {}", filename));
+            return Ok(());
+        };
+        let Some((context, original_lines)) = self.multiline_suggestion_target() else {
+            return Ok(());
+        };
+
+        let original_code = original_lines.join("\n");
+        let working_dir = self.working_dir.clone().unwrap_or_else(|| ".".to_string());
+        let formatted = match run_formatter(
+            &command,
+            &format!("{}\n", original_code),
+            &working_dir,
+            self.config.shell.timeout_secs,
+        )
+        .await
+        {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                self.set_format_message(format!("Formatter failed: {}", e));
+                return Ok(());
+            }
+        };
+        self.multiline_selection = None;
+
+        let start_line = context.start_line_number.unwrap_or(context.line_number) as usize;
+        let lines: Vec<&str> = formatted.lines().collect();
+
+        self.pause_review_timer();
+        ui::restore_terminal(terminal)?;
+        let editor_result = crate::editor::open_suggestion_editor(
+            self.config.editor.as_deref(),
+            &filename,
+            start_line,
+            &lines,
+        );
+        *terminal = ui::setup_terminal()?;
+        self.resume_review_timer();
+
+        match editor_result {
+            Ok(Some(code)) => self.submit_suggestion(context, &original_code, code),
+            Ok(None) => {}
+            Err(e) => self.set_format_message(format!("Editor failed: {}", e)),
+        }
+        Ok(())
+    }
+
+    fn set_format_message(&mut self, message: String) {
+        self.cmt.submission_result = Some((false, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formatters() -> HashMap<String, String> {
+        HashMap::from([
+            ("rs".to_string(), "rustfmt --emit stdout".to_string()),
+            (
+                ".ts".to_string(),
+                "prettier --parser typescript".to_string(),
+            ),
+            ("py".to_string(), "  ".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_formatter_for_matches_extension() {
+        let formatters = formatters();
+        assert_eq!(
+            formatter_for("src/main.rs", &formatters),
+            Some("rustfmt --emit stdout")
+        );
+        // 先頭のドットや大文字小文字の違いは無視する
+        assert_eq!(
+            formatter_for("web/App.TS", &formatters),
+            Some("prettier --parser typescript")
+        );
+    }
+
+    #[test]
+    fn test_formatter_for_unconfigured_or_blank() {
+        let formatters = formatters();
+        assert_eq!(formatter_for("README.md", &formatters), None);
+        assert_eq!(formatter_for("Makefile", &formatters), None);
+        assert_eq!(formatter_for("tool.py", &formatters), None);
+        // 拡張子はファイル名の最後の部分だけを見る
+        assert_eq!(formatter_for("rs/notes.txt", &formatters), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_formatter_reports_stderr_on_failure() {
+        let formatted = run_formatter("sort", "b\na\n", ".", 10).await.unwrap();
+        assert_eq!(formatted, "a\nb\n");
+
+        let err = run_formatter("sh -c 'echo bad input >&2; exit 1'", "x", ".", 10)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "bad input");
+    }
+}
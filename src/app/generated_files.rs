@@ -0,0 +1,147 @@
+use crate::syntax::linguist::pattern_matches;
+use crate::syntax::LinguistOverrides;
+
+use super::App;
+
+/// フラットなファイル一覧の 1 行
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileListRow {
+    /// `files()` へのインデックス
+    File(usize),
+    /// 生成ファイルをまとめた「Generated (N)」の見出し行
+    GeneratedHeader { count: usize },
+}
+
+impl FileListRow {
+    pub fn file_index(self) -> Option<usize> {
+        match self {
+            Self::File(idx) => Some(idx),
+            Self::GeneratedHeader { .. } => None,
+        }
+    }
+}
+
+/// 生成ファイルとして一覧の末尾に折りたたむか
+///
+/// `.gitattributes` の `linguist-generated` 指定を優先し（`-linguist-generated` なら
+/// glob に一致しても生成ファイル扱いしない）、指定がなければ `diff.generated_globs` で判定する。
+pub(crate) fn is_generated_path(
+    linguist: &LinguistOverrides,
+    globs: &[String],
+    path: &str,
+) -> bool {
+    linguist
+        .generated_attr(path)
+        .unwrap_or_else(|| globs.iter().any(|glob| pattern_matches(glob, path)))
+}
+
+impl App {
+    pub(crate) fn is_generated_file(&self, path: &str) -> bool {
+        is_generated_path(&self.linguist, &self.config.diff.generated_globs, path)
+    }
+
+    /// フラットなファイル一覧の行。手書きのファイルを並び順どおりに並べ、
+    /// 生成ファイルは末尾の見出し行の下にまとめる（折りたたみ中は見出しのみ）。
+    pub(crate) fn file_list_rows(&self) -> Vec<FileListRow> {
+        let files = self.files();
+        let mut order: Vec<usize> = (0..files.len()).collect();
+        self.file_sort.sort_indices(files, &mut order);
+        if self.linguist.is_empty() && self.config.diff.generated_globs.is_empty() {
+            return order.into_iter().map(FileListRow::File).collect();
+        }
+
+        let (generated, hand_written): (Vec<usize>, Vec<usize>) = order
+            .into_iter()
+            .partition(|&idx| self.is_generated_file(&files[idx].filename));
+        let mut rows: Vec<FileListRow> = hand_written.into_iter().map(FileListRow::File).collect();
+        if !generated.is_empty() {
+            rows.push(FileListRow::GeneratedHeader {
+                count: generated.len(),
+            });
+            if self.generated_files_expanded {
+                rows.extend(generated.into_iter().map(FileListRow::File));
+            }
+        }
+        rows
+    }
+
+    /// `rows` のうち選択中の行の位置（見つからなければ先頭）
+    pub(crate) fn selected_file_list_row(&self, rows: &[FileListRow]) -> usize {
+        let header = self
+            .generated_header_selected
+            .then(|| {
+                rows.iter()
+                    .position(|row| matches!(row, FileListRow::GeneratedHeader { .. }))
+            })
+            .flatten();
+        header
+            .or_else(|| {
+                rows.iter()
+                    .position(|&row| row == FileListRow::File(self.selected_file))
+            })
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn select_file_list_row(&mut self, row: FileListRow) {
+        match row {
+            FileListRow::File(idx) => {
+                self.selected_file = idx;
+                self.generated_header_selected = false;
+            }
+            FileListRow::GeneratedHeader { .. } => self.generated_header_selected = true,
+        }
+    }
+
+    /// 見出し行を選択中なら「Generated (N)」グループを開閉する（開閉したら true）
+    pub(crate) fn toggle_generated_files_if_selected(&mut self) -> bool {
+        if !self.generated_header_selected {
+            return false;
+        }
+        let has_header = self
+            .file_list_rows()
+            .iter()
+            .any(|row| matches!(row, FileListRow::GeneratedHeader { .. }));
+        if !has_header {
+            self.generated_header_selected = false;
+            return false;
+        }
+        self.generated_files_expanded = !self.generated_files_expanded;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn globs(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|p| (*p).to_string()).collect()
+    }
+
+    #[test]
+    fn test_is_generated_path_matches_globs() {
+        let linguist = LinguistOverrides::default();
+        let globs = globs(&["*.lock", "*.snap"]);
+        assert!(is_generated_path(&linguist, &globs, "Cargo.lock"));
+        assert!(is_generated_path(
+            &linguist,
+            &globs,
+            "tests/snapshots/a.snap"
+        ));
+        assert!(!is_generated_path(&linguist, &globs, "src/main.rs"));
+        assert!(!is_generated_path(&linguist, &[], "Cargo.lock"));
+    }
+
+    #[test]
+    fn test_is_generated_path_prefers_gitattributes() {
+        let linguist = LinguistOverrides::parse(
+            "src/gen/** linguist-generated\nkeep.lock -linguist-generated\n",
+        );
+        let globs = globs(&["*.lock"]);
+        assert!(is_generated_path(&linguist, &globs, "src/gen/api.rs"));
+        assert!(is_generated_path(&linguist, &globs, "Cargo.lock"));
+        // `-linguist-generated` は glob より優先される
+        assert!(!is_generated_path(&linguist, &globs, "keep.lock"));
+        assert!(!is_generated_path(&linguist, &globs, "src/lib.rs"));
+    }
+}
@@ -88,8 +88,9 @@ impl App {
         } else {
             let repo = self.repo.clone();
             let pr_number = self.pr_number();
+            let github = self.github.clone();
             tokio::spawn(async move {
-                let result = github::fetch_pr_commits(&repo, pr_number, page, per_page)
+                let result = github::fetch_pr_commits(&*github, &repo, pr_number, page, per_page)
                     .await
                     .map_err(|e| e.to_string());
                 let _ = tx.send(result).await;
@@ -150,8 +151,9 @@ impl App {
             });
         } else {
             let repo = self.repo.clone();
+            let github = self.github.clone();
             tokio::spawn(async move {
-                let result = github::fetch_commit_diff(&repo, &sha)
+                let result = github::fetch_commit_diff(&*github, &repo, &sha)
                     .await
                     .map(|diff| (sha, diff))
                     .map_err(|e| e.to_string());
@@ -203,12 +205,13 @@ impl App {
             let tx = fetch_tx.clone();
             let repo = repo.clone();
             let working_dir = working_dir.clone();
+            let github = self.github.clone();
 
             tokio::spawn(async move {
                 let diff_text = if use_local {
                     github::fetch_local_commit_diff(working_dir.as_deref(), &sha).await
                 } else {
-                    github::fetch_commit_diff(&repo, &sha).await
+                    github::fetch_commit_diff(&*github, &repo, &sha).await
                 };
                 if let Ok(diff_text) = diff_text {
                     let _ = tx.send((sha, diff_text)).await;
@@ -647,7 +650,7 @@ impl App {
         let working_dir = self.working_dir.clone();
 
         tokio::spawn(async move {
-            loader::fetch_single_file_diff(working_dir, path, is_untracked, tx).await;
+            loader::fetch_single_file_diff(working_dir, None, path, is_untracked, tx).await;
         });
     }
 
@@ -888,6 +891,7 @@ impl App {
             return Ok(());
         }
 
+        self.pause_review_timer();
         crate::ui::restore_terminal(terminal)?;
 
         let working_dir = self.working_dir.as_deref().unwrap_or(".");
@@ -897,6 +901,7 @@ impl App {
             .status();
 
         *terminal = crate::ui::setup_terminal()?;
+        self.resume_review_timer();
 
         match status {
             Ok(s) if s.success() => {
@@ -1980,7 +1985,7 @@ fn start_git_ops_prefetch(
             let wd = wd.clone();
             let handle = tokio::spawn(async move {
                 let (dtx, mut drx) = mpsc::channel(1);
-                loader::fetch_single_file_diff(wd, path.clone(), is_untracked, dtx).await;
+                loader::fetch_single_file_diff(wd, None, path.clone(), is_untracked, dtx).await;
                 drx.recv().await.map(|r| (r.filename, r.patch))
             });
             handles.push(handle);
@@ -2188,6 +2193,11 @@ mod tests {
             interner: lasso::Rodeo::new(),
             highlighted: false,
             markdown_rich: false,
+            line_ending_hunks: Vec::new(),
+            line_ending: crate::diff::LineEnding::Lf,
+            context_runs: Vec::new(),
+            conflict_lines: Vec::new(),
+            moved_blocks: Vec::new(),
         };
         ops.diff_store.set_current("a.rs".to_string(), plain);
 
@@ -2198,6 +2208,11 @@ mod tests {
             interner: lasso::Rodeo::new(),
             highlighted: true,
             markdown_rich: false,
+            line_ending_hunks: Vec::new(),
+            line_ending: crate::diff::LineEnding::Lf,
+            context_runs: Vec::new(),
+            conflict_lines: Vec::new(),
+            moved_blocks: Vec::new(),
         };
         let (tx, rx) = tokio::sync::mpsc::channel(1);
         tx.try_send(("a.rs".to_string(), highlighted)).unwrap();
@@ -2218,6 +2233,11 @@ mod tests {
             interner: lasso::Rodeo::new(),
             highlighted: true,
             markdown_rich: false,
+            line_ending_hunks: Vec::new(),
+            line_ending: crate::diff::LineEnding::Lf,
+            context_runs: Vec::new(),
+            conflict_lines: Vec::new(),
+            moved_blocks: Vec::new(),
         };
         ops.diff_store.set_current("a.rs".to_string(), highlighted);
 
@@ -2228,6 +2248,11 @@ mod tests {
             interner: lasso::Rodeo::new(),
             highlighted: false,
             markdown_rich: false,
+            line_ending_hunks: Vec::new(),
+            line_ending: crate::diff::LineEnding::Lf,
+            context_runs: Vec::new(),
+            conflict_lines: Vec::new(),
+            moved_blocks: Vec::new(),
         };
         ops.diff_store.set_current("b.rs".to_string(), plain_b);
 
@@ -2250,6 +2275,11 @@ mod tests {
             interner: lasso::Rodeo::new(),
             highlighted: true,
             markdown_rich: false,
+            line_ending_hunks: Vec::new(),
+            line_ending: crate::diff::LineEnding::Lf,
+            context_runs: Vec::new(),
+            conflict_lines: Vec::new(),
+            moved_blocks: Vec::new(),
         };
         let (tx, rx) = tokio::sync::mpsc::channel(10);
         tx.try_send(("a.rs".to_string(), cache)).unwrap();
@@ -2270,6 +2300,11 @@ mod tests {
             interner: lasso::Rodeo::new(),
             highlighted: true,
             markdown_rich: false,
+            line_ending_hunks: Vec::new(),
+            line_ending: crate::diff::LineEnding::Lf,
+            context_runs: Vec::new(),
+            conflict_lines: Vec::new(),
+            moved_blocks: Vec::new(),
         };
         let (tx, rx) = tokio::sync::mpsc::channel(10);
         tx.try_send(("a.rs".to_string(), cache)).unwrap();
@@ -0,0 +1,78 @@
+use std::time::Instant;
+
+use crate::diff::hide_whitespace_changes;
+
+use super::App;
+
+impl App {
+    /// 空白だけの変更の表示/非表示を切り替える（全ファイル共通）
+    ///
+    /// 非表示にしたファイルは元の patch を控えておき、再表示時にそれへ戻す。
+    /// 戻すと文脈行の展開も取り消される。
+    pub(crate) fn toggle_whitespace(&mut self) {
+        self.hide_whitespace = !self.hide_whitespace;
+        if !self.hide_whitespace {
+            let hidden: Vec<String> = self.whitespace_hidden.drain().collect();
+            for filename in hidden {
+                let Some(original) = self.original_patches.remove(&filename) else {
+                    continue;
+                };
+                if let Some(index) = self.files().iter().position(|f| f.filename == filename) {
+                    self.set_file_patch(index, original);
+                }
+            }
+        }
+
+        self.hunk_filter = None;
+        self.diff_search = None;
+        self.multiline_selection = None;
+        self.diff_store.clear_current();
+        self.update_diff_line_count();
+        self.update_file_comment_positions();
+        if tokio::runtime::Handle::try_current().is_ok() {
+            self.ensure_diff_cache();
+        } else {
+            self.apply_hidden_whitespace(self.selected_file);
+        }
+
+        let message = if self.hide_whitespace {
+            "Whitespace-only changes: hidden"
+        } else {
+            "Whitespace-only changes: shown"
+        };
+        self.cmt.submission_result = Some((true, message.to_string()));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// 空白差分の非表示が有効なら、`index` のファイルの patch に適用する（適用済みなら何もしない）
+    ///
+    /// 表示するファイルの diff キャッシュを作る直前に呼ぶ。後から読み込まれた patch にも効く。
+    pub(crate) fn apply_hidden_whitespace(&mut self, index: usize) {
+        if !self.hide_whitespace {
+            return;
+        }
+        let Some(file) = self.files().get(index) else {
+            return;
+        };
+        let Some(patch) = file.patch.as_deref() else {
+            return;
+        };
+        if self.whitespace_hidden.contains(&file.filename) {
+            return;
+        }
+        let filename = file.filename.clone();
+        let hidden = hide_whitespace_changes(patch);
+        let patch = patch.to_string();
+        self.whitespace_hidden.insert(filename.clone());
+        let Some(hidden) = hidden else {
+            return;
+        };
+
+        self.original_patches.entry(filename).or_insert(patch);
+        self.set_file_patch(index, hidden);
+        if index == self.selected_file {
+            self.update_diff_line_count();
+            self.update_file_comment_positions();
+        }
+    }
+}
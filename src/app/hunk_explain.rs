@@ -0,0 +1,120 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+use crate::ai::explain::{build_explain_hunk_prompt, explain_hunk};
+use crate::ai::Context;
+use crate::diff::hunk_lines_at;
+
+use super::types::{HunkExplainState, LoadState};
+use super::App;
+
+impl App {
+    /// カーソル位置の hunk を AI に解説させ、結果をポップアップで表示する。
+    /// rally とは独立した 1 回きりの reviewer 呼び出しで、GitHub には何も投稿しない。
+    pub(crate) fn open_hunk_explain(&mut self) {
+        let Some(context) = self.hunk_explain_context() else {
+            self.cmt.submission_result = Some((false, "Cursor is not inside a hunk".to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        };
+        let (context, filename, hunk) = context;
+
+        self.cancel_hunk_explain();
+        self.hunk_explain = Some(HunkExplainState {
+            filename: filename.clone(),
+            header: hunk.lines().next().unwrap_or_default().to_string(),
+            explanation: LoadState::Loading,
+            scroll: 0,
+        });
+
+        let prompt = build_explain_hunk_prompt(&context, &filename, &hunk);
+        let config = self.config.ai.clone();
+        let (tx, rx) = mpsc::channel(1);
+        self.hunk_explain_receiver = Some(rx);
+        let handle = tokio::spawn(async move {
+            let result = explain_hunk(&config, &prompt, &context)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result).await;
+        });
+        self.hunk_explain_abort_handle = Some(handle.abort_handle());
+    }
+
+    /// 解説に渡す Context（対象ファイルの patch のみ）と、ファイル名・hunk 本文
+    fn hunk_explain_context(&self) -> Option<(Context, String, String)> {
+        let file = self.files().get(self.selected_file)?;
+        let patch = file.patch.as_deref()?;
+        let hunk = hunk_lines_at(patch, self.diff_scroll.selected_line)?.join("\n");
+        let pr = self.pr();
+        let context = Context {
+            repo: self.repo.clone(),
+            pr_number: self.pr_number(),
+            pr_title: pr.map(|pr| pr.title.clone()).unwrap_or_default(),
+            pr_body: pr.and_then(|pr| pr.body.clone()),
+            diff: patch.to_string(),
+            working_dir: self.working_dir.clone(),
+            head_sha: pr.map(|pr| pr.head.sha.clone()).unwrap_or_default(),
+            base_branch: pr.map(|pr| pr.base.ref_name.clone()).unwrap_or_default(),
+            external_comments: Vec::new(),
+            local_mode: self.local_mode,
+            file_patches: vec![(file.filename.clone(), patch.to_string())],
+        };
+        Some((context, file.filename.clone(), hunk))
+    }
+
+    /// 実行中の解説を中断する
+    pub(crate) fn cancel_hunk_explain(&mut self) {
+        if let Some(handle) = self.hunk_explain_abort_handle.take() {
+            handle.abort();
+        }
+        self.hunk_explain_receiver = None;
+    }
+
+    pub(crate) fn poll_hunk_explain_updates(&mut self) {
+        let Some(ref mut rx) = self.hunk_explain_receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                Err("Explain task ended unexpectedly".to_string())
+            }
+        };
+        self.hunk_explain_receiver = None;
+        self.hunk_explain_abort_handle = None;
+        if let Some(popup) = self.hunk_explain.as_mut() {
+            popup.explanation = match result {
+                Ok(text) => LoadState::Loaded(text),
+                Err(e) => LoadState::Error(e),
+            };
+        }
+    }
+
+    /// ポップアップ表示中のキー処理。表示中は全キーを消費し、閉じると解説も中断する。
+    pub(crate) fn handle_hunk_explain_input(&mut self, key: &KeyEvent) -> bool {
+        let Some(line_count) = self.hunk_explain.as_ref().map(|p| match &p.explanation {
+            LoadState::Loaded(text) => text.lines().count(),
+            _ => 1,
+        }) else {
+            return false;
+        };
+        let kb = &self.config.keybindings;
+        let close = key.code == KeyCode::Esc || self.matches_single_key(key, &kb.quit);
+        let down = key.code == KeyCode::Down || self.matches_single_key(key, &kb.move_down);
+        let up = key.code == KeyCode::Up || self.matches_single_key(key, &kb.move_up);
+
+        if close {
+            self.cancel_hunk_explain();
+            self.hunk_explain = None;
+        } else if let Some(popup) = self.hunk_explain.as_mut() {
+            if down {
+                popup.scroll = (popup.scroll + 1).min(line_count.saturating_sub(1));
+            } else if up {
+                popup.scroll = popup.scroll.saturating_sub(1);
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,355 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::ops::Range;
+use std::time::Instant;
+
+use crate::diff::{HunkMatch, LineEndingHunk};
+
+use super::types::HunkFilter;
+use super::App;
+
+impl App {
+    /// 現在のファイルに対する hunk フィルタ
+    pub fn current_hunk_filter(&self) -> Option<&HunkFilter> {
+        self.hunk_filter
+            .as_ref()
+            .filter(|f| f.file_index == self.selected_file)
+    }
+
+    /// hunk フィルタのクエリ入力を開く（既存クエリは引き継ぐ）
+    pub(crate) fn open_hunk_filter(&mut self) {
+        match self.hunk_filter {
+            Some(ref mut filter) if filter.file_index == self.selected_file => {
+                filter.input_active = true;
+                filter.enabled = true;
+            }
+            _ => self.hunk_filter = Some(HunkFilter::new(self.selected_file)),
+        }
+        self.reapply_hunk_filter();
+    }
+
+    /// クエリ入力中のキー処理。処理した場合は true を返す。
+    pub(crate) fn handle_hunk_filter_input(&mut self, key: &KeyEvent) -> bool {
+        let Some(filter) = self.hunk_filter.as_mut().filter(|f| f.input_active) else {
+            return false;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.hunk_filter = None;
+                return true;
+            }
+            KeyCode::Enter => {
+                if filter.query.is_empty() {
+                    self.hunk_filter = None;
+                } else {
+                    filter.input_active = false;
+                }
+                return true;
+            }
+            KeyCode::Backspace => {
+                filter.query.pop();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                filter.query.clear();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                filter.query.push(c);
+            }
+            _ => return true,
+        }
+        self.reapply_hunk_filter();
+        true
+    }
+
+    /// 全 hunk 表示とフィルタ表示を切り替える
+    pub(crate) fn toggle_hunk_filter(&mut self) {
+        let message = match self.hunk_filter.as_mut() {
+            Some(filter) if !filter.query.is_empty() => {
+                filter.enabled = !filter.enabled;
+                if filter.enabled {
+                    format!(
+                        "Hunk filter: ON ({}/{} hunks match \"{}\")",
+                        filter.matched_count(),
+                        filter.hunks.len(),
+                        filter.query
+                    )
+                } else {
+                    "Hunk filter: OFF (showing all hunks)".to_string()
+                }
+            }
+            _ => {
+                let kb = &self.config.keybindings;
+                format!("No hunk filter ({} to search hunks)", kb.filter.display())
+            }
+        };
+        self.cmt.submission_result = Some((true, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+        self.reapply_hunk_filter();
+    }
+
+    /// クエリからマッチする hunk を再計算し、選択行を表示中の hunk へ寄せる
+    pub(crate) fn reapply_hunk_filter(&mut self) {
+        let Some(filter) = self.hunk_filter.as_ref() else {
+            return;
+        };
+        let patch = self
+            .files()
+            .get(filter.file_index)
+            .and_then(|f| f.patch.as_deref())
+            .unwrap_or("");
+        let hunks = crate::diff::match_hunks(patch, &filter.query);
+        if let Some(ref mut filter) = self.hunk_filter {
+            filter.hunks = hunks;
+        }
+        let line = self.diff_scroll.selected_line;
+        self.snap_to_visible_hunk(line);
+    }
+
+    /// 改行コードのみの hunk を折りたたみ表示するか
+    pub fn line_endings_collapsed(&self) -> bool {
+        self.config.diff.collapse_line_endings && !self.line_endings_expanded
+    }
+
+    /// `line` を含む、1 行に畳んで表示する範囲
+    pub fn collapsed_lines_at(&self, line: usize) -> Option<CollapsedLines<'_>> {
+        if let Some(hunk) = self
+            .current_hunk_filter()
+            .and_then(|f| f.hidden_hunk_at(line))
+        {
+            return Some(CollapsedLines::FilteredHunk(hunk));
+        }
+        if let Some(body) = self
+            .current_folded_hunks()
+            .iter()
+            .find(|r| r.contains(&line))
+        {
+            return Some(CollapsedLines::FoldedHunk(body));
+        }
+        if let Some(fold) = self
+            .unchanged_fold_containing(line)
+            .filter(|fold| fold.start != line)
+        {
+            return Some(CollapsedLines::Unchanged(fold.start + 1..fold.end));
+        }
+        if !self.line_endings_collapsed() {
+            return None;
+        }
+        self.diff_store
+            .current
+            .as_ref()
+            .filter(|cache| cache.file_index == self.selected_file)?
+            .line_ending_hunks
+            .iter()
+            .find(|h| h.body.contains(&line))
+            .map(CollapsedLines::LineEndings)
+    }
+
+    /// 改行コードのみの hunk の折りたたみ / 展開を切り替える
+    pub(crate) fn toggle_line_endings(&mut self) {
+        let count = self
+            .diff_store
+            .current
+            .as_ref()
+            .filter(|cache| cache.file_index == self.selected_file)
+            .map_or(0, |cache| cache.line_ending_hunks.len());
+        let message = if count == 0 {
+            "No line-ending-only hunks in this file".to_string()
+        } else {
+            self.line_endings_expanded = !self.line_endings_expanded;
+            if self.line_endings_expanded {
+                format!("Line-ending-only hunks: expanded ({})", count)
+            } else {
+                format!("Line-ending-only hunks: collapsed ({})", count)
+            }
+        };
+        self.cmt.submission_result = Some((true, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+        let line = self.diff_scroll.selected_line;
+        self.snap_to_visible_hunk(line);
+    }
+
+    /// 変更行の間の長い文脈行を折りたたみ表示するか
+    pub fn unchanged_collapsed(&self) -> bool {
+        self.config.diff.collapse_unchanged != self.collapse_unchanged_toggled
+    }
+
+    /// `line` を含む、折りたたみ中の文脈行の範囲（先頭行がプレースホルダ）
+    fn unchanged_fold_containing(&self, line: usize) -> Option<Range<usize>> {
+        if !self.unchanged_collapsed() {
+            return None;
+        }
+        let cache = self
+            .diff_store
+            .current
+            .as_ref()
+            .filter(|cache| cache.file_index == self.selected_file)?;
+        let run = cache.context_runs.iter().find(|r| r.contains(&line))?;
+        let expanded = self
+            .files()
+            .get(self.selected_file)
+            .and_then(|f| self.expanded_unchanged.get(&f.filename));
+        if expanded.is_some_and(|starts| starts.contains(&run.start)) {
+            return None;
+        }
+        crate::diff::fold_context_run(run, self.config.diff.context_lines)
+            .filter(|fold| fold.contains(&line))
+    }
+
+    /// `line` がプレースホルダ行なら、それが表す（隠した）行数を返す
+    pub fn unchanged_placeholder_at(&self, line: usize) -> Option<usize> {
+        self.unchanged_fold_containing(line)
+            .filter(|fold| fold.start == line)
+            .map(|fold| fold.len())
+    }
+
+    /// カーソル行のプレースホルダをその場で展開する。展開した場合は true を返す。
+    pub(crate) fn expand_unchanged_at_cursor(&mut self) -> bool {
+        let line = self.diff_scroll.selected_line;
+        if self.unchanged_placeholder_at(line).is_none() {
+            return false;
+        }
+        let Some(run_start) = self.diff_store.current.as_ref().and_then(|cache| {
+            cache
+                .context_runs
+                .iter()
+                .find(|r| r.contains(&line))
+                .map(|r| r.start)
+        }) else {
+            return false;
+        };
+        let Some(file) = self.files().get(self.selected_file) else {
+            return false;
+        };
+        let filename = file.filename.clone();
+        self.expanded_unchanged
+            .entry(filename)
+            .or_default()
+            .push(run_start);
+        true
+    }
+
+    /// 長い文脈行の折りたたみを切り替える（個別に展開した連続も畳み直す）
+    pub(crate) fn toggle_unchanged(&mut self) {
+        self.collapse_unchanged_toggled = !self.collapse_unchanged_toggled;
+        self.expanded_unchanged.clear();
+        let message = if self.unchanged_collapsed() {
+            format!(
+                "Unchanged lines: collapsed (keeping {} around changes)",
+                self.config.diff.context_lines
+            )
+        } else {
+            "Unchanged lines: expanded".to_string()
+        };
+        self.cmt.submission_result = Some((true, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+        let line = self.diff_scroll.selected_line;
+        self.snap_to_visible_hunk(line);
+    }
+
+    /// 現在のファイルで畳んでいる hunk の本体行範囲
+    pub fn current_folded_hunks(&self) -> &[Range<usize>] {
+        self.files()
+            .get(self.selected_file)
+            .and_then(|f| self.folded_hunks.get(&f.filename))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// カーソル行を含む hunk の本体を畳む / 展開する（ヘッダ行は残す）
+    pub(crate) fn toggle_hunk_fold(&mut self) {
+        let line = self.diff_scroll.selected_line;
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        let filename = file.filename.clone();
+        let patch = file.patch.as_deref().unwrap_or("");
+        let headers = crate::diff::hunk_header_lines(patch);
+        let line_count = patch.lines().count();
+
+        let folds = self.folded_hunks.entry(filename).or_default();
+        // ヘッダ行はその hunk に属する
+        let message = if let Some(pos) = folds
+            .iter()
+            .position(|r| r.contains(&line) || r.start == line + 1)
+        {
+            let body = folds.remove(pos);
+            format!("Hunk expanded ({} lines)", body.len())
+        } else if let Some(&start) = headers.iter().rev().find(|&&h| h <= line) {
+            let end = headers
+                .iter()
+                .copied()
+                .find(|&h| h > start)
+                .unwrap_or(line_count);
+            if end <= start + 1 {
+                return;
+            }
+            folds.push(start + 1..end);
+            // 選択行をヘッダへ寄せる（もう一度押せば展開できる）
+            self.diff_scroll.selected_line = start;
+            self.diff_scroll.scroll_offset = self.diff_scroll.scroll_offset.min(start);
+            format!("Hunk collapsed ({} lines)", end - start - 1)
+        } else {
+            "No hunk under cursor".to_string()
+        };
+        self.cmt.submission_result = Some((true, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// `line` が畳まれた範囲にあれば、進行方向で最も近い表示行を返す（なければ逆方向）
+    fn nearest_visible_line(&self, line: usize, forward: bool) -> Option<usize> {
+        let line_count = self.diff_scroll.line_count;
+        let mut forward_hit = line;
+        while let Some(collapsed) = self.collapsed_lines_at(forward_hit) {
+            forward_hit = collapsed.range().end;
+        }
+        let forward_hit = (forward_hit < line_count).then_some(forward_hit);
+        let mut backward_hit = Some(line);
+        while let Some(collapsed) = backward_hit.and_then(|l| self.collapsed_lines_at(l)) {
+            backward_hit = collapsed.range().start.checked_sub(1);
+        }
+        if forward {
+            forward_hit.or(backward_hit)
+        } else {
+            backward_hit.or(forward_hit)
+        }
+    }
+
+    /// 選択行が畳まれた範囲に入った場合、移動方向の表示行へ移す
+    ///
+    /// `before` は移動前の選択行（移動方向の判定に使う）。
+    pub(crate) fn snap_to_visible_hunk(&mut self, before: usize) {
+        let line = self.diff_scroll.selected_line;
+        if self.collapsed_lines_at(line).is_none() {
+            return;
+        }
+        let Some(visible) = self.nearest_visible_line(line, line >= before) else {
+            return;
+        };
+        self.diff_scroll.selected_line = visible;
+        if self.diff_scroll.scroll_offset > visible {
+            self.diff_scroll.scroll_offset = visible;
+        }
+    }
+}
+
+/// diff 表示で 1 行に畳んで描画する行範囲
+pub enum CollapsedLines<'a> {
+    /// hunk フィルタに一致しなかった hunk（ヘッダごと隠す）
+    FilteredHunk(&'a HunkMatch),
+    /// 改行コードのみが変わった hunk の本体（ヘッダは残す）
+    LineEndings(&'a LineEndingHunk),
+    /// ユーザーが畳んだ hunk の本体（ヘッダに隠した行数を添える）
+    FoldedHunk(&'a Range<usize>),
+    /// 長い文脈行の連続（先頭のプレースホルダ行を除く）
+    Unchanged(Range<usize>),
+}
+
+impl CollapsedLines<'_> {
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::FilteredHunk(hunk) => hunk.range.clone(),
+            Self::LineEndings(hunk) => hunk.body.clone(),
+            Self::FoldedHunk(body) => (*body).clone(),
+            Self::Unchanged(range) => range.clone(),
+        }
+    }
+}
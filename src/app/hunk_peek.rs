@@ -0,0 +1,61 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use std::time::Instant;
+
+use crate::diff::side_by_side_hunk;
+
+use super::types::HunkPeekState;
+use super::App;
+
+impl App {
+    /// カーソル位置の hunk を左右比較ポップアップで開く
+    pub(crate) fn open_hunk_peek(&mut self) {
+        let hunk = self
+            .files()
+            .get(self.selected_file)
+            .and_then(|file| Some((file.filename.clone(), file.patch.as_deref()?)))
+            .and_then(|(filename, patch)| {
+                Some((
+                    filename,
+                    side_by_side_hunk(patch, self.diff_scroll.selected_line)?,
+                ))
+            });
+        match hunk {
+            Some((filename, hunk)) => {
+                self.hunk_peek = Some(HunkPeekState {
+                    filename,
+                    hunk,
+                    scroll: 0,
+                });
+            }
+            None => {
+                self.cmt.submission_result =
+                    Some((false, "Cursor is not inside a hunk".to_string()));
+                self.cmt.submission_result_time = Some(Instant::now());
+            }
+        }
+    }
+
+    /// ポップアップ表示中のキー処理。処理した場合は true を返す（表示中は全キーを消費する）。
+    pub(crate) fn handle_hunk_peek_input(&mut self, key: &KeyEvent) -> bool {
+        let Some(row_count) = self.hunk_peek.as_ref().map(|p| p.hunk.rows.len()) else {
+            return false;
+        };
+        let kb = &self.config.keybindings;
+        let close = key.code == KeyCode::Esc
+            || self.matches_single_key(key, &kb.quit)
+            || self.matches_single_key(key, &kb.peek_split_hunk);
+        let down = key.code == KeyCode::Down || self.matches_single_key(key, &kb.move_down);
+        let up = key.code == KeyCode::Up || self.matches_single_key(key, &kb.move_up);
+
+        if close {
+            self.hunk_peek = None;
+        } else if let Some(peek) = self.hunk_peek.as_mut() {
+            if down {
+                peek.scroll = (peek.scroll + 1).min(row_count.saturating_sub(1));
+            } else if up {
+                peek.scroll = peek.scroll.saturating_sub(1);
+            }
+        }
+        true
+    }
+}
@@ -6,7 +6,7 @@ use std::time::Instant;
 use tokio::sync::mpsc;
 
 use crate::filter::ListFilter;
-use crate::github::{self, ChangedFile};
+use crate::github::{self, ChangedFile, GithubFilePage};
 use crate::keybinding::{event_to_keybinding, SequenceMatch};
 
 use super::types::*;
@@ -18,7 +18,12 @@ impl App {
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<()> {
         if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                self.handle_mouse_event(mouse);
+                return Ok(());
+            }
+            if let Event::Key(key) = event {
                 // Only handle Press events to avoid double-execution when
                 // Kitty keyboard protocol reports Release/Repeat events.
                 if key.kind != KeyEventKind::Press {
@@ -87,6 +92,25 @@ impl App {
                         }
                         return Ok(());
                     }
+
+                    if self.review_picker.is_some() {
+                        if let Some(action) = self.handle_review_picker_key(&key) {
+                            self.submit_review(action, terminal).await?;
+                        }
+                        return Ok(());
+                    }
+
+                    if self.session_autosave.pending_restore.is_some() {
+                        let kb = &self.config.keybindings;
+                        if self.matches_single_key(&key, &kb.confirm_yes) {
+                            self.restore_review_session();
+                        } else if self.matches_single_key(&key, &kb.confirm_no)
+                            || key.code == KeyCode::Esc
+                        {
+                            self.discard_review_session();
+                        }
+                        return Ok(());
+                    }
                 }
 
                 {
@@ -101,9 +125,12 @@ impl App {
                             .is_some_and(|f| f.input_active)
                         || self.issue_state.as_ref().is_some_and(|s| {
                             s.issue_list_filter.as_ref().is_some_and(|f| f.input_active)
-                        });
+                        })
+                        || self.hunk_filter.as_ref().is_some_and(|f| f.input_active)
+                        || self.diff_base_input.is_some();
                     let has_modal = self.multiline_selection.is_some()
                         || self.symbol_popup.is_some()
+                        || self.reaction_picker.is_some()
                         || self
                             .git_ops_state
                             .as_ref()
@@ -119,9 +146,18 @@ impl App {
                             self.enter_shell_command_mode();
                             return Ok(());
                         }
+                        if self.matches_single_key(&key, &kb.reload_config) {
+                            self.reload_config();
+                            return Ok(());
+                        }
                     }
                 }
 
+                // 読み取り専用モードの書き込み操作はここで一括して拒否する
+                if self.deny_write_key(&key) {
+                    return Ok(());
+                }
+
                 match self.state {
                     AppState::PullRequestList => self.handle_pr_list_input(key).await?,
                     AppState::FileList => self.handle_file_list_input(key, terminal).await?,
@@ -139,6 +175,7 @@ impl App {
                     }
                     AppState::PrDescription => self.handle_pr_description_input(key, terminal)?,
                     AppState::ChecksList => self.handle_checks_list_input(key)?,
+                    AppState::SymbolDigest => self.handle_symbol_digest_input(key),
                     AppState::IssueList => self.handle_issue_list_input(key).await?,
                     AppState::IssueDetail => self.handle_issue_detail_input(key, terminal)?,
                     AppState::IssueCommentList => self.handle_issue_comment_list_input(key)?,
@@ -169,7 +206,10 @@ impl App {
     pub(crate) fn retry_load(&mut self) {
         if let Some(ref tx) = self.retry_sender {
             // Keep current data visible during background refresh
-            if !matches!(self.data_state, DataState::Loaded { .. }) {
+            if !matches!(
+                self.data_state,
+                DataState::Loaded { .. } | DataState::PartiallyLoaded { .. }
+            ) {
                 self.data_state = DataState::Loading;
             }
             let request = if self.local_mode {
@@ -191,6 +231,14 @@ impl App {
             return Ok(());
         }
 
+        if self.handle_file_glob_input(&key) {
+            return Ok(());
+        }
+
+        if self.handle_diff_base_input(&key) {
+            return Ok(());
+        }
+
         // Prevent stale selection when filter yields no results
         // Disable mark_viewed on directory rows in tree mode
         if !self.is_filter_selection_empty("file") {
@@ -238,9 +286,8 @@ impl App {
                 self.handle_filter_navigation("file", true);
             } else if tree_active {
                 self.file_tree_move_down();
-            } else if !self.files().is_empty() {
-                self.selected_file =
-                    (self.selected_file + 1).min(self.files().len().saturating_sub(1));
+            } else {
+                self.move_file_selection(1);
             }
             return Ok(());
         }
@@ -251,7 +298,7 @@ impl App {
             } else if tree_active {
                 self.file_tree_move_up();
             } else {
-                self.selected_file = self.selected_file.saturating_sub(1);
+                self.move_file_selection(-1);
             }
             return Ok(());
         }
@@ -262,9 +309,8 @@ impl App {
                 let step = page_step.max(1);
                 if tree_active {
                     self.file_tree_page_down(step);
-                } else if !self.files().is_empty() {
-                    self.selected_file =
-                        (self.selected_file + step).min(self.files().len().saturating_sub(1));
+                } else {
+                    self.move_file_selection(step as isize);
                 }
             }
             return Ok(());
@@ -277,7 +323,7 @@ impl App {
                 if tree_active {
                     self.file_tree_page_up(step);
                 } else {
-                    self.selected_file = self.selected_file.saturating_sub(step);
+                    self.move_file_selection(-(step as isize));
                 }
             }
             return Ok(());
@@ -302,6 +348,8 @@ impl App {
                         let mut filter = ListFilter::new();
                         let files = self.files();
                         filter.apply(files, |_file, _q| true);
+                        self.file_sort
+                            .sort_indices(files, &mut filter.matched_indices);
                         if let Some(idx) = filter.sync_selection() {
                             self.selected_file = idx;
                         }
@@ -310,13 +358,73 @@ impl App {
                     return Ok(());
                 }
 
+                if self.try_file_category_sequence(&kb) {
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.filter_files) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_file_glob_input();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.sort_files) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.cycle_file_sort();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.next_file_with_comments) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_file_with_comments(true);
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.prev_file_with_comments) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_file_with_comments(false);
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.open_all_files_in_editor) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_all_files_in_editor(terminal).await?;
+                    return Ok(());
+                }
+
+                if !self.local_mode
+                    && self.try_match_sequence(&kb.review_submit) == SequenceMatch::Full
+                {
+                    self.clear_pending_keys();
+                    self.open_review_picker();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.request_reviewer) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_pr_people_input(crate::github::PrPeopleRole::Reviewer);
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.add_assignee) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_pr_people_input(crate::github::PrPeopleRole::Assignee);
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.jump_to_base_pr) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_base_pr();
+                    return Ok(());
+                }
+
                 // gg: Jump to first (shared across tree/flat modes)
                 if self.try_match_sequence(&kb.jump_to_first) == SequenceMatch::Full {
                     self.clear_pending_keys();
                     if tree_active {
                         self.file_tree_jump_to_first();
                     } else {
-                        self.selected_file = 0;
+                        self.select_edge_file(false);
                     }
                     return Ok(());
                 }
@@ -325,7 +433,21 @@ impl App {
             } else {
                 let could_start_filter = self.key_could_match_sequence(&key, &kb.filter);
                 let could_start_gg = self.key_could_match_sequence(&key, &kb.jump_to_first);
-                if could_start_filter || could_start_gg {
+                let could_start_base_pr = self.key_could_match_sequence(&key, &kb.jump_to_base_pr)
+                    || self.key_could_match_sequence(&key, &kb.open_all_files_in_editor);
+                let could_start_category = self.key_could_start_file_category(&key, &kb)
+                    || self.key_could_match_sequence(&key, &kb.filter_files)
+                    || self.key_could_match_sequence(&key, &kb.sort_files)
+                    || self.key_could_match_sequence(&key, &kb.next_file_with_comments)
+                    || self.key_could_match_sequence(&key, &kb.prev_file_with_comments)
+                    || self.key_could_match_sequence(&key, &kb.review_submit)
+                    || self.key_could_match_sequence(&key, &kb.request_reviewer)
+                    || self.key_could_match_sequence(&key, &kb.add_assignee);
+                if could_start_filter
+                    || could_start_gg
+                    || could_start_base_pr
+                    || could_start_category
+                {
                     self.push_pending_key(kb_event);
                     return Ok(());
                 }
@@ -336,8 +458,8 @@ impl App {
         if self.matches_single_key(&key, &kb.jump_to_last) {
             if tree_active {
                 self.file_tree_jump_to_last();
-            } else if !self.files().is_empty() {
-                self.selected_file = self.files().len().saturating_sub(1);
+            } else {
+                self.select_edge_file(true);
             }
             return Ok(());
         }
@@ -353,6 +475,10 @@ impl App {
             if tree_active && self.file_tree_enter() {
                 return Ok(());
             }
+            // 「Generated (N)」の見出し行: グループを開閉する
+            if !tree_active && !has_filter && self.toggle_generated_files_if_selected() {
+                return Ok(());
+            }
             if !self.files().is_empty() {
                 self.enter_diff_from_file_list();
                 self.sync_diff_to_selected_file();
@@ -388,17 +514,19 @@ impl App {
             return Ok(());
         }
 
+        if self.matches_single_key(&key, &kb.set_diff_base) {
+            self.open_diff_base_input();
+            return Ok(());
+        }
+
         // In local mode, API calls (comment posting, etc.) are skipped by the orchestrator
         if self.matches_single_key(&key, &kb.ai_rally) {
             self.resume_or_start_ai_rally();
             return Ok(());
         }
 
-        // Open in browser (disabled in local mode)
-        if !self.local_mode && self.matches_single_key(&key, &kb.open_in_browser) {
-            if let Some(pr_number) = self.pr_number {
-                self.open_pr_in_browser(pr_number);
-            }
+        if self.matches_single_key(&key, &kb.open_in_browser) {
+            self.open_current_pr_in_browser();
             return Ok(());
         }
 
@@ -431,6 +559,16 @@ impl App {
             return Ok(());
         }
 
+        if self.matches_single_key(&key, &kb.symbol_digest) {
+            self.toggle_symbol_digest();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.toggle_pending_review) {
+            self.toggle_pending_review();
+            return Ok(());
+        }
+
         if self.matches_single_key(&key, &kb.help) {
             self.open_help(AppState::FileList);
             return Ok(());
@@ -471,15 +609,18 @@ impl App {
             return Ok(true);
         }
 
+        if self.matches_single_key(&key, &kb.set_diff_base) {
+            self.open_diff_base_input();
+            return Ok(true);
+        }
+
         if self.matches_single_key(&key, &kb.ai_rally) {
             self.resume_or_start_ai_rally();
             return Ok(true);
         }
 
-        if !self.local_mode && self.matches_single_key(&key, &kb.open_in_browser) {
-            if let Some(pr_number) = self.pr_number {
-                self.open_pr_in_browser(pr_number);
-            }
+        if self.matches_single_key(&key, &kb.open_in_browser) {
+            self.open_current_pr_in_browser();
             return Ok(true);
         }
 
@@ -495,6 +636,16 @@ impl App {
             return Ok(true);
         }
 
+        if self.matches_single_key(&key, &kb.symbol_digest) {
+            self.toggle_symbol_digest();
+            return Ok(true);
+        }
+
+        if self.matches_single_key(&key, &kb.toggle_pending_review) {
+            self.toggle_pending_review();
+            return Ok(true);
+        }
+
         if self.matches_single_key(&key, &kb.toggle_local_mode) {
             self.toggle_local_mode();
             return Ok(true);
@@ -516,6 +667,11 @@ impl App {
     }
     pub(crate) fn handle_mark_viewed_key(&mut self, key: event::KeyEvent) -> bool {
         if self.local_mode {
+            // local mode は GitHub に送らず、ローカルにのみ記録する
+            if self.matches_single_key(&key, &self.config.keybindings.mark_viewed) {
+                self.toggle_local_file_viewed();
+                return true;
+            }
             return false;
         }
 
@@ -586,7 +742,7 @@ impl App {
             return;
         };
 
-        let repo = self.repo.clone();
+        let github = self.github.clone();
         let (tx, rx) = mpsc::channel(1);
         self.mark_viewed_receiver = Some((pr_number, rx));
         let action_label = if set_viewed { "viewed" } else { "unviewed" };
@@ -601,7 +757,8 @@ impl App {
             let mut error = None;
 
             for path in paths {
-                let result = github::set_file_viewed(&repo, &pr_node_id, &path, set_viewed).await;
+                let result =
+                    github::set_file_viewed(&*github, &pr_node_id, &path, set_viewed).await;
                 match result {
                     Ok(()) => marked_paths.push(path),
                     Err(e) => {
@@ -656,20 +813,67 @@ impl App {
         self.cmt.discussion_comments = None;
         self.cmt.comments_loading = false;
         self.cmt.discussion_comments_loading = false;
-        self.chk.ci_status = None;
+        self.chk.check_counts = None;
         self.chk.ci_status_receiver = None;
         self.file_list_filter = None;
+        self.file_category_filter = None;
+        self.file_glob_filter = None;
         self.data_state = DataState::Loading;
         self.retry_load();
     }
 
+    /// PR を github.com で開く
+    ///
+    /// 読み込み済みの PR なら `html_url` を OS の opener で直接開き、
+    /// それ以外（一覧上の PR や古いキャッシュ）は `gh pr view --web` に任せる。
     pub(crate) fn open_pr_in_browser(&self, pr_number: u32) {
-        let repo = self.repo.clone();
-        tokio::spawn(async move {
-            let _ =
-                github::gh_command(&["pr", "view", &pr_number.to_string(), "-R", &repo, "--web"])
-                    .await;
-        });
+        if let Some(url) = self
+            .pr()
+            .filter(|pr| pr.number == pr_number)
+            .and_then(|pr| pr.html_url.as_deref())
+        {
+            Self::open_url_in_browser(url);
+            return;
+        }
+        Self::open_url_in_browser(&github::pr_url(
+            &github::github_host(),
+            &self.repo,
+            pr_number,
+        ));
+    }
+
+    /// 選択中ファイルの blame / 履歴ページを GitHub（Enterprise ではそのホスト）で開く
+    ///
+    /// local mode には GitHub 上の ref がないため、ステータスメッセージのみ表示する。
+    pub(crate) fn open_file_page_in_browser(&mut self, page: GithubFilePage) {
+        if self.local_mode {
+            self.cmt.submission_result = Some((
+                false,
+                format!("GitHub {} is unavailable in local mode", page.label()),
+            ));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        }
+        let Some(pr) = self.pr() else {
+            return;
+        };
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        // 削除されたファイルは head に存在しないため base 側で開く
+        let git_ref = if file.status == "removed" {
+            &pr.base.sha
+        } else {
+            &pr.head.sha
+        };
+        let url = github::file_page_url(
+            &github::github_host(),
+            &self.repo,
+            git_ref,
+            &file.filename,
+            page,
+        );
+        Self::open_url_in_browser(&url);
     }
 
     pub(crate) fn open_help(&mut self, from: AppState) {
@@ -677,6 +881,7 @@ impl App {
         self.state = AppState::Help;
         self.help_scroll_offset = 0;
         self.config_scroll_offset = 0;
+        self.help_filter = None;
     }
 
     pub(crate) fn open_checks_list(&mut self, pr_number: u32) {
@@ -693,8 +898,9 @@ impl App {
         let (tx, rx) = mpsc::channel(1);
         self.chk.checks_receiver = Some((pr_number, rx));
         let repo = self.repo.clone();
+        let github = self.github.clone();
         tokio::spawn(async move {
-            let result = github::fetch_pr_checks(&repo, pr_number)
+            let result = github::fetch_pr_checks(&*github, &repo, pr_number)
                 .await
                 .map_err(|e| e.to_string());
             let _ = tx.send(result).await;
@@ -780,16 +986,24 @@ impl App {
         Ok(())
     }
 
+    /// local mode では開く PR がないため、ステータスメッセージのみ表示する
+    pub(crate) fn open_current_pr_in_browser(&mut self) {
+        if self.local_mode {
+            self.cmt.submission_result =
+                Some((false, "No GitHub PR to open in local mode".to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        }
+        if let Some(pr_number) = self.pr_number {
+            self.open_pr_in_browser(pr_number);
+        }
+    }
+
     pub(crate) fn open_url_in_browser(url: &str) {
-        let url = url.to_string();
+        let (program, args) = browser_command(url, std::env::consts::OS);
         tokio::spawn(async move {
-            let opener = if cfg!(target_os = "macos") {
-                "open"
-            } else {
-                "xdg-open"
-            };
-            let _ = tokio::process::Command::new(opener)
-                .arg(&url)
+            let _ = tokio::process::Command::new(program)
+                .args(&args)
                 .output()
                 .await;
         });
@@ -839,7 +1053,7 @@ impl App {
         }
     }
 
-    fn with_file_tree(&mut self, f: impl FnOnce(&mut super::file_tree::FileTreeState)) {
+    pub(crate) fn with_file_tree(&mut self, f: impl FnOnce(&mut super::file_tree::FileTreeState)) {
         if let Some(ref mut tree) = self.file_tree_state {
             f(tree);
             if let Some(idx) = tree.selected_file_index() {
@@ -978,3 +1192,21 @@ impl App {
             .collect()
     }
 }
+
+/// URL をブラウザで開く OS ごとのコマンド（プログラムと引数）
+pub(crate) fn browser_command(url: &str, os: &str) -> (&'static str, Vec<String>) {
+    match os {
+        "macos" => ("open", vec![url.to_string()]),
+        // `start` の第 1 引数はウィンドウタイトルとして扱われるため空文字を渡す
+        "windows" => (
+            "cmd",
+            vec![
+                "/C".to_string(),
+                "start".to_string(),
+                String::new(),
+                url.to_string(),
+            ],
+        ),
+        _ => ("xdg-open", vec![url.to_string()]),
+    }
+}
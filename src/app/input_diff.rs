@@ -4,6 +4,7 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io::Stdout;
 
 use crate::filter::ListFilter;
+use crate::github::GithubFilePage;
 use crate::keybinding::{event_to_keybinding, SequenceMatch};
 
 use super::types::*;
@@ -20,6 +21,15 @@ impl App {
             return Ok(());
         }
 
+        if self.handle_file_glob_input(&key) {
+            self.sync_diff_to_selected_file();
+            return Ok(());
+        }
+
+        if self.handle_diff_base_input(&key) {
+            return Ok(());
+        }
+
         let kb = self.config.keybindings.clone();
         let has_filter = self.file_list_filter.is_some();
         let tree_active = self.is_file_tree_active();
@@ -49,9 +59,8 @@ impl App {
                 self.handle_filter_navigation("file", true);
             } else if tree_active {
                 self.file_tree_move_down();
-            } else if !self.files().is_empty() {
-                self.selected_file =
-                    (self.selected_file + 1).min(self.files().len().saturating_sub(1));
+            } else {
+                self.move_file_selection(1);
             }
             // File 行移動時のみ diff 同期（Dir 行ではスキップ → 直前ファイルの diff を維持）
             if !tree_active
@@ -70,8 +79,8 @@ impl App {
                 self.handle_filter_navigation("file", false);
             } else if tree_active {
                 self.file_tree_move_up();
-            } else if self.selected_file > 0 {
-                self.selected_file = self.selected_file.saturating_sub(1);
+            } else {
+                self.move_file_selection(-1);
             }
             if !tree_active
                 || self
@@ -90,9 +99,8 @@ impl App {
                 let step = page_step.max(1);
                 if tree_active {
                     self.file_tree_page_down(step);
-                } else if !self.files().is_empty() {
-                    self.selected_file =
-                        (self.selected_file + step).min(self.files().len().saturating_sub(1));
+                } else {
+                    self.move_file_selection(step as isize);
                 }
                 if !tree_active
                     || self
@@ -113,7 +121,7 @@ impl App {
                 if tree_active {
                     self.file_tree_page_up(step);
                 } else {
-                    self.selected_file = self.selected_file.saturating_sub(step);
+                    self.move_file_selection(-(step as isize));
                 }
                 if !tree_active
                     || self
@@ -154,6 +162,8 @@ impl App {
                         let mut filter = ListFilter::new();
                         let files = self.files();
                         filter.apply(files, |_file, _q| true);
+                        self.file_sort
+                            .sort_indices(files, &mut filter.matched_indices);
                         if let Some(idx) = filter.sync_selection() {
                             self.selected_file = idx;
                         }
@@ -162,12 +172,63 @@ impl App {
                     return Ok(());
                 }
 
+                if self.try_file_category_sequence(&kb) {
+                    self.sync_diff_to_selected_file();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.filter_files) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_file_glob_input();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.sort_files) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.cycle_file_sort();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.next_file_with_comments) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_file_with_comments(true);
+                    self.sync_diff_to_selected_file();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.prev_file_with_comments) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_file_with_comments(false);
+                    self.sync_diff_to_selected_file();
+                    return Ok(());
+                }
+
+                if !self.local_mode
+                    && self.try_match_sequence(&kb.review_submit) == SequenceMatch::Full
+                {
+                    self.clear_pending_keys();
+                    self.open_review_picker();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.request_reviewer) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_pr_people_input(crate::github::PrPeopleRole::Reviewer);
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.add_assignee) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_pr_people_input(crate::github::PrPeopleRole::Assignee);
+                    return Ok(());
+                }
+
                 if self.try_match_sequence(&kb.jump_to_first) == SequenceMatch::Full {
                     self.clear_pending_keys();
                     if tree_active {
                         self.file_tree_jump_to_first();
                     } else {
-                        self.selected_file = 0;
+                        self.select_edge_file(false);
                     }
                     self.sync_diff_to_selected_file();
                     return Ok(());
@@ -177,7 +238,15 @@ impl App {
             } else {
                 let could_start_filter = self.key_could_match_sequence(&key, &kb.filter);
                 let could_start_gg = self.key_could_match_sequence(&key, &kb.jump_to_first);
-                if could_start_filter || could_start_gg {
+                let could_start_category = self.key_could_start_file_category(&key, &kb)
+                    || self.key_could_match_sequence(&key, &kb.filter_files)
+                    || self.key_could_match_sequence(&key, &kb.sort_files)
+                    || self.key_could_match_sequence(&key, &kb.next_file_with_comments)
+                    || self.key_could_match_sequence(&key, &kb.prev_file_with_comments)
+                    || self.key_could_match_sequence(&key, &kb.review_submit)
+                    || self.key_could_match_sequence(&key, &kb.request_reviewer)
+                    || self.key_could_match_sequence(&key, &kb.add_assignee);
+                if could_start_filter || could_start_gg || could_start_category {
                     self.push_pending_key(kb_event);
                     return Ok(());
                 }
@@ -188,8 +257,8 @@ impl App {
         if self.matches_single_key(&key, &kb.jump_to_last) {
             if tree_active {
                 self.file_tree_jump_to_last();
-            } else if !self.files().is_empty() {
-                self.selected_file = self.files().len().saturating_sub(1);
+            } else {
+                self.select_edge_file(true);
             }
             self.sync_diff_to_selected_file();
             return Ok(());
@@ -204,6 +273,9 @@ impl App {
             if tree_active && self.file_tree_enter() {
                 return Ok(());
             }
+            if !tree_active && !has_filter && self.toggle_generated_files_if_selected() {
+                return Ok(());
+            }
             if !self.files().is_empty() {
                 self.state = AppState::SplitViewDiff;
             }
@@ -241,6 +313,30 @@ impl App {
             return Ok(());
         }
 
+        if self.handle_hunk_filter_input(&key) {
+            return Ok(());
+        }
+
+        if self.handle_diff_search_input(&key) {
+            return Ok(());
+        }
+
+        if self.handle_hunk_peek_input(&key) {
+            return Ok(());
+        }
+
+        if self.handle_hunk_explain_input(&key) {
+            return Ok(());
+        }
+
+        if self.handle_blame_popup_input(&key) {
+            return Ok(());
+        }
+
+        if self.handle_theme_picker_input(&key) {
+            return Ok(());
+        }
+
         if self.matches_single_key(&key, &self.config.keybindings.help) {
             let from = match variant {
                 DiffViewVariant::SplitPane => AppState::SplitViewDiff,
@@ -308,6 +404,25 @@ impl App {
                 return Ok(());
             }
 
+            // 選択範囲の permalink はシーケンスキーなので、選択中もここで受け付ける
+            self.check_sequence_timeout();
+            if let Some(kb_event) = event_to_keybinding(&key) {
+                if !self.pending_keys.is_empty() {
+                    self.push_pending_key(kb_event);
+                    let matched =
+                        self.try_match_sequence(&kb.yank_permalink) == SequenceMatch::Full;
+                    self.clear_pending_keys();
+                    if matched {
+                        self.yank_permalink();
+                    }
+                    return Ok(());
+                }
+                if self.key_could_match_sequence(&key, &kb.yank_permalink) {
+                    self.push_pending_key(kb_event);
+                    return Ok(());
+                }
+            }
+
             if self.matches_single_key(&key, &kb.comment) {
                 self.enter_multiline_comment_input();
                 return Ok(());
@@ -318,6 +433,16 @@ impl App {
                 return Ok(());
             }
 
+            if self.matches_single_key(&key, &kb.suggestion_in_editor) {
+                self.open_multiline_suggestion_editor(terminal).await?;
+                return Ok(());
+            }
+
+            if self.matches_single_key(&key, &kb.suggest_format) {
+                self.suggest_format(terminal).await?;
+                return Ok(());
+            }
+
             if self.matches_single_key(&key, &kb.quit) {
                 self.multiline_selection = None;
                 return Ok(());
@@ -327,6 +452,36 @@ impl App {
         }
 
         if self.cmt.comment_panel_open {
+            self.check_sequence_timeout();
+            if let Some(kb_event) = event_to_keybinding(&key) {
+                let fold_keys = [
+                    &kb.collapse_all_threads,
+                    &kb.expand_all_threads,
+                    &kb.toggle_thread,
+                ];
+                if !self.pending_keys.is_empty() {
+                    self.push_pending_key(kb_event);
+                    let matched = fold_keys
+                        .iter()
+                        .position(|seq| self.try_match_sequence(seq) == SequenceMatch::Full);
+                    self.clear_pending_keys();
+                    match matched {
+                        Some(0) => self.collapse_all_threads(),
+                        Some(1) => self.expand_all_threads(),
+                        Some(_) => self.toggle_selected_thread(),
+                        None => {}
+                    }
+                    return Ok(());
+                }
+                if fold_keys
+                    .iter()
+                    .any(|seq| self.key_could_match_sequence(&key, seq))
+                {
+                    self.push_pending_key(kb_event);
+                    return Ok(());
+                }
+            }
+
             if self.matches_single_key(&key, &kb.move_down) {
                 let max_scroll = self.max_comment_panel_scroll(term_h, term_w);
                 self.cmt.comment_panel_scroll = self
@@ -383,7 +538,7 @@ impl App {
 
             if self.matches_single_key(&key, &kb.tab_switch) {
                 if self.has_comment_at_current_line() {
-                    let count = self.get_comment_indices_at_current_line().len();
+                    let count = self.visible_inline_comment_indices().len();
                     if count > 1 && self.cmt.selected_inline_comment + 1 < count {
                         self.cmt.selected_inline_comment += 1;
                         self.cmt.comment_panel_scroll = self.comment_panel_offset_for(
@@ -397,7 +552,7 @@ impl App {
 
             if key.code == KeyCode::BackTab {
                 if self.has_comment_at_current_line() {
-                    let count = self.get_comment_indices_at_current_line().len();
+                    let count = self.visible_inline_comment_indices().len();
                     if count > 1 && self.cmt.selected_inline_comment > 0 {
                         self.cmt.selected_inline_comment -= 1;
                         self.cmt.comment_panel_scroll = self.comment_panel_offset_for(
@@ -463,6 +618,38 @@ impl App {
                     return Ok(());
                 }
 
+                if self.try_match_sequence(&kb.open_all_files_in_editor) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_all_files_in_editor(terminal).await?;
+                    return Ok(());
+                }
+
+                // local mode では GitHub の blame ページが無いためローカルの git blame を表示する
+                if self.local_mode && self.try_match_sequence(&kb.show_blame) == SequenceMatch::Full
+                {
+                    self.clear_pending_keys();
+                    self.open_blame_popup();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.open_blame_in_browser) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_file_page_in_browser(GithubFilePage::Blame);
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.open_history_in_browser) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_file_page_in_browser(GithubFilePage::History);
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.yank_permalink) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.yank_permalink();
+                    return Ok(());
+                }
+
                 if self.try_match_sequence(&kb.jump_to_first) == SequenceMatch::Full {
                     self.clear_pending_keys();
                     self.diff_scroll.selected_line = 0;
@@ -470,13 +657,113 @@ impl App {
                     return Ok(());
                 }
 
+                if self.try_match_sequence(&kb.filter) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_hunk_filter();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.ai_explain_hunk) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_hunk_explain();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.first_comment) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_first_comment();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.last_comment) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_last_comment();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.toggle_hunk) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_hunk_fold();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.toggle_wrap) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_diff_wrap();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.toggle_unchanged) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_unchanged();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.toggle_whitespace) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_whitespace();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.choose_theme) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_theme_picker();
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.next_change_block) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_change_block(true);
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.prev_change_block) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_change_block(false);
+                    return Ok(());
+                }
+
+                if self.try_match_sequence(&kb.next_conflict) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_next_conflict();
+                    return Ok(());
+                }
+
                 self.clear_pending_keys();
             } else {
                 let could_start_gd = self.key_could_match_sequence(&key, &kb.go_to_definition);
-                let could_start_gf = self.key_could_match_sequence(&key, &kb.go_to_file);
+                let could_start_gf = self.key_could_match_sequence(&key, &kb.go_to_file)
+                    || self.key_could_match_sequence(&key, &kb.open_all_files_in_editor);
                 let could_start_gg = self.key_could_match_sequence(&key, &kb.jump_to_first);
-
-                if could_start_gd || could_start_gf || could_start_gg {
+                let could_start_file_page = self
+                    .key_could_match_sequence(&key, &kb.open_blame_in_browser)
+                    || self.key_could_match_sequence(&key, &kb.open_history_in_browser)
+                    || self.key_could_match_sequence(&key, &kb.show_blame)
+                    || self.key_could_match_sequence(&key, &kb.yank_permalink);
+                let could_start_filter = self.key_could_match_sequence(&key, &kb.filter)
+                    || self.key_could_match_sequence(&key, &kb.ai_explain_hunk);
+                let could_start_comment_edge = self
+                    .key_could_match_sequence(&key, &kb.first_comment)
+                    || self.key_could_match_sequence(&key, &kb.last_comment);
+                let could_start_hunk_fold = self.key_could_match_sequence(&key, &kb.toggle_hunk)
+                    || self.key_could_match_sequence(&key, &kb.toggle_wrap)
+                    || self.key_could_match_sequence(&key, &kb.toggle_unchanged)
+                    || self.key_could_match_sequence(&key, &kb.toggle_whitespace)
+                    || self.key_could_match_sequence(&key, &kb.choose_theme);
+                let could_start_change_block = self
+                    .key_could_match_sequence(&key, &kb.next_change_block)
+                    || self.key_could_match_sequence(&key, &kb.prev_change_block)
+                    || self.key_could_match_sequence(&key, &kb.next_conflict);
+
+                if could_start_gd
+                    || could_start_gf
+                    || could_start_gg
+                    || could_start_file_page
+                    || could_start_filter
+                    || could_start_comment_edge
+                    || could_start_hunk_fold
+                    || could_start_change_block
+                {
                     self.push_pending_key(kb_event);
                     return Ok(());
                 }
@@ -590,13 +877,23 @@ impl App {
             return Ok(());
         }
 
+        if self.matches_single_key(&key, &kb.search_in_diff) {
+            self.open_diff_search();
+            return Ok(());
+        }
+
         if self.matches_single_key(&key, &kb.next_comment) {
-            self.jump_to_next_comment();
+            // 検索確定中は n / N で一致を巡回する
+            if !self.jump_to_search_match(true) {
+                self.jump_to_next_comment();
+            }
             return Ok(());
         }
 
         if self.matches_single_key(&key, &kb.prev_comment) {
-            self.jump_to_prev_comment();
+            if !self.jump_to_search_match(false) {
+                self.jump_to_prev_comment();
+            }
             return Ok(());
         }
 
@@ -606,6 +903,90 @@ impl App {
             return Ok(());
         }
 
+        if self.matches_single_key(&key, &kb.reload_current_file) {
+            self.reload_current_file();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.peek_split_hunk) {
+            self.open_hunk_peek();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.yank_line) {
+            self.yank(false);
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.yank_hunk) {
+            self.yank(true);
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.toggle_highlighting) {
+            self.toggle_highlighting();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.toggle_diff_layout) {
+            self.toggle_diff_layout();
+            return Ok(());
+        }
+
+        if self.diff_layout == DiffLayout::Split {
+            if self.matches_single_key(&key, &kb.tab_switch) {
+                self.toggle_split_focus();
+                return Ok(());
+            }
+            if self.matches_single_key(&key, &kb.scroll_left) {
+                self.scroll_split_column(false);
+                return Ok(());
+            }
+            if self.matches_single_key(&key, &kb.scroll_right) {
+                self.scroll_split_column(true);
+                return Ok(());
+            }
+        }
+
+        if self.matches_single_key(&key, &kb.toggle_hunk_filter) {
+            self.toggle_hunk_filter();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.toggle_line_endings) {
+            self.toggle_line_endings();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.expand_context) {
+            self.expand_context_at_cursor();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.reset_context) {
+            self.reset_context();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.toggle_pending_review) {
+            self.toggle_pending_review();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.next_change) {
+            self.jump_to_change(true);
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.prev_change) {
+            self.jump_to_change(false);
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.open_panel) && self.expand_unchanged_at_cursor() {
+            return Ok(());
+        }
+
         if self.try_open_comment_panel(&key, &kb) {
             return Ok(());
         }
@@ -654,16 +1035,22 @@ impl App {
         key: event::KeyEvent,
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<()> {
+        let before = self.diff_scroll.selected_line;
         self.handle_diff_input_common(key, terminal, DiffViewVariant::SplitPane)
-            .await
+            .await?;
+        self.snap_to_visible_hunk(before);
+        Ok(())
     }
     pub(crate) async fn handle_diff_view_input(
         &mut self,
         key: event::KeyEvent,
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<()> {
+        let before = self.diff_scroll.selected_line;
         self.handle_diff_input_common(key, terminal, DiffViewVariant::Fullscreen)
-            .await
+            .await?;
+        self.snap_to_visible_hunk(before);
+        Ok(())
     }
     pub(crate) fn adjust_scroll(&mut self, visible_lines: usize) {
         if visible_lines == 0 {
@@ -12,6 +12,7 @@ use crate::github::comment::ReviewComment;
 use crate::loader::CommentSubmitResult;
 use crate::ui::text_area::TextAreaAction;
 
+use super::read_only::WriteAction;
 use super::types::*;
 use super::{App, SuggestionHighlightCache};
 
@@ -32,6 +33,21 @@ impl App {
         match self.input_text_area.input(key) {
             TextAreaAction::Submit => {
                 let content = self.input_text_area.content();
+                if matches!(self.input_mode, Some(InputMode::PrListFilters)) {
+                    // 空のクエリは絞り込みの解除として扱う
+                    self.input_mode = None;
+                    self.input_text_area.clear();
+                    self.state = self.preview_return_state;
+                    self.apply_pr_list_filters(&content);
+                    return Ok(());
+                }
+                if let Some(InputMode::PrPeople(role)) = self.input_mode {
+                    self.input_mode = None;
+                    self.input_text_area.clear();
+                    self.state = self.preview_return_state;
+                    self.apply_pr_people(role, &content);
+                    return Ok(());
+                }
                 if content.trim().is_empty() {
                     // 空の場合はキャンセル扱い
                     self.cancel_input();
@@ -44,9 +60,9 @@ impl App {
                     }
                     Some(InputMode::Suggestion {
                         context,
-                        original_code: _,
+                        original_code,
                     }) => {
-                        self.submit_suggestion(context, content);
+                        self.submit_suggestion(context, &original_code, content);
                         self.suggestion_highlight_cache = None;
                     }
                     Some(InputMode::Reply { comment_id, .. }) => {
@@ -55,7 +71,7 @@ impl App {
                     Some(InputMode::IssueComment { issue_number }) => {
                         self.submit_issue_comment(issue_number, content);
                     }
-                    None => {}
+                    Some(InputMode::PrListFilters) | Some(InputMode::PrPeople(_)) | None => {}
                 }
                 self.state = self.preview_return_state;
             }
@@ -74,6 +90,20 @@ impl App {
         }
         Ok(())
     }
+    /// 入力中の本文がソフト上限（`comment_warn_length`）を超えていれば (文字数, 上限) を返す
+    pub fn comment_length_warning(&self) -> Option<(usize, usize)> {
+        let limit = self
+            .config
+            .comment_warn_length
+            .unwrap_or(crate::config::DEFAULT_COMMENT_WARN_LENGTH);
+        if limit == 0 {
+            return None;
+        }
+        let body = crate::editor::extract_comment_body(&self.input_text_area.content());
+        let len = body.trim().chars().count();
+        (len > limit).then_some((len, limit))
+    }
+
     pub(crate) fn cancel_input(&mut self) {
         self.input_mode = None;
         self.input_text_area.clear();
@@ -126,16 +156,38 @@ impl App {
         self.submit_review_comment_inner(ctx, body);
     }
 
-    pub(crate) fn submit_suggestion(&mut self, ctx: LineInputContext, suggested_code: String) {
-        let body = format!("```suggestion\n{}\n```", suggested_code.trim_end());
+    pub(crate) fn submit_suggestion(
+        &mut self,
+        ctx: LineInputContext,
+        original_code: &str,
+        suggested_code: String,
+    ) {
+        let suggested_code = reindent_suggestion(original_code, &suggested_code);
+        // CRLF のファイルでは suggestion のコードも CRLF で送り、適用時に改行コードを崩さない
+        let line_ending = self
+            .files()
+            .get(ctx.file_index)
+            .and_then(|file| file.patch.as_deref())
+            .map_or(crate::diff::LineEnding::Lf, crate::diff::LineEnding::detect);
+        let body = format!(
+            "```suggestion\n{}\n```",
+            line_ending.apply(suggested_code.trim_end())
+        );
         self.submit_review_comment_inner(ctx, body);
     }
 
     fn submit_review_comment_inner(&mut self, ctx: LineInputContext, body: String) {
+        if self.deny_write(WriteAction::Comment) {
+            return;
+        }
         if self.local_mode {
             self.submit_local_review_comment(ctx, body);
             return;
         }
+        if self.cmt.pending_review {
+            self.queue_pending_comment(ctx, body);
+            return;
+        }
 
         let Some(file) = self.files().get(ctx.file_index) else {
             return;
@@ -149,22 +201,26 @@ impl App {
         let repo = self.repo.clone();
         let pr_number = self.pr_number();
         let position = ctx.diff_position;
+        let side = ctx.side;
         let start_line = ctx.start_line_number;
         let end_line = ctx.line_number;
 
+        self.hold_in_flight_comment(&ctx, &body);
         let (tx, rx) = mpsc::channel(1);
         self.cmt.comment_submit_receiver = Some((pr_number, rx));
         self.cmt.comment_submitting = true;
 
+        let github = self.github.clone();
         tokio::spawn(async move {
             let result = if let Some(start) = start_line {
                 github::create_multiline_review_comment(
-                    &repo, pr_number, &commit_id, &filename, start, end_line, "RIGHT", &body,
+                    &*github, &repo, pr_number, &commit_id, &filename, start, end_line, "RIGHT",
+                    &body,
                 )
                 .await
             } else {
                 github::create_review_comment(
-                    &repo, pr_number, &commit_id, &filename, position, &body,
+                    &*github, &repo, pr_number, &commit_id, &filename, position, side, &body,
                 )
                 .await
             };
@@ -210,12 +266,16 @@ impl App {
             },
             created_at: Utc::now().to_rfc3339(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         }));
 
         self.persist_local_review_comments(comments, "Saved local comment");
     }
 
     pub(crate) fn submit_reply(&mut self, comment_id: u64, body: String) {
+        if self.deny_write(WriteAction::Reply) {
+            return;
+        }
         if self.local_mode {
             self.submit_local_reply(comment_id, body);
             return;
@@ -228,8 +288,10 @@ impl App {
         self.cmt.comment_submit_receiver = Some((pr_number, rx));
         self.cmt.comment_submitting = true;
 
+        let github = self.github.clone();
         tokio::spawn(async move {
-            let result = github::create_reply_comment(&repo, pr_number, comment_id, &body).await;
+            let result =
+                github::create_reply_comment(&*github, &repo, pr_number, comment_id, &body).await;
 
             let _ = tx
                 .send(match result {
@@ -284,6 +346,7 @@ impl App {
             },
             created_at: Utc::now().to_rfc3339(),
             in_reply_to_id: Some(comment_id),
+            reactions: Default::default(),
         }));
 
         self.persist_local_review_comments(comments, "Saved local reply");
@@ -302,6 +365,7 @@ impl App {
             self.cmt.submission_result_time = Some(Instant::now());
             return;
         }
+        self.record_comment_made();
 
         let (review_comments, meta) = super::comments::split_local_comments(comments);
         let cache_key = PrCacheKey {
@@ -332,7 +396,7 @@ impl App {
         self.cmt.submission_result_time = Some(Instant::now());
     }
 
-    fn local_comment_author() -> String {
+    pub(super) fn local_comment_author() -> String {
         std::env::var("USER")
             .or_else(|_| std::env::var("USERNAME"))
             .ok()
@@ -347,6 +411,9 @@ impl App {
     }
 
     pub(crate) fn submit_issue_comment(&mut self, issue_number: u32, body: String) {
+        if self.deny_write(WriteAction::IssueComment) {
+            return;
+        }
         let Some(ref mut state) = self.issue_state else {
             return;
         };
@@ -356,8 +423,9 @@ impl App {
         state.issue_comment_submitting = true;
 
         let repo = self.repo.clone();
+        let github = self.github.clone();
         tokio::spawn(async move {
-            let result = github::create_issue_comment(&repo, issue_number, &body).await;
+            let result = github::create_issue_comment(&*github, &repo, issue_number, &body).await;
             let _ = tx.send(result.map_err(|e| e.to_string())).await;
         });
     }
@@ -378,3 +446,77 @@ impl App {
         }
     }
 }
+
+/// 行頭の空白部分を返す
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// 空行を除いた行のうち、最も浅いインデントを返す
+fn min_indent(text: &str) -> Option<&str> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(leading_whitespace)
+        .min_by_key(|ws| ws.len())
+}
+
+/// suggestion 本文のインデントを元コードに合わせて正規化する
+///
+/// 編集中にインデントがずれても GitHub の suggestion が誤った空白で適用されないよう、
+/// 提案ブロックの最小共通インデントを取り除き、元コードの行頭空白を付け直す。
+/// ブロック内の相対的なインデントは維持し、空行は空のままにする。
+pub(crate) fn reindent_suggestion(original_code: &str, suggested_code: &str) -> String {
+    let (Some(target), Some(current)) = (min_indent(original_code), min_indent(suggested_code))
+    else {
+        return suggested_code.to_string();
+    };
+    if target == current {
+        return suggested_code.to_string();
+    }
+
+    suggested_code
+        .lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                let body = line.get(current.len()..).unwrap_or(line.trim_start());
+                format!("{}{}", target, body)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reindent_suggestion_restores_original_indentation() {
+        let original = "        let x = 1;";
+        let suggested = "    let x = 2;\n    if x > 1 {\n        x += 1;\n    }";
+        assert_eq!(
+            reindent_suggestion(original, suggested),
+            "        let x = 2;\n        if x > 1 {\n            x += 1;\n        }"
+        );
+    }
+
+    #[test]
+    fn test_reindent_suggestion_keeps_matching_and_blank_lines() {
+        let original = "    foo();\n    bar();";
+        assert_eq!(
+            reindent_suggestion(original, "    baz();\n\n    qux();"),
+            "    baz();\n\n    qux();"
+        );
+        assert_eq!(
+            reindent_suggestion(original, "baz();\n  \nqux();"),
+            "    baz();\n\n    qux();"
+        );
+    }
+
+    #[test]
+    fn test_reindent_suggestion_noop_for_empty_original() {
+        assert_eq!(reindent_suggestion("", "  x"), "  x");
+    }
+}
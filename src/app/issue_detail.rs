@@ -15,12 +15,7 @@ impl App {
     pub(crate) fn enter_pr_from_issue(&mut self, pr_number: u32, pr_repo: Option<&str>) {
         if let Some(repo) = pr_repo {
             // クロスリポPR: 別リポのPRデータをキャッシュに混ぜないためブラウザで開く
-            let repo = repo.to_string();
-            let pr_number_str = pr_number.to_string();
-            tokio::spawn(async move {
-                let _ =
-                    github::gh_command(&["pr", "view", &pr_number_str, "-R", &repo, "--web"]).await;
-            });
+            Self::open_url_in_browser(&github::pr_url(&github::github_host(), repo, pr_number));
         } else {
             self.issue_detail_return = true;
             self.select_pr(pr_number);
@@ -33,9 +33,10 @@ impl App {
 
         let repo = self.repo.clone();
         let filter = state.issue_list_state_filter;
+        let github = self.github.clone();
 
         tokio::spawn(async move {
-            let result = github::fetch_issue_list(&repo, filter, 20).await;
+            let result = github::fetch_issue_list(&*github, &repo, filter, 20).await;
             let _ = tx.send(result.map_err(|e| e.to_string())).await;
         });
     }
@@ -57,9 +58,11 @@ impl App {
 
         let repo = self.repo.clone();
         let filter = state.issue_list_state_filter;
+        let github = self.github.clone();
 
         tokio::spawn(async move {
-            let result = github::fetch_issue_list_with_offset(&repo, filter, offset, 20).await;
+            let result =
+                github::fetch_issue_list_with_offset(&*github, &repo, filter, offset, 20).await;
             let _ = tx.send(result.map_err(|e| e.to_string())).await;
         });
     }
@@ -86,16 +89,18 @@ impl App {
         let (detail_tx, detail_rx) = mpsc::channel(1);
         state.issue_detail_receiver = Some((issue_number, detail_rx));
         let repo = self.repo.clone();
+        let github = self.github.clone();
         tokio::spawn(async move {
-            let result = github::fetch_issue_detail(&repo, issue_number).await;
+            let result = github::fetch_issue_detail(&*github, &repo, issue_number).await;
             let _ = detail_tx.send(result.map_err(|e| e.to_string())).await;
         });
 
         let (prs_tx, prs_rx) = mpsc::channel(1);
         state.linked_prs_receiver = Some((issue_number, prs_rx));
         let repo = self.repo.clone();
+        let github = self.github.clone();
         tokio::spawn(async move {
-            let result = github::fetch_linked_prs(&repo, issue_number).await;
+            let result = github::fetch_linked_prs(&*github, &repo, issue_number).await;
             let _ = prs_tx.send(result.map_err(|e| e.to_string())).await;
         });
 
@@ -103,18 +108,11 @@ impl App {
     }
 
     pub(crate) fn open_issue_in_browser(&self, issue_number: u32) {
-        let repo = self.repo.clone();
-        tokio::spawn(async move {
-            let _ = github::gh_command(&[
-                "issue",
-                "view",
-                &issue_number.to_string(),
-                "-R",
-                &repo,
-                "--web",
-            ])
-            .await;
-        });
+        Self::open_url_in_browser(&github::issue_url(
+            &github::github_host(),
+            &self.repo,
+            issue_number,
+        ));
     }
 
     pub(crate) async fn handle_issue_list_input(&mut self, key: event::KeyEvent) -> Result<()> {
@@ -87,6 +87,7 @@ impl App {
             self.pr_number = Some(0);
             self.state = AppState::FileList;
             self.update_data_receiver_origin(0);
+            self.load_linguist_overrides();
 
             // SessionCache にローカルデータがあれば復元
             let cache_key = PrCacheKey {
@@ -100,6 +101,7 @@ impl App {
                 };
                 self.diff_scroll
                     .set_line_count(Self::calc_diff_line_count(&cached.files, 0));
+                self.refresh_review_marker_count();
                 self.start_prefetch_all_files();
             } else {
                 self.data_state = DataState::Loading;
@@ -118,13 +120,26 @@ impl App {
     fn reset_view_state(&mut self) {
         self.selected_file = 0;
         self.file_list_scroll_offset = 0;
+        self.generated_files_expanded = false;
+        self.generated_header_selected = false;
         self.diff_scroll.reset();
         self.diff_store.clear();
         self.file_list_filter = None;
+        self.file_category_filter = None;
+        self.file_glob_filter = None;
+        self.review_marker_count = 0;
+        self.diff_stat = DiffStat::default();
+        self.review_marker_count_partial = false;
         self.cmt.review_comments = None;
         self.cmt.local_comment_meta.clear();
+        self.linguist = Default::default();
+        self.highlight_memo.clear();
         self.cmt.discussion_comments = None;
         self.cmt.comment_receiver = None;
+        self.cmt.pr_review_threads.clear();
+        self.cmt.review_thread_receiver = None;
+        self.cmt.reaction_receiver = None;
+        self.cmt.own_reactions.clear();
         self.cmt.discussion_comment_receiver = None;
         self.cmt.comment_submit_receiver = None;
         self.mark_viewed_receiver = None;
@@ -157,6 +172,7 @@ impl App {
             };
             self.diff_scroll.line_count =
                 Self::calc_diff_line_count(&cached.files, self.selected_file);
+            self.refresh_review_marker_count();
             self.start_prefetch_all_files();
         } else {
             self.data_state = DataState::Loading;
@@ -0,0 +1,89 @@
+use std::time::Instant;
+
+use tracing::warn;
+
+use crate::cache::{load_local_viewed_files, save_local_viewed_files};
+
+use super::types::hash_string;
+use super::{App, DataState};
+
+impl App {
+    /// local mode の viewed 状態をディスクから読み込み、ファイル一覧へ反映する
+    pub(crate) fn load_local_viewed_files(&mut self) {
+        self.local_viewed_files =
+            match load_local_viewed_files(&self.repo, self.working_dir.as_deref()) {
+                Ok(files) => files,
+                Err(e) => {
+                    warn!("Failed to load local viewed files: {}", e);
+                    Default::default()
+                }
+            };
+        self.apply_local_viewed_state();
+    }
+
+    /// 保存済みの patch ハッシュと一致するファイルだけを viewed にする
+    ///
+    /// patch 未取得のファイルは保存状態をそのまま表示し、取得後に再判定する。
+    /// patch が変わったファイルは viewed を外し、保存内容からも削除する。
+    pub(crate) fn apply_local_viewed_state(&mut self) {
+        if !self.local_mode {
+            return;
+        }
+        let DataState::Loaded { files, .. } = &mut self.data_state else {
+            return;
+        };
+        let mut changed = false;
+        for file in files.iter_mut() {
+            let stored = self.local_viewed_files.get(&file.filename).copied();
+            file.viewed = match (stored, file.patch.as_deref()) {
+                (Some(hash), Some(patch)) if hash != hash_string(patch) => {
+                    self.local_viewed_files.remove(&file.filename);
+                    changed = true;
+                    false
+                }
+                (stored, _) => stored.is_some(),
+            };
+        }
+        if changed {
+            self.save_local_viewed_files();
+        }
+    }
+
+    /// 選択中ファイルの viewed を切り替える（local mode、GitHub には送らない）
+    pub(crate) fn toggle_local_file_viewed(&mut self) {
+        let selected = self.selected_file;
+        let DataState::Loaded { files, .. } = &mut self.data_state else {
+            return;
+        };
+        let Some(file) = files.get_mut(selected) else {
+            return;
+        };
+        let message = match file.patch.as_deref() {
+            None => "Diff not loaded yet".to_string(),
+            Some(patch) => {
+                file.viewed = !file.viewed;
+                if file.viewed {
+                    self.local_viewed_files
+                        .insert(file.filename.clone(), hash_string(patch));
+                    format!("Marked {} as viewed", file.filename)
+                } else {
+                    self.local_viewed_files.remove(&file.filename);
+                    format!("Marked {} as unviewed", file.filename)
+                }
+            }
+        };
+        self.save_local_viewed_files();
+        self.cmt.submission_result = Some((true, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    fn save_local_viewed_files(&self) {
+        if let Err(e) = save_local_viewed_files(
+            &self.repo,
+            self.working_dir.as_deref(),
+            &self.local_viewed_files,
+        ) {
+            warn!("Failed to save local viewed files: {}", e);
+        }
+    }
+}
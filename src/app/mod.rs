@@ -1,6 +1,7 @@
 use anyhow::Result;
 use smallvec::SmallVec;
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -11,6 +12,7 @@ use crate::ai::prompt_loader::PromptLoader;
 use crate::ai::Context as AiContext;
 use crate::cache::SessionCache;
 use crate::config::Config;
+use crate::diff::FileCategory;
 use crate::diff_store::{DiffCacheStore, DiffScrollState, ScrollMode, MAX_STORE_ENTRIES};
 use crate::filter::ListFilter;
 use crate::github;
@@ -21,27 +23,52 @@ use crate::ui::text_area::TextArea;
 use std::time::Instant;
 
 mod types;
+pub use auto_refresh::run_auto_refresh;
+pub use file_sort::FileSort;
+pub use generated_files::FileListRow;
+pub use hunk_filter::CollapsedLines;
 pub use types::{
-    hash_string, AiRallyState, AppState, CachedDiffLine, CachedShellLine, ChecksState,
-    CockpitMenuItem, CockpitState, CommentPosition, CommentState, CommentTab, CommentThread,
-    CommitLogState, DataState, DestructiveOp, DiffCache, FileStatus, GitOpsState, GitStatusEntry,
-    HelpTab, IndexEntry, InputMode, InternedSpan, IssueDetailFocus, IssueState, JumpLocation,
-    LeftPaneFocus, LineInputContext, LoadState, LogEntry, LogEventType, MultilineSelection,
-    PauseState, PendingGitOpsConfirm, PendingPostConfirmation, PermissionInfo, PrListState,
-    RefreshRequest, RepoSymbolSearchResult, ReviewAction, ShellCommandResult, ShellPhase,
-    ShellState, SimulationPreview, SimulationResult, SpanVec, SymbolPopupState, SymbolSearchState,
-    SymbolSearchUpdate, TreeRow, UndoAction, WatcherHandle,
+    hash_string, AiRallyState, AppState, AutoRefreshTarget, BlamePopupState, CachedDiffLine,
+    CachedShellLine, ChecksState, CockpitMenuItem, CockpitState, CommentAnchorCache,
+    CommentPosition, CommentState, CommentTab, CommentThread, CommitLogState, DataState,
+    DestructiveOp, DiffCache, DiffLayout, DiffSearch, DiffStat, FileStatus, GitOpsState,
+    GitStatusEntry, HelpTab, HunkExplainState, HunkFilter, HunkPeekState, IndexEntry, InputMode,
+    InternedSpan, IssueDetailFocus, IssueState, JumpLocation, LeftPaneFocus, LineInputContext,
+    LoadState, LogEntry, LogEventType, MultilineSelection, PauseState, PendingGitOpsConfirm,
+    PendingPostConfirmation, PermissionInfo, PrListState, ReactionPickerState, RefreshRequest,
+    RepoSymbolSearchResult, ReviewAction, ReviewPickerOutcome, ReviewPickerState, SharedDiffBase,
+    ShellCommandResult, ShellPhase, ShellState, SimulationPreview, SimulationResult, SpanVec,
+    SplitDiffScroll, SymbolDigestRow, SymbolDigestState, SymbolPopupState, SymbolSearchState,
+    SymbolSearchUpdate, ThemePickerState, TreeRow, UndoAction, WatcherHandle,
+    REVIEW_PICKER_ACTIONS,
 };
 // Internal-only types (not re-exported from crate::app)
 use types::MarkViewedResult;
 
 mod ai_rally;
+mod auto_refresh;
+mod blame;
+mod change_nav;
 mod cockpit;
+mod comment_threads;
 mod comments;
+mod config_reload;
+mod context_expand;
+mod diff_base;
 mod diff_cache;
+mod diff_layout;
+mod diff_search;
+mod file_comment_nav;
+mod file_sort;
 pub mod file_tree;
 mod filter;
+mod format_suggestion;
+mod generated_files;
 mod git_ops;
+mod hide_whitespace;
+mod hunk_explain;
+mod hunk_filter;
+mod hunk_peek;
 mod input;
 mod input_diff;
 mod input_text;
@@ -49,17 +76,37 @@ mod issue_detail;
 mod issue_list;
 mod key_sequence;
 mod local_mode;
+mod local_viewed;
+mod mouse;
+mod open_file;
+mod pending_review;
 mod polling;
 mod pr_list;
+mod pr_people;
+mod reactions;
+mod read_only;
+mod review_picker;
+mod review_timer;
+mod session;
 mod shell_command;
+mod stacked_pr;
 mod symbol;
+mod symbol_digest;
 #[cfg(test)]
 mod tests;
+mod theme_picker;
+mod thread_resolution;
+mod yank;
 
-const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+pub(crate) const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
 /// PR番号と紐づいたレシーバー（発信元PRを追跡してクロスPRキャッシュ汚染を防止）
 pub(crate) type PrReceiver<T> = Option<(u32, mpsc::Receiver<T>)>;
+/// base ブランチ名と、その branch を head とする PR 番号の解決結果
+type BasePrReceiver = Option<(String, mpsc::Receiver<Result<Option<u32>, String>>)>;
+/// 文脈行の取得元ファイル（filename, 展開対象の hunk ヘッダ行, 内容）
+type ContextSourceResult = (String, usize, Result<String, String>);
+type PrPeopleResult = (github::PrPeopleRole, Vec<String>, Result<(), String>);
 
 /// サジェスチョン入力のシンタックスハイライトキャッシュ
 ///
@@ -108,6 +155,71 @@ pub struct App {
     pub diff_scroll: DiffScrollState,
     /// 複数行選択モードの状態（None = 非選択モード）
     pub multiline_selection: Option<MultilineSelection>,
+    /// hunk フィルタ（None = 全 hunk 表示）
+    pub hunk_filter: Option<HunkFilter>,
+    /// diff 内検索（None = 検索なし）
+    pub diff_search: Option<DiffSearch>,
+    /// カーソル位置の hunk の左右比較ポップアップ
+    pub hunk_peek: Option<HunkPeekState>,
+    /// カーソル位置の hunk の AI 解説ポップアップ
+    pub hunk_explain: Option<HunkExplainState>,
+    /// `review_submit` の判定ピッカー
+    pub review_picker: Option<ReviewPickerState>,
+    /// コメント一覧のリアクションピッカー
+    pub reaction_picker: Option<ReactionPickerState>,
+    /// local mode の blame ポップアップ
+    pub blame_popup: Option<BlamePopupState>,
+    /// テーマ選択ポップアップ（`choose_theme`）
+    pub theme_picker: Option<ThemePickerState>,
+    blame_receiver: Option<mpsc::Receiver<Result<Vec<crate::blame::BlameLine>, String>>>,
+    blame_abort_handle: Option<AbortHandle>,
+    hunk_explain_receiver: Option<mpsc::Receiver<Result<String, String>>>,
+    hunk_explain_abort_handle: Option<AbortHandle>,
+    /// 変更ファイル一括オープンの件数警告を出した時刻（期限内の再押下で実行）
+    open_all_files_confirm_at: Option<Instant>,
+    /// local mode のバイナリ画像プレビュー（terminal graphics）
+    pub(crate) image_preview: crate::ui::image_preview::ImagePreviewState,
+    /// マウスのクリック位置を行に変換するための描画領域（設定 `mouse`）
+    pub(crate) mouse_layout: crate::ui::mouse::MouseLayout,
+    /// diff の表示レイアウト（unified / 左右分割）
+    pub diff_layout: DiffLayout,
+    /// 左右分割レイアウトの横スクロール
+    pub split_scroll: SplitDiffScroll,
+    /// unified diff の長い行を表示幅で折り返すか（`zw`）
+    pub diff_wrap: bool,
+    /// 改行コードのみの hunk を展開表示中か（ファイル切替でリセット）
+    pub line_endings_expanded: bool,
+    /// ファイルごとに畳んだ hunk の本体行範囲（ヘッダ行は表示したまま）
+    pub folded_hunks: HashMap<String, Vec<std::ops::Range<usize>>>,
+    /// `collapse_unchanged` の設定値を実行時に反転しているか（`zu`）
+    pub collapse_unchanged_toggled: bool,
+    /// ファイルごとに展開した文脈行の連続（連続の先頭行で識別する）
+    pub expanded_unchanged: HashMap<String, Vec<usize>>,
+    /// 表示用に書き換えた（文脈行の展開・空白差分の非表示）ファイルの元の patch
+    /// （filename -> patch。コメント位置の基準）
+    pub(crate) original_patches: HashMap<String, String>,
+    /// コメント入力中の位置プレビュー（毎フレーム patch を走査しないよう位置ごとに保持）
+    pub(crate) comment_anchor_cache: RefCell<Option<CommentAnchorCache>>,
+    /// `gd` で引くパッチ内の定義表（パッチが変わったら破棄し、次の `gd` で作り直す）
+    pub(crate) patch_symbol_table: Option<crate::symbol::PatchSymbolTable>,
+    /// 空白だけの変更を文脈行として表示するか（`zi`）
+    pub hide_whitespace: bool,
+    /// 空白差分の非表示を適用済みのファイル
+    pub(crate) whitespace_hidden: HashSet<String>,
+    /// 文脈行の取得元として読み込んだファイル内容（filename -> 新しい側の全文）
+    context_sources: HashMap<String, String>,
+    /// 取得中の文脈行の取得元ファイル
+    context_source_receiver: Option<mpsc::Receiver<ContextSourceResult>>,
+    /// 送信中のレビュアー依頼・担当者追加
+    pr_people_receiver: Option<mpsc::Receiver<PrPeopleResult>>,
+    /// local mode で viewed にしたファイル（filename -> viewed 時点の patch ハッシュ）
+    pub(crate) local_viewed_files: HashMap<String, u64>,
+    /// local mode で読み込んだ `.gitattributes` の linguist 属性（ハイライト言語の上書き）
+    pub(crate) linguist: crate::syntax::LinguistOverrides,
+    /// local mode: リフレッシュを跨いで再利用するハイライト済みキャッシュ
+    pub(crate) highlight_memo: crate::diff_store::HighlightMemo,
+    /// シンボルダイジェスト（変更シンボル一覧）ビューの状態
+    pub symbol_digest: Option<SymbolDigestState>,
     /// 統一入力モード
     pub input_mode: Option<InputMode>,
     /// 統一入力テキストエリア
@@ -122,6 +234,8 @@ pub struct App {
     pub help_tab: HelpTab,
     /// Config タブのスクロールオフセット（行単位）
     pub config_scroll_offset: usize,
+    /// Keybindings タブの絞り込み（アクション名・説明で一致）
+    pub help_filter: Option<ListFilter>,
     pub ai_rally_state: Option<AiRallyState>,
     pub working_dir: Option<String>,
     // Receivers
@@ -132,6 +246,8 @@ pub struct App {
     rally_abort_handle: Option<AbortHandle>,
     // Command sender to communicate with the orchestrator
     rally_command_sender: Option<mpsc::Sender<OrchestratorCommand>>,
+    // Result of posting the held-back rally comments (`commit_rally`)
+    rally_commit_receiver: Option<mpsc::Receiver<crate::ai::pending::CommitOutcome>>,
     // Context saved while waiting for config warning confirmation
     pending_rally_context: Option<AiContext>,
     // PromptLoader saved while waiting for config warning confirmation
@@ -142,12 +258,26 @@ pub struct App {
     start_ai_rally_on_load: bool,
     // Pending AI Rally flag (set when --ai-rally is passed with PR list mode)
     pending_ai_rally: bool,
+    // File to open directly when data is loaded (set by --file CLI flag)
+    open_file_on_load: Option<String>,
+    /// 前回この PR を離れたときの閲覧位置（初回ロード時に復元）
+    pending_review_position: Option<crate::cache::ReviewSessionState>,
+    /// `--offline`: ディスクキャッシュのみで閲覧（コメント・CI 状態を取得しない）
+    offline: bool,
+    /// GitHub との通信に使うバックエンド（バックグラウンドタスクには clone して渡す）
+    pub(crate) github: Arc<dyn github::GitHubBackend>,
+    /// 大きな PR の変更ファイル取得中の累計件数（ロード画面の進捗表示用）
+    pub(crate) files_load_progress: Option<usize>,
+    /// ディスクキャッシュの `Stale` データを表示中（`Fresh` 待ち）
+    pub(crate) showing_stale_data: bool,
     // File viewed-state mutation results
     mark_viewed_receiver: PrReceiver<MarkViewedResult>,
     /// Spinner animation frame counter (incremented each tick)
     pub spinner_frame: usize,
     /// ジャンプ履歴スタック（Go to Definition / Jump Back 用）
     pub jump_stack: Vec<JumpLocation>,
+    /// 表示中 diff の変更ブロック開始行（`]c`/`[c` 用）。(file_index, patch_hash, 開始行)
+    pub(crate) change_block_stops: Option<(usize, u64, Vec<usize>)>,
     /// Pending keys for multi-key sequences (e.g., "gg", "gd")
     pub pending_keys: SmallVec<[KeyBinding; 4]>,
     /// Timestamp when pending keys started (for timeout)
@@ -160,6 +290,8 @@ pub struct App {
     pub session_cache: SessionCache,
     /// Markdown リッチ表示モード（見出し太字・斜体等を適用）
     markdown_rich: bool,
+    /// シンタックスハイライトの有効/無効（無効時は +/- の色分けのみのプレーン表示）
+    highlighting_enabled: bool,
     /// サジェスチョン入力のシンタックスハイライトキャッシュ
     pub suggestion_highlight_cache: Option<SuggestionHighlightCache>,
     /// PR description 画面のスクロールオフセット
@@ -168,12 +300,38 @@ pub struct App {
     pub pr_description_cache: Option<DiffCache>,
     /// ファイル一覧のキーワードフィルタ
     pub file_list_filter: Option<ListFilter>,
+    /// ファイル一覧の変更種別フィルタ（`file_list_filter` がある間のみ有効）
+    pub file_category_filter: Option<FileCategory>,
+    /// ファイル一覧の glob フィルタ（`file_list_filter` がある間のみ有効）
+    pub file_glob_filter: Option<String>,
+    /// glob フィルタの入力中のパターン（入力バー表示中のみ Some）
+    pub file_glob_input: Option<String>,
+    /// フラットなファイル一覧の並び順
+    pub file_sort: FileSort,
+    /// 生成ファイルの「Generated (N)」グループを展開しているか
+    pub generated_files_expanded: bool,
+    /// フラットなファイル一覧で「Generated (N)」の見出し行を選択しているか
+    pub generated_header_selected: bool,
+    /// 差分の比較元リビジョン（`--base` / 入力バーで変更。retry ループと共有）
+    diff_base: SharedDiffBase,
+    /// 自動更新タイマーと共有する更新対象の PR 番号（0 なら更新しない）
+    auto_refresh_target: AutoRefreshTarget,
+    /// 比較元リビジョンの入力中の文字列（入力バー表示中のみ Some）
+    pub diff_base_input: Option<String>,
+    /// PR の追加行に含まれるレビューマーカー（TODO/FIXME 等）の数
+    pub review_marker_count: usize,
+    /// バッチ diff ロード中で未取得の patch が残っている（count は下限値）
+    pub review_marker_count_partial: bool,
+    /// ファイル一覧ヘッダの diffstat（パッチ更新時に再計算）
+    pub diff_stat: DiffStat,
     /// BG バッチ diff ロード結果の受信チャネル（Phase 2）
     batch_diff_receiver: Option<mpsc::Receiver<Vec<SingleFileDiffResult>>>,
     /// 単一ファイル diff のオンデマンド受信チャネル
     lazy_diff_receiver: Option<mpsc::Receiver<SingleFileDiffResult>>,
     /// 現在オンデマンドロード要求中のファイル名（重複リクエスト防止）
     lazy_diff_pending_file: Option<String>,
+    /// diff の取得が失敗・中断したファイル（`reload_current_file` で再取得できる）
+    incomplete_patches: HashSet<String>,
     pub chk: ChecksState,
     /// Git Log 画面の全状態（None = 非表示）
     /// GitOps 画面の全状態（None = 非表示）
@@ -185,6 +343,12 @@ pub struct App {
     /// Latest available version (None = not checked yet or up-to-date)
     pub update_available: Option<String>,
     update_check_receiver: Option<mpsc::Receiver<Option<String>>>,
+    /// stacked PR の親（base ブランチを head とする PR）解決結果の受信チャネル
+    base_pr_receiver: BasePrReceiver,
+    /// クラッシュ復旧用セッション自動保存の状態
+    pub(crate) session_autosave: session::SessionAutoSave,
+    /// レビュー時間・閲覧ファイル数などのセッション統計
+    pub(crate) review_stats: review_timer::ReviewStats,
     /// ファイル一覧のツリー表示モード ON/OFF
     pub tree_mode_active: bool,
     /// ファイルツリー状態（初回トグルで生成、展開状態を保持）
@@ -201,6 +365,10 @@ pub struct App {
 impl App {
     fn base_app(repo: String, config: Config) -> Self {
         let submit_key = config.keybindings.submit.clone();
+        let github = Arc::new(github::GhCliBackend::new(
+            github::GhRetryPolicy::from_config(&config.github),
+            false,
+        ));
         Self {
             repo,
             pr_number: Some(1),
@@ -223,6 +391,40 @@ impl App {
             file_list_scroll_offset: 0,
             diff_scroll: DiffScrollState::new(ScrollMode::Margin),
             multiline_selection: None,
+            hunk_filter: None,
+            diff_search: None,
+            hunk_peek: None,
+            hunk_explain: None,
+            review_picker: None,
+            reaction_picker: None,
+            blame_popup: None,
+            theme_picker: None,
+            open_all_files_confirm_at: None,
+            image_preview: crate::ui::image_preview::ImagePreviewState::new(),
+            mouse_layout: Default::default(),
+            blame_receiver: None,
+            blame_abort_handle: None,
+            hunk_explain_receiver: None,
+            hunk_explain_abort_handle: None,
+            diff_layout: DiffLayout::default(),
+            diff_wrap: false,
+            split_scroll: SplitDiffScroll::default(),
+            line_endings_expanded: false,
+            folded_hunks: HashMap::new(),
+            collapse_unchanged_toggled: false,
+            expanded_unchanged: HashMap::new(),
+            original_patches: HashMap::new(),
+            comment_anchor_cache: RefCell::new(None),
+            patch_symbol_table: None,
+            hide_whitespace: false,
+            whitespace_hidden: HashSet::new(),
+            context_sources: HashMap::new(),
+            context_source_receiver: None,
+            pr_people_receiver: None,
+            local_viewed_files: HashMap::new(),
+            linguist: Default::default(),
+            highlight_memo: Default::default(),
+            symbol_digest: None,
             input_mode: None,
             input_text_area: TextArea::with_submit_key(submit_key),
             config,
@@ -232,6 +434,7 @@ impl App {
             help_scroll_offset: 0,
             help_tab: HelpTab::default(),
             config_scroll_offset: 0,
+            help_filter: None,
             ai_rally_state: None,
             working_dir: None,
             data_receiver: None,
@@ -239,33 +442,58 @@ impl App {
             rally_event_receiver: None,
             rally_abort_handle: None,
             rally_command_sender: None,
+            rally_commit_receiver: None,
             pending_rally_context: None,
             pending_rally_prompt_loader: None,
             pending_rally_seed_review: None,
             start_ai_rally_on_load: false,
             pending_ai_rally: false,
+            open_file_on_load: None,
+            pending_review_position: None,
+            offline: false,
+            github,
+            files_load_progress: None,
+            showing_stale_data: false,
             mark_viewed_receiver: None,
             spinner_frame: 0,
             jump_stack: Vec::new(),
+            change_block_stops: None,
             pending_keys: SmallVec::new(),
             pending_since: None,
             symbol_popup: None,
             symbol_search: SymbolSearchState::Idle,
             session_cache: SessionCache::new(),
             markdown_rich: false,
+            highlighting_enabled: true,
             suggestion_highlight_cache: None,
             pr_description_scroll_offset: 0,
             pr_description_cache: None,
             file_list_filter: None,
+            file_category_filter: None,
+            file_glob_filter: None,
+            file_sort: FileSort::default(),
+            generated_files_expanded: false,
+            generated_header_selected: false,
+            file_glob_input: None,
+            diff_base: SharedDiffBase::default(),
+            auto_refresh_target: AutoRefreshTarget::default(),
+            diff_base_input: None,
+            review_marker_count: 0,
+            diff_stat: DiffStat::default(),
+            review_marker_count_partial: false,
             batch_diff_receiver: None,
             lazy_diff_receiver: None,
             lazy_diff_pending_file: None,
+            incomplete_patches: HashSet::new(),
             chk: ChecksState::default(),
             git_ops_state: None,
             issue_state: None,
             issue_detail_return: false,
             update_available: None,
             update_check_receiver: None,
+            base_pr_receiver: None,
+            session_autosave: session::SessionAutoSave::default(),
+            review_stats: review_timer::ReviewStats::default(),
             tree_mode_active: false,
             file_tree_state: None,
             shell_state: None,
@@ -288,6 +516,9 @@ impl App {
         app.original_pr_number = Some(pr_number);
         app.data_receiver = Some((pr_number, rx));
         app.zen_mode = app.config.layout.zen_mode;
+        if pr_number > 0 {
+            app.pending_review_position = session::load_closed_session(repo, pr_number);
+        }
         (app, tx)
     }
 
@@ -335,13 +566,21 @@ impl App {
     }
 
     pub async fn run(&mut self) -> Result<()> {
+        ui::set_mouse_capture(self.config.mouse);
         let mut terminal = ui::setup_terminal()?;
+        self.resume_review_timer();
 
         // データが既にロード済み（キャッシュヒット）の場合、プリフェッチを開始
         if matches!(self.data_state, DataState::Loaded { .. }) {
+            self.refresh_review_marker_count();
             self.start_prefetch_all_files();
         }
 
+        if matches!(self.data_state, DataState::Loaded { .. }) {
+            self.apply_review_position_on_load();
+            self.apply_open_file_on_load();
+        }
+
         // Start AI Rally immediately if flag is set and data is already loaded (from cache)
         if self.start_ai_rally_on_load && matches!(self.data_state, DataState::Loaded { .. }) {
             self.start_ai_rally_on_load = false;
@@ -350,9 +589,14 @@ impl App {
 
         while !self.should_quit {
             self.spinner_frame = self.spinner_frame.wrapping_add(1);
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                rally_state.tick();
+            }
             self.poll_pr_list_updates();
             self.poll_data_updates();
             self.poll_comment_updates();
+            self.poll_review_thread_updates();
+            self.poll_reaction_updates();
             self.poll_diff_cache_updates();
             self.poll_prefetch_updates();
             self.poll_batch_diff_updates();
@@ -361,6 +605,7 @@ impl App {
             self.poll_comment_submit_updates();
             self.poll_mark_viewed_updates();
             self.poll_rally_events();
+            self.poll_rally_commit();
             self.poll_checks_updates();
             self.poll_ci_status_updates();
             self.poll_git_ops_updates();
@@ -370,20 +615,30 @@ impl App {
             self.poll_cockpit_updates();
             self.poll_issue_comment_submit_updates();
             self.poll_update_check();
+            self.poll_base_pr_updates();
             self.poll_symbol_search_updates();
             self.poll_shell_result();
+            self.poll_blame_updates();
+            self.poll_hunk_explain_updates();
+            self.poll_context_source_updates();
+            self.poll_pr_people_updates();
+            self.sync_auto_refresh_target();
+            self.tick_session_autosave();
             if let SymbolSearchState::Ready(..) = &self.symbol_search {
                 if let Some(result) = self.symbol_search.take_ready() {
                     let full_path = std::path::Path::new(&result.repo_root).join(&result.file_path);
                     let path_str = full_path.to_string_lossy().to_string();
                     let line = result.line_number;
                     let editor = self.config.editor.clone();
+                    self.pause_review_timer();
                     ui::restore_terminal(&mut terminal)?;
                     let _ = crate::editor::open_file_at_line(editor.as_deref(), &path_str, line);
                     terminal = ui::setup_terminal()?;
+                    self.resume_review_timer();
                 }
             }
             terminal.draw(|frame| ui::render(frame, self))?;
+            ui::image_preview::sync(&mut terminal, self)?;
             self.handle_input(&mut terminal).await?;
         }
 
@@ -391,8 +646,12 @@ impl App {
         if let Some(handle) = self.rally_abort_handle.take() {
             handle.abort();
         }
+        self.close_review_session();
 
         ui::restore_terminal(&mut terminal)?;
+        if let Some(summary) = self.review_stats_summary() {
+            println!("{}", summary);
+        }
         Ok(())
     }
 
@@ -407,6 +666,9 @@ impl App {
 
     pub fn set_local_mode(&mut self, local: bool) {
         self.local_mode = local;
+        if local {
+            self.pending_review_position = None;
+        }
     }
 
     pub fn set_local_auto_focus(&mut self, enable: bool) {
@@ -425,6 +687,10 @@ impl App {
         self.markdown_rich
     }
 
+    pub fn is_highlighting_enabled(&self) -> bool {
+        self.highlighting_enabled
+    }
+
     pub(crate) fn toggle_zen_mode(&mut self) {
         self.zen_mode = !self.zen_mode;
         let msg = if self.zen_mode {
@@ -453,6 +719,18 @@ impl App {
         self.start_ai_rally_on_load = start;
     }
 
+    /// Enable offline browsing (used by --offline CLI flag)
+    ///
+    /// GitHub への接続自体は offline で作ったバックエンド（`set_github_backend`）が拒否する。
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    /// GitHub との通信に使うバックエンドを設定する（`[github] backend` から作ったもの）
+    pub fn set_github_backend(&mut self, github: Arc<dyn github::GitHubBackend>) {
+        self.github = github;
+    }
+
     /// Set pending AI Rally flag (for PR list mode with --ai-rally)
     pub fn set_pending_ai_rally(&mut self, pending: bool) {
         self.pending_ai_rally = pending;
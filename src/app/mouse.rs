@@ -0,0 +1,216 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+
+use crate::ui::mouse::{list_index_at, row_in};
+
+use super::types::{DataState, DiffLayout};
+use super::{App, AppState, CollapsedLines};
+
+/// ホイール 1 ノッチでスクロールする diff の行数
+const MOUSE_SCROLL_LINES: usize = 3;
+
+impl App {
+    /// マウス操作（設定 `mouse`）。ファイル一覧のクリックでファイルを選択し、
+    /// diff 上のホイールでスクロール、クリックでカーソルを移動する。
+    pub(crate) fn handle_mouse_event(&mut self, mouse: MouseEvent) {
+        if !self.config.mouse || self.mouse_input_blocked() {
+            return;
+        }
+        let (column, row) = (mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if !self.click_file_list(column, row) => {
+                self.click_diff(column, row);
+            }
+            MouseEventKind::ScrollDown => self.scroll_diff_with_wheel(column, row, true),
+            MouseEventKind::ScrollUp => self.scroll_diff_with_wheel(column, row, false),
+            _ => {}
+        }
+    }
+
+    /// 入力中・ポップアップ表示中などはマウス操作を無視する
+    fn mouse_input_blocked(&self) -> bool {
+        !matches!(
+            self.state,
+            AppState::FileList
+                | AppState::SplitViewFileList
+                | AppState::SplitViewDiff
+                | AppState::DiffView
+        ) || matches!(self.data_state, DataState::Loading | DataState::Error(_))
+            || self.shell_state.is_some()
+            || self.symbol_popup.is_some()
+            || self.hunk_peek.is_some()
+            || self.hunk_explain.is_some()
+            || self.blame_popup.is_some()
+            || self.theme_picker.is_some()
+            || self.review_picker.is_some()
+            || self.multiline_selection.is_some()
+            || self.file_glob_input.is_some()
+            || self.diff_base_input.is_some()
+            || self
+                .file_list_filter
+                .as_ref()
+                .is_some_and(|f| f.input_active)
+            || self.current_hunk_filter().is_some_and(|f| f.input_active)
+            || self.current_diff_search().is_some_and(|s| s.input_active)
+    }
+
+    /// ファイル一覧のクリック位置のファイルを選択する（一覧上のクリックなら true）
+    fn click_file_list(&mut self, column: u16, row: u16) -> bool {
+        let Some(area) = self.mouse_layout.file_list.get() else {
+            return false;
+        };
+        if row_in(area, column, row).is_none() {
+            return false;
+        }
+        self.clear_pending_keys();
+        if self.state == AppState::SplitViewDiff {
+            self.state = AppState::SplitViewFileList;
+        }
+
+        let before = self.selected_file;
+        if let Some(ref mut filter) = self.file_list_filter {
+            let Some(index) = list_index_at(
+                area,
+                self.file_list_scroll_offset,
+                filter.matched_indices.len(),
+                column,
+                row,
+            ) else {
+                return true;
+            };
+            filter.selected = Some(index);
+            self.selected_file = filter.matched_indices[index];
+        } else if self.is_file_tree_active() {
+            let Some(index) = self.file_tree_state.as_ref().and_then(|tree| {
+                list_index_at(area, tree.scroll_offset, tree.row_count(), column, row)
+            }) else {
+                return true;
+            };
+            self.with_file_tree(|tree| tree.selected_row = index);
+        } else {
+            let rows = self.file_list_rows();
+            let Some(index) =
+                list_index_at(area, self.file_list_scroll_offset, rows.len(), column, row)
+            else {
+                return true;
+            };
+            self.select_file_list_row(rows[index]);
+        }
+        if self.selected_file != before {
+            self.sync_diff_to_selected_file();
+        }
+        true
+    }
+
+    /// diff 上のクリック位置の行へカーソルを移す。
+    /// 行の位置を追えない左右分割レイアウト・折り返し表示では何もしない。
+    fn click_diff(&mut self, column: u16, row: u16) {
+        let Some(area) = self.mouse_layout.diff.get() else {
+            return;
+        };
+        let Some(row) = row_in(area, column, row) else {
+            return;
+        };
+        if self.state == AppState::SplitViewFileList {
+            if self.files().is_empty() {
+                return;
+            }
+            self.state = AppState::SplitViewDiff;
+        }
+        self.clear_pending_keys();
+        if self.diff_layout == DiffLayout::Split || self.diff_wrap {
+            return;
+        }
+        let Some(line_count) = self.diff_store.current.as_ref().map(|c| c.lines.len()) else {
+            return;
+        };
+        let visible_height = area.height.saturating_sub(2) as usize;
+        let start = self
+            .diff_scroll
+            .scroll_offset
+            .min(line_count.saturating_sub(visible_height));
+        if let Some(line) = self.diff_line_at_row(start, row, line_count) {
+            self.diff_scroll.selected_line = line;
+        }
+    }
+
+    /// 先頭を `start` 行目から描画した diff の、上から `row` 行目に表示される diff 行。
+    /// 描画（`render_diff_window`）と同じく畳んだ範囲を飛ばし、要約行の位置なら `None`。
+    pub(crate) fn diff_line_at_row(
+        &self,
+        start: usize,
+        row: usize,
+        line_count: usize,
+    ) -> Option<usize> {
+        let mut i = start;
+        let mut remaining = row;
+        while i < line_count {
+            let (line, next) = if self.unchanged_placeholder_at(i).is_some() {
+                (Some(i), i + 1)
+            } else {
+                match self.collapsed_lines_at(i) {
+                    Some(CollapsedLines::FilteredHunk(_)) => {
+                        let mut end = i;
+                        while let Some(CollapsedLines::FilteredHunk(h)) =
+                            self.collapsed_lines_at(end)
+                        {
+                            end = h.range.end;
+                        }
+                        (None, end)
+                    }
+                    Some(CollapsedLines::LineEndings(h)) => (None, h.body.end),
+                    // 行を描画しない範囲
+                    Some(CollapsedLines::FoldedHunk(body)) => {
+                        i = body.end;
+                        continue;
+                    }
+                    Some(CollapsedLines::Unchanged(range)) => {
+                        i = range.end;
+                        continue;
+                    }
+                    None => (Some(i), i + 1),
+                }
+            };
+            if remaining == 0 {
+                return line;
+            }
+            remaining -= 1;
+            i = next;
+        }
+        None
+    }
+
+    /// ホイールで diff の表示範囲をスクロールし、カーソルを表示範囲内に保つ
+    fn scroll_diff_with_wheel(&mut self, column: u16, row: u16, down: bool) {
+        let Some(area) = self
+            .mouse_layout
+            .diff
+            .get()
+            .filter(|&area| row_in(area, column, row).is_some())
+        else {
+            return;
+        };
+        self.scroll_diff_by_lines(area, down);
+    }
+
+    fn scroll_diff_by_lines(&mut self, area: Rect, down: bool) {
+        let visible_height = area.height.saturating_sub(2) as usize;
+        let line_count = self.diff_scroll.line_count;
+        if visible_height == 0 || line_count == 0 {
+            return;
+        }
+        let max_scroll = line_count.saturating_sub(visible_height);
+        let offset = self.diff_scroll.scroll_offset.min(max_scroll);
+        let offset = if down {
+            (offset + MOUSE_SCROLL_LINES).min(max_scroll)
+        } else {
+            offset.saturating_sub(MOUSE_SCROLL_LINES)
+        };
+        self.diff_scroll.scroll_offset = offset;
+
+        let before = self.diff_scroll.selected_line;
+        let last_visible = (offset + visible_height - 1).min(line_count - 1);
+        self.diff_scroll.selected_line = before.clamp(offset, last_visible);
+        self.snap_to_visible_hunk(before);
+    }
+}
@@ -0,0 +1,153 @@
+use std::path::{Component, Path, PathBuf};
+use std::time::Instant;
+
+use crate::config::find_project_root_in;
+
+use super::{App, AppState};
+
+/// `--file` で渡されたパスを変更ファイル一覧の repo 相対パスに正規化して照合する
+///
+/// 先頭の `./` や `a/../` を取り除き、絶対パスは `repo_root` からの相対パスに変換する。
+/// 相対パスはまず repo 相対として照合し、見つからなければ `cwd` からの相対パス
+/// （repo のサブディレクトリで実行した場合）として照合する。
+pub(crate) fn resolve_changed_file<'a>(
+    filenames: impl IntoIterator<Item = &'a str>,
+    path: &str,
+    repo_root: Option<&Path>,
+    cwd: Option<&Path>,
+) -> Option<usize> {
+    let filenames: Vec<&str> = filenames.into_iter().collect();
+    let path = Path::new(path);
+    let candidates = if path.is_absolute() {
+        vec![path.strip_prefix(repo_root?).ok()?.to_path_buf()]
+    } else {
+        let cwd_prefix = repo_root
+            .zip(cwd)
+            .and_then(|(root, cwd)| cwd.strip_prefix(root).ok())
+            .filter(|prefix| !prefix.as_os_str().is_empty());
+        std::iter::once(path.to_path_buf())
+            .chain(cwd_prefix.map(|prefix| prefix.join(path)))
+            .collect()
+    };
+
+    candidates
+        .iter()
+        .filter_map(|candidate| normalize_relative(candidate))
+        .find_map(|normalized| filenames.iter().position(|name| *name == normalized))
+}
+
+/// `./` や `a/../` を取り除いた `/` 区切りのパス（repo ルートより上に出るなら None）
+fn normalize_relative(path: &Path) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            _ => {}
+        }
+    }
+    Some(parts.join("/"))
+}
+
+impl App {
+    /// 起動時に開くファイルを設定する（`--file` CLI フラグ）
+    pub fn set_open_file_on_load(&mut self, path: String) {
+        self.open_file_on_load = Some(path);
+    }
+
+    /// データロード完了時に `--file` のファイルの diff を直接開く
+    pub(crate) fn apply_open_file_on_load(&mut self) {
+        let Some(path) = self.open_file_on_load.take() else {
+            return;
+        };
+        let cwd = std::env::current_dir().ok();
+        let dir: PathBuf = self
+            .working_dir
+            .clone()
+            .map(Into::into)
+            .or_else(|| cwd.clone())
+            .unwrap_or_else(|| ".".into());
+        let repo_root = find_project_root_in(&dir);
+
+        let found = resolve_changed_file(
+            self.files().iter().map(|f| f.filename.as_str()),
+            &path,
+            Some(&repo_root),
+            cwd.as_deref(),
+        );
+        let Some(index) = found else {
+            self.cmt.submission_result =
+                Some((false, format!("File '{}' is not part of the changes", path)));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        };
+
+        self.selected_file = index;
+        self.file_list_scroll_offset = self.file_list_scroll_offset.min(index);
+        self.diff_view_return_state = AppState::FileList;
+        self.state = AppState::DiffView;
+        self.sync_diff_to_selected_file();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_changed_file_normalizes_paths() {
+        let files = ["src/main.rs", "README.md"];
+        let root = Path::new("/work/repo");
+
+        assert_eq!(
+            resolve_changed_file(files, "src/main.rs", None, None),
+            Some(0)
+        );
+        assert_eq!(
+            resolve_changed_file(files, "./README.md", None, None),
+            Some(1)
+        );
+        assert_eq!(
+            resolve_changed_file(files, "src/../src/main.rs", None, None),
+            Some(0)
+        );
+        assert_eq!(
+            resolve_changed_file(files, "/work/repo/src/main.rs", Some(root), None),
+            Some(0)
+        );
+        // repo 外の絶対パスや変更に含まれないファイルは見つからない
+        assert_eq!(
+            resolve_changed_file(files, "/other/src/main.rs", Some(root), None),
+            None
+        );
+        assert_eq!(resolve_changed_file(files, "src/lib.rs", None, None), None);
+    }
+
+    #[test]
+    fn test_resolve_changed_file_relative_to_cwd_in_subdirectory() {
+        let files = ["src/main.rs", "README.md"];
+        let root = Path::new("/work/repo");
+        let src = Path::new("/work/repo/src");
+
+        assert_eq!(
+            resolve_changed_file(files, "main.rs", Some(root), Some(src)),
+            Some(0)
+        );
+        assert_eq!(
+            resolve_changed_file(files, "../README.md", Some(root), Some(src)),
+            Some(1)
+        );
+        // repo 相対として一致するならそちらを優先する
+        assert_eq!(
+            resolve_changed_file(files, "src/main.rs", Some(root), Some(src)),
+            Some(0)
+        );
+        // cwd が repo 外なら cwd 基準の照合はしない
+        assert_eq!(
+            resolve_changed_file(files, "main.rs", Some(root), Some(Path::new("/elsewhere"))),
+            None
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use std::time::Instant;
+
+use chrono::Utc;
+
+use crate::cache::{self, PendingReviewComment, PrCacheKey};
+use crate::diff::{relocate_comment_anchor, resolve_comment_anchor};
+use crate::github;
+use crate::github::comment::ReviewComment;
+use crate::github::DraftReviewComment;
+
+use super::types::LineInputContext;
+use super::App;
+
+/// pending コメントを表示用 `ReviewComment` に変換する際の ID（GitHub の ID と衝突しない上端から割り当てる）
+const PENDING_COMMENT_ID_BASE: u64 = u64::MAX;
+
+/// GitHub から取得したコメントと同一か（送信済みの pending コメントの二重表示防止）
+fn is_same_comment(pending: &PendingReviewComment, comment: &ReviewComment) -> bool {
+    comment.path == pending.path
+        && comment.line == Some(pending.line)
+        && comment.body.trim() == pending.body.trim()
+}
+
+impl App {
+    /// pending レビューモード（インラインコメントを即時送信せずレビュー送信時にまとめて送る）を切り替える
+    pub(crate) fn toggle_pending_review(&mut self) {
+        let message = if self.local_mode {
+            (
+                false,
+                "Pending review is unavailable in local mode".to_string(),
+            )
+        } else {
+            self.cmt.pending_review = !self.cmt.pending_review;
+            let count = self.cmt.pending_comments.len();
+            if self.cmt.pending_review {
+                (
+                    true,
+                    format!("Pending review: on ({} pending comments)", count),
+                )
+            } else {
+                (
+                    true,
+                    format!("Pending review: off ({} pending comments kept)", count),
+                )
+            }
+        };
+        self.cmt.submission_result = Some(message);
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// 表示中のコメントが未送信の pending コメントか
+    pub fn is_pending_comment(&self, comment_id: u64) -> bool {
+        self.cmt.pending_comment_ids.contains(&comment_id)
+    }
+
+    /// 現在の PR の pending コメントをキャッシュから読み込む
+    pub(crate) fn load_pending_comments(&mut self) {
+        let Some(pr_number) = self.pr_number else {
+            self.cmt.pending_comments.clear();
+            return;
+        };
+        self.cmt.pending_comments = match cache::load_pending_review_comments(&self.repo, pr_number)
+        {
+            Ok(comments) => comments,
+            Err(e) => {
+                tracing::warn!("Failed to load pending comments: {}", e);
+                Vec::new()
+            }
+        };
+        // 送信中に終了したコメントは送れたか分からないため pending に戻す
+        // （GitHub 側に届いていれば merge_pending_comments で取り除かれる）
+        if self.cmt.in_flight_comment.is_none() {
+            match cache::load_in_flight_comment(&self.repo, pr_number) {
+                Ok(Some(comment)) => {
+                    if !self.cmt.pending_comments.contains(&comment) {
+                        self.cmt.pending_comments.push(comment);
+                    }
+                    if self.save_pending_comments() {
+                        self.clear_in_flight_slot(pr_number);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to load in-flight comment: {}", e),
+            }
+        }
+    }
+
+    fn save_pending_comments(&mut self) -> bool {
+        let Some(pr_number) = self.pr_number else {
+            return false;
+        };
+        if let Err(e) =
+            cache::save_pending_review_comments(&self.repo, pr_number, &self.cmt.pending_comments)
+        {
+            self.cmt.submission_result =
+                Some((false, format!("Failed to save pending comments: {}", e)));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return false;
+        }
+        true
+    }
+
+    /// GitHub から取得したコメントに pending コメントを追加する
+    ///
+    /// 既に GitHub 側に存在する pending コメント（送信後に控えの削除が間に合わなかった等）は
+    /// 控えから取り除き、二重に表示しない。
+    pub(crate) fn merge_pending_comments(
+        &mut self,
+        mut comments: Vec<ReviewComment>,
+    ) -> Vec<ReviewComment> {
+        self.cmt.pending_comment_ids.clear();
+        if self.local_mode {
+            return comments;
+        }
+
+        let before = self.cmt.pending_comments.len();
+        self.cmt
+            .pending_comments
+            .retain(|pending| !comments.iter().any(|c| is_same_comment(pending, c)));
+        if self.cmt.pending_comments.len() != before {
+            let _ = self.save_pending_comments();
+        }
+
+        let author = Self::local_comment_author();
+        for (i, pending) in self.cmt.pending_comments.iter().enumerate() {
+            let id = PENDING_COMMENT_ID_BASE - i as u64;
+            self.cmt.pending_comment_ids.insert(id);
+            comments.push(ReviewComment {
+                id,
+                path: pending.path.clone(),
+                line: Some(pending.line),
+                start_line: pending.start_line,
+                body: pending.body.clone(),
+                user: github::User {
+                    login: author.clone(),
+                },
+                created_at: pending.created_at.clone(),
+                in_reply_to_id: None,
+                reactions: Default::default(),
+            });
+        }
+        comments
+    }
+
+    /// 表示中のコメントから pending 分を除き、最新の pending 一覧で再構築する
+    fn refresh_pending_comments(&mut self) {
+        let submitted: Vec<ReviewComment> = self
+            .cmt
+            .review_comments
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|c| !self.cmt.pending_comment_ids.contains(&c.id))
+            .collect();
+        self.apply_review_comments(submitted);
+        self.update_file_comment_positions();
+        if tokio::runtime::Handle::try_current().is_ok() {
+            self.ensure_diff_cache();
+        }
+    }
+
+    fn pending_from_context(
+        &self,
+        ctx: &LineInputContext,
+        body: String,
+    ) -> Option<PendingReviewComment> {
+        let file = self.files().get(ctx.file_index)?;
+        let anchor_text = file
+            .patch
+            .as_deref()
+            .and_then(|patch| resolve_comment_anchor(patch, ctx.diff_position))
+            .map(|anchor| anchor.content);
+        Some(PendingReviewComment {
+            path: file.filename.clone(),
+            line: ctx.line_number,
+            diff_position: ctx.diff_position,
+            start_line: ctx.start_line_number,
+            side: ctx.side,
+            body,
+            created_at: Utc::now().to_rfc3339(),
+            anchor_text,
+        })
+    }
+
+    /// インラインコメントを pending として保存する（GitHub へは送信しない）
+    pub(crate) fn queue_pending_comment(&mut self, ctx: LineInputContext, body: String) {
+        let Some(pending) = self.pending_from_context(&ctx, body) else {
+            return;
+        };
+        self.cmt.pending_comments.push(pending);
+        let saved = self.save_pending_comments();
+        self.refresh_pending_comments();
+        if saved {
+            self.record_comment_made();
+            self.cmt.submission_result = Some((
+                true,
+                format!(
+                    "Saved as pending ({} pending comments)",
+                    self.cmt.pending_comments.len()
+                ),
+            ));
+            self.cmt.submission_result_time = Some(Instant::now());
+        }
+    }
+
+    /// 即時送信するコメントを、送信が終わるまで pending とは別の控えに保存しておく
+    ///
+    /// 送信中は pending 一覧にもまとめ送信の対象にも含めない。送信中に終了しても
+    /// 次回起動時に pending として復元され、送信に失敗した場合も pending に移して本文を失わない。
+    pub(crate) fn hold_in_flight_comment(&mut self, ctx: &LineInputContext, body: &str) {
+        let Some(pending) = self.pending_from_context(ctx, body.to_string()) else {
+            return;
+        };
+        if let Err(e) = cache::save_in_flight_comment(&self.repo, self.pr_number(), Some(&pending))
+        {
+            tracing::warn!("Failed to save in-flight comment: {}", e);
+        }
+        self.cmt.in_flight_comment = Some(pending);
+    }
+
+    fn clear_in_flight_slot(&self, pr_number: u32) {
+        if let Err(e) = cache::save_in_flight_comment(&self.repo, pr_number, None) {
+            tracing::warn!("Failed to clear in-flight comment: {}", e);
+        }
+    }
+
+    /// 送信できたコメントの控えを `origin_pr` から取り除く
+    pub(crate) fn release_in_flight_comment(&mut self, origin_pr: u32) {
+        if self.cmt.in_flight_comment.take().is_some() {
+            self.clear_in_flight_slot(origin_pr);
+        }
+    }
+
+    /// 送信に失敗したコメントを `origin_pr` の pending に移す
+    pub(crate) fn keep_failed_comment_pending(&mut self, origin_pr: u32) -> bool {
+        let Some(failed) = self.cmt.in_flight_comment.take() else {
+            return false;
+        };
+        if self.pr_number == Some(origin_pr) {
+            self.cmt.pending_comments.push(failed);
+            if !self.save_pending_comments() {
+                return true;
+            }
+            if self.cmt.review_comments.is_some() {
+                self.refresh_pending_comments();
+            }
+        } else {
+            // 送信中に別の PR へ切り替えた場合は、元の PR の pending を直接書き換える
+            let result = cache::load_pending_review_comments(&self.repo, origin_pr).and_then(
+                |mut comments| {
+                    comments.push(failed);
+                    cache::save_pending_review_comments(&self.repo, origin_pr, &comments)
+                },
+            );
+            if let Err(e) = result {
+                tracing::warn!("Failed to update pending comments: {}", e);
+                return true;
+            }
+        }
+        self.clear_in_flight_slot(origin_pr);
+        true
+    }
+
+    /// pending コメントを、レビューと同時に送る `comments[]` に変換する
+    ///
+    /// 作成後に force-push 等で patch が変わったコメントは、同じ内容の行へ付け直す。
+    /// 付け直せないもの（行が消えた・内容が変わった）は送らずに pending に残す。
+    /// 戻り値は送るコメントと、その pending 内の添字。
+    pub(crate) fn pending_review_drafts(&self) -> (Vec<DraftReviewComment>, Vec<usize>) {
+        if self.local_mode {
+            return (Vec::new(), Vec::new());
+        }
+        let files = self.files();
+        self.cmt
+            .pending_comments
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pending)| {
+                let patch = files
+                    .iter()
+                    .find(|f| f.filename == pending.path)?
+                    .patch
+                    .as_deref()?;
+                let (_, line) = relocate_comment_anchor(
+                    patch,
+                    pending.line,
+                    pending.side,
+                    pending.anchor_text.as_deref(),
+                )?;
+                // 複数行コメントは開始行も同じだけずらし、diff 内に残っているか確かめる
+                let start_line = match pending.start_line {
+                    Some(start) => {
+                        let start = (start + line).checked_sub(pending.line)?;
+                        relocate_comment_anchor(patch, start, pending.side, None)?;
+                        Some(start)
+                    }
+                    None => None,
+                };
+                let draft = DraftReviewComment {
+                    path: pending.path.clone(),
+                    line,
+                    start_line,
+                    side: pending.side,
+                    body: pending.body.clone(),
+                };
+                Some((draft, i))
+            })
+            .unzip()
+    }
+
+    /// レビューと一緒に送った pending コメントを控えから取り除く
+    pub(crate) fn remove_sent_pending_comments(&mut self, sent: &[usize]) {
+        let mut index = 0;
+        self.cmt.pending_comments.retain(|_| {
+            let keep = !sent.contains(&index);
+            index += 1;
+            keep
+        });
+        self.clear_sent_pending_comments(sent.len());
+    }
+
+    /// 送信後の後始末: 控えを保存し直し、GitHub からコメントを取り直す
+    pub(crate) fn clear_sent_pending_comments(&mut self, sent: usize) {
+        if sent == 0 {
+            return;
+        }
+        let _ = self.save_pending_comments();
+        let cache_key = PrCacheKey {
+            repo: self.repo.clone(),
+            pr_number: self.pr_number(),
+        };
+        self.session_cache.remove_review_comments(&cache_key);
+        if tokio::runtime::Handle::try_current().is_ok() {
+            self.cmt.review_comments = None;
+            self.load_review_comments();
+        } else {
+            self.refresh_pending_comments();
+        }
+    }
+}
@@ -7,9 +7,10 @@ use crate::ai::orchestrator::RallyEvent;
 use crate::ai::RallyState;
 use crate::cache::{PrCacheKey, PrData};
 use crate::diff_store::{PrefetchItem, MAX_PREFETCH_FILES};
-use crate::github::{ChangedFile, CiStatus};
-use crate::loader::{CommentSubmitResult, DataLoadResult};
+use crate::github::ChangedFile;
+use crate::loader::{CommentSubmitResult, DataLoadResult, DataRevision};
 
+use super::pr_list::append_pr_page;
 use super::types::*;
 use super::{App, DataState};
 
@@ -21,15 +22,17 @@ impl App {
 
         match rx.try_recv() {
             Ok(Ok(page)) => {
-                if self.prs.pr_list_scroll_offset == 0 && self.prs.selected_pr == 0 {
-                    self.prs.pr_list = LoadState::Loaded(page.items);
-                } else if let Some(existing) = self.prs.pr_list.as_loaded_mut() {
-                    existing.extend(page.items);
-                    // Transition LoadingMore -> Loaded
-                    let items = std::mem::take(existing);
-                    self.prs.pr_list = LoadState::Loaded(items);
-                } else {
-                    self.prs.pr_list = LoadState::Loaded(page.items);
+                let fetched = page.items.len() as u32;
+                match std::mem::take(&mut self.prs.pr_list) {
+                    LoadState::LoadingMore(mut existing) => {
+                        append_pr_page(&mut existing, page.items);
+                        self.prs.pr_list = LoadState::Loaded(existing);
+                        self.prs.pr_list_offset += fetched;
+                    }
+                    _ => {
+                        self.prs.pr_list = LoadState::Loaded(page.items);
+                        self.prs.pr_list_offset = fetched;
+                    }
                 }
                 self.prs.pr_list_has_more = page.has_more;
                 self.prs.pr_list_receiver = None;
@@ -62,19 +65,54 @@ impl App {
             return;
         };
 
-        match rx.try_recv() {
+        let mut next = rx.try_recv();
+        // ストリーミング中の patch は溜まっている分をまとめて反映する
+        let mut streamed_patch = false;
+        while let Ok(DataLoadResult::FilePatch {
+            pr_number,
+            filename,
+            patch,
+        }) = next
+        {
+            if Some(pr_number) == self.pr_number {
+                streamed_patch |= self.apply_streamed_patch(filename, patch);
+            }
+            next = match self.data_receiver.as_mut() {
+                Some((_, rx)) => rx.try_recv(),
+                None => return,
+            };
+        }
+        if streamed_patch {
+            self.refresh_review_marker_count();
+        }
+
+        match next {
+            Ok(DataLoadResult::Progress {
+                pr_number,
+                files_loaded,
+            }) => {
+                if Some(pr_number) == self.pr_number
+                    && matches!(self.data_state, DataState::Loading)
+                {
+                    self.files_load_progress = Some(files_loaded);
+                }
+            }
             Ok(result) => {
                 // メッセージ自体から発信元PR番号を取得（mutable な origin_pr に依存しない）
                 let source_pr = match &result {
-                    DataLoadResult::Success { pr, .. } => Some(pr.number),
-                    DataLoadResult::Error(_) => None,
+                    DataLoadResult::Success { pr, .. } | DataLoadResult::Partial { pr, .. } => {
+                        Some(pr.number)
+                    }
+                    DataLoadResult::Error(_)
+                    | DataLoadResult::Progress { .. }
+                    | DataLoadResult::FilePatch { .. } => None,
                 };
 
                 if source_pr == self.pr_number || source_pr.is_none() {
                     // 現在のPR/モードに一致 → UI状態に反映
                     let pr_number = self.pr_number.unwrap_or(0);
                     self.handle_data_result(pr_number, result);
-                } else if let DataLoadResult::Success { pr, files } = result {
+                } else if let DataLoadResult::Success { pr, files, .. } = result {
                     // 異なるPRのデータ: セッションキャッシュにのみ格納
                     // receiver は破棄しない（永続チャンネルを維持）
                     let cache_key = PrCacheKey {
@@ -170,6 +208,10 @@ impl App {
     pub(crate) fn start_prefetch_all_files(&mut self) {
         // 既存のプリフェッチを中断
         self.diff_store.drop_prefetch_rx();
+        if !self.highlighting_enabled {
+            return;
+        }
+        self.restore_memoized_caches();
 
         // キャッシュ済みファイルをスキップし、上限まで収集
         // poll_prefetch_updates() で現在表示中のハイライト済みファイルはストアに格納されないため、
@@ -183,20 +225,25 @@ impl App {
                     && !self.diff_store.store_contains_key(i)
                     && self.diff_store.current_key() != Some(i)
             })
-            .take(MAX_PREFETCH_FILES)
-            .map(|(i, f)| PrefetchItem {
-                key: i,
-                file_index: i,
-                filename: f.filename.clone(),
-                patch: f.patch.clone().unwrap(),
+            .filter_map(|(i, f)| {
+                // linguist-generated はハイライトしない
+                let filename = self.linguist.highlight_filename(&f.filename)?;
+                Some(PrefetchItem {
+                    key: i,
+                    file_index: i,
+                    filename: filename.into_owned(),
+                    patch: f.patch.clone().unwrap(),
+                })
             })
+            .take(MAX_PREFETCH_FILES)
             .collect();
 
         let theme = self.config.diff.theme.clone();
         let markdown_rich = self.markdown_rich;
         let tab_width = self.config.diff.tab_width;
+        let reuse_trees = self.local_mode;
         self.diff_store
-            .start_prefetch(items, &theme, markdown_rich, tab_width);
+            .start_prefetch(items, &theme, markdown_rich, tab_width, reuse_trees);
     }
 
     /// プリフェッチ結果をポーリングして diff_store に格納
@@ -251,9 +298,11 @@ impl App {
         self.batch_diff_receiver = Some(rx);
 
         let working_dir = self.working_dir.clone();
+        let base = self.diff_base();
         tokio::spawn(async move {
             crate::loader::fetch_local_diffs_batched(
                 working_dir,
+                base,
                 tracked_filenames,
                 untracked_filenames,
                 20,
@@ -298,6 +347,9 @@ impl App {
                     if let DataState::Loaded { ref mut files, .. } = self.data_state {
                         if let Some(ref index_map) = index_map {
                             for result in &results {
+                                if result.incomplete {
+                                    self.incomplete_patches.insert(result.filename.clone());
+                                }
                                 if let Some(&idx) = index_map.get(&result.filename) {
                                     if files[idx].patch.is_none() {
                                         files[idx].patch = result.patch.clone();
@@ -346,6 +398,15 @@ impl App {
             self.ensure_diff_cache();
         }
 
+        if any_received {
+            self.apply_local_viewed_state();
+        }
+
+        // 受信時に加え、全バッチ完了時（partial 解除）にも再計算
+        if any_received || self.batch_diff_receiver.is_none() {
+            self.refresh_review_marker_count();
+        }
+
         if any_received && !self.diff_store.has_prefetch_rx() {
             self.start_prefetch_all_files();
         }
@@ -375,8 +436,10 @@ impl App {
         self.lazy_diff_pending_file = Some(filename.clone());
 
         let working_dir = self.working_dir.clone();
+        let base = self.diff_base();
         tokio::spawn(async move {
-            crate::loader::fetch_single_file_diff(working_dir, filename, is_untracked, tx).await;
+            crate::loader::fetch_single_file_diff(working_dir, base, filename, is_untracked, tx)
+                .await;
         });
     }
 
@@ -390,6 +453,11 @@ impl App {
             Ok(result) => {
                 self.lazy_diff_receiver = None;
                 self.lazy_diff_pending_file = None;
+                if result.incomplete {
+                    self.incomplete_patches.insert(result.filename.clone());
+                } else {
+                    self.incomplete_patches.remove(&result.filename);
+                }
 
                 if let DataState::Loaded { ref mut files, .. } = self.data_state {
                     if let Some(file) = files.iter_mut().find(|f| f.filename == result.filename) {
@@ -410,16 +478,89 @@ impl App {
                         .update_file_patch(&key, &result.filename, result.patch);
                 }
 
+                self.apply_local_viewed_state();
+                self.refresh_review_marker_count();
                 self.diff_store.clear_current();
                 self.update_diff_line_count();
                 self.ensure_diff_cache();
             }
             Err(mpsc::error::TryRecvError::Empty) => {}
             Err(mpsc::error::TryRecvError::Disconnected) => {
+                // 結果を送らずにタスクが終了した（中断・panic）→ 未取得のまま残す
                 self.lazy_diff_receiver = None;
-                self.lazy_diff_pending_file = None;
+                if let Some(filename) = self.lazy_diff_pending_file.take() {
+                    self.incomplete_patches.insert(filename);
+                }
+            }
+        }
+    }
+
+    /// 選択中ファイルの diff 取得が失敗・中断したままか
+    pub fn is_current_patch_incomplete(&self) -> bool {
+        self.files()
+            .get(self.selected_file)
+            .is_some_and(|f| f.patch.is_none() && self.incomplete_patches.contains(&f.filename))
+    }
+
+    /// 選択中ファイルの diff だけを取り直す
+    ///
+    /// local mode は `git diff`、PR は `gh pr diff` から該当ファイルを抜き出す。
+    pub(crate) fn reload_current_file(&mut self) {
+        // コミット範囲の表示中は `gh pr diff`（PR 全体）から切り出せないため、範囲ごと取り直す
+        if !self.local_mode && self.diff_base().is_some() {
+            self.retry_load();
+            return;
+        }
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        let filename = file.filename.clone();
+        let is_untracked = file.status == "added" && file.additions == 0 && file.deletions == 0;
+
+        self.incomplete_patches.remove(&filename);
+        // ストリーミング中 (PartiallyLoaded) でも古い patch と diff キャッシュを捨てる
+        if let DataState::Loaded { ref mut files, .. }
+        | DataState::PartiallyLoaded { ref mut files, .. } = self.data_state
+        {
+            if let Some(file) = files.get_mut(self.selected_file) {
+                file.patch = None;
             }
         }
+        let index = self.selected_file;
+        self.diff_store.invalidate_if(|&k, _| k == index);
+        self.patch_symbol_table = None;
+        self.diff_store.clear_current();
+        self.update_diff_line_count();
+
+        let (tx, rx) = mpsc::channel(1);
+        self.lazy_diff_receiver = Some(rx);
+        self.lazy_diff_pending_file = Some(filename.clone());
+        self.cmt.submission_result = Some((true, format!("Reloading {}...", filename)));
+        self.cmt.submission_result_time = Some(Instant::now());
+
+        if self.local_mode {
+            let working_dir = self.working_dir.clone();
+            let base = self.diff_base();
+            tokio::spawn(async move {
+                crate::loader::fetch_single_file_diff(
+                    working_dir,
+                    base,
+                    filename,
+                    is_untracked,
+                    tx,
+                )
+                .await;
+            });
+        } else {
+            let repo = self.repo.clone();
+            let pr_number = self.pr_number();
+            let github = self.github.clone();
+            let index = self.selected_file;
+            tokio::spawn(async move {
+                crate::loader::fetch_pr_file_diff(github, repo, pr_number, filename, index, tx)
+                    .await;
+            });
+        }
     }
 
     /// UI 用: lazy diff がロード中かどうか
@@ -499,6 +640,8 @@ impl App {
             Ok(CommentSubmitResult::Success) => {
                 self.cmt.comment_submitting = false;
                 self.cmt.comment_submit_receiver = None;
+                self.record_comment_made();
+                self.release_in_flight_comment(origin_pr);
                 self.cmt.submission_result = Some((true, "Submitted".to_string()));
                 self.cmt.submission_result_time = Some(Instant::now());
                 let cache_key = PrCacheKey {
@@ -516,13 +659,19 @@ impl App {
             Ok(CommentSubmitResult::Error(e)) => {
                 self.cmt.comment_submitting = false;
                 self.cmt.comment_submit_receiver = None;
-                self.cmt.submission_result = Some((false, format!("Failed: {}", e)));
+                let message = if self.keep_failed_comment_pending(origin_pr) {
+                    format!("Failed: {} (kept as pending)", e)
+                } else {
+                    format!("Failed: {}", e)
+                };
+                self.cmt.submission_result = Some((false, message));
                 self.cmt.submission_result_time = Some(Instant::now());
             }
             Err(mpsc::error::TryRecvError::Empty) => {}
             Err(mpsc::error::TryRecvError::Disconnected) => {
                 self.cmt.comment_submitting = false;
                 self.cmt.comment_submit_receiver = None;
+                self.keep_failed_comment_pending(origin_pr);
             }
         }
     }
@@ -633,9 +782,29 @@ impl App {
             match rx.try_recv() {
                 Ok(event) => {
                     if let Some(ref mut rally_state) = self.ai_rally_state {
+                        // ステップの入力と再実行結果は履歴に積まない
+                        let event = match event {
+                            RallyEvent::StepStarted(input) => {
+                                rally_state.step_inputs.push(input);
+                                continue;
+                            }
+                            RallyEvent::StepReplayed { iteration, output } => {
+                                rally_state.push_log(LogEntry::new(
+                                    LogEventType::Info,
+                                    format!(
+                                        "Iteration {} re-run completed: {}",
+                                        iteration,
+                                        output.summary()
+                                    ),
+                                ));
+                                rally_state.replace_step_output(iteration, output.into_event());
+                                continue;
+                            }
+                            event => event,
+                        };
                         match &event {
                             RallyEvent::StateChanged(state) => {
-                                rally_state.state = *state;
+                                rally_state.set_state(*state);
                                 // Clear pending post info on terminal states
                                 if matches!(
                                     state,
@@ -780,6 +949,18 @@ impl App {
                                     ),
                                 ));
                             }
+                            RallyEvent::PendingComments(comments) => {
+                                rally_state
+                                    .pending_comments
+                                    .extend(comments.iter().cloned());
+                                rally_state.push_log(LogEntry::new(
+                                    LogEventType::Review,
+                                    format!(
+                                        "{} inline comment(s) held for review (dry run)",
+                                        comments.len()
+                                    ),
+                                ));
+                            }
                             RallyEvent::Paused => {
                                 rally_state.pause_state = PauseState::Paused;
                             }
@@ -813,9 +994,26 @@ impl App {
         self.batch_diff_receiver = None;
         self.lazy_diff_receiver = None;
         self.lazy_diff_pending_file = None;
+        self.files_load_progress = None;
+        self.incomplete_patches.clear();
 
         match result {
-            DataLoadResult::Success { pr, files } => {
+            DataLoadResult::Success {
+                pr,
+                files,
+                revision,
+            } => {
+                let replacing_stale = self.showing_stale_data;
+                // 表示中の PR の再取得（自動更新など）。表示位置を保ち、コメントも取り直す
+                let background_refresh = !self.local_mode
+                    && !replacing_stale
+                    && revision == DataRevision::Fresh
+                    && matches!(self.data_state, DataState::Loaded { .. });
+                // local refresh: 下で diff_store を破棄する前に、patch が変わらないファイルの
+                // ハイライト結果を再利用できるよう退避する
+                if self.local_mode {
+                    self.stash_highlighted_caches();
+                }
                 let changed_file_index = if self.local_mode && self.local_auto_focus {
                     self.find_changed_local_file_index(&files, self.selected_file)
                 } else {
@@ -828,10 +1026,10 @@ impl App {
                 let old_selected = self.selected_file;
                 let mut next_selected = if files.is_empty() {
                     0
-                } else if let Some(filename) = old_selected_file {
+                } else if let Some(filename) = old_selected_file.as_ref() {
                     files
                         .iter()
-                        .position(|file| file.filename == filename)
+                        .position(|file| &file.filename == filename)
                         .unwrap_or_else(|| self.selected_file.min(files.len() - 1))
                 } else {
                     self.selected_file.min(files.len() - 1)
@@ -840,8 +1038,12 @@ impl App {
                 if let Some(idx) = changed_file_index {
                     next_selected = idx;
                 }
+                // キャッシュ表示から最新データへの差し替えや再取得では、同じファイルを見ている限りスクロールを保つ
+                let keeps_selected_file = (replacing_stale || background_refresh)
+                    && old_selected_file.is_some()
+                    && files.get(next_selected).map(|f| &f.filename) == old_selected_file.as_ref();
 
-                if next_selected != old_selected {
+                if next_selected != old_selected && !keeps_selected_file {
                     self.diff_store.clear_current();
                     self.diff_scroll.reset();
                     self.cmt.comment_panel_open = false;
@@ -875,9 +1077,18 @@ impl App {
                 self.diff_scroll.set_line_count(line_count);
                 // ファイル一覧が変わるため、ハイライトキャッシュストアをクリア
                 self.diff_store.clear();
+                // patch が変わると畳んだ hunk の行範囲がずれる
+                self.folded_hunks.clear();
+                self.expanded_unchanged.clear();
+                self.clear_patch_overrides();
                 // Check if we need to start AI Rally (--ai-rally flag was passed)
-                let should_start_rally =
-                    self.start_ai_rally_on_load && matches!(self.data_state, DataState::Loading);
+                let should_start_rally = self.start_ai_rally_on_load
+                    && revision == DataRevision::Fresh
+                    && (replacing_stale
+                        || matches!(
+                            self.data_state,
+                            DataState::Loading | DataState::PartiallyLoaded { .. }
+                        ));
                 // clone() でキャッシュと DataState の両方にデータを格納（Arc不使用）
                 let cache_key = PrCacheKey {
                     repo: self.repo.clone(),
@@ -896,7 +1107,17 @@ impl App {
                         pr_updated_at: pr.updated_at.clone(),
                     },
                 );
+                // PR mode: GitHub が patch を省き、diff からも埋まらなかったファイルは未取得として扱う
+                if !self.local_mode {
+                    self.incomplete_patches.extend(
+                        files
+                            .iter()
+                            .filter(|f| f.patch.is_none() && f.additions + f.deletions > 0)
+                            .map(|f| f.filename.clone()),
+                    );
+                }
                 self.data_state = DataState::Loaded { pr, files };
+                self.showing_stale_data = revision == DataRevision::Stale;
                 // PRデータが更新されたため、PR description キャッシュを無効化・再構築
                 self.pr_description_cache = None;
                 if self.state == AppState::PrDescription {
@@ -917,6 +1138,7 @@ impl App {
                 // wait for the full batch order. Must be after DataState::Loaded
                 // so that self.files() returns the new file list.
                 if self.local_mode {
+                    self.load_linguist_overrides();
                     self.request_lazy_diff();
                 }
                 // local mode: バッチ diff ロード → 完了後にプリフェッチ開始
@@ -926,42 +1148,41 @@ impl App {
                 } else {
                     self.start_prefetch_all_files();
                 }
+                // バッチ開始後に数えることで、未取得 patch があれば partial になる
+                self.refresh_review_marker_count();
                 if self.needs_review_comment_load() {
                     self.load_review_comments();
+                } else if background_refresh && self.cmt.comment_receiver.is_none() {
+                    self.session_cache.remove_review_comments(&PrCacheKey {
+                        repo: self.repo.clone(),
+                        pr_number: origin_pr,
+                    });
+                    self.load_review_comments();
                 }
-                // CLI 直接指定時: ci_status をバックグラウンドで取得
+                // 前回の閲覧位置を復元（--file 指定があればそちらを優先）
+                self.apply_review_position_on_load();
+                // --file 指定時: 対象ファイルの diff を直接開く
+                self.apply_open_file_on_load();
+                // CLI 直接指定時: チェックの状態別件数をバックグラウンドで取得
                 if !self.local_mode
-                    && self.chk.ci_status.is_none()
+                    && !self.offline
+                    && self.chk.check_counts.is_none()
                     && self.chk.ci_status_receiver.is_none()
                 {
                     let (tx, rx) = mpsc::channel(1);
                     self.chk.ci_status_receiver = Some(rx);
                     let repo = self.repo.clone();
+                    let github = self.github.clone();
                     tokio::spawn(async move {
-                        let status = match crate::github::fetch_pr_checks(&repo, origin_pr).await {
-                            Ok(checks) => {
-                                use crate::github::CiStatus;
-                                if checks.is_empty() {
-                                    CiStatus::None
-                                } else {
-                                    let has_pending = checks
-                                        .iter()
-                                        .any(|c| c.bucket.as_deref() == Some("pending"));
-                                    let has_fail = checks.iter().any(|c| {
-                                        matches!(c.bucket.as_deref(), Some("fail") | Some("cancel"))
-                                    });
-                                    if has_fail {
-                                        CiStatus::Failure
-                                    } else if has_pending {
-                                        CiStatus::Pending
-                                    } else {
-                                        CiStatus::Success
-                                    }
-                                }
-                            }
-                            Err(_) => CiStatus::None,
+                        let counts = match crate::github::fetch_pr_checks(
+                            &*github, &repo, origin_pr,
+                        )
+                        .await
+                        {
+                            Ok(checks) => crate::github::CheckCounts::from_checks(&checks),
+                            Err(_) => crate::github::CheckCounts::default(),
                         };
-                        let _ = tx.send(status).await;
+                        let _ = tx.send(counts).await;
                     });
                 }
                 if should_start_rally {
@@ -970,6 +1191,7 @@ impl App {
                 }
                 if let Some(local_files) = local_files_for_signature {
                     self.remember_local_file_signatures(&local_files);
+                    self.load_local_viewed_files();
                 }
                 // Local モードのデータ処理完了後、ウォッチャーの debounce フラグをリセット。
                 // app.rs の activate_watcher で作成した refresh_pending は main.rs の
@@ -988,9 +1210,52 @@ impl App {
                 // Loading状態の場合のみエラー表示（既にデータがある場合は無視）
                 if matches!(self.data_state, DataState::Loading) {
                     self.data_state = DataState::Error(msg);
+                } else if self.showing_stale_data {
+                    self.showing_stale_data = false;
+                    self.cmt.submission_result = Some((
+                        false,
+                        format!("Showing cached PR data, refresh failed: {}", msg),
+                    ));
+                    self.cmt.submission_result_time = Some(Instant::now());
+                }
+            }
+            // キャッシュ表示中は patch 欠けの一覧に戻さず、Success で差し替える
+            DataLoadResult::Partial { .. } if self.showing_stale_data => {}
+            DataLoadResult::Partial { pr, files } => {
+                // patch が揃うまでは一覧と取得済み patch だけを表示する（Success で確定）
+                self.selected_file = self.selected_file.min(files.len().saturating_sub(1));
+                self.diff_store.clear();
+                self.data_state = DataState::PartiallyLoaded { pr, files };
+                self.update_diff_line_count();
+                if self.file_list_filter.is_some() {
+                    self.reapply_filter("file");
                 }
+                self.rebuild_file_tree_if_active();
+                self.refresh_review_marker_count();
+                self.ensure_diff_cache();
             }
+            DataLoadResult::Progress { .. } | DataLoadResult::FilePatch { .. } => {}
+        }
+    }
+
+    /// ストリーミングで届いた patch を反映する。反映した場合 true
+    fn apply_streamed_patch(&mut self, filename: String, patch: String) -> bool {
+        let DataState::PartiallyLoaded { files, .. } = &mut self.data_state else {
+            return false;
+        };
+        let Some(index) = files.iter().position(|f| f.filename == filename) else {
+            return false;
+        };
+        if files[index].patch.is_some() {
+            return false;
+        }
+        files[index].patch = Some(patch);
+        if index == self.selected_file {
+            self.diff_store.clear_current();
+            self.update_diff_line_count();
+            self.ensure_diff_cache();
         }
+        true
     }
 
     /// base シグネチャ（patch 除外）: Phase 1 での構造変更検出用
@@ -1143,6 +1408,11 @@ impl App {
             Ok(Ok(items)) => {
                 // クロスPR汚染防止
                 if self.chk.checks_target_pr == Some(origin_pr) {
+                    // 一覧を開いたときの結果でヘッダの件数も更新する
+                    if self.pr_number == Some(origin_pr) {
+                        self.chk.check_counts =
+                            Some(crate::github::CheckCounts::from_checks(&items));
+                    }
                     self.chk.checks = Some(items);
                     self.chk.checks_loading = false;
                 }
@@ -1172,8 +1442,8 @@ impl App {
         };
 
         match rx.try_recv() {
-            Ok(status) => {
-                self.chk.ci_status = Some(status);
+            Ok(counts) => {
+                self.chk.check_counts = Some(counts);
                 self.chk.ci_status_receiver = None;
             }
             Err(mpsc::error::TryRecvError::Empty) => {}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use crossterm::event;
+use std::time::Instant;
 use tokio::sync::mpsc;
 
 use crate::cache::PrCacheKey;
@@ -7,9 +8,9 @@ use crate::filter::ListFilter;
 use crate::github::{self, PrStateFilter};
 use crate::keybinding::{event_to_keybinding, SequenceMatch};
 
-use crate::github::CiStatus;
+use crate::github::CheckCounts;
 
-use super::types::LoadState;
+use super::types::{DiffStat, InputMode, LoadState};
 use super::{App, AppState, DataState};
 
 impl App {
@@ -181,6 +182,11 @@ impl App {
             return Ok(());
         }
 
+        if self.matches_single_key(&key, &kb.pr_list_filters) {
+            self.open_pr_list_filters_input();
+            return Ok(());
+        }
+
         if self.matches_single_key(&key, &kb.refresh) {
             self.reload_pr_list();
             return Ok(());
@@ -220,11 +226,48 @@ impl App {
 
         Ok(())
     }
+    /// 作成者・担当者・ラベル絞り込みの入力を開く（現在の条件を編集できる）
+    pub(crate) fn open_pr_list_filters_input(&mut self) {
+        self.input_mode = Some(InputMode::PrListFilters);
+        self.input_text_area.clear();
+        self.input_text_area
+            .set_content(&self.prs.pr_list_filters.query());
+        self.input_text_area.move_to_end();
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+
+    /// 入力されたクエリを絞り込み条件として適用し、変わっていれば再取得する。
+    /// 空のクエリは絞り込みの解除。
+    pub(crate) fn apply_pr_list_filters(&mut self, query: &str) {
+        let filters = match github::PrListFilters::parse(query) {
+            Ok(filters) => filters,
+            Err(e) => {
+                self.cmt.submission_result = Some((false, format!("Invalid PR filter: {}", e)));
+                self.cmt.submission_result_time = Some(Instant::now());
+                return;
+            }
+        };
+        if filters == self.prs.pr_list_filters {
+            return;
+        }
+        let message = if filters.is_empty() {
+            "PR filters cleared".to_string()
+        } else {
+            format!("PR filters: {}", filters.query())
+        };
+        self.prs.pr_list_filters = filters;
+        self.cmt.submission_result = Some((true, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+        self.reload_pr_list();
+    }
+
     pub(crate) fn reload_pr_list(&mut self) {
         self.prs.selected_pr = 0;
         self.prs.pr_list_scroll_offset = 0;
         self.prs.pr_list = LoadState::Loading;
         self.prs.pr_list_has_more = false;
+        self.prs.pr_list_offset = 0;
         self.prs.pr_list_filter = None;
 
         let (tx, rx) = mpsc::channel(2);
@@ -232,20 +275,38 @@ impl App {
 
         let repo = self.repo.clone();
         let state = self.prs.pr_list_state_filter;
+        let filters = self.prs.pr_list_filters.clone();
+        let github = self.github.clone();
 
         tokio::spawn(async move {
-            let result = github::fetch_pr_list(&repo, state, 30).await;
+            let result = github::fetch_pr_list(&*github, &repo, state, &filters, 30).await;
             let _ = tx.send(result.map_err(|e| e.to_string())).await;
         });
     }
 
+    /// PR 一覧の対象リポジトリを切り替えて一覧を取り直す
+    ///
+    /// `[repos."owner/repo"]` セクションはリポジトリごとに選ばれるため、設定も新しい
+    /// リポジトリで解決し直す。読み込みに失敗した場合は現在の設定のまま切り替える。
+    pub fn switch_repo(&mut self, repo: &str) {
+        if self.repo == repo {
+            return;
+        }
+        self.repo = repo.to_string();
+        if let Err(e) = self.load_config_for_repo() {
+            self.cmt.submission_result = Some((false, format!("Config reload failed: {:#}", e)));
+            self.cmt.submission_result_time = Some(Instant::now());
+        }
+        self.reload_pr_list();
+    }
+
     /// Load next page of PRs for infinite scroll.
     pub(crate) fn load_more_prs(&mut self) {
         if self.prs.pr_list.is_loading() {
             return;
         }
 
-        let offset = self.prs.pr_list.as_loaded().map(|l| l.len()).unwrap_or(0) as u32;
+        let offset = self.prs.pr_list_offset;
         let existing = std::mem::take(&mut self.prs.pr_list)
             .into_loaded()
             .unwrap_or_default();
@@ -256,9 +317,13 @@ impl App {
 
         let repo = self.repo.clone();
         let state = self.prs.pr_list_state_filter;
+        let filters = self.prs.pr_list_filters.clone();
+        let github = self.github.clone();
 
         tokio::spawn(async move {
-            let result = github::fetch_pr_list_with_offset(&repo, state, offset, 30).await;
+            let result =
+                github::fetch_pr_list_with_offset(&*github, &repo, state, &filters, offset, 30)
+                    .await;
             let _ = tx.send(result.map_err(|e| e.to_string())).await;
         });
     }
@@ -266,6 +331,11 @@ impl App {
         self.pr_number = Some(pr_number);
         self.state = AppState::FileList;
         self.file_list_filter = None;
+        self.file_category_filter = None;
+        self.file_glob_filter = None;
+        self.review_marker_count = 0;
+        self.diff_stat = DiffStat::default();
+        self.review_marker_count_partial = false;
         self.cmt.pending_approve_body = None;
         self.cmt.review_comments = None;
         self.cmt.file_comment_counts.clear();
@@ -289,13 +359,13 @@ impl App {
 
         if let Some(prs) = self.prs.pr_list.as_loaded() {
             if let Some(pr_summary) = prs.iter().find(|p| p.number == pr_number) {
-                let status = CiStatus::from_rollup(&pr_summary.status_check_rollup);
-                self.chk.ci_status = Some(status);
+                let counts = CheckCounts::from_rollup(&pr_summary.status_check_rollup);
+                self.chk.check_counts = Some(counts);
             } else {
-                self.chk.ci_status = None;
+                self.chk.check_counts = None;
             }
         } else {
-            self.chk.ci_status = None;
+            self.chk.check_counts = None;
         }
 
         if self.pending_ai_rally {
@@ -315,6 +385,7 @@ impl App {
                 files: cached.files.clone(),
             };
             self.diff_scroll.line_count = diff_line_count;
+            self.refresh_review_marker_count();
             self.start_prefetch_all_files();
             if self.start_ai_rally_on_load {
                 self.start_ai_rally_on_load = false;
@@ -342,6 +413,9 @@ impl App {
 
         self.pr_number = None;
         self.data_state = DataState::Loading;
+        self.patch_symbol_table = None;
+        self.files_load_progress = None;
+        self.showing_stale_data = false;
         self.cmt.review_comments = None;
         self.cmt.discussion_comments = None;
         self.cmt.file_comment_counts.clear();
@@ -349,6 +423,10 @@ impl App {
         self.diff_store.clear();
         self.diff_scroll.reset();
         self.cmt.comment_receiver = None;
+        self.cmt.pr_review_threads.clear();
+        self.cmt.review_thread_receiver = None;
+        self.cmt.reaction_receiver = None;
+        self.cmt.own_reactions.clear();
         self.cmt.discussion_comment_receiver = None;
         self.cmt.comment_submit_receiver = None;
         self.mark_viewed_receiver = None;
@@ -361,19 +439,28 @@ impl App {
         self.cmt.discussion_comments_loading = false;
         self.selected_file = 0;
         self.file_list_scroll_offset = 0;
+        self.generated_files_expanded = false;
+        self.generated_header_selected = false;
         self.file_list_filter = None;
+        self.file_category_filter = None;
+        self.file_glob_filter = None;
+        self.review_marker_count = 0;
+        self.diff_stat = DiffStat::default();
+        self.review_marker_count_partial = false;
         self.tree_mode_active = false;
         self.file_tree_state = None;
         self.chk.checks = None;
         self.chk.checks_loading = false;
         self.chk.checks_target_pr = None;
         self.chk.checks_receiver = None;
-        self.chk.ci_status = None;
+        self.chk.check_counts = None;
         self.chk.ci_status_receiver = None;
+        self.base_pr_receiver = None;
     }
 
     pub fn back_to_pr_list(&mut self) {
         if self.started_from_pr_list {
+            self.close_review_session();
             let return_to_issue = self.issue_detail_return;
             if return_to_issue {
                 self.issue_detail_return = false;
@@ -387,3 +474,12 @@ impl App {
         }
     }
 }
+
+/// 追加ロードしたページを一覧の末尾に足す（取得中に PR が増減してページがずれた場合の重複は捨てる）
+pub(crate) fn append_pr_page(
+    existing: &mut Vec<github::PullRequestSummary>,
+    page: Vec<github::PullRequestSummary>,
+) {
+    let seen: std::collections::HashSet<u32> = existing.iter().map(|pr| pr.number).collect();
+    existing.extend(page.into_iter().filter(|pr| !seen.contains(&pr.number)));
+}
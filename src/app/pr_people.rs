@@ -0,0 +1,82 @@
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+use crate::github::{self, PrPeopleRole};
+
+use super::types::InputMode;
+use super::{App, AppState};
+
+impl App {
+    /// レビュアー依頼 / 担当者追加のログイン名入力を開く
+    pub(crate) fn open_pr_people_input(&mut self, role: PrPeopleRole) {
+        if self.local_mode || self.pr_number.is_none() {
+            return;
+        }
+        if self.pr_people_receiver.is_some() {
+            self.set_pr_people_message(false, "Still updating the PR, please wait".to_string());
+            return;
+        }
+        self.input_mode = Some(InputMode::PrPeople(role));
+        self.input_text_area.clear();
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+
+    /// 入力されたログイン名を PR に追加する。空の入力は何もしない。
+    pub(crate) fn apply_pr_people(&mut self, role: PrPeopleRole, input: &str) {
+        if input.trim().is_empty() {
+            return;
+        }
+        let Some(pr_number) = self.pr_number else {
+            return;
+        };
+        let logins = match github::parse_logins(input, role) {
+            Ok(logins) => logins,
+            Err(e) => {
+                self.set_pr_people_message(false, e);
+                return;
+            }
+        };
+
+        let (tx, rx) = mpsc::channel(1);
+        self.pr_people_receiver = Some(rx);
+        let repo = self.repo.clone();
+        let github = self.github.clone();
+        tokio::spawn(async move {
+            let result = github::add_pr_people(&*github, &repo, pr_number, role, &logins)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send((role, logins, result)).await;
+        });
+        self.set_pr_people_message(true, format!("Adding {}...", role.label()));
+    }
+
+    pub(crate) fn poll_pr_people_updates(&mut self) {
+        let Some(ref mut rx) = self.pr_people_receiver else {
+            return;
+        };
+        let (role, logins, result) = match rx.try_recv() {
+            Ok(update) => update,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.pr_people_receiver = None;
+                return;
+            }
+        };
+        self.pr_people_receiver = None;
+        match result {
+            Ok(()) => self.set_pr_people_message(
+                true,
+                format!("Added {}: {}", role.label(), logins.join(", ")),
+            ),
+            Err(e) => {
+                self.set_pr_people_message(false, format!("Failed to add {}: {}", role.label(), e))
+            }
+        }
+    }
+
+    fn set_pr_people_message(&mut self, success: bool, message: String) {
+        self.cmt.submission_result = Some((success, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+}
@@ -0,0 +1,196 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::cache::PrCacheKey;
+use crate::github::{self, ReactionKind};
+
+use super::types::{ReactionPickerState, ReactionUpdate};
+use super::App;
+
+impl App {
+    /// コメント一覧で選択中のレビューコメント ID（展開中はスレッド内の選択コメント）
+    pub(crate) fn selected_review_comment_id(&self) -> Option<u64> {
+        let comments = self.cmt.review_comments.as_ref()?;
+        let index = match self.cmt.expanded_thread {
+            Some(thread_idx) => {
+                let thread = self.cmt.review_threads.get(thread_idx)?;
+                std::iter::once(thread.root)
+                    .chain(thread.replies.iter().copied())
+                    .nth(self.cmt.expanded_selected)?
+            }
+            None => self.cmt.review_threads.get(self.cmt.selected_thread)?.root,
+        };
+        comments.get(index).map(|c| c.id)
+    }
+
+    /// 選択中のレビューコメントにリアクションを付けるピッカーを開く
+    pub(crate) fn open_reaction_picker(&mut self) {
+        if let Some(comment_id) = self.selected_review_comment_id() {
+            self.reaction_picker = Some(ReactionPickerState {
+                comment_id,
+                selected: 0,
+            });
+        }
+    }
+
+    /// ピッカー表示中のキー処理（表示中は全キーを消費する）
+    pub(crate) fn handle_reaction_picker_key(&mut self, key: &KeyEvent) {
+        let kb = &self.config.keybindings;
+        let close = key.code == KeyCode::Esc || self.matches_single_key(key, &kb.quit);
+        let next = matches!(key.code, KeyCode::Right | KeyCode::Down)
+            || self.matches_single_key(key, &kb.move_right)
+            || self.matches_single_key(key, &kb.move_down);
+        let prev = matches!(key.code, KeyCode::Left | KeyCode::Up)
+            || self.matches_single_key(key, &kb.move_left)
+            || self.matches_single_key(key, &kb.move_up);
+        // 1-8 で直接選んで確定できる
+        let digit = match key.code {
+            KeyCode::Char(c) => c
+                .to_digit(10)
+                .map(|d| d as usize)
+                .filter(|d| (1..=ReactionKind::ALL.len()).contains(d)),
+            _ => None,
+        };
+
+        let Some(picker) = self.reaction_picker.as_mut() else {
+            return;
+        };
+        let last = ReactionKind::ALL.len() - 1;
+        if close {
+            self.reaction_picker = None;
+        } else if next {
+            picker.selected = (picker.selected + 1).min(last);
+        } else if prev {
+            picker.selected = picker.selected.saturating_sub(1);
+        } else if key.code == KeyCode::Enter || digit.is_some() {
+            let index = digit.map_or(picker.selected, |d| d - 1);
+            let comment_id = picker.comment_id;
+            self.reaction_picker = None;
+            self.toggle_reaction(comment_id, ReactionKind::ALL[index]);
+        }
+    }
+
+    /// リアクションを付ける。このセッションで付けた種類なら取り消す。
+    ///
+    /// 表示中の件数は送信前に更新し、失敗したら元に戻す。
+    pub(crate) fn toggle_reaction(&mut self, comment_id: u64, kind: ReactionKind) {
+        if self.local_mode {
+            self.cmt.submission_result = Some((
+                false,
+                "Reactions are not available in local mode".to_string(),
+            ));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        }
+        if self.cmt.reaction_receiver.is_some() {
+            self.cmt.submission_result =
+                Some((false, "Previous reaction is still being sent".to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        }
+
+        let existing = self.cmt.own_reactions.remove(&(comment_id, kind));
+        self.adjust_reaction_count(comment_id, kind, existing.is_none());
+
+        let (tx, rx) = mpsc::channel(1);
+        let pr_number = self.pr_number();
+        self.cmt.reaction_receiver = Some((pr_number, rx));
+
+        let repo = self.repo.clone();
+        let github = self.github.clone();
+        tokio::spawn(async move {
+            let result = match existing {
+                Some(reaction_id) => {
+                    github::remove_review_comment_reaction(&*github, &repo, comment_id, reaction_id)
+                        .await
+                        .map(|()| None)
+                }
+                None => github::add_review_comment_reaction(&*github, &repo, comment_id, kind)
+                    .await
+                    .map(Some),
+            };
+            let _ = tx
+                .send(ReactionUpdate {
+                    comment_id,
+                    kind,
+                    removed: existing,
+                    result: result.map_err(|e| e.to_string()),
+                })
+                .await;
+        });
+    }
+
+    /// リアクション送信結果のポーリング
+    pub(crate) fn poll_reaction_updates(&mut self) {
+        let Some((origin_pr, rx)) = self.cmt.reaction_receiver.as_mut() else {
+            return;
+        };
+        let origin_pr = *origin_pr;
+
+        match rx.try_recv() {
+            Ok(update) => {
+                self.cmt.reaction_receiver = None;
+                if self.pr_number != Some(origin_pr) {
+                    return;
+                }
+                self.apply_reaction_update(update);
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.cmt.reaction_receiver = None;
+            }
+        }
+    }
+
+    pub(crate) fn apply_reaction_update(&mut self, update: ReactionUpdate) {
+        let ReactionUpdate {
+            comment_id,
+            kind,
+            removed,
+            result,
+        } = update;
+        match result {
+            Ok(Some(reaction_id)) => {
+                self.cmt
+                    .own_reactions
+                    .insert((comment_id, kind), reaction_id);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                // 楽観的に更新した件数と取り消し用の ID を元に戻す
+                if let Some(reaction_id) = removed {
+                    self.cmt
+                        .own_reactions
+                        .insert((comment_id, kind), reaction_id);
+                }
+                self.adjust_reaction_count(comment_id, kind, removed.is_some());
+                self.cmt.submission_result =
+                    Some((false, format!("Failed to update reaction: {}", e)));
+                self.cmt.submission_result_time = Some(Instant::now());
+            }
+        }
+    }
+
+    /// 表示中（とセッションキャッシュ）のリアクション件数を増減する
+    fn adjust_reaction_count(&mut self, comment_id: u64, kind: ReactionKind, increment: bool) {
+        let cache_key = PrCacheKey {
+            repo: self.repo.clone(),
+            pr_number: self.pr_number(),
+        };
+        let Some(comments) = self.cmt.review_comments.as_mut() else {
+            return;
+        };
+        let Some(comment) = comments.iter_mut().find(|c| c.id == comment_id) else {
+            return;
+        };
+        if increment {
+            comment.reactions.increment(kind);
+        } else {
+            comment.reactions.decrement(kind);
+        }
+        self.session_cache
+            .put_review_comments(cache_key, comments.clone());
+    }
+}
@@ -0,0 +1,262 @@
+use crossterm::event::KeyEvent;
+use std::time::Instant;
+
+use crate::config::KeybindingsConfig;
+use crate::keybinding::{event_to_keybinding, KeyBinding, KeySequence};
+
+use super::types::{CommentTab, LeftPaneFocus};
+use super::{App, AppState};
+
+/// 読み取り専用モードで無効化する書き込み操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteAction {
+    Comment,
+    Suggestion,
+    Reply,
+    Review,
+    MarkViewed,
+    ResolveThread,
+    Reaction,
+    IssueComment,
+    AiRally,
+    EditPeople,
+    Stage,
+    Discard,
+    Commit,
+    Undo,
+    Push,
+    Reset,
+}
+
+impl WriteAction {
+    /// 全ての書き込み操作（網羅テスト用）
+    #[cfg(test)]
+    pub const ALL: [WriteAction; 16] = [
+        Self::Comment,
+        Self::Suggestion,
+        Self::Reply,
+        Self::Review,
+        Self::MarkViewed,
+        Self::ResolveThread,
+        Self::Reaction,
+        Self::IssueComment,
+        Self::AiRally,
+        Self::EditPeople,
+        Self::Stage,
+        Self::Discard,
+        Self::Commit,
+        Self::Undo,
+        Self::Push,
+        Self::Reset,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Comment => "Comments are",
+            Self::Suggestion => "Suggestions are",
+            Self::Reply => "Replies are",
+            Self::Review => "Review submission is",
+            Self::MarkViewed => "Marking files as viewed is",
+            Self::ResolveThread => "Resolving review threads is",
+            Self::Reaction => "Reactions are",
+            Self::IssueComment => "Issue comments are",
+            Self::AiRally => "AI Rally is",
+            Self::EditPeople => "Requesting reviewers and assignees is",
+            Self::Stage => "Staging is",
+            Self::Discard => "Discarding changes is",
+            Self::Commit => "Committing is",
+            Self::Undo => "Undoing git operations is",
+            Self::Push => "Pushing is",
+            Self::Reset => "Resetting commits is",
+        }
+    }
+
+    fn is_git_op(self) -> bool {
+        matches!(
+            self,
+            Self::Stage | Self::Discard | Self::Commit | Self::Undo | Self::Push | Self::Reset
+        )
+    }
+}
+
+impl App {
+    /// `--read-only` / `read_only = true` で起動しているか
+    pub fn is_read_only(&self) -> bool {
+        self.config.read_only
+    }
+
+    /// `handle_input` が各画面のハンドラへキーを渡す直前に一度だけ呼ぶ。
+    /// 読み取り専用モードでこのキーが書き込み操作を起動するなら、保留中のキーを捨てて
+    /// ステータスメッセージを出し、true を返す（呼び出し側はキーを破棄する）。
+    pub(crate) fn deny_write_key(&mut self, key: &KeyEvent) -> bool {
+        if !self.is_read_only() || self.key_capturing_overlay_open() {
+            return false;
+        }
+        // ハンドラと同じく、期限切れの保留キーは捨ててから判定する
+        self.check_sequence_timeout();
+        let Some(action) = self.write_action_for_key(key) else {
+            return false;
+        };
+        self.clear_pending_keys();
+        self.show_read_only_denial(action);
+        true
+    }
+
+    /// 書き込みを実際に行う箇所で呼ぶ最終防衛線。読み取り専用ならステータスを出して true を返す
+    ///
+    /// キー単位の判定（[`Self::deny_write_key`]）を経ない経路（復元した下書きの送信、
+    /// AI Rally の投稿・再実行など）もここで止める。
+    pub(crate) fn deny_write(&mut self, action: WriteAction) -> bool {
+        if !self.is_read_only() {
+            return false;
+        }
+        self.show_read_only_denial(action);
+        true
+    }
+
+    /// 保留中のキーと `key` で起動される書き込み操作（現在の画面・モード基準）
+    pub(crate) fn write_action_for_key(&self, key: &KeyEvent) -> Option<WriteAction> {
+        let kb = &self.config.keybindings;
+        let event = event_to_keybinding(key);
+        let completes = |seq: &KeySequence| {
+            !self.pending_keys.is_empty()
+                && event
+                    .as_ref()
+                    .is_some_and(|event| self.completes_sequence(event, seq))
+        };
+        // 保留中のキーと合わせてどのシーケンスにもならなければ、
+        // 各ハンドラは保留を捨ててこのキーを単キーとして扱う
+        let as_single = !kb.bindings().iter().any(|(_, seq)| completes(seq));
+        self.write_bindings(kb)
+            .into_iter()
+            .find(|(seq, _)| completes(seq) || (as_single && self.matches_single_key(key, seq)))
+            .map(|(_, action)| action)
+    }
+
+    fn completes_sequence(&self, event: &KeyBinding, seq: &KeySequence) -> bool {
+        seq.all_sequences().any(|keys| {
+            keys.split_last().is_some_and(|(last, prefix)| {
+                last == event && prefix == self.pending_keys.as_slice()
+            })
+        })
+    }
+
+    /// 現在の画面で書き込み操作に割り当てられているキー
+    fn write_bindings<'a>(&self, kb: &'a KeybindingsConfig) -> Vec<(&'a KeySequence, WriteAction)> {
+        use WriteAction::*;
+
+        match self.state {
+            AppState::FileList | AppState::SplitViewFileList => {
+                let mut bindings = vec![
+                    (&kb.ai_rally, AiRally),
+                    (&kb.request_reviewer, EditPeople),
+                    (&kb.add_assignee, EditPeople),
+                ];
+                if !self.local_mode {
+                    bindings.extend([
+                        (&kb.approve, Review),
+                        (&kb.request_changes, Review),
+                        (&kb.comment, Review),
+                        (&kb.review_submit, Review),
+                        (&kb.mark_viewed, MarkViewed),
+                        (&kb.mark_viewed_dir, MarkViewed),
+                    ]);
+                }
+                bindings
+            }
+            AppState::DiffView | AppState::SplitViewDiff => {
+                let mut bindings = vec![(&kb.comment, Comment), (&kb.suggestion, Suggestion)];
+                if self.multiline_selection.is_some() {
+                    bindings.extend([
+                        (&kb.suggestion_in_editor, Suggestion),
+                        (&kb.suggest_format, Suggestion),
+                    ]);
+                } else if self.cmt.comment_panel_open {
+                    bindings.push((&kb.reply, Reply));
+                }
+                bindings
+            }
+            AppState::CommentList
+                if !self.local_mode
+                    && !self.cmt.discussion_comment_detail_mode
+                    && self.cmt.comment_tab == CommentTab::Review =>
+            {
+                vec![
+                    (&kb.toggle_resolved, ResolveThread),
+                    (&kb.add_reaction, Reaction),
+                ]
+            }
+            AppState::IssueDetail => vec![(&kb.comment, IssueComment)],
+            AppState::IssueCommentList => {
+                let detail_mode = self
+                    .issue_state
+                    .as_ref()
+                    .is_some_and(|s| s.issue_comment_detail_mode);
+                if detail_mode {
+                    vec![(&kb.reply, IssueComment)]
+                } else {
+                    vec![(&kb.comment, IssueComment)]
+                }
+            }
+            AppState::GitOpsSplitTree => {
+                let focus = self
+                    .git_ops_state
+                    .as_ref()
+                    .map(|ops| ops.left_focus)
+                    .unwrap_or(LeftPaneFocus::Tree);
+                match focus {
+                    LeftPaneFocus::Tree => vec![
+                        (&kb.git_ops_stage, Stage),
+                        (&kb.git_ops_stage_all, Stage),
+                        (&kb.git_ops_discard, Discard),
+                        (&kb.git_ops_commit, Commit),
+                        (&kb.git_ops_undo, Undo),
+                        (&kb.git_ops_push, Push),
+                    ],
+                    LeftPaneFocus::Commits => {
+                        vec![(&kb.git_ops_undo, Undo), (&kb.git_ops_reset, Reset)]
+                    }
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// 文字入力を受け付けているオーバーレイ・ポップアップが開いているか。
+    /// 開いている間はキーがそちらに吸われ、書き込み操作には届かない。
+    fn key_capturing_overlay_open(&self) -> bool {
+        self.file_list_filter
+            .as_ref()
+            .is_some_and(|f| f.input_active)
+            || self
+                .issue_state
+                .as_ref()
+                .is_some_and(|s| s.issue_list_filter.as_ref().is_some_and(|f| f.input_active))
+            || self.file_glob_input.is_some()
+            || self.diff_base_input.is_some()
+            || self.hunk_filter.as_ref().is_some_and(|f| f.input_active)
+            || self.diff_search.as_ref().is_some_and(|s| s.input_active)
+            || self.symbol_popup.is_some()
+            || self.hunk_peek.is_some()
+            || self.hunk_explain.is_some()
+            || self.blame_popup.is_some()
+            || self.theme_picker.is_some()
+            || self.reaction_picker.is_some()
+            || self
+                .git_ops_state
+                .as_ref()
+                .is_some_and(|ops| ops.pending_confirm.is_some())
+    }
+
+    fn show_read_only_denial(&mut self, action: WriteAction) {
+        let message = format!("Read-only mode: {} disabled", action.label());
+        if action.is_git_op() {
+            if let Some(ref mut ops) = self.git_ops_state {
+                ops.op_message = Some((message, Instant::now()));
+                return;
+            }
+        }
+        self.cmt.submission_result = Some((false, message));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+}
@@ -0,0 +1,40 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::types::{ReviewAction, ReviewPickerOutcome, ReviewPickerState};
+use super::App;
+
+impl App {
+    /// Approve / Request changes / Comment を選ぶピッカーを開く
+    pub(crate) fn open_review_picker(&mut self) {
+        let pending = &self.cmt.pending_comments;
+        let suggestions = pending
+            .iter()
+            .filter(|c| c.body.contains("```suggestion"))
+            .count();
+        self.review_picker = Some(ReviewPickerState::new(pending.len(), suggestions));
+    }
+
+    /// ピッカー表示中のキー処理。確定した判定を返す（表示中は全キーを消費する）。
+    pub(crate) fn handle_review_picker_key(&mut self, key: &KeyEvent) -> Option<ReviewAction> {
+        let kb = &self.config.keybindings;
+        let close = key.code == KeyCode::Esc || self.matches_single_key(key, &kb.quit);
+        let down = key.code == KeyCode::Down || self.matches_single_key(key, &kb.move_down);
+        let up = key.code == KeyCode::Up || self.matches_single_key(key, &kb.move_up);
+        let enter = key.code == KeyCode::Enter;
+
+        let picker = self.review_picker.as_mut()?;
+        if close {
+            self.review_picker = None;
+        } else if down {
+            picker.move_down();
+        } else if up {
+            picker.move_up();
+        } else if enter {
+            if let ReviewPickerOutcome::Submit(action) = picker.choose() {
+                self.review_picker = None;
+                return Some(action);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use super::App;
+
+/// 稼働中のみ時間を積算するストップウォッチ
+///
+/// 外部エディタ等で TUI を離れている間は `pause` し、実際にレビューしていた時間だけを数える。
+#[derive(Debug, Default)]
+pub(crate) struct ReviewTimer {
+    /// 計測再開時刻（停止中は None）
+    running_since: Option<Instant>,
+    /// 停止までに積算した時間
+    accumulated: Duration,
+}
+
+impl ReviewTimer {
+    pub(crate) fn resume(&mut self, now: Instant) {
+        if self.running_since.is_none() {
+            self.running_since = Some(now);
+        }
+    }
+
+    pub(crate) fn pause(&mut self, now: Instant) {
+        if let Some(since) = self.running_since.take() {
+            self.accumulated += now.saturating_duration_since(since);
+        }
+    }
+
+    pub(crate) fn elapsed(&self, now: Instant) -> Duration {
+        self.accumulated
+            + self
+                .running_since
+                .map_or(Duration::ZERO, |since| now.saturating_duration_since(since))
+    }
+}
+
+/// ローカルのみで集計するレビューセッションの統計（`session.review_timer` 有効時に表示）
+#[derive(Debug, Default)]
+pub(crate) struct ReviewStats {
+    pub(crate) timer: ReviewTimer,
+    /// diff を表示したファイル
+    pub(crate) files_viewed: HashSet<String>,
+    /// 投稿（pending / local 含む）したコメント数
+    pub(crate) comments_made: usize,
+}
+
+/// ステータスバー用の経過時間表示（`mm:ss`、1 時間以上は `h:mm:ss`）
+pub(crate) fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+impl App {
+    /// TUI を離れる前に呼ぶ（外部エディタ等の間はレビュー時間に数えない）
+    pub(crate) fn pause_review_timer(&mut self) {
+        self.review_stats.timer.pause(Instant::now());
+    }
+
+    /// TUI に戻った後に呼ぶ
+    pub(crate) fn resume_review_timer(&mut self) {
+        self.review_stats.timer.resume(Instant::now());
+    }
+
+    /// ステータスバーに出すタイマー表示（無効時は None）
+    pub fn review_timer_display(&self) -> Option<String> {
+        self.config
+            .session
+            .review_timer
+            .then(|| format_elapsed(self.review_stats.timer.elapsed(Instant::now())))
+    }
+
+    pub(crate) fn record_file_viewed(&mut self, file_index: usize) {
+        let Some(file) = self.files().get(file_index) else {
+            return;
+        };
+        if !self.review_stats.files_viewed.contains(&file.filename) {
+            let filename = file.filename.clone();
+            self.review_stats.files_viewed.insert(filename);
+        }
+    }
+
+    pub(crate) fn record_comment_made(&mut self) {
+        self.review_stats.comments_made += 1;
+    }
+
+    /// 終了時に表示する統計行（無効時は None）
+    pub(crate) fn review_stats_summary(&self) -> Option<String> {
+        if !self.config.session.review_timer {
+            return None;
+        }
+        let stats = &self.review_stats;
+        Some(format!(
+            "Review session: {} files viewed, {} comments made, {} active",
+            stats.files_viewed.len(),
+            stats.comments_made,
+            format_elapsed(stats.timer.elapsed(Instant::now()))
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_timer_excludes_editor_suspend() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut timer = ReviewTimer::default();
+
+        timer.resume(at(0));
+        assert_eq!(timer.elapsed(at(30)), Duration::from_secs(30));
+
+        // 30s 時点で外部エディタへ → 150s 時点で復帰
+        timer.pause(at(30));
+        assert_eq!(timer.elapsed(at(100)), Duration::from_secs(30));
+        // 停止中の二重 pause は無視
+        timer.pause(at(120));
+        timer.resume(at(150));
+        // 稼働中の二重 resume で起点がずれない
+        timer.resume(at(160));
+
+        assert_eq!(timer.elapsed(at(200)), Duration::from_secs(80));
+        assert_eq!(format_elapsed(timer.elapsed(at(200))), "01:20");
+        assert_eq!(format_elapsed(Duration::from_secs(3725)), "1:02:05");
+    }
+}
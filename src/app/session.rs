@@ -0,0 +1,296 @@
+use std::time::{Duration, Instant};
+
+use crate::cache::{self, CommentDraft, ReviewSessionState};
+
+use super::types::{AppState, DataState, InputMode, LineInputContext};
+use super::App;
+
+/// 前回この PR を正常に離れたときの閲覧位置を読み込む（読めなければ復元しない）
+pub(super) fn load_closed_session(repo: &str, pr_number: u32) -> Option<ReviewSessionState> {
+    match cache::load_review_session(repo, pr_number) {
+        Ok(saved) => saved.filter(|s| s.closed),
+        Err(e) => {
+            tracing::warn!("Failed to load review session: {}", e);
+            None
+        }
+    }
+}
+
+/// レビューセッション自動保存の進行状態
+#[derive(Debug, Default)]
+pub(crate) struct SessionAutoSave {
+    /// 直近に観測したセッション状態
+    last_seen: Option<ReviewSessionState>,
+    /// `last_seen` が変化した時刻（無操作時間の起点）
+    changed_at: Option<Instant>,
+    /// `last_seen` をディスクへ保存済みか
+    saved: bool,
+    /// 復元確認中の保存済みセッション
+    pub(crate) pending_restore: Option<ReviewSessionState>,
+}
+
+impl App {
+    /// 現在の PR のレビューセッション状態を取り出す（PR を開いていなければ None）
+    pub(crate) fn capture_review_session(&self) -> Option<ReviewSessionState> {
+        if self.local_mode
+            || self.state == AppState::PullRequestList
+            || !matches!(self.data_state, DataState::Loaded { .. })
+        {
+            return None;
+        }
+        let pr_number = self.pr_number?;
+        let view = if self.state == AppState::TextInput {
+            self.preview_return_state
+        } else {
+            self.state
+        };
+
+        let files = self.files();
+        let comment_draft = match &self.input_mode {
+            Some(InputMode::Comment(ctx)) => Some((ctx, None)),
+            Some(InputMode::Suggestion {
+                context,
+                original_code,
+            }) => Some((context, Some(original_code.clone()))),
+            _ => None,
+        }
+        .filter(|_| self.state == AppState::TextInput && !self.input_text_area.is_empty())
+        .and_then(|(ctx, original_code)| {
+            Some(CommentDraft {
+                path: files.get(ctx.file_index)?.filename.clone(),
+                line_number: ctx.line_number,
+                diff_position: ctx.diff_position,
+                start_line_number: ctx.start_line_number,
+                side: ctx.side,
+                original_code,
+                body: self.input_text_area.content(),
+            })
+        });
+
+        Some(ReviewSessionState {
+            selected_path: files.get(self.selected_file).map(|f| f.filename.clone()),
+            file_list_scroll_offset: self.file_list_scroll_offset,
+            in_diff_view: matches!(view, AppState::DiffView | AppState::SplitViewDiff),
+            selected_line: self.diff_scroll.selected_line,
+            scroll_offset: self.diff_scroll.scroll_offset,
+            comment_draft,
+            ..ReviewSessionState::new(pr_number)
+        })
+    }
+
+    /// イベントループ毎に呼ばれ、状態が `auto_save_secs` 秒変化しなければ保存する
+    ///
+    /// 状態が変わるたびに panic hook 用のスナップショットも更新する。
+    pub(crate) fn tick_session_autosave(&mut self) {
+        let secs = self.config.session.auto_save_secs;
+        if secs == 0 || self.session_autosave.pending_restore.is_some() {
+            return;
+        }
+
+        let current = self.capture_review_session();
+        if current != self.session_autosave.last_seen {
+            let previous_pr = self
+                .session_autosave
+                .last_seen
+                .as_ref()
+                .map(|s| s.pr_number);
+            let current_pr = current.as_ref().map(|s| s.pr_number);
+            if previous_pr != current_pr {
+                // PR を離れた場合は正常終了扱いで閲覧位置のみ残す
+                if let Some(previous) = self.session_autosave.last_seen.take() {
+                    self.save_closed_session(previous);
+                }
+                if let Some(pr_number) = current_pr {
+                    if self.offer_session_restore(pr_number, current.as_ref()) {
+                        return;
+                    }
+                }
+            }
+            self.observe_review_session(current);
+            return;
+        }
+
+        let idle = self
+            .session_autosave
+            .changed_at
+            .is_some_and(|t| t.elapsed() >= Duration::from_secs(secs));
+        if !self.session_autosave.saved && idle {
+            if let Some(ref state) = self.session_autosave.last_seen {
+                if let Err(e) = cache::save_review_session(&self.repo, state) {
+                    tracing::warn!("Failed to save review session: {}", e);
+                }
+            }
+            self.session_autosave.saved = true;
+        }
+    }
+
+    /// 保存済みセッションがあり現在と異なれば復元確認を開始する
+    fn offer_session_restore(
+        &mut self,
+        pr_number: u32,
+        current: Option<&ReviewSessionState>,
+    ) -> bool {
+        match cache::load_review_session(&self.repo, pr_number) {
+            Ok(Some(saved)) if !saved.closed && Some(&saved) != current => {
+                self.session_autosave.pending_restore = Some(saved);
+                true
+            }
+            Ok(_) => false,
+            Err(e) => {
+                tracing::warn!("Discarding unreadable review session: {}", e);
+                cache::delete_review_session(&self.repo, pr_number);
+                false
+            }
+        }
+    }
+
+    fn observe_review_session(&mut self, current: Option<ReviewSessionState>) {
+        let snapshot = current.as_ref().and_then(|state| {
+            let path = cache::review_session_path(&self.repo, state.pr_number).ok()?;
+            let json = cache::serialize_review_session(state).ok()?;
+            Some((path, json))
+        });
+        cache::set_crash_session_snapshot(snapshot);
+        self.session_autosave.last_seen = current;
+        self.session_autosave.changed_at = Some(Instant::now());
+        self.session_autosave.saved = false;
+    }
+
+    /// 復元確認: 保存済みセッションを適用する
+    pub(crate) fn restore_review_session(&mut self) {
+        let Some(saved) = self.session_autosave.pending_restore.take() else {
+            return;
+        };
+
+        if self.apply_session_position(&saved)
+            && saved.in_diff_view
+            && matches!(self.state, AppState::FileList | AppState::SplitViewFileList)
+        {
+            self.enter_diff_from_file_list();
+        }
+
+        if let Some(draft) = saved.comment_draft {
+            if let Some(idx) = self.files().iter().position(|f| f.filename == draft.path) {
+                let context = LineInputContext {
+                    file_index: idx,
+                    line_number: draft.line_number,
+                    diff_position: draft.diff_position,
+                    start_line_number: draft.start_line_number,
+                    side: draft.side,
+                };
+                let is_suggestion = draft.original_code.is_some();
+                self.input_mode = Some(match draft.original_code {
+                    Some(original_code) => InputMode::Suggestion {
+                        context,
+                        original_code,
+                    },
+                    None => InputMode::Comment(context),
+                });
+                self.input_text_area.set_content(&draft.body);
+                self.preview_return_state = self.state;
+                self.state = AppState::TextInput;
+                if is_suggestion {
+                    self.update_suggestion_highlight_cache();
+                }
+            }
+        }
+
+        self.cmt.submission_result = Some((true, "Restored previous session".to_string()));
+        self.cmt.submission_result_time = Some(Instant::now());
+        let current = self.capture_review_session();
+        self.observe_review_session(current);
+    }
+
+    /// 復元確認: 保存済みセッションを破棄する
+    pub(crate) fn discard_review_session(&mut self) {
+        let Some(saved) = self.session_autosave.pending_restore.take() else {
+            return;
+        };
+        cache::delete_review_session(&self.repo, saved.pr_number);
+        let current = self.capture_review_session();
+        self.observe_review_session(current);
+    }
+
+    /// 保存済みの選択ファイルと diff の位置を適用する（ファイルが消えていれば false）
+    fn apply_session_position(&mut self, saved: &ReviewSessionState) -> bool {
+        let Some(idx) = saved
+            .selected_path
+            .as_deref()
+            .and_then(|path| self.files().iter().position(|f| f.filename == path))
+        else {
+            return false;
+        };
+        self.selected_file = idx;
+        self.file_list_scroll_offset = saved.file_list_scroll_offset.min(idx);
+        self.sync_diff_to_selected_file();
+        let line_count = self.diff_scroll.line_count;
+        // patch 未取得 (lazy) の場合は行数 0 なので保存値をそのまま使う
+        let clamp = |v: usize| {
+            if line_count > 0 {
+                v.min(line_count - 1)
+            } else {
+                v
+            }
+        };
+        self.diff_scroll.selected_line = clamp(saved.selected_line);
+        self.diff_scroll.scroll_offset = clamp(saved.scroll_offset);
+        true
+    }
+
+    /// 初回ロード時に前回 PR を離れたときの閲覧位置を復元する
+    ///
+    /// 保存時のファイルが変更ファイルから消えていれば先頭ファイルに戻す。
+    pub(crate) fn apply_review_position_on_load(&mut self) {
+        let Some(saved) = self.pending_review_position.take() else {
+            return;
+        };
+        if self.local_mode {
+            return;
+        }
+        if !self.apply_session_position(&saved) {
+            self.selected_file = 0;
+            self.file_list_scroll_offset = 0;
+            self.sync_diff_to_selected_file();
+        }
+    }
+
+    /// 正常終了・PR を離れる際: 閲覧位置のみを保存し、クラッシュ復旧用の保存分を置き換える
+    ///
+    /// 復元確認中なら保存済みセッションをそのまま残す。
+    pub(crate) fn close_review_session(&mut self) {
+        let last_seen = self.session_autosave.last_seen.take();
+        cache::set_crash_session_snapshot(None);
+        if self.session_autosave.pending_restore.is_some() {
+            return;
+        }
+        if let Some(state) = self.capture_review_session().or(last_seen) {
+            self.save_closed_session(state);
+        }
+    }
+
+    /// 次回この PR を開いたときに確認なしで復元する閲覧位置として保存する（best-effort）
+    fn save_closed_session(&self, state: ReviewSessionState) {
+        let state = ReviewSessionState {
+            closed: true,
+            comment_draft: None,
+            ..state
+        };
+        if let Err(e) = cache::save_review_session(&self.repo, &state) {
+            tracing::warn!("Failed to save review session: {}", e);
+        }
+    }
+
+    pub fn is_pending_session_restore(&self) -> bool {
+        self.session_autosave.pending_restore.is_some()
+    }
+
+    /// Footer text for the session restore prompt.
+    pub fn session_restore_footer_text(&self) -> String {
+        let kb = &self.config.keybindings;
+        format!(
+            "Restore previous session? {}: restore | {}/Esc: discard",
+            kb.confirm_yes.display(),
+            kb.confirm_no.display(),
+        )
+    }
+}
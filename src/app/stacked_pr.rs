@@ -0,0 +1,75 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::github;
+
+use super::App;
+
+impl App {
+    /// stacked PR: この PR の base ブランチを head とする PR へ切り替える
+    ///
+    /// base ブランチから PR 番号への解決は `gh pr list --head` で非同期に行い、
+    /// 結果は `poll_base_pr_updates` で反映する。
+    pub(crate) fn jump_to_base_pr(&mut self) {
+        if self.local_mode {
+            self.cmt.submission_result = Some((
+                false,
+                "Stacked PR navigation is unavailable in local mode".to_string(),
+            ));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        }
+        if self.base_pr_receiver.is_some() {
+            return;
+        }
+        let Some(base_ref) = self.pr().map(|pr| pr.base.ref_name.clone()) else {
+            return;
+        };
+
+        let (tx, rx) = mpsc::channel(1);
+        self.base_pr_receiver = Some((base_ref.clone(), rx));
+        self.cmt.submission_result =
+            Some((true, format!("Resolving base PR for '{}'...", base_ref)));
+        self.cmt.submission_result_time = Some(Instant::now());
+
+        let repo = self.repo.clone();
+        let github = self.github.clone();
+        tokio::spawn(async move {
+            let result = github::find_open_pr_by_head(&*github, &repo, &base_ref).await;
+            let _ = tx.send(result.map_err(|e| e.to_string())).await;
+        });
+    }
+
+    pub(crate) fn poll_base_pr_updates(&mut self) {
+        let Some((_, ref mut rx)) = self.base_pr_receiver else {
+            return;
+        };
+
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.base_pr_receiver = None;
+                return;
+            }
+        };
+        let Some((base_ref, _)) = self.base_pr_receiver.take() else {
+            return;
+        };
+
+        self.cmt.submission_result = Some(match result {
+            Ok(Some(number)) if Some(number) != self.pr_number => {
+                self.select_pr(number);
+                (true, format!("Switched to base PR #{}", number))
+            }
+            // base が main 等の通常ブランチ → stack なし
+            Ok(_) => (
+                false,
+                format!("Base branch '{}' is not the head of an open PR", base_ref),
+            ),
+            Err(e) => (false, format!("Failed to resolve base PR: {}", e)),
+        });
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+}
@@ -2,12 +2,20 @@ use anyhow::Result;
 use crossterm::event;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io::Stdout;
+use std::time::{Duration, Instant};
 
+use crate::filter::ListFilter;
 use crate::keybinding::{event_to_keybinding, SequenceMatch};
+use crate::symbol::{find_definition_in_patches, PatchSymbolTable};
 
 use super::types::*;
 use super::App;
 
+/// これを超える件数を一括で開く場合は確認を挟む
+const OPEN_ALL_FILES_WARN_THRESHOLD: usize = 20;
+/// 件数警告から再押下で実行できるまでの猶予
+const OPEN_ALL_FILES_CONFIRM_WINDOW: Duration = Duration::from_secs(5);
+
 impl App {
     pub(crate) fn push_jump_location(&mut self) {
         let loc = JumpLocation {
@@ -109,6 +117,36 @@ impl App {
         Ok(())
     }
 
+    /// diff パッチ内の定義元へジャンプする。見つからなければ false
+    ///
+    /// 定義表は PR データのロード後に一度だけ作り、パッチが変わるまで使い回す。
+    pub(crate) fn jump_to_definition_in_patches(&mut self, symbol: &str) -> bool {
+        if self.patch_symbol_table.is_none() {
+            self.patch_symbol_table = Some(PatchSymbolTable::build(self.files()));
+        }
+        let Some(table) = self.patch_symbol_table.as_ref() else {
+            return false;
+        };
+        let Some((file_idx, line_idx)) =
+            find_definition_in_patches(symbol, self.files(), table, self.selected_file)
+        else {
+            return false;
+        };
+
+        self.push_jump_location();
+        let file_changed = self.selected_file != file_idx;
+        self.selected_file = file_idx;
+        self.diff_scroll.selected_line = line_idx;
+        self.diff_scroll.scroll_offset = line_idx;
+
+        if file_changed {
+            self.update_diff_line_count();
+            self.update_file_comment_positions();
+            self.ensure_diff_cache();
+        }
+        true
+    }
+
     /// シンボルの定義元へジャンプ（diff パッチ内 → リポジトリ全体、非同期）
     pub(crate) async fn jump_to_symbol_definition_async(
         &mut self,
@@ -116,21 +154,7 @@ impl App {
         _terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<()> {
         // Phase 1: diff パッチ内を検索
-        let files: Vec<crate::github::ChangedFile> = self.files().to_vec();
-        if let Some((file_idx, line_idx)) =
-            crate::symbol::find_definition_in_patches(symbol, &files, self.selected_file)
-        {
-            self.push_jump_location();
-            let file_changed = self.selected_file != file_idx;
-            self.selected_file = file_idx;
-            self.diff_scroll.selected_line = line_idx;
-            self.diff_scroll.scroll_offset = line_idx;
-
-            if file_changed {
-                self.update_diff_line_count();
-                self.update_file_comment_positions();
-                self.ensure_diff_cache();
-            }
+        if self.jump_to_definition_in_patches(symbol) {
             return Ok(());
         }
 
@@ -228,6 +252,7 @@ impl App {
         };
 
         // TUI 一時停止 → エディタ → TUI 復帰
+        self.pause_review_timer();
         crate::ui::restore_terminal(terminal)?;
         let _ = crate::editor::open_file_at_line(
             self.config.editor.as_deref(),
@@ -235,6 +260,94 @@ impl App {
             line_number.unwrap_or(1) as usize,
         );
         *terminal = crate::ui::setup_terminal()?;
+        self.resume_review_timer();
+
+        Ok(())
+    }
+
+    /// 一括オープンの対象ファイル（リポジトリルートからのパス）を返す。
+    ///
+    /// local mode 以外、または件数が閾値を超えていて未確認の場合は
+    /// メッセージを出して None を返す。削除されたファイルは作業ツリーに無いので除く。
+    pub(crate) fn take_files_to_open_in_editor(&mut self) -> Option<Vec<String>> {
+        if !self.local_mode {
+            self.cmt.submission_result = Some((
+                false,
+                "Opening all files is only available in local mode".to_string(),
+            ));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return None;
+        }
+        let files: Vec<String> = self
+            .files()
+            .iter()
+            .filter(|file| file.status != "removed")
+            .map(|file| file.filename.clone())
+            .collect();
+        if files.is_empty() {
+            self.cmt.submission_result = Some((false, "No files to open".to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return None;
+        }
+
+        let confirmed = self
+            .open_all_files_confirm_at
+            .take()
+            .is_some_and(|at| at.elapsed() < OPEN_ALL_FILES_CONFIRM_WINDOW);
+        if files.len() > OPEN_ALL_FILES_WARN_THRESHOLD && !confirmed {
+            self.open_all_files_confirm_at = Some(Instant::now());
+            self.cmt.submission_result = Some((
+                false,
+                format!(
+                    "Open {} files in editor? Press {} again to confirm",
+                    files.len(),
+                    self.config.keybindings.open_all_files_in_editor.display()
+                ),
+            ));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return None;
+        }
+        Some(files)
+    }
+
+    /// 変更ファイルをすべて 1 回のエディタ起動で開く（gF キー、local mode 専用）
+    pub(crate) async fn open_all_files_in_editor(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<()> {
+        let Some(files) = self.take_files_to_open_in_editor() else {
+            return Ok(());
+        };
+
+        let mut command = tokio::process::Command::new("git");
+        command.args(["rev-parse", "--show-toplevel"]);
+        if let Some(dir) = &self.working_dir {
+            command.current_dir(dir);
+        }
+        let root = match command.output().await {
+            Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).trim().to_string(),
+            _ => return Ok(()),
+        };
+        let paths: Vec<String> = files
+            .iter()
+            .map(|filename| {
+                std::path::Path::new(&root)
+                    .join(filename)
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+
+        // TUI 一時停止 → エディタ → TUI 復帰
+        self.pause_review_timer();
+        crate::ui::restore_terminal(terminal)?;
+        let result = crate::editor::open_files(self.config.editor.as_deref(), &paths);
+        *terminal = crate::ui::setup_terminal()?;
+        self.resume_review_timer();
+        if let Err(e) = result {
+            self.cmt.submission_result = Some((false, e.to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+        }
 
         Ok(())
     }
@@ -261,10 +374,8 @@ impl App {
             return Ok(());
         }
 
-        if !self.local_mode && self.matches_single_key(&key, &kb.open_in_browser) {
-            if let Some(pr_number) = self.pr_number {
-                self.open_pr_in_browser(pr_number);
-            }
+        if self.matches_single_key(&key, &kb.open_in_browser) {
+            self.open_current_pr_in_browser();
             return Ok(());
         }
 
@@ -331,11 +442,57 @@ impl App {
         Ok(())
     }
 
+    /// Keybindings タブの絞り込み入力。入力中なら true（キーを消費した）
+    fn handle_help_filter_input(&mut self, key: &event::KeyEvent) -> bool {
+        let Some(filter) = self.help_filter.as_mut() else {
+            return false;
+        };
+        if !filter.input_active {
+            return false;
+        }
+        match key.code {
+            event::KeyCode::Esc => self.help_filter = None,
+            event::KeyCode::Enter => {
+                if filter.query.is_empty() {
+                    self.help_filter = None;
+                } else {
+                    filter.input_active = false;
+                }
+            }
+            event::KeyCode::Backspace => filter.delete_char(),
+            event::KeyCode::Char('u') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+                filter.clear_query()
+            }
+            event::KeyCode::Char(c) if !key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+                filter.insert_char(c)
+            }
+            _ => return true,
+        }
+        self.help_scroll_offset = 0;
+        true
+    }
+
     /// Help viewport height: tab header (3) + content borders (2) + footer (1)
     pub(crate) const HELP_VIEWPORT_OVERHEAD: u16 = 6;
 
     pub(crate) fn apply_help_scroll(&mut self, key: event::KeyEvent, terminal_height: u16) {
         let kb = self.config.keybindings.clone();
+        if self.handle_help_filter_input(&key) {
+            return;
+        }
+        if self.help_tab == HelpTab::Keybindings
+            && self.matches_single_key(&key, &kb.search_in_diff)
+        {
+            self.help_filter
+                .get_or_insert_with(ListFilter::new)
+                .input_active = true;
+            return;
+        }
+        if key.code == event::KeyCode::Esc && self.help_filter.take().is_some() {
+            self.help_scroll_offset = 0;
+            return;
+        }
+
         if self.matches_single_key(&key, &kb.tab_prev)
             || self.matches_single_key(&key, &kb.tab_next)
         {
@@ -365,7 +522,14 @@ impl App {
             }
         }
 
-        let visible_lines = terminal_height.saturating_sub(Self::HELP_VIEWPORT_OVERHEAD) as usize;
+        // 絞り込みバー（3 行）の分だけ表示領域が狭まる
+        let filter_bar = if self.help_tab == HelpTab::Keybindings && self.help_filter.is_some() {
+            3
+        } else {
+            0
+        };
+        let visible_lines =
+            terminal_height.saturating_sub(Self::HELP_VIEWPORT_OVERHEAD + filter_bar) as usize;
         let half_page = (visible_lines / 2).max(1);
 
         let mut offset = match self.help_tab {
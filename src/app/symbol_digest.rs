@@ -0,0 +1,152 @@
+use crossterm::event;
+
+use crate::keybinding::{event_to_keybinding, SequenceMatch};
+use crate::symbol::changed_symbols;
+
+use super::types::{SymbolDigestRow, SymbolDigestState};
+use super::{App, AppState};
+
+impl App {
+    /// ファイルごとの変更シンボル一覧を開く（開いていれば閉じる）
+    pub(crate) fn toggle_symbol_digest(&mut self) {
+        if self.state == AppState::SymbolDigest {
+            self.close_symbol_digest();
+            return;
+        }
+
+        let mut rows = Vec::new();
+        let mut selected = 0;
+        for (file_index, file) in self.files().iter().enumerate() {
+            if file_index == self.selected_file {
+                selected = rows.len();
+            }
+            rows.push(SymbolDigestRow {
+                file_index,
+                symbol: None,
+            });
+            let Some(patch) = file.patch.as_deref() else {
+                continue;
+            };
+            rows.extend(
+                changed_symbols(&file.filename, patch)
+                    .into_iter()
+                    .map(|symbol| SymbolDigestRow {
+                        file_index,
+                        symbol: Some(symbol),
+                    }),
+            );
+        }
+        if rows.is_empty() {
+            return;
+        }
+
+        self.symbol_digest = Some(SymbolDigestState {
+            rows,
+            selected,
+            scroll_offset: 0,
+            return_state: self.state,
+        });
+        self.state = AppState::SymbolDigest;
+    }
+
+    fn close_symbol_digest(&mut self) {
+        if let Some(digest) = self.symbol_digest.take() {
+            self.state = digest.return_state;
+        }
+    }
+
+    pub(crate) fn handle_symbol_digest_input(&mut self, key: event::KeyEvent) {
+        let kb = self.config.keybindings.clone();
+        let Some(row_count) = self.symbol_digest.as_ref().map(|d| d.rows.len()) else {
+            self.state = AppState::FileList;
+            return;
+        };
+
+        if self.matches_single_key(&key, &kb.quit)
+            || self.matches_single_key(&key, &kb.symbol_digest)
+        {
+            self.close_symbol_digest();
+            return;
+        }
+
+        if self.matches_single_key(&key, &kb.move_down) {
+            if let Some(ref mut digest) = self.symbol_digest {
+                digest.selected = (digest.selected + 1).min(row_count.saturating_sub(1));
+            }
+            return;
+        }
+
+        if self.matches_single_key(&key, &kb.move_up) {
+            if let Some(ref mut digest) = self.symbol_digest {
+                digest.selected = digest.selected.saturating_sub(1);
+            }
+            return;
+        }
+
+        if let Some(kb_event) = event_to_keybinding(&key) {
+            self.check_sequence_timeout();
+
+            if !self.pending_keys.is_empty() {
+                self.push_pending_key(kb_event);
+                if self.try_match_sequence(&kb.jump_to_first) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    if let Some(ref mut digest) = self.symbol_digest {
+                        digest.selected = 0;
+                    }
+                    return;
+                }
+                self.clear_pending_keys();
+            } else if self.key_could_match_sequence(&key, &kb.jump_to_first) {
+                self.push_pending_key(kb_event);
+                return;
+            }
+        }
+
+        if self.matches_single_key(&key, &kb.jump_to_last) {
+            if let Some(ref mut digest) = self.symbol_digest {
+                digest.selected = row_count.saturating_sub(1);
+            }
+            return;
+        }
+
+        if self.matches_single_key(&key, &kb.open_panel) {
+            self.open_symbol_digest_row();
+            return;
+        }
+
+        if self.matches_single_key(&key, &kb.help) {
+            self.open_help(AppState::SymbolDigest);
+        }
+    }
+
+    /// 選択行のファイル（シンボル行なら該当 hunk の行）を diff view で開く
+    pub(crate) fn open_symbol_digest_row(&mut self) {
+        let Some(row) = self
+            .symbol_digest
+            .as_ref()
+            .and_then(|d| d.rows.get(d.selected))
+            .cloned()
+            .filter(|row| row.file_index < self.files().len())
+        else {
+            return;
+        };
+
+        self.selected_file = row.file_index;
+        self.diff_view_return_state = AppState::SymbolDigest;
+        self.state = AppState::DiffView;
+        self.multiline_selection = None;
+        self.hunk_filter = None;
+        self.diff_search = None;
+        self.line_endings_expanded = false;
+        self.diff_scroll.selected_line = 0;
+        self.diff_scroll.scroll_offset = 0;
+        self.update_diff_line_count();
+        self.update_file_comment_positions();
+        self.ensure_diff_cache();
+
+        if let Some(symbol) = row.symbol {
+            self.diff_scroll.selected_line = symbol.line_index;
+            self.diff_scroll.scroll_offset = symbol.line_index;
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::types::{MarkViewedResult, PendingApproveChoice};
+use super::types::{MarkViewedResult, PendingApproveChoice, ReactionUpdate};
 use super::*;
 use crossterm::event::{self, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 use lasso::Rodeo;
@@ -8,25 +8,35 @@ use tempfile::tempdir;
 use crate::cache::{PrCacheKey, PrData};
 use crate::github::comment::ReviewComment;
 use crate::github::{ChangedFile, PrCommit, PullRequest};
-use crate::loader::DataLoadResult;
+use crate::loader::{DataLoadResult, DataRevision};
 
 struct ScopedCacheHome {
+    var: &'static str,
     old: Option<std::ffi::OsString>,
 }
 
 impl ScopedCacheHome {
     fn new(dir: &std::path::Path) -> Self {
-        let old = std::env::var_os("XDG_CACHE_HOME");
-        unsafe { std::env::set_var("XDG_CACHE_HOME", dir) };
-        Self { old }
+        Self::set("XDG_CACHE_HOME", dir)
+    }
+
+    /// グローバル設定（`$XDG_CONFIG_HOME/octorus/config.toml`）の置き場所を差し替える
+    fn config(dir: &std::path::Path) -> Self {
+        Self::set("XDG_CONFIG_HOME", dir)
+    }
+
+    fn set(var: &'static str, dir: &std::path::Path) -> Self {
+        let old = std::env::var_os(var);
+        unsafe { std::env::set_var(var, dir) };
+        Self { var, old }
     }
 }
 
 impl Drop for ScopedCacheHome {
     fn drop(&mut self) {
         match self.old.take() {
-            Some(v) => unsafe { std::env::set_var("XDG_CACHE_HOME", v) },
-            None => unsafe { std::env::remove_var("XDG_CACHE_HOME") },
+            Some(v) => unsafe { std::env::set_var(self.var, v) },
+            None => unsafe { std::env::remove_var(self.var) },
         }
     }
 }
@@ -124,6 +134,65 @@ fn test_get_comment_indices_at_current_line() {
     assert!(indices.is_empty());
 }
 
+#[test]
+fn test_thread_collapse_and_expand_in_comment_panel() {
+    let comment = |id: u64, body: &str, reply_to: Option<u64>| ReviewComment {
+        id,
+        path: "src/a.rs".to_string(),
+        line: Some(1),
+        start_line: None,
+        body: body.to_string(),
+        user: crate::github::User {
+            login: "alice".to_string(),
+        },
+        created_at: "2026-04-01T00:00:00Z".to_string(),
+        in_reply_to_id: reply_to,
+        reactions: Default::default(),
+    };
+    let mut app = App::new_for_test();
+    app.apply_review_comments(vec![
+        comment(1, "first line\nsecond line", None),
+        comment(2, "reply", Some(1)),
+    ]);
+    app.cmt.file_comment_positions = vec![
+        CommentPosition {
+            diff_line_index: 3,
+            comment_index: 0,
+        },
+        CommentPosition {
+            diff_line_index: 3,
+            comment_index: 1,
+        },
+    ];
+    app.cmt.file_comment_lines = [3].into();
+    app.diff_scroll.selected_line = 3;
+    app.cmt.selected_inline_comment = 1;
+    assert_eq!(app.visible_inline_comment_indices(), vec![0, 1]);
+
+    app.collapse_all_threads();
+    assert_eq!(app.visible_inline_comment_indices(), vec![0]);
+    // diff 上のコメント記号も折りたたみ表示になる
+    assert!(app.cmt.file_collapsed_comment_lines.contains(&3));
+    assert_eq!(app.cmt.selected_inline_comment, 0);
+    assert_eq!(
+        app.collapsed_thread_summary(0),
+        "▸ @alice: first line (1 reply)"
+    );
+    assert_eq!(app.comment_panel_content_lines(80), 1);
+
+    // 個別トグルで展開、再度トグルで折りたたみ
+    app.toggle_selected_thread();
+    assert!(!app.is_thread_collapsed(0));
+    assert_eq!(app.visible_inline_comment_indices(), vec![0, 1]);
+    assert!(app.cmt.file_collapsed_comment_lines.is_empty());
+    app.toggle_selected_thread();
+    assert!(app.is_thread_collapsed(1));
+
+    app.expand_all_threads();
+    assert_eq!(app.visible_inline_comment_indices(), vec![0, 1]);
+    assert!(app.cmt.thread_fold_overrides.is_empty());
+}
+
 #[test]
 fn test_jump_to_next_comment_basic() {
     let config = Config::default();
@@ -228,6 +297,58 @@ fn test_jump_with_empty_positions() {
     assert_eq!(app.diff_scroll.selected_line, 10);
 }
 
+#[test]
+fn test_jump_to_comment_wraps_when_configured() {
+    let mut config = Config::default();
+    config.diff.wrap_comment_navigation = true;
+    let (mut app, _) = App::new_loading("owner/repo", 1, config);
+    app.cmt.file_comment_positions = vec![
+        CommentPosition {
+            diff_line_index: 5,
+            comment_index: 0,
+        },
+        CommentPosition {
+            diff_line_index: 10,
+            comment_index: 1,
+        },
+    ];
+
+    app.diff_scroll.selected_line = 10;
+    app.jump_to_next_comment();
+    assert_eq!(app.diff_scroll.selected_line, 5);
+
+    app.jump_to_prev_comment();
+    assert_eq!(app.diff_scroll.selected_line, 10);
+}
+
+#[test]
+fn test_jump_to_first_and_last_comment() {
+    let config = Config::default();
+    let (mut app, _) = App::new_loading("owner/repo", 1, config);
+    app.cmt.file_comment_positions = vec![
+        CommentPosition {
+            diff_line_index: 5,
+            comment_index: 0,
+        },
+        CommentPosition {
+            diff_line_index: 10,
+            comment_index: 1,
+        },
+        CommentPosition {
+            diff_line_index: 15,
+            comment_index: 2,
+        },
+    ];
+
+    app.diff_scroll.selected_line = 12;
+    app.jump_to_last_comment();
+    assert_eq!(app.diff_scroll.selected_line, 15);
+    assert_eq!(app.diff_scroll.scroll_offset, 15);
+
+    app.jump_to_first_comment();
+    assert_eq!(app.diff_scroll.selected_line, 5);
+}
+
 #[test]
 fn test_liststate_autoscroll_with_multiline_items() {
     use ratatui::buffer::Buffer;
@@ -371,6 +492,9 @@ async fn test_pr_list_local_toggle_round_trip() {
             login: "local".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     };
     let local_files = vec![ChangedFile {
         filename: "src/main.rs".to_string(),
@@ -442,10 +566,14 @@ async fn test_poll_data_updates_discards_stale_pr_data() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     };
     tx.send(DataLoadResult::Success {
         pr: Box::new(pr),
         files: vec![],
+        revision: DataRevision::Fresh,
     })
     .await
     .unwrap();
@@ -465,6 +593,36 @@ async fn test_poll_data_updates_discards_stale_pr_data() {
     assert!(app.session_cache.get_pr_data(&cache_key).is_some());
 }
 
+#[tokio::test]
+async fn test_poll_data_updates_tracks_file_load_progress() {
+    let (mut app, tx) = App::new_loading("owner/repo", 1, Config::default());
+
+    tx.send(DataLoadResult::Progress {
+        pr_number: 1,
+        files_loaded: 300,
+    })
+    .await
+    .unwrap();
+    app.poll_data_updates();
+    assert_eq!(app.files_load_progress, Some(300));
+
+    // 別 PR の進捗は無視
+    tx.send(DataLoadResult::Progress {
+        pr_number: 2,
+        files_loaded: 500,
+    })
+    .await
+    .unwrap();
+    app.poll_data_updates();
+    assert_eq!(app.files_load_progress, Some(300));
+
+    tx.send(DataLoadResult::Error("boom".to_string()))
+        .await
+        .unwrap();
+    app.poll_data_updates();
+    assert_eq!(app.files_load_progress, None);
+}
+
 #[tokio::test]
 async fn test_poll_comment_updates_discards_stale_pr_comments() {
     let config = Config::default();
@@ -534,6 +692,9 @@ async fn test_handle_data_result_clamps_selected_file_when_files_shrink() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     // Set initial loaded state with 5 files
@@ -553,6 +714,7 @@ async fn test_handle_data_result_clamps_selected_file_when_files_shrink() {
         DataLoadResult::Success {
             pr,
             files: fewer_files,
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -598,6 +760,9 @@ async fn test_handle_data_result_resyncs_diff_state_when_selected_file_changes()
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     // Set initial loaded state with 5 files
@@ -617,6 +782,11 @@ async fn test_handle_data_result_resyncs_diff_state_when_selected_file_changes()
         interner: Rodeo::default(),
         highlighted: false,
         markdown_rich: false,
+        line_ending_hunks: Vec::new(),
+        line_ending: crate::diff::LineEnding::Lf,
+        context_runs: Vec::new(),
+        conflict_lines: Vec::new(),
+        moved_blocks: Vec::new(),
     });
 
     // Refresh with only 2 files (selected_file will be clamped from 4 to 1)
@@ -629,6 +799,7 @@ async fn test_handle_data_result_resyncs_diff_state_when_selected_file_changes()
         DataLoadResult::Success {
             pr,
             files: fewer_files,
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -687,6 +858,9 @@ async fn test_handle_data_result_resyncs_comment_positions_when_selected_file_ch
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     // Set initial loaded state with 5 files, selected_file = 4
@@ -708,6 +882,7 @@ async fn test_handle_data_result_resyncs_comment_positions_when_selected_file_ch
         },
         created_at: "2024-01-01T00:00:00Z".to_string(),
         in_reply_to_id: None,
+        reactions: Default::default(),
     }]);
 
     // Pre-populate stale comment positions for the old file
@@ -729,6 +904,7 @@ async fn test_handle_data_result_resyncs_comment_positions_when_selected_file_ch
         DataLoadResult::Success {
             pr,
             files: fewer_files,
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -792,6 +968,9 @@ async fn test_handle_data_result_preserves_diff_state_when_selected_file_unchang
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     // Set initial loaded state
@@ -811,6 +990,11 @@ async fn test_handle_data_result_preserves_diff_state_when_selected_file_unchang
         interner: Rodeo::default(),
         highlighted: false,
         markdown_rich: false,
+        line_ending_hunks: Vec::new(),
+        line_ending: crate::diff::LineEnding::Lf,
+        context_runs: Vec::new(),
+        conflict_lines: Vec::new(),
+        moved_blocks: Vec::new(),
     });
 
     // Refresh with same or more files (selected_file stays at 1)
@@ -823,6 +1007,7 @@ async fn test_handle_data_result_preserves_diff_state_when_selected_file_unchang
         DataLoadResult::Success {
             pr,
             files: same_files,
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -878,6 +1063,9 @@ async fn test_handle_data_result_keeps_selected_file_by_filename() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     app.data_state = DataState::Loaded {
@@ -892,6 +1080,7 @@ async fn test_handle_data_result_keeps_selected_file_by_filename() {
         DataLoadResult::Success {
             pr,
             files: vec![make_file("file_b.rs"), make_file("file_c.rs")],
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -901,6 +1090,120 @@ async fn test_handle_data_result_keeps_selected_file_by_filename() {
     );
 }
 
+#[tokio::test]
+async fn test_handle_data_result_fresh_replaces_stale_keeping_scroll() {
+    let config = Config::default();
+    let (mut app, _tx) = App::new_loading("owner/repo", 1, config);
+    let long_patch = format!(
+        "@@ -1,1 +1,40 @@\n{}",
+        (0..40).map(|i| format!("+line {i}\n")).collect::<String>()
+    );
+    let make_file = |name: &str| ChangedFile {
+        patch: Some(long_patch.clone()),
+        ..make_changed_file(name)
+    };
+
+    app.handle_data_result(
+        1,
+        DataLoadResult::Success {
+            pr: make_test_pr(),
+            files: vec![make_file("a.rs"), make_file("b.rs")],
+            revision: DataRevision::Stale,
+        },
+    );
+    assert!(app.showing_stale_data);
+    app.selected_file = 1;
+    app.diff_scroll.selected_line = 20;
+    app.diff_scroll.scroll_offset = 15;
+
+    // キャッシュ表示中の patch 欠けの途中経過は無視する
+    app.handle_data_result(
+        1,
+        DataLoadResult::Partial {
+            pr: make_test_pr(),
+            files: vec![make_changed_file("new.rs")],
+        },
+    );
+    assert!(matches!(app.data_state, DataState::Loaded { .. }));
+    assert_eq!(app.files().len(), 2);
+
+    // 先頭にファイルが増えても、同じファイルを見ていればスクロールを保つ
+    app.handle_data_result(
+        1,
+        DataLoadResult::Success {
+            pr: make_test_pr(),
+            files: vec![make_file("new.rs"), make_file("a.rs"), make_file("b.rs")],
+            revision: DataRevision::Fresh,
+        },
+    );
+    assert!(!app.showing_stale_data);
+    assert_eq!(app.selected_file, 2);
+    assert_eq!(app.diff_scroll.selected_line, 20);
+    assert_eq!(app.diff_scroll.scroll_offset, 15);
+}
+
+#[tokio::test]
+async fn test_background_refresh_keeps_scroll_and_auto_refresh_target() {
+    use std::sync::atomic::Ordering;
+
+    let config = Config::default();
+    let (mut app, _tx) = App::new_loading("owner/repo", 1, config);
+    let target = app.auto_refresh_handle();
+    let long_patch = format!(
+        "@@ -1,1 +1,40 @@\n{}",
+        (0..40).map(|i| format!("+line {i}\n")).collect::<String>()
+    );
+    let make_file = |name: &str| ChangedFile {
+        patch: Some(long_patch.clone()),
+        ..make_changed_file(name)
+    };
+
+    app.sync_auto_refresh_target();
+    assert_eq!(
+        target.load(Ordering::Relaxed),
+        0,
+        "nothing to refresh while loading"
+    );
+
+    app.handle_data_result(
+        1,
+        DataLoadResult::Success {
+            pr: make_test_pr(),
+            files: vec![make_file("a.rs"), make_file("b.rs")],
+            revision: DataRevision::Fresh,
+        },
+    );
+    app.state = AppState::DiffView;
+    app.sync_auto_refresh_target();
+    assert_eq!(target.load(Ordering::Relaxed), 1);
+
+    app.selected_file = 1;
+    app.diff_scroll.selected_line = 20;
+    app.diff_scroll.scroll_offset = 15;
+
+    // 自動更新の再取得: 先頭にファイルが増えても同じファイルの表示位置を保つ
+    app.handle_data_result(
+        1,
+        DataLoadResult::Success {
+            pr: make_test_pr(),
+            files: vec![make_file("new.rs"), make_file("a.rs"), make_file("b.rs")],
+            revision: DataRevision::Fresh,
+        },
+    );
+    assert_eq!(app.selected_file, 2);
+    assert_eq!(app.diff_scroll.selected_line, 20);
+    assert_eq!(app.diff_scroll.scroll_offset, 15);
+
+    // 入力中・ローカルモードでは更新しない
+    app.state = AppState::TextInput;
+    app.sync_auto_refresh_target();
+    assert_eq!(target.load(Ordering::Relaxed), 0);
+    app.state = AppState::DiffView;
+    app.set_local_mode(true);
+    app.sync_auto_refresh_target();
+    assert_eq!(target.load(Ordering::Relaxed), 0);
+}
+
 #[tokio::test]
 async fn test_handle_data_result_auto_focus_selects_next_changed_file() {
     let config = Config::default();
@@ -943,6 +1246,9 @@ async fn test_handle_data_result_auto_focus_selects_next_changed_file() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     app.data_state = DataState::Loaded {
@@ -961,6 +1267,7 @@ async fn test_handle_data_result_auto_focus_selects_next_changed_file() {
                 make_file("file_c.rs", 1), // unchanged
                 make_file("file_d.rs", 2), // changed (additions: 1→2)
             ],
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -1013,6 +1320,9 @@ async fn test_handle_data_result_auto_focus_prefers_nearest_changed_file() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     app.data_state = DataState::Loaded {
@@ -1032,6 +1342,7 @@ async fn test_handle_data_result_auto_focus_prefers_nearest_changed_file() {
                 make_file("file_d.rs", 1), // unchanged
                 make_file("file_e.rs", 2), // changed after (additions: 1→2)
             ],
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -1084,6 +1395,9 @@ async fn test_handle_data_result_auto_focus_prefers_next_when_distances_are_tie(
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     app.data_state = DataState::Loaded {
@@ -1103,6 +1417,7 @@ async fn test_handle_data_result_auto_focus_prefers_next_when_distances_are_tie(
                 make_file("file_d.rs", 2), // changed (index 3, additions: 1→2)
                 make_file("file_e.rs", 1), // unchanged (index 4)
             ],
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -1147,6 +1462,9 @@ async fn test_handle_data_result_auto_focus_transitions_to_split_view_diff() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     app.handle_data_result(
@@ -1154,6 +1472,7 @@ async fn test_handle_data_result_auto_focus_transitions_to_split_view_diff() {
         DataLoadResult::Success {
             pr: pr.clone(),
             files: vec![make_file("initial.rs", "@@ -1,1 +1,1 @@\n-old\n+new")],
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -1475,6 +1794,53 @@ fn test_retry_load_sends_correct_request_type() {
     assert!(matches!(req, RefreshRequest::LocalRefresh));
 }
 
+#[test]
+fn test_diff_base_input_sets_base_and_refetches() {
+    let (tx, mut rx) = mpsc::channel::<RefreshRequest>(1);
+    let mut app = App::new_for_test();
+    app.retry_sender = Some(tx);
+    app.pr_number = Some(42);
+
+    app.open_diff_base_input();
+    for c in "abc1234".chars() {
+        assert!(app.handle_diff_base_input(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)));
+    }
+    app.handle_diff_base_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    assert!(app.diff_base_input.is_none());
+    assert_eq!(app.diff_base().as_deref(), Some("abc1234"));
+    // retry ループと同じ値を共有する
+    assert_eq!(
+        app.diff_base_handle().lock().unwrap().as_deref(),
+        Some("abc1234")
+    );
+    assert!(matches!(
+        rx.try_recv().unwrap(),
+        RefreshRequest::PrRefresh { pr_number: 42 }
+    ));
+
+    // 空入力で PR 全体に戻す
+    app.open_diff_base_input();
+    assert_eq!(app.diff_base_input.as_deref(), Some("abc1234"));
+    app.handle_diff_base_input(&KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+    app.handle_diff_base_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.diff_base(), None);
+    assert!(rx.try_recv().is_ok());
+}
+
+#[test]
+fn test_diff_base_rejects_option_like_revision() {
+    let (tx, mut rx) = mpsc::channel::<RefreshRequest>(1);
+    let mut app = App::new_for_test();
+    app.retry_sender = Some(tx);
+
+    app.apply_diff_base("--output=/tmp/x");
+
+    assert_eq!(app.diff_base(), None);
+    assert!(rx.try_recv().is_err());
+    assert!(matches!(app.cmt.submission_result, Some((false, _))));
+}
+
 #[test]
 fn test_is_shift_char_shortcut_accepts_uppercase() {
     let key = KeyEvent {
@@ -1605,6 +1971,9 @@ async fn test_poll_mark_viewed_applies_unmark() {
                 login: "user".to_string(),
             },
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
         }),
         files: vec![
             ChangedFile {
@@ -1675,6 +2044,9 @@ async fn test_poll_mark_viewed_skips_apply_on_pr_mismatch() {
                 login: "user".to_string(),
             },
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
         }),
         files: vec![ChangedFile {
             filename: "src/main.rs".to_string(),
@@ -1736,6 +2108,12 @@ async fn test_handle_data_result_auto_focus_skips_state_transition_during_bg_ral
         last_visible_log_height: 0,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
 
     let pr = Box::new(make_local_pr());
@@ -1748,7 +2126,14 @@ async fn test_handle_data_result_auto_focus_skips_state_transition_during_bg_ral
         viewed: false,
     }];
 
-    app.handle_data_result(0, DataLoadResult::Success { pr, files });
+    app.handle_data_result(
+        0,
+        DataLoadResult::Success {
+            pr,
+            files,
+            revision: DataRevision::Fresh,
+        },
+    );
 
     // State should NOT transition to SplitViewDiff during BG rally
     assert_eq!(app.state, AppState::FileList);
@@ -1756,6 +2141,79 @@ async fn test_handle_data_result_auto_focus_skips_state_transition_during_bg_ral
     assert_eq!(app.selected_file, 0);
 }
 
+#[tokio::test]
+async fn test_local_refresh_reuses_highlight_cache_for_unchanged_patch() {
+    let mut app = App::new_for_test();
+    app.local_mode = true;
+    app.local_auto_focus = false;
+
+    let make_file = |name: &str, patch: &str| ChangedFile {
+        filename: name.to_string(),
+        status: "modified".to_string(),
+        additions: 1,
+        deletions: 1,
+        patch: Some(patch.to_string()),
+        viewed: false,
+    };
+    let patch_a = "@@ -1,1 +1,1 @@\n-let a = 1;\n+let a = 2;";
+    let patch_b = "@@ -1,1 +1,1 @@\n-let b = 1;\n+let b = 2;";
+    app.data_state = DataState::Loaded {
+        pr: Box::new(make_local_pr()),
+        files: vec![make_file("a.rs", patch_a), make_file("b.rs", patch_b)],
+    };
+    let build = |patch: &str, name: &str, index: usize| {
+        let mut cache = crate::ui::diff_view::build_diff_cache(
+            patch,
+            name,
+            &app.config.diff.theme,
+            &mut crate::syntax::ParserPool::new(),
+            false,
+            4,
+        );
+        cache.file_index = index;
+        cache
+    };
+    let cache_a = build(patch_a, "a.rs", 0);
+    let cache_b = build(patch_b, "b.rs", 1);
+    app.diff_store.set_current(0, cache_a);
+    app.diff_store.insert_store(1, cache_b);
+
+    // a.rs は変更なし、b.rs だけ patch が変わったリフレッシュ
+    let patch_b2 = "@@ -1,1 +1,1 @@\n-let b = 1;\n+let b = 3;";
+    app.handle_data_result(
+        0,
+        DataLoadResult::Success {
+            pr: Box::new(make_local_pr()),
+            files: vec![make_file("a.rs", patch_a), make_file("b.rs", patch_b2)],
+            revision: DataRevision::Fresh,
+        },
+    );
+
+    // a.rs はハイライト済みキャッシュをそのまま戻し、再構築しない
+    let current = app.diff_store.current.as_ref().unwrap();
+    assert!(current.highlighted);
+    assert_eq!(app.diff_store.current_key(), Some(&0));
+    assert!(!app.diff_store.has_highlight_rx());
+    // b.rs の古いキャッシュは再利用されない
+    assert!(app
+        .highlight_memo
+        .take("b.rs", hash_string(patch_b2), &app.config.diff.theme)
+        .is_none());
+
+    // a.rs の patch が変われば再構築する
+    let patch_a2 = "@@ -1,1 +1,1 @@\n-let a = 1;\n+let a = 3;";
+    app.handle_data_result(
+        0,
+        DataLoadResult::Success {
+            pr: Box::new(make_local_pr()),
+            files: vec![make_file("a.rs", patch_a2), make_file("b.rs", patch_b2)],
+            revision: DataRevision::Fresh,
+        },
+    );
+    assert!(!app.diff_store.current.as_ref().unwrap().highlighted);
+    assert!(app.diff_store.has_highlight_rx());
+}
+
 fn make_local_pr() -> PullRequest {
     PullRequest {
         number: 0,
@@ -1775,6 +2233,9 @@ fn make_local_pr() -> PullRequest {
             login: "local".to_string(),
         },
         updated_at: "".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     }
 }
 
@@ -1945,6 +2406,9 @@ fn make_app_with_patch(patch: &str) -> App {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.data_state = DataState::Loaded {
         pr,
@@ -2036,6 +2500,31 @@ fn test_multiline_suggestion_preserves_selection_on_invalid_range() {
     assert!(app.input_mode.is_none());
 }
 
+#[test]
+fn test_multiline_suggestion_target_rejects_hunk_boundary_and_collects_lines() {
+    let patch = "@@ -1,2 +1,3 @@\n a\n+b\n c\n@@ -10,2 +11,2 @@\n x\n y";
+    let mut app = make_app_with_patch(patch);
+    // 1 つ目のハンクの末尾から 2 つ目のハンクまで
+    app.multiline_selection = Some(MultilineSelection {
+        anchor_line: 2,
+        cursor_line: 5,
+    });
+    assert!(app.multiline_suggestion_target().is_none());
+    assert!(app.multiline_selection.is_some());
+    let (success, message) = app.cmt.submission_result.clone().unwrap();
+    assert!(!success);
+    assert!(message.contains("multiple hunks"), "{message}");
+
+    app.multiline_selection = Some(MultilineSelection {
+        anchor_line: 3,
+        cursor_line: 1,
+    });
+    let (context, lines) = app.multiline_suggestion_target().unwrap();
+    assert_eq!(lines, vec!["a", "b", "c"]);
+    assert_eq!(context.start_line_number, Some(1));
+    assert_eq!(context.line_number, 3);
+}
+
 #[test]
 fn test_multiline_suggestion_clears_selection_on_valid_range() {
     let patch = "@@ -1,3 +1,4 @@\n context\n+added\n more context";
@@ -2353,6 +2842,9 @@ async fn test_patch_signature_detects_same_numstat_different_patch() {
                 login: "user".to_string(),
             },
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
         }),
         files: initial_files,
     };
@@ -2386,6 +2878,9 @@ async fn test_patch_signature_detects_same_numstat_different_patch() {
                 login: "user".to_string(),
             },
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
         }),
         files: updated_files,
     };
@@ -2483,17 +2978,85 @@ fn test_pending_approve_choice_a_submits_with_body() {
     assert_eq!(app.cmt.pending_approve_body.as_deref(), Some("LGTM!"));
 }
 
-// ===================================================================
-// 1. diff_cache.rs tests
-// ===================================================================
-
 #[test]
-fn test_calc_diff_line_count_basic() {
-    let files = vec![ChangedFile {
-        filename: "test.rs".to_string(),
-        status: "modified".to_string(),
-        additions: 1,
-        deletions: 1,
+fn test_review_picker_moves_and_submits_selected_action() {
+    let mut app = App::new_for_test();
+    app.open_review_picker();
+    let picker = app.review_picker.as_ref().unwrap();
+    // 初期選択は Comment（誤 Approve 防止）
+    assert_eq!(picker.selected_action(), ReviewAction::Comment);
+    assert_eq!(picker.pending_comments, 0);
+
+    // 末尾で止まり、先頭へ戻れる
+    assert_eq!(
+        app.handle_review_picker_key(&make_key(KeyCode::Char('j'))),
+        None
+    );
+    assert_eq!(
+        app.review_picker.as_ref().unwrap().selected_action(),
+        ReviewAction::Comment
+    );
+    app.handle_review_picker_key(&make_key(KeyCode::Char('k')));
+    assert_eq!(
+        app.review_picker.as_ref().unwrap().selected_action(),
+        ReviewAction::RequestChanges
+    );
+    assert_eq!(
+        app.handle_review_picker_key(&make_key(KeyCode::Enter)),
+        Some(ReviewAction::RequestChanges)
+    );
+    assert!(app.review_picker.is_none());
+
+    // Esc は何も送信せずに閉じる
+    app.open_review_picker();
+    assert_eq!(app.handle_review_picker_key(&make_key(KeyCode::Esc)), None);
+    assert!(app.review_picker.is_none());
+}
+
+#[test]
+fn test_review_picker_confirms_approve_with_pending_suggestions() {
+    let mut picker = ReviewPickerState::new(3, 1);
+    picker.move_up();
+    picker.move_up();
+    assert_eq!(picker.selected_action(), ReviewAction::Approve);
+
+    // 1 回目は確認待ちになり、移動すると確認は取り消される
+    assert_eq!(picker.choose(), ReviewPickerOutcome::Pending);
+    assert!(picker.confirming_approve);
+    picker.move_down();
+    picker.move_up();
+    assert!(!picker.confirming_approve);
+    assert_eq!(picker.choose(), ReviewPickerOutcome::Pending);
+    assert_eq!(
+        picker.choose(),
+        ReviewPickerOutcome::Submit(ReviewAction::Approve)
+    );
+
+    // suggestion が無ければ即送信、Approve 以外は確認しない
+    let mut picker = ReviewPickerState::new(2, 0);
+    picker.selected = 0;
+    assert_eq!(
+        picker.choose(),
+        ReviewPickerOutcome::Submit(ReviewAction::Approve)
+    );
+    let mut picker = ReviewPickerState::new(2, 2);
+    assert_eq!(
+        picker.choose(),
+        ReviewPickerOutcome::Submit(ReviewAction::Comment)
+    );
+}
+
+// ===================================================================
+// 1. diff_cache.rs tests
+// ===================================================================
+
+#[test]
+fn test_calc_diff_line_count_basic() {
+    let files = vec![ChangedFile {
+        filename: "test.rs".to_string(),
+        status: "modified".to_string(),
+        additions: 1,
+        deletions: 1,
         patch: Some("@@ -1,1 +1,1 @@\n-old\n+new".to_string()),
         viewed: false,
     }];
@@ -2673,6 +3236,12 @@ fn test_ai_rally_state_push_log_auto_follow() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     };
 
     // No selection = tail, should auto-follow
@@ -2706,6 +3275,12 @@ fn test_ai_rally_state_push_log_no_auto_follow() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     };
 
     // User is NOT at tail, should not auto-follow
@@ -2735,6 +3310,12 @@ fn test_ai_rally_state_is_selection_at_tail() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     };
 
     // At tail: selected_log_index == logs.len() - 1
@@ -3067,6 +3648,205 @@ fn test_reapply_filter_file_list() {
     assert_eq!(filter.matched_indices, vec![1]); // only "src/lib.rs"
 }
 
+#[test]
+fn test_toggle_file_category_filter() {
+    let mut app = make_app_with_files(&["a.rs", "b.rs", "c.rs", "d.rs"]);
+    if let DataState::Loaded { files, .. } = &mut app.data_state {
+        files[1].status = "added".to_string();
+        files[2].patch = Some("@@ -1,2 +0,0 @@\n-x\n-y".to_string());
+        files[3].status = "added".to_string();
+    }
+    app.selected_file = 3;
+
+    app.toggle_file_category_filter(FileCategory::Added);
+    let filter = app.file_list_filter.as_ref().unwrap();
+    assert!(!filter.input_active);
+    assert_eq!(filter.matched_indices, vec![1, 3]);
+    // 選択中のファイルが残っていれば維持
+    assert_eq!(filter.selected, Some(1));
+    assert_eq!(app.selected_file, 3);
+
+    // 別の種別に切り替えると、選択は残った先頭へ
+    app.toggle_file_category_filter(FileCategory::Deleted);
+    assert_eq!(
+        app.file_list_filter.as_ref().unwrap().matched_indices,
+        vec![2]
+    );
+    assert_eq!(app.selected_file, 2);
+
+    // キーワードと組み合わせられる
+    app.toggle_file_category_filter(FileCategory::Modified);
+    let filter = app.file_list_filter.as_mut().unwrap();
+    filter.insert_char('a');
+    app.reapply_filter("file");
+    assert_eq!(
+        app.file_list_filter.as_ref().unwrap().matched_indices,
+        vec![0]
+    );
+
+    // 同じ種別で解除（キーワードは残る）
+    app.toggle_file_category_filter(FileCategory::Modified);
+    assert!(app.file_category_filter.is_none());
+    assert_eq!(app.file_list_filter.as_ref().unwrap().query, "a");
+
+    // キーワードが無ければフィルタごと解除
+    app.file_list_filter = None;
+    app.toggle_file_category_filter(FileCategory::Renamed);
+    assert!(app
+        .file_list_filter
+        .as_ref()
+        .unwrap()
+        .matched_indices
+        .is_empty());
+    app.toggle_file_category_filter(FileCategory::Renamed);
+    assert!(app.file_list_filter.is_none());
+}
+
+#[test]
+fn test_cycle_file_sort_keeps_selection() {
+    let mut app = make_app_with_files(&["b.rs", "c.rs", "a.rs"]);
+    if let DataState::Loaded { files, .. } = &mut app.data_state {
+        files[0].additions = 1;
+        files[1].additions = 20;
+        files[2].additions = 5;
+    }
+    app.selected_file = 2;
+
+    app.cycle_file_sort();
+    assert_eq!(app.file_sort, FileSort::Churn);
+    assert_eq!(app.file_display_order(), vec![1, 2, 0]);
+    assert_eq!(app.selected_file, 2);
+
+    // j/k・gg/G は表示順で動く
+    app.move_file_selection(1);
+    assert_eq!(app.selected_file, 0);
+    app.move_file_selection(1);
+    assert_eq!(app.selected_file, 0);
+    app.select_edge_file(false);
+    assert_eq!(app.selected_file, 1);
+
+    // フィルタ中も並び替え、選択中のファイルの位置を追従する
+    app.toggle_file_category_filter(FileCategory::Modified);
+    app.selected_file = 2;
+    app.file_list_filter.as_mut().unwrap().selected = Some(1);
+    app.cycle_file_sort();
+    assert_eq!(app.file_sort, FileSort::Name);
+    let filter = app.file_list_filter.as_ref().unwrap();
+    assert_eq!(filter.matched_indices, vec![2, 0, 1]);
+    assert_eq!(filter.selected, Some(0));
+    assert_eq!(app.selected_file, 2);
+}
+
+#[test]
+fn test_generated_files_fold_under_collapsible_header() {
+    let mut app = make_app_with_files(&["Cargo.lock", "src/main.rs", "snap/a.snap", "README.md"]);
+    app.config.diff.generated_globs = vec!["*.lock".to_string(), "*.snap".to_string()];
+
+    // 折りたたみ中: 手書きのファイル + 見出しのみ
+    assert_eq!(
+        app.file_list_rows(),
+        vec![
+            FileListRow::File(1),
+            FileListRow::File(3),
+            FileListRow::GeneratedHeader { count: 2 },
+        ]
+    );
+    assert_eq!(app.file_display_order(), vec![1, 3]);
+
+    // j で見出し行まで移動し、Enter で展開する
+    app.selected_file = 3;
+    app.move_file_selection(1);
+    assert!(app.generated_header_selected);
+    assert!(app.toggle_generated_files_if_selected());
+    assert_eq!(app.file_list_rows().len(), 5);
+    assert_eq!(app.file_display_order(), vec![1, 3, 0, 2]);
+
+    app.move_file_selection(1);
+    assert!(!app.generated_header_selected);
+    assert_eq!(app.selected_file, 0);
+    // ファイル行では開閉しない
+    assert!(!app.toggle_generated_files_if_selected());
+
+    app.select_edge_file(false);
+    assert_eq!(app.selected_file, 1);
+}
+
+#[test]
+fn test_open_all_files_requires_local_mode_and_confirms_large_batches() {
+    let mut app = make_app_with_files(&["a.rs", "b.rs", "gone.rs"]);
+    assert!(app.take_files_to_open_in_editor().is_none());
+    assert!(app
+        .cmt
+        .submission_result
+        .as_ref()
+        .is_some_and(|(ok, msg)| !ok && msg.contains("local mode")));
+
+    app.local_mode = true;
+    if let DataState::Loaded { ref mut files, .. } = app.data_state {
+        files[2].status = "removed".to_string();
+    }
+    assert_eq!(
+        app.take_files_to_open_in_editor(),
+        Some(vec!["a.rs".to_string(), "b.rs".to_string()])
+    );
+
+    // 閾値を超える件数は 1 回目で警告し、再押下で実行する
+    let names: Vec<String> = (0..25).map(|i| format!("f{}.rs", i)).collect();
+    let refs: Vec<&str> = names.iter().map(String::as_str).collect();
+    let mut app = make_app_with_files(&refs);
+    app.local_mode = true;
+    assert!(app.take_files_to_open_in_editor().is_none());
+    assert!(app
+        .cmt
+        .submission_result
+        .as_ref()
+        .is_some_and(|(_, msg)| msg.contains("Open 25 files")));
+    assert_eq!(
+        app.take_files_to_open_in_editor().map(|f| f.len()),
+        Some(25)
+    );
+    // 確認は 1 回きり
+    assert!(app.take_files_to_open_in_editor().is_none());
+}
+
+#[test]
+fn test_file_glob_filter_input_applies_and_clears() {
+    let mut app =
+        make_app_with_files(&["Cargo.lock", "README.md", "src/app/mod.rs", "src/main.rs"]);
+
+    app.open_file_glob_input();
+    for c in "src/**/*.rs".chars() {
+        assert!(app.handle_file_glob_input(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)));
+    }
+    app.handle_file_glob_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(app.file_glob_input.is_none());
+    assert_eq!(app.file_glob_filter.as_deref(), Some("src/**/*.rs"));
+    let filter = app.file_list_filter.as_ref().unwrap();
+    assert!(!filter.input_active);
+    assert_eq!(filter.matched_indices, vec![2, 3]);
+    assert_eq!(app.selected_file, 2);
+
+    // 開き直すと適用中のパターンを編集できる
+    app.open_file_glob_input();
+    assert_eq!(app.file_glob_input.as_deref(), Some("src/**/*.rs"));
+    app.handle_file_glob_input(&KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+    for c in "!*.lock".chars() {
+        app.handle_file_glob_input(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    app.handle_file_glob_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(
+        app.file_list_filter.as_ref().unwrap().matched_indices,
+        vec![1, 2, 3]
+    );
+
+    // 空のパターンで解除
+    app.open_file_glob_input();
+    app.handle_file_glob_input(&KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+    app.handle_file_glob_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(app.file_glob_filter.is_none());
+    assert!(app.file_list_filter.is_none());
+}
+
 #[test]
 fn test_handle_filter_navigation_down() {
     let mut app = App::new_for_test();
@@ -3263,7 +4043,10 @@ fn test_refresh_all_resets_state() {
     app.cmt.discussion_comments = Some(vec![]);
     app.cmt.comments_loading = true;
     app.cmt.discussion_comments_loading = true;
-    app.chk.ci_status = Some(crate::github::CiStatus::Failure);
+    app.chk.check_counts = Some(crate::github::CheckCounts {
+        failed: 1,
+        ..Default::default()
+    });
     let filter = crate::filter::ListFilter::new();
     app.file_list_filter = Some(filter);
 
@@ -3274,7 +4057,7 @@ fn test_refresh_all_resets_state() {
     assert!(app.cmt.discussion_comments.is_none());
     assert!(!app.cmt.comments_loading);
     assert!(!app.cmt.discussion_comments_loading);
-    assert!(app.chk.ci_status.is_none());
+    assert!(app.chk.check_counts.is_none());
     assert!(app.chk.ci_status_receiver.is_none());
     assert!(app.file_list_filter.is_none());
 }
@@ -3328,6 +4111,9 @@ async fn test_handle_mark_viewed_key_v_returns_true() {
                 login: "u".to_string(),
             },
             updated_at: "".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
         }),
         files: vec![ChangedFile {
             filename: "test.rs".to_string(),
@@ -3343,11 +4129,46 @@ async fn test_handle_mark_viewed_key_v_returns_true() {
 }
 
 #[test]
-fn test_handle_mark_viewed_key_ignored_local_mode() {
+fn test_handle_mark_viewed_key_local_mode_toggles_file_only() {
     let mut app = App::new_for_test();
     app.local_mode = true;
-    let key = make_key(KeyCode::Char('v'));
-    assert!(!app.handle_mark_viewed_key(key));
+    // ディレクトリ単位の mark は GitHub 専用
+    assert!(!app.handle_mark_viewed_key(make_key(KeyCode::Char('V'))));
+    assert!(app.handle_mark_viewed_key(make_key(KeyCode::Char('v'))));
+}
+
+#[test]
+#[serial]
+fn test_local_viewed_files_persist_and_reset_on_patch_change() {
+    let tempdir = tempdir().unwrap();
+    let _cache_home = ScopedCacheHome::new(tempdir.path());
+    let open_local = |patches: [&str; 2]| {
+        let mut app = make_app_with_files(&["a.rs", "b.rs"]);
+        app.local_mode = true;
+        app.set_working_dir(Some("/tmp/octorus-viewed".to_string()));
+        if let DataState::Loaded { files, .. } = &mut app.data_state {
+            for (file, patch) in files.iter_mut().zip(patches) {
+                file.patch = Some(patch.to_string());
+            }
+        }
+        app.load_local_viewed_files();
+        app
+    };
+
+    let mut app = open_local(["@@ -1 +1 @@\n-a\n+b", "@@ -1 +1 @@\n-c\n+d"]);
+    app.toggle_local_file_viewed();
+    app.selected_file = 1;
+    app.toggle_local_file_viewed();
+    assert!(app.files().iter().all(|f| f.viewed));
+
+    // 再起動後: a.rs は変更なしで viewed のまま、b.rs は patch が変わったので解除
+    let reopened = open_local(["@@ -1 +1 @@\n-a\n+b", "@@ -1 +1 @@\n-c\n+e"]);
+    let viewed: Vec<bool> = reopened.files().iter().map(|f| f.viewed).collect();
+    assert_eq!(viewed, [true, false]);
+    let stored =
+        crate::cache::load_local_viewed_files("owner/repo", Some("/tmp/octorus-viewed")).unwrap();
+    assert!(stored.contains_key("a.rs"));
+    assert!(!stored.contains_key("b.rs"));
 }
 
 #[test]
@@ -3369,6 +4190,7 @@ fn test_cancel_input_clears_mode() {
         line_number: 1,
         diff_position: 1,
         start_line_number: None,
+        side: crate::diff::CommentSide::Right,
     }));
     app.state = AppState::TextInput;
     app.preview_return_state = AppState::DiffView;
@@ -3685,6 +4507,12 @@ fn test_cleanup_rally_state() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
     let (cmd_tx, _cmd_rx) = mpsc::channel(10);
     app.rally_command_sender = Some(cmd_tx);
@@ -3748,6 +4576,7 @@ fn test_build_seed_review_from_local_comments_uses_persisted_comments() {
                 },
                 created_at: "2026-03-24T00:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
         )],
     )
@@ -3799,6 +4628,7 @@ fn test_build_seed_review_from_local_comments_skips_resolved_comments() {
                 },
                 created_at: "2026-03-24T00:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
             crate::cache::LocalCommentMeta {
                 is_resolved: true,
@@ -3842,6 +4672,7 @@ fn test_start_ai_rally_stashes_seed_review_while_waiting_for_confirmation() {
                 },
                 created_at: "2026-03-24T00:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
         )],
     )
@@ -3901,6 +4732,12 @@ fn test_is_rally_running_in_background_not_in_rally() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
     assert!(app.is_rally_running_in_background());
 }
@@ -3925,6 +4762,12 @@ fn test_is_rally_running_in_background_in_rally() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
     assert!(!app.is_rally_running_in_background());
 }
@@ -3957,6 +4800,12 @@ fn test_is_rally_running_in_background_finished() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
     assert!(!app.is_rally_running_in_background());
 }
@@ -3981,6 +4830,12 @@ fn test_has_background_rally_true() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
     assert!(app.has_background_rally());
 }
@@ -4005,6 +4860,12 @@ fn test_has_background_rally_false_in_rally() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
     assert!(!app.has_background_rally());
 }
@@ -4037,6 +4898,12 @@ fn test_is_background_rally_finished_completed() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
     assert!(app.is_background_rally_finished());
 }
@@ -4061,6 +4928,12 @@ fn test_is_background_rally_finished_running() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
     assert!(!app.is_background_rally_finished());
 }
@@ -4086,6 +4959,12 @@ fn test_adjust_log_scroll_selection_above() {
         last_visible_log_height: 5,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
 
     app.adjust_log_scroll_to_selection();
@@ -4115,6 +4994,12 @@ fn test_adjust_log_scroll_selection_below() {
         last_visible_log_height: 5,
         pending_config_warning: None,
         pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
 
     app.adjust_log_scroll_to_selection();
@@ -4153,6 +5038,12 @@ fn test_pause_state_reset_on_approve_state_change() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::PauseRequested,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
 
     // Simulate: reviewer approved → StateChanged(Completed) arrives
@@ -4171,6 +5062,235 @@ fn test_pause_state_reset_on_approve_state_change() {
     );
 }
 
+#[test]
+fn test_pending_comments_accumulate_and_drop() {
+    use crate::ai::orchestrator::RallyEvent;
+    use crate::ai::{PendingComment, RallyState};
+
+    let pending = |line: u32| PendingComment {
+        path: "src/lib.rs".to_string(),
+        line,
+        position: line,
+        commit_id: "abc123".to_string(),
+        body: format!("comment {}", line),
+    };
+
+    let mut app = App::new_for_test();
+    let (event_tx, event_rx) = mpsc::channel(100);
+    app.rally_event_receiver = Some(event_rx);
+    app.ai_rally_state = Some(AiRallyState {
+        iteration: 1,
+        max_iterations: 10,
+        review_only: false,
+        state: RallyState::ReviewerReviewing,
+        history: vec![],
+        logs: vec![],
+        log_scroll_offset: 0,
+        selected_log_index: None,
+        showing_log_detail: false,
+        pending_question: None,
+        pending_permission: None,
+        pending_post_confirmation: crate::app::PendingPostConfirmation::None,
+        last_visible_log_height: 10,
+        pending_config_warning: None,
+        pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
+    });
+
+    // 2 回のレビューの保留コメントは順に積み上がる
+    event_tx
+        .try_send(RallyEvent::PendingComments(vec![pending(1), pending(2)]))
+        .unwrap();
+    event_tx
+        .try_send(RallyEvent::PendingComments(vec![pending(3)]))
+        .unwrap();
+    app.poll_rally_events();
+    assert_eq!(
+        app.ai_rally_state.as_ref().unwrap().pending_comments.len(),
+        3
+    );
+
+    app.ai_rally_state
+        .as_mut()
+        .unwrap()
+        .selected_pending_comment = 1;
+    app.drop_selected_pending_comment();
+    let lines: Vec<u32> = app
+        .ai_rally_state
+        .as_ref()
+        .unwrap()
+        .pending_comments
+        .iter()
+        .map(|c| c.line)
+        .collect();
+    assert_eq!(lines, [1, 3]);
+
+    // 末尾を消したら選択は新しい末尾に寄せる
+    app.drop_selected_pending_comment();
+    let rally_state = app.ai_rally_state.as_ref().unwrap();
+    assert_eq!(rally_state.pending_comments.len(), 1);
+    assert_eq!(rally_state.selected_pending_comment, 0);
+}
+
+#[test]
+fn test_replayed_step_replaces_only_target_iteration() {
+    use crate::ai::orchestrator::{AgentStep, RallyEvent, StepInput, StepOutput};
+    use crate::ai::{Context, RallyState, ReviewAction, ReviewerOutput};
+
+    let review = |summary: &str| ReviewerOutput {
+        action: ReviewAction::RequestChanges,
+        summary: summary.to_string(),
+        comments: vec![],
+        blocking_issues: vec![],
+    };
+    let input = |iteration: u32| StepInput {
+        iteration,
+        step: AgentStep::Review,
+        prompt: format!("review prompt {}", iteration),
+        context: Context {
+            repo: "owner/repo".to_string(),
+            pr_number: 1,
+            pr_title: "test".to_string(),
+            pr_body: None,
+            diff: String::new(),
+            working_dir: None,
+            head_sha: "deadbeef".to_string(),
+            base_branch: "main".to_string(),
+            external_comments: vec![],
+            local_mode: true,
+            file_patches: vec![],
+        },
+    };
+
+    let mut app = App::new_for_test();
+    let (event_tx, event_rx) = mpsc::channel(100);
+    app.rally_event_receiver = Some(event_rx);
+    app.ai_rally_state = Some(AiRallyState {
+        iteration: 0,
+        max_iterations: 10,
+        review_only: false,
+        state: RallyState::ReviewerReviewing,
+        history: vec![],
+        logs: vec![],
+        log_scroll_offset: 0,
+        selected_log_index: None,
+        showing_log_detail: false,
+        pending_question: None,
+        pending_permission: None,
+        pending_post_confirmation: crate::app::PendingPostConfirmation::None,
+        last_visible_log_height: 10,
+        pending_config_warning: None,
+        pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
+    });
+
+    for event in [
+        RallyEvent::IterationStarted(1),
+        RallyEvent::StepStarted(input(1)),
+        RallyEvent::ReviewCompleted(review("first")),
+        RallyEvent::IterationStarted(2),
+        RallyEvent::StepStarted(input(2)),
+        RallyEvent::ReviewCompleted(review("second")),
+        RallyEvent::StateChanged(RallyState::Completed),
+        RallyEvent::StepReplayed {
+            iteration: 2,
+            output: StepOutput::Review(review("second, re-run")),
+        },
+    ] {
+        event_tx.try_send(event).unwrap();
+    }
+    app.poll_rally_events();
+
+    let rally_state = app.ai_rally_state.as_ref().unwrap();
+    let inputs: Vec<u32> = rally_state
+        .step_inputs
+        .iter()
+        .map(|i| i.iteration)
+        .collect();
+    assert_eq!(inputs, [1, 2]);
+    let summaries: Vec<&str> = rally_state
+        .history
+        .iter()
+        .filter_map(|e| match e {
+            RallyEvent::ReviewCompleted(r) => Some(r.summary.as_str()),
+            _ => None,
+        })
+        .collect();
+    // 1 回目の結果は残り、2 回目だけが置き換わる
+    assert_eq!(summaries, ["first", "second, re-run"]);
+    assert!(!rally_state.history.iter().any(|e| matches!(
+        e,
+        RallyEvent::StepStarted(_) | RallyEvent::StepReplayed { .. }
+    )));
+}
+
+#[test]
+fn test_replayed_failed_step_is_appended_to_its_iteration() {
+    use crate::ai::orchestrator::RallyEvent;
+    use crate::ai::{RallyState, RevieweeOutput, RevieweeStatus};
+
+    let mut rally_state = AiRallyState {
+        iteration: 2,
+        max_iterations: 10,
+        review_only: false,
+        state: RallyState::Error,
+        history: vec![
+            RallyEvent::IterationStarted(1),
+            RallyEvent::IterationStarted(2),
+            RallyEvent::Error("Reviewee timeout after 600 seconds".to_string()),
+        ],
+        logs: vec![],
+        log_scroll_offset: 0,
+        selected_log_index: None,
+        showing_log_detail: false,
+        pending_question: None,
+        pending_permission: None,
+        pending_post_confirmation: crate::app::PendingPostConfirmation::None,
+        last_visible_log_height: 10,
+        pending_config_warning: None,
+        pause_state: PauseState::Running,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
+    };
+
+    rally_state.replace_step_output(
+        1,
+        RallyEvent::FixCompleted(RevieweeOutput {
+            status: RevieweeStatus::Completed,
+            summary: "fixed".to_string(),
+            files_modified: vec![],
+            question: None,
+            permission_request: None,
+            error_details: None,
+        }),
+    );
+
+    // 元の結果がなければ対象イテレーションの末尾（次のイテレーションの前）に入る
+    assert!(matches!(
+        rally_state.history.as_slice(),
+        [
+            RallyEvent::IterationStarted(1),
+            RallyEvent::FixCompleted(_),
+            RallyEvent::IterationStarted(2),
+            RallyEvent::Error(_),
+        ]
+    ));
+}
+
 #[test]
 fn test_pause_state_reset_on_waiting_for_clarification() {
     use crate::ai::orchestrator::RallyEvent;
@@ -4198,6 +5318,12 @@ fn test_pause_state_reset_on_waiting_for_clarification() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::PauseRequested,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
 
     // Simulate: reviewee needs clarification
@@ -4243,6 +5369,12 @@ fn test_pause_state_reset_on_waiting_for_permission() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::PauseRequested,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
 
     event_tx
@@ -4286,6 +5418,12 @@ fn test_pause_state_reset_on_waiting_for_post_confirmation() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::PauseRequested,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
 
     event_tx
@@ -4342,6 +5480,12 @@ fn test_post_confirm_response_does_not_force_rally_state_to_reviewee_fix() {
             last_visible_log_height: 10,
             pending_config_warning: None,
             pause_state: PauseState::Running,
+            agent_started_at: None,
+            spinner_frame: 0,
+            pending_comments: Vec::new(),
+            selected_pending_comment: 0,
+            pending_comments_focused: false,
+            step_inputs: Vec::new(),
         });
 
         app.handle_post_confirm_response(approved);
@@ -4390,6 +5534,12 @@ fn test_pause_state_preserved_on_active_state_change() {
         last_visible_log_height: 10,
         pending_config_warning: None,
         pause_state: PauseState::PauseRequested,
+        agent_started_at: None,
+        spinner_frame: 0,
+        pending_comments: Vec::new(),
+        selected_pending_comment: 0,
+        pending_comments_focused: false,
+        step_inputs: Vec::new(),
     });
 
     event_tx
@@ -4540,19 +5690,42 @@ fn test_enter_comment_input_sets_mode() {
 }
 
 #[test]
-fn test_enter_comment_input_no_patch() {
-    let mut app = App::new_for_test();
-    app.data_state = DataState::Loaded {
-        pr: Box::new(make_local_pr()),
-        files: vec![ChangedFile {
-            filename: "test.rs".to_string(),
-            status: "modified".to_string(),
-            additions: 0,
-            deletions: 0,
-            patch: None,
-            viewed: false,
-        }],
-    };
+fn test_enter_comment_input_on_removed_line_uses_left_side() {
+    let patch = "@@ -10,3 +10,2 @@\n context\n-removed\n more context";
+    let mut app = make_app_with_patch(patch);
+    app.diff_scroll.selected_line = 2;
+    app.state = AppState::DiffView;
+
+    app.enter_comment_input();
+
+    let Some(InputMode::Comment(ctx)) = &app.input_mode else {
+        panic!("expected comment input");
+    };
+    assert_eq!(ctx.side, crate::diff::CommentSide::Left);
+    assert_eq!(ctx.line_number, 11);
+    assert_eq!(ctx.diff_position, 2);
+
+    // サジェスチョンは従来どおり削除行では開かない
+    app.input_mode = None;
+    app.state = AppState::DiffView;
+    app.enter_suggestion_input();
+    assert!(app.input_mode.is_none());
+}
+
+#[test]
+fn test_enter_comment_input_no_patch() {
+    let mut app = App::new_for_test();
+    app.data_state = DataState::Loaded {
+        pr: Box::new(make_local_pr()),
+        files: vec![ChangedFile {
+            filename: "test.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 0,
+            deletions: 0,
+            patch: None,
+            viewed: false,
+        }],
+    };
 
     // Should not panic
     app.enter_comment_input();
@@ -4625,6 +5798,9 @@ async fn test_open_comment_list_transitions_state() {
                 login: "u".to_string(),
             },
             updated_at: "".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
         }),
         files: vec![],
     };
@@ -4783,6 +5959,7 @@ fn test_load_review_comments_local_mode_refreshes_meta_from_disk() {
             },
             created_at: "2026-03-24T00:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         },
     )];
     crate::cache::save_local_review_comments(
@@ -4861,6 +6038,7 @@ fn test_load_review_comments_local_mode_builds_threads_and_counts() {
             },
             created_at: "2026-04-01T00:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         }),
         crate::cache::LocalReviewComment::new(crate::github::comment::ReviewComment {
             id: 2,
@@ -4873,6 +6051,7 @@ fn test_load_review_comments_local_mode_builds_threads_and_counts() {
             },
             created_at: "2026-04-01T01:00:00Z".to_string(),
             in_reply_to_id: Some(1),
+            reactions: Default::default(),
         }),
         crate::cache::LocalReviewComment::new(crate::github::comment::ReviewComment {
             id: 3,
@@ -4885,6 +6064,7 @@ fn test_load_review_comments_local_mode_builds_threads_and_counts() {
             },
             created_at: "2026-04-01T02:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         }),
     ];
     crate::cache::save_local_review_comments(
@@ -4968,6 +6148,7 @@ fn test_update_file_comment_positions_with_comments() {
         },
         created_at: "2024-01-01T00:00:00Z".to_string(),
         in_reply_to_id: None,
+        reactions: Default::default(),
     }]);
     app.update_file_comment_positions();
     assert_eq!(app.cmt.file_comment_positions.len(), 1);
@@ -4988,6 +6169,7 @@ fn test_update_file_comment_positions_stale_comment() {
         },
         created_at: "2024-01-01T00:00:00Z".to_string(),
         in_reply_to_id: None,
+        reactions: Default::default(),
     }]);
     app.update_file_comment_positions();
     assert!(app.cmt.file_comment_positions.is_empty());
@@ -5057,6 +6239,7 @@ fn test_enter_reply_input_sets_mode() {
         },
         created_at: "2024-01-01T00:00:00Z".to_string(),
         in_reply_to_id: None,
+        reactions: Default::default(),
     }]);
     app.cmt.file_comment_positions = vec![CommentPosition {
         diff_line_index: 1,
@@ -5114,6 +6297,9 @@ async fn test_jump_to_comment_sets_file_and_line() {
                 login: "u".to_string(),
             },
             updated_at: "".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
         }),
         files: vec![
             ChangedFile {
@@ -5145,6 +6331,7 @@ async fn test_jump_to_comment_sets_file_and_line() {
         },
         created_at: "2024-01-01T00:00:00Z".to_string(),
         in_reply_to_id: None,
+        reactions: Default::default(),
     }]);
     app.cmt.selected_comment = 0;
 
@@ -5320,6 +6507,36 @@ async fn test_reload_pr_list_resets_state() {
     assert!(app.prs.pr_list_filter.is_none());
 }
 
+#[tokio::test]
+async fn test_pr_list_filters_input_applies_and_persists() {
+    let mut app = App::new_for_test();
+    app.state = AppState::PullRequestList;
+    app.prs.pr_list = LoadState::Loaded(vec![]);
+
+    app.handle_pr_list_input(make_key(KeyCode::Char('f')))
+        .await
+        .unwrap();
+    assert_eq!(app.state, AppState::TextInput);
+    assert!(matches!(app.input_mode, Some(InputMode::PrListFilters)));
+
+    app.apply_pr_list_filters("author:octocat label:bug");
+    assert_eq!(app.prs.pr_list_filters.query(), "author:octocat label:bug");
+    assert!(app.prs.pr_list.is_loading());
+
+    // 不正なクエリは条件を変えない
+    app.apply_pr_list_filters("reviewer:me");
+    assert_eq!(app.prs.pr_list_filters.author.as_deref(), Some("octocat"));
+    assert!(matches!(app.cmt.submission_result, Some((false, _))));
+
+    // 再度開くと現在の条件が入っている
+    app.state = AppState::PullRequestList;
+    app.open_pr_list_filters_input();
+    assert_eq!(app.input_text_area.content(), "author:octocat label:bug");
+
+    app.apply_pr_list_filters("");
+    assert!(app.prs.pr_list_filters.is_empty());
+}
+
 #[test]
 fn test_load_more_prs_skips_when_loading() {
     let mut app = App::new_for_test();
@@ -5688,6 +6905,36 @@ fn test_help_tab_switch_with_bracket_keys() {
     assert_eq!(app.help_tab, HelpTab::Keybindings);
 }
 
+#[test]
+fn test_help_filter_input() {
+    let config = Config::default();
+    let (mut app, _) = App::new_loading("owner/repo", 1, config);
+    app.state = AppState::Help;
+    app.help_scroll_offset = 4;
+
+    app.apply_help_scroll(make_key(KeyCode::Char('/')), 30);
+    assert!(app.help_filter.as_ref().is_some_and(|f| f.input_active));
+
+    // 入力中は j / ] / q もクエリに入る
+    for c in ['j', ']', 'q'] {
+        app.apply_help_scroll(make_key(KeyCode::Char(c)), 30);
+    }
+    assert_eq!(app.help_filter.as_ref().unwrap().query, "j]q");
+    assert_eq!(app.help_tab, HelpTab::Keybindings);
+    assert_eq!(app.state, AppState::Help);
+    assert_eq!(app.help_scroll_offset, 0);
+
+    app.apply_help_scroll(make_key(KeyCode::Enter), 30);
+    assert!(app.help_filter.as_ref().is_some_and(|f| !f.input_active));
+
+    // 確定後は通常のスクロール、Esc で絞り込みを解除
+    app.apply_help_scroll(make_key(KeyCode::Char('j')), 30);
+    assert_eq!(app.help_scroll_offset, 1);
+    app.apply_help_scroll(make_key(KeyCode::Esc), 30);
+    assert!(app.help_filter.is_none());
+    assert_eq!(app.state, AppState::Help);
+}
+
 #[test]
 fn test_help_tab_independent_scroll_offsets() {
     let config = Config::default();
@@ -5800,6 +7047,9 @@ fn test_open_pr_description_state_transition() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.data_state = DataState::Loaded { pr, files: vec![] };
     app.state = AppState::FileList;
@@ -5835,6 +7085,9 @@ fn test_open_pr_description_from_split_view() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.data_state = DataState::Loaded { pr, files: vec![] };
     app.state = AppState::SplitViewFileList;
@@ -5868,6 +7121,9 @@ fn test_open_pr_description_body_none() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.data_state = DataState::Loaded { pr, files: vec![] };
 
@@ -5900,6 +7156,9 @@ fn test_open_pr_description_body_empty() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.data_state = DataState::Loaded { pr, files: vec![] };
 
@@ -5932,6 +7191,9 @@ fn test_toggle_markdown_rich_clears_pr_description_cache() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.data_state = DataState::Loaded { pr, files: vec![] };
 
@@ -5967,6 +7229,9 @@ fn test_pr_description_cache_reuse() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.data_state = DataState::Loaded { pr, files: vec![] };
 
@@ -6130,6 +7395,9 @@ async fn test_pr_description_toggle_rich_preserves_prefetch_and_store() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.data_state = DataState::Loaded {
         pr,
@@ -6204,6 +7472,9 @@ fn test_rebuild_pr_description_cache_preserves_scroll() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.data_state = DataState::Loaded { pr, files: vec![] };
 
@@ -6345,6 +7616,31 @@ fn test_symbol_search_failed_resets_to_idle() {
     assert!(msg.contains("Search failed"));
 }
 
+#[tokio::test]
+async fn test_jump_to_definition_in_patches_reuses_symbol_table_until_patch_changes() {
+    let mut app = make_app_with_patch("@@ -0,0 +1,2 @@\n+fn helper() {}\n+fn main() { helper(); }");
+    assert!(app.patch_symbol_table.is_none());
+
+    assert!(app.jump_to_definition_in_patches("helper"));
+    assert_eq!(app.diff_scroll.selected_line, 1);
+    assert!(app.patch_symbol_table.is_some());
+    assert!(!app.jump_to_definition_in_patches("missing"));
+    assert!(app.patch_symbol_table.is_some());
+
+    // patch が変わったら定義表を作り直す
+    app.replace_patch(
+        0,
+        "@@ -0,0 +1,3 @@\n+fn main() { helper(); }\n+\n+fn helper() {}".to_string(),
+    );
+    assert!(app.patch_symbol_table.is_none());
+    assert!(app.jump_to_definition_in_patches("helper"));
+    assert_eq!(app.diff_scroll.selected_line, 3);
+
+    // PR データの再取得でも破棄する
+    app.refresh_review_marker_count();
+    assert!(app.patch_symbol_table.is_none());
+}
+
 #[test]
 fn test_symbol_search_clears_submission_result_on_new_search() {
     let mut app = App::new_for_test();
@@ -6731,6 +8027,9 @@ fn make_test_pr() -> Box<PullRequest> {
             login: "u".to_string(),
         },
         updated_at: String::new(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     })
 }
 
@@ -7254,6 +8553,9 @@ fn make_loaded_app() -> App {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
     app.handle_data_result(
         1,
@@ -7269,6 +8571,7 @@ fn make_loaded_app() -> App {
                 ),
                 viewed: false,
             }],
+            revision: DataRevision::Fresh,
         },
     );
     app
@@ -7286,7 +8589,7 @@ async fn test_zen_mode_navigation_scenario() {
     ┌octorus───────────────────────────────────────────────────────────────────────┐
     │PR #1: Add zen mode by @user                                                  │
     └──────────────────────────────────────────────────────────────────────────────┘
-    ┌Changed Files (1)─────────────────────────────────────────────────────────────┐
+    ┌Changed Files (1)────────────────────────────────────────────── 1 file, +3 −1 ┐
     │[M]   src/app.rs +3 -1                                                        │
     │                                                                              │
     "#);
@@ -7297,7 +8600,7 @@ async fn test_zen_mode_navigation_scenario() {
     ┌octorus───────────────────────────────────────────────────────────────────────┐
     │PR #1: Add zen mode by @user                                                  │
     └──────────────────────────────────────────────────────────────────────────────┘
-    ┌Changed Files (1)─────────────────────────────────────────────────────────────┐
+    ┌Changed Files (1)────────────────────────────────────────────── 1 file, +3 −1 ┐
     │[M]   src/app.rs +3 -1                                                        │
     │                                                                              │
     "#);
@@ -7320,7 +8623,7 @@ async fn test_zen_mode_navigation_scenario() {
     ┌octorus───────────────────────────────────────────────────────────────────────┐
     │PR #1: Add zen mode by @user                                                  │
     └──────────────────────────────────────────────────────────────────────────────┘
-    ┌Changed Files (1)─────────────────────────────────────────────────────────────┐
+    ┌Changed Files (1)────────────────────────────────────────────── 1 file, +3 −1 ┐
     │[M]   src/app.rs +3 -1                                                        │
     │                                                                              │
     "#);
@@ -7340,9 +8643,13 @@ async fn test_zen_mode_navigation_scenario() {
 }
 
 #[tokio::test]
+#[serial]
 async fn test_zen_mode_pr_list_origin_quit_scenario() {
     use insta::assert_snapshot;
 
+    // PR 一覧へ戻る際に閲覧位置が保存される
+    let tempdir = tempdir().unwrap();
+    let _cache_home = ScopedCacheHome::new(tempdir.path());
     let mut app = make_loaded_app();
     app.started_from_pr_list = true;
 
@@ -7365,7 +8672,7 @@ async fn test_zen_mode_pr_list_origin_quit_scenario() {
     ┌octorus───────────────────────────────────────────────────────────────────────┐
     │PR #1: Add zen mode by @user                                                  │
     └──────────────────────────────────────────────────────────────────────────────┘
-    ┌Changed Files (1)─────────────────────────────────────────────────────────────┐
+    ┌Changed Files (1)────────────────────────────────────────────── 1 file, +3 −1 ┐
     │[M]   src/app.rs +3 -1                                                        │
     │                                                                              │
     "#);
@@ -7449,6 +8756,9 @@ async fn test_zen_mode_auto_focus_transitions_to_diff_view() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     });
 
     app.handle_data_result(
@@ -7463,6 +8773,7 @@ async fn test_zen_mode_auto_focus_transitions_to_diff_view() {
                 patch: Some("@@ -1,1 +1,1 @@\n-old\n+new".to_string()),
                 viewed: false,
             }],
+            revision: DataRevision::Fresh,
         },
     );
 
@@ -7713,6 +9024,7 @@ fn test_try_open_comment_panel_in_local_mode() {
         },
         created_at: "2026-04-27T00:00:00Z".to_string(),
         in_reply_to_id: None,
+        reactions: Default::default(),
     }]);
 
     let kb = app.config.keybindings.clone();
@@ -7757,6 +9069,7 @@ fn test_apply_review_comments_preserves_expanded_selection_by_id() {
             },
             created_at: created_at.to_string(),
             in_reply_to_id: parent,
+            reactions: Default::default(),
         }
     }
 
@@ -7814,6 +9127,7 @@ fn test_apply_review_comments_populates_file_counts() {
             },
             created_at: "2025-01-01T00:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         },
         ReviewComment {
             id: 2,
@@ -7826,6 +9140,7 @@ fn test_apply_review_comments_populates_file_counts() {
             },
             created_at: "2025-01-01T01:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         },
         ReviewComment {
             id: 3,
@@ -7838,6 +9153,7 @@ fn test_apply_review_comments_populates_file_counts() {
             },
             created_at: "2025-01-01T02:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         },
     ]);
 
@@ -7857,6 +9173,7 @@ fn pr157_rc(id: u64, parent: Option<u64>, path: &str, created_at: &str) -> Revie
         },
         created_at: created_at.to_string(),
         in_reply_to_id: parent,
+        reactions: Default::default(),
     }
 }
 
@@ -8179,3 +9496,2135 @@ fn test_diff_page_down_in_diff_focus_matches_page_down_step() {
         30 + super::input_diff::DIFF_PAGE_STEP
     );
 }
+
+#[test]
+fn test_open_file_page_in_browser_disabled_in_local_mode() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    app.set_local_mode(true);
+
+    app.open_file_page_in_browser(crate::github::GithubFilePage::Blame);
+
+    let (ok, msg) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert_eq!(msg, "GitHub blame is unavailable in local mode");
+}
+
+#[test]
+fn test_poll_base_pr_updates_reports_no_stack() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    tx.try_send(Ok(None)).unwrap();
+    app.base_pr_receiver = Some(("main".to_string(), rx));
+
+    app.poll_base_pr_updates();
+
+    assert!(app.base_pr_receiver.is_none());
+    assert_eq!(app.pr_number, Some(1));
+    let (ok, msg) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert_eq!(msg, "Base branch 'main' is not the head of an open PR");
+}
+
+#[test]
+fn test_jump_to_base_pr_disabled_in_local_mode() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    app.set_local_mode(true);
+
+    app.jump_to_base_pr();
+
+    assert!(app.base_pr_receiver.is_none());
+    assert!(!app.cmt.submission_result.clone().unwrap().0);
+}
+
+#[tokio::test]
+async fn test_review_session_capture_and_restore_roundtrip() {
+    let patch = "@@ -1,3 +1,4 @@\n context\n+added\n more context\n+last";
+    let mut app = make_app_with_patch(patch);
+    app.state = AppState::DiffView;
+    app.diff_scroll.selected_line = 2;
+    app.diff_scroll.scroll_offset = 1;
+    app.enter_comment_input();
+    app.input_text_area.set_content("half-written comment");
+
+    let saved = app.capture_review_session().unwrap();
+    assert_eq!(saved.pr_number, 1);
+    assert_eq!(saved.selected_path.as_deref(), Some("test.rs"));
+    assert!(saved.in_diff_view);
+    assert_eq!(
+        saved.comment_draft.as_ref().unwrap().body,
+        "half-written comment"
+    );
+
+    let mut restored = make_app_with_patch(patch);
+    restored.state = AppState::FileList;
+    restored.session_autosave.pending_restore = Some(saved.clone());
+    restored.restore_review_session();
+
+    assert!(!restored.is_pending_session_restore());
+    assert_eq!(restored.state, AppState::TextInput);
+    assert!(matches!(
+        restored.input_mode,
+        Some(InputMode::Comment(ref ctx)) if ctx.line_number == 2
+    ));
+    assert_eq!(restored.capture_review_session(), Some(saved));
+}
+
+#[tokio::test]
+async fn test_read_only_restored_comment_draft_cannot_be_submitted() {
+    let patch = "@@ -1,3 +1,4 @@\n context\n+added\n more context\n+last";
+    let mut app = make_app_with_patch(patch);
+    app.state = AppState::DiffView;
+    app.diff_scroll.selected_line = 2;
+    app.enter_comment_input();
+    app.input_text_area
+        .set_content("draft from an earlier session");
+    let saved = app.capture_review_session().unwrap();
+
+    let mut restored = make_app_with_patch(patch);
+    restored.config.read_only = true;
+    restored.state = AppState::FileList;
+    restored.session_autosave.pending_restore = Some(saved);
+    restored.restore_review_session();
+    assert_eq!(restored.state, AppState::TextInput);
+
+    restored
+        .handle_text_input(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL))
+        .unwrap();
+
+    assert!(restored.cmt.comment_submit_receiver.is_none());
+    assert!(!restored.cmt.comment_submitting);
+    let (ok, message) = restored.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert_eq!(message, "Read-only mode: Comments are disabled");
+}
+
+#[test]
+fn test_read_only_blocks_rally_commit_and_retry() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    app.config.read_only = true;
+
+    app.commit_rally_comments();
+    assert!(app.rally_commit_receiver.is_none());
+    app.retry_last_rally_step();
+    assert!(app.rally_event_receiver.is_none());
+    let (ok, message) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert_eq!(message, "Read-only mode: AI Rally is disabled");
+}
+
+#[test]
+fn test_review_session_not_captured_in_local_mode() {
+    let mut app = make_app_with_patch("@@ -1,1 +1,1 @@\n-old\n+new");
+    assert!(app.capture_review_session().is_some());
+    app.set_local_mode(true);
+    assert!(app.capture_review_session().is_none());
+}
+
+#[test]
+fn test_diff_search_jumps_incrementally_and_cycles_matches() {
+    let patch =
+        "@@ -1,3 +1,3 @@\n-let Foo = 1;\n+let foo = 2;\n ctx\n@@ -10,1 +10,1 @@\n+foo again";
+    let mut app = make_app_with_patch(patch);
+    app.diff_scroll.line_count = 6;
+    app.diff_scroll.selected_line = 3;
+
+    app.open_diff_search();
+    for c in "fo".chars() {
+        assert!(app.handle_diff_search_input(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)));
+    }
+    // 大文字小文字を無視し、開いた行以降の最初の一致へ移動
+    let search = app.current_diff_search().unwrap();
+    assert_eq!(
+        search.matches.iter().map(|m| m.line).collect::<Vec<_>>(),
+        vec![1, 2, 5]
+    );
+    assert_eq!(app.diff_scroll.selected_line, 5);
+
+    app.handle_diff_search_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(!app.current_diff_search().unwrap().input_active);
+
+    // n は末尾から先頭へ折り返し、N は逆方向
+    assert!(app.jump_to_search_match(true));
+    assert_eq!(app.diff_scroll.selected_line, 1);
+    assert!(app.jump_to_search_match(true));
+    assert_eq!(app.diff_scroll.selected_line, 2);
+    assert!(app.jump_to_search_match(false));
+    assert_eq!(app.diff_scroll.selected_line, 1);
+
+    // 大文字小文字を区別する設定
+    app.config.diff.search_case_sensitive = true;
+    app.open_diff_search();
+    // 同じファイルで開き直すと前回のクエリが残るので Ctrl-U で消す
+    assert_eq!(app.current_diff_search().unwrap().query, "fo");
+    app.handle_diff_search_input(&KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+    app.handle_diff_search_input(&KeyEvent::new(KeyCode::Char('F'), KeyModifiers::NONE));
+    assert_eq!(app.current_diff_search().unwrap().matches.len(), 1);
+
+    // Esc で検索を閉じて元の行へ戻る
+    app.handle_diff_search_input(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.current_diff_search().is_none());
+    assert_eq!(app.diff_scroll.selected_line, 1);
+    assert!(!app.jump_to_search_match(true));
+}
+
+fn type_hunk_filter(app: &mut App, query: &str) {
+    app.open_hunk_filter();
+    for c in query.chars() {
+        app.handle_hunk_filter_input(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    app.handle_hunk_filter_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+}
+
+#[test]
+fn test_hunk_filter_skips_non_matching_hunks() {
+    let patch = "@@ -1,2 +1,2 @@\n-foo\n+bar\n@@ -10,2 +10,2 @@\n-Needle\n+needle2\n@@ -20,1 +20,2 @@\n ctx\n+other";
+    let mut app = make_app_with_patch(patch);
+    app.diff_scroll.line_count = 9;
+    app.diff_scroll.selected_line = 1;
+
+    type_hunk_filter(&mut app, "needle");
+    let filter = app.current_hunk_filter().unwrap();
+    assert!(!filter.input_active);
+    assert_eq!(filter.matched_count(), 1);
+    assert_eq!(filter.hunks.len(), 3);
+    // 選択行は表示中の hunk（2 番目）へ移動し、行インデックスは元の patch のまま
+    assert_eq!(app.diff_scroll.selected_line, 3);
+
+    // 下方向の移動で非表示 hunk に入ったら、その先（なければ手前）へ寄せる
+    app.diff_scroll.selected_line = 6;
+    app.snap_to_visible_hunk(5);
+    assert_eq!(app.diff_scroll.selected_line, 5);
+}
+
+#[test]
+fn test_toggle_hunk_filter_restores_all_hunks() {
+    let patch = "@@ -1,1 +1,1 @@\n-foo\n+bar\n@@ -10,1 +10,1 @@\n-baz\n+qux";
+    let mut app = make_app_with_patch(patch);
+    app.diff_scroll.line_count = 6;
+
+    type_hunk_filter(&mut app, "qux");
+    assert!(app
+        .current_hunk_filter()
+        .unwrap()
+        .hidden_hunk_at(1)
+        .is_some());
+
+    app.toggle_hunk_filter();
+    let filter = app.current_hunk_filter().unwrap();
+    assert!(!filter.is_hiding());
+    assert!(filter.hidden_hunk_at(1).is_none());
+    assert_eq!(filter.query, "qux");
+
+    app.toggle_hunk_filter();
+    assert!(app.current_hunk_filter().unwrap().is_hiding());
+
+    // 空クエリで確定するとフィルタ自体を解除する
+    app.open_hunk_filter();
+    app.handle_hunk_filter_input(&KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+    app.handle_hunk_filter_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(app.hunk_filter.is_none());
+}
+
+/// 読み取り専用モードのテスト用: 画面・モードを整え、書き込み操作のキーを返す
+struct WriteKeyCase {
+    action: super::read_only::WriteAction,
+    state: AppState,
+    setup: fn(&mut App),
+    pending: &'static [KeyEvent],
+    key: KeyEvent,
+}
+
+const fn char_key(c: char) -> KeyEvent {
+    let modifiers = if c.is_ascii_uppercase() {
+        KeyModifiers::SHIFT
+    } else {
+        KeyModifiers::NONE
+    };
+    KeyEvent {
+        code: KeyCode::Char(c),
+        modifiers,
+        kind: KeyEventKind::Press,
+        state: KeyEventState::NONE,
+    }
+}
+
+fn write_key_cases() -> Vec<WriteKeyCase> {
+    use super::read_only::WriteAction;
+    use super::types::{CommentTab, GitOpsState, LeftPaneFocus};
+
+    fn none(_: &mut App) {}
+    fn multiline(app: &mut App) {
+        app.multiline_selection = Some(MultilineSelection {
+            anchor_line: 1,
+            cursor_line: 2,
+        });
+    }
+    fn comment_panel(app: &mut App) {
+        app.cmt.comment_panel_open = true;
+    }
+    fn review_tab(app: &mut App) {
+        app.cmt.comment_tab = CommentTab::Review;
+    }
+    fn issue_comment_detail(app: &mut App) {
+        let mut state = super::types::IssueState::new();
+        state.issue_comment_detail_mode = true;
+        app.issue_state = Some(state);
+    }
+    fn git_ops_tree(app: &mut App) {
+        app.git_ops_state = Some(GitOpsState::new(Vec::new()));
+    }
+    fn git_ops_commits(app: &mut App) {
+        let mut ops = GitOpsState::new(Vec::new());
+        ops.left_focus = LeftPaneFocus::Commits;
+        app.git_ops_state = Some(ops);
+    }
+
+    const SPACE: KeyEvent = char_key(' ');
+    let case = |action, state, setup: fn(&mut App), pending, key| WriteKeyCase {
+        action,
+        state,
+        setup,
+        pending,
+        key,
+    };
+    vec![
+        case(
+            WriteAction::Comment,
+            AppState::DiffView,
+            none,
+            &[],
+            char_key('c'),
+        ),
+        case(
+            WriteAction::Comment,
+            AppState::SplitViewDiff,
+            multiline,
+            &[],
+            char_key('c'),
+        ),
+        case(
+            WriteAction::Suggestion,
+            AppState::DiffView,
+            none,
+            &[],
+            char_key('s'),
+        ),
+        case(
+            WriteAction::Suggestion,
+            AppState::DiffView,
+            multiline,
+            &[],
+            char_key('e'),
+        ),
+        case(
+            WriteAction::Suggestion,
+            AppState::DiffView,
+            multiline,
+            &[],
+            char_key('f'),
+        ),
+        case(
+            WriteAction::Reply,
+            AppState::DiffView,
+            comment_panel,
+            &[],
+            char_key('r'),
+        ),
+        case(
+            WriteAction::Review,
+            AppState::FileList,
+            none,
+            &[],
+            char_key('a'),
+        ),
+        case(
+            WriteAction::Review,
+            AppState::SplitViewFileList,
+            none,
+            &[],
+            char_key('r'),
+        ),
+        case(
+            WriteAction::Review,
+            AppState::FileList,
+            none,
+            &[SPACE],
+            char_key('s'),
+        ),
+        case(
+            WriteAction::MarkViewed,
+            AppState::FileList,
+            none,
+            &[],
+            char_key('v'),
+        ),
+        case(
+            WriteAction::MarkViewed,
+            AppState::SplitViewFileList,
+            none,
+            &[],
+            char_key('V'),
+        ),
+        case(
+            WriteAction::ResolveThread,
+            AppState::CommentList,
+            review_tab,
+            &[],
+            char_key('x'),
+        ),
+        case(
+            WriteAction::Reaction,
+            AppState::CommentList,
+            review_tab,
+            &[],
+            char_key('+'),
+        ),
+        case(
+            WriteAction::IssueComment,
+            AppState::IssueDetail,
+            none,
+            &[],
+            char_key('c'),
+        ),
+        case(
+            WriteAction::IssueComment,
+            AppState::IssueCommentList,
+            none,
+            &[],
+            char_key('c'),
+        ),
+        case(
+            WriteAction::IssueComment,
+            AppState::IssueCommentList,
+            issue_comment_detail,
+            &[],
+            char_key('r'),
+        ),
+        case(
+            WriteAction::AiRally,
+            AppState::FileList,
+            none,
+            &[],
+            char_key('A'),
+        ),
+        case(
+            WriteAction::EditPeople,
+            AppState::FileList,
+            none,
+            &[SPACE],
+            char_key('R'),
+        ),
+        case(
+            WriteAction::EditPeople,
+            AppState::SplitViewFileList,
+            none,
+            &[SPACE],
+            char_key('A'),
+        ),
+        case(
+            WriteAction::Stage,
+            AppState::GitOpsSplitTree,
+            git_ops_tree,
+            &[],
+            SPACE,
+        ),
+        case(
+            WriteAction::Stage,
+            AppState::GitOpsSplitTree,
+            git_ops_tree,
+            &[],
+            char_key('s'),
+        ),
+        case(
+            WriteAction::Discard,
+            AppState::GitOpsSplitTree,
+            git_ops_tree,
+            &[],
+            char_key('d'),
+        ),
+        case(
+            WriteAction::Commit,
+            AppState::GitOpsSplitTree,
+            git_ops_tree,
+            &[],
+            char_key('c'),
+        ),
+        case(
+            WriteAction::Undo,
+            AppState::GitOpsSplitTree,
+            git_ops_tree,
+            &[],
+            char_key('u'),
+        ),
+        case(
+            WriteAction::Undo,
+            AppState::GitOpsSplitTree,
+            git_ops_commits,
+            &[],
+            char_key('u'),
+        ),
+        case(
+            WriteAction::Push,
+            AppState::GitOpsSplitTree,
+            git_ops_tree,
+            &[],
+            char_key('P'),
+        ),
+        case(
+            WriteAction::Reset,
+            AppState::GitOpsSplitTree,
+            git_ops_commits,
+            &[],
+            char_key('r'),
+        ),
+    ]
+}
+
+fn prepare_write_key_case(case: &WriteKeyCase, read_only: bool) -> App {
+    let mut app = make_app_with_patch("@@ -1,2 +1,3 @@\n context\n+added\n+more");
+    app.config.read_only = read_only;
+    app.state = case.state;
+    (case.setup)(&mut app);
+    for pending in case.pending {
+        app.push_pending_key(crate::keybinding::event_to_keybinding(pending).unwrap());
+    }
+    app
+}
+
+#[test]
+fn test_read_only_dispatch_denies_every_write_action() {
+    use super::read_only::WriteAction;
+
+    let cases = write_key_cases();
+    for action in WriteAction::ALL {
+        assert!(
+            cases.iter().any(|case| case.action == action),
+            "no key case for {action:?}"
+        );
+    }
+
+    for case in &cases {
+        let label = format!(
+            "{:?} via {:?} in {:?}",
+            case.action, case.key.code, case.state
+        );
+
+        let mut app = prepare_write_key_case(case, true);
+        assert_eq!(
+            app.write_action_for_key(&case.key),
+            Some(case.action),
+            "{label}"
+        );
+        assert!(app.deny_write_key(&case.key), "{label}");
+        assert!(app.pending_keys.is_empty(), "{label}");
+        let message = match app
+            .git_ops_state
+            .as_ref()
+            .and_then(|ops| ops.op_message.clone())
+        {
+            Some((message, _)) => message,
+            None => app.cmt.submission_result.clone().unwrap().1,
+        };
+        assert!(message.starts_with("Read-only mode"), "{label}: {message}");
+
+        let mut app = prepare_write_key_case(case, false);
+        assert!(!app.deny_write_key(&case.key), "{label}");
+        assert!(app.cmt.submission_result.is_none(), "{label}");
+    }
+}
+
+#[test]
+fn test_read_only_dispatch_lets_navigation_and_overlays_through() {
+    let mut app = make_app_with_patch("@@ -1,2 +1,3 @@\n context\n+added\n+more");
+    app.config.read_only = true;
+
+    // ナビゲーションやシーケンスの途中のキーは通す
+    app.state = AppState::DiffView;
+    for key in [char_key('j'), char_key('k'), char_key('g'), char_key('n')] {
+        assert!(!app.deny_write_key(&key));
+    }
+    app.state = AppState::FileList;
+    assert!(!app.deny_write_key(&char_key(' ')));
+    app.push_pending_key(crate::keybinding::event_to_keybinding(&char_key('g')).unwrap());
+    assert!(!app.deny_write_key(&char_key('g')));
+    app.clear_pending_keys();
+
+    // 文字入力中のオーバーレイではキーは書き込み操作に届かない
+    app.state = AppState::DiffView;
+    app.open_hunk_filter();
+    assert!(!app.deny_write_key(&char_key('c')));
+    app.hunk_filter = None;
+
+    // 書き込み操作の無い画面では何も拒否しない
+    app.state = AppState::PullRequestList;
+    assert!(!app.deny_write_key(&char_key('c')));
+    assert!(app.cmt.submission_result.is_none());
+
+    // local mode のファイル一覧では viewed はローカル記録なので通す
+    app.state = AppState::FileList;
+    app.local_mode = true;
+    assert!(!app.deny_write_key(&char_key('v')));
+    assert!(app.deny_write_key(&char_key('A')));
+}
+
+#[tokio::test]
+async fn test_symbol_digest_lists_symbols_and_opens_hunk() {
+    let patch = "@@ -1,3 +1,3 @@\n fn keep() {}\n-fn old() {}\n+fn renamed() {}";
+    let mut app = make_app_with_patch(patch);
+    app.state = AppState::FileList;
+
+    app.toggle_symbol_digest();
+    assert_eq!(app.state, AppState::SymbolDigest);
+    let digest = app.symbol_digest.as_ref().unwrap();
+    let names: Vec<Option<&str>> = digest
+        .rows
+        .iter()
+        .map(|r| r.symbol.as_ref().map(|s| s.name.as_str()))
+        .collect();
+    assert_eq!(names, vec![None, Some("old"), Some("renamed")]);
+    assert_eq!(digest.selected, 0);
+
+    app.symbol_digest.as_mut().unwrap().selected = 2;
+    app.open_symbol_digest_row();
+    assert_eq!(app.state, AppState::DiffView);
+    assert_eq!(app.diff_scroll.selected_line, 3);
+    assert_eq!(app.diff_view_return_state, AppState::SymbolDigest);
+
+    // diff から戻るとダイジェストへ、もう一度トグルすると元の画面へ
+    app.handle_fullscreen_diff_quit();
+    assert_eq!(app.state, AppState::SymbolDigest);
+    app.toggle_symbol_digest();
+    assert_eq!(app.state, AppState::FileList);
+    assert!(app.symbol_digest.is_none());
+}
+
+#[test]
+fn test_crlf_to_lf_hunk_is_summarized() {
+    let patch = "@@ -1,3 +1,3 @@\n-fn main() {\r\n-    run();\r\n-}\r\n+fn main() {\n+    run();\n+}\n@@ -10,1 +10,1 @@\n-old\n+new\n";
+    let mut app = make_app_with_patch(patch);
+    app.state = AppState::DiffView;
+    app.update_diff_line_count();
+    let mut cache = crate::ui::diff_view::build_plain_diff_cache(patch, 4);
+    cache.file_index = 0;
+    app.diff_store.current = Some(cache);
+
+    let render = |app: &App| -> Vec<String> {
+        let cache = app.diff_store.current.as_ref().unwrap();
+        crate::ui::diff_view::render_diff_window(app, cache, 0, 20, 80)
+            .iter()
+            .map(|l| l.to_string())
+            .collect()
+    };
+
+    let lines = render(&app);
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("@@ -1,3 +1,3 @@"));
+    assert!(lines[1].contains("line endings changed (CRLF→LF), 3 lines"));
+    assert!(lines[2].starts_with("@@ -10,1 +10,1 @@"));
+    assert!(!lines.iter().any(|l| l.contains("run();")));
+
+    // 折りたたまれた行には選択が止まらない
+    app.diff_scroll.selected_line = 1;
+    app.snap_to_visible_hunk(0);
+    assert_eq!(app.diff_scroll.selected_line, 7);
+
+    app.toggle_line_endings();
+    assert!(app.line_endings_expanded);
+    assert_eq!(render(&app).len(), 10);
+}
+
+#[test]
+fn test_folded_hunk_survives_cache_rebuild() {
+    let patch = "@@ -1,3 +1,3 @@\n a\n-b\n+c\n@@ -10,2 +10,2 @@\n-old\n+new";
+    let mut app = make_app_with_patch(patch);
+    app.state = AppState::DiffView;
+    app.update_diff_line_count();
+    app.diff_scroll.selected_line = 2;
+
+    app.toggle_hunk_fold();
+    assert_eq!(app.diff_scroll.selected_line, 0);
+    assert_eq!(app.current_folded_hunks().first(), Some(&(1..4)));
+
+    // キャッシュを作り直しても畳んだ状態はファイル単位で残る
+    let mut cache = crate::ui::diff_view::build_plain_diff_cache(patch, 4);
+    cache.file_index = 0;
+    app.diff_store.current = Some(cache);
+    let render = |app: &App| -> Vec<String> {
+        let cache = app.diff_store.current.as_ref().unwrap();
+        crate::ui::diff_view::render_diff_window(app, cache, 0, 20, 80)
+            .iter()
+            .map(|l| l.to_string())
+            .collect()
+    };
+    let lines = render(&app);
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("@@ -1,3 +1,3 @@"));
+    assert!(lines[0].ends_with("(3 lines hidden)"));
+    assert!(lines[1].starts_with("@@ -10,2 +10,2 @@"));
+
+    // j はヘッダから畳んだ本体を飛ばして次の hunk へ
+    app.diff_scroll.selected_line = 1;
+    app.snap_to_visible_hunk(0);
+    assert_eq!(app.diff_scroll.selected_line, 4);
+
+    app.diff_scroll.selected_line = 0;
+    app.toggle_hunk_fold();
+    assert!(app.current_folded_hunks().is_empty());
+    assert_eq!(render(&app).len(), 7);
+}
+
+#[test]
+fn test_collapse_unchanged_placeholder_expands_on_enter() {
+    let mut patch = String::from("@@ -1,22 +1,22 @@\n-a\n+b\n");
+    for i in 0..20 {
+        patch.push_str(&format!(" ctx{}\n", i));
+    }
+    patch.push_str("-c\n+d");
+    let mut app = make_app_with_patch(&patch);
+    app.config.diff.collapse_unchanged = true;
+    app.state = AppState::DiffView;
+    app.update_diff_line_count();
+    let mut cache = crate::ui::diff_view::build_plain_diff_cache(&patch, 4);
+    cache.file_index = 0;
+    app.diff_store.current = Some(cache);
+    let render = |app: &App| -> Vec<String> {
+        let cache = app.diff_store.current.as_ref().unwrap();
+        crate::ui::diff_view::render_diff_window(app, cache, 0, 40, 80)
+            .iter()
+            .map(|l| l.to_string())
+            .collect()
+    };
+
+    // 文脈行 3..23 のうち前後 3 行を残し、6..20 をプレースホルダ 1 行に畳む
+    let lines = render(&app);
+    assert_eq!(lines.len(), 12);
+    assert_eq!(lines[6], "┈┈ 14 unchanged lines ┈┈ Enter to expand");
+    assert!(lines[7].contains("ctx17"));
+
+    // j はプレースホルダから隠した行を飛ばす
+    app.diff_scroll.selected_line = 7;
+    app.snap_to_visible_hunk(6);
+    assert_eq!(app.diff_scroll.selected_line, 20);
+
+    // 隠した行の外ではプレースホルダ展開は何もしない
+    assert!(!app.expand_unchanged_at_cursor());
+    app.diff_scroll.selected_line = 6;
+    assert!(app.expand_unchanged_at_cursor());
+    assert_eq!(app.diff_scroll.selected_line, 6);
+    assert_eq!(render(&app).len(), 25);
+
+    // zu で畳み直すと個別の展開も戻る
+    app.toggle_unchanged();
+    assert!(!app.unchanged_collapsed());
+    app.toggle_unchanged();
+    assert_eq!(render(&app).len(), 12);
+}
+
+#[test]
+#[serial]
+fn test_pending_comments_persist_across_sessions() {
+    let tempdir = tempdir().unwrap();
+    let _cache_home = ScopedCacheHome::new(tempdir.path());
+    let patch = "@@ -1,2 +1,3 @@\n context\n+added\n more";
+
+    let mut app = make_app_with_patch(patch);
+    app.cmt.pending_review = true;
+    let ctx = LineInputContext {
+        file_index: 0,
+        line_number: 2,
+        diff_position: 2,
+        start_line_number: None,
+        side: crate::diff::CommentSide::Right,
+    };
+    app.submit_comment(ctx, "hold this".to_string());
+
+    assert!(app.cmt.comment_submit_receiver.is_none());
+    assert_eq!(app.cmt.pending_comments.len(), 1);
+    let shown = app.cmt.review_comments.clone().unwrap();
+    assert_eq!(shown.len(), 1);
+    assert!(app.is_pending_comment(shown[0].id));
+
+    // 再起動後: キャッシュから復元され、GitHub のコメントと並んで pending 表示される
+    let mut reopened = make_app_with_patch(patch);
+    reopened.load_pending_comments();
+    reopened.apply_review_comments(vec![ReviewComment {
+        id: 10,
+        path: "test.rs".to_string(),
+        line: Some(1),
+        start_line: None,
+        body: "already on GitHub".to_string(),
+        user: crate::github::User {
+            login: "reviewer".to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        in_reply_to_id: None,
+        reactions: Default::default(),
+    }]);
+
+    let shown = reopened.cmt.review_comments.clone().unwrap();
+    assert_eq!(shown.len(), 2);
+    assert!(!reopened.is_pending_comment(10));
+    let pending = shown.iter().find(|c| c.body == "hold this").unwrap();
+    assert!(reopened.is_pending_comment(pending.id));
+    assert_eq!(pending.line, Some(2));
+}
+
+#[test]
+#[serial]
+fn test_pending_comments_cleared_after_submit() {
+    let tempdir = tempdir().unwrap();
+    let _cache_home = ScopedCacheHome::new(tempdir.path());
+    let mut app = make_app_with_patch("@@ -1,2 +1,3 @@\n context\n+added\n more");
+    app.cmt.pending_review = true;
+    for body in ["first", "second"] {
+        let ctx = LineInputContext {
+            file_index: 0,
+            line_number: 2,
+            diff_position: 2,
+            start_line_number: None,
+            side: crate::diff::CommentSide::Right,
+        };
+        app.submit_comment(ctx, body.to_string());
+    }
+    assert_eq!(app.cmt.pending_comments.len(), 2);
+
+    // 1 件目の送信後: 控えから外し、残りのみ保存される
+    app.cmt.pending_comments.remove(0);
+    app.clear_sent_pending_comments(1);
+    let stored = crate::cache::load_pending_review_comments("owner/repo", 1).unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].body, "second");
+
+    // 送信済みのコメントが GitHub から返ってきたら二重に表示しない
+    app.apply_review_comments(vec![ReviewComment {
+        id: 20,
+        path: "test.rs".to_string(),
+        line: Some(2),
+        start_line: None,
+        body: "second".to_string(),
+        user: crate::github::User {
+            login: "me".to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        in_reply_to_id: None,
+        reactions: Default::default(),
+    }]);
+    assert!(app.cmt.pending_comments.is_empty());
+    assert_eq!(app.cmt.review_comments.as_ref().unwrap().len(), 1);
+    assert!(crate::cache::load_pending_review_comments("owner/repo", 1)
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_in_flight_comment_is_saved_until_submitted() {
+    use crate::loader::CommentSubmitResult;
+    let tempdir = tempdir().unwrap();
+    let _cache_home = ScopedCacheHome::new(tempdir.path());
+    let patch = "@@ -1,2 +1,3 @@\n context\n+added\n more";
+    let ctx = LineInputContext {
+        file_index: 0,
+        line_number: 2,
+        diff_position: 2,
+        start_line_number: None,
+        side: crate::diff::CommentSide::Right,
+    };
+
+    // 送信中のコメントは pending 一覧にもまとめ送信の対象にも入らない
+    let mut app = make_app_with_patch(patch);
+    app.hold_in_flight_comment(&ctx, "do not lose me");
+    assert!(app.cmt.pending_comments.is_empty());
+    assert!(crate::cache::load_pending_review_comments("owner/repo", 1)
+        .unwrap()
+        .is_empty());
+
+    // 送信中に終了しても、次回起動時に pending として復元される
+    let mut reopened = make_app_with_patch(patch);
+    reopened.load_pending_comments();
+    assert_eq!(reopened.cmt.pending_comments.len(), 1);
+    assert_eq!(reopened.cmt.pending_comments[0].body, "do not lose me");
+    assert!(crate::cache::load_in_flight_comment("owner/repo", 1)
+        .unwrap()
+        .is_none());
+    crate::cache::save_pending_review_comments("owner/repo", 1, &[]).unwrap();
+
+    // 送信に失敗したら pending に移す
+    app.hold_in_flight_comment(&ctx, "do not lose me");
+    let (tx, rx) = mpsc::channel(1);
+    app.cmt.comment_submit_receiver = Some((1, rx));
+    app.cmt.comment_submitting = true;
+    tx.send(CommentSubmitResult::Error("network error".to_string()))
+        .await
+        .unwrap();
+    app.poll_comment_submit_updates();
+    let (success, msg) = app.cmt.submission_result.clone().unwrap();
+    assert!(!success);
+    assert!(msg.contains("kept as pending"), "{msg}");
+    assert!(app.cmt.in_flight_comment.is_none());
+    assert_eq!(app.cmt.pending_comments.len(), 1);
+    assert_eq!(
+        crate::cache::load_pending_review_comments("owner/repo", 1)
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(crate::cache::load_in_flight_comment("owner/repo", 1)
+        .unwrap()
+        .is_none());
+
+    // 送信できたら控えから消える（送信中に別 PR へ移っていても元の PR から消す）
+    app.hold_in_flight_comment(&ctx, "second try");
+    assert_eq!(app.cmt.pending_comments.len(), 1);
+    app.pr_number = Some(2);
+    app.release_in_flight_comment(1);
+    assert!(crate::cache::load_in_flight_comment("owner/repo", 1)
+        .unwrap()
+        .is_none());
+    let stored = crate::cache::load_pending_review_comments("owner/repo", 1).unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].body, "do not lose me");
+}
+
+#[tokio::test]
+async fn test_next_change_walks_hunks_across_files() {
+    let mut app = make_app_with_files(&["a.rs", "b.rs"]);
+    if let DataState::Loaded { ref mut files, .. } = app.data_state {
+        files[0].patch = Some("@@ -1 +1 @@\n-a\n+b\n@@ -10 +10 @@\n-c\n+d".to_string());
+    }
+    app.cmt.review_comments = Some(vec![]);
+    app.state = AppState::DiffView;
+    app.sync_diff_to_selected_file();
+
+    let mut walk = Vec::new();
+    for _ in 0..3 {
+        app.jump_to_change(true);
+        walk.push((app.selected_file, app.diff_scroll.selected_line));
+    }
+    // b.rs の唯一の hunk が最後。折り返しなしなので留まる
+    assert_eq!(walk, vec![(0, 3), (1, 0), (1, 0)]);
+
+    app.jump_to_change(false);
+    assert_eq!((app.selected_file, app.diff_scroll.selected_line), (0, 3));
+
+    app.config.diff.wrap_change_navigation = true;
+    app.jump_to_change(false);
+    app.jump_to_change(false);
+    assert_eq!((app.selected_file, app.diff_scroll.selected_line), (1, 0));
+}
+
+#[tokio::test]
+async fn test_change_block_jumps_within_file_from_top_line() {
+    let mut app = make_app_with_files(&["a.rs"]);
+    if let DataState::Loaded { ref mut files, .. } = app.data_state {
+        files[0].patch =
+            Some("@@ -1,6 +1,6 @@\n a\n-b\n+B\n c\n d\n e\n+f\n@@ -20 +20 @@\n-x\n+X".to_string());
+    }
+    app.cmt.review_comments = Some(vec![]);
+    app.state = AppState::DiffView;
+    app.sync_diff_to_selected_file();
+
+    let mut walk = Vec::new();
+    for _ in 0..4 {
+        app.jump_to_change_block(true);
+        walk.push(app.diff_scroll.scroll_offset);
+    }
+    // 変更ブロック先頭 (2, 7) と hunk ヘッダ (8)。末尾では留まる
+    assert_eq!(walk, vec![2, 7, 8, 8]);
+
+    // 基準は画面上端の行
+    app.diff_scroll.scroll_offset = 5;
+    app.jump_to_change_block(false);
+    assert_eq!(app.diff_scroll.selected_line, 2);
+
+    app.config.diff.wrap_change_navigation = true;
+    app.diff_scroll.scroll_offset = 8;
+    app.jump_to_change_block(true);
+    assert_eq!(app.diff_scroll.scroll_offset, 0);
+}
+
+#[test]
+fn test_comment_length_warning_threshold() {
+    let config = Config {
+        comment_warn_length: Some(10),
+        ..Default::default()
+    };
+    let (mut app, _) = App::new_loading("owner/repo", 1, config);
+
+    app.input_text_area.set_content("short");
+    assert!(app.comment_length_warning().is_none());
+
+    // テンプレートのコメント行は本文に数えない
+    app.input_text_area
+        .set_content("<!-- octorus: Enter your comment below -->\nshort");
+    assert!(app.comment_length_warning().is_none());
+
+    app.input_text_area.set_content("this body is too long");
+    assert_eq!(app.comment_length_warning(), Some((21, 10)));
+
+    app.config.comment_warn_length = Some(0);
+    assert!(app.comment_length_warning().is_none());
+}
+
+#[tokio::test]
+async fn test_open_file_on_load_opens_diff_or_reports_missing_file() {
+    let mut app = make_app_with_files(&["src/app/mod.rs", "src/lib.rs", "README.md"]);
+    app.set_open_file_on_load("./src/lib.rs".to_string());
+    app.apply_open_file_on_load();
+
+    assert_eq!(app.selected_file, 1);
+    assert_eq!(app.state, AppState::DiffView);
+    assert_eq!(app.diff_view_return_state, AppState::FileList);
+    assert!(app.open_file_on_load.is_none());
+
+    let mut app = make_app_with_files(&["src/app/mod.rs", "src/lib.rs"]);
+    app.set_open_file_on_load("src/main.rs".to_string());
+    app.apply_open_file_on_load();
+
+    assert_eq!(app.selected_file, 0);
+    assert_eq!(app.state, AppState::FileList);
+    let (ok, message) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert!(message.contains("src/main.rs"));
+}
+
+#[test]
+fn test_comment_anchor_preview_is_cached_per_position_and_patch() {
+    let mut app = make_app_with_patch("@@ -1,2 +1,3 @@\n context\n+added\n more");
+    let ctx = |diff_position| LineInputContext {
+        file_index: 0,
+        line_number: 2,
+        diff_position,
+        start_line_number: None,
+        side: crate::diff::CommentSide::Right,
+    };
+
+    let anchor = app.comment_anchor_preview(&ctx(2)).unwrap();
+    assert_eq!((anchor.line, anchor.content.as_str()), (2, "added"));
+    let cached = app.comment_anchor_cache.borrow().clone().unwrap();
+    assert_eq!((cached.file_index, cached.diff_position), (0, 2));
+    assert_eq!(cached.anchor, Some(anchor.clone()));
+    // 同じ位置なら解決し直さずキャッシュを返す
+    assert_eq!(app.comment_anchor_preview(&ctx(2)), Some(anchor));
+
+    // 位置が変われば解決し直す
+    assert_eq!(app.comment_anchor_preview(&ctx(3)).unwrap().content, "more");
+    assert_eq!(
+        app.comment_anchor_cache
+            .borrow()
+            .as_ref()
+            .unwrap()
+            .diff_position,
+        3
+    );
+
+    // patch が差し替わったら古い解決結果は使わない
+    assert_eq!(
+        app.comment_anchor_preview(&ctx(2)).unwrap().content,
+        "added"
+    );
+    if let DataState::Loaded { files, .. } = &mut app.data_state {
+        files[0].patch = Some("@@ -1,2 +1,3 @@\n context\n+changed\n more".to_string());
+    }
+    assert_eq!(
+        app.comment_anchor_preview(&ctx(2)).unwrap().content,
+        "changed"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_review_position_restores_file_or_falls_back_when_stale() {
+    let tempdir = tempdir().unwrap();
+    let _cache_home = ScopedCacheHome::new(tempdir.path());
+    // 並行実行中の他テスト（owner/repo #1）が保存位置を拾わないようリポジトリを分ける
+    let make_app = |filenames: &[&str]| {
+        let mut app = make_app_with_files(filenames);
+        app.repo = "owner/position".to_string();
+        app.pending_review_position = session::load_closed_session("owner/position", 1);
+        app
+    };
+
+    // PR を離れる際に閲覧位置のみをセッションとして保存する
+    let mut app = make_app(&["README.md", "src/lib.rs", "src/main.rs"]);
+    app.selected_file = 1;
+    app.diff_scroll.selected_line = 2;
+    app.diff_scroll.scroll_offset = 1;
+    app.close_review_session();
+    let saved = crate::cache::load_review_session("owner/position", 1)
+        .unwrap()
+        .unwrap();
+    assert!(saved.closed);
+    assert_eq!(saved.selected_path.as_deref(), Some("src/lib.rs"));
+
+    // 再訪時は復元確認を出さずに適用する
+    let mut app = make_app(&["README.md", "src/lib.rs", "src/main.rs"]);
+    assert!(app.pending_review_position.is_some());
+    app.apply_review_position_on_load();
+    assert_eq!(app.selected_file, 1);
+    assert_eq!(app.diff_scroll.selected_line, 2);
+    assert_eq!(app.diff_scroll.scroll_offset, 1);
+    assert!(app.pending_review_position.is_none());
+    app.tick_session_autosave();
+    assert!(!app.is_pending_session_restore());
+
+    // 保存時のファイルが消えていれば先頭ファイルへ戻す
+    let mut app = make_app(&["README.md", "src/main.rs"]);
+    app.selected_file = 1;
+    app.apply_review_position_on_load();
+    assert_eq!(app.selected_file, 0);
+    assert_eq!(app.diff_scroll.selected_line, 0);
+    assert_eq!(app.diff_scroll.scroll_offset, 0);
+
+    // local mode では保存・復元しない
+    let mut app = make_app(&["README.md", "src/lib.rs"]);
+    app.set_local_mode(true);
+    app.apply_review_position_on_load();
+    assert_eq!(app.selected_file, 0);
+    assert!(app.capture_review_session().is_none());
+}
+
+#[tokio::test]
+async fn test_toggle_highlighting_swaps_cache_and_keeps_position() {
+    let mut app = make_app_with_patch("@@ -1,2 +1,3 @@\n fn a() {}\n+fn b() {}\n+fn c() {}");
+    app.state = AppState::DiffView;
+    app.ensure_diff_cache();
+    app.diff_scroll.selected_line = 2;
+
+    // ハイライト済みキャッシュが届いた状態を再現
+    let mut highlighted = crate::ui::diff_view::build_plain_diff_cache(
+        app.files()[0].patch.as_deref().unwrap(),
+        app.config.diff.tab_width,
+    );
+    highlighted.highlighted = true;
+    app.diff_store.set_current(0, highlighted);
+
+    app.toggle_highlighting();
+    assert!(!app.is_highlighting_enabled());
+    let current = app.diff_store.current.as_ref().unwrap();
+    assert!(!current.highlighted);
+    assert!(!app.diff_store.has_highlight_rx());
+    assert!(!app.diff_store.has_prefetch_rx());
+    assert_eq!(app.diff_scroll.selected_line, 2);
+
+    app.toggle_highlighting();
+    assert!(app.is_highlighting_enabled());
+    // 退避されたハイライト済みキャッシュがストアから復元される
+    assert!(app.diff_store.current.as_ref().unwrap().highlighted);
+    assert_eq!(app.diff_scroll.selected_line, 2);
+}
+
+#[test]
+fn test_hunk_peek_opens_scrolls_and_closes() {
+    let mut app = make_app_with_patch("@@ -1,2 +1,3 @@\n ctx\n-old\n+new\n+more");
+    app.state = AppState::DiffView;
+
+    app.diff_scroll.selected_line = 2;
+    app.open_hunk_peek();
+    let peek = app.hunk_peek.as_ref().expect("peek should open");
+    assert_eq!(peek.filename, "test.rs");
+    assert_eq!(peek.hunk.rows.len(), 3);
+
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    assert!(app.handle_hunk_peek_input(&key(KeyCode::Char('j'))));
+    assert!(app.handle_hunk_peek_input(&key(KeyCode::Char('j'))));
+    assert!(app.handle_hunk_peek_input(&key(KeyCode::Char('j'))));
+    assert_eq!(app.hunk_peek.as_ref().unwrap().scroll, 2);
+    // 他のキーは消費するが何もしない
+    assert!(app.handle_hunk_peek_input(&key(KeyCode::Char('c'))));
+    assert!(app.hunk_peek.is_some());
+
+    assert!(app.handle_hunk_peek_input(&key(KeyCode::Esc)));
+    assert!(app.hunk_peek.is_none());
+    assert!(!app.handle_hunk_peek_input(&key(KeyCode::Char('j'))));
+}
+
+#[test]
+fn test_diff_layout_toggle_and_independent_column_scroll() {
+    let mut app = make_app_with_patch("@@ -1,2 +1,3 @@\n ctx\n-old\n+new\n+more");
+    assert_eq!(app.diff_layout, DiffLayout::Unified);
+
+    app.toggle_diff_layout();
+    assert_eq!(app.diff_layout, DiffLayout::Split);
+
+    // 左列（old）をスクロールしてからフォーカスを右列へ
+    app.scroll_split_column(true);
+    app.scroll_split_column(true);
+    app.toggle_split_focus();
+    app.scroll_split_column(true);
+    app.scroll_split_column(false);
+    app.scroll_split_column(false);
+    assert_eq!(app.split_scroll.old, 16);
+    assert_eq!(app.split_scroll.new, 0);
+
+    // unified に戻すと横スクロールはリセットされる
+    app.toggle_diff_layout();
+    assert_eq!(app.diff_layout, DiffLayout::Unified);
+    assert_eq!(app.split_scroll, SplitDiffScroll::default());
+}
+
+#[tokio::test]
+async fn test_linguist_generated_file_skips_highlighting() {
+    let dir = tempdir().unwrap();
+    std::fs::write(
+        dir.path().join(".gitattributes"),
+        "*.lock linguist-generated\n",
+    )
+    .unwrap();
+    let mut app = make_app_with_files(&["Cargo.lock", "src/main.rs"]);
+    app.working_dir = Some(dir.path().to_string_lossy().into_owned());
+    app.load_linguist_overrides();
+
+    app.selected_file = 0;
+    app.ensure_diff_cache();
+    assert!(!app.diff_store.current.as_ref().unwrap().highlighted);
+    assert!(!app.diff_store.has_highlight_rx());
+
+    app.selected_file = 1;
+    app.ensure_diff_cache();
+    assert!(app.diff_store.has_highlight_rx());
+}
+
+#[test]
+fn test_hunk_peek_outside_hunk_shows_message() {
+    let mut app = make_app_with_patch("diff --git a/test.rs b/test.rs\n@@ -1 +1 @@\n-a\n+b");
+    app.diff_scroll.selected_line = 0;
+    app.open_hunk_peek();
+    assert!(app.hunk_peek.is_none());
+    assert!(matches!(
+        app.cmt.submission_result,
+        Some((false, ref msg)) if msg.contains("not inside a hunk")
+    ));
+}
+
+#[test]
+fn test_hunk_explain_outside_hunk_shows_message() {
+    let mut app = make_app_with_patch("diff --git a/test.rs b/test.rs\n@@ -1 +1 @@\n-a\n+b");
+    app.diff_scroll.selected_line = 0;
+    app.open_hunk_explain();
+    assert!(app.hunk_explain.is_none());
+    assert!(matches!(
+        app.cmt.submission_result,
+        Some((false, ref msg)) if msg.contains("not inside a hunk")
+    ));
+}
+
+#[test]
+fn test_hunk_explain_shows_result_scrolls_and_closes() {
+    let mut app = make_app_with_patch("@@ -1,2 +1,3 @@\n ctx\n-old\n+new\n+more");
+    app.state = AppState::DiffView;
+    app.hunk_explain = Some(HunkExplainState {
+        filename: "test.rs".to_string(),
+        header: "@@ -1,2 +1,3 @@".to_string(),
+        explanation: LoadState::Loading,
+        scroll: 0,
+    });
+    let (tx, rx) = mpsc::channel(1);
+    app.hunk_explain_receiver = Some(rx);
+
+    app.poll_hunk_explain_updates();
+    assert!(matches!(
+        app.hunk_explain.as_ref().unwrap().explanation,
+        LoadState::Loading
+    ));
+
+    tx.try_send(Ok("line one\nline two".to_string())).unwrap();
+    app.poll_hunk_explain_updates();
+    assert!(app.hunk_explain_receiver.is_none());
+    assert!(matches!(
+        app.hunk_explain.as_ref().unwrap().explanation,
+        LoadState::Loaded(ref text) if text == "line one\nline two"
+    ));
+
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    assert!(app.handle_hunk_explain_input(&key(KeyCode::Char('j'))));
+    assert!(app.handle_hunk_explain_input(&key(KeyCode::Char('j'))));
+    assert_eq!(app.hunk_explain.as_ref().unwrap().scroll, 1);
+
+    assert!(app.handle_hunk_explain_input(&key(KeyCode::Esc)));
+    assert!(app.hunk_explain.is_none());
+    assert!(!app.handle_hunk_explain_input(&key(KeyCode::Char('j'))));
+}
+
+#[test]
+fn test_yank_text_strips_prefix_and_collects_hunk() {
+    let mut app = make_app_with_patch(
+        "diff --git a/test.rs b/test.rs\n@@ -1,2 +1,2 @@\n ctx\n-old line\n+new line\n@@ -9 +9 @@\n-x\n+y",
+    );
+    app.diff_scroll.selected_line = 4;
+    assert_eq!(app.yank_text(false).as_deref(), Some("new line"));
+    assert_eq!(
+        app.yank_text(true).as_deref(),
+        Some("@@ -1,2 +1,2 @@\n ctx\n-old line\n+new line")
+    );
+
+    app.diff_scroll.selected_line = 0;
+    assert!(app.yank_text(true).is_none());
+    app.diff_scroll.selected_line = 99;
+    assert!(app.yank_text(false).is_none());
+}
+
+#[test]
+fn test_permalink_uses_head_sha_and_new_side_range() {
+    let mut app = make_app_with_patch("@@ -1,3 +1,3 @@\n ctx\n-old line\n+new line\n ctx2");
+    app.diff_scroll.selected_line = 3;
+    assert_eq!(
+        app.permalink().as_deref(),
+        Some("https://github.com/owner/repo/blob/abc123/test.rs#L2")
+    );
+
+    // 範囲の両端は新しい側の行に詰める（削除行・ヘッダは飛ばす）
+    app.multiline_selection = Some(MultilineSelection {
+        anchor_line: 0,
+        cursor_line: 4,
+    });
+    assert_eq!(
+        app.permalink().as_deref(),
+        Some("https://github.com/owner/repo/blob/abc123/test.rs#L1-L3")
+    );
+
+    // 削除行だけでは新しい側の行がない
+    app.multiline_selection = None;
+    app.diff_scroll.selected_line = 2;
+    assert!(app.permalink().is_none());
+
+    app.local_mode = true;
+    app.yank_permalink();
+    assert!(matches!(
+        app.cmt.submission_result,
+        Some((false, ref msg)) if msg.contains("local mode")
+    ));
+}
+
+/// 実行中の単一ファイル取得の結果（またはタスク終了）を待ってから適用する
+async fn await_lazy_diff(app: &mut App) {
+    let mut rx = app.lazy_diff_receiver.take().unwrap();
+    let (tx, relay) = mpsc::channel(1);
+    if let Some(result) = rx.recv().await {
+        tx.send(result).await.unwrap();
+    }
+    drop(tx);
+    app.lazy_diff_receiver = Some(relay);
+    app.poll_lazy_diff_updates();
+}
+
+/// 結果を送らずに終了した（中断された）単一ファイル取得は「差分なし」ではなく incomplete になり、
+/// `reload_current_file` で再取得される
+#[tokio::test]
+async fn test_cancelled_lazy_diff_marks_file_incomplete_and_reload_refetches() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    if let DataState::Loaded { ref mut files, .. } = app.data_state {
+        files[0].patch = None;
+    }
+    app.local_mode = true;
+
+    let (tx, rx) = mpsc::channel(1);
+    app.lazy_diff_receiver = Some(rx);
+    app.lazy_diff_pending_file = Some("test.rs".to_string());
+    drop(tx);
+    app.poll_lazy_diff_updates();
+
+    assert!(app.lazy_diff_pending_file.is_none());
+    assert!(app.is_current_patch_incomplete());
+
+    // 再取得中は incomplete 表示を解除し、ロード中扱いにする
+    let tempdir = tempdir().unwrap();
+    app.working_dir = Some(tempdir.path().to_string_lossy().to_string());
+    app.reload_current_file();
+    assert!(!app.is_current_patch_incomplete());
+    assert!(app.is_lazy_diff_loading());
+
+    // git リポジトリ外なので取得は失敗 → 再び incomplete
+    await_lazy_diff(&mut app).await;
+    assert!(app.is_current_patch_incomplete());
+
+    // 取得に成功した結果が届けば incomplete は解除される
+    let (tx, rx) = mpsc::channel(1);
+    app.lazy_diff_receiver = Some(rx);
+    tx.send(SingleFileDiffResult {
+        filename: "test.rs".to_string(),
+        patch: Some("@@ -1 +1 @@\n-a\n+b".to_string()),
+        incomplete: false,
+    })
+    .await
+    .unwrap();
+    app.poll_lazy_diff_updates();
+    assert!(!app.is_current_patch_incomplete());
+    assert!(app.files()[0].patch.is_some());
+}
+
+/// PR mode: patch の無いファイルは incomplete になり、再取得はそのファイルの分だけを読む
+#[tokio::test]
+async fn test_pr_reload_current_file_fetches_only_that_file() {
+    use crate::github::backend::mock::RecordingBackend;
+
+    let patch = "@@ -1 +1 @@\n-a\n+b";
+    let backend = std::sync::Arc::new(RecordingBackend {
+        files_pages: std::collections::HashMap::from([(
+            1,
+            serde_json::json!([{
+                "filename": "test.rs",
+                "status": "modified",
+                "additions": 1,
+                "deletions": 1,
+                "patch": patch,
+            }])
+            .to_string(),
+        )]),
+        ..Default::default()
+    });
+    let mut app = make_app_with_patch(patch);
+    app.set_github_backend(backend.clone());
+    let DataState::Loaded { pr, mut files } =
+        std::mem::replace(&mut app.data_state, DataState::Loading)
+    else {
+        unreachable!();
+    };
+    files[0].patch = None;
+    app.handle_data_result(
+        1,
+        DataLoadResult::Success {
+            pr,
+            files,
+            revision: DataRevision::Fresh,
+        },
+    );
+    assert!(app.is_current_patch_incomplete());
+
+    app.reload_current_file();
+    assert!(app.is_lazy_diff_loading());
+    await_lazy_diff(&mut app).await;
+
+    assert!(!app.is_current_patch_incomplete());
+    assert_eq!(app.files()[0].patch.as_deref(), Some(patch));
+    assert!(backend
+        .calls()
+        .contains(&"fetch_changed_files_page owner/repo #1 page=1".to_string()));
+    assert!(!backend
+        .calls()
+        .iter()
+        .any(|c| c.starts_with("fetch_pr_diff")));
+}
+
+/// 全体のロード完了前 (PartiallyLoaded) でも、再取得時に古い patch と diff キャッシュを捨てる
+#[tokio::test]
+async fn test_reload_current_file_before_load_completes_drops_stale_patch() {
+    let mut app = make_app_with_files(&["a.rs", "b.rs"]);
+    let DataState::Loaded { pr, mut files } =
+        std::mem::replace(&mut app.data_state, DataState::Loading)
+    else {
+        unreachable!();
+    };
+    files[0].patch = Some("@@ -1 +1 @@\n-old\n+stale".to_string());
+    app.data_state = DataState::PartiallyLoaded { pr, files };
+    app.diff_store.store.insert(
+        0,
+        crate::ui::diff_view::build_plain_diff_cache("@@ -1 +1 @@\n-old\n+stale", 4),
+    );
+    app.diff_store.store.insert(
+        1,
+        crate::ui::diff_view::build_plain_diff_cache("@@ -1 +1 @@\n+b", 4),
+    );
+    app.local_mode = true;
+    let tempdir = tempdir().unwrap();
+    app.working_dir = Some(tempdir.path().to_string_lossy().to_string());
+
+    app.reload_current_file();
+
+    assert!(app.files()[0].patch.is_none());
+    assert!(!app.diff_store.store_contains_key(&0));
+    assert!(app.diff_store.store_contains_key(&1));
+    assert!(app.is_lazy_diff_loading());
+}
+
+#[tokio::test]
+async fn test_streamed_patches_fill_partially_loaded_files() {
+    let (mut app, tx) = App::new_loading("owner/repo", 1, Config::default());
+    app.state = AppState::FileList;
+    let files: Vec<ChangedFile> = ["a.rs", "b.rs"]
+        .iter()
+        .map(|name| ChangedFile {
+            patch: None,
+            ..make_changed_file(name)
+        })
+        .collect();
+
+    tx.try_send(DataLoadResult::Partial {
+        pr: make_test_pr(),
+        files: files.clone(),
+    })
+    .unwrap();
+    app.poll_data_updates();
+    assert!(matches!(app.data_state, DataState::PartiallyLoaded { .. }));
+    assert_eq!(app.files().len(), 2);
+    assert!(app.pr().is_some());
+
+    // 溜まっている patch は 1 回のポーリングでまとめて反映される
+    for name in ["a.rs", "b.rs"] {
+        tx.try_send(DataLoadResult::FilePatch {
+            pr_number: 1,
+            filename: name.to_string(),
+            patch: "@@ -1 +1 @@\n-x\n+y".to_string(),
+        })
+        .unwrap();
+    }
+    app.poll_data_updates();
+    assert!(app.files().iter().all(|f| f.patch.is_some()));
+    assert_eq!(app.diff_scroll.line_count, 3);
+
+    let files = app.files().to_vec();
+    tx.try_send(DataLoadResult::Success {
+        pr: make_test_pr(),
+        files,
+        revision: DataRevision::Fresh,
+    })
+    .unwrap();
+    app.poll_data_updates();
+    assert!(matches!(app.data_state, DataState::Loaded { .. }));
+}
+
+#[tokio::test]
+async fn test_submit_review_goes_through_github_backend() {
+    use crate::github::backend::mock::RecordingBackend;
+
+    let backend = std::sync::Arc::new(RecordingBackend::default());
+    let mut app = make_app_with_files(&["src/lib.rs"]);
+    app.set_github_backend(backend.clone());
+
+    app.submit_review_with_body(ReviewAction::Approve, "LGTM")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        backend.calls(),
+        vec!["submit_review owner/repo #1 APPROVE \"LGTM\"".to_string()]
+    );
+    assert_eq!(
+        app.cmt.submission_result,
+        Some((true, "Review submitted (approved)".to_string()))
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_submit_review_sends_pending_comments_in_one_call() {
+    use crate::github::backend::mock::RecordingBackend;
+
+    let tempdir = tempdir().unwrap();
+    let _cache_home = ScopedCacheHome::new(tempdir.path());
+    let backend = std::sync::Arc::new(RecordingBackend::default());
+    let mut app = make_app_with_patch("@@ -1,2 +1,3 @@\n context\n+added\n more");
+    app.set_github_backend(backend.clone());
+    app.cmt.pending_review = true;
+    for (line, position, body) in [(2, 2, "on added"), (3, 3, "on more")] {
+        let ctx = LineInputContext {
+            file_index: 0,
+            line_number: line,
+            diff_position: position,
+            start_line_number: None,
+            side: crate::diff::CommentSide::Right,
+        };
+        app.submit_comment(ctx, body.to_string());
+    }
+
+    // force-push: "added" は 1 行下へずれ、"more" は書き換えられて消える
+    if let DataState::Loaded { files, .. } = &mut app.data_state {
+        files[0].patch = Some("@@ -1,2 +1,4 @@\n context\n+new\n+added\n-more\n+other".to_string());
+    }
+    app.submit_review_with_body(ReviewAction::Comment, "see inline")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        backend.calls(),
+        vec![
+            "submit_review owner/repo #1 COMMENT \"see inline\" [test.rs:3 \"on added\"]"
+                .to_string()
+        ]
+    );
+    assert_eq!(app.cmt.pending_comments.len(), 1);
+    assert_eq!(app.cmt.pending_comments[0].body, "on more");
+    let (ok, message) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert!(message.contains("1 pending comment(s) no longer match the diff"));
+    let stored = crate::cache::load_pending_review_comments("owner/repo", 1).unwrap();
+    assert_eq!(stored.len(), 1);
+}
+
+#[tokio::test]
+async fn test_offline_app_never_calls_gh() {
+    use crate::github::backend::mock::ScriptedGhRunner;
+    use crate::github::{GhCliBackend, GhRetryPolicy};
+
+    // 呼ばれたら panic する（積んだ出力が無い）ランナー
+    let runner = std::sync::Arc::new(ScriptedGhRunner::default());
+    let mut app = make_app_with_files(&["src/lib.rs"]);
+    if let DataState::Loaded { pr, .. } = &mut app.data_state {
+        pr.node_id = Some("PR_node".to_string());
+    }
+    app.set_offline(true);
+    app.set_github_backend(std::sync::Arc::new(GhCliBackend::with_runner(
+        runner.clone(),
+        GhRetryPolicy::default(),
+        true,
+    )));
+
+    app.load_discussion_comments();
+    let (_, mut rx) = app.cmt.discussion_comment_receiver.take().unwrap();
+    assert!(rx.recv().await.unwrap().unwrap_err().contains("offline"));
+
+    app.start_mark_selected_file_as_viewed();
+    let (_, mut rx) = app.mark_viewed_receiver.take().unwrap();
+    let MarkViewedResult::Completed { error, .. } = rx.recv().await.unwrap();
+    assert!(error.unwrap().contains("offline"));
+
+    app.open_checks_list(1);
+    let (_, mut rx) = app.chk.checks_receiver.take().unwrap();
+    assert!(rx.recv().await.unwrap().unwrap_err().contains("offline"));
+
+    assert!(runner.calls().is_empty());
+}
+
+#[test]
+fn test_apply_reloaded_config_swaps_theme_and_invalidates_caches() {
+    let mut app = App::new_for_test();
+    app.data_state = DataState::Loaded {
+        pr: Box::new(make_local_pr()),
+        files: vec![ChangedFile {
+            filename: "main.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some("@@ -1 +1 @@\n+fn main(){}".to_string()),
+            viewed: false,
+        }],
+    };
+    let build = || crate::ui::diff_view::build_plain_diff_cache("@@ -1 +1 @@\n+fn main(){}", 4);
+    app.diff_store.current = Some(build());
+    app.diff_store.store.insert(0, build());
+    app.config.read_only = true;
+
+    // テーマが同じなら既存キャッシュは維持される
+    app.apply_reloaded_config(app.config.clone());
+    assert!(app.diff_store.current.is_some());
+    assert_eq!(app.diff_store.store.len(), 1);
+
+    let mut config = Config::default();
+    config.diff.theme = "Dracula".to_string();
+    app.apply_reloaded_config(config);
+
+    assert_eq!(app.config.diff.theme, "Dracula");
+    assert!(
+        app.config.read_only,
+        "CLI-derived read_only must survive reload"
+    );
+    assert!(app.diff_store.current.is_none());
+    assert!(app.diff_store.store.is_empty());
+}
+
+#[test]
+fn test_reload_config_keeps_old_config_on_parse_error() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join(".octorus")).unwrap();
+    std::fs::write(dir.path().join(".octorus/config.toml"), "[diff\ntheme = ").unwrap();
+
+    let mut app = App::new_for_test();
+    app.config.diff.theme = "base16-ocean.dark".to_string();
+    app.config.project_root = dir.path().to_path_buf();
+
+    app.reload_config();
+
+    assert_eq!(app.config.diff.theme, "base16-ocean.dark");
+    let (ok, message) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert!(message.starts_with("Config reload failed"), "{message}");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_switch_repo_reapplies_repo_section() {
+    let dir = tempdir().unwrap();
+    let _config_home = ScopedCacheHome::config(dir.path());
+    std::fs::create_dir_all(dir.path().join("octorus")).unwrap();
+    std::fs::write(
+        dir.path().join("octorus/config.toml"),
+        "[diff]\ntab_width = 4\n\n[repos.\"owner/other\"]\nread_only = true\n\n[repos.\"owner/other\".diff]\ntab_width = 2\n",
+    )
+    .unwrap();
+    let project_root = dir.path().join("project");
+    std::fs::create_dir_all(&project_root).unwrap();
+
+    let config = Config {
+        project_root,
+        ..Default::default()
+    };
+    let mut app = App::new_pr_list("owner/repo", config);
+
+    app.switch_repo("owner/other");
+    assert_eq!(app.repo, "owner/other");
+    assert!(app.config.read_only);
+    assert_eq!(app.config.diff.tab_width, 2);
+    assert_eq!(
+        app.config.loaded_repo_section.as_deref(),
+        Some("owner/other")
+    );
+    assert!(app.prs.pr_list.is_loading());
+
+    // セクションの無いリポジトリに戻るとグローバルの値に戻る
+    app.switch_repo("owner/repo");
+    assert_eq!(app.config.diff.tab_width, 4);
+    assert!(app.config.loaded_repo_section.is_none());
+    assert!(
+        app.config.read_only,
+        "read_only is never lifted by a switch"
+    );
+}
+
+#[test]
+fn test_reaction_picker_targets_selected_reply_and_closes_on_esc() {
+    let (mut app, _tx) = App::new_loading("owner/repo", 1, Config::default());
+    app.apply_review_comments(vec![
+        pr157_rc(1, None, "src/a.rs", "2025-01-01T00:00:00Z"),
+        pr157_rc(2, Some(1), "src/a.rs", "2025-01-01T01:00:00Z"),
+    ]);
+    app.cmt.expanded_thread = Some(0);
+    app.cmt.expanded_selected = 1;
+
+    app.open_reaction_picker();
+    assert_eq!(
+        app.reaction_picker,
+        Some(ReactionPickerState {
+            comment_id: 2,
+            selected: 0
+        })
+    );
+
+    app.handle_reaction_picker_key(&KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE));
+    app.handle_reaction_picker_key(&KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+    assert_eq!(app.reaction_picker.as_ref().unwrap().selected, 2);
+
+    app.handle_reaction_picker_key(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.reaction_picker.is_none());
+    assert!(app.cmt.reaction_receiver.is_none());
+}
+
+#[test]
+fn test_reaction_picker_denied_in_read_only() {
+    let (mut app, _tx) = App::new_loading("owner/repo", 1, Config::default());
+    app.config.read_only = true;
+    app.state = AppState::CommentList;
+    app.apply_review_comments(vec![pr157_rc(1, None, "src/a.rs", "2025-01-01T00:00:00Z")]);
+
+    assert!(app.deny_write_key(&char_key('+')));
+    assert!(app.reaction_picker.is_none());
+    let (ok, msg) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert!(msg.starts_with("Read-only mode"), "{msg}");
+}
+
+#[test]
+fn test_reaction_update_records_id_and_reverts_failed_removal() {
+    use crate::github::ReactionKind;
+
+    let (mut app, _tx) = App::new_loading("owner/repo", 1, Config::default());
+    let mut comment = pr157_rc(1, None, "src/a.rs", "2025-01-01T00:00:00Z");
+    comment.reactions.rocket = 1;
+    app.apply_review_comments(vec![comment]);
+
+    app.apply_reaction_update(ReactionUpdate {
+        comment_id: 1,
+        kind: ReactionKind::Rocket,
+        removed: None,
+        result: Ok(Some(77)),
+    });
+    assert_eq!(
+        app.cmt.own_reactions.get(&(1, ReactionKind::Rocket)),
+        Some(&77)
+    );
+
+    // 削除は送信前に件数を減らし ID を外しているので、失敗時は両方を戻す
+    app.cmt.own_reactions.clear();
+    app.cmt.review_comments.as_mut().unwrap()[0]
+        .reactions
+        .rocket = 0;
+    app.apply_reaction_update(ReactionUpdate {
+        comment_id: 1,
+        kind: ReactionKind::Rocket,
+        removed: Some(77),
+        result: Err("boom".to_string()),
+    });
+    assert_eq!(
+        app.cmt.own_reactions.get(&(1, ReactionKind::Rocket)),
+        Some(&77)
+    );
+    assert_eq!(
+        app.cmt.review_comments.as_ref().unwrap()[0]
+            .reactions
+            .rocket,
+        1
+    );
+    let (ok, msg) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert!(msg.contains("boom"), "{msg}");
+}
+
+#[test]
+fn test_browser_command_per_platform() {
+    use super::input::browser_command;
+
+    let url = "https://github.com/owner/repo/pull/42";
+    assert_eq!(
+        browser_command(url, "macos"),
+        ("open", vec![url.to_string()])
+    );
+    assert_eq!(
+        browser_command(url, "linux"),
+        ("xdg-open", vec![url.to_string()])
+    );
+    assert_eq!(
+        browser_command(url, "windows"),
+        (
+            "cmd",
+            vec![
+                "/C".to_string(),
+                "start".to_string(),
+                String::new(),
+                url.to_string()
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_pr_html_url_deserialized_and_open_in_local_mode_is_noop() {
+    let pr: PullRequest = serde_json::from_value(serde_json::json!({
+        "number": 42,
+        "title": "t",
+        "body": null,
+        "state": "open",
+        "head": { "ref": "feature", "sha": "abc" },
+        "base": { "ref": "main", "sha": "def" },
+        "user": { "login": "alice" },
+        "updated_at": "2025-01-01T00:00:00Z",
+        "html_url": "https://github.com/owner/repo/pull/42"
+    }))
+    .unwrap();
+    assert_eq!(
+        pr.html_url.as_deref(),
+        Some("https://github.com/owner/repo/pull/42")
+    );
+
+    let mut app = App::new_for_test();
+    app.local_mode = true;
+    app.open_current_pr_in_browser();
+    let (ok, msg) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert!(msg.contains("local mode"), "{msg}");
+}
+
+#[test]
+fn test_context_expansion_splices_and_resets_and_keeps_review_positions() {
+    let source: String = (1..=12).map(|i| format!("line {}\n", i)).collect();
+    let patch = "@@ -5,2 +5,2 @@\n line 5\n-old 6\n+line 6";
+    let mut app = make_app_with_patch(patch);
+    app.update_diff_line_count();
+
+    app.diff_scroll.selected_line = 1;
+    app.expand_context_at_cursor();
+    let (ok, msg) = app.cmt.submission_result.clone().unwrap();
+    assert!(!ok);
+    assert!(msg.contains("hunk header"), "{msg}");
+
+    app.apply_context_expansion("test.rs", 0, &source);
+    let expanded = app.files()[0].patch.clone().unwrap();
+    assert!(expanded.starts_with("@@ -1,12 +1,12 @@\n line 1\n"));
+    assert_eq!(app.diff_scroll.line_count, 14);
+
+    // 表示上の position は展開前の patch の position に読み替える
+    let info = crate::diff::get_line_info(&expanded, 7).unwrap();
+    assert_eq!(info.new_line_number, Some(6));
+    assert_eq!(
+        app.review_position(
+            crate::diff::CommentSide::Right,
+            6,
+            info.diff_position.unwrap()
+        ),
+        Some(3)
+    );
+    assert_eq!(
+        app.review_position(crate::diff::CommentSide::Left, 6, 6),
+        Some(2)
+    );
+    // 足した文脈行にはコメントできない
+    assert_eq!(
+        app.review_position(crate::diff::CommentSide::Right, 1, 1),
+        None
+    );
+    assert_eq!(app.review_patch(0), Some(patch));
+
+    app.reset_context();
+    assert_eq!(app.files()[0].patch.as_deref(), Some(patch));
+    assert_eq!(app.diff_scroll.line_count, 4);
+    assert_eq!(
+        app.review_position(crate::diff::CommentSide::Right, 1, 1),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_toggle_whitespace_hides_and_restores_whitespace_only_changes() {
+    let patch = "@@ -1,3 +1,3 @@\n keep\n-foo \n+foo\n-foo\n+bar";
+    let mut app = make_app_with_patch(patch);
+    app.update_diff_line_count();
+    assert_eq!(app.diff_scroll.line_count, 6);
+
+    app.toggle_whitespace();
+    assert!(app.hide_whitespace);
+    assert_eq!(
+        app.files()[0].patch.as_deref(),
+        Some("@@ -1,3 +1,3 @@\n keep\n foo\n-foo\n+bar")
+    );
+    assert_eq!(app.diff_scroll.line_count, 5);
+    // 文脈行になった行へのコメントは元の追加行の position に付く
+    assert_eq!(
+        app.review_position(crate::diff::CommentSide::Right, 2, 2),
+        Some(3)
+    );
+
+    app.toggle_whitespace();
+    assert!(!app.hide_whitespace);
+    assert_eq!(app.files()[0].patch.as_deref(), Some(patch));
+    assert_eq!(app.diff_scroll.line_count, 6);
+    assert!(app.original_patches.is_empty());
+}
+
+#[tokio::test]
+async fn test_pr_people_input_opens_and_rejects_invalid_logins() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    app.state = AppState::FileList;
+
+    app.open_pr_people_input(crate::github::PrPeopleRole::Reviewer);
+    assert_eq!(app.state, AppState::TextInput);
+    assert!(matches!(
+        app.input_mode,
+        Some(InputMode::PrPeople(crate::github::PrPeopleRole::Reviewer))
+    ));
+
+    // 不正なログインは送信せずにメッセージを出す
+    app.cancel_input();
+    app.apply_pr_people(crate::github::PrPeopleRole::Assignee, "org/team");
+    assert!(app.pr_people_receiver.is_none());
+    assert!(matches!(
+        app.cmt.submission_result,
+        Some((false, ref msg)) if msg.contains("org/team")
+    ));
+
+    // 読み取り専用モードでは開かない
+    app.config.read_only = true;
+    app.push_pending_key(crate::keybinding::event_to_keybinding(&char_key(' ')).unwrap());
+    assert!(app.deny_write_key(&char_key('A')));
+    assert_eq!(app.state, AppState::FileList);
+    assert!(app.input_mode.is_none());
+}
+
+#[tokio::test]
+async fn test_poll_pr_list_appends_next_page_without_duplicates() {
+    use tokio::sync::mpsc;
+
+    let pr = |number: u32| crate::github::PullRequestSummary {
+        number,
+        title: format!("PR {}", number),
+        state: "OPEN".to_string(),
+        author: crate::github::User {
+            login: "user".to_string(),
+        },
+        is_draft: false,
+        labels: vec![],
+        updated_at: "".to_string(),
+        status_check_rollup: vec![],
+    };
+    let mut app = App::new_for_test();
+    app.prs.pr_list = LoadState::Loading;
+
+    let (tx, rx) = mpsc::channel(1);
+    app.prs.pr_list_receiver = Some(rx);
+    tx.send(Ok(crate::github::PrListPage {
+        items: vec![pr(5), pr(4), pr(3)],
+        has_more: true,
+    }))
+    .await
+    .unwrap();
+    app.poll_pr_list_updates();
+    assert_eq!(app.prs.pr_list_offset, 3);
+
+    // 取得の間に新しい PR が増えて 2 ページ目の先頭が 1 ページ目と重なった
+    app.prs.selected_pr = 0;
+    app.load_more_prs();
+    assert!(matches!(app.prs.pr_list, LoadState::LoadingMore(_)));
+    let (tx, rx) = mpsc::channel(1);
+    app.prs.pr_list_receiver = Some(rx);
+    tx.send(Ok(crate::github::PrListPage {
+        items: vec![pr(3), pr(2), pr(1)],
+        has_more: false,
+    }))
+    .await
+    .unwrap();
+    app.poll_pr_list_updates();
+
+    let numbers: Vec<u32> = app
+        .prs
+        .pr_list
+        .as_loaded()
+        .unwrap()
+        .iter()
+        .map(|pr| pr.number)
+        .collect();
+    assert_eq!(numbers, vec![5, 4, 3, 2, 1]);
+    // offset は重複を除く前の取得件数で進める
+    assert_eq!(app.prs.pr_list_offset, 6);
+    assert!(!app.prs.pr_list_has_more);
+}
+
+#[test]
+fn test_theme_picker_previews_current_file_and_restores_on_cancel() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-fn old() {}\n+fn main() { let x = \"s\"; }");
+    app.state = AppState::DiffView;
+    let original = app.config.diff.theme.clone();
+
+    let line_styles = |app: &App| -> Vec<ratatui::style::Style> {
+        let cache = app.diff_store.current.as_ref().unwrap();
+        cache.lines[2].spans.iter().map(|s| s.style).collect()
+    };
+    app.preview_theme("base16-ocean.dark");
+    app.preview_theme("InspiredGitHub");
+    assert_eq!(app.config.diff.theme, "InspiredGitHub");
+    let light = line_styles(&app);
+    app.preview_theme("base16-ocean.dark");
+    let dark = line_styles(&app);
+    assert_ne!(
+        light, dark,
+        "the same line must be re-highlighted per theme"
+    );
+
+    // 移動で試し適用、Esc で開いた時点のテーマに戻す
+    app.config.diff.theme = original.clone();
+    app.open_theme_picker();
+    let start = app.theme_picker.as_ref().unwrap().selected;
+    assert!(app.handle_theme_picker_input(&make_key(KeyCode::Char('j'))));
+    let picker = app.theme_picker.as_ref().unwrap();
+    assert_eq!(picker.selected, start + 1);
+    assert_eq!(app.config.diff.theme, picker.themes[start + 1]);
+    assert!(app.handle_theme_picker_input(&make_key(KeyCode::Esc)));
+    assert!(app.theme_picker.is_none());
+    assert_eq!(app.config.diff.theme, original);
+
+    // Enter で確定
+    app.open_theme_picker();
+    app.handle_theme_picker_input(&make_key(KeyCode::Char('j')));
+    app.handle_theme_picker_input(&make_key(KeyCode::Enter));
+    assert!(app.theme_picker.is_none());
+    assert_ne!(app.config.diff.theme, original);
+    assert!(!app.handle_theme_picker_input(&make_key(KeyCode::Char('j'))));
+}
+
+fn mouse(kind: crossterm::event::MouseEventKind, column: u16, row: u16) -> event::MouseEvent {
+    event::MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+fn make_mouse_diff_app(patch: &str) -> App {
+    let mut app = make_app_with_patch(patch);
+    app.config.mouse = true;
+    app.state = AppState::DiffView;
+    let mut cache = crate::ui::diff_view::build_plain_diff_cache(patch, 4);
+    cache.file_index = 0;
+    app.diff_scroll.line_count = cache.lines.len();
+    app.diff_store.set_current(0, cache);
+    // 枠の内側は y=4..14（10 行）
+    app.mouse_layout
+        .diff
+        .set(Some(ratatui::layout::Rect::new(0, 3, 80, 12)));
+    app
+}
+
+#[tokio::test]
+async fn test_mouse_click_selects_file_in_file_list() {
+    use crossterm::event::{MouseButton, MouseEventKind};
+
+    let mut app = make_app_with_files(&["a.rs", "b.rs", "c.rs"]);
+    app.mouse_layout
+        .file_list
+        .set(Some(ratatui::layout::Rect::new(0, 3, 40, 10)));
+    let click = |row| mouse(MouseEventKind::Down(MouseButton::Left), 5, row);
+
+    // 設定で無効なら何もしない
+    app.handle_mouse_event(click(6));
+    assert_eq!(app.selected_file, 0);
+
+    app.config.mouse = true;
+    let rows = app.file_list_rows();
+    app.handle_mouse_event(click(6));
+    assert_eq!(rows[2], FileListRow::File(app.selected_file));
+
+    // 最終行より下・枠線上は無視
+    app.handle_mouse_event(click(8));
+    app.handle_mouse_event(click(3));
+    assert_eq!(rows[2], FileListRow::File(app.selected_file));
+
+    // スクロール済みの一覧では表示オフセットを足す
+    app.file_list_scroll_offset = 1;
+    app.handle_mouse_event(click(4));
+    assert_eq!(rows[1], FileListRow::File(app.selected_file));
+}
+
+#[test]
+fn test_mouse_wheel_and_click_move_diff_cursor() {
+    use crossterm::event::{MouseButton, MouseEventKind};
+
+    let patch = std::iter::once("@@ -1,30 +1,30 @@".to_string())
+        .chain((1..30).map(|i| format!(" line {}", i)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut app = make_mouse_diff_app(&patch);
+
+    // ホイールは表示範囲を 3 行ずつ動かし、カーソルを範囲内に保つ
+    app.handle_mouse_event(mouse(MouseEventKind::ScrollDown, 10, 8));
+    assert_eq!(app.diff_scroll.scroll_offset, 3);
+    assert_eq!(app.diff_scroll.selected_line, 3);
+    // diff の外でのホイールは無視
+    app.handle_mouse_event(mouse(MouseEventKind::ScrollDown, 10, 20));
+    assert_eq!(app.diff_scroll.scroll_offset, 3);
+
+    // クリックした表示行へカーソルを移す（先頭 3 行はスクロール済み）
+    app.handle_mouse_event(mouse(MouseEventKind::Down(MouseButton::Left), 10, 8));
+    assert_eq!(app.diff_scroll.selected_line, 7);
+
+    app.handle_mouse_event(mouse(MouseEventKind::ScrollUp, 10, 8));
+    assert_eq!(app.diff_scroll.scroll_offset, 0);
+    assert_eq!(app.diff_scroll.selected_line, 7);
+
+    // ポップアップ表示中は無視
+    app.open_hunk_peek();
+    app.handle_mouse_event(mouse(MouseEventKind::Down(MouseButton::Left), 10, 4));
+    assert_eq!(app.diff_scroll.selected_line, 7);
+}
+
+#[test]
+fn test_diff_line_at_row_skips_folded_hunk() {
+    let patch = "@@ -1,3 +1,3 @@\n a\n-b\n+c\n@@ -10,2 +10,2 @@\n x\n+y";
+    let mut app = make_mouse_diff_app(patch);
+    let line_count = app.diff_scroll.line_count;
+    assert_eq!(app.diff_line_at_row(0, 1, line_count), Some(1));
+
+    // 先頭 hunk の本体を畳むと、ヘッダの次の表示行は次の hunk のヘッダ
+    app.diff_scroll.selected_line = 0;
+    app.toggle_hunk_fold();
+    assert_eq!(app.diff_line_at_row(0, 0, line_count), Some(0));
+    assert_eq!(app.diff_line_at_row(0, 1, line_count), Some(4));
+    assert_eq!(app.diff_line_at_row(0, 3, line_count), Some(6));
+    assert_eq!(app.diff_line_at_row(0, 4, line_count), None);
+}
@@ -0,0 +1,109 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use std::time::Instant;
+
+use crate::syntax::available_themes;
+
+use super::types::ThemePickerState;
+use super::App;
+
+impl App {
+    /// テーマ選択ポップアップを開く（現在のテーマを選択した状態）
+    pub(crate) fn open_theme_picker(&mut self) {
+        let themes = available_themes();
+        let original = self.config.diff.theme.clone();
+        let selected = themes.iter().position(|t| *t == original).unwrap_or(0);
+        self.theme_picker = Some(ThemePickerState {
+            themes,
+            selected,
+            original,
+        });
+    }
+
+    /// ポップアップ表示中のキー処理。表示中は全キーを消費して true を返す。
+    ///
+    /// 移動のたびに選択中のテーマで現在のファイルを描き直し、Enter で確定、Esc で元に戻す。
+    pub(crate) fn handle_theme_picker_input(&mut self, key: &KeyEvent) -> bool {
+        let Some(picker) = self.theme_picker.as_ref() else {
+            return false;
+        };
+        let kb = &self.config.keybindings;
+        let len = picker.themes.len();
+        let selected = if key.code == KeyCode::Down || self.matches_single_key(key, &kb.move_down) {
+            (picker.selected + 1).min(len.saturating_sub(1))
+        } else if key.code == KeyCode::Up || self.matches_single_key(key, &kb.move_up) {
+            picker.selected.saturating_sub(1)
+        } else if key.code == KeyCode::Enter {
+            self.confirm_theme();
+            return true;
+        } else if key.code == KeyCode::Esc || self.matches_single_key(key, &kb.quit) {
+            if let Some(picker) = self.theme_picker.take() {
+                self.preview_theme(&picker.original);
+            }
+            return true;
+        } else {
+            return true;
+        };
+
+        if let Some(picker) = self.theme_picker.as_mut() {
+            picker.selected = selected;
+            let theme = picker.themes[selected];
+            self.preview_theme(theme);
+        }
+        true
+    }
+
+    /// 選択中のテーマを確定し、他のハイライト済みキャッシュも作り直す（設定ファイルには書き込まない）
+    fn confirm_theme(&mut self) {
+        let Some(picker) = self.theme_picker.take() else {
+            return;
+        };
+        if self.config.diff.theme == picker.original {
+            return;
+        }
+        self.rebuild_theme_caches();
+        if tokio::runtime::Handle::try_current().is_ok() {
+            self.start_prefetch_all_files();
+        }
+        self.cmt.submission_result = Some((true, format!("Theme: {}", self.config.diff.theme)));
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// `theme` を現在のテーマにして、表示中のファイルだけをその場でハイライトし直す
+    ///
+    /// 他のファイルのキャッシュは古いテーマのものなので捨てる。
+    pub(crate) fn preview_theme(&mut self, theme: &str) {
+        if self.config.diff.theme == theme {
+            return;
+        }
+        self.config.diff.theme = theme.to_string();
+        self.diff_store.clear();
+        if !self.highlighting_enabled {
+            return;
+        }
+        let file_index = self.selected_file;
+        let Some(file) = self.files().get(file_index) else {
+            return;
+        };
+        let Some(patch) = file.patch.clone() else {
+            return;
+        };
+        let Some(filename) = self
+            .linguist
+            .highlight_filename(&file.filename)
+            .map(|name| name.into_owned())
+        else {
+            return;
+        };
+        let mut parser_pool = self.diff_store.parser_pool(self.local_mode);
+        let mut cache = crate::ui::diff_view::build_diff_cache(
+            &patch,
+            &filename,
+            theme,
+            &mut parser_pool,
+            self.markdown_rich,
+            self.config.diff.tab_width,
+        );
+        cache.file_index = file_index;
+        self.diff_store.set_current(file_index, cache);
+    }
+}
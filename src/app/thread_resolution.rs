@@ -0,0 +1,114 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::github::{self, ReviewThread};
+
+use super::App;
+
+impl App {
+    /// PR のレビュースレッド（resolved 状態）をバックグラウンドで取得する
+    pub(crate) fn load_review_thread_states(&mut self) {
+        if self.local_mode {
+            return;
+        }
+        let (tx, rx) = mpsc::channel(1);
+        let pr_number = self.pr_number();
+        self.cmt.review_thread_receiver = Some((pr_number, rx));
+
+        let repo = self.repo.clone();
+        let github = self.github.clone();
+        tokio::spawn(async move {
+            let result = github::fetch_review_threads(&*github, &repo, pr_number)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result).await;
+        });
+    }
+
+    /// レビュースレッド取得 / resolve 結果のポーリング
+    pub(crate) fn poll_review_thread_updates(&mut self) {
+        let Some((origin_pr, rx)) = self.cmt.review_thread_receiver.as_mut() else {
+            return;
+        };
+        let origin_pr = *origin_pr;
+
+        match rx.try_recv() {
+            Ok(result) => {
+                self.cmt.review_thread_receiver = None;
+                if self.pr_number != Some(origin_pr) {
+                    return;
+                }
+                match result {
+                    Ok(threads) => self.cmt.pr_review_threads = threads,
+                    Err(e) => {
+                        self.cmt.submission_result =
+                            Some((false, format!("Failed to update review threads: {}", e)));
+                        self.cmt.submission_result_time = Some(Instant::now());
+                    }
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.cmt.review_thread_receiver = None;
+            }
+        }
+    }
+
+    /// コメント ID が属する PR レビュースレッド（PR mode で取得済みの場合のみ）
+    pub fn review_thread_state(&self, comment_id: u64) -> Option<&ReviewThread> {
+        self.cmt
+            .pr_review_threads
+            .iter()
+            .find(|t| t.contains_comment(comment_id))
+    }
+
+    /// コメント一覧で選択中のスレッドの resolved 状態を切り替える
+    ///
+    /// mutation 後にスレッド一覧を取り直し、GitHub 側の状態で表示を更新する。
+    pub(crate) fn toggle_selected_thread_resolved(&mut self) {
+        let thread_idx = self.cmt.expanded_thread.unwrap_or(self.cmt.selected_thread);
+        let Some(root_id) = self
+            .cmt
+            .review_threads
+            .get(thread_idx)
+            .and_then(|t| self.cmt.review_comments.as_ref()?.get(t.root))
+            .map(|c| c.id)
+        else {
+            return;
+        };
+        let Some(thread) = self.review_thread_state(root_id) else {
+            self.cmt.submission_result =
+                Some((false, "No review thread for this comment".to_string()));
+            self.cmt.submission_result_time = Some(Instant::now());
+            return;
+        };
+
+        let resolved = !thread.is_resolved;
+        let thread_id = thread.id.clone();
+        self.cmt.submission_result = Some((
+            true,
+            if resolved {
+                "Resolving thread...".to_string()
+            } else {
+                "Reopening thread...".to_string()
+            },
+        ));
+        self.cmt.submission_result_time = Some(Instant::now());
+
+        let (tx, rx) = mpsc::channel(1);
+        let pr_number = self.pr_number();
+        self.cmt.review_thread_receiver = Some((pr_number, rx));
+
+        let repo = self.repo.clone();
+        let github = self.github.clone();
+        tokio::spawn(async move {
+            let result =
+                match github::set_review_thread_resolved(&*github, &thread_id, resolved).await {
+                    Ok(()) => github::fetch_review_threads(&*github, &repo, pr_number).await,
+                    Err(e) => Err(e),
+                };
+            let _ = tx.send(result.map_err(|e| e.to_string())).await;
+        });
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::mpsc;
 
 use crate::ai::orchestrator::RallyEvent;
@@ -52,6 +53,70 @@ pub struct SymbolPopupState {
     pub selected: usize,
 }
 
+/// PR 全体の変更量（`git diff --stat` の最終行相当）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffStat {
+    pub files: usize,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+/// Diff view layout (`toggle_diff_layout`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffLayout {
+    #[default]
+    Unified,
+    /// Old content in the left column, new content in the right column.
+    Split,
+}
+
+/// Horizontal scroll of the split layout columns (each side scrolls independently).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SplitDiffScroll {
+    pub old: usize,
+    pub new: usize,
+    /// `scroll_left` / `scroll_right` act on the new (right) column.
+    pub focus_new: bool,
+}
+
+/// State for the side-by-side hunk popup (`peek_split_hunk`).
+#[derive(Debug, Clone)]
+pub struct HunkPeekState {
+    pub filename: String,
+    pub hunk: crate::diff::SideBySideHunk,
+    /// First visible row.
+    pub scroll: usize,
+}
+
+/// State for the hunk explanation popup (`ai_explain_hunk`).
+#[derive(Debug, Clone)]
+pub struct HunkExplainState {
+    pub filename: String,
+    /// 対象 hunk の `@@` ヘッダー
+    pub header: String,
+    pub explanation: LoadState<String>,
+    /// First visible line.
+    pub scroll: usize,
+}
+
+/// State for the local-mode blame popup (`show_blame`).
+#[derive(Debug, Clone)]
+pub struct BlamePopupState {
+    pub filename: String,
+    /// カーソル行の行番号（削除行なら HEAD 側）
+    pub line: u32,
+    pub lines: LoadState<Vec<crate::blame::BlameLine>>,
+}
+
+/// `choose_theme` のテーマ選択ポップアップ。移動するたびに現在のファイルへ試し適用する。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemePickerState {
+    pub themes: Vec<&'static str>,
+    pub selected: usize,
+    /// 開いたときのテーマ（キャンセル時に戻す）
+    pub original: String,
+}
+
 /// Interned span: a 4-byte `Spur` reference + style, reducing allocations
 /// for repeated tokens.
 #[derive(Clone)]
@@ -69,6 +134,14 @@ pub struct CachedDiffLine {
     pub spans: SpanVec,
     /// Used to determine background color.
     pub line_type: LineType,
+    /// 展開済みタブのバイト範囲 (start, end)。スパン連結後の行テキスト基準。
+    ///
+    /// `show_whitespace` でタブ記号を描画するために使う。タブのない行では空。
+    pub tabs: Vec<(u32, u32)>,
+    /// 旧ファイルでの行番号（削除行・文脈行のみ）
+    pub old_line_number: Option<u32>,
+    /// 新ファイルでの行番号（追加行・文脈行のみ）
+    pub new_line_number: Option<u32>,
 }
 
 /// Diff rendering cache.
@@ -82,6 +155,16 @@ pub struct DiffCache {
     /// False for plain caches (diff coloring only).
     pub highlighted: bool,
     pub markdown_rich: bool,
+    /// 改行コードのみが変わった hunk（表示時に折りたたむ）
+    pub line_ending_hunks: Vec<crate::diff::LineEndingHunk>,
+    /// ファイルの改行コード（ヘッダに CRLF を表示する）
+    pub line_ending: crate::diff::LineEnding,
+    /// 変更行の間にある文脈行の連続（`collapse_unchanged` で折りたたむ候補）
+    pub context_runs: Vec<std::ops::Range<usize>>,
+    /// 衝突マーカー（`<<<<<<<` / `=======` / `>>>>>>>`）のある追加・文脈行
+    pub conflict_lines: Vec<usize>,
+    /// 同じファイル内で移動したコードブロック（削除側と追加側の組）
+    pub moved_blocks: Vec<crate::diff::MovedBlock>,
 }
 
 impl DiffCache {
@@ -121,6 +204,102 @@ impl MultilineSelection {
     }
 }
 
+/// Hunk filter state for the diff view: hides hunks whose changed lines do
+/// not contain the query. Line indices are never remapped, so comments and
+/// hunk headers keep the real file line numbers.
+#[derive(Debug, Clone)]
+pub struct HunkFilter {
+    pub query: String,
+    /// クエリ入力バー表示中か
+    pub input_active: bool,
+    /// false の間は全 hunk を表示する（クエリは保持）
+    pub enabled: bool,
+    /// フィルタ対象のファイル
+    pub file_index: usize,
+    pub hunks: Vec<crate::diff::HunkMatch>,
+}
+
+impl HunkFilter {
+    pub fn new(file_index: usize) -> Self {
+        Self {
+            query: String::new(),
+            input_active: true,
+            enabled: true,
+            file_index,
+            hunks: Vec::new(),
+        }
+    }
+
+    /// 非表示が実際に効いているか
+    pub fn is_hiding(&self) -> bool {
+        self.enabled && !self.query.is_empty()
+    }
+
+    pub fn matched_count(&self) -> usize {
+        self.hunks.iter().filter(|h| h.matched).count()
+    }
+
+    /// `line` を含む非表示 hunk
+    pub fn hidden_hunk_at(&self, line: usize) -> Option<&crate::diff::HunkMatch> {
+        if !self.is_hiding() {
+            return None;
+        }
+        self.hunks
+            .iter()
+            .find(|h| !h.matched && h.range.contains(&line))
+    }
+}
+
+/// diff 内検索（`/`）の状態
+#[derive(Debug, Clone)]
+pub struct DiffSearch {
+    pub query: String,
+    /// クエリ入力バー表示中か
+    pub input_active: bool,
+    /// 検索対象のファイル
+    pub file_index: usize,
+    /// 検索を開いた時点のカーソル行（入力中はここ以降の最初の一致へ移動する）
+    pub origin_line: usize,
+    /// 行順に並んだ一致箇所
+    pub matches: Vec<crate::diff::SearchMatch>,
+}
+
+impl DiffSearch {
+    pub fn new(file_index: usize, origin_line: usize) -> Self {
+        Self {
+            query: String::new(),
+            input_active: true,
+            file_index,
+            origin_line,
+            matches: Vec::new(),
+        }
+    }
+
+    /// 一致のある行数（重複なし）
+    pub fn matched_line_count(&self) -> usize {
+        let mut lines: Vec<usize> = self.matches.iter().map(|m| m.line).collect();
+        lines.dedup();
+        lines.len()
+    }
+}
+
+/// シンボルダイジェストの 1 行（ファイル見出し、または変更シンボル）
+#[derive(Debug, Clone)]
+pub struct SymbolDigestRow {
+    pub file_index: usize,
+    /// None ならファイル見出し行
+    pub symbol: Option<crate::symbol::ChangedSymbol>,
+}
+
+/// ファイルごとの変更シンボル一覧ビューの状態
+#[derive(Debug, Clone)]
+pub struct SymbolDigestState {
+    pub rows: Vec<SymbolDigestRow>,
+    pub selected: usize,
+    pub scroll_offset: usize,
+    pub return_state: AppState,
+}
+
 /// Line-based input context shared by comment and suggestion modes.
 #[derive(Debug, Clone)]
 pub struct LineInputContext {
@@ -130,6 +309,17 @@ pub struct LineInputContext {
     pub diff_position: u32,
     /// Start line number in the new file (for multiline selections).
     pub start_line_number: Option<u32>,
+    /// Side the comment attaches to; `line_number` is an old-file line for `Left`.
+    pub side: crate::diff::CommentSide,
+}
+
+/// Resolved comment anchor for the composer preview, keyed by file, position and patch.
+#[derive(Debug, Clone)]
+pub struct CommentAnchorCache {
+    pub file_index: usize,
+    pub diff_position: u32,
+    pub patch_hash: u64,
+    pub anchor: Option<crate::diff::CommentAnchor>,
 }
 
 /// Unified input mode.
@@ -148,6 +338,10 @@ pub enum InputMode {
     IssueComment {
         issue_number: u32,
     },
+    /// PR 一覧の作成者・担当者・ラベル絞り込み（`author:x label:y` 形式）
+    PrListFilters,
+    /// PR へのレビュアー依頼・担当者追加（カンマ/空白区切りのログイン名）
+    PrPeople(crate::github::PrPeopleRole),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -169,6 +363,7 @@ pub enum AppState {
     GitOpsSplitTree,
     GitOpsSplitDiff,
     Cockpit,
+    SymbolDigest,
 }
 
 impl AppState {
@@ -307,9 +502,70 @@ pub struct AiRallyState {
     pub pending_config_warning: Option<Vec<(String, String)>>,
     /// Current pause state
     pub pause_state: PauseState,
+    /// エージェント呼び出し（レビュー / 修正 / 提案）を開始した時刻（待機中は None）
+    pub agent_started_at: Option<Instant>,
+    /// ステータス表示のスピナーのフレーム（tick ごとに進める）
+    pub spinner_frame: usize,
+    /// dry run で投稿を保留しているインラインコメント（`commit_rally` で投稿）
+    pub pending_comments: Vec<crate::ai::PendingComment>,
+    /// 保留コメント一覧の選択位置
+    pub selected_pending_comment: usize,
+    /// 移動キーがログではなく保留コメント一覧に効くか（`tab_switch` で切り替え）
+    pub pending_comments_focused: bool,
+    /// エージェント呼び出しごとの入力（`retry_iteration` で同じ入力のまま再実行する）
+    pub step_inputs: Vec<crate::ai::StepInput>,
 }
 
 impl AiRallyState {
+    /// エージェントが応答待ちの状態か（経過時間とスピナーを表示する）
+    pub fn is_agent_running(&self) -> bool {
+        matches!(
+            self.state,
+            RallyState::ReviewerReviewing | RallyState::RevieweeFix | RallyState::RevieweeProposing
+        )
+    }
+
+    /// 状態を更新し、エージェント呼び出しが始まったら開始時刻を記録する
+    pub fn set_state(&mut self, state: RallyState) {
+        if self.state != state || self.agent_started_at.is_none() {
+            self.state = state;
+            self.agent_started_at = self.is_agent_running().then(Instant::now);
+        }
+    }
+
+    /// 描画ループの tick。エージェント実行中（一時停止中を除く）だけスピナーを進める。
+    pub fn tick(&mut self) {
+        if self.is_agent_running() && self.pause_state != PauseState::Paused {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        }
+    }
+
+    /// 再実行したステップの結果で、`iteration` の同じ種類の結果を置き換える。
+    /// 他のイテレーションの履歴には触れない。元の結果がない（失敗した）場合は
+    /// そのイテレーションの末尾に追加する。
+    pub fn replace_step_output(&mut self, iteration: u32, event: RallyEvent) {
+        let Some(start) = self
+            .history
+            .iter()
+            .position(|e| matches!(e, RallyEvent::IterationStarted(i) if *i == iteration))
+        else {
+            self.history.push(event);
+            return;
+        };
+        let end = self.history[start + 1..]
+            .iter()
+            .position(|e| matches!(e, RallyEvent::IterationStarted(_)))
+            .map_or(self.history.len(), |offset| start + 1 + offset);
+        let kind = std::mem::discriminant(&event);
+        match self.history[start..end]
+            .iter()
+            .rposition(|e| std::mem::discriminant(e) == kind)
+        {
+            Some(offset) => self.history[start + offset] = event,
+            None => self.history.insert(end, event),
+        }
+    }
+
     /// Push a new log entry, auto-following to the bottom if the selection is at the tail.
     /// This keeps auto-scroll active when the user is watching the latest logs.
     pub fn push_log(&mut self, entry: LogEntry) {
@@ -342,6 +598,91 @@ pub enum ReviewAction {
     Comment,
 }
 
+/// `review_submit` ピッカーに並べる判定（表示順）
+pub const REVIEW_PICKER_ACTIONS: [ReviewAction; 3] = [
+    ReviewAction::Approve,
+    ReviewAction::RequestChanges,
+    ReviewAction::Comment,
+];
+
+/// レビュー判定ピッカー。選択を確定するまでは何も送信しない。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewPickerState {
+    /// [`REVIEW_PICKER_ACTIONS`] のインデックス
+    pub selected: usize,
+    /// レビューと一緒に送信される保留中のインラインコメント数
+    pub pending_comments: usize,
+    /// そのうち suggestion を含むもの
+    pub pending_suggestions: usize,
+    /// suggestion が残ったままの Approve を確認待ち
+    pub confirming_approve: bool,
+}
+
+/// レビューコメントに付けるリアクションを選ぶピッカー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReactionPickerState {
+    /// リアクション対象のレビューコメント ID
+    pub comment_id: u64,
+    /// [`crate::github::ReactionKind::ALL`] のインデックス
+    pub selected: usize,
+}
+
+/// リアクション追加 / 削除の送信結果
+pub(crate) struct ReactionUpdate {
+    pub comment_id: u64,
+    pub kind: crate::github::ReactionKind,
+    /// 取り消したリアクションの ID（追加なら None）
+    pub removed: Option<u64>,
+    /// 追加なら作成されたリアクションの ID、削除なら None
+    pub result: Result<Option<u64>, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReviewPickerOutcome {
+    /// ピッカーを開いたまま（移動・確認待ち）
+    Pending,
+    Submit(ReviewAction),
+}
+
+impl ReviewPickerState {
+    /// 誤って Approve しないよう Comment を初期選択にする
+    pub fn new(pending_comments: usize, pending_suggestions: usize) -> Self {
+        Self {
+            selected: REVIEW_PICKER_ACTIONS.len() - 1,
+            pending_comments,
+            pending_suggestions,
+            confirming_approve: false,
+        }
+    }
+
+    pub fn selected_action(&self) -> ReviewAction {
+        REVIEW_PICKER_ACTIONS[self.selected]
+    }
+
+    pub fn move_down(&mut self) {
+        self.selected = (self.selected + 1).min(REVIEW_PICKER_ACTIONS.len() - 1);
+        self.confirming_approve = false;
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        self.confirming_approve = false;
+    }
+
+    /// 選択を確定する。未送信の suggestion がある Approve は 2 回目の確定で送信する。
+    pub fn choose(&mut self) -> ReviewPickerOutcome {
+        let action = self.selected_action();
+        if action == ReviewAction::Approve
+            && self.pending_suggestions > 0
+            && !self.confirming_approve
+        {
+            self.confirming_approve = true;
+            return ReviewPickerOutcome::Pending;
+        }
+        ReviewPickerOutcome::Submit(action)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum PendingApproveChoice {
     Ignore,
@@ -444,6 +785,12 @@ impl CockpitState {
     }
 }
 
+/// Revision the diff is taken from (`None` = whole PR / HEAD), shared with the retry loop.
+pub type SharedDiffBase = Arc<std::sync::Mutex<Option<String>>>;
+
+/// PR number the auto-refresh timer should refresh (`0` = none), shared with the timer task.
+pub type AutoRefreshTarget = Arc<std::sync::atomic::AtomicU32>;
+
 /// Retry request variants dispatched through the unified retry loop.
 #[derive(Debug, Clone)]
 pub enum RefreshRequest {
@@ -482,6 +829,12 @@ pub enum DataState {
         pr: Box<PullRequest>,
         files: Vec<ChangedFile>,
     },
+    /// 巨大 PR で `gh pr diff` をストリーミング中。描画は Loaded と同じで、
+    /// patch はファイル単位で届き次第埋まる（完了時に Loaded へ置き換わる）
+    PartiallyLoaded {
+        pr: Box<PullRequest>,
+        files: Vec<ChangedFile>,
+    },
     Error(String),
 }
 
@@ -1050,6 +1403,12 @@ pub struct CommentState {
     /// the on-disk [`crate::cache::LocalReviewComment`] records when in local
     /// mode; empty otherwise.
     pub local_comment_meta: std::collections::HashMap<u64, crate::cache::LocalCommentMeta>,
+    /// pending レビューモード: インラインコメントを即時送信せず pending として保存する
+    pub pending_review: bool,
+    /// 現在の PR の未送信コメント（キャッシュに永続化）
+    pub pending_comments: Vec<crate::cache::PendingReviewComment>,
+    /// `review_comments` 中の pending コメントの ID
+    pub pending_comment_ids: std::collections::HashSet<u64>,
     pub review_threads: Vec<CommentThread>,
     pub selected_thread: usize,
     pub expanded_thread: Option<usize>,
@@ -1062,6 +1421,8 @@ pub struct CommentState {
     pub comments_loading: bool,
     pub file_comment_positions: Vec<CommentPosition>,
     pub file_comment_lines: std::collections::HashSet<usize>,
+    /// `file_comment_lines` のうち、スレッドがすべて折りたたまれている行（diff 上で `▸` 表示）
+    pub file_collapsed_comment_lines: std::collections::HashSet<usize>,
     pub file_comment_counts: std::collections::HashMap<String, usize>,
     pub comment_panel_open: bool,
     pub comment_panel_scroll: u16,
@@ -1078,10 +1439,24 @@ pub struct CommentState {
         super::PrReceiver<Result<Vec<crate::github::comment::DiscussionComment>, String>>,
     pub(crate) comment_submit_receiver: super::PrReceiver<crate::loader::CommentSubmitResult>,
     pub comment_submitting: bool,
+    /// 送信中のインラインコメント（完了まで pending とは別の控えに保存しておく）
+    pub(crate) in_flight_comment: Option<crate::cache::PendingReviewComment>,
     pub submission_result: Option<(bool, String)>,
     pub(crate) submission_result_time: Option<std::time::Instant>,
     pub(crate) pending_approve_body: Option<String>,
     pub selected_inline_comment: usize,
+    /// コメントパネルのスレッドをデフォルトで折りたたむか（collapse/expand all で切り替え）
+    pub threads_collapsed: bool,
+    /// デフォルトと逆の状態にしたスレッドのルートコメント ID
+    pub thread_fold_overrides: std::collections::HashSet<u64>,
+    /// PR mode のレビュースレッド（resolved 状態）。GraphQL から取得する
+    pub pr_review_threads: Vec<crate::github::ReviewThread>,
+    pub(crate) review_thread_receiver:
+        super::PrReceiver<Result<Vec<crate::github::ReviewThread>, String>>,
+    /// このセッションで付けたリアクション（(コメント ID, 種類) → リアクション ID）。
+    /// 同じ種類を再度選んだときに取り消すために使う。
+    pub(crate) own_reactions: std::collections::HashMap<(u64, crate::github::ReactionKind), u64>,
+    pub(crate) reaction_receiver: super::PrReceiver<ReactionUpdate>,
 }
 
 impl CommentState {
@@ -1102,7 +1477,11 @@ pub struct PrListState {
     pub selected_pr: usize,
     pub pr_list_scroll_offset: usize,
     pub pr_list_has_more: bool,
+    /// 取得済みの PR 数（次のページの offset。重複を除く前の件数）
+    pub pr_list_offset: u32,
     pub pr_list_state_filter: crate::github::PrStateFilter,
+    /// 作成者・担当者・ラベルの絞り込み（セッション中は保持する）
+    pub pr_list_filters: crate::github::PrListFilters,
     pub pr_list_filter: Option<crate::filter::ListFilter>,
     pub(crate) pr_list_receiver:
         Option<tokio::sync::mpsc::Receiver<Result<crate::github::PrListPage, String>>>,
@@ -1115,9 +1494,10 @@ pub struct ChecksState {
     pub checks_loading: bool,
     pub checks_target_pr: Option<u32>,
     pub checks_return_state: AppState,
-    pub ci_status: Option<crate::github::CiStatus>,
+    /// ヘッダに表示するチェックの状態別件数
+    pub check_counts: Option<crate::github::CheckCounts>,
     pub(crate) checks_receiver: super::PrReceiver<Result<Vec<crate::github::CheckItem>, String>>,
-    pub(crate) ci_status_receiver: Option<tokio::sync::mpsc::Receiver<crate::github::CiStatus>>,
+    pub(crate) ci_status_receiver: Option<tokio::sync::mpsc::Receiver<crate::github::CheckCounts>>,
 }
 
 impl Default for ChecksState {
@@ -1129,7 +1509,7 @@ impl Default for ChecksState {
             checks_loading: false,
             checks_target_pr: None,
             checks_return_state: AppState::FileList,
-            ci_status: None,
+            check_counts: None,
             checks_receiver: None,
             ci_status_receiver: None,
         }
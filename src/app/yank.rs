@@ -0,0 +1,91 @@
+use std::time::Instant;
+
+use crate::diff::{hunk_lines_at, LineType, PatchIndex};
+use crate::github;
+
+use super::App;
+
+impl App {
+    /// コピー対象のテキスト。行はdiffの接頭辞（`+`/`-`/` `）を除いた内容、
+    /// hunk は `@@` ヘッダーを含む patch の該当部分をそのまま返す。
+    pub(crate) fn yank_text(&self, whole_hunk: bool) -> Option<String> {
+        let patch = self.files().get(self.selected_file)?.patch.as_deref()?;
+        let index = self.diff_scroll.selected_line;
+        if whole_hunk {
+            return hunk_lines_at(patch, index).map(|lines| lines.join("\n"));
+        }
+        // 結合 diff の行も接頭辞を正しく外せるよう、hunk を追って分類する
+        Some(PatchIndex::build(patch).get(index)?.content.to_string())
+    }
+
+    /// カーソル行（`whole_hunk` なら hunk 全体）をクリップボードにコピーする
+    pub(crate) fn yank(&mut self, whole_hunk: bool) {
+        let result = match self.yank_text(whole_hunk) {
+            Some(text) => match crate::clipboard::copy(&text) {
+                Ok(()) if whole_hunk => (
+                    true,
+                    format!("Copied hunk ({} lines)", text.lines().count()),
+                ),
+                Ok(()) => (true, "Copied line".to_string()),
+                Err(e) => (false, format!("Copy failed: {}", e)),
+            },
+            None if whole_hunk => (false, "Cursor is not inside a hunk".to_string()),
+            None => (false, "No line to copy".to_string()),
+        };
+        self.cmt.submission_result = Some(result);
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+
+    /// カーソル行（複数行選択中は選択範囲）の head コミット上の permalink。
+    /// 範囲の両端は新しい側に存在する行（追加行・文脈行）に詰める。
+    pub(crate) fn permalink(&self) -> Option<String> {
+        let pr = self.pr()?;
+        let file = self.files().get(self.selected_file)?;
+        if file.status == "removed" {
+            return None;
+        }
+        let patch = file.patch.as_deref()?;
+        let (start, end) = match self.multiline_selection {
+            Some(ref sel) => (sel.start(), sel.end()),
+            None => (
+                self.diff_scroll.selected_line,
+                self.diff_scroll.selected_line,
+            ),
+        };
+        let index = PatchIndex::build(patch);
+        let mut lines = (start..=end)
+            .filter_map(|i| index.get(i))
+            .filter(|info| matches!(info.line_type, LineType::Added | LineType::Context))
+            .filter_map(|info| info.new_line_number);
+        let first = lines.next()?;
+        let last = lines.next_back().unwrap_or(first);
+        Some(github::permalink_url(
+            &github::github_host(),
+            &self.repo,
+            &pr.head.sha,
+            &file.filename,
+            first,
+            last,
+        ))
+    }
+
+    /// permalink をクリップボードにコピーする（local mode では GitHub 上の commit がないため無効）
+    pub(crate) fn yank_permalink(&mut self) {
+        let result = if self.local_mode {
+            (
+                false,
+                "Permalinks are unavailable in local mode".to_string(),
+            )
+        } else {
+            match self.permalink() {
+                Some(url) => match crate::clipboard::copy(&url) {
+                    Ok(()) => (true, format!("Copied {}", url)),
+                    Err(e) => (false, format!("Copy failed: {}", e)),
+                },
+                None => (false, "No line on the head side to link".to_string()),
+            }
+        };
+        self.cmt.submission_result = Some(result);
+        self.cmt.submission_result_time = Some(Instant::now());
+    }
+}
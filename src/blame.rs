@@ -0,0 +1,221 @@
+//! `git blame` for the line under the cursor in local mode.
+//!
+//! Runs `git blame --porcelain -L` around the cursor line and parses the
+//! porcelain output into [`BlameLine`]s for the blame popup.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use tokio::process::Command;
+
+/// カーソル行の前後に表示する行数
+pub const BLAME_CONTEXT_LINES: u32 = 5;
+
+/// 未コミット行の commit（porcelain では 0 埋めの SHA になる）
+const UNCOMMITTED_SHA: &str = "0000000000000000000000000000000000000000";
+
+/// `git blame --porcelain` の 1 行分
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    /// blame 対象ファイルでの行番号（1 始まり）
+    pub line_number: u32,
+    pub commit: String,
+    pub author: String,
+    /// author-time（UNIX 秒）
+    pub author_time: i64,
+    pub summary: String,
+    pub content: String,
+}
+
+impl BlameLine {
+    pub fn is_uncommitted(&self) -> bool {
+        self.commit == UNCOMMITTED_SHA
+    }
+
+    /// 表示用の短縮 SHA
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(8)]
+    }
+
+    /// author-time を `YYYY-MM-DD` で返す
+    pub fn date(&self) -> String {
+        chrono::DateTime::from_timestamp(self.author_time, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct CommitInfo {
+    author: String,
+    author_time: i64,
+    summary: String,
+}
+
+/// `git blame --porcelain` の出力をパースする
+///
+/// porcelain 形式では commit のヘッダー（author など）はその commit が初出の
+/// 行にだけ付くため、commit ごとに覚えておいて後続の行に使い回す。
+pub fn parse_blame_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut commits: HashMap<String, CommitInfo> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut current: Option<(String, u32)> = None;
+
+    for raw in output.lines() {
+        if let Some(content) = raw.strip_prefix('\t') {
+            let Some((commit, line_number)) = current.take() else {
+                continue;
+            };
+            let info = commits.get(&commit).cloned().unwrap_or_default();
+            lines.push(BlameLine {
+                line_number,
+                commit,
+                author: info.author,
+                author_time: info.author_time,
+                summary: info.summary,
+                content: content.to_string(),
+            });
+            continue;
+        }
+
+        let Some((commit, _)) = current.as_ref() else {
+            // "<sha> <orig_line> <final_line> [<num_lines>]"
+            let mut fields = raw.split(' ');
+            let sha = fields.next().unwrap_or_default();
+            let final_line = fields.nth(1).and_then(|n| n.parse().ok());
+            if let Some(final_line) = final_line.filter(|_| sha.len() == 40) {
+                commits.entry(sha.to_string()).or_default();
+                current = Some((sha.to_string(), final_line));
+            }
+            continue;
+        };
+
+        let info = commits.entry(commit.clone()).or_default();
+        if let Some(author) = raw.strip_prefix("author ") {
+            info.author = author.to_string();
+        } else if let Some(time) = raw.strip_prefix("author-time ") {
+            info.author_time = time.parse().unwrap_or(0);
+        } else if let Some(summary) = raw.strip_prefix("summary ") {
+            info.summary = summary.to_string();
+        }
+    }
+    lines
+}
+
+/// `line` の前後 [`BLAME_CONTEXT_LINES`] 行を blame する
+///
+/// `revision` が None なら作業ツリー、`Some("HEAD")` などなら その版のファイルを対象にする
+/// （削除行は HEAD 側の行番号で blame する）。`filename` はリポジトリルートからのパス。
+pub async fn fetch_blame(
+    working_dir: Option<&str>,
+    filename: &str,
+    revision: Option<&str>,
+    line: u32,
+) -> Result<Vec<BlameLine>> {
+    let root = repo_root(working_dir).await?;
+    let start = line.saturating_sub(BLAME_CONTEXT_LINES).max(1);
+    let range = format!("{},{}", start, line + BLAME_CONTEXT_LINES);
+
+    let mut command = Command::new("git");
+    command.args([
+        "-c",
+        "core.quotePath=false",
+        "blame",
+        "--porcelain",
+        "-L",
+        &range,
+    ]);
+    if let Some(revision) = revision {
+        command.arg(revision);
+    }
+    command.args(["--", filename]).current_dir(&root);
+    // キャンセル（タスクの abort）時に git も終了させる
+    command.kill_on_drop(true);
+
+    let output = command
+        .output()
+        .await
+        .context("failed to spawn git blame")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git blame failed: {}", stderr.trim());
+    }
+    Ok(parse_blame_porcelain(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+async fn repo_root(working_dir: Option<&str>) -> Result<String> {
+    let mut command = Command::new("git");
+    command.args(["rev-parse", "--show-toplevel"]);
+    if let Some(dir) = working_dir {
+        command.current_dir(dir);
+    }
+    let output = command
+        .output()
+        .await
+        .context("failed to spawn git rev-parse")?;
+    if !output.status.success() {
+        anyhow::bail!("Not a git repository");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let output = "\
+3f2a9c1d4e5b6a7980112233445566778899aabb 10 12 2
+author Alice
+author-mail <alice@example.com>
+author-time 1700000000
+author-tz +0900
+committer Alice
+committer-mail <alice@example.com>
+committer-time 1700000000
+committer-tz +0900
+summary Add parser
+previous 1111111111111111111111111111111111111111 src/lib.rs
+filename src/lib.rs
+\tfn parse() {
+3f2a9c1d4e5b6a7980112233445566778899aabb 11 13
+\t    todo!()
+0000000000000000000000000000000000000000 14 14 1
+author Not Committed Yet
+author-mail <not.committed.yet>
+author-time 1710000000
+author-tz +0000
+committer Not Committed Yet
+committer-mail <not.committed.yet>
+committer-time 1710000000
+committer-tz +0000
+summary Version of src/lib.rs from src/lib.rs
+filename src/lib.rs
+\t}
+";
+        let lines = parse_blame_porcelain(output);
+        assert_eq!(lines.len(), 3);
+
+        assert_eq!(lines[0].line_number, 12);
+        assert_eq!(lines[0].author, "Alice");
+        assert_eq!(lines[0].summary, "Add parser");
+        assert_eq!(lines[0].content, "fn parse() {");
+        assert_eq!(lines[0].short_commit(), "3f2a9c1d");
+        assert_eq!(lines[0].date(), "2023-11-14");
+        assert!(!lines[0].is_uncommitted());
+
+        // 2 行目以降の同じ commit はヘッダーなしでも author を引き継ぐ
+        assert_eq!(lines[1].line_number, 13);
+        assert_eq!(lines[1].author, "Alice");
+        assert_eq!(lines[1].content, "    todo!()");
+
+        assert_eq!(lines[2].line_number, 14);
+        assert!(lines[2].is_uncommitted());
+        assert_eq!(lines[2].author, "Not Committed Yet");
+
+        assert!(parse_blame_porcelain("").is_empty());
+    }
+}
@@ -248,6 +248,543 @@ fn save_local_review_comments_with_base(
     Ok(())
 }
 
+const PENDING_REVIEW_COMMENTS_VERSION: u32 = 1;
+
+/// レビュー送信前（pending）のインラインコメント。
+///
+/// PR 単位でキャッシュに保存し、終了・再起動をまたいで保持する。
+/// レビュー送信時にまとめて GitHub へ送られ、ローカルの控えは削除される。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingReviewComment {
+    pub path: String,
+    pub line: u32,
+    pub diff_position: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<u32>,
+    /// 削除行へのコメントは `Left`（`line` は旧ファイルの行番号）
+    #[serde(default)]
+    pub side: crate::diff::CommentSide,
+    pub body: String,
+    pub created_at: String,
+    /// コメントした行の内容（force-push で patch が変わった際の付け直しに使う）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingReviewCommentsFile {
+    version: u32,
+    comments: Vec<PendingReviewComment>,
+}
+
+fn pending_review_comments_path_with_base(
+    repo: &str,
+    pr_number: u32,
+    base: &std::path::Path,
+) -> Result<PathBuf> {
+    let repo = sanitize_repo_name(repo)?;
+    Ok(base
+        .join("pending-comments")
+        .join(format!("{}-{}.json", repo, pr_number)))
+}
+
+/// 保存済みの pending コメントを読み込む。ファイルがない場合は空。
+pub fn load_pending_review_comments(
+    repo: &str,
+    pr_number: u32,
+) -> Result<Vec<PendingReviewComment>> {
+    load_pending_review_comments_with_base(repo, pr_number, &cache_dir())
+}
+
+fn load_pending_review_comments_with_base(
+    repo: &str,
+    pr_number: u32,
+    base: &std::path::Path,
+) -> Result<Vec<PendingReviewComment>> {
+    read_pending_comments_file(&pending_review_comments_path_with_base(
+        repo, pr_number, base,
+    )?)
+}
+
+fn read_pending_comments_file(path: &std::path::Path) -> Result<Vec<PendingReviewComment>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    let file: PendingReviewCommentsFile = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+    if file.version != PENDING_REVIEW_COMMENTS_VERSION {
+        return Err(anyhow::anyhow!(
+            "Unsupported pending comments version: {}",
+            file.version
+        ));
+    }
+    Ok(file.comments)
+}
+
+/// pending コメントを保存する。空ならファイルを削除する。
+pub fn save_pending_review_comments(
+    repo: &str,
+    pr_number: u32,
+    comments: &[PendingReviewComment],
+) -> Result<()> {
+    save_pending_review_comments_with_base(repo, pr_number, comments, &cache_dir())
+}
+
+fn save_pending_review_comments_with_base(
+    repo: &str,
+    pr_number: u32,
+    comments: &[PendingReviewComment],
+    base: &std::path::Path,
+) -> Result<()> {
+    write_pending_comments_file(
+        &pending_review_comments_path_with_base(repo, pr_number, base)?,
+        comments,
+    )
+}
+
+fn write_pending_comments_file(
+    path: &std::path::Path,
+    comments: &[PendingReviewComment],
+) -> Result<()> {
+    if comments.is_empty() {
+        if path.exists() {
+            fs::remove_file(path)
+                .map_err(|e| anyhow::anyhow!("Failed to remove {}: {}", path.display(), e))?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let payload = PendingReviewCommentsFile {
+        version: PENDING_REVIEW_COMMENTS_VERSION,
+        comments: comments.to_vec(),
+    };
+    let json = serde_json::to_string_pretty(&payload)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize pending comments: {}", e))?;
+    fs::write(path, json).map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))
+}
+
+/// 即時送信中のインラインコメントの控え（pending コメントとは別ファイル）
+fn in_flight_comment_path_with_base(
+    repo: &str,
+    pr_number: u32,
+    base: &std::path::Path,
+) -> Result<PathBuf> {
+    let repo = sanitize_repo_name(repo)?;
+    Ok(base
+        .join("pending-comments")
+        .join(format!("{}-{}.in-flight.json", repo, pr_number)))
+}
+
+/// 送信中のまま残ったコメントを読み込む（送信中に終了した場合のみ存在する）
+pub fn load_in_flight_comment(repo: &str, pr_number: u32) -> Result<Option<PendingReviewComment>> {
+    load_in_flight_comment_with_base(repo, pr_number, &cache_dir())
+}
+
+fn load_in_flight_comment_with_base(
+    repo: &str,
+    pr_number: u32,
+    base: &std::path::Path,
+) -> Result<Option<PendingReviewComment>> {
+    let path = in_flight_comment_path_with_base(repo, pr_number, base)?;
+    Ok(read_pending_comments_file(&path)?.into_iter().next())
+}
+
+/// 送信中のコメントを保存する。`None` なら削除する。
+pub fn save_in_flight_comment(
+    repo: &str,
+    pr_number: u32,
+    comment: Option<&PendingReviewComment>,
+) -> Result<()> {
+    save_in_flight_comment_with_base(repo, pr_number, comment, &cache_dir())
+}
+
+fn save_in_flight_comment_with_base(
+    repo: &str,
+    pr_number: u32,
+    comment: Option<&PendingReviewComment>,
+    base: &std::path::Path,
+) -> Result<()> {
+    let path = in_flight_comment_path_with_base(repo, pr_number, base)?;
+    write_pending_comments_file(&path, comment.map(std::slice::from_ref).unwrap_or_default())
+}
+
+const LOCAL_VIEWED_FILES_VERSION: u32 = 1;
+
+/// local mode で viewed にしたファイル。値は viewed にした時点の patch ハッシュで、
+/// patch が変わったファイルは読み込み側で viewed を外す。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LocalViewedFilesFile {
+    version: u32,
+    files: HashMap<String, u64>,
+}
+
+fn local_viewed_files_path_with_base(
+    repo: &str,
+    working_dir: Option<&str>,
+    base: &std::path::Path,
+) -> Result<PathBuf> {
+    let repo = sanitize_repo_name(repo)?;
+    let workdir = effective_working_dir(working_dir)?;
+    let workdir_hash = hash_path_for_filename(&workdir);
+    Ok(base
+        .join("local-viewed")
+        .join(format!("{}-{:016x}.json", repo, workdir_hash)))
+}
+
+/// local mode の viewed 状態（filename -> patch ハッシュ）を読み込む。ファイルがない場合は空。
+pub fn load_local_viewed_files(
+    repo: &str,
+    working_dir: Option<&str>,
+) -> Result<HashMap<String, u64>> {
+    load_local_viewed_files_with_base(repo, working_dir, &cache_dir())
+}
+
+fn load_local_viewed_files_with_base(
+    repo: &str,
+    working_dir: Option<&str>,
+    base: &std::path::Path,
+) -> Result<HashMap<String, u64>> {
+    let path = local_viewed_files_path_with_base(repo, working_dir, base)?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    let file: LocalViewedFilesFile = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+    if file.version != LOCAL_VIEWED_FILES_VERSION {
+        return Err(anyhow::anyhow!(
+            "Unsupported local viewed-files version: {}",
+            file.version
+        ));
+    }
+    Ok(file.files)
+}
+
+/// local mode の viewed 状態を保存する。空ならファイルを削除する。
+pub fn save_local_viewed_files(
+    repo: &str,
+    working_dir: Option<&str>,
+    files: &HashMap<String, u64>,
+) -> Result<()> {
+    save_local_viewed_files_with_base(repo, working_dir, files, &cache_dir())
+}
+
+fn save_local_viewed_files_with_base(
+    repo: &str,
+    working_dir: Option<&str>,
+    files: &HashMap<String, u64>,
+    base: &std::path::Path,
+) -> Result<()> {
+    let path = local_viewed_files_path_with_base(repo, working_dir, base)?;
+
+    if files.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| anyhow::anyhow!("Failed to remove {}: {}", path.display(), e))?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let payload = LocalViewedFilesFile {
+        version: LOCAL_VIEWED_FILES_VERSION,
+        files: files.clone(),
+    };
+    let json = serde_json::to_string_pretty(&payload)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize viewed files: {}", e))?;
+    fs::write(&path, json).map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))
+}
+
+const PR_DATA_SNAPSHOT_VERSION: u32 = 1;
+
+/// オフライン閲覧（`--offline`）用に保存する PR データ。
+///
+/// GitHub から取得に成功するたびに上書きされる。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrDataSnapshot {
+    pub version: u32,
+    pub pr: PullRequest,
+    pub files: Vec<ChangedFile>,
+    /// レビューコメント（コメントの取得に成功するたびに差し替える）
+    ///
+    /// PR データと取得タイミングが異なるため別ファイルに保存し、読み込み時に合わせる。
+    #[serde(default, skip_serializing)]
+    pub comments: Vec<ReviewComment>,
+}
+
+fn pr_data_snapshot_path_with_base(
+    repo: &str,
+    pr_number: u32,
+    base: &std::path::Path,
+) -> Result<PathBuf> {
+    let repo = sanitize_repo_name(repo)?;
+    Ok(base
+        .join("pr-data")
+        .join(format!("{}-{}.json", repo, pr_number)))
+}
+
+fn pr_comments_snapshot_path_with_base(
+    repo: &str,
+    pr_number: u32,
+    base: &std::path::Path,
+) -> Result<PathBuf> {
+    let repo = sanitize_repo_name(repo)?;
+    Ok(base
+        .join("pr-data")
+        .join(format!("{}-{}.comments.json", repo, pr_number)))
+}
+
+/// 保存済みの PR データを読み込む。ファイルがない場合は None。
+pub fn load_pr_data_snapshot(repo: &str, pr_number: u32) -> Result<Option<PrDataSnapshot>> {
+    load_pr_data_snapshot_with_base(repo, pr_number, &cache_dir())
+}
+
+pub(crate) fn load_pr_data_snapshot_with_base(
+    repo: &str,
+    pr_number: u32,
+    base: &std::path::Path,
+) -> Result<Option<PrDataSnapshot>> {
+    let path = pr_data_snapshot_path_with_base(repo, pr_number, base)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    let mut snapshot: PrDataSnapshot = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+    if snapshot.version != PR_DATA_SNAPSHOT_VERSION {
+        return Ok(None);
+    }
+    let comments_path = pr_comments_snapshot_path_with_base(repo, pr_number, base)?;
+    if comments_path.exists() {
+        let content = fs::read_to_string(&comments_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", comments_path.display(), e))?;
+        snapshot.comments = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", comments_path.display(), e))?;
+    }
+    Ok(Some(snapshot))
+}
+
+/// 取得した PR データをオフライン閲覧用に保存する。
+pub fn save_pr_data_snapshot(
+    repo: &str,
+    pr_number: u32,
+    pr: &PullRequest,
+    files: &[ChangedFile],
+) -> Result<()> {
+    save_pr_data_snapshot_with_base(repo, pr_number, pr, files, &cache_dir())
+}
+
+pub(crate) fn save_pr_data_snapshot_with_base(
+    repo: &str,
+    pr_number: u32,
+    pr: &PullRequest,
+    files: &[ChangedFile],
+    base: &std::path::Path,
+) -> Result<()> {
+    let payload = PrDataSnapshot {
+        version: PR_DATA_SNAPSHOT_VERSION,
+        pr: pr.clone(),
+        files: files.to_vec(),
+        comments: Vec::new(),
+    };
+    let path = pr_data_snapshot_path_with_base(repo, pr_number, base)?;
+    let json = serde_json::to_string(&payload)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize PR data: {}", e))?;
+    write_snapshot_file(&path, &json)
+}
+
+/// 取得したレビューコメントを保存済みの PR データに加える。PR データが未保存なら何もしない。
+pub fn save_pr_comments_snapshot(
+    repo: &str,
+    pr_number: u32,
+    comments: &[ReviewComment],
+) -> Result<()> {
+    save_pr_comments_snapshot_with_base(repo, pr_number, comments, &cache_dir())
+}
+
+pub(crate) fn save_pr_comments_snapshot_with_base(
+    repo: &str,
+    pr_number: u32,
+    comments: &[ReviewComment],
+    base: &std::path::Path,
+) -> Result<()> {
+    if !pr_data_snapshot_path_with_base(repo, pr_number, base)?.exists() {
+        return Ok(());
+    }
+    let path = pr_comments_snapshot_path_with_base(repo, pr_number, base)?;
+    let json = serde_json::to_string(comments)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize review comments: {}", e))?;
+    write_snapshot_file(&path, &json)
+}
+
+/// 同じディレクトリの一時ファイルに書いてから rename する
+///
+/// PR データとコメントは別タスクから保存されるため、書きかけのファイルを読ませない。
+fn write_snapshot_file(path: &std::path::Path, json: &str) -> Result<()> {
+    use std::io::Write;
+
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Invalid cache path: {}", path.display()))?;
+    fs::create_dir_all(parent)
+        .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", parent.display(), e))?;
+    let mut temp = tempfile::NamedTempFile::new_in(parent).map_err(|e| {
+        anyhow::anyhow!("Failed to create temp file in {}: {}", parent.display(), e)
+    })?;
+    temp.write_all(json.as_bytes())
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", temp.path().display(), e))?;
+    temp.persist(path)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e.error))?;
+    Ok(())
+}
+
+const REVIEW_SESSION_VERSION: u32 = 1;
+
+/// クラッシュ復旧用に保存するレビューセッションの状態（PR 単位）。
+///
+/// 全フィールドに `#[serde(default)]` が効くため、フィールド追加後も古い
+/// ファイルを読み込める。未知のフィールドは無視される。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewSessionState {
+    pub version: u32,
+    pub pr_number: u32,
+    /// ファイル一覧はリロードで並びが変わり得るため、インデックスではなくパスで保持
+    pub selected_path: Option<String>,
+    pub file_list_scroll_offset: usize,
+    pub in_diff_view: bool,
+    pub selected_line: usize,
+    pub scroll_offset: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_draft: Option<CommentDraft>,
+    /// PR を正常に離れた際の保存（閲覧位置のみ。次回は確認なしで復元する）
+    pub closed: bool,
+}
+
+/// 入力途中のコメント / サジェスチョン
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommentDraft {
+    pub path: String,
+    pub line_number: u32,
+    pub diff_position: u32,
+    pub start_line_number: Option<u32>,
+    pub side: crate::diff::CommentSide,
+    /// Some ならサジェスチョン（元のコード）
+    pub original_code: Option<String>,
+    pub body: String,
+}
+
+impl ReviewSessionState {
+    pub fn new(pr_number: u32) -> Self {
+        Self {
+            version: REVIEW_SESSION_VERSION,
+            pr_number,
+            ..Self::default()
+        }
+    }
+}
+
+pub fn review_session_path(repo: &str, pr_number: u32) -> Result<PathBuf> {
+    review_session_path_with_base(repo, pr_number, &cache_dir())
+}
+
+fn review_session_path_with_base(
+    repo: &str,
+    pr_number: u32,
+    base: &std::path::Path,
+) -> Result<PathBuf> {
+    let repo = sanitize_repo_name(repo)?;
+    Ok(base
+        .join("sessions")
+        .join(format!("{}-{}.json", repo, pr_number)))
+}
+
+/// 保存済みセッションを読み込む。ファイルがない場合は `Ok(None)`。
+pub fn load_review_session(repo: &str, pr_number: u32) -> Result<Option<ReviewSessionState>> {
+    load_review_session_with_base(repo, pr_number, &cache_dir())
+}
+
+fn load_review_session_with_base(
+    repo: &str,
+    pr_number: u32,
+    base: &std::path::Path,
+) -> Result<Option<ReviewSessionState>> {
+    let path = review_session_path_with_base(repo, pr_number, base)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    let state: ReviewSessionState = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+    if state.version == 0 || state.pr_number != pr_number {
+        return Err(anyhow::anyhow!(
+            "Invalid review session file: {}",
+            path.display()
+        ));
+    }
+    Ok(Some(state))
+}
+
+pub fn save_review_session(repo: &str, state: &ReviewSessionState) -> Result<()> {
+    let path = review_session_path(repo, state.pr_number)?;
+    write_review_session(&path, &serialize_review_session(state)?)
+}
+
+pub fn serialize_review_session(state: &ReviewSessionState) -> Result<String> {
+    serde_json::to_string_pretty(state)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize review session: {}", e))
+}
+
+fn write_review_session(path: &std::path::Path, json: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, json).map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))
+}
+
+pub fn delete_review_session(repo: &str, pr_number: u32) {
+    if let Ok(path) = review_session_path(repo, pr_number) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// panic hook から書き出す最新のセッション（保存先パス, JSON）
+static CRASH_SESSION_SNAPSHOT: std::sync::Mutex<Option<(PathBuf, String)>> =
+    std::sync::Mutex::new(None);
+
+/// panic 時に書き出すセッションを登録する（None で解除）
+pub fn set_crash_session_snapshot(snapshot: Option<(PathBuf, String)>) {
+    if let Ok(mut guard) = CRASH_SESSION_SNAPSHOT.lock() {
+        *guard = snapshot;
+    }
+}
+
+/// 登録済みのセッションを best-effort で書き出す（panic hook 用）
+///
+/// panic 中にロックを待つとデッドロックし得るため `try_lock` のみ行う。
+pub fn flush_crash_session_snapshot() {
+    let Ok(mut guard) = CRASH_SESSION_SNAPSHOT.try_lock() else {
+        return;
+    };
+    if let Some((path, json)) = guard.take() {
+        let _ = write_review_session(&path, &json);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PrCacheKey {
     pub repo: String,
@@ -415,6 +952,9 @@ mod tests {
                 login: "testuser".to_string(),
             },
             updated_at: updated_at.to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
         }
     }
 
@@ -525,6 +1065,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pending_review_comments_roundtrip_and_clear() {
+        let tempdir = tempdir().unwrap();
+        let base = tempdir.path().join("cache");
+
+        let comments = vec![PendingReviewComment {
+            path: "src/main.rs".to_string(),
+            line: 42,
+            diff_position: 7,
+            start_line: Some(40),
+            side: crate::diff::CommentSide::Right,
+            body: "pending note".to_string(),
+            created_at: "2026-03-24T00:00:00Z".to_string(),
+            anchor_text: None,
+        }];
+
+        save_pending_review_comments_with_base("owner/repo", 12, &comments, &base).unwrap();
+        let loaded = load_pending_review_comments_with_base("owner/repo", 12, &base).unwrap();
+        assert_eq!(loaded, comments);
+        // 別 PR の保存先とは混ざらない
+        assert!(
+            load_pending_review_comments_with_base("owner/repo", 13, &base)
+                .unwrap()
+                .is_empty()
+        );
+
+        save_pending_review_comments_with_base("owner/repo", 12, &[], &base).unwrap();
+        let path = pending_review_comments_path_with_base("owner/repo", 12, &base).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_in_flight_comment_is_stored_apart_from_pending() {
+        let tempdir = tempdir().unwrap();
+        let base = tempdir.path().join("cache");
+        let comment = PendingReviewComment {
+            path: "src/main.rs".to_string(),
+            line: 3,
+            diff_position: 2,
+            start_line: None,
+            side: crate::diff::CommentSide::Right,
+            body: "sending".to_string(),
+            created_at: "2026-03-24T00:00:00Z".to_string(),
+            anchor_text: None,
+        };
+
+        save_in_flight_comment_with_base("owner/repo", 12, Some(&comment), &base).unwrap();
+        assert_eq!(
+            load_in_flight_comment_with_base("owner/repo", 12, &base).unwrap(),
+            Some(comment)
+        );
+        assert!(
+            load_pending_review_comments_with_base("owner/repo", 12, &base)
+                .unwrap()
+                .is_empty()
+        );
+
+        save_in_flight_comment_with_base("owner/repo", 12, None, &base).unwrap();
+        assert_eq!(
+            load_in_flight_comment_with_base("owner/repo", 12, &base).unwrap(),
+            None
+        );
+    }
+
     #[test]
     #[serial]
     fn test_local_review_comments_roundtrip() {
@@ -544,6 +1148,7 @@ mod tests {
             },
             created_at: "2026-03-24T00:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         })];
 
         save_local_review_comments_with_base(
@@ -684,6 +1289,7 @@ mod tests {
                 },
                 created_at: "2026-04-27T00:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             }),
             LocalReviewComment::new(ReviewComment {
                 id: 2,
@@ -696,6 +1302,7 @@ mod tests {
                 },
                 created_at: "2026-04-27T00:01:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             }),
         ];
         save_local_review_comments_with_base(
@@ -748,6 +1355,91 @@ mod tests {
         assert_ne!(path_a, path_b);
     }
 
+    #[test]
+    fn test_local_viewed_files_roundtrip() {
+        let dir = tempdir().unwrap();
+        let base = dir.path();
+        let workdir = Some("/tmp/work");
+        assert!(
+            load_local_viewed_files_with_base("owner/repo", workdir, base)
+                .unwrap()
+                .is_empty()
+        );
+
+        let files = HashMap::from([("src/a.rs".to_string(), 42u64)]);
+        save_local_viewed_files_with_base("owner/repo", workdir, &files, base).unwrap();
+        assert_eq!(
+            load_local_viewed_files_with_base("owner/repo", workdir, base).unwrap(),
+            files
+        );
+        // 別の作業ディレクトリとは共有しない
+        assert!(
+            load_local_viewed_files_with_base("owner/repo", Some("/tmp/other"), base)
+                .unwrap()
+                .is_empty()
+        );
+
+        // 空で保存するとファイルごと消える
+        save_local_viewed_files_with_base("owner/repo", workdir, &HashMap::new(), base).unwrap();
+        let path = local_viewed_files_path_with_base("owner/repo", workdir, base).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_review_session_roundtrip() {
+        let dir = tempdir().unwrap();
+        let repo = "owner/repo";
+        assert!(load_review_session_with_base(repo, 7, dir.path())
+            .unwrap()
+            .is_none());
+
+        let state = ReviewSessionState {
+            selected_path: Some("src/main.rs".to_string()),
+            file_list_scroll_offset: 2,
+            in_diff_view: true,
+            selected_line: 42,
+            scroll_offset: 30,
+            comment_draft: Some(CommentDraft {
+                path: "src/main.rs".to_string(),
+                line_number: 10,
+                diff_position: 5,
+                start_line_number: None,
+                side: crate::diff::CommentSide::Right,
+                original_code: Some("let x = 1;".to_string()),
+                body: "```suggestion\nlet x = 2;\n```".to_string(),
+            }),
+            ..ReviewSessionState::new(7)
+        };
+        let path = review_session_path_with_base(repo, 7, dir.path()).unwrap();
+        write_review_session(&path, &serialize_review_session(&state).unwrap()).unwrap();
+
+        let loaded = load_review_session_with_base(repo, 7, dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.version, REVIEW_SESSION_VERSION);
+    }
+
+    #[test]
+    fn test_review_session_tolerates_missing_and_unknown_fields() {
+        let dir = tempdir().unwrap();
+        let path = review_session_path_with_base("owner/repo", 3, dir.path()).unwrap();
+        write_review_session(
+            &path,
+            r#"{"version": 2, "pr_number": 3, "selected_line": 9, "folds": [1, 2]}"#,
+        )
+        .unwrap();
+
+        let loaded = load_review_session_with_base("owner/repo", 3, dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.selected_line, 9);
+        assert_eq!(loaded.selected_path, None);
+        assert!(loaded.comment_draft.is_none());
+        // closed のない旧ファイルはクラッシュ復旧用として扱う
+        assert!(!loaded.closed);
+    }
+
     #[test]
     fn test_session_cache_put_get_pr_data() {
         let mut cache = SessionCache::new();
@@ -1102,4 +1794,78 @@ mod tests {
         assert!(cache.get_review_comments(&evicted_key).is_none());
         assert!(cache.get_discussion_comments(&evicted_key).is_none());
     }
+
+    #[test]
+    fn test_pr_data_snapshot_round_trips_pr_files_and_comments() {
+        let tempdir = tempdir().unwrap();
+        let base = tempdir.path();
+        let pr = make_test_pr("Cached", "2024-01-01");
+        let files = vec![ChangedFile {
+            filename: "src/lib.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some("@@ -1 +1 @@\n+x".to_string()),
+            viewed: false,
+        }];
+        let comment = ReviewComment {
+            id: 10,
+            path: "src/lib.rs".to_string(),
+            line: Some(1),
+            start_line: None,
+            body: "nit".to_string(),
+            user: User {
+                login: "reviewer".to_string(),
+            },
+            created_at: "2024-01-02T00:00:00Z".to_string(),
+            in_reply_to_id: None,
+            reactions: Default::default(),
+        };
+
+        // PR データが無ければコメントだけは保存しない
+        save_pr_comments_snapshot_with_base("owner/repo", 1, std::slice::from_ref(&comment), base)
+            .unwrap();
+        assert!(load_pr_data_snapshot_with_base("owner/repo", 1, base)
+            .unwrap()
+            .is_none());
+
+        save_pr_data_snapshot_with_base("owner/repo", 1, &pr, &files, base).unwrap();
+        save_pr_comments_snapshot_with_base("owner/repo", 1, &[comment], base).unwrap();
+        // PR データを取り直してもコメントは引き継がれる
+        save_pr_data_snapshot_with_base("owner/repo", 1, &pr, &files, base).unwrap();
+
+        let snapshot = load_pr_data_snapshot_with_base("owner/repo", 1, base)
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.pr.title, "Cached");
+        assert_eq!(snapshot.files.len(), 1);
+        assert_eq!(snapshot.files[0].patch.as_deref(), Some("@@ -1 +1 @@\n+x"));
+        assert_eq!(snapshot.comments.len(), 1);
+        assert_eq!(snapshot.comments[0].id, 10);
+        assert_eq!(snapshot.comments[0].body, "nit");
+        assert_eq!(snapshot.comments[0].user.login, "reviewer");
+
+        // PR データとコメントを並行して保存しても互いを消さない
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in 0..50 {
+                    save_pr_data_snapshot_with_base("owner/repo", 1, &pr, &files, base).unwrap();
+                }
+            });
+            scope.spawn(|| {
+                for _ in 0..50 {
+                    save_pr_comments_snapshot_with_base("owner/repo", 1, &snapshot.comments, base)
+                        .unwrap();
+                }
+            });
+        });
+        let reloaded = load_pr_data_snapshot_with_base("owner/repo", 1, base)
+            .unwrap()
+            .unwrap();
+        assert_eq!(reloaded.files.len(), 1);
+        assert_eq!(reloaded.comments.len(), 1);
+        // 一時ファイルは残らない
+        let entries = fs::read_dir(base.join("pr-data")).unwrap().count();
+        assert_eq!(entries, 2);
+    }
 }
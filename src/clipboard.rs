@@ -0,0 +1,82 @@
+//! System clipboard access for yanking diff lines.
+//!
+//! The text is always sent as an OSC 52 escape sequence, which the terminal
+//! turns into a clipboard write — this works over SSH without a clipboard
+//! daemon. On a local session the text is also piped to a platform clipboard
+//! command when one is installed, for terminals that ignore OSC 52.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::Result;
+use base64::Engine;
+
+/// ローカルのクリップボードコマンド（先に見つかったものを使う）
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// OSC 52 でクリップボードに書き込むシーケンス
+///
+/// tmux 内では DCS passthrough で包まないと外側の端末に届かない。
+pub fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    if in_tmux {
+        format!("\x1bPtmux;\x1b\x1b]52;c;{}\x07\x1b\\", encoded)
+    } else {
+        format!("\x1b]52;c;{}\x07", encoded)
+    }
+}
+
+/// `text` をクリップボードにコピーする
+pub fn copy(text: &str) -> Result<()> {
+    let in_tmux = std::env::var_os("TMUX").is_some();
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text, in_tmux).as_bytes())?;
+    stdout.flush()?;
+
+    // SSH 越しではローカルのコマンドは手元のクリップボードに届かない
+    let over_ssh =
+        std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some();
+    if !over_ssh {
+        if let Some((cmd, args)) = CLIPBOARD_COMMANDS
+            .iter()
+            .find(|(cmd, _)| which::which(cmd).is_ok())
+        {
+            // OSC 52 は送信済みなので、コマンドの失敗はエラーにしない
+            let _ = copy_with_command(cmd, args, text);
+        }
+    }
+    Ok(())
+}
+
+fn copy_with_command(cmd: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(cmd)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    child.wait()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hello", false), "\x1b]52;c;aGVsbG8=\x07");
+        assert_eq!(
+            osc52_sequence("hello", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGVsbG8=\x07\x1b\\"
+        );
+    }
+}
@@ -1,5 +1,4 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 use crate::keybinding::{KeyBinding, KeySequence, NamedKey};
 
@@ -77,9 +76,67 @@ pub struct KeybindingsConfig {
     pub tab_next: KeySequence,
     pub rally_background: KeySequence,
     pub rally_pause: KeySequence,
+    pub commit_rally: KeySequence,
+    pub rally_drop_comment: KeySequence,
     pub retry: KeySequence,
     pub confirm_yes: KeySequence,
     pub confirm_no: KeySequence,
+    pub open_blame_in_browser: KeySequence,
+    pub open_history_in_browser: KeySequence,
+    pub jump_to_base_pr: KeySequence,
+    pub toggle_hunk_filter: KeySequence,
+    pub symbol_digest: KeySequence,
+    pub toggle_line_endings: KeySequence,
+    pub first_comment: KeySequence,
+    pub last_comment: KeySequence,
+    pub toggle_pending_review: KeySequence,
+    pub next_change: KeySequence,
+    pub prev_change: KeySequence,
+    pub toggle_highlighting: KeySequence,
+    pub collapse_all_threads: KeySequence,
+    pub expand_all_threads: KeySequence,
+    pub toggle_thread: KeySequence,
+    pub peek_split_hunk: KeySequence,
+    pub reload_current_file: KeySequence,
+    pub filter_added_files: KeySequence,
+    pub filter_deleted_files: KeySequence,
+    pub filter_modified_files: KeySequence,
+    pub filter_renamed_files: KeySequence,
+    pub toggle_hunk: KeySequence,
+    pub toggle_resolved: KeySequence,
+    pub toggle_diff_layout: KeySequence,
+    pub scroll_left: KeySequence,
+    pub scroll_right: KeySequence,
+    pub suggestion_in_editor: KeySequence,
+    pub search_in_diff: KeySequence,
+    pub show_blame: KeySequence,
+    pub filter_files: KeySequence,
+    pub open_all_files_in_editor: KeySequence,
+    pub yank_line: KeySequence,
+    pub yank_hunk: KeySequence,
+    pub review_submit: KeySequence,
+    pub next_change_block: KeySequence,
+    pub prev_change_block: KeySequence,
+    pub reload_config: KeySequence,
+    pub set_diff_base: KeySequence,
+    pub toggle_wrap: KeySequence,
+    pub add_reaction: KeySequence,
+    pub toggle_unchanged: KeySequence,
+    pub pr_list_filters: KeySequence,
+    pub expand_context: KeySequence,
+    pub reset_context: KeySequence,
+    pub toggle_whitespace: KeySequence,
+    pub request_reviewer: KeySequence,
+    pub add_assignee: KeySequence,
+    pub yank_permalink: KeySequence,
+    pub choose_theme: KeySequence,
+    pub suggest_format: KeySequence,
+    pub sort_files: KeySequence,
+    pub next_file_with_comments: KeySequence,
+    pub prev_file_with_comments: KeySequence,
+    pub next_conflict: KeySequence,
+    pub retry_iteration: KeySequence,
+    pub ai_explain_hunk: KeySequence,
 }
 
 impl Default for KeybindingsConfig {
@@ -154,27 +211,96 @@ impl Default for KeybindingsConfig {
             tab_next: KeySequence::single(KeyBinding::char(']')),
             rally_background: KeySequence::single(KeyBinding::char('b')),
             rally_pause: KeySequence::single(KeyBinding::char('p')),
+            commit_rally: KeySequence::single(KeyBinding::char('C')),
+            rally_drop_comment: KeySequence::single(KeyBinding::char('d')),
             retry: KeySequence::single(KeyBinding::char('r')),
             confirm_yes: KeySequence::single(KeyBinding::char('y'))
                 .with_alt(vec![KeyBinding::char('Y')]),
             confirm_no: KeySequence::single(KeyBinding::char('n'))
                 .with_alt(vec![KeyBinding::char('N')]),
+            open_blame_in_browser: KeySequence::double(
+                KeyBinding::char('g'),
+                KeyBinding::char('b'),
+            ),
+            open_history_in_browser: KeySequence::double(
+                KeyBinding::char('g'),
+                KeyBinding::char('h'),
+            ),
+            jump_to_base_pr: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('p')),
+            toggle_hunk_filter: KeySequence::single(KeyBinding::char('H')),
+            symbol_digest: KeySequence::single(KeyBinding::char('D')),
+            toggle_line_endings: KeySequence::single(KeyBinding::char('E')),
+            first_comment: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('n')),
+            last_comment: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('N')),
+            toggle_pending_review: KeySequence::single(KeyBinding::char('P')),
+            next_change: KeySequence::single(KeyBinding::char('}')),
+            prev_change: KeySequence::single(KeyBinding::char('{')),
+            toggle_highlighting: KeySequence::single(KeyBinding::char('T')),
+            collapse_all_threads: KeySequence::double(KeyBinding::char('z'), KeyBinding::char('M')),
+            expand_all_threads: KeySequence::double(KeyBinding::char('z'), KeyBinding::char('R')),
+            toggle_thread: KeySequence::double(KeyBinding::char('z'), KeyBinding::char('a')),
+            peek_split_hunk: KeySequence::single(KeyBinding::char('|')),
+            reload_current_file: KeySequence::single(KeyBinding::char('U')),
+            filter_added_files: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('a')),
+            filter_deleted_files: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('d')),
+            filter_modified_files: KeySequence::double(
+                KeyBinding::char(' '),
+                KeyBinding::char('m'),
+            ),
+            filter_renamed_files: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('r')),
+            toggle_hunk: KeySequence::double(KeyBinding::char('z'), KeyBinding::char('a')),
+            toggle_resolved: KeySequence::single(KeyBinding::char('x')),
+            toggle_diff_layout: KeySequence::single(KeyBinding::char('w')),
+            scroll_left: KeySequence::single(KeyBinding::char('<')),
+            scroll_right: KeySequence::single(KeyBinding::char('>')),
+            suggestion_in_editor: KeySequence::single(KeyBinding::char('e')),
+            search_in_diff: KeySequence::single(KeyBinding::char('/')),
+            show_blame: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('B')),
+            filter_files: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('g')),
+            open_all_files_in_editor: KeySequence::double(
+                KeyBinding::char('g'),
+                KeyBinding::char('F'),
+            ),
+            yank_line: KeySequence::single(KeyBinding::char('y')),
+            yank_hunk: KeySequence::single(KeyBinding::char('Y')),
+            review_submit: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('s')),
+            next_change_block: KeySequence::double(KeyBinding::char(']'), KeyBinding::char('c')),
+            prev_change_block: KeySequence::double(KeyBinding::char('['), KeyBinding::char('c')),
+            reload_config: KeySequence::single(KeyBinding::ctrl('r')),
+            set_diff_base: KeySequence::single(KeyBinding::char('B')),
+            toggle_wrap: KeySequence::double(KeyBinding::char('z'), KeyBinding::char('w')),
+            add_reaction: KeySequence::single(KeyBinding::char('+')),
+            toggle_unchanged: KeySequence::double(KeyBinding::char('z'), KeyBinding::char('u')),
+            pr_list_filters: KeySequence::single(KeyBinding::char('f')),
+            expand_context: KeySequence::single(KeyBinding::char('+')),
+            reset_context: KeySequence::single(KeyBinding::char('-')),
+            toggle_whitespace: KeySequence::double(KeyBinding::char('z'), KeyBinding::char('i')),
+            request_reviewer: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('R')),
+            add_assignee: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('A')),
+            yank_permalink: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('y')),
+            choose_theme: KeySequence::double(KeyBinding::char('z'), KeyBinding::char('T')),
+            suggest_format: KeySequence::single(KeyBinding::char('f')),
+            sort_files: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('o')),
+            next_file_with_comments: KeySequence::double(
+                KeyBinding::char(']'),
+                KeyBinding::char('C'),
+            ),
+            prev_file_with_comments: KeySequence::double(
+                KeyBinding::char('['),
+                KeyBinding::char('C'),
+            ),
+            next_conflict: KeySequence::double(KeyBinding::char(']'), KeyBinding::char('n')),
+            retry_iteration: KeySequence::single(KeyBinding::char('R')),
+            ai_explain_hunk: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('e')),
         }
     }
 }
 
 impl KeybindingsConfig {
-    /// Validate keybindings for conflicts
-    ///
-    /// Detects:
-    /// - Single keys that conflict with sequence prefixes
-    /// - Duplicate keybindings for different actions
-    pub fn validate(&self) -> Result<(), Vec<String>> {
-        let mut errors = Vec::new();
-        let mut single_keys: HashMap<KeyBinding, &str> = HashMap::new();
-        let mut sequence_prefixes: HashMap<KeyBinding, &str> = HashMap::new();
-
-        let bindings: Vec<(&str, &KeySequence)> = vec![
+    /// All actions as `(name, keys)` pairs, in definition order.
+    /// `name` is the `[keybindings]` key (see [`crate::keybinding::action_meta`]).
+    pub fn bindings(&self) -> Vec<(&'static str, &KeySequence)> {
+        vec![
             ("move_down", &self.move_down),
             ("move_up", &self.move_up),
             ("move_left", &self.move_left),
@@ -232,52 +358,119 @@ impl KeybindingsConfig {
             ("tab_next", &self.tab_next),
             ("rally_background", &self.rally_background),
             ("rally_pause", &self.rally_pause),
+            ("commit_rally", &self.commit_rally),
+            ("rally_drop_comment", &self.rally_drop_comment),
             ("retry", &self.retry),
             ("confirm_yes", &self.confirm_yes),
             ("confirm_no", &self.confirm_no),
-        ];
+            ("open_blame_in_browser", &self.open_blame_in_browser),
+            ("open_history_in_browser", &self.open_history_in_browser),
+            ("jump_to_base_pr", &self.jump_to_base_pr),
+            ("toggle_hunk_filter", &self.toggle_hunk_filter),
+            ("symbol_digest", &self.symbol_digest),
+            ("toggle_line_endings", &self.toggle_line_endings),
+            ("first_comment", &self.first_comment),
+            ("last_comment", &self.last_comment),
+            ("toggle_pending_review", &self.toggle_pending_review),
+            ("next_change", &self.next_change),
+            ("prev_change", &self.prev_change),
+            ("toggle_highlighting", &self.toggle_highlighting),
+            ("collapse_all_threads", &self.collapse_all_threads),
+            ("expand_all_threads", &self.expand_all_threads),
+            ("toggle_thread", &self.toggle_thread),
+            ("peek_split_hunk", &self.peek_split_hunk),
+            ("reload_current_file", &self.reload_current_file),
+            ("filter_added_files", &self.filter_added_files),
+            ("filter_deleted_files", &self.filter_deleted_files),
+            ("filter_modified_files", &self.filter_modified_files),
+            ("filter_renamed_files", &self.filter_renamed_files),
+            ("toggle_hunk", &self.toggle_hunk),
+            ("toggle_resolved", &self.toggle_resolved),
+            ("toggle_diff_layout", &self.toggle_diff_layout),
+            ("scroll_left", &self.scroll_left),
+            ("scroll_right", &self.scroll_right),
+            ("suggestion_in_editor", &self.suggestion_in_editor),
+            ("search_in_diff", &self.search_in_diff),
+            ("show_blame", &self.show_blame),
+            ("filter_files", &self.filter_files),
+            ("open_all_files_in_editor", &self.open_all_files_in_editor),
+            ("yank_line", &self.yank_line),
+            ("yank_hunk", &self.yank_hunk),
+            ("review_submit", &self.review_submit),
+            ("next_change_block", &self.next_change_block),
+            ("prev_change_block", &self.prev_change_block),
+            ("reload_config", &self.reload_config),
+            ("set_diff_base", &self.set_diff_base),
+            ("toggle_wrap", &self.toggle_wrap),
+            ("add_reaction", &self.add_reaction),
+            ("toggle_unchanged", &self.toggle_unchanged),
+            ("pr_list_filters", &self.pr_list_filters),
+            ("expand_context", &self.expand_context),
+            ("reset_context", &self.reset_context),
+            ("toggle_whitespace", &self.toggle_whitespace),
+            ("request_reviewer", &self.request_reviewer),
+            ("add_assignee", &self.add_assignee),
+            ("yank_permalink", &self.yank_permalink),
+            ("choose_theme", &self.choose_theme),
+            ("suggest_format", &self.suggest_format),
+            ("sort_files", &self.sort_files),
+            ("next_file_with_comments", &self.next_file_with_comments),
+            ("prev_file_with_comments", &self.prev_file_with_comments),
+            ("next_conflict", &self.next_conflict),
+            ("retry_iteration", &self.retry_iteration),
+            ("ai_explain_hunk", &self.ai_explain_hunk),
+        ]
+    }
+
+    /// Validate keybindings for conflicts
+    ///
+    /// Detects (across primary and alternative sequences):
+    /// - Duplicate keybindings for different actions
+    /// - Sequences that are a prefix of another action's sequence
+    ///   (the shorter one would fire before the longer one can complete)
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut sequences: Vec<(&str, &[KeyBinding])> = Vec::new();
 
-        for (name, seq) in &bindings {
+        for (name, seq) in self.bindings() {
             if seq.keys.is_empty() {
                 errors.push(format!("keybinding '{}' is empty", name));
                 continue;
             }
-
-            if seq.is_single() {
-                let key = seq.keys[0];
-                if let Some(existing) = single_keys.get(&key) {
-                    // Allow same key for different contexts (e.g., 'r' for reply and request_changes)
-                    // This is intentional - context determines which action is triggered
-                    if !is_context_compatible(name, existing) {
-                        errors.push(format!(
-                            "duplicate keybinding: '{}' and '{}' both use {}",
-                            name,
-                            existing,
-                            key.display()
-                        ));
-                    }
-                } else {
-                    single_keys.insert(key, name);
-                }
-            } else {
-                // For sequences, track the first key as a prefix
-                if let Some(first) = seq.first() {
-                    sequence_prefixes.insert(*first, name);
-                }
-            }
+            sequences.extend(
+                seq.all_sequences()
+                    .filter(|keys| !keys.is_empty())
+                    .map(|keys| (name, keys)),
+            );
         }
 
-        // Check for conflicts between single keys and sequence prefixes
-        for (key, single_name) in &single_keys {
-            if let Some(seq_name) = sequence_prefixes.get(key) {
-                // Only warn if they're in the same context
-                if !is_context_compatible(single_name, seq_name) {
+        for (i, &(name, keys)) in sequences.iter().enumerate() {
+            for &(other, other_keys) in &sequences[..i] {
+                // Allow same key for different contexts (e.g., 'r' for reply and request_changes)
+                // This is intentional - context determines which action is triggered
+                if name == other || is_context_compatible(name, other) {
+                    continue;
+                }
+                if keys == other_keys {
+                    errors.push(format!(
+                        "duplicate keybinding: '{}' and '{}' both use {}",
+                        name,
+                        other,
+                        display_keys(keys)
+                    ));
+                } else if keys.starts_with(other_keys) || other_keys.starts_with(keys) {
+                    let ((short_name, short), (long_name, long)) = if keys.len() < other_keys.len()
+                    {
+                        ((name, keys), (other, other_keys))
+                    } else {
+                        ((other, other_keys), (name, keys))
+                    };
                     errors.push(format!(
-                        "keybinding conflict: '{}' ({}) conflicts with sequence prefix for '{}' ({})",
-                        single_name,
-                        key.display(),
-                        seq_name,
-                        key.display()
+                        "keybinding conflict: '{}' ({}) is a prefix of '{}' ({})",
+                        short_name,
+                        display_keys(short),
+                        long_name,
+                        display_keys(long)
                     ));
                 }
             }
@@ -291,6 +484,10 @@ impl KeybindingsConfig {
     }
 }
 
+fn display_keys(keys: &[KeyBinding]) -> String {
+    keys.iter().map(|k| k.display()).collect()
+}
+
 /// Check if two keybindings are in compatible contexts
 /// (i.e., they won't conflict because they're used in different views)
 fn is_context_compatible(name1: &str, name2: &str) -> bool {
@@ -320,9 +517,17 @@ fn is_context_compatible(name1: &str, name2: &str) -> bool {
         "tab_next",
         "rally_background",
         "rally_pause",
+        "commit_rally",
+        "rally_drop_comment",
         "retry",
+        "retry_iteration",
         "confirm_yes",
         "confirm_no",
+        "toggle_resolved",
+        "reload_config",
+        "set_diff_base",
+        "add_reaction",
+        "pr_list_filters",
     ];
 
     let context_groups: &[&[&str]] = &[
@@ -334,6 +539,7 @@ fn is_context_compatible(name1: &str, name2: &str) -> bool {
         &["filter_all", "approve"],
         &["retry", "reply", "request_changes"],
         &["confirm_no", "next_comment"],
+        &["toggle_hunk", "toggle_thread"],
     ];
 
     // git ops 固有キーは git ops 画面でのみ有効なので、他の全キーと context compatible
@@ -438,9 +644,124 @@ impl Serialize for KeybindingsConfig {
         map.serialize_entry("tab_next", &seq_to_value(&self.tab_next))?;
         map.serialize_entry("rally_background", &seq_to_value(&self.rally_background))?;
         map.serialize_entry("rally_pause", &seq_to_value(&self.rally_pause))?;
+        map.serialize_entry("commit_rally", &seq_to_value(&self.commit_rally))?;
+        map.serialize_entry(
+            "rally_drop_comment",
+            &seq_to_value(&self.rally_drop_comment),
+        )?;
         map.serialize_entry("retry", &seq_to_value(&self.retry))?;
         map.serialize_entry("confirm_yes", &seq_to_value(&self.confirm_yes))?;
         map.serialize_entry("confirm_no", &seq_to_value(&self.confirm_no))?;
+        map.serialize_entry(
+            "open_blame_in_browser",
+            &seq_to_value(&self.open_blame_in_browser),
+        )?;
+        map.serialize_entry(
+            "open_history_in_browser",
+            &seq_to_value(&self.open_history_in_browser),
+        )?;
+        map.serialize_entry("jump_to_base_pr", &seq_to_value(&self.jump_to_base_pr))?;
+        map.serialize_entry(
+            "toggle_hunk_filter",
+            &seq_to_value(&self.toggle_hunk_filter),
+        )?;
+        map.serialize_entry("symbol_digest", &seq_to_value(&self.symbol_digest))?;
+        map.serialize_entry(
+            "toggle_line_endings",
+            &seq_to_value(&self.toggle_line_endings),
+        )?;
+        map.serialize_entry("first_comment", &seq_to_value(&self.first_comment))?;
+        map.serialize_entry("last_comment", &seq_to_value(&self.last_comment))?;
+        map.serialize_entry(
+            "toggle_pending_review",
+            &seq_to_value(&self.toggle_pending_review),
+        )?;
+        map.serialize_entry("next_change", &seq_to_value(&self.next_change))?;
+        map.serialize_entry("prev_change", &seq_to_value(&self.prev_change))?;
+        map.serialize_entry(
+            "toggle_highlighting",
+            &seq_to_value(&self.toggle_highlighting),
+        )?;
+        map.serialize_entry(
+            "collapse_all_threads",
+            &seq_to_value(&self.collapse_all_threads),
+        )?;
+        map.serialize_entry(
+            "expand_all_threads",
+            &seq_to_value(&self.expand_all_threads),
+        )?;
+        map.serialize_entry("toggle_thread", &seq_to_value(&self.toggle_thread))?;
+        map.serialize_entry("peek_split_hunk", &seq_to_value(&self.peek_split_hunk))?;
+        map.serialize_entry(
+            "reload_current_file",
+            &seq_to_value(&self.reload_current_file),
+        )?;
+        map.serialize_entry(
+            "filter_added_files",
+            &seq_to_value(&self.filter_added_files),
+        )?;
+        map.serialize_entry(
+            "filter_deleted_files",
+            &seq_to_value(&self.filter_deleted_files),
+        )?;
+        map.serialize_entry(
+            "filter_modified_files",
+            &seq_to_value(&self.filter_modified_files),
+        )?;
+        map.serialize_entry(
+            "filter_renamed_files",
+            &seq_to_value(&self.filter_renamed_files),
+        )?;
+        map.serialize_entry("toggle_hunk", &seq_to_value(&self.toggle_hunk))?;
+        map.serialize_entry("toggle_resolved", &seq_to_value(&self.toggle_resolved))?;
+        map.serialize_entry(
+            "toggle_diff_layout",
+            &seq_to_value(&self.toggle_diff_layout),
+        )?;
+        map.serialize_entry("scroll_left", &seq_to_value(&self.scroll_left))?;
+        map.serialize_entry("scroll_right", &seq_to_value(&self.scroll_right))?;
+        map.serialize_entry(
+            "suggestion_in_editor",
+            &seq_to_value(&self.suggestion_in_editor),
+        )?;
+        map.serialize_entry("search_in_diff", &seq_to_value(&self.search_in_diff))?;
+        map.serialize_entry("show_blame", &seq_to_value(&self.show_blame))?;
+        map.serialize_entry("filter_files", &seq_to_value(&self.filter_files))?;
+        map.serialize_entry(
+            "open_all_files_in_editor",
+            &seq_to_value(&self.open_all_files_in_editor),
+        )?;
+        map.serialize_entry("yank_line", &seq_to_value(&self.yank_line))?;
+        map.serialize_entry("yank_hunk", &seq_to_value(&self.yank_hunk))?;
+        map.serialize_entry("review_submit", &seq_to_value(&self.review_submit))?;
+        map.serialize_entry("next_change_block", &seq_to_value(&self.next_change_block))?;
+        map.serialize_entry("prev_change_block", &seq_to_value(&self.prev_change_block))?;
+        map.serialize_entry("reload_config", &seq_to_value(&self.reload_config))?;
+        map.serialize_entry("set_diff_base", &seq_to_value(&self.set_diff_base))?;
+        map.serialize_entry("toggle_wrap", &seq_to_value(&self.toggle_wrap))?;
+        map.serialize_entry("add_reaction", &seq_to_value(&self.add_reaction))?;
+        map.serialize_entry("toggle_unchanged", &seq_to_value(&self.toggle_unchanged))?;
+        map.serialize_entry("pr_list_filters", &seq_to_value(&self.pr_list_filters))?;
+        map.serialize_entry("expand_context", &seq_to_value(&self.expand_context))?;
+        map.serialize_entry("reset_context", &seq_to_value(&self.reset_context))?;
+        map.serialize_entry("toggle_whitespace", &seq_to_value(&self.toggle_whitespace))?;
+        map.serialize_entry("request_reviewer", &seq_to_value(&self.request_reviewer))?;
+        map.serialize_entry("add_assignee", &seq_to_value(&self.add_assignee))?;
+        map.serialize_entry("yank_permalink", &seq_to_value(&self.yank_permalink))?;
+        map.serialize_entry("choose_theme", &seq_to_value(&self.choose_theme))?;
+        map.serialize_entry("suggest_format", &seq_to_value(&self.suggest_format))?;
+        map.serialize_entry("sort_files", &seq_to_value(&self.sort_files))?;
+        map.serialize_entry(
+            "next_file_with_comments",
+            &seq_to_value(&self.next_file_with_comments),
+        )?;
+        map.serialize_entry(
+            "prev_file_with_comments",
+            &seq_to_value(&self.prev_file_with_comments),
+        )?;
+        map.serialize_entry("next_conflict", &seq_to_value(&self.next_conflict))?;
+        map.serialize_entry("retry_iteration", &seq_to_value(&self.retry_iteration))?;
+        map.serialize_entry("ai_explain_hunk", &seq_to_value(&self.ai_explain_hunk))?;
 
         map.end()
     }
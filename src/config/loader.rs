@@ -67,31 +67,34 @@ pub fn find_project_root_in(dir: &Path) -> PathBuf {
 }
 
 impl Config {
-    pub fn load() -> Result<Self> {
+    /// Load config. `repo` selects the matching `[repos."owner/repo"]` section
+    /// of the global config, if any.
+    pub fn load(repo: Option<&str>) -> Result<Self> {
         let global_path = Self::config_path();
         let project_root = find_project_root();
         let local_path = project_root.join(".octorus/config.toml");
-        Self::load_from_paths(&global_path, &local_path, project_root)
+        Self::load_from_paths_for_repo(&global_path, &local_path, project_root, repo)
     }
 
     /// Load config with project root resolved from a specific directory.
     /// Use this when `--working-dir` is specified so that `.octorus/` is
     /// resolved relative to the working directory's git root, not the
     /// process cwd.
-    pub fn load_for_dir(dir: &Path) -> Result<Self> {
+    pub fn load_for_dir(dir: &Path, repo: Option<&str>) -> Result<Self> {
         let global_path = Self::config_path();
         let project_root = find_project_root_in(dir);
         let local_path = project_root.join(".octorus/config.toml");
-        Self::load_from_paths(&global_path, &local_path, project_root)
+        Self::load_from_paths_for_repo(&global_path, &local_path, project_root, repo)
     }
 
     /// Load config by merging global and local TOML files.
     /// Local values override global values at the TOML table level (deep merge).
     ///
     /// SECURITY NOTE: Local `.octorus/config.toml` has a 3-tier trust model:
-    /// - **Stripped**: `editor` is removed before merge (command injection risk)
+    /// - **Stripped**: `editor` and `formatters` are removed before merge
+    ///   (command injection risk)
     /// - **Confirmation required**: `ai.*_additional_tools`, `ai.auto_post`,
-    ///   `ai.reviewer`, `ai.reviewee`, `ai.review_only` — tracked in
+    ///   `ai.reviewer`, `ai.reviewee`, `ai.review_only`, `ai.rally_mode` — tracked in
     ///   `local_overrides` and guarded by TUI confirmation / headless
     ///   `--accept-local-overrides`
     /// - **Validated**: `ai.prompt_dir` — path traversal checks applied
@@ -101,6 +104,21 @@ impl Config {
         global_path: &Path,
         local_path: &Path,
         project_root: PathBuf,
+    ) -> Result<Self> {
+        Self::load_from_paths_for_repo(global_path, local_path, project_root, None)
+    }
+
+    /// [`Self::load_from_paths`] with a per-repo section applied.
+    ///
+    /// The global config may contain `[repos."owner/repo"]` tables holding any
+    /// top-level keys or sections. When `repo` matches one (case-insensitive),
+    /// it is deep-merged over the global values. Precedence:
+    /// local `.octorus/config.toml` > repo section > global.
+    pub fn load_from_paths_for_repo(
+        global_path: &Path,
+        local_path: &Path,
+        project_root: PathBuf,
+        repo: Option<&str>,
     ) -> Result<Self> {
         let mut base_value: toml::Value = if global_path.exists() {
             let content =
@@ -110,6 +128,23 @@ impl Config {
             toml::Value::Table(toml::map::Map::new())
         };
 
+        let mut loaded_repo_section = None;
+        let repo_sections = match base_value {
+            toml::Value::Table(ref mut t) => t.remove("repos"),
+            _ => None,
+        };
+        if let (Some(repo), Some(toml::Value::Table(mut sections))) = (repo, repo_sections) {
+            let key = sections
+                .keys()
+                .find(|name| name.eq_ignore_ascii_case(repo))
+                .cloned();
+            if let Some(toml::Value::Table(mut section)) = key.and_then(|k| sections.remove(&k)) {
+                section.remove("repos");
+                deep_merge_toml(&mut base_value, toml::Value::Table(section));
+                loaded_repo_section = Some(repo.to_string());
+            }
+        }
+
         let mut stripped_local_value: Option<toml::Value> = None;
         if local_path.exists() {
             let local_content = fs::read_to_string(local_path)
@@ -125,6 +160,15 @@ impl Config {
                         "editor key in local .octorus/config.toml is ignored for security"
                     );
                 }
+                if t.remove("formatters").is_some() {
+                    tracing::warn!(
+                        "formatters in local .octorus/config.toml are ignored for security"
+                    );
+                }
+                // repo 別セクションはグローバル設定でのみ有効
+                if t.remove("repos").is_some() {
+                    tracing::warn!("repos section in local .octorus/config.toml is ignored");
+                }
             }
 
             stripped_local_value = Some(local_value.clone());
@@ -145,6 +189,7 @@ impl Config {
         } else {
             None
         };
+        config.loaded_repo_section = loaded_repo_section;
         config.local_overrides = match stripped_local_value {
             Some(ref v) => Self::collect_override_keys_from_value(v),
             None => HashSet::new(),
@@ -168,6 +213,9 @@ impl Config {
         const MAX_TIMEOUT_SECS_LIMIT: u64 = 7200; // 2 hours
         config.ai.max_iterations = config.ai.max_iterations.min(MAX_ITERATIONS_LIMIT);
         config.ai.timeout_secs = config.ai.timeout_secs.min(MAX_TIMEOUT_SECS_LIMIT);
+        if config.auto_refresh_secs > 0 {
+            config.auto_refresh_secs = config.auto_refresh_secs.max(super::MIN_AUTO_REFRESH_SECS);
+        }
 
         // Validate keybindings and warn on conflicts
         if let Err(errors) = config.keybindings.validate() {
@@ -186,8 +234,17 @@ impl Config {
         let toml::Value::Table(table) = value else {
             return overrides;
         };
-        if table.contains_key("editor") {
-            overrides.insert("editor".to_string());
+        for key in [
+            "editor",
+            "read_only",
+            "comment_warn_length",
+            "quote_line_in_comment_editor",
+            "auto_refresh_secs",
+            "mouse",
+        ] {
+            if table.contains_key(key) {
+                overrides.insert(key.to_string());
+            }
         }
         for section in ["diff", "ai", "keybindings", "layout"] {
             if let Some(toml::Value::Table(sub)) = table.get(section) {
@@ -5,11 +5,13 @@ mod schema;
 pub use keybindings::KeybindingsConfig;
 pub use loader::{find_project_root, find_project_root_in};
 pub use schema::{
-    AiConfig, DiffConfig, GitOpsConfig, LayoutConfig, ProposalPostStrategy, ShellConfig,
+    AiConfig, DiffColorsConfig, DiffConfig, GitOpsConfig, GithubBackendKind, GithubConfig, Gutter,
+    LayoutConfig, LineNumbers, ProposalPostStrategy, RallyMode, SessionConfig, ShellConfig,
+    ShowWhitespace, DEFAULT_GH_RETRIES, DEFAULT_GH_RETRY_BASE_DELAY_MS,
 };
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 /// Security-sensitive AI config keys that require user confirmation
@@ -25,13 +27,37 @@ pub const SENSITIVE_AI_KEYS: &[&str] = &[
     "ai.prompt_dir",
     "ai.review_only",
     "ai.post_reviewee_proposals",
+    "ai.rally_mode",
 ];
 
+/// `auto_refresh_secs` の下限（API を叩きすぎないため）
+pub const MIN_AUTO_REFRESH_SECS: u64 = 10;
+
+/// `comment_warn_length` 未設定時のソフト上限（文字数）
+pub const DEFAULT_COMMENT_WARN_LENGTH: usize = 2000;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
     pub editor: Option<String>,
+    /// 閲覧専用: コメント・suggestion・レビュー送信などの書き込み操作を無効化する
+    pub read_only: bool,
+    /// コメント本文がこの文字数を超えると入力欄に警告を出す（送信は妨げない）。
+    /// 未設定なら [`DEFAULT_COMMENT_WARN_LENGTH`]、0 で無効。
+    pub comment_warn_length: Option<usize>,
+    /// 外部エディタでコメントを書くとき、対象行を fenced code block で引用して開く
+    pub quote_line_in_comment_editor: bool,
+    /// リモート PR を開いている間、この秒数ごとに PR データとコメントを再取得する。
+    /// 0 で無効（既定）。[`MIN_AUTO_REFRESH_SECS`] 未満は切り上げる。
+    pub auto_refresh_secs: u64,
+    /// マウス操作（ファイル一覧のクリック選択、diff のホイールスクロール・クリックでのカーソル移動）。
+    /// 有効にすると端末のテキスト選択には Shift 等の修飾キーが必要になる。
+    pub mouse: bool,
+    /// `suggest_format` で使う拡張子ごとのフォーマッタコマンド（例: `rs = "rustfmt --emit stdout"`）。
+    /// ローカル設定では無視する。
+    pub formatters: HashMap<String, String>,
     pub diff: DiffConfig,
+    pub diff_colors: DiffColorsConfig,
     pub keybindings: KeybindingsConfig,
     pub ai: AiConfig,
     #[serde(default)]
@@ -39,14 +65,22 @@ pub struct Config {
     #[serde(alias = "git_log")]
     pub git_ops: GitOpsConfig,
     pub shell: ShellConfig,
+    pub session: SessionConfig,
+    pub github: GithubConfig,
     #[serde(skip)]
     pub project_root: PathBuf,
+    /// `--theme` で起動時に指定したテーマ（設定の再読み込み後も `diff.theme` より優先する）
+    #[serde(skip)]
+    pub theme_override: Option<String>,
     /// Path of the global config file if it was loaded successfully.
     #[serde(skip)]
     pub loaded_global_config: Option<PathBuf>,
     /// Path of the local config file if it was loaded successfully.
     #[serde(skip)]
     pub loaded_local_config: Option<PathBuf>,
+    /// Repo name of the `[repos."owner/repo"]` section applied at load time.
+    #[serde(skip)]
+    pub loaded_repo_section: Option<String>,
     /// Set of dotted key paths overridden by the local config (e.g. "diff.theme", "editor").
     /// Computed once at load time to avoid per-frame disk I/O.
     #[serde(skip)]
@@ -65,7 +99,55 @@ mod tests {
     #[test]
     fn test_default_keybindings() {
         let config = KeybindingsConfig::default();
-        assert!(config.validate().is_ok());
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_duplicate_and_prefix_sequences() {
+        use crate::keybinding::{KeyBinding, KeySequence};
+
+        let mut config = KeybindingsConfig::default();
+        config.show_blame = config.open_blame_in_browser.clone();
+        assert_eq!(
+            config.validate(),
+            Err(vec![
+                "duplicate keybinding: 'show_blame' and 'open_blame_in_browser' both use gb"
+                    .to_string()
+            ])
+        );
+
+        let config = KeybindingsConfig {
+            show_blame: KeySequence {
+                keys: vec![
+                    KeyBinding::char('g'),
+                    KeyBinding::char('h'),
+                    KeyBinding::char('x'),
+                ],
+                alt: vec![],
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(vec![
+                "keybinding conflict: 'open_history_in_browser' (gh) is a prefix of 'show_blame' (ghx)"
+                    .to_string()
+            ])
+        );
+
+        // 代替シーケンスも対象にする
+        let config = KeybindingsConfig {
+            show_blame: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('B'))
+                .with_alt(vec![KeyBinding::char('U')]),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(vec![
+                "duplicate keybinding: 'show_blame' and 'reload_current_file' both use U"
+                    .to_string()
+            ])
+        );
     }
 
     #[test]
@@ -135,13 +217,15 @@ mod tests {
           "reviewee": "claude",
           "max_iterations": 10,
           "timeout_secs": 600,
+          "stall_warning_secs": 120,
           "prompt_dir": null,
           "reviewer_additional_tools": [],
           "reviewee_additional_tools": [],
           "reviewee_proposal_additional_tools": [],
           "auto_post": false,
           "review_only": false,
-          "post_reviewee_proposals": "final"
+          "post_reviewee_proposals": "final",
+          "rally_mode": "dry_run"
         }
         "#);
     }
@@ -162,13 +246,15 @@ mod tests {
           "reviewee": "claude",
           "max_iterations": 5,
           "timeout_secs": 300,
+          "stall_warning_secs": 120,
           "prompt_dir": null,
           "reviewer_additional_tools": [],
           "reviewee_additional_tools": [],
           "reviewee_proposal_additional_tools": [],
           "auto_post": false,
           "review_only": false,
-          "post_reviewee_proposals": "final"
+          "post_reviewee_proposals": "final",
+          "rally_mode": "dry_run"
         }
         "#);
     }
@@ -187,6 +273,7 @@ mod tests {
           "reviewee": "claude",
           "max_iterations": 10,
           "timeout_secs": 600,
+          "stall_warning_secs": 120,
           "prompt_dir": null,
           "reviewer_additional_tools": [
             "Skill",
@@ -198,7 +285,8 @@ mod tests {
           "reviewee_proposal_additional_tools": [],
           "auto_post": false,
           "review_only": false,
-          "post_reviewee_proposals": "final"
+          "post_reviewee_proposals": "final",
+          "rally_mode": "dry_run"
         }
         "#);
     }
@@ -298,6 +386,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_diff_marker_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.diff.highlight_markers);
+        assert_eq!(
+            config.diff.marker_words,
+            vec!["TODO", "FIXME", "HACK", "XXX"]
+        );
+        assert_eq!(config.diff.marker_color, "yellow");
+        assert!(config.diff.marker_bold);
+    }
+
+    #[test]
+    fn test_diff_marker_custom_roundtrip() {
+        let toml_str = r##"
+            [diff]
+            highlight_markers = true
+            marker_words = ["NOTE", "SAFETY"]
+            marker_color = "#ff8800"
+            marker_bold = false
+        "##;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert!(config.diff.highlight_markers);
+        assert_eq!(config.diff.marker_words, vec!["NOTE", "SAFETY"]);
+        assert_eq!(config.diff.marker_color, "#ff8800");
+        assert!(!config.diff.marker_bold);
+
+        let serialized = toml::to_string(&config.diff).unwrap();
+        let deserialized: DiffConfig = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.marker_words, config.diff.marker_words);
+        assert_eq!(deserialized.marker_color, config.diff.marker_color);
+        assert_eq!(deserialized.marker_bold, config.diff.marker_bold);
+        assert!(deserialized.highlight_markers);
+    }
+
+    #[test]
+    fn test_parse_show_whitespace_values() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.diff.show_whitespace, ShowWhitespace::None);
+
+        for (value, expected) in [
+            ("\"none\"", ShowWhitespace::None),
+            ("\"trailing\"", ShowWhitespace::Trailing),
+            ("\"all\"", ShowWhitespace::All),
+        ] {
+            let toml_str = format!("[diff]\nshow_whitespace = {}\n", value);
+            let config: Config = toml::from_str(&toml_str)
+                .unwrap_or_else(|e| panic!("parse {} failed: {}", value, e));
+            assert_eq!(config.diff.show_whitespace, expected);
+        }
+        assert!(toml::from_str::<Config>("[diff]\nshow_whitespace = \"some\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_gutter_values() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.diff.gutter, Gutter::None);
+
+        for (value, expected) in [
+            ("\"none\"", Gutter::None),
+            ("\"new\"", Gutter::New),
+            ("\"both\"", Gutter::Both),
+        ] {
+            let toml_str = format!("[diff]\ngutter = {}\n", value);
+            let config: Config = toml::from_str(&toml_str)
+                .unwrap_or_else(|e| panic!("parse {} failed: {}", value, e));
+            assert_eq!(config.diff.gutter, expected);
+        }
+        assert!(toml::from_str::<Config>("[diff]\ngutter = \"old\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_line_numbers_values() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.diff.line_numbers, LineNumbers::Absolute);
+
+        for (value, expected) in [
+            ("\"absolute\"", LineNumbers::Absolute),
+            ("\"relative\"", LineNumbers::Relative),
+            ("\"hybrid\"", LineNumbers::Hybrid),
+        ] {
+            let toml_str = format!("[diff]\nline_numbers = {}\n", value);
+            let config: Config = toml::from_str(&toml_str)
+                .unwrap_or_else(|e| panic!("parse {} failed: {}", value, e));
+            assert_eq!(config.diff.line_numbers, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_github_backend() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.github.backend, GithubBackendKind::Gh);
+
+        let config: Config = toml::from_str("[github]\nbackend = \"rest\"\n").unwrap();
+        assert_eq!(config.github.backend, GithubBackendKind::Rest);
+        assert_eq!(config.github.retries, DEFAULT_GH_RETRIES);
+        assert!(toml::from_str::<Config>("[github]\nbackend = \"graphql\"\n").is_err());
+    }
+
     #[test]
     fn test_serialize_roundtrip_includes_toggle_markdown_rich() {
         let config = KeybindingsConfig::default();
@@ -419,6 +606,139 @@ max_iterations = 3
         assert_eq!(config.ai.max_iterations, 3); // overridden by local
     }
 
+    #[test]
+    fn test_repo_section_overrides_global_for_selected_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let global = dir.path().join("global.toml");
+        let local = dir.path().join("local.toml");
+
+        fs::write(
+            &global,
+            r#"
+[diff]
+theme = "base16-ocean.dark"
+tab_width = 8
+
+[ai]
+reviewer = "claude"
+max_iterations = 10
+
+[repos."owner/app"]
+read_only = true
+
+[repos."owner/app".diff]
+theme = "Dracula"
+
+[repos."owner/app".ai]
+reviewer = "codex"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_paths_for_repo(
+            &global,
+            &local,
+            dir.path().to_path_buf(),
+            Some("Owner/App"),
+        )
+        .unwrap();
+        assert_eq!(config.loaded_repo_section.as_deref(), Some("Owner/App"));
+        assert!(config.read_only);
+        assert_eq!(config.diff.theme, "Dracula");
+        assert_eq!(config.ai.reviewer, "codex");
+        // セクションにないキーはグローバルの値
+        assert_eq!(config.diff.tab_width, 8);
+        assert_eq!(config.ai.max_iterations, 10);
+
+        // 別 repo ではセクションを適用しない
+        let other = Config::load_from_paths_for_repo(
+            &global,
+            &local,
+            dir.path().to_path_buf(),
+            Some("owner/other"),
+        )
+        .unwrap();
+        assert!(other.loaded_repo_section.is_none());
+        assert!(!other.read_only);
+        assert_eq!(other.diff.theme, "base16-ocean.dark");
+        assert_eq!(other.ai.reviewer, "claude");
+    }
+
+    #[test]
+    fn test_theme_light_dark_selected_by_terminal_background() {
+        use crate::syntax::TerminalBackground;
+
+        let config: Config = toml::from_str(
+            r#"
+[diff]
+theme = "Dracula"
+theme_light = "InspiredGitHub"
+theme_dark = "base16-eighties.dark"
+"#,
+        )
+        .unwrap();
+
+        let mut light = config.diff.clone();
+        light.apply_terminal_background(Some(TerminalBackground::Light));
+        assert_eq!(light.theme, "InspiredGitHub");
+
+        let mut dark = config.diff.clone();
+        dark.apply_terminal_background(Some(TerminalBackground::Dark));
+        assert_eq!(dark.theme, "base16-eighties.dark");
+
+        // 判定できなければ `theme` のまま
+        let mut unknown = config.diff.clone();
+        unknown.apply_terminal_background(None);
+        assert_eq!(unknown.theme, "Dracula");
+
+        // 片方だけ設定されている場合、もう一方の背景では `theme` を使う
+        let mut only_light = DiffConfig {
+            theme_dark: None,
+            ..config.diff
+        };
+        only_light.apply_terminal_background(Some(TerminalBackground::Dark));
+        assert_eq!(only_light.theme, "Dracula");
+    }
+
+    #[test]
+    fn test_local_config_overrides_repo_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let global = dir.path().join("global.toml");
+        let local = dir.path().join("local.toml");
+
+        fs::write(
+            &global,
+            r#"
+[repos."owner/app".diff]
+theme = "Dracula"
+tab_width = 2
+"#,
+        )
+        .unwrap();
+        fs::write(
+            &local,
+            r#"
+[diff]
+theme = "InspiredGitHub"
+
+[repos."owner/app".diff]
+tab_width = 6
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_paths_for_repo(
+            &global,
+            &local,
+            dir.path().to_path_buf(),
+            Some("owner/app"),
+        )
+        .unwrap();
+        assert_eq!(config.diff.theme, "InspiredGitHub");
+        // ローカル設定の repos セクションは無視される
+        assert_eq!(config.diff.tab_width, 2);
+    }
+
     #[test]
     fn test_deep_merge_nested_table_partial_override() {
         let dir = tempfile::tempdir().unwrap();
@@ -661,6 +981,27 @@ tab_width = 0
         assert!(config.editor.is_none());
     }
 
+    #[test]
+    fn test_local_formatters_are_stripped() {
+        let dir = tempfile::tempdir().unwrap();
+        let global = dir.path().join("global.toml");
+        let local = dir.path().join("local.toml");
+
+        fs::write(&global, "[formatters]\nrs = \"rustfmt --emit stdout\"\n").unwrap();
+        fs::write(
+            &local,
+            "[formatters]\nrs = \"malicious\"\npy = \"malicious\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_paths(&global, &local, dir.path().to_path_buf()).unwrap();
+        assert_eq!(
+            config.formatters.get("rs").map(String::as_str),
+            Some("rustfmt --emit stdout")
+        );
+        assert!(!config.formatters.contains_key("py"));
+    }
+
     #[test]
     fn test_local_overrides_tracks_ai_keys() {
         let dir = tempfile::tempdir().unwrap();
@@ -922,6 +1263,24 @@ timeout_secs = 999999
         assert_eq!(config.ai.timeout_secs, 7200);
     }
 
+    #[test]
+    fn test_auto_refresh_secs_raised_to_minimum() {
+        let dir = tempfile::tempdir().unwrap();
+        let global = dir.path().join("global.toml");
+        let local = dir.path().join("local.toml");
+
+        let config = Config::load_from_paths(&global, &local, dir.path().to_path_buf()).unwrap();
+        assert_eq!(config.auto_refresh_secs, 0, "disabled by default");
+
+        fs::write(&global, "auto_refresh_secs = 3\n").unwrap();
+        let config = Config::load_from_paths(&global, &local, dir.path().to_path_buf()).unwrap();
+        assert_eq!(config.auto_refresh_secs, MIN_AUTO_REFRESH_SECS);
+
+        fs::write(&global, "auto_refresh_secs = 120\n").unwrap();
+        let config = Config::load_from_paths(&global, &local, dir.path().to_path_buf()).unwrap();
+        assert_eq!(config.auto_refresh_secs, 120);
+    }
+
     #[test]
     fn test_normal_iterations_and_timeout_not_clamped() {
         let dir = tempfile::tempdir().unwrap();
@@ -1033,6 +1392,64 @@ timeout_secs = 3600
             "mark_viewed",
             "mark_viewed_dir",
             "tree_toggle",
+            "open_blame_in_browser",
+            "open_history_in_browser",
+            "jump_to_base_pr",
+            "toggle_hunk_filter",
+            "symbol_digest",
+            "toggle_line_endings",
+            "first_comment",
+            "last_comment",
+            "toggle_pending_review",
+            "next_change",
+            "prev_change",
+            "toggle_highlighting",
+            "collapse_all_threads",
+            "expand_all_threads",
+            "toggle_thread",
+            "peek_split_hunk",
+            "reload_current_file",
+            "filter_added_files",
+            "filter_deleted_files",
+            "filter_modified_files",
+            "filter_renamed_files",
+            "toggle_hunk",
+            "toggle_resolved",
+            "toggle_diff_layout",
+            "scroll_left",
+            "scroll_right",
+            "suggestion_in_editor",
+            "search_in_diff",
+            "show_blame",
+            "filter_files",
+            "open_all_files_in_editor",
+            "yank_line",
+            "yank_hunk",
+            "review_submit",
+            "next_change_block",
+            "prev_change_block",
+            "reload_config",
+            "set_diff_base",
+            "toggle_wrap",
+            "add_reaction",
+            "toggle_unchanged",
+            "pr_list_filters",
+            "expand_context",
+            "reset_context",
+            "toggle_whitespace",
+            "request_reviewer",
+            "add_assignee",
+            "yank_permalink",
+            "choose_theme",
+            "suggest_format",
+            "sort_files",
+            "commit_rally",
+            "rally_drop_comment",
+            "next_file_with_comments",
+            "prev_file_with_comments",
+            "next_conflict",
+            "retry_iteration",
+            "ai_explain_hunk",
         ];
 
         for field in &expected_fields {
@@ -1080,6 +1497,31 @@ timeout_secs = 3600
         );
     }
 
+    /// bindings() が全フィールドを含み、各アクションにヘルプ用のメタデータがあることを検証。
+    #[test]
+    fn test_all_bindings_have_help_metadata() {
+        let config = KeybindingsConfig::default();
+        let serialized = toml::to_string(&config).unwrap();
+        let names: Vec<&str> = config.bindings().iter().map(|(name, _)| *name).collect();
+
+        for line in serialized.lines() {
+            if let Some((key, _)) = line.split_once(" = ") {
+                assert!(
+                    names.contains(&key.trim()),
+                    "keybinding '{}' is missing from bindings()",
+                    key.trim()
+                );
+            }
+        }
+        for name in &names {
+            assert!(
+                crate::keybinding::action_meta(name).is_some(),
+                "keybinding '{}' has no help metadata in action_meta()",
+                name
+            );
+        }
+    }
+
     #[test]
     fn test_zen_mode_deserialization() {
         let toml = r#"
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::syntax::TerminalBackground;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AiConfig {
@@ -7,6 +9,9 @@ pub struct AiConfig {
     pub reviewee: String,
     pub max_iterations: u32,
     pub timeout_secs: u64,
+    /// Seconds without a finished agent call before the AI Rally status turns
+    /// into a warning (the call keeps running until `timeout_secs`).
+    pub stall_warning_secs: u64,
     /// Custom prompt directory (default: ~/.config/octorus/prompts/)
     pub prompt_dir: Option<String>,
     /// Additional tools for reviewer (Claude adapter only).
@@ -63,6 +68,35 @@ pub struct AiConfig {
     /// - `None`: never post proposals; they remain in session history only.
     #[serde(default)]
     pub post_reviewee_proposals: ProposalPostStrategy,
+    /// Whether reviewer inline comments are posted as they are generated.
+    ///
+    /// - `dry_run` (default): inline comments are held in the AI Rally view
+    ///   for review and posted only on `commit_rally`.
+    /// - `post`: inline comments are posted to the PR right after each review.
+    ///
+    /// Headless mode always uses `post`.
+    #[serde(default)]
+    pub rally_mode: RallyMode,
+}
+
+/// How AI Rally handles the reviewer's inline comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RallyMode {
+    /// Hold inline comments in memory until the user commits them.
+    #[default]
+    DryRun,
+    /// Post inline comments immediately.
+    Post,
+}
+
+impl RallyMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DryRun => "dry_run",
+            Self::Post => "post",
+        }
+    }
 }
 
 /// When to post reviewee proposals to the PR.
@@ -85,6 +119,7 @@ impl Default for AiConfig {
             reviewee: "claude".to_owned(),
             max_iterations: 10,
             timeout_secs: 600,
+            stall_warning_secs: 120,
             prompt_dir: None,
             reviewer_additional_tools: Vec::new(),
             reviewee_additional_tools: Vec::new(),
@@ -92,10 +127,23 @@ impl Default for AiConfig {
             auto_post: false,
             review_only: false,
             post_reviewee_proposals: ProposalPostStrategy::default(),
+            rally_mode: RallyMode::default(),
         }
     }
 }
 
+/// `[diff_colors]`: diff 行の記号・背景色と hunk ヘッダの色（シンタックスの色とは独立）。
+/// 色名（`green`, `lightred` など）か `#rrggbb`。未設定・不正な値は既定色を使う。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiffColorsConfig {
+    pub added_fg: Option<String>,
+    pub added_bg: Option<String>,
+    pub removed_fg: Option<String>,
+    pub removed_bg: Option<String>,
+    pub header_fg: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct GitOpsConfig {
@@ -120,11 +168,128 @@ impl Default for GitOpsConfig {
 #[serde(default)]
 pub struct DiffConfig {
     pub theme: String,
+    /// 明るい背景の端末で使うテーマ（`COLORFGBG` で判定できた場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme_light: Option<String>,
+    /// 暗い背景の端末で使うテーマ（`COLORFGBG` で判定できた場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme_dark: Option<String>,
     #[serde(deserialize_with = "deserialize_tab_width")]
     pub tab_width: u8,
     /// 追加/削除行に背景色を表示するかどうか
     #[serde(default = "default_true")]
     pub bg_color: bool,
+    /// 追加行内のレビューマーカー（TODO/FIXME 等）を強調表示するかどうか
+    #[serde(default)]
+    pub highlight_markers: bool,
+    /// 強調表示するマーカー語（大文字小文字を区別、単語単位で一致）
+    #[serde(default = "default_marker_words")]
+    pub marker_words: Vec<String>,
+    /// マーカーの前景色（ratatui の色名または `#rrggbb`）
+    #[serde(default = "default_marker_color")]
+    pub marker_color: String,
+    /// マーカーを太字で表示するかどうか
+    #[serde(default = "default_true")]
+    pub marker_bold: bool,
+    /// 空白文字の可視化（`none` / `trailing` / `all`）
+    #[serde(default)]
+    pub show_whitespace: ShowWhitespace,
+    /// 追加行の行末の空白を赤い背景で強調するかどうか
+    #[serde(default)]
+    pub highlight_trailing_whitespace: bool,
+    /// 追加行のインデント中のタブを赤い背景で強調するかどうか
+    #[serde(default)]
+    pub highlight_tab_indent: bool,
+    /// 改行コードのみの変更（CRLF↔LF）の hunk を 1 行の要約に折りたたむかどうか
+    #[serde(default = "default_true")]
+    pub collapse_line_endings: bool,
+    /// 変更行の間にある長い文脈行の連続を 1 行のプレースホルダに折りたたむかどうか
+    #[serde(default)]
+    pub collapse_unchanged: bool,
+    /// 折りたたみ時に変更行の前後へ残す文脈行数
+    #[serde(default = "default_context_lines")]
+    pub context_lines: usize,
+    /// コメント間移動（next/prev）がファイル末尾・先頭で、
+    /// コメント付きファイル間の移動（`]C`/`[C`）が一覧の末尾・先頭で反対側へ折り返すかどうか
+    #[serde(default)]
+    pub wrap_comment_navigation: bool,
+    /// 変更単位の移動（next/prev change）が PR の末尾・先頭で、
+    /// ファイル内の変更ブロック移動（`]c`/`[c`）がファイルの末尾・先頭で反対側へ折り返すかどうか
+    #[serde(default)]
+    pub wrap_change_navigation: bool,
+    /// diff 行に含まれる ANSI エスケープ（色・太字）をスタイルとして表示するかどうか
+    #[serde(default)]
+    pub render_ansi_content: bool,
+    /// コメント入力画面に、コメントが付く行（行番号・LEFT/RIGHT・行内容）を表示するかどうか
+    #[serde(default = "default_true")]
+    pub comment_anchor_preview: bool,
+    /// diff 内検索（`/`）で大文字小文字を区別するかどうか
+    #[serde(default)]
+    pub search_case_sensitive: bool,
+    /// 行番号ガター（`none` / `new` / `both`）
+    #[serde(default)]
+    pub gutter: Gutter,
+    /// ガターの行番号の数え方（`absolute` / `relative` / `hybrid`）
+    #[serde(default)]
+    pub line_numbers: LineNumbers,
+    /// 生成ファイルとして一覧の「Generated」グループに折りたたむパスの glob
+    /// （`.gitattributes` の `linguist-generated` に加えて使う）
+    #[serde(default)]
+    pub generated_globs: Vec<String>,
+}
+
+/// Which whitespace characters to render as visible glyphs in the diff view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ShowWhitespace {
+    /// Render whitespace as-is.
+    #[default]
+    None,
+    /// Only whitespace at the end of a line.
+    Trailing,
+    /// Every space and tab in the line content.
+    All,
+}
+
+/// Line-number columns drawn in front of each diff line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Gutter {
+    /// No line numbers.
+    #[default]
+    None,
+    /// New-file line numbers only.
+    New,
+    /// Old and new line numbers, like GitHub's web view.
+    Both,
+}
+
+/// How the gutter numbers lines, like vim's `number` / `relativenumber`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LineNumbers {
+    /// File line numbers.
+    #[default]
+    Absolute,
+    /// Distance from the cursor line.
+    Relative,
+    /// File line numbers on the cursor line, distance elsewhere.
+    Hybrid,
+}
+
+fn default_marker_words() -> Vec<String> {
+    ["TODO", "FIXME", "HACK", "XXX"]
+        .iter()
+        .map(|w| (*w).to_owned())
+        .collect()
+}
+
+fn default_marker_color() -> String {
+    "yellow".to_owned()
+}
+
+fn default_context_lines() -> usize {
+    3
 }
 
 fn default_true() -> bool {
@@ -144,8 +309,44 @@ impl Default for DiffConfig {
     fn default() -> Self {
         Self {
             theme: "base16-ocean.dark".to_owned(),
+            theme_light: None,
+            theme_dark: None,
             tab_width: 4,
             bg_color: true,
+            highlight_markers: false,
+            marker_words: default_marker_words(),
+            marker_color: default_marker_color(),
+            marker_bold: true,
+            show_whitespace: ShowWhitespace::default(),
+            highlight_trailing_whitespace: false,
+            highlight_tab_indent: false,
+            collapse_line_endings: true,
+            collapse_unchanged: false,
+            context_lines: default_context_lines(),
+            wrap_comment_navigation: false,
+            wrap_change_navigation: false,
+            render_ansi_content: false,
+            comment_anchor_preview: true,
+            search_case_sensitive: false,
+            gutter: Gutter::default(),
+            line_numbers: LineNumbers::default(),
+            generated_globs: Vec::new(),
+        }
+    }
+}
+
+impl DiffConfig {
+    /// 端末の背景に合わせて `theme_light` / `theme_dark` を `theme` に反映する。
+    ///
+    /// 背景が判定できない、または対応するテーマが未設定なら `theme` をそのまま使う。
+    pub fn apply_terminal_background(&mut self, background: Option<TerminalBackground>) {
+        let selected = match background {
+            Some(TerminalBackground::Light) => self.theme_light.as_ref(),
+            Some(TerminalBackground::Dark) => self.theme_dark.as_ref(),
+            None => None,
+        };
+        if let Some(theme) = selected {
+            self.theme = theme.clone();
         }
     }
 }
@@ -213,3 +414,63 @@ impl Default for ShellConfig {
         }
     }
 }
+
+const DEFAULT_SESSION_AUTO_SAVE_SECS: u64 = 30;
+
+/// クラッシュ復旧用のレビューセッション自動保存設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// 操作が止まってから保存するまでの秒数（0 で無効）
+    pub auto_save_secs: u64,
+    /// ステータスバーにレビュー時間を表示し、終了時に統計を出す
+    pub review_timer: bool,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            auto_save_secs: DEFAULT_SESSION_AUTO_SAVE_SECS,
+            review_timer: false,
+        }
+    }
+}
+
+pub const DEFAULT_GH_RETRIES: u32 = 3;
+pub const DEFAULT_GH_RETRY_BASE_DELAY_MS: u64 = 500;
+
+/// How octorus talks to GitHub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GithubBackendKind {
+    /// Shell out to the `gh` CLI.
+    #[default]
+    Gh,
+    /// Call the REST API directly with `GITHUB_TOKEN`.
+    Rest,
+}
+
+/// `gh` コマンドのリトライ設定（レート制限時。読み取りはタイムアウト時も）と接続方法、CI チェックの表示
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GithubConfig {
+    /// 最大リトライ回数（0 で無効）
+    pub retries: u32,
+    /// 初回リトライまでの待ち時間（ミリ秒）。以降は倍々に延び、最大 50% のジッタが付く
+    pub retry_base_delay_ms: u64,
+    /// GitHub との通信に使うバックエンド
+    pub backend: GithubBackendKind,
+    /// ヘッダに CI チェックの状態別件数（`checks: 5✓ 1✗ 2•`）を表示する
+    pub show_checks: bool,
+}
+
+impl Default for GithubConfig {
+    fn default() -> Self {
+        Self {
+            retries: DEFAULT_GH_RETRIES,
+            retry_base_delay_ms: DEFAULT_GH_RETRY_BASE_DELAY_MS,
+            backend: GithubBackendKind::default(),
+            show_checks: true,
+        }
+    }
+}
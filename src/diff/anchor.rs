@@ -0,0 +1,189 @@
+//! Resolution of the line a review comment attaches to.
+//!
+//! GitHub anchors a comment to a diff `position`; this maps it back to the
+//! file line and side so the composer can show exactly where it will land.
+
+use serde::{Deserialize, Serialize};
+
+use super::{LineType, PatchIndex, PatchLineInfo};
+
+/// Side of the diff a comment attaches to (GitHub API `side`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CommentSide {
+    /// Old file (removed lines)
+    Left,
+    /// New file (added and context lines)
+    #[default]
+    Right,
+}
+
+impl CommentSide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Left => "LEFT",
+            Self::Right => "RIGHT",
+        }
+    }
+}
+
+/// Target line of a comment at a given diff position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentAnchor {
+    /// Line number in the old file for `Left`, in the new file for `Right`.
+    pub line: u32,
+    pub side: CommentSide,
+    /// Line content without the diff prefix.
+    pub content: String,
+}
+
+/// Resolve the line a comment at `diff_position` attaches to.
+///
+/// Added and context lines anchor to the new file (`RIGHT`); removed lines
+/// anchor to the old file (`LEFT`) via their old line number. Hunk headers
+/// and unknown positions return `None`.
+pub fn resolve_comment_anchor(patch: &str, diff_position: u32) -> Option<CommentAnchor> {
+    let index = PatchIndex::build(patch);
+    let info = (0..index.len())
+        .filter_map(|i| index.get(i))
+        .find(|info| info.diff_position == Some(diff_position))?;
+    let (line, side) = line_anchor(info)?;
+    Some(CommentAnchor {
+        line,
+        side,
+        content: info.content.to_string(),
+    })
+}
+
+fn line_anchor(info: &PatchLineInfo<'_>) -> Option<(u32, CommentSide)> {
+    match info.line_type {
+        LineType::Header | LineType::Meta => None,
+        line_type if line_type.in_new_file() => Some((info.new_line_number?, CommentSide::Right)),
+        _ => Some((info.old_line_number?, CommentSide::Left)),
+    }
+}
+
+/// Find where a comment made against an earlier version of a patch lands in `patch`.
+///
+/// `line` and `side` are where the comment was written; `content` is that line's
+/// text when it is known. The line is kept when it is still in the patch with the same
+/// text; otherwise the nearest line on the same side with identical text is used.
+/// Returns the (diff position, line) to post at, or `None` when the line is gone.
+pub fn relocate_comment_anchor(
+    patch: &str,
+    line: u32,
+    side: CommentSide,
+    content: Option<&str>,
+) -> Option<(u32, u32)> {
+    let index = PatchIndex::build(patch);
+    let candidates: Vec<(u32, u32, &str)> = (0..index.len())
+        .filter_map(|i| index.get(i))
+        .filter_map(|info| {
+            let (info_line, info_side) = line_anchor(info)?;
+            (info_side == side).then_some((info.diff_position?, info_line, info.content))
+        })
+        .collect();
+    let matches = |text: &str| content.is_none_or(|content| content == text);
+    if let Some(&(position, line, _)) = candidates
+        .iter()
+        .find(|(_, l, text)| *l == line && matches(text))
+    {
+        return Some((position, line));
+    }
+    let content = content?;
+    candidates
+        .iter()
+        .filter(|(_, _, text)| *text == content)
+        .min_by_key(|(_, l, _)| l.abs_diff(line))
+        .map(|&(position, line, _)| (position, line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "\
+@@ -10,4 +10,4 @@ fn main() {
+ let a = 1;
+-let b = 2;
++let b = 3;
+ let c = 4;
+@@ -30,2 +30,3 @@
+ tail();
++added();
+-removed();";
+
+    #[test]
+    fn test_resolve_comment_anchor_added_context_removed() {
+        // context: 新ファイルの行番号で RIGHT
+        let anchor = resolve_comment_anchor(PATCH, 1).unwrap();
+        assert_eq!(
+            (anchor.line, anchor.side, anchor.content.as_str()),
+            (10, CommentSide::Right, "let a = 1;")
+        );
+
+        // removed: 旧ファイルの行番号で LEFT
+        let anchor = resolve_comment_anchor(PATCH, 2).unwrap();
+        assert_eq!(
+            (anchor.line, anchor.side, anchor.content.as_str()),
+            (11, CommentSide::Left, "let b = 2;")
+        );
+
+        // added: 削除行の直後でも新ファイル側の行番号
+        let anchor = resolve_comment_anchor(PATCH, 3).unwrap();
+        assert_eq!(
+            (anchor.line, anchor.side, anchor.content.as_str()),
+            (11, CommentSide::Right, "let b = 3;")
+        );
+
+        // 2 つ目の hunk: 境界の header は解決しない
+        assert_eq!(resolve_comment_anchor(PATCH, 5), None);
+        assert_eq!(resolve_comment_anchor(PATCH, 7).unwrap().line, 31);
+        let anchor = resolve_comment_anchor(PATCH, 8).unwrap();
+        assert_eq!((anchor.line, anchor.side), (31, CommentSide::Left));
+        assert_eq!(resolve_comment_anchor(PATCH, 99), None);
+    }
+
+    #[test]
+    fn test_relocate_comment_anchor_after_patch_change() {
+        // 変わっていなければそのまま
+        assert_eq!(
+            relocate_comment_anchor(PATCH, 11, CommentSide::Right, Some("let b = 3;")),
+            Some((3, 11))
+        );
+        assert_eq!(
+            relocate_comment_anchor(PATCH, 11, CommentSide::Left, Some("let b = 2;")),
+            Some((2, 11))
+        );
+
+        // force-push で上に 2 行増えた: 同じ内容の行へ付け直す
+        let pushed = "\
+@@ -10,4 +10,6 @@ fn main() {
++// one
++// two
+ let a = 1;
+-let b = 2;
++let b = 3;
+ let c = 4;";
+        assert_eq!(
+            relocate_comment_anchor(pushed, 11, CommentSide::Right, Some("let b = 3;")),
+            Some((5, 13))
+        );
+
+        // 行が消えた・内容が変わった場合は付け直せない
+        let rewritten = "@@ -10,2 +10,2 @@\n let a = 1;\n-let b = 2;\n+let b = 4;";
+        assert_eq!(
+            relocate_comment_anchor(rewritten, 11, CommentSide::Right, Some("let b = 3;")),
+            None
+        );
+        // 内容が分からない古い控えは、同じ行が diff に残っていれば付け直す
+        assert_eq!(
+            relocate_comment_anchor(rewritten, 11, CommentSide::Right, None),
+            Some((3, 11))
+        );
+        assert_eq!(
+            relocate_comment_anchor(rewritten, 40, CommentSide::Right, None),
+            None
+        );
+    }
+}
@@ -0,0 +1,54 @@
+/// Change category of a file in a diff, used to narrow the file list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileCategory {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+}
+
+impl FileCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Deleted => "deleted",
+            Self::Modified => "modified",
+            Self::Renamed => "renamed",
+        }
+    }
+}
+
+/// Classify a changed file into a [`FileCategory`].
+///
+/// Git extended headers in the patch (`new file mode`, `deleted file mode`,
+/// `rename from`) take precedence. GitHub API patches carry no headers, so
+/// the API `status` is consulted next. As a last resort, a patch whose only
+/// hunk starts at `-0,0` (`+0,0`) is treated as an added (deleted) file.
+pub fn categorize_file(status: &str, patch: Option<&str>) -> FileCategory {
+    let patch = patch.unwrap_or_default();
+    for line in patch.lines().take_while(|l| !l.starts_with("@@")) {
+        if line.starts_with("new file mode") {
+            return FileCategory::Added;
+        }
+        if line.starts_with("deleted file mode") {
+            return FileCategory::Deleted;
+        }
+        if line.starts_with("rename from") {
+            return FileCategory::Renamed;
+        }
+    }
+
+    match status {
+        "added" => return FileCategory::Added,
+        "removed" => return FileCategory::Deleted,
+        "renamed" => return FileCategory::Renamed,
+        _ => {}
+    }
+
+    let mut hunks = patch.lines().filter(|l| l.starts_with("@@"));
+    match (hunks.next(), hunks.next()) {
+        (Some(header), None) if header.starts_with("@@ -0,0 ") => FileCategory::Added,
+        (Some(header), None) if header.contains(" +0,0 @@") => FileCategory::Deleted,
+        _ => FileCategory::Modified,
+    }
+}
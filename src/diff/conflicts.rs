@@ -0,0 +1,93 @@
+//! Merge conflict marker detection for added and context diff lines.
+//!
+//! Only the markers git writes are recognized: seven `<`, `|`, `=` or `>`
+//! at the start of the line, followed by the end of the line or a space and
+//! a label (`<<<<<<< HEAD`). Longer runs such as a Markdown `========`
+//! underline are not markers.
+
+use super::{classify_hunk_line, LineType};
+
+/// Whether `content` (a line without its diff prefix) is a conflict marker.
+pub fn is_conflict_marker(content: &str) -> bool {
+    let content = content.strip_suffix('\r').unwrap_or(content);
+    if content == "=======" {
+        return true;
+    }
+    ["<<<<<<<", "|||||||", ">>>>>>>"].iter().any(|marker| {
+        content
+            .strip_prefix(marker)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+    })
+}
+
+/// Indices of the added and context lines in `patch` that are conflict markers.
+///
+/// Removed lines are ignored: a marker being deleted is a resolved conflict.
+pub fn conflict_marker_lines(patch: &str) -> Vec<usize> {
+    let mut combined = false;
+    patch
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let (line_type, content) = classify_hunk_line(line, &mut combined);
+            matches!(line_type.two_way(), LineType::Added | LineType::Context)
+                && is_conflict_marker(content)
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// The first conflict marker after `line`, wrapping around to the first one.
+///
+/// `conflicts` must be sorted, as returned by [`conflict_marker_lines`].
+pub fn next_conflict_line(conflicts: &[usize], line: usize) -> Option<usize> {
+    let next = conflicts.partition_point(|&l| l <= line);
+    conflicts.get(next).or(conflicts.first()).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_conflict_marker() {
+        assert!(is_conflict_marker("<<<<<<< HEAD"));
+        assert!(is_conflict_marker("<<<<<<<"));
+        assert!(is_conflict_marker("||||||| merged common ancestors"));
+        assert!(is_conflict_marker("======="));
+        assert!(is_conflict_marker("=======\r"));
+        assert!(is_conflict_marker(">>>>>>> feature/login"));
+
+        assert!(!is_conflict_marker("========"));
+        assert!(!is_conflict_marker("<<<<<<<< HEAD"));
+        assert!(!is_conflict_marker(" <<<<<<< HEAD"));
+        assert!(!is_conflict_marker("a >>>>>>> b"));
+        assert!(!is_conflict_marker(""));
+    }
+
+    #[test]
+    fn test_conflict_marker_lines_skips_removed_lines() {
+        let patch = "\
+@@ -1,3 +1,7 @@
+ fn main() {
++<<<<<<< HEAD
++    run();
++=======
++    start();
+->>>>>>> old
++>>>>>>> feature
+ }";
+        assert_eq!(conflict_marker_lines(patch), vec![2, 4, 7]);
+        assert!(conflict_marker_lines("@@ -1 +1 @@\n-a\n+b").is_empty());
+    }
+
+    #[test]
+    fn test_next_conflict_line_wraps_around() {
+        let conflicts = [2, 4, 7];
+        assert_eq!(next_conflict_line(&conflicts, 0), Some(2));
+        assert_eq!(next_conflict_line(&conflicts, 2), Some(4));
+        assert_eq!(next_conflict_line(&conflicts, 5), Some(7));
+        assert_eq!(next_conflict_line(&conflicts, 7), Some(2));
+        assert_eq!(next_conflict_line(&[], 3), None);
+    }
+}
@@ -0,0 +1,649 @@
+use std::ops::Range;
+
+use super::{classify_hunk_line, LineType};
+
+/// A hunk of a patch and whether it matched a hunk-filter query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkMatch {
+    /// Patch line indices from the `@@` header up to (excluding) the next header.
+    pub range: Range<usize>,
+    pub matched: bool,
+}
+
+/// Split `patch` into hunks and mark those whose added or removed lines
+/// contain `query` (case-insensitive). Context lines are not searched.
+///
+/// Lines before the first `@@` header belong to no hunk and are never hidden.
+pub fn match_hunks(patch: &str, query: &str) -> Vec<HunkMatch> {
+    let query = query.to_lowercase();
+    let mut hunks: Vec<HunkMatch> = Vec::new();
+    let mut combined = false;
+    for (i, line) in patch.lines().enumerate() {
+        let (line_type, content) = classify_hunk_line(line, &mut combined);
+        if line.starts_with("@@") {
+            hunks.push(HunkMatch {
+                range: i..i + 1,
+                matched: false,
+            });
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+        hunk.range.end = i + 1;
+        if hunk.matched || query.is_empty() {
+            continue;
+        }
+        if matches!(line_type.two_way(), LineType::Added | LineType::Removed)
+            && content.to_lowercase().contains(&query)
+        {
+            hunk.matched = true;
+        }
+    }
+    hunks
+}
+
+/// Patch line indices of every `@@` hunk header.
+pub fn hunk_header_lines(patch: &str) -> Vec<usize> {
+    patch
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("@@"))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Raw patch lines (header included) of the hunk containing `line_index`.
+///
+/// Returns `None` when the index is before the first `@@` header or out of
+/// range.
+pub fn hunk_lines_at(patch: &str, line_index: usize) -> Option<Vec<&str>> {
+    let hunk = match_hunks(patch, "")
+        .into_iter()
+        .find(|hunk| hunk.range.contains(&line_index))?;
+    Some(
+        patch
+            .lines()
+            .skip(hunk.range.start)
+            .take(hunk.range.len())
+            .collect(),
+    )
+}
+
+/// Find the next (or previous) change stop across the whole PR.
+///
+/// `stops[f]` holds the sorted line indices to stop at in file `f`. From
+/// `(file, line)`, moves to the nearest stop after (`forward`) or before the
+/// current line in the same file, then to the first (or last) stop of the
+/// closest file that has any. With `wrap`, the walk continues from the other
+/// end of the PR; otherwise `None` is returned at the boundary.
+pub fn next_change_stop(
+    stops: &[Vec<usize>],
+    file: usize,
+    line: usize,
+    forward: bool,
+    wrap: bool,
+) -> Option<(usize, usize)> {
+    let in_file = stops.get(file).and_then(|lines| {
+        if forward {
+            lines.iter().find(|&&l| l > line)
+        } else {
+            lines.iter().rev().find(|&&l| l < line)
+        }
+    });
+    if let Some(&l) = in_file {
+        return Some((file, l));
+    }
+
+    let count = stops.len();
+    let edge = |f: usize| {
+        let lines = &stops[f];
+        if forward { lines.first() } else { lines.last() }.map(|&l| (f, l))
+    };
+    let others: Vec<usize> = match (forward, wrap) {
+        (true, false) => (file + 1..count).collect(),
+        (false, false) => (0..file.min(count)).rev().collect(),
+        (true, true) => (1..=count).map(|step| (file + step) % count).collect(),
+        (false, true) => (1..=count)
+            .map(|step| (file + count - step) % count)
+            .collect(),
+    };
+    for f in others {
+        if f == file {
+            // 一周して戻ってきた: 現在のファイルの反対側の端
+            return edge(f).filter(|&(_, l)| l != line);
+        }
+        if let Some(stop) = edge(f) {
+            return Some(stop);
+        }
+    }
+    None
+}
+
+/// Line indices where a hunk header or a block of changed lines starts.
+///
+/// Consecutive `+`/`-` lines form one block, so the long context runs between
+/// blocks are skipped. A block right below its `@@` header is covered by the
+/// header's stop.
+pub fn change_block_starts(line_types: impl IntoIterator<Item = LineType>) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut prev = None;
+    for (i, line_type) in line_types.into_iter().enumerate() {
+        // 結合 diff の行は結果から見た追加/削除として扱う
+        let line_type = line_type.two_way();
+        let is_change = matches!(line_type, LineType::Added | LineType::Removed);
+        let block_start = is_change
+            && !matches!(
+                prev,
+                Some(LineType::Added | LineType::Removed | LineType::Header)
+            );
+        if line_type == LineType::Header || block_start {
+            starts.push(i);
+        }
+        prev = Some(line_type);
+    }
+    starts
+}
+
+/// The change block start after (`forward`) or before `line` within one file.
+///
+/// At the ends, `wrap` continues from the other end; otherwise `None` is returned.
+pub fn next_change_block(
+    starts: &[usize],
+    line: usize,
+    forward: bool,
+    wrap: bool,
+) -> Option<usize> {
+    next_change_stop(&[starts.to_vec()], 0, line, forward, wrap).map(|(_, l)| l)
+}
+
+/// Direction of a line-ending-only change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEndingChange {
+    CrlfToLf,
+    LfToCrlf,
+}
+
+impl LineEndingChange {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CrlfToLf => "CRLF→LF",
+            Self::LfToCrlf => "LF→CRLF",
+        }
+    }
+}
+
+/// A hunk whose removed/added lines differ only in their line endings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineEndingHunk {
+    /// Patch line indices of the hunk body (after the `@@` header).
+    pub body: Range<usize>,
+    pub change: LineEndingChange,
+    /// Number of removed/added line pairs.
+    pub pairs: usize,
+}
+
+/// Find hunks whose only change is the line ending (`\r\n` ↔ `\n`).
+///
+/// Removed and added lines are paired in order; every pair must be equal
+/// once a trailing `\r` is stripped, and all pairs must convert in the same
+/// direction. Line indices match `patch.lines()`.
+pub fn line_ending_only_hunks(patch: &str) -> Vec<LineEndingHunk> {
+    fn classify_hunk(
+        body: Range<usize>,
+        removed: &[&str],
+        added: &[&str],
+    ) -> Option<LineEndingHunk> {
+        if removed.is_empty() || removed.len() != added.len() {
+            return None;
+        }
+        let mut change = None;
+        for (old, new) in removed.iter().zip(added) {
+            let pair_change = match (old.strip_suffix('\r'), new.strip_suffix('\r')) {
+                (Some(old), None) if old == *new => LineEndingChange::CrlfToLf,
+                (None, Some(new)) if *old == new => LineEndingChange::LfToCrlf,
+                _ => return None,
+            };
+            if change.is_some_and(|c| c != pair_change) {
+                return None;
+            }
+            change = Some(pair_change);
+        }
+        Some(LineEndingHunk {
+            body,
+            change: change?,
+            pairs: removed.len(),
+        })
+    }
+
+    let mut hunks = Vec::new();
+    let mut body: Option<Range<usize>> = None;
+    let mut removed: Vec<&str> = Vec::new();
+    let mut added: Vec<&str> = Vec::new();
+    let mut combined = false;
+
+    // `lines()` は "\r\n" の \r を落とすため、'\n' で分割して \r を残す
+    for (i, line) in patch.split('\n').enumerate() {
+        let (line_type, _) = classify_hunk_line(line, &mut combined);
+        if line.starts_with("@@") {
+            if let Some(prev) = body.take() {
+                hunks.extend(classify_hunk(prev, &removed, &added));
+            }
+            body = Some(i + 1..i + 1);
+            removed.clear();
+            added.clear();
+            continue;
+        }
+        let Some(ref mut range) = body else {
+            continue;
+        };
+        if line.is_empty() {
+            // 末尾改行による空要素
+            continue;
+        }
+        range.end = i + 1;
+        // 分類結果の内容は末尾の \r が落ちているので、プレフィックスだけ外した内容で比較する
+        let content = line
+            .get(line_type.marker().map_or(1, str::len)..)
+            .unwrap_or_default();
+        match line_type.two_way() {
+            LineType::Removed => removed.push(content),
+            LineType::Added => added.push(content),
+            _ => {}
+        }
+    }
+    if let Some(prev) = body {
+        hunks.extend(classify_hunk(prev, &removed, &added));
+    }
+    hunks
+}
+
+/// One side (`-a,b` or `+c,d`) of a two-way hunk header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HunkSpan {
+    start: usize,
+    count: usize,
+}
+
+impl HunkSpan {
+    fn parse(range: &str) -> Option<Self> {
+        let mut parts = range[1..].splitn(2, ',');
+        let start = parts.next()?.parse().ok()?;
+        let count = match parts.next() {
+            Some(count) => count.parse().ok()?,
+            None => 1,
+        };
+        Some(Self { start, count })
+    }
+
+    /// 1-based line before the hunk (an empty side points at the line it follows)
+    fn before(self) -> usize {
+        if self.count == 0 {
+            self.start
+        } else {
+            self.start - 1
+        }
+    }
+
+    /// 1-based last line covered by the hunk
+    fn end(self) -> usize {
+        self.before() + self.count
+    }
+
+    fn format(self, sign: char) -> String {
+        format!("{}{},{}", sign, self.start, self.count)
+    }
+}
+
+/// `(old, new, trailing section text)` of a two-way `@@ -a,b +c,d @@` header
+fn parse_hunk_spans(header: &str) -> Option<(HunkSpan, HunkSpan, &str)> {
+    let rest = header.strip_prefix("@@ ")?;
+    let end = rest.find(" @@")?;
+    let mut ranges = rest[..end].split_whitespace();
+    let old = HunkSpan::parse(ranges.next().filter(|r| r.starts_with('-'))?)?;
+    let new = HunkSpan::parse(ranges.next().filter(|r| r.starts_with('+'))?)?;
+    if ranges.next().is_some() {
+        return None;
+    }
+    Some((old, new, &rest[end + 3..]))
+}
+
+/// Splice up to `extra` unchanged lines of the new file `source` above and
+/// below the hunk whose `@@` header is at `header_line`, like GitHub's
+/// "expand" arrows.
+///
+/// Expansion stops at the neighbouring hunks and at the file boundaries; the
+/// header's starts and counts are adjusted to match. Returns `None` when the
+/// line is not a two-way hunk header or there is nothing left to add.
+pub fn expand_hunk_context(
+    patch: &str,
+    header_line: usize,
+    source: &[&str],
+    extra: usize,
+) -> Option<String> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let headers = hunk_header_lines(patch);
+    let pos = headers.iter().position(|&h| h == header_line)?;
+    let (old, new, section) = parse_hunk_spans(lines[header_line])?;
+
+    let prev_end = match pos.checked_sub(1) {
+        Some(prev) => parse_hunk_spans(lines[headers[prev]])?.1.end(),
+        None => 0,
+    };
+    let next_start = match headers.get(pos + 1) {
+        Some(&next) => parse_hunk_spans(lines[next])?.1.before(),
+        None => source.len(),
+    };
+    let above = extra.min(new.before().saturating_sub(prev_end));
+    let below = extra.min(next_start.saturating_sub(new.end()));
+    if above + below == 0 || new.end() + below > source.len() {
+        return None;
+    }
+
+    let grow = |span: HunkSpan| HunkSpan {
+        start: span.before() + 1 - above,
+        count: span.count + above + below,
+    };
+    let body_end = headers.get(pos + 1).copied().unwrap_or(lines.len());
+    let context = |range: std::ops::Range<usize>| source[range].iter().map(|l| format!(" {}", l));
+
+    let mut out: Vec<String> = lines[..header_line].iter().map(|l| l.to_string()).collect();
+    out.push(format!(
+        "@@ {} {} @@{}",
+        grow(old).format('-'),
+        grow(new).format('+'),
+        section
+    ));
+    out.extend(context(new.before() - above..new.before()));
+    out.extend(
+        lines[header_line + 1..body_end]
+            .iter()
+            .map(|l| l.to_string()),
+    );
+    out.extend(context(new.end()..new.end() + below));
+    out.extend(lines[body_end..].iter().map(|l| l.to_string()));
+
+    let mut expanded = out.join("\n");
+    if patch.ends_with('\n') {
+        expanded.push('\n');
+    }
+    Some(expanded)
+}
+
+/// Find runs of context lines that sit between two changed lines.
+///
+/// Runs touching a hunk header or the end of the patch are not returned:
+/// git already trims those to its own context size. Line indices match
+/// `patch.lines()`.
+pub fn context_runs(patch: &str) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    // 直前の変更行の次の行（変更行の後でなければ None）
+    let mut run_start: Option<usize> = None;
+    let mut combined = false;
+    for (i, line) in patch.lines().enumerate() {
+        match classify_hunk_line(line, &mut combined).0.two_way() {
+            LineType::Context => {}
+            LineType::Added | LineType::Removed => {
+                if let Some(start) = run_start.filter(|&start| start < i) {
+                    runs.push(start..i);
+                }
+                run_start = Some(i + 1);
+            }
+            _ => run_start = None,
+        }
+    }
+    runs
+}
+
+/// Lines of a context run to fold when keeping `context` lines next to each
+/// change. The first line of the range is shown as the placeholder; `None`
+/// when folding would not hide at least one line.
+pub fn fold_context_run(run: &Range<usize>, context: usize) -> Option<Range<usize>> {
+    let fold = run.start + context..run.end.saturating_sub(context);
+    (fold.len() >= 2).then_some(fold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::classify_line;
+
+    #[test]
+    fn test_hunk_lines_at_returns_enclosing_hunk() {
+        let patch = "diff --git a/a.rs b/a.rs\n\
+                     @@ -1,2 +1,2 @@\n\
+                     \x20fn a() {}\n\
+                     -old\n\
+                     +new\n\
+                     @@ -10 +10,2 @@\n\
+                     \x20ctx\n\
+                     +added";
+        let first = vec!["@@ -1,2 +1,2 @@", " fn a() {}", "-old", "+new"];
+        assert_eq!(hunk_lines_at(patch, 1), Some(first.clone()));
+        assert_eq!(hunk_lines_at(patch, 3), Some(first));
+        assert_eq!(
+            hunk_lines_at(patch, 7),
+            Some(vec!["@@ -10 +10,2 @@", " ctx", "+added"])
+        );
+        // 最初の hunk より前・範囲外は対象外
+        assert_eq!(hunk_lines_at(patch, 0), None);
+        assert_eq!(hunk_lines_at(patch, 8), None);
+    }
+
+    #[test]
+    fn test_next_change_stop_walks_hunks_then_files() {
+        // file 1 has no hunks and is skipped
+        let stops = vec![vec![0, 5], vec![], vec![0, 3, 9]];
+        let mut walk = vec![(0, 0)];
+        let mut pos = (0, 0);
+        while let Some(next) = next_change_stop(&stops, pos.0, pos.1, true, false) {
+            walk.push(next);
+            pos = next;
+        }
+        assert_eq!(walk, vec![(0, 0), (0, 5), (2, 0), (2, 3), (2, 9)]);
+
+        // inside a hunk body, prev goes to that hunk's header
+        assert_eq!(next_change_stop(&stops, 2, 4, false, false), Some((2, 3)));
+        assert_eq!(next_change_stop(&stops, 2, 0, false, false), Some((0, 5)));
+        assert_eq!(next_change_stop(&stops, 0, 0, false, false), None);
+    }
+
+    #[test]
+    fn test_next_change_stop_wraps_at_pr_boundary() {
+        let stops = vec![vec![0, 5], vec![2]];
+        assert_eq!(next_change_stop(&stops, 1, 2, true, true), Some((0, 0)));
+        assert_eq!(next_change_stop(&stops, 0, 0, false, true), Some((1, 2)));
+        assert_eq!(next_change_stop(&stops, 1, 2, true, false), None);
+
+        // single hunk: wrapping has nowhere else to go
+        let single = vec![vec![4]];
+        assert_eq!(next_change_stop(&single, 0, 4, true, true), None);
+        assert_eq!(next_change_stop(&single, 0, 7, true, true), Some((0, 4)));
+    }
+
+    const MULTI_HUNK_PATCH: &str = "\
+@@ -1,6 +1,6 @@
+ a
+-b
++B
+ c
+ d
+ e
++f
+@@ -20,3 +20,3 @@
+-x
++X
+ y";
+
+    fn multi_hunk_starts() -> Vec<usize> {
+        change_block_starts(MULTI_HUNK_PATCH.lines().map(|l| classify_line(l).0))
+    }
+
+    #[test]
+    fn test_change_block_starts_skips_context_runs() {
+        // 0: @@, 2-3: -b/+B, 7: +f, 8: @@（直後の -x/+X はヘッダで代表）
+        assert_eq!(multi_hunk_starts(), vec![0, 2, 7, 8]);
+    }
+
+    #[test]
+    fn test_next_change_block_after_line() {
+        let starts = multi_hunk_starts();
+        assert_eq!(next_change_block(&starts, 0, true, false), Some(2));
+        assert_eq!(next_change_block(&starts, 3, true, false), Some(7));
+        assert_eq!(next_change_block(&starts, 7, true, false), Some(8));
+        assert_eq!(next_change_block(&starts, 8, true, false), None);
+        assert_eq!(next_change_block(&starts, 8, true, true), Some(0));
+
+        assert_eq!(next_change_block(&starts, 5, false, false), Some(2));
+        assert_eq!(next_change_block(&starts, 0, false, false), None);
+        assert_eq!(next_change_block(&starts, 0, false, true), Some(8));
+        assert_eq!(next_change_block(&[], 3, true, true), None);
+    }
+
+    #[test]
+    fn test_match_hunks_only_searches_changed_lines() {
+        let patch = "\
+@@ -1,2 +1,2 @@
+ let old_name = 1;
+-foo(old_name);
++foo(new_name);
+@@ -10,2 +10,2 @@
+ // OLD_NAME is mentioned in context only
+-bar();
++baz();
+@@ -20,1 +20,1 @@
+-Old_Name
++x";
+        let hunks = match_hunks(patch, "old_name");
+        assert_eq!(
+            hunks,
+            vec![
+                HunkMatch {
+                    range: 0..4,
+                    matched: true
+                },
+                HunkMatch {
+                    range: 4..8,
+                    matched: false
+                },
+                HunkMatch {
+                    range: 8..11,
+                    matched: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_match_hunks_ignores_lines_before_first_header() {
+        let patch = "diff --git a/x b/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b";
+        let hunks = match_hunks(patch, "x");
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].range, 2..5);
+        assert!(!hunks[0].matched);
+    }
+
+    #[test]
+    fn test_line_ending_only_hunks_detects_crlf_to_lf() {
+        let patch = "@@ -1,3 +1,3 @@\n-fn main() {\r\n-}\r\n+fn main() {\n+}\n@@ -10,1 +10,1 @@\n-old\r\n+new\n";
+        let hunks = line_ending_only_hunks(patch);
+        assert_eq!(
+            hunks,
+            vec![LineEndingHunk {
+                body: 1..5,
+                change: LineEndingChange::CrlfToLf,
+                pairs: 2,
+            }]
+        );
+        // インデックスは lines() と一致する
+        assert_eq!(patch.lines().nth(4), Some("+}"));
+    }
+
+    #[test]
+    fn test_context_runs_fold_long_run_between_changes() {
+        let mut patch = String::from("@@ -1,24 +1,24 @@\n a\n-old\n+new\n");
+        for i in 0..20 {
+            patch.push_str(&format!(" ctx{}\n", i));
+        }
+        patch.push_str("-old2\n+new2\n tail\n");
+
+        // 先頭の " a" は hunk ヘッダに接するため対象外
+        let runs = context_runs(&patch);
+        assert_eq!(runs, vec![4..24]);
+        assert_eq!(patch.lines().nth(4), Some(" ctx0"));
+
+        // 前後 3 行を残し、残り 14 行をプレースホルダ 1 行に畳む
+        let fold = fold_context_run(&runs[0], 3).unwrap();
+        assert_eq!(fold, 7..21);
+        assert_eq!(patch.lines().nth(fold.start), Some(" ctx3"));
+        assert_eq!(patch.lines().nth(fold.end), Some(" ctx17"));
+
+        // 畳んでも 1 行しか隠れない短い連続はそのまま
+        assert_eq!(fold_context_run(&(0..7), 3), None);
+        assert_eq!(fold_context_run(&(0..8), 3), Some(3..5));
+    }
+
+    #[test]
+    fn test_expand_hunk_context_splices_source_lines() {
+        let source: Vec<String> = (1..=30).map(|i| format!("line {}", i)).collect();
+        let source: Vec<&str> = source.iter().map(String::as_str).collect();
+        let patch = "@@ -10,3 +10,3 @@ fn main() {\n line 10\n-old 11\n+line 11\n line 12\n\
+                     @@ -20,1 +20,2 @@\n line 20\n+line 21\n";
+
+        let expanded = expand_hunk_context(patch, 0, &source, 2).unwrap();
+        assert_eq!(
+            expanded,
+            "@@ -8,7 +8,7 @@ fn main() {\n line 8\n line 9\n line 10\n-old 11\n+line 11\n line 12\n\
+             \x20line 13\n line 14\n@@ -20,1 +20,2 @@\n line 20\n+line 21\n"
+        );
+        // 後続 hunk の行番号はそのまま
+        assert_eq!(
+            crate::diff::get_line_info(&expanded, 10)
+                .unwrap()
+                .new_line_number,
+            Some(20)
+        );
+
+        // 隣の hunk との間（13..=19）を超えては広げない
+        let expanded = expand_hunk_context(patch, 0, &source, 20).unwrap();
+        assert!(expanded.starts_with("@@ -1,19 +1,19 @@ fn main() {\n line 1\n"));
+        assert!(expanded.contains(" line 19\n@@ -20,1 +20,2 @@"));
+        assert_eq!(expand_hunk_context(&expanded, 0, &source, 5), None);
+    }
+
+    #[test]
+    fn test_expand_hunk_context_stops_at_end_of_file() {
+        let source = ["a", "b", "c", "d"];
+        // 末尾の hunk は EOF まで、先頭側は 1 行目まで
+        let patch = "@@ -3,1 +3,2 @@\n c\n+d";
+        assert_eq!(
+            expand_hunk_context(patch, 0, &source, 10).unwrap(),
+            "@@ -1,3 +1,4 @@\n a\n b\n c\n+d"
+        );
+        // 削除のみの hunk（+c,0）は直前の行の後ろに位置する
+        let patch = "@@ -2,1 +1,0 @@\n-x\n";
+        assert_eq!(
+            expand_hunk_context(patch, 0, &source, 1).unwrap(),
+            "@@ -1,3 +1,2 @@\n a\n-x\n b\n"
+        );
+        // ヘッダ以外の行・結合 diff は対象外
+        assert_eq!(expand_hunk_context(patch, 1, &source, 1), None);
+        assert_eq!(
+            expand_hunk_context("@@@ -1,1 -1,1 +1,1 @@@\n  a", 0, &source, 1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_line_ending_only_hunks_rejects_mixed_or_real_changes() {
+        // 向きが混在
+        let patch = "@@ -1,2 +1,2 @@\n-a\r\n-b\n+a\n+b\r\n";
+        assert!(line_ending_only_hunks(patch).is_empty());
+        // 行数が合わない
+        let patch = "@@ -1,1 +1,2 @@\n-a\r\n+a\n+b\n";
+        assert!(line_ending_only_hunks(patch).is_empty());
+        // 改行コード以外も変わっている
+        let patch = "@@ -1,1 +1,1 @@\n-a\n+a\n";
+        assert!(line_ending_only_hunks(patch).is_empty());
+    }
+}
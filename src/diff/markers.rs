@@ -0,0 +1,108 @@
+//! Review marker detection (TODO / FIXME / HACK / ...) for added diff lines.
+//!
+//! Markers are matched case-sensitively on word boundaries so that `TODO`
+//! matches `// TODO: fix` but not `TODOS` or `MY_TODO`.
+
+use smallvec::SmallVec;
+
+use super::{classify_hunk_line, LineType};
+
+/// Byte ranges of marker matches within a single line.
+pub type MarkerRanges = SmallVec<[(usize, usize); 2]>;
+
+#[inline]
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Find byte ranges `(start, end)` of every marker word in `text`.
+///
+/// Ranges are sorted and non-overlapping. Empty words are ignored.
+pub fn find_marker_ranges(text: &str, words: &[String]) -> MarkerRanges {
+    let mut ranges = MarkerRanges::new();
+    if words.is_empty() {
+        return ranges;
+    }
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        // マーカー語は単語境界からのみ開始する
+        if pos > 0 && is_word_byte(bytes[pos - 1]) {
+            pos += 1;
+            continue;
+        }
+        let matched = words.iter().find_map(|w| {
+            let end = pos + w.len();
+            let ok = !w.is_empty()
+                && bytes[pos..].starts_with(w.as_bytes())
+                && (end == bytes.len() || !is_word_byte(bytes[end]));
+            ok.then_some(end)
+        });
+        match matched {
+            Some(end) => {
+                ranges.push((pos, end));
+                pos = end;
+            }
+            None => pos += 1,
+        }
+    }
+    ranges
+}
+
+/// Count marker occurrences in the added lines of a patch.
+///
+/// Context and removed lines are ignored: only newly introduced markers count.
+pub fn count_added_markers(patch: &str, words: &[String]) -> usize {
+    if words.is_empty() {
+        return 0;
+    }
+    let mut combined = false;
+    patch
+        .lines()
+        .filter_map(|line| {
+            let (line_type, content) = classify_hunk_line(line, &mut combined);
+            (line_type.two_way() == LineType::Added)
+                .then(|| find_marker_ranges(content, words).len())
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_words() -> Vec<String> {
+        ["TODO", "FIXME", "HACK", "XXX"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_find_marker_ranges_word_boundaries() {
+        let words = default_words();
+        assert_eq!(
+            find_marker_ranges("// TODO: fix FIXME", &words).as_slice(),
+            &[(3, 7), (13, 18)]
+        );
+        assert!(find_marker_ranges("TODOS MY_TODO todo", &words).is_empty());
+        assert_eq!(find_marker_ranges("(HACK)", &words).as_slice(), &[(1, 5)]);
+    }
+
+    #[test]
+    fn test_find_marker_ranges_multibyte_text() {
+        let words = default_words();
+        let text = "// 修正 TODO 後で";
+        let ranges = find_marker_ranges(text, &words);
+        assert_eq!(ranges.len(), 1);
+        let (s, e) = ranges[0];
+        assert_eq!(&text[s..e], "TODO");
+    }
+
+    #[test]
+    fn test_count_added_markers_only_counts_added_lines() {
+        let patch = "@@ -1,3 +1,4 @@\n // TODO: context\n-// FIXME: removed\n+// TODO: added\n+// HACK and XXX\n+// TODOS not counted";
+        assert_eq!(count_added_markers(patch, &default_words()), 3);
+        assert_eq!(count_added_markers(patch, &[]), 0);
+    }
+}
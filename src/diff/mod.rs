@@ -5,6 +5,35 @@
 //! - Line type classification (Added, Removed, Context, Header)
 //! - New file line numbers for suggestion positioning
 //! - Unified diff parsing for splitting multi-file diffs
+//! - Moved code blocks (removed here, re-added elsewhere in the patch)
+
+mod anchor;
+mod category;
+mod conflicts;
+mod hunks;
+mod markers;
+mod moved;
+mod search;
+mod side_by_side;
+mod stream;
+mod whitespace;
+
+pub use anchor::{relocate_comment_anchor, resolve_comment_anchor, CommentAnchor, CommentSide};
+pub use category::{categorize_file, FileCategory};
+pub use conflicts::{conflict_marker_lines, is_conflict_marker, next_conflict_line};
+pub use hunks::{
+    change_block_starts, context_runs, expand_hunk_context, fold_context_run, hunk_header_lines,
+    hunk_lines_at, line_ending_only_hunks, match_hunks, next_change_block, next_change_stop,
+    HunkMatch, LineEndingChange, LineEndingHunk,
+};
+pub use markers::{count_added_markers, find_marker_ranges, MarkerRanges};
+pub use moved::{moved_block_at, moved_blocks, MovedBlock, MovedSide};
+pub use search::{find_search_matches, SearchMatch};
+pub use side_by_side::{
+    side_by_side_hunk, side_by_side_rows, SideBySideHunk, SideBySideRow, SideLine, SplitDiffRow,
+};
+pub use stream::UnifiedDiffStream;
+pub use whitespace::hide_whitespace_changes;
 
 use std::collections::HashMap;
 use tracing::warn;
@@ -22,8 +51,47 @@ pub enum LineType {
     Header,
     /// Metadata lines (diff --, +++, index, etc.)
     Meta,
+    /// Body line of a combined (merge) diff hunk (`@@@ ... @@@`), one change
+    /// column per parent
+    Combined {
+        first: ChangeColumn,
+        second: ChangeColumn,
+    },
 }
 
+/// One prefix column of a combined diff line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeColumn {
+    /// `+`: in the result but not in this parent
+    Added,
+    /// `-`: in this parent but not in the result
+    Removed,
+    /// ` `: unchanged relative to this parent
+    Context,
+}
+
+impl ChangeColumn {
+    fn from_prefix(c: u8) -> Option<Self> {
+        match c {
+            b'+' => Some(Self::Added),
+            b'-' => Some(Self::Removed),
+            b' ' => Some(Self::Context),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::Context => 0,
+            Self::Added => 1,
+            Self::Removed => 2,
+        }
+    }
+}
+
+/// Two-column markers of combined diff lines, indexed by `first * 3 + second`
+const COMBINED_MARKERS: [&str; 9] = ["  ", " +", " -", "+ ", "++", "+-", "- ", "-+", "--"];
+
 impl LineType {
     #[inline]
     pub fn marker(&self) -> Option<&'static str> {
@@ -31,29 +99,67 @@ impl LineType {
             Self::Added => Some("+"),
             Self::Removed => Some("-"),
             Self::Context => Some(" "),
+            Self::Combined { first, second } => {
+                Some(COMBINED_MARKERS[first.index() * 3 + second.index()])
+            }
             Self::Header | Self::Meta => None,
         }
     }
 
     #[inline]
     pub fn fg_color(&self) -> Option<ratatui::style::Color> {
-        match self {
+        match self.two_way() {
             Self::Header => Some(ratatui::style::Color::Cyan),
             Self::Meta => Some(ratatui::style::Color::Yellow),
             Self::Added => Some(ratatui::style::Color::Green),
             Self::Removed => Some(ratatui::style::Color::Red),
-            Self::Context => None,
+            _ => None,
         }
     }
 
     #[inline]
     pub fn bg_color(&self) -> Option<ratatui::style::Color> {
-        match self {
+        match self.two_way() {
             Self::Added => Some(ratatui::style::Color::Rgb(0, 40, 0)),
             Self::Removed => Some(ratatui::style::Color::Rgb(40, 0, 0)),
             _ => None,
         }
     }
+
+    /// Collapse a combined line to the two-way type it looks like from the
+    /// result's point of view: removed if any parent column is `-`, added if
+    /// any is `+`, otherwise context. Other types are returned unchanged.
+    pub fn two_way(self) -> Self {
+        match self {
+            Self::Combined { first, second } => {
+                if first == ChangeColumn::Removed || second == ChangeColumn::Removed {
+                    Self::Removed
+                } else if first == ChangeColumn::Added || second == ChangeColumn::Added {
+                    Self::Added
+                } else {
+                    Self::Context
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// The line exists in the new (result) file.
+    fn in_new_file(self) -> bool {
+        matches!(self.two_way(), Self::Added | Self::Context)
+    }
+
+    /// The line exists in the old file (the first parent for combined lines).
+    fn in_old_file(self) -> bool {
+        match self {
+            Self::Removed | Self::Context => true,
+            Self::Combined { first, second } => {
+                first == ChangeColumn::Removed
+                    || (first == ChangeColumn::Context && second != ChangeColumn::Removed)
+            }
+            _ => false,
+        }
+    }
 }
 
 /// Information extracted from a single line in a diff patch
@@ -65,6 +171,8 @@ pub struct DiffLineInfo {
     pub line_type: LineType,
     /// Line number in the new file (None for removed lines and headers)
     pub new_line_number: Option<u32>,
+    /// Line number in the old file (None for added lines and headers)
+    pub old_line_number: Option<u32>,
     /// Position within the patch (1-based). Corresponds to GitHub API's `position` parameter.
     /// Meta lines (diff --git, ---, +++, index) are not counted.
     /// The first `@@` header is not counted; position 1 is the first line below it.
@@ -88,6 +196,8 @@ pub struct PatchLineInfo<'a> {
     pub line_type: LineType,
     /// Line number in the new file (None for removed lines and headers)
     pub new_line_number: Option<u32>,
+    /// Line number in the old file (None for added lines and headers)
+    pub old_line_number: Option<u32>,
     /// Position within the patch (1-based, GitHub API compatible)
     pub diff_position: Option<u32>,
 }
@@ -102,30 +212,28 @@ impl<'a> PatchIndex<'a> {
         let mut lines = Vec::with_capacity(line_iter.len());
 
         let mut new_line_number: Option<u32> = None;
+        let mut old_line_number: Option<u32> = None;
         let mut position_counter: Option<u32> = None;
+        let mut combined = false;
 
         for line in &line_iter {
             let line_clean = line.strip_suffix('\r').unwrap_or(line);
-            let (line_type, content) = classify_line(line_clean);
+            let (line_type, content) = classify_hunk_line(line_clean, &mut combined);
 
             match line_type {
                 LineType::Meta => {}
                 LineType::Header => {
-                    new_line_number = parse_hunk_header(line_clean);
+                    let starts = parse_hunk_header(line_clean);
+                    old_line_number = starts.map(|(old, _)| old);
+                    new_line_number = starts.map(|(_, new)| new);
                     position_counter = Some(position_counter.map_or(0, |p| p + 1));
                 }
-                LineType::Added | LineType::Context => {
-                    position_counter = position_counter.map(|p| p + 1);
-                }
-                LineType::Removed => {
+                _ => {
                     position_counter = position_counter.map(|p| p + 1);
                 }
             }
 
-            let current_new_line = match line_type {
-                LineType::Removed | LineType::Header | LineType::Meta => None,
-                _ => new_line_number,
-            };
+            let current_new_line = line_type.in_new_file().then_some(new_line_number).flatten();
 
             let current_position = match line_type {
                 LineType::Meta => None,
@@ -137,17 +245,11 @@ impl<'a> PatchIndex<'a> {
                 content,
                 line_type,
                 new_line_number: current_new_line,
+                old_line_number: current_old_line(line_type, old_line_number),
                 diff_position: current_position,
             });
 
-            match line_type {
-                LineType::Added | LineType::Context => {
-                    if let Some(n) = new_line_number {
-                        new_line_number = Some(n + 1);
-                    }
-                }
-                _ => {}
-            }
+            advance_line_numbers(line_type, &mut new_line_number, &mut old_line_number);
         }
 
         Self { lines }
@@ -173,23 +275,55 @@ impl PatchLineInfo<'_> {
             line_content: self.content.to_string(),
             line_type: self.line_type,
             new_line_number: self.new_line_number,
+            old_line_number: self.old_line_number,
             diff_position: self.diff_position,
         }
     }
 }
 
-/// Parse a hunk header to extract the starting line number for new file
+/// The range groups of a hunk header, between the opening and closing `@`s
+fn hunk_ranges(line: &str) -> impl Iterator<Item = &str> {
+    let rest = line.trim_start_matches('@');
+    let ranges = rest.find("@@").map_or(rest, |end| &rest[..end]);
+    ranges.split_whitespace()
+}
+
+fn parse_range_start(range: &str) -> Option<u32> {
+    range[1..].split(',').next()?.parse().ok()
+}
+
+/// Parse a hunk header to extract the starting line numbers `(old_start, new_start)`
 /// Format: @@ -old_start,old_count +new_start,new_count @@
-fn parse_hunk_header(line: &str) -> Option<u32> {
-    // Find the +new_start part
-    let plus_pos = line.find('+')?;
-    let after_plus = &line[plus_pos + 1..];
+///
+/// Combined diffs (`@@@ -a,b -c,d +e,f @@@`) list one `-` group per parent;
+/// the old side is the first parent and the final `+` group is the result file.
+fn parse_hunk_header(line: &str) -> Option<(u32, u32)> {
+    let old = parse_range_start(hunk_ranges(line).find(|r| r.starts_with('-'))?)?;
+    let new = parse_range_start(hunk_ranges(line).filter(|r| r.starts_with('+')).last()?)?;
+    Some((old, new))
+}
 
-    // Extract the number (stop at comma or space)
-    let end_pos = after_plus.find([',', ' ']).unwrap_or(after_plus.len());
-    let num_str = &after_plus[..end_pos];
+/// Old-file line number of a line (only Removed and Context lines exist in the old file)
+fn current_old_line(line_type: LineType, old_line_number: Option<u32>) -> Option<u32> {
+    line_type.in_old_file().then_some(old_line_number).flatten()
+}
 
-    num_str.parse().ok()
+/// Advance the new/old line counters past a line of the given type
+fn advance_line_numbers(
+    line_type: LineType,
+    new_line_number: &mut Option<u32>,
+    old_line_number: &mut Option<u32>,
+) {
+    if line_type.in_new_file() {
+        if let Some(n) = new_line_number {
+            *n += 1;
+        }
+    }
+    if line_type.in_old_file() {
+        if let Some(n) = old_line_number {
+            *n += 1;
+        }
+    }
 }
 
 /// Get information about a specific line in a patch
@@ -208,13 +342,15 @@ pub fn get_line_info(patch: &str, line_index: usize) -> Option<DiffLineInfo> {
         return None;
     }
 
-    // Track the current new file line number
+    // Track the current new/old file line numbers
     let mut new_line_number: Option<u32> = None;
+    let mut old_line_number: Option<u32> = None;
     // Track the position within the patch (1-based, skipping meta lines)
     let mut position_counter: Option<u32> = None;
+    let mut combined = false;
 
     for (i, line) in lines.iter().enumerate() {
-        let (line_type, content) = classify_line(line);
+        let (line_type, content) = classify_hunk_line(line, &mut combined);
 
         // Update position counter and line number BEFORE checking target
         match line_type {
@@ -222,24 +358,20 @@ pub fn get_line_info(patch: &str, line_index: usize) -> Option<DiffLineInfo> {
                 // Meta lines don't count toward position
             }
             LineType::Header => {
-                new_line_number = parse_hunk_header(line);
+                let starts = parse_hunk_header(line);
+                old_line_number = starts.map(|(old, _)| old);
+                new_line_number = starts.map(|(_, new)| new);
                 // First @@ initializes to 0 (not counted); subsequent @@ lines increment
                 position_counter = Some(position_counter.map_or(0, |p| p + 1));
             }
-            LineType::Added | LineType::Context => {
-                position_counter = position_counter.map(|p| p + 1);
-            }
-            LineType::Removed => {
+            _ => {
                 position_counter = position_counter.map(|p| p + 1);
             }
         }
 
         if i == line_index {
             // For the target line, return the info
-            let current_new_line = match line_type {
-                LineType::Removed | LineType::Header | LineType::Meta => None,
-                _ => new_line_number,
-            };
+            let current_new_line = line_type.in_new_file().then_some(new_line_number).flatten();
 
             let current_position = match line_type {
                 // Meta lines and the first @@ header (position 0) have no valid position
@@ -252,26 +384,28 @@ pub fn get_line_info(patch: &str, line_index: usize) -> Option<DiffLineInfo> {
                 line_content: content.to_string(),
                 line_type,
                 new_line_number: current_new_line,
+                old_line_number: current_old_line(line_type, old_line_number),
                 diff_position: current_position,
             });
         }
 
-        // Update new_line_number for next iteration
-        match line_type {
-            LineType::Added | LineType::Context => {
-                if let Some(n) = new_line_number {
-                    new_line_number = Some(n + 1);
-                }
-            }
-            _ => {}
-        }
+        // Update line numbers for next iteration
+        advance_line_numbers(line_type, &mut new_line_number, &mut old_line_number);
     }
 
     None
 }
 
 /// Classify a line and extract its content without the prefix
+///
+/// Hunk headers of both two-way (`@@`) and combined (`@@@`) diffs are
+/// `Header`. Body lines are classified as two-way lines; use
+/// [`classify_hunk_line`] when walking a patch that may contain combined hunks.
+///
+/// A trailing `\r` (CRLF files) is stripped from the returned content; use
+/// [`LineEnding::detect`] to know whether the file uses CRLF.
 pub fn classify_line(line: &str) -> (LineType, &str) {
+    let line = line.strip_suffix('\r').unwrap_or(line);
     if line.starts_with("@@") {
         (LineType::Header, line)
     } else if line.starts_with("+++")
@@ -292,24 +426,106 @@ pub fn classify_line(line: &str) -> (LineType, &str) {
     }
 }
 
+/// Classify a body line of a combined (`@@@`) hunk, stripping both prefix columns
+pub fn classify_combined_line(line: &str) -> (LineType, &str) {
+    let columns = line.as_bytes().get(..2).and_then(|p| {
+        Some((
+            ChangeColumn::from_prefix(p[0])?,
+            ChangeColumn::from_prefix(p[1])?,
+        ))
+    });
+    match columns {
+        _ if line.starts_with("@@") || line.starts_with("diff ") || line.starts_with("index ") => {
+            classify_line(line)
+        }
+        Some((first, second)) => (LineType::Combined { first, second }, &line[2..]),
+        None => classify_line(line),
+    }
+}
+
+/// Classify a patch line, tracking whether the current hunk is combined
+///
+/// Feed the lines of a patch in order with one `combined` flag (initially
+/// `false`); body lines of `@@@` hunks come back as [`LineType::Combined`].
+pub fn classify_hunk_line<'a>(line: &'a str, combined: &mut bool) -> (LineType, &'a str) {
+    if line.starts_with("@@") {
+        *combined = line.starts_with("@@@");
+    }
+    if *combined {
+        classify_combined_line(line)
+    } else {
+        classify_line(line)
+    }
+}
+
+/// Line-ending style of a file, as seen from the new side of its patch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    /// Detect the style from the added/context lines of `patch`.
+    ///
+    /// The file is treated as CRLF when more than half of those lines end with `\r`.
+    pub fn detect(patch: &str) -> Self {
+        let mut total = 0usize;
+        let mut crlf = 0usize;
+        // `lines()` は "\r\n" の \r を落とすため、'\n' で分割して \r を残す
+        for line in patch.split('\n') {
+            if line.is_empty() || !line.starts_with(['+', ' ']) || line.starts_with("+++") {
+                continue;
+            }
+            total += 1;
+            if line.ends_with('\r') {
+                crlf += 1;
+            }
+        }
+        if crlf * 2 > total {
+            Self::Crlf
+        } else {
+            Self::Lf
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Lf => "LF",
+            Self::Crlf => "CRLF",
+        }
+    }
+
+    /// Convert the `\n` line breaks of `text` to this line ending.
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            Self::Lf => text.to_string(),
+            Self::Crlf => text.replace("\r\n", "\n").replace('\n', "\r\n"),
+        }
+    }
+}
+
 /// Validate that all lines in `start..=end` are contiguous new-side lines within a single hunk.
 ///
 /// Returns `true` when every line in the range is `Added` or `Context` and no `Header` line
 /// appears between `start` and `end` (i.e. the range does not cross a hunk boundary).
 pub fn validate_multiline_range(patch: &str, start: usize, end: usize) -> bool {
-    let lines: Vec<&str> = patch.lines().collect();
-    for idx in start..=end {
-        let Some(line) = lines.get(idx) else {
-            return false;
-        };
-        let (line_type, _) = classify_line(line);
-        match line_type {
-            LineType::Added | LineType::Context => {}
+    // Classify from the top so that lines of combined hunks are recognized
+    let mut combined = false;
+    let mut checked = 0;
+    for (idx, line) in patch.lines().enumerate().take(end.saturating_add(1)) {
+        let (line_type, _) = classify_hunk_line(line, &mut combined);
+        if idx < start {
+            continue;
+        }
+        match line_type.two_way() {
+            LineType::Added | LineType::Context => checked += 1,
             // Removed, Header, or Meta lines inside the range → invalid
             _ => return false,
         }
     }
-    true
+    checked == (start..=end).count()
 }
 
 /// Convert a file line number (new_line_number) to a patch position.
@@ -325,25 +541,26 @@ pub fn validate_multiline_range(patch: &str, start: usize, end: usize) -> bool {
 pub fn line_number_to_position(patch: &str, target_line: u32) -> Option<u32> {
     let mut new_line_number: Option<u32> = None;
     let mut position_counter: Option<u32> = None;
+    let mut combined = false;
 
     for line in patch.lines() {
-        let (line_type, _) = classify_line(line);
+        let (line_type, _) = classify_hunk_line(line, &mut combined);
 
         match line_type {
             LineType::Meta => continue,
             LineType::Header => {
-                new_line_number = parse_hunk_header(line);
+                new_line_number = parse_hunk_header(line).map(|(_, new)| new);
                 // First @@ initializes to 0 (not counted); subsequent @@ lines increment
                 position_counter = Some(position_counter.map_or(0, |p| p + 1));
             }
-            LineType::Added | LineType::Context => {
+            line_type if line_type.in_new_file() => {
                 position_counter = position_counter.map(|p| p + 1);
                 if new_line_number == Some(target_line) {
                     return position_counter;
                 }
                 new_line_number = new_line_number.map(|n| n + 1);
             }
-            LineType::Removed => {
+            _ => {
                 position_counter = position_counter.map(|p| p + 1);
             }
         }
@@ -351,6 +568,36 @@ pub fn line_number_to_position(patch: &str, target_line: u32) -> Option<u32> {
     None
 }
 
+/// ファイル単位の diff の開始行（`diff --git` または結合 diff の `diff --cc`）
+fn is_file_header(line: &str) -> bool {
+    line.starts_with("diff --git ")
+        || line.starts_with("diff --cc ")
+        || line.starts_with("diff --combined ")
+}
+
+/// プレビュー対象の画像拡張子
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg"];
+
+/// 拡張子（大文字小文字を区別しない）で画像ファイルか判定する
+pub fn is_image(filename: &str) -> bool {
+    std::path::Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|image| ext.eq_ignore_ascii_case(image))
+        })
+}
+
+/// バイナリファイルの patch か（`Binary files ... differ` / `GIT binary patch`）
+pub fn is_binary_patch(patch: &str) -> bool {
+    patch.lines().any(|line| {
+        (line.starts_with("Binary files ") && line.ends_with(" differ"))
+            || line == "GIT binary patch"
+    })
+}
+
 /// Parse a unified diff output into a map of filename -> patch content
 ///
 /// This function splits the output of `git diff` or `gh pr diff` into individual
@@ -380,7 +627,10 @@ pub fn parse_unified_diff(unified_diff: &str) -> HashMap<String, String> {
 
         let line = raw_line.strip_suffix('\r').unwrap_or(raw_line);
 
-        if line.starts_with("diff --git ") {
+        if is_file_header(line) {
+            let combined_filename = line
+                .strip_prefix("diff --cc ")
+                .or_else(|| line.strip_prefix("diff --combined "));
             if let (Some(filename), Some(start)) = (&current_filename, current_patch_start) {
                 let end = trim_trailing_newline(unified_diff, line_start);
                 if end > start {
@@ -389,7 +639,11 @@ pub fn parse_unified_diff(unified_diff: &str) -> HashMap<String, String> {
                 }
             }
 
-            current_filename = extract_filename(line);
+            // 結合 diff (merge commit) のヘッダはパスを 1 つだけ持つ
+            current_filename = match combined_filename {
+                Some(path) => Some(path.to_string()),
+                None => extract_filename(line),
+            };
             current_patch_start = Some(line_start);
             pending_minus_filename = None;
         } else if current_filename.is_none() && current_patch_start.is_some() {
@@ -634,11 +888,125 @@ index 0000000..1234567
 Binary files /dev/null and b/image.png differ
 "#;
 
+    /// Merge commit with a conflict resolution (`git show --cc`)
+    const UNIFIED_DIFF_COMBINED: &str = r#"diff --cc src/config.rs
+index 1a2b3c4,5d6e7f8..9a0b1c2
+--- a/src/config.rs
++++ b/src/config.rs
+@@@ -1,5 -1,6 +1,7 @@@
+  pub struct Config {
+      pub name: String,
+-     pub timeout: u32,
+ -    pub legacy: bool,
++     pub timeout: u64,
+ +    pub retries: u8,
++     pub debug: bool,
+++    pub merged: bool,
+  }
+"#;
+
     #[test]
     fn test_parse_hunk_header() {
-        assert_eq!(parse_hunk_header("@@ -1,4 +1,5 @@"), Some(1));
-        assert_eq!(parse_hunk_header("@@ -10,3 +15,7 @@"), Some(15));
-        assert_eq!(parse_hunk_header("@@ -1 +1 @@"), Some(1));
+        assert_eq!(parse_hunk_header("@@ -1,4 +1,5 @@"), Some((1, 1)));
+        assert_eq!(parse_hunk_header("@@ -10,3 +15,7 @@"), Some((10, 15)));
+        assert_eq!(parse_hunk_header("@@ -1 +1 @@"), Some((1, 1)));
+        assert_eq!(parse_hunk_header("@@ -0,0 +1,3 @@"), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_patch_index_old_and_new_line_numbers_sample_patch() {
+        let index = PatchIndex::build(SAMPLE_PATCH);
+        let numbers: Vec<(Option<u32>, Option<u32>)> = (0..index.len())
+            .map(|i| {
+                let info = index.get(i).unwrap();
+                (info.old_line_number, info.new_line_number)
+            })
+            .collect();
+        assert_eq!(
+            numbers,
+            vec![
+                (None, None),       // @@ -1,4 +1,5 @@
+                (Some(1), Some(1)), //  line 1
+                (Some(2), None),    // -old line 2
+                (None, Some(2)),    // +new line 2
+                (None, Some(3)),    // +added line
+                (Some(3), Some(4)), //  line 3
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_search_matches_sample_patch() {
+        let matches = find_search_matches(SAMPLE_PATCH.lines(), "LINE 2", false);
+        assert_eq!(
+            matches,
+            vec![
+                SearchMatch {
+                    line: 2,
+                    range: 5..11
+                },
+                SearchMatch {
+                    line: 3,
+                    range: 5..11
+                },
+            ]
+        );
+
+        // 大文字小文字を区別する場合は一致しない
+        assert!(find_search_matches(SAMPLE_PATCH.lines(), "LINE 2", true).is_empty());
+
+        // 1 行に複数一致・ハンクヘッダも対象
+        let matches = find_search_matches(SAMPLE_PATCH.lines(), "l", true);
+        assert_eq!(matches.iter().filter(|m| m.line == 2).count(), 2);
+        let lines: Vec<usize> = find_search_matches(SAMPLE_PATCH.lines(), "1", true)
+            .iter()
+            .map(|m| m.line)
+            .collect();
+        assert_eq!(lines, vec![0, 0, 1]);
+
+        assert!(find_search_matches(SAMPLE_PATCH.lines(), "", false).is_empty());
+    }
+
+    #[test]
+    fn test_side_by_side_rows_sample_patch() {
+        type Side<'a> = Option<(u32, &'a str)>;
+        let rows = side_by_side_rows(SAMPLE_PATCH);
+        let pairs: Vec<(Side, Side)> = rows
+            .iter()
+            .filter_map(|row| match row {
+                SplitDiffRow::Pair(row) => Some((
+                    row.old
+                        .as_ref()
+                        .map(|s| (s.line_number, s.content.as_str())),
+                    row.new
+                        .as_ref()
+                        .map(|s| (s.line_number, s.content.as_str())),
+                )),
+                SplitDiffRow::Header { .. } => None,
+            })
+            .collect();
+
+        assert_eq!(
+            rows[0],
+            SplitDiffRow::Header {
+                patch_line: 0,
+                text: "@@ -1,4 +1,5 @@".to_string()
+            }
+        );
+        assert_eq!(
+            pairs,
+            vec![
+                (Some((1, "line 1")), Some((1, "line 1"))),
+                (Some((2, "old line 2")), Some((2, "new line 2"))),
+                (None, Some((3, "added line"))),
+                (Some((3, "line 3")), Some((4, "line 3"))),
+            ]
+        );
+        // 対応付けた行は両側の patch 行を含む
+        assert!(rows[2].contains_patch_line(2));
+        assert!(rows[2].contains_patch_line(3));
+        assert_eq!(rows[3].first_patch_line(), 4);
+        assert!(side_by_side_rows("diff --git a/x b/x\n").is_empty());
     }
 
     #[test]
@@ -662,6 +1030,9 @@ Binary files /dev/null and b/image.png differ
         assert_eq!(info.line_type, LineType::Removed);
         assert_eq!(info.line_content, "old line 2");
         assert!(info.new_line_number.is_none());
+        // 削除行へのコメントは旧ファイル側の行番号に付ける
+        assert_eq!(info.old_line_number, Some(2));
+        assert_eq!(info.diff_position, Some(2));
     }
 
     #[test]
@@ -688,6 +1059,46 @@ Binary files /dev/null and b/image.png differ
         assert_eq!(content, "");
     }
 
+    #[test]
+    fn test_classify_line_strips_crlf() {
+        for (line, expected_type, expected) in [
+            ("+added\r", LineType::Added, "added"),
+            ("-removed\r", LineType::Removed, "removed"),
+            (" context\r", LineType::Context, "context"),
+            ("@@ -1,2 +1,2 @@\r", LineType::Header, "@@ -1,2 +1,2 @@"),
+            ("+\r", LineType::Added, ""),
+        ] {
+            let (line_type, content) = classify_line(line);
+            assert_eq!(line_type, expected_type);
+            assert_eq!(content, expected);
+            assert!(!content.contains('\r'));
+        }
+    }
+
+    #[test]
+    fn test_line_ending_detect() {
+        let crlf = "@@ -1,2 +1,3 @@\n fn main() {\r\n+    hello\r\n-    bye\r\n }\r\n";
+        assert_eq!(LineEnding::detect(crlf), LineEnding::Crlf);
+        assert_eq!(LineEnding::detect(SAMPLE_PATCH), LineEnding::Lf);
+        assert_eq!(LineEnding::detect(""), LineEnding::Lf);
+        // 削除行だけが CRLF（CRLF→LF への変換）なら新しいファイルは LF
+        let to_lf = "@@ -1,1 +1,1 @@\n-a\r\n+a\n";
+        assert_eq!(LineEnding::detect(to_lf), LineEnding::Lf);
+        // 最後の行が改行なしでも \r を見る
+        assert_eq!(
+            LineEnding::detect("@@ -0,0 +1,1 @@\n+a\r"),
+            LineEnding::Crlf
+        );
+    }
+
+    #[test]
+    fn test_line_ending_apply() {
+        assert_eq!(LineEnding::Lf.apply("a\nb"), "a\nb");
+        assert_eq!(LineEnding::Crlf.apply("a\nb"), "a\r\nb");
+        // 既に CRLF の行を二重に変換しない
+        assert_eq!(LineEnding::Crlf.apply("a\r\nb\nc"), "a\r\nb\r\nc");
+    }
+
     #[test]
     fn test_parse_hunk_header_no_comma_no_space() {
         // "@@ -1 +42\ntest" → after_plus = "42" で find([',', ' ']) が None
@@ -934,6 +1345,153 @@ index 1234567..abcdefg 100644
         index 0000000..1234567
         Binary files /dev/null and b/image.png differ
         "#);
+        assert!(is_binary_patch(&result["image.png"]));
+        assert!(!is_binary_patch(
+            &parse_unified_diff(UNIFIED_DIFF_RENAMED)["src/new_name.rs"]
+        ));
+        assert!(is_binary_patch(
+            "diff --git a/a.bin b/a.bin\nindex 1..2 100644\nGIT binary patch\nliteral 3\n"
+        ));
+    }
+
+    #[test]
+    fn test_is_image() {
+        assert!(is_image("assets/logo.png"));
+        assert!(is_image("photo.JPG"));
+        assert!(is_image("a/b/c.jpeg"));
+        assert!(is_image("icon.svg"));
+        assert!(!is_image("src/main.rs"));
+        assert!(!is_image("png"));
+        assert!(!is_image("archive.png.zip"));
+    }
+
+    #[test]
+    fn test_unified_diff_stream_yields_files_in_order() {
+        let full = format!("{}{}", UNIFIED_DIFF_MULTIPLE, UNIFIED_DIFF_NEW_FILE);
+        let mut stream = UnifiedDiffStream::default();
+        let mut seen = Vec::new();
+        for (i, line) in full.split_inclusive('\n').enumerate() {
+            if let Some((filename, patch)) = stream.push_line(line) {
+                seen.push((i, filename, patch));
+            }
+        }
+        let total_lines = full.lines().count();
+        seen.extend(stream.finish().map(|(f, p)| (total_lines, f, p)));
+
+        let filenames: Vec<&str> = seen.iter().map(|(_, f, _)| f.as_str()).collect();
+        assert_eq!(filenames, ["src/lib.rs", "src/app.rs", "src/new_file.rs"]);
+        // 最初のファイルは 2 つ目のヘッダ行が届いた時点で確定する
+        assert_eq!(seen[0].0, 7);
+
+        let expected = parse_unified_diff(&full);
+        for (_, filename, patch) in &seen {
+            assert_eq!(Some(patch), expected.get(filename));
+        }
+    }
+
+    #[test]
+    fn test_categorize_file_from_unified_diff_fixtures() {
+        let categorize = |diff: &str| {
+            let parsed = parse_unified_diff(diff);
+            let patch = parsed.values().next().unwrap();
+            // ローカル diff 由来を想定し status はヒントにしない
+            categorize_file("modified", Some(patch))
+        };
+        assert_eq!(categorize(UNIFIED_DIFF_SINGLE), FileCategory::Modified);
+        assert_eq!(categorize(UNIFIED_DIFF_NEW_FILE), FileCategory::Added);
+        assert_eq!(categorize(UNIFIED_DIFF_DELETED), FileCategory::Deleted);
+        assert_eq!(categorize(UNIFIED_DIFF_RENAMED), FileCategory::Renamed);
+        assert_eq!(categorize(UNIFIED_DIFF_BINARY), FileCategory::Added);
+
+        // GitHub API の patch はヘッダを含まないので status、次に hunk 範囲で判定
+        assert_eq!(categorize_file("renamed", None), FileCategory::Renamed);
+        assert_eq!(
+            categorize_file("modified", Some("@@ -0,0 +1,2 @@\n+a\n+b")),
+            FileCategory::Added
+        );
+        assert_eq!(
+            categorize_file("changed", Some("@@ -1,2 +0,0 @@\n-a\n-b")),
+            FileCategory::Deleted
+        );
+        assert_eq!(
+            categorize_file("modified", Some("@@ -1 +1 @@\n-a\n+b")),
+            FileCategory::Modified
+        );
+    }
+
+    #[test]
+    fn test_parse_combined_diff() {
+        let result = parse_unified_diff(UNIFIED_DIFF_COMBINED);
+        assert_snapshot!(format_parsed_diff(&result), @r#"
+        [src/config.rs]
+        diff --cc src/config.rs
+        index 1a2b3c4,5d6e7f8..9a0b1c2
+        --- a/src/config.rs
+        +++ b/src/config.rs
+        @@@ -1,5 -1,6 +1,7 @@@
+          pub struct Config {
+              pub name: String,
+        -     pub timeout: u32,
+         -    pub legacy: bool,
+        +     pub timeout: u64,
+         +    pub retries: u8,
+        +     pub debug: bool,
+        ++    pub merged: bool,
+          }
+        "#);
+    }
+
+    #[test]
+    fn test_get_line_info_combined_diff() {
+        assert_eq!(parse_hunk_header("@@@ -1,5 -1,6 +1,7 @@@"), Some((1, 1)));
+        assert_eq!(
+            parse_hunk_header("@@@ -3,5 -8,6 +12,7 @@@ fn a()"),
+            Some((3, 12))
+        );
+
+        let patch = &parse_unified_diff(UNIFIED_DIFF_COMBINED)["src/config.rs"];
+        let index = PatchIndex::build(patch);
+        let lines: Vec<String> = (0..index.len())
+            .map(|i| {
+                let info = get_line_info(patch, i).unwrap();
+                let indexed = index.get(i).unwrap();
+                assert_eq!(info.new_line_number, indexed.new_line_number);
+                assert_eq!(info.old_line_number, indexed.old_line_number);
+                assert_eq!(info.diff_position, indexed.diff_position);
+                format!(
+                    "{:>2} {:<2} new={:?} old={:?} {}",
+                    i,
+                    info.line_type.marker().unwrap_or("@"),
+                    info.new_line_number,
+                    info.old_line_number,
+                    info.line_content
+                )
+            })
+            .collect();
+        assert_snapshot!(lines.join("\n"), @r"
+         0 @  new=None old=None diff --cc src/config.rs
+         1 @  new=None old=None index 1a2b3c4,5d6e7f8..9a0b1c2
+         2 @  new=None old=None --- a/src/config.rs
+         3 @  new=None old=None +++ b/src/config.rs
+         4 @  new=None old=None @@@ -1,5 -1,6 +1,7 @@@
+         5    new=Some(1) old=Some(1) pub struct Config {
+         6    new=Some(2) old=Some(2)     pub name: String,
+         7 -  new=None old=Some(3)     pub timeout: u32,
+         8  - new=None old=None     pub legacy: bool,
+         9 +  new=Some(3) old=None     pub timeout: u64,
+        10  + new=Some(4) old=Some(4)     pub retries: u8,
+        11 +  new=Some(5) old=None     pub debug: bool,
+        12 ++ new=Some(6) old=None     pub merged: bool,
+        13    new=Some(7) old=Some(5) }
+        ");
+        assert_eq!(
+            get_line_info(patch, 10).unwrap().line_type,
+            LineType::Combined {
+                first: ChangeColumn::Context,
+                second: ChangeColumn::Added,
+            }
+        );
+        assert_eq!(line_number_to_position(patch, 4), Some(6));
     }
 
     #[test]
@@ -0,0 +1,204 @@
+//! Moved code detection, similar to `git diff --color-moved`.
+//!
+//! A block moved within a file shows up as a run of removed lines plus an
+//! identical run of added lines elsewhere in the patch. Lines are compared
+//! after trimming surrounding whitespace, so a block that was moved and
+//! re-indented still matches.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use super::{classify_hunk_line, LineType};
+
+/// Minimum number of consecutive matching lines for a run to count as moved.
+pub const MIN_MOVED_LINES: usize = 3;
+
+/// A run of removed lines that reappears verbatim as a run of added lines.
+///
+/// Both ranges are patch line indices and have the same length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MovedBlock {
+    pub removed: Range<usize>,
+    pub added: Range<usize>,
+}
+
+/// Which end of a move a diff line belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovedSide {
+    /// Removed line whose content was re-added elsewhere.
+    From,
+    /// Added line whose content was removed elsewhere.
+    To,
+}
+
+impl MovedBlock {
+    /// The side of this block `line` is on, if any.
+    pub fn side_of(&self, line: usize) -> Option<MovedSide> {
+        if self.removed.contains(&line) {
+            Some(MovedSide::From)
+        } else if self.added.contains(&line) {
+            Some(MovedSide::To)
+        } else {
+            None
+        }
+    }
+}
+
+/// Find blocks of at least [`MIN_MOVED_LINES`] removed lines that are re-added
+/// elsewhere in `patch`.
+///
+/// A removed run directly followed by its added counterpart (in the same
+/// change block) is an in-place edit such as re-indentation, not a move.
+/// Runs made only of blank or punctuation lines (closing braces and the
+/// like) are ignored. Each line belongs to at most one block.
+pub fn moved_blocks(patch: &str) -> Vec<MovedBlock> {
+    // (line type, trimmed content, change block number)
+    let mut lines: Vec<(LineType, &str, usize)> = Vec::new();
+    let mut change_block = 0;
+    let mut combined = false;
+    for line in patch.lines() {
+        // 結合 diff の行は結果から見た追加/削除として扱う
+        let (line_type, content) = classify_hunk_line(line, &mut combined);
+        let line_type = line_type.two_way();
+        if !matches!(line_type, LineType::Added | LineType::Removed) {
+            change_block += 1;
+        }
+        lines.push((line_type, content.trim(), change_block));
+    }
+
+    let is_run = |start: usize, line_type: LineType| {
+        lines
+            .get(start..start + MIN_MOVED_LINES)
+            .is_some_and(|run| run.iter().all(|&(t, _, _)| t == line_type))
+    };
+    let key =
+        |start: usize| -> [&str; MIN_MOVED_LINES] { std::array::from_fn(|k| lines[start + k].1) };
+
+    // Added runs indexed by the content of their first MIN_MOVED_LINES lines
+    let mut added_runs: HashMap<[&str; MIN_MOVED_LINES], Vec<usize>> = HashMap::new();
+    for start in 0..lines.len() {
+        if is_run(start, LineType::Added) {
+            added_runs.entry(key(start)).or_default().push(start);
+        }
+    }
+
+    let mut used = vec![false; lines.len()];
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if used[i] || !is_run(i, LineType::Removed) {
+            i += 1;
+            continue;
+        }
+        let run_len = |j: usize| {
+            (0..)
+                .take_while(|&k| {
+                    matches!(
+                        (lines.get(i + k), lines.get(j + k)),
+                        (Some(&(LineType::Removed, a, _)), Some(&(LineType::Added, b, _)))
+                            if a == b && !used[i + k] && !used[j + k]
+                    )
+                })
+                .count()
+        };
+        let best = added_runs
+            .get(&key(i))
+            .into_iter()
+            .flatten()
+            .filter(|&&j| lines[j].2 != lines[i].2)
+            .map(|&j| (j, run_len(j)))
+            .filter(|&(_, len)| len >= MIN_MOVED_LINES)
+            .max_by_key(|&(j, len)| (len, std::cmp::Reverse(j)));
+        let Some((j, len)) = best else {
+            i += 1;
+            continue;
+        };
+        let has_code = lines[i..i + len]
+            .iter()
+            .any(|&(_, content, _)| content.chars().any(char::is_alphanumeric));
+        if !has_code {
+            i += 1;
+            continue;
+        }
+        used[i..i + len].fill(true);
+        used[j..j + len].fill(true);
+        blocks.push(MovedBlock {
+            removed: i..i + len,
+            added: j..j + len,
+        });
+        i += len;
+    }
+    blocks
+}
+
+/// The block containing `line` and the side of the move it is on.
+pub fn moved_block_at(blocks: &[MovedBlock], line: usize) -> Option<(&MovedBlock, MovedSide)> {
+    blocks
+        .iter()
+        .find_map(|block| block.side_of(line).map(|side| (block, side)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 4-line block moved from the top of the file to the bottom.
+    const MOVED_PATCH: &str = "@@ -1,8 +1,9 @@\n-fn helper(x: u32) -> u32 {\n-    let y = x * 2;\n-    y + 1\n-}\n \n fn main() {\n     run();\n }\n+\n+fn helper(x: u32) -> u32 {\n+    let y = x * 2;\n+    y + 1\n+}";
+
+    #[test]
+    fn test_moved_blocks_detects_verbatim_move() {
+        assert_eq!(
+            moved_blocks(MOVED_PATCH),
+            vec![MovedBlock {
+                removed: 1..5,
+                added: 10..14,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_moved_block_at_reports_side() {
+        let blocks = moved_blocks(MOVED_PATCH);
+        assert_eq!(
+            moved_block_at(&blocks, 1).map(|(_, side)| side),
+            Some(MovedSide::From)
+        );
+        assert_eq!(
+            moved_block_at(&blocks, 13).map(|(_, side)| side),
+            Some(MovedSide::To)
+        );
+        // The blank line added before the block is a plain addition
+        assert!(moved_block_at(&blocks, 9).is_none());
+        assert!(moved_block_at(&blocks, 6).is_none());
+    }
+
+    #[test]
+    fn test_moved_blocks_ignores_indentation() {
+        let patch = "@@ -1,7 +1,7 @@\n-let a = 1;\n-let b = 2;\n-let c = a + b;\n fn f() {\n }\n fn g() {\n+    let a = 1;\n+    let b = 2;\n+    let c = a + b;\n }";
+        assert_eq!(
+            moved_blocks(patch),
+            vec![MovedBlock {
+                removed: 1..4,
+                added: 7..10,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_moved_blocks_requires_minimum_run() {
+        let patch =
+            "@@ -1,3 +1,3 @@\n-let a = 1;\n-let b = 2;\n fn f() {}\n+let a = 1;\n+let b = 2;";
+        assert!(moved_blocks(patch).is_empty());
+    }
+
+    #[test]
+    fn test_moved_blocks_skips_in_place_edits_and_punctuation() {
+        // Re-indented in place: the removed run is directly followed by the added run
+        let reindent = "@@ -1,3 +1,3 @@\n-let a = 1;\n-let b = 2;\n-let c = 3;\n+    let a = 1;\n+    let b = 2;\n+    let c = 3;";
+        assert!(moved_blocks(reindent).is_empty());
+
+        // Only closing braces
+        let braces = "@@ -1,4 +1,4 @@\n-}\n-}\n-}\n fn f() {\n+}\n+}\n+}";
+        assert!(moved_blocks(braces).is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+use std::ops::Range;
+
+/// diff 内検索の一致箇所
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// diff の行インデックス
+    pub line: usize,
+    /// 行テキスト中のバイト範囲
+    pub range: Range<usize>,
+}
+
+/// 各行から `query` の一致箇所を重ならないように集める
+///
+/// 大文字小文字の無視は ASCII のみ（バイト位置が変わらないため、そのまま描画の
+/// スパン分割に使える）。空クエリは何にも一致しない。
+pub fn find_search_matches<S: AsRef<str>>(
+    lines: impl IntoIterator<Item = S>,
+    query: &str,
+    case_sensitive: bool,
+) -> Vec<SearchMatch> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query = if case_sensitive {
+        query.to_string()
+    } else {
+        query.to_ascii_lowercase()
+    };
+
+    let mut matches = Vec::new();
+    for (line, text) in lines.into_iter().enumerate() {
+        let text = text.as_ref();
+        let haystack = if case_sensitive {
+            std::borrow::Cow::Borrowed(text)
+        } else {
+            std::borrow::Cow::Owned(text.to_ascii_lowercase())
+        };
+        matches.extend(
+            haystack
+                .match_indices(&query)
+                .map(|(start, _)| SearchMatch {
+                    line,
+                    range: start..start + query.len(),
+                }),
+        );
+    }
+    matches
+}
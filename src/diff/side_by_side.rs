@@ -0,0 +1,255 @@
+use super::{classify_hunk_line, parse_hunk_header};
+
+/// One side (old or new) of a side-by-side row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideLine {
+    pub line_number: u32,
+    /// Index of this line within the patch (cursor position in the diff view).
+    pub patch_line: usize,
+    pub content: String,
+    /// Removed (old side) or added (new side) rather than context.
+    pub changed: bool,
+}
+
+/// A row of a side-by-side hunk. A side is `None` when the other side has
+/// no counterpart (e.g. every row of a pure-add hunk has no old side).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideBySideRow {
+    pub old: Option<SideLine>,
+    pub new: Option<SideLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideBySideHunk {
+    /// The `@@ ... @@` header line.
+    pub header: String,
+    pub rows: Vec<SideBySideRow>,
+}
+
+/// A row of a whole-file side-by-side layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SplitDiffRow {
+    /// The `@@ ... @@` header line, spanning both columns.
+    Header {
+        patch_line: usize,
+        text: String,
+    },
+    Pair(SideBySideRow),
+}
+
+impl SplitDiffRow {
+    /// Whether this row displays patch line `line` (on either side).
+    pub fn contains_patch_line(&self, line: usize) -> bool {
+        match self {
+            Self::Header { patch_line, .. } => *patch_line == line,
+            Self::Pair(row) => [&row.old, &row.new]
+                .into_iter()
+                .flatten()
+                .any(|side| side.patch_line == line),
+        }
+    }
+
+    /// The first patch line shown in this row.
+    pub fn first_patch_line(&self) -> usize {
+        match self {
+            Self::Header { patch_line, .. } => *patch_line,
+            Self::Pair(row) => [&row.old, &row.new]
+                .into_iter()
+                .flatten()
+                .map(|side| side.patch_line)
+                .min()
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Pair the old/new lines of the hunk containing patch line `line`.
+///
+/// Context lines appear on both sides. Within a run of changes, the n-th
+/// removed line is paired with the n-th added line; the longer side's
+/// remaining lines get an empty counterpart. Returns `None` when `line` is
+/// not inside a hunk.
+pub fn side_by_side_hunk(patch: &str, line: usize) -> Option<SideBySideHunk> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let start = (0..=line.min(lines.len().checked_sub(1)?))
+        .rev()
+        .find(|&i| lines[i].starts_with("@@"))?;
+    Some(SideBySideHunk {
+        header: lines[start].to_string(),
+        rows: pair_hunk_lines(&lines, start),
+    })
+}
+
+/// Pair the old/new lines of every hunk in `patch` (see [`side_by_side_hunk`]).
+///
+/// Each hunk contributes a [`SplitDiffRow::Header`] followed by its rows;
+/// file header lines before the first hunk are skipped.
+pub fn side_by_side_rows(patch: &str) -> Vec<SplitDiffRow> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let mut rows = Vec::new();
+    for (start, header) in lines.iter().enumerate() {
+        if !header.starts_with("@@") {
+            continue;
+        }
+        rows.push(SplitDiffRow::Header {
+            patch_line: start,
+            text: header.to_string(),
+        });
+        rows.extend(
+            pair_hunk_lines(&lines, start)
+                .into_iter()
+                .map(SplitDiffRow::Pair),
+        );
+    }
+    rows
+}
+
+/// `lines[start]` のハンクヘッダに続く本体行を左右の行に対応付ける
+fn pair_hunk_lines(lines: &[&str], start: usize) -> Vec<SideBySideRow> {
+    let header = lines[start];
+    let (mut old_number, mut new_number) = parse_hunk_header(header).unwrap_or((1, 1));
+    let mut combined = header.starts_with("@@@");
+
+    let mut rows = Vec::new();
+    let mut removed: Vec<SideLine> = Vec::new();
+    let mut added: Vec<SideLine> = Vec::new();
+    for (patch_line, raw) in lines
+        .iter()
+        .enumerate()
+        .skip(start + 1)
+        .take_while(|(_, l)| !l.starts_with("@@"))
+        // "\ No newline at end of file"
+        .filter(|(_, l)| !l.starts_with('\\'))
+    {
+        let (line_type, content) = classify_hunk_line(raw, &mut combined);
+        // 結合 diff の行は、1 つ目の親（左）と結果（右）のどちらにあるかで振り分ける
+        match (line_type.in_old_file(), line_type.in_new_file()) {
+            (true, false) => {
+                removed.push(SideLine {
+                    line_number: old_number,
+                    patch_line,
+                    content: content.to_string(),
+                    changed: true,
+                });
+                old_number += 1;
+            }
+            (false, true) => {
+                added.push(SideLine {
+                    line_number: new_number,
+                    patch_line,
+                    content: content.to_string(),
+                    changed: true,
+                });
+                new_number += 1;
+            }
+            (true, true) => {
+                flush_changes(&mut rows, &mut removed, &mut added);
+                let side = |line_number| SideLine {
+                    line_number,
+                    patch_line,
+                    content: content.to_string(),
+                    changed: false,
+                };
+                rows.push(SideBySideRow {
+                    old: Some(side(old_number)),
+                    new: Some(side(new_number)),
+                });
+                old_number += 1;
+                new_number += 1;
+            }
+            (false, false) => {}
+        }
+    }
+    flush_changes(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+fn flush_changes(
+    rows: &mut Vec<SideBySideRow>,
+    removed: &mut Vec<SideLine>,
+    added: &mut Vec<SideLine>,
+) {
+    let mut old = removed.drain(..);
+    let mut new = added.drain(..);
+    loop {
+        let row = SideBySideRow {
+            old: old.next(),
+            new: new.next(),
+        };
+        if row.old.is_none() && row.new.is_none() {
+            break;
+        }
+        rows.push(row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(hunk: &SideBySideHunk) -> Vec<(Option<u32>, Option<u32>)> {
+        hunk.rows
+            .iter()
+            .map(|r| {
+                (
+                    r.old.as_ref().map(|s| s.line_number),
+                    r.new.as_ref().map(|s| s.line_number),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_side_by_side_pairs_changes_and_pads_shorter_side() {
+        let patch = "@@ -1,2 +1,1 @@\n a\n-b\n@@ -10,4 +9,5 @@\n ctx\n-old1\n-old2\n+new1\n+new2\n+new3\n tail";
+        // 2 つ目の hunk 内の行を指定
+        let hunk = side_by_side_hunk(patch, 6).unwrap();
+
+        assert_eq!(hunk.header, "@@ -10,4 +9,5 @@");
+        assert_eq!(
+            numbers(&hunk),
+            vec![
+                (Some(10), Some(9)),
+                (Some(11), Some(10)),
+                (Some(12), Some(11)),
+                (None, Some(12)),
+                (Some(13), Some(13)),
+            ]
+        );
+        assert_eq!(hunk.rows[1].old.as_ref().unwrap().content, "old1");
+        assert_eq!(hunk.rows[1].new.as_ref().unwrap().content, "new1");
+        assert!(!hunk.rows[4].new.as_ref().unwrap().changed);
+    }
+
+    #[test]
+    fn test_side_by_side_pure_add_and_delete() {
+        let added = side_by_side_hunk("@@ -0,0 +1,2 @@\n+x\n+y", 0).unwrap();
+        assert_eq!(numbers(&added), vec![(None, Some(1)), (None, Some(2))]);
+
+        let deleted =
+            side_by_side_hunk("@@ -5,1 +4,0 @@\n-gone\n\\ No newline at end of file", 1).unwrap();
+        assert_eq!(numbers(&deleted), vec![(Some(5), None)]);
+
+        assert!(side_by_side_hunk("diff --git a/x b/x\n@@ -1 +1 @@\n-a\n+b", 0).is_none());
+        assert!(side_by_side_hunk("", 0).is_none());
+    }
+
+    #[test]
+    fn test_side_by_side_combined_hunk_uses_first_parent() {
+        let patch = "@@@ -1,2 -1,2 +1,3 @@@\n  keep\n- gone\n +theirs\n++both";
+        let hunk = side_by_side_hunk(patch, 1).unwrap();
+        assert_eq!(
+            numbers(&hunk),
+            vec![
+                (Some(1), Some(1)),
+                (Some(2), None),
+                (Some(3), Some(2)),
+                (None, Some(3)),
+            ]
+        );
+        assert_eq!(hunk.rows[1].old.as_ref().unwrap().content, "gone");
+        // " +" は 1 つ目の親には既にある行なので、左右に並ぶ
+        assert_eq!(hunk.rows[2].new.as_ref().unwrap().content, "theirs");
+        assert_eq!(hunk.rows[3].new.as_ref().unwrap().content, "both");
+    }
+}
@@ -0,0 +1,40 @@
+use super::{is_file_header, parse_unified_diff};
+
+/// Incremental counterpart of [`parse_unified_diff`].
+///
+/// Feed the diff line by line as it arrives; a file's patch is returned as
+/// soon as the next file header is seen, and [`UnifiedDiffStream::finish`]
+/// flushes the last one. Files are yielded in diff order.
+#[derive(Debug, Default)]
+pub struct UnifiedDiffStream {
+    /// 現在のファイルのセクション（ファイルヘッダ行から次のヘッダ直前まで）
+    section: String,
+}
+
+impl UnifiedDiffStream {
+    /// 1 行を追加する（末尾の改行はあってもなくてもよい）。
+    /// 直前のファイルが確定した場合、その `(filename, patch)` を返す。
+    pub fn push_line(&mut self, line: &str) -> Option<(String, String)> {
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        let completed = if is_file_header(line.strip_suffix('\r').unwrap_or(line)) {
+            self.take_section()
+        } else {
+            None
+        };
+        self.section.push_str(line);
+        self.section.push('\n');
+        completed
+    }
+
+    /// 入力終了。最後のファイルを返す。
+    pub fn finish(mut self) -> Option<(String, String)> {
+        self.take_section()
+    }
+
+    fn take_section(&mut self) -> Option<(String, String)> {
+        // セクションはファイル 1 つ分なので結果は高々 1 件
+        parse_unified_diff(&std::mem::take(&mut self.section))
+            .into_iter()
+            .next()
+    }
+}
@@ -0,0 +1,111 @@
+//! Hide changes that only touch leading/trailing whitespace.
+
+use super::{LineType, PatchIndex};
+
+/// 削除行と追加行が前後の空白を除いて一致するか
+fn same_ignoring_whitespace(removed: &str, added: &str) -> bool {
+    removed[1..].trim() == added[1..].trim()
+}
+
+/// 削除行の連続と直後の追加行の連続を先頭から対にし、空白だけが違う対を文脈行にする
+///
+/// 対にならなかった行は元の順（削除 → 追加）のまま、文脈行の手前に出す。
+fn flush_block(
+    removed: &mut Vec<&str>,
+    added: &mut Vec<&str>,
+    out: &mut Vec<String>,
+    changed: &mut bool,
+) {
+    fn emit_pending(out: &mut Vec<String>, removed: &mut Vec<&str>, added: &mut Vec<&str>) {
+        out.extend(removed.drain(..).chain(added.drain(..)).map(str::to_string));
+    }
+
+    let mut pending_removed = Vec::new();
+    let mut pending_added = Vec::new();
+
+    let pairs = removed.len().min(added.len());
+    for (r, a) in removed.iter().zip(added.iter()).take(pairs) {
+        if same_ignoring_whitespace(r, a) {
+            emit_pending(out, &mut pending_removed, &mut pending_added);
+            out.push(format!(" {}", &a[1..]));
+            *changed = true;
+        } else {
+            pending_removed.push(*r);
+            pending_added.push(*a);
+        }
+    }
+    pending_removed.extend(&removed[pairs..]);
+    pending_added.extend(&added[pairs..]);
+    emit_pending(out, &mut pending_removed, &mut pending_added);
+    removed.clear();
+    added.clear();
+}
+
+/// 前後の空白だけが違う削除行・追加行の対を文脈行にまとめた patch を返す
+///
+/// 文脈行には新しい側の内容を使う。対 1 組は旧・新それぞれ 1 行ずつ数えるので、
+/// hunk ヘッダの行数と以降の行番号はそのまま使える。変化が無ければ `None`。
+pub fn hide_whitespace_changes(patch: &str) -> Option<String> {
+    let index = PatchIndex::build(patch);
+    let mut out = Vec::new();
+    let mut removed: Vec<&str> = Vec::new();
+    let mut added: Vec<&str> = Vec::new();
+    let mut changed = false;
+
+    for (i, line) in patch.lines().enumerate() {
+        match index.get(i).map(|info| info.line_type) {
+            Some(LineType::Removed) if line.starts_with('-') => {
+                if !added.is_empty() {
+                    flush_block(&mut removed, &mut added, &mut out, &mut changed);
+                }
+                removed.push(line);
+            }
+            Some(LineType::Added) if line.starts_with('+') && !removed.is_empty() => {
+                added.push(line);
+            }
+            _ => {
+                flush_block(&mut removed, &mut added, &mut out, &mut changed);
+                out.push(line.to_string());
+            }
+        }
+    }
+    flush_block(&mut removed, &mut added, &mut out, &mut changed);
+
+    if !changed {
+        return None;
+    }
+    let mut hidden = out.join("\n");
+    if patch.ends_with('\n') {
+        hidden.push('\n');
+    }
+    Some(hidden)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whitespace_only_pair_collapses_to_context() {
+        let patch = "@@ -1,3 +1,3 @@\n keep\n-foo \n+foo\n-foo\n+bar\n";
+        assert_eq!(
+            hide_whitespace_changes(patch).unwrap(),
+            "@@ -1,3 +1,3 @@\n keep\n foo\n-foo\n+bar\n"
+        );
+        assert_eq!(hide_whitespace_changes("@@ -1 +1 @@\n-foo\n+bar"), None);
+    }
+
+    #[test]
+    fn test_unpaired_lines_stay_before_collapsed_context() {
+        // 2 組目だけが空白差分: 1 組目と余った削除行は文脈行より前に残る
+        let patch = "@@ -1,3 +1,2 @@\n-a\n-\tb\n-c\n+x\n+b  ";
+        let hidden = hide_whitespace_changes(patch).unwrap();
+        assert_eq!(hidden, "@@ -1,3 +1,2 @@\n-a\n+x\n b  \n-c");
+        // 行番号は展開前と同じ
+        let info = crate::diff::get_line_info(&hidden, 3).unwrap();
+        assert_eq!(
+            (info.old_line_number, info.new_line_number),
+            (Some(2), Some(2))
+        );
+    }
+}
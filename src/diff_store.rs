@@ -3,7 +3,7 @@ use std::hash::Hash;
 use tokio::sync::mpsc;
 
 use crate::app::DiffCache;
-use crate::syntax::ParserPool;
+use crate::syntax::{ParserPool, SharedTreeCache};
 use crate::ui::diff_view::build_diff_cache;
 
 /// ハイライトキャッシュストアの最大エントリ数（メモリ上限）
@@ -152,6 +152,12 @@ impl DiffScrollState {
         self.scroll_offset = 0;
     }
 
+    /// 指定行へ移動し、ビューポートに収まるようスクロールする
+    pub fn jump_to(&mut self, line: usize) {
+        self.selected_line = line.min(self.line_count.saturating_sub(1));
+        self.adjust_scroll(self.visible_lines);
+    }
+
     pub fn jump_to_last(&mut self) {
         if self.line_count > 0 {
             self.selected_line = self.line_count.saturating_sub(1);
@@ -189,6 +195,8 @@ pub struct DiffCacheStore<K: Hash + Eq + Clone + Send + 'static> {
     max_store_entries: usize,
     highlight_rx: Option<mpsc::Receiver<(K, DiffCache)>>,
     prefetch_rx: Option<mpsc::Receiver<(K, DiffCache)>>,
+    /// ファイルごとの前回パース結果（local mode のリフレッシュ時に増分パースで再利用）
+    tree_cache: SharedTreeCache,
 }
 
 impl<K: Hash + Eq + Clone + Send + 'static> DiffCacheStore<K> {
@@ -200,6 +208,19 @@ impl<K: Hash + Eq + Clone + Send + 'static> DiffCacheStore<K> {
             max_store_entries,
             highlight_rx: None,
             prefetch_rx: None,
+            tree_cache: SharedTreeCache::default(),
+        }
+    }
+
+    /// ハイライト構築用の ParserPool を作る
+    ///
+    /// `reuse_trees` が true なら前回の tree-sitter ツリーを増分パースに再利用する。
+    /// 同じファイルが内容を少しずつ変えて再構築される local mode 向け。
+    pub fn parser_pool(&self, reuse_trees: bool) -> ParserPool {
+        if reuse_trees {
+            ParserPool::with_tree_cache(self.tree_cache.clone())
+        } else {
+            ParserPool::new()
         }
     }
 
@@ -345,6 +366,11 @@ impl<K: Hash + Eq + Clone + Send + 'static> DiffCacheStore<K> {
         self.prefetch_rx = None;
     }
 
+    /// highlight_rx をドロップ（構築中のハイライト結果を破棄）
+    pub fn drop_highlight_rx(&mut self) {
+        self.highlight_rx = None;
+    }
+
     /// 全ファイルのハイライト済みキャッシュをバックグラウンドで事前構築
     ///
     /// 呼び出し側は未キャッシュのアイテム一覧を収集して渡す。
@@ -355,17 +381,18 @@ impl<K: Hash + Eq + Clone + Send + 'static> DiffCacheStore<K> {
         theme: &str,
         markdown_rich: bool,
         tab_width: u8,
+        reuse_trees: bool,
     ) {
         if items.is_empty() {
             return;
         }
+        let mut parser_pool = self.parser_pool(reuse_trees);
         let channel_size = items.len();
         let (tx, rx) = mpsc::channel(channel_size);
         self.prefetch_rx = Some(rx);
 
         let theme = theme.to_string();
         tokio::task::spawn_blocking(move || {
-            let mut parser_pool = ParserPool::new();
             for item in items {
                 let mut cache = build_diff_cache(
                     &item.patch,
@@ -428,6 +455,27 @@ impl<K: Hash + Eq + Clone + Send + 'static> DiffCacheStore<K> {
         self.prefetch_rx = None;
     }
 
+    /// store に直接格納する（上限超過時は格納せず false）
+    pub fn insert_store(&mut self, key: K, cache: DiffCache) -> bool {
+        if self.store.len() >= self.max_store_entries {
+            return false;
+        }
+        self.store.insert(key, cache);
+        true
+    }
+
+    /// highlighted な current と store を取り出す（キーの付け替え用）
+    pub fn drain_highlighted(&mut self) -> Vec<(K, DiffCache)> {
+        let mut entries: Vec<(K, DiffCache)> = self.store.drain().collect();
+        if let (Some(key), Some(cache)) = (self.current_key.clone(), self.current.as_ref()) {
+            if cache.highlighted {
+                entries.push((key, self.current.take().unwrap()));
+                self.current_key = None;
+            }
+        }
+        entries
+    }
+
     /// 旧 current を store に退避（highlighted のみ）
     fn retire_current(&mut self) {
         if let Some(old) = self.current.take() {
@@ -443,6 +491,62 @@ impl<K: Hash + Eq + Clone + Send + 'static> DiffCacheStore<K> {
     }
 }
 
+// ========================================
+// HighlightMemo
+// ========================================
+
+/// local mode のリフレッシュを跨いで再利用するハイライト済みキャッシュ
+///
+/// `DiffCacheStore<usize>` は file_index がキーなので、ファイル一覧が更新されるたびに
+/// 破棄される。その前に `(filename, patch_hash)` をキーにして退避し、patch が
+/// 変わっていないファイルは再ハイライトせずに戻す。テーマが変わったら全破棄する。
+#[derive(Default)]
+pub struct HighlightMemo {
+    theme: String,
+    entries: HashMap<(String, u64), DiffCache>,
+}
+
+impl HighlightMemo {
+    /// ハイライト済みキャッシュを退避する（plain は無視）
+    pub fn insert(&mut self, filename: String, theme: &str, cache: DiffCache) {
+        self.sync_theme(theme);
+        if cache.highlighted && self.entries.len() < MAX_STORE_ENTRIES {
+            self.entries.insert((filename, cache.patch_hash), cache);
+        }
+    }
+
+    /// patch が変わっていないファイルのキャッシュを取り出す
+    pub fn take(&mut self, filename: &str, patch_hash: u64, theme: &str) -> Option<DiffCache> {
+        self.sync_theme(theme);
+        self.entries.remove(&(filename.to_string(), patch_hash))
+    }
+
+    /// `keep(filename, patch_hash)` が false のエントリを捨てる
+    pub fn retain(&mut self, keep: impl Fn(&str, u64) -> bool) {
+        self.entries
+            .retain(|(filename, hash), _| keep(filename, *hash));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn sync_theme(&mut self, theme: &str) {
+        if self.theme != theme {
+            self.entries.clear();
+            self.theme = theme.to_string();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -838,6 +942,11 @@ mod tests {
             interner: Rodeo::default(),
             highlighted,
             markdown_rich: false,
+            line_ending_hunks: Vec::new(),
+            line_ending: crate::diff::LineEnding::Lf,
+            context_runs: Vec::new(),
+            conflict_lines: Vec::new(),
+            moved_blocks: Vec::new(),
         }
     }
 
@@ -1103,4 +1212,46 @@ mod tests {
         assert!(!store.store_contains_key(&2));
         assert_eq!(store.store_len(), 2);
     }
+
+    #[test]
+    fn test_drain_highlighted_skips_plain_current() {
+        let mut store = DiffCacheStore::<usize>::new(50);
+        store.set_current(0, make_cache(true, 100));
+        store.set_current(1, make_cache(false, 200));
+        let mut drained = store.drain_highlighted();
+        drained.sort_by_key(|(k, _)| *k);
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].0, 0);
+        // plain の current はそのまま
+        assert_eq!(store.current_key(), Some(&1));
+        assert_eq!(store.store_len(), 0);
+    }
+
+    // ========================================
+    // HighlightMemo テスト
+    // ========================================
+
+    #[test]
+    fn test_highlight_memo_reuses_same_patch_and_resets_on_theme_change() {
+        let mut memo = HighlightMemo::default();
+        memo.insert("a.rs".to_string(), "base16", make_cache(true, 100));
+        memo.insert("b.rs".to_string(), "base16", make_cache(false, 200));
+        // plain は退避しない
+        assert_eq!(memo.len(), 1);
+
+        // patch が変わっていれば再利用しない
+        assert!(memo.take("a.rs", 101, "base16").is_none());
+        let cache = memo.take("a.rs", 100, "base16").unwrap();
+        assert_eq!(cache.patch_hash, 100);
+        assert!(memo.is_empty());
+
+        memo.insert("a.rs".to_string(), "base16", make_cache(true, 100));
+        assert!(memo.take("a.rs", 100, "Dracula").is_none());
+        assert!(memo.is_empty());
+
+        memo.insert("a.rs".to_string(), "Dracula", make_cache(true, 100));
+        memo.insert("c.rs".to_string(), "Dracula", make_cache(true, 300));
+        memo.retain(|name, _| name != "c.rs");
+        assert_eq!(memo.len(), 1);
+    }
 }
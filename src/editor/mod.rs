@@ -2,13 +2,36 @@ use anyhow::Result;
 use std::borrow::Cow;
 use std::env;
 use std::fs;
+use std::path::Path;
 use std::process::Command;
 use tempfile::NamedTempFile;
 
+/// コメント本文は Markdown なので `.md` で開く
+const MARKDOWN_SUFFIX: &str = ".md";
+
 /// エディタのテンプレート設定
 struct EditorTemplate<'a> {
     header: Cow<'a, str>,
     initial_content: Option<Cow<'a, str>>,
+    /// `initial_content` が参照用の引用か（手付かずのまま残っていれば空コメント扱い）
+    initial_is_quote: bool,
+    /// 一時ファイルの拡張子（エディタの filetype 判定用）
+    suffix: Cow<'a, str>,
+}
+
+/// suggestion 用: 対象ファイルの拡張子をそのまま使う（拡張子なしなら付けない）
+fn suffix_for_filename(filename: &str) -> Cow<'static, str> {
+    Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(Cow::Borrowed(""), |ext| Cow::Owned(format!(".{}", ext)))
+}
+
+fn create_editor_temp_file(template: &EditorTemplate<'_>) -> Result<NamedTempFile> {
+    Ok(tempfile::Builder::new()
+        .prefix("octorus-")
+        .suffix(template.suffix.as_ref())
+        .tempfile()?)
 }
 
 /// Check whether a command can be found in PATH and is executable.
@@ -98,9 +121,9 @@ fn open_editor_internal(
     editor: Option<&str>,
     template: EditorTemplate<'_>,
 ) -> Result<Option<String>> {
-    let temp_file = NamedTempFile::new()?;
+    let temp_file = create_editor_temp_file(&template)?;
 
-    let content = if let Some(initial) = template.initial_content {
+    let content = if let Some(initial) = template.initial_content.as_deref() {
         format!("{}\n\n{}", template.header, initial)
     } else {
         format!("{}\n\n", template.header)
@@ -118,33 +141,70 @@ fn open_editor_internal(
     }
 
     let content = fs::read_to_string(temp_file.path())?;
-    let body = extract_comment_body(&content);
+    Ok(finish_editor_content(&content, &template))
+}
+
+/// 保存された内容から本文を取り出す。空（引用だけが手付かずで残った場合を含む）なら None。
+fn finish_editor_content(content: &str, template: &EditorTemplate<'_>) -> Option<String> {
+    let body = extract_comment_body(content);
+    let remaining = match template.initial_content.as_deref() {
+        Some(quote) if template.initial_is_quote => body.replacen(quote.trim_end(), "", 1),
+        _ => body.clone(),
+    };
 
-    if body.trim().is_empty() {
-        Ok(None)
+    if remaining.trim().is_empty() {
+        None
     } else {
-        Ok(Some(body))
+        Some(body)
+    }
+}
+
+/// `line_content` を囲む fenced code block。本文中のバッククォート列より長いフェンスを使う。
+fn quoted_code_block(filename: &str, line_content: &str) -> String {
+    let longest_run = line_content
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let lang = Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+    format!("{fence}{lang}\n{line_content}\n{fence}\n")
+}
+
+fn comment_template(
+    filename: &str,
+    line: usize,
+    quoted_line: Option<&str>,
+) -> EditorTemplate<'static> {
+    EditorTemplate {
+        header: Cow::Owned(format!(
+            "<!-- octorus: Enter your comment below -->\n\
+             <!-- File: {} Line: {} -->\n\
+             <!-- Save and close to submit, delete all content to cancel -->",
+            filename, line
+        )),
+        initial_content: quoted_line
+            .map(|content| Cow::Owned(format!("{}\n", quoted_code_block(filename, content)))),
+        initial_is_quote: quoted_line.is_some(),
+        suffix: Cow::Borrowed(MARKDOWN_SUFFIX),
     }
 }
 
 /// Open external editor for comment input
+///
+/// `quoted_line` (the commented line's content, typically passed when
+/// `quote_line_in_comment_editor` is enabled) seeds the body with a fenced
+/// code block. Leaving only that block unchanged cancels like an empty comment.
 pub fn open_comment_editor(
     editor: Option<&str>,
     filename: &str,
     line: usize,
+    quoted_line: Option<&str>,
 ) -> Result<Option<String>> {
-    open_editor_internal(
-        editor,
-        EditorTemplate {
-            header: Cow::Owned(format!(
-                "<!-- octorus: Enter your comment below -->\n\
-                 <!-- File: {} Line: {} -->\n\
-                 <!-- Save and close to submit, delete all content to cancel -->",
-                filename, line
-            )),
-            initial_content: None,
-        },
-    )
+    open_editor_internal(editor, comment_template(filename, line, quoted_line))
 }
 
 /// Open external editor for review submission
@@ -157,11 +217,14 @@ pub fn open_review_editor(editor: Option<&str>) -> Result<Option<String>> {
                  <!-- Save and close to submit -->",
             ),
             initial_content: None,
+            initial_is_quote: false,
+            suffix: Cow::Borrowed(MARKDOWN_SUFFIX),
         },
     )
 }
 
-fn extract_comment_body(content: &str) -> String {
+/// Strip the `<!-- ... -->` template lines and return the comment body.
+pub fn extract_comment_body(content: &str) -> String {
     content
         .lines()
         .filter(|line| !line.trim().starts_with("<!--"))
@@ -169,25 +232,44 @@ fn extract_comment_body(content: &str) -> String {
         .join("\n")
 }
 
+/// `start_line` は選択範囲の先頭行（new side）。複数行なら `Lines: start-end` を表示する
+fn suggestion_template(
+    filename: &str,
+    start_line: usize,
+    original_lines: &[&str],
+) -> EditorTemplate<'static> {
+    let lines = match original_lines.len() {
+        0 | 1 => format!("Line: {}", start_line),
+        n => format!("Lines: {}-{}", start_line, start_line + n - 1),
+    };
+    EditorTemplate {
+        header: Cow::Owned(format!(
+            "<!-- octorus: Edit the code below to create a suggestion -->\n\
+             <!-- File: {} {} -->\n\
+             <!-- Save and close to submit, delete all content to cancel -->",
+            filename, lines
+        )),
+        initial_content: Some(Cow::Owned(original_lines.join("\n"))),
+        initial_is_quote: false,
+        suffix: suffix_for_filename(filename),
+    }
+}
+
 /// Open external editor for suggestion input
 /// Returns the suggested code (without the original template comments)
+///
+/// The temp file is seeded with every selected line and carries the target
+/// file's extension so the editor highlights the suggested code with the
+/// right syntax.
 pub fn open_suggestion_editor(
     editor: Option<&str>,
     filename: &str,
-    line: usize,
-    original_code: &str,
+    start_line: usize,
+    original_lines: &[&str],
 ) -> Result<Option<String>> {
     open_editor_internal(
         editor,
-        EditorTemplate {
-            header: Cow::Owned(format!(
-                "<!-- octorus: Edit the code below to create a suggestion -->\n\
-                 <!-- File: {} Line: {} -->\n\
-                 <!-- Save and close to submit, delete all content to cancel -->",
-                filename, line
-            )),
-            initial_content: Some(Cow::Borrowed(original_code)),
-        },
+        suggestion_template(filename, start_line, original_lines),
     )
 }
 
@@ -208,6 +290,27 @@ pub fn open_file_at_line(editor: Option<&str>, file_path: &str, line: usize) ->
     Ok(())
 }
 
+/// `{editor args} file1 file2 ...` の引数列を組み立てる
+fn editor_args_for_files(editor_args: &[String], file_paths: &[String]) -> Vec<String> {
+    editor_args.iter().chain(file_paths).cloned().collect()
+}
+
+/// Open all given files in a single editor invocation (e.g. `hx a.rs b.rs`).
+///
+/// The caller is responsible for suspending/restoring the TUI terminal.
+pub fn open_files(editor: Option<&str>, file_paths: &[String]) -> Result<()> {
+    let (cmd, args) = resolve_and_split_editor(editor)?;
+    let mut command = Command::new(&cmd);
+    command.args(editor_args_for_files(&args, file_paths));
+    let status = run_editor_command(&cmd, command)?;
+
+    if !status.success() {
+        anyhow::bail!("Editor exited with non-zero status");
+    }
+
+    Ok(())
+}
+
 /// Open external editor for AI Rally clarification response
 /// Returns the user's answer to the clarification question
 pub fn open_clarification_editor(editor: Option<&str>, question: &str) -> Result<Option<String>> {
@@ -222,6 +325,8 @@ pub fn open_clarification_editor(editor: Option<&str>, question: &str) -> Result
                 question
             )),
             initial_content: None,
+            initial_is_quote: false,
+            suffix: Cow::Borrowed(MARKDOWN_SUFFIX),
         },
     )
 }
@@ -481,8 +586,105 @@ mod tests {
         assert!(msg.contains("config.toml"));
     }
 
+    // ── open_files argument assembly ──
+
+    #[test]
+    fn test_editor_args_for_files() {
+        let files = vec!["/repo/src/a.rs".to_string(), "/repo/b c.md".to_string()];
+        // エディタ設定の引数の後ろに全ファイルを並べる（1 回の起動で開く）
+        assert_eq!(editor_args_for_files(&[], &files), files);
+        assert_eq!(
+            editor_args_for_files(&["-c".to_string(), "-a".to_string()], &files),
+            vec!["-c", "-a", "/repo/src/a.rs", "/repo/b c.md"]
+        );
+        assert!(editor_args_for_files(&[], &[]).is_empty());
+    }
+
     // ── helpers ──
 
+    // ── temp file suffix tests ──
+
+    #[test]
+    fn test_comment_editor_temp_file_is_markdown() {
+        let file = create_editor_temp_file(&comment_template("src/main.rs", 10, None)).unwrap();
+        assert_eq!(file.path().extension().and_then(|e| e.to_str()), Some("md"));
+    }
+
+    #[test]
+    fn test_suggestion_editor_temp_file_uses_target_extension() {
+        let file =
+            create_editor_temp_file(&suggestion_template("src/app/mod.rs", 3, &["let x = 1;"]))
+                .unwrap();
+        assert_eq!(file.path().extension().and_then(|e| e.to_str()), Some("rs"));
+
+        let file = create_editor_temp_file(&suggestion_template("Makefile", 1, &["all:"])).unwrap();
+        assert_eq!(file.path().extension(), None);
+    }
+
+    #[test]
+    fn test_suggestion_template_contains_each_selected_line() {
+        let lines = ["fn main() {", "    let x = 1;", "}"];
+        let template = suggestion_template("src/main.rs", 10, &lines);
+        assert!(template.header.contains("File: src/main.rs Lines: 10-12"));
+
+        let body = template.initial_content.unwrap();
+        for line in lines {
+            assert!(body.lines().any(|l| l == line), "missing line: {line}");
+        }
+        // エディタ保存後も全行が本文として残る
+        let content = format!("{}\n\n{}", template.header, body);
+        assert_eq!(extract_comment_body(&content).trim(), lines.join("\n"));
+
+        let single = suggestion_template("src/main.rs", 7, &["let y = 2;"]);
+        assert!(single.header.contains("Line: 7 -->"));
+    }
+
+    #[test]
+    fn test_comment_template_quotes_original_line() {
+        let template = comment_template("src/main.rs", 42, Some("    let x = 1;"));
+        assert!(template.header.contains("File: src/main.rs Line: 42"));
+        let body = template.initial_content.as_deref().unwrap();
+        assert!(body.starts_with("```rs\n    let x = 1;\n```\n"));
+
+        // バッククォートを含む行は長いフェンスで囲む
+        let block = quoted_code_block("README.md", "use ```rust``` here");
+        assert!(block.starts_with("````md\n"));
+        assert!(block.ends_with("\n````\n"));
+
+        assert!(comment_template("src/main.rs", 42, None)
+            .initial_content
+            .is_none());
+    }
+
+    #[test]
+    fn test_unchanged_quote_cancels_comment() {
+        let template = comment_template("src/main.rs", 42, Some("let x = 1;"));
+        let seeded = format!(
+            "{}\n\n{}",
+            template.header,
+            template.initial_content.as_deref().unwrap()
+        );
+        assert_eq!(finish_editor_content(&seeded, &template), None);
+        assert_eq!(
+            finish_editor_content(&format!("{}\n\n", template.header), &template),
+            None
+        );
+
+        // 引用の周りに書いたコメントは引用ごと残す
+        let commented = format!("{}\nWhy not a const?\n", seeded);
+        let body = finish_editor_content(&commented, &template).unwrap();
+        assert!(body.contains("```rs\nlet x = 1;\n```"));
+        assert!(body.contains("Why not a const?"));
+
+        // suggestion の元コードは引用扱いしない
+        let suggestion = suggestion_template("src/main.rs", 42, &["let x = 1;"]);
+        let content = format!("{}\n\nlet x = 1;", suggestion.header);
+        assert_eq!(
+            finish_editor_content(&content, &suggestion).as_deref(),
+            Some("\nlet x = 1;")
+        );
+    }
+
     fn restore_env(orig_visual: Option<String>, orig_editor: Option<String>) {
         match orig_visual {
             Some(v) => env::set_var("VISUAL", v),
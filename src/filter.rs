@@ -1,3 +1,5 @@
+use crate::syntax::linguist::pattern_matches;
+
 /// リストフィルタ機能
 ///
 /// PR一覧やファイル一覧でキーワードによる絞り込みを提供する。
@@ -139,10 +141,38 @@ impl ListFilter {
     }
 }
 
+/// ファイル名が glob パターン（`filter_files`）に一致するか
+///
+/// 書式は `.gitattributes` と同じで、`/` を含まないパターンは任意の階層のファイル名に、
+/// 含むパターンはルートからのパスに一致する（`**` は階層を跨ぐ）。
+/// 先頭の `!` で一致しないファイルを残す。
+pub fn file_glob_matches(pattern: &str, filename: &str) -> bool {
+    let pattern = pattern.trim();
+    match pattern.strip_prefix('!') {
+        Some(negated) => !pattern_matches(negated, filename),
+        None => pattern_matches(pattern, filename),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_file_glob_matches() {
+        assert!(file_glob_matches("src/**/*.rs", "src/main.rs"));
+        assert!(file_glob_matches("src/**/*.rs", "src/app/mod.rs"));
+        assert!(!file_glob_matches("src/**/*.rs", "tests/app.rs"));
+        assert!(!file_glob_matches("src/**/*.rs", "src/lib.ts"));
+        // `/` を含まないパターンはどの階層のファイル名にも一致する
+        assert!(file_glob_matches("*.lock", "Cargo.lock"));
+        assert!(file_glob_matches("*.lock", "web/yarn.lock"));
+        assert!(!file_glob_matches("!*.lock", "Cargo.lock"));
+        assert!(!file_glob_matches("!*.lock", "web/yarn.lock"));
+        assert!(file_glob_matches("!*.lock", "src/main.rs"));
+        assert!(file_glob_matches(" *.md ", "README.md"));
+    }
+
     #[test]
     fn test_new_filter_is_input_active() {
         let filter = ListFilter::new();
@@ -0,0 +1,964 @@
+//! GitHub へのアクセス手段の抽象化
+//!
+//! GitHub との通信はすべて [`GitHubBackend`] を経由する。既定は `gh` CLI、
+//! `[github] backend = "rest"` で `GITHUB_TOKEN` を使って REST / GraphQL API を直接呼ぶ実装に切り替わる。
+//! バックエンドは起動時に設定から作り、`App` やローダーに引数として渡す。
+
+use std::cell::Cell;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use super::client::{
+    flatten_pages, is_retryable_gh_error, run_gh_with_retry, GhRetryPolicy, GhRunner,
+    SystemGhRunner,
+};
+use super::comment::{DraftReviewComment, ReviewComment};
+use super::issue::{self, IssueDetail, IssueStateFilter, IssueSummary};
+use super::pr::{
+    self, CheckItem, PrListFilters, PrPeopleRole, PrStateFilter, PullRequest, PullRequestSummary,
+};
+use crate::app::ReviewAction;
+use crate::config::{GithubBackendKind, GithubConfig};
+
+/// PR ファイル一覧・コメント API の 1 ページあたりの件数（REST の上限）
+pub(super) const PER_PAGE: usize = 100;
+
+/// diff を 1 行ずつ受け取るコールバック（`fold_pr_diff_lines` 用）
+pub type LineSink = Box<dyn FnMut(&str) + Send>;
+
+/// HTTP メソッド
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiMethod {
+    Get,
+    Post,
+    Delete,
+}
+
+impl ApiMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Delete => "DELETE",
+        }
+    }
+}
+
+/// REST API 呼び出し 1 回分。`endpoint` は `repos/...` のような API ルートからの相対パス
+#[derive(Debug, Clone)]
+pub struct ApiRequest<'a> {
+    pub method: ApiMethod,
+    pub endpoint: &'a str,
+    /// JSON の送信本文
+    pub body: Option<Value>,
+    /// `Accept` ヘッダ（None なら JSON）
+    pub accept: Option<&'static str>,
+}
+
+impl<'a> ApiRequest<'a> {
+    pub fn get(endpoint: &'a str) -> Self {
+        Self {
+            method: ApiMethod::Get,
+            endpoint,
+            body: None,
+            accept: None,
+        }
+    }
+
+    pub fn post(endpoint: &'a str, body: Value) -> Self {
+        Self {
+            method: ApiMethod::Post,
+            endpoint,
+            body: Some(body),
+            accept: None,
+        }
+    }
+
+    pub fn delete(endpoint: &'a str) -> Self {
+        Self {
+            method: ApiMethod::Delete,
+            endpoint,
+            body: None,
+            accept: None,
+        }
+    }
+
+    pub fn accept(mut self, media_type: &'static str) -> Self {
+        self.accept = Some(media_type);
+        self
+    }
+
+    /// 副作用のない読み取りか（GET と mutation 以外の GraphQL）
+    ///
+    /// 読み取りはタイムアウト時にも再試行してよい。
+    pub fn is_read(&self) -> bool {
+        match self.method {
+            ApiMethod::Get => true,
+            ApiMethod::Post if self.endpoint == "graphql" => self
+                .body
+                .as_ref()
+                .and_then(|body| body.get("query")?.as_str())
+                .is_some_and(|query| !query.trim_start().starts_with("mutation")),
+            _ => false,
+        }
+    }
+}
+
+/// GitHub との通信
+///
+/// 実装が必須なのは [`GitHubBackend::api`] だけで、その他は API 呼び出しによる既定実装を持つ。
+/// `gh` CLI 版は一部を `gh pr list` などのサブコマンドで上書きする。
+#[async_trait]
+pub trait GitHubBackend: Send + Sync {
+    /// REST API（`endpoint` が `graphql` なら GraphQL API）を呼び、レスポンス本文を返す
+    async fn api(&self, request: ApiRequest<'_>) -> Result<String>;
+
+    /// GraphQL のクエリ / ミューテーションを実行する（`errors` の確認は呼び出し側で行う）
+    async fn graphql(&self, query: &str, variables: Value) -> Result<Value> {
+        let body = json!({ "query": query, "variables": variables });
+        let text = self.api(ApiRequest::post("graphql", body)).await?;
+        serde_json::from_str(&text).context("Failed to parse GraphQL response as JSON")
+    }
+
+    /// 配列を返す `endpoint`（ページ指定なし）を全ページ取得して 1 つの配列にする
+    async fn api_paginate(&self, endpoint: &str) -> Result<Value> {
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        for page in 1.. {
+            let paged = format!(
+                "{}{}per_page={}&page={}",
+                endpoint, separator, PER_PAGE, page
+            );
+            let batch: Vec<Value> = api_json(
+                self,
+                ApiRequest::get(&paged),
+                "Failed to parse paginated API response",
+            )
+            .await?;
+            let batch_len = batch.len();
+            items.extend(batch);
+            if batch_len < PER_PAGE {
+                break;
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    async fn fetch_pr(&self, repo: &str, pr_number: u32) -> Result<PullRequest> {
+        let endpoint = format!("repos/{}/pulls/{}", repo, pr_number);
+        api_json(
+            self,
+            ApiRequest::get(&endpoint),
+            "Failed to parse PR response",
+        )
+        .await
+    }
+
+    /// 変更ファイル一覧の `page` ページ目（1 始まり）。JSON 配列をそのまま返す
+    async fn fetch_changed_files_page(
+        &self,
+        repo: &str,
+        pr_number: u32,
+        page: usize,
+    ) -> Result<String> {
+        let endpoint = format!(
+            "repos/{}/pulls/{}/files?per_page={}&page={}",
+            repo, pr_number, PER_PAGE, page
+        );
+        self.api(ApiRequest::get(&endpoint)).await
+    }
+
+    async fn fetch_pr_diff(&self, repo: &str, pr_number: u32) -> Result<String> {
+        let endpoint = format!("repos/{}/pulls/{}", repo, pr_number);
+        self.api(ApiRequest::get(&endpoint).accept("application/vnd.github.diff"))
+            .await
+    }
+
+    /// PR の diff を 1 行ずつ `on_line` に渡す（blocking スレッド上で呼ぶ）
+    ///
+    /// 既定では diff 全体を取得してから分割する。
+    async fn fold_pr_diff_lines(
+        &self,
+        repo: &str,
+        pr_number: u32,
+        mut on_line: LineSink,
+    ) -> Result<()> {
+        let diff = self.fetch_pr_diff(repo, pr_number).await?;
+        // `on_line` は blocking_send を使い得るので、ストリーミング時と同じく blocking スレッドで呼ぶ
+        tokio::task::spawn_blocking(move || diff.lines().for_each(&mut on_line))
+            .await
+            .context("diff splitting task panicked")
+    }
+
+    async fn fetch_review_comments(
+        &self,
+        repo: &str,
+        pr_number: u32,
+    ) -> Result<Vec<ReviewComment>> {
+        let json = self
+            .api_paginate(&format!("repos/{}/pulls/{}/comments", repo, pr_number))
+            .await?;
+        serde_json::from_value(json).context("Failed to parse review comments response")
+    }
+
+    /// レビューを送信する（`comments` はレビューと同時に作成される）
+    async fn submit_review(
+        &self,
+        repo: &str,
+        pr_number: u32,
+        action: ReviewAction,
+        body: &str,
+        comments: &[DraftReviewComment],
+    ) -> Result<()> {
+        let endpoint = format!("repos/{}/pulls/{}/reviews", repo, pr_number);
+        self.api(ApiRequest::post(
+            &endpoint,
+            review_body(action, body, comments),
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// 新しい順に最大 `count` 件の PR
+    async fn fetch_pr_list(
+        &self,
+        repo: &str,
+        state: PrStateFilter,
+        filters: &PrListFilters,
+        count: u32,
+    ) -> Result<Vec<PullRequestSummary>> {
+        pr::search_pr_list(self, repo, state, filters, count).await
+    }
+
+    /// `head_ref` を head とする open PR の番号
+    async fn find_open_pr_by_head(&self, repo: &str, head_ref: &str) -> Result<Option<u32>> {
+        let owner = repo.split('/').next().unwrap_or_default();
+        let endpoint = format!(
+            "repos/{}/pulls?state=open&head={}:{}&per_page=1",
+            repo,
+            owner,
+            super::url::encode_path(head_ref)
+        );
+        let text = self.api(ApiRequest::get(&endpoint)).await?;
+        pr::parse_pr_numbers(&text).map(|numbers| numbers.first().copied())
+    }
+
+    async fn fetch_pr_checks(&self, repo: &str, pr_number: u32) -> Result<Vec<CheckItem>> {
+        pr::query_pr_checks(self, repo, pr_number).await
+    }
+
+    async fn add_pr_people(
+        &self,
+        repo: &str,
+        pr_number: u32,
+        role: PrPeopleRole,
+        logins: &[String],
+    ) -> Result<()> {
+        pr::request_pr_people(self, repo, pr_number, role, logins).await
+    }
+
+    /// 新しい順に最大 `count` 件の Issue
+    async fn fetch_issue_list(
+        &self,
+        repo: &str,
+        state: IssueStateFilter,
+        count: u32,
+    ) -> Result<Vec<IssueSummary>> {
+        issue::query_issue_list(self, repo, state, count).await
+    }
+
+    async fn fetch_issue_detail(&self, repo: &str, issue_number: u32) -> Result<IssueDetail> {
+        issue::query_issue_detail(self, repo, issue_number).await
+    }
+}
+
+/// `request` を送り、レスポンスを JSON として `T` に読む
+pub(crate) async fn api_json<T, B>(
+    backend: &B,
+    request: ApiRequest<'_>,
+    context: &'static str,
+) -> Result<T>
+where
+    T: DeserializeOwned,
+    B: GitHubBackend + ?Sized,
+{
+    let text = backend.api(request).await?;
+    serde_json::from_str(&text).context(context)
+}
+
+/// `[github] backend` に従ってバックエンドを作る。`offline` なら GitHub に一切接続しない
+pub fn backend_from_config(config: &GithubConfig, offline: bool) -> Result<Arc<dyn GitHubBackend>> {
+    Ok(match config.backend {
+        GithubBackendKind::Gh => Arc::new(GhCliBackend::new(
+            GhRetryPolicy::from_config(config),
+            offline,
+        )),
+        GithubBackendKind::Rest => Arc::new(RestBackend::from_env(offline)?),
+    })
+}
+
+/// `--offline` 中は GitHub へ接続する前に失敗させる
+fn ensure_online(offline: bool) -> Result<()> {
+    if offline {
+        anyhow::bail!("GitHub is not available in offline mode");
+    }
+    Ok(())
+}
+
+fn review_body(action: ReviewAction, body: &str, comments: &[DraftReviewComment]) -> Value {
+    let mut fields = json!({ "event": review_event(action), "body": body });
+    if !comments.is_empty() {
+        fields["comments"] = comments.iter().map(DraftReviewComment::to_json).collect();
+    }
+    fields
+}
+
+pub(super) fn review_event(action: ReviewAction) -> &'static str {
+    match action {
+        ReviewAction::Approve => "APPROVE",
+        ReviewAction::RequestChanges => "REQUEST_CHANGES",
+        ReviewAction::Comment => "COMMENT",
+    }
+}
+
+/// `gh` CLI 経由の実装（既定）
+///
+/// 失敗は `policy` に従って再試行する。読み取りはレート制限とタイムアウト、
+/// 書き込みは GitHub が処理前に拒否したと分かるレート制限のときだけ再試行する。
+pub struct GhCliBackend {
+    runner: Arc<dyn GhRunner>,
+    policy: GhRetryPolicy,
+    offline: bool,
+}
+
+impl GhCliBackend {
+    pub fn new(policy: GhRetryPolicy, offline: bool) -> Self {
+        Self::with_runner(Arc::new(SystemGhRunner), policy, offline)
+    }
+
+    pub(crate) fn with_runner(
+        runner: Arc<dyn GhRunner>,
+        policy: GhRetryPolicy,
+        offline: bool,
+    ) -> Self {
+        Self {
+            runner,
+            policy,
+            offline,
+        }
+    }
+
+    /// `gh <args>` を実行して stdout を返す（`allowed_codes` の終了コードも成功扱い）
+    async fn gh(
+        &self,
+        args: Vec<String>,
+        input: Option<Vec<u8>>,
+        read: bool,
+        allowed_codes: &'static [i32],
+    ) -> Result<String> {
+        ensure_online(self.offline)?;
+        let runner = self.runner.clone();
+        let policy = self.policy;
+        tokio::task::spawn_blocking(move || {
+            run_gh_with_retry(
+                policy,
+                allowed_codes,
+                || runner.run(&args, input.as_deref()),
+                |stderr| is_retryable_gh_error(stderr, read),
+                std::thread::sleep,
+            )
+        })
+        .await
+        .context("spawn_blocking task panicked")?
+    }
+
+    async fn gh_read(&self, args: Vec<String>) -> Result<String> {
+        self.gh(args, None, true, &[]).await
+    }
+}
+
+fn to_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| s.to_string()).collect()
+}
+
+#[async_trait]
+impl GitHubBackend for GhCliBackend {
+    async fn api(&self, request: ApiRequest<'_>) -> Result<String> {
+        let mut args = to_args(&["api", "--method", request.method.as_str(), request.endpoint]);
+        if let Some(accept) = request.accept {
+            args.extend(["-H".to_string(), format!("Accept: {}", accept)]);
+        }
+        let input = request
+            .body
+            .as_ref()
+            .map(|body| body.to_string().into_bytes());
+        if input.is_some() {
+            args.extend(to_args(&["--input", "-"]));
+        }
+        tracing::debug!(args = ?args, "gh api");
+        self.gh(args, input, request.is_read(), &[]).await
+    }
+
+    async fn api_paginate(&self, endpoint: &str) -> Result<Value> {
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let endpoint = format!("{}{}per_page={}", endpoint, separator, PER_PAGE);
+        let output = self
+            .gh_read(to_args(&["api", "--paginate", "--slurp", &endpoint]))
+            .await?;
+        let pages: Vec<Value> =
+            serde_json::from_str(&output).context("Failed to parse gh api paginated response")?;
+        flatten_pages(pages)
+    }
+
+    async fn fetch_pr_diff(&self, repo: &str, pr_number: u32) -> Result<String> {
+        self.gh_read(to_args(&["pr", "diff", &pr_number.to_string(), "-R", repo]))
+            .await
+    }
+
+    /// `gh pr diff` を読みながら 1 行ずつ渡す（巨大な diff を全体の取得を待たずに表示する）
+    async fn fold_pr_diff_lines(
+        &self,
+        repo: &str,
+        pr_number: u32,
+        mut on_line: LineSink,
+    ) -> Result<()> {
+        ensure_online(self.offline)?;
+        let args = to_args(&["pr", "diff", &pr_number.to_string(), "-R", repo]);
+        let runner = self.runner.clone();
+        let policy = self.policy;
+        tokio::task::spawn_blocking(move || {
+            // 一度でも行を渡したら、やり直すと同じ行を二重に渡すので再試行しない
+            let streamed = Cell::new(false);
+            run_gh_with_retry(
+                policy,
+                &[],
+                || {
+                    runner.run_lines(&args, &mut |line| {
+                        streamed.set(true);
+                        on_line(line);
+                    })
+                },
+                |stderr| !streamed.get() && is_retryable_gh_error(stderr, true),
+                std::thread::sleep,
+            )
+            .map(|_| ())
+        })
+        .await
+        .context("spawn_blocking task panicked")?
+    }
+
+    async fn fetch_pr_list(
+        &self,
+        repo: &str,
+        state: PrStateFilter,
+        filters: &PrListFilters,
+        count: u32,
+    ) -> Result<Vec<PullRequestSummary>> {
+        let output = self
+            .gh_read(pr::pr_list_args(repo, state, filters, count))
+            .await?;
+        serde_json::from_str(&output).context("Failed to parse PR list response")
+    }
+
+    async fn find_open_pr_by_head(&self, repo: &str, head_ref: &str) -> Result<Option<u32>> {
+        let output = self
+            .gh_read(to_args(&[
+                "pr", "list", "-R", repo, "--head", head_ref, "-s", "open", "--json", "number",
+                "--limit", "1",
+            ]))
+            .await?;
+        pr::parse_pr_numbers(&output).map(|numbers| numbers.first().copied())
+    }
+
+    async fn fetch_pr_checks(&self, repo: &str, pr_number: u32) -> Result<Vec<CheckItem>> {
+        let args = to_args(&[
+            "pr",
+            "checks",
+            &pr_number.to_string(),
+            "-R",
+            repo,
+            "--json",
+            "name,state,bucket,link,workflow,description,startedAt,completedAt",
+        ]);
+        // exit code 8 = checks pending
+        let output = self.gh(args, None, true, &[8]).await?;
+        serde_json::from_str(&output).context("Failed to parse PR checks response")
+    }
+
+    async fn add_pr_people(
+        &self,
+        repo: &str,
+        pr_number: u32,
+        role: PrPeopleRole,
+        logins: &[String],
+    ) -> Result<()> {
+        let args = pr::pr_people_args(repo, pr_number, role, logins);
+        self.gh(args, None, false, &[]).await?;
+        Ok(())
+    }
+
+    async fn fetch_issue_list(
+        &self,
+        repo: &str,
+        state: IssueStateFilter,
+        count: u32,
+    ) -> Result<Vec<IssueSummary>> {
+        let output = self
+            .gh_read(to_args(&[
+                "issue",
+                "list",
+                "-R",
+                repo,
+                "-s",
+                state.as_gh_arg(),
+                "--json",
+                "number,title,state,author,labels,updatedAt,comments",
+                "--limit",
+                &count.to_string(),
+            ]))
+            .await?;
+        serde_json::from_str(&output).context("Failed to parse issue list response")
+    }
+
+    async fn fetch_issue_detail(&self, repo: &str, issue_number: u32) -> Result<IssueDetail> {
+        let output = self
+            .gh_read(to_args(&[
+                "issue",
+                "view",
+                &issue_number.to_string(),
+                "-R",
+                repo,
+                "--json",
+                "number,title,body,state,author,labels,createdAt,updatedAt,comments",
+            ]))
+            .await?;
+        serde_json::from_str(&output).context("Failed to parse issue detail response")
+    }
+}
+
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// ホストの REST API のベース URL（Enterprise は `https://<host>/api/v3`）
+fn default_api_url(host: &str) -> String {
+    if host == super::client::DEFAULT_GITHUB_HOST {
+        DEFAULT_API_URL.to_string()
+    } else {
+        format!("https://{}/api/v3", host)
+    }
+}
+
+/// `GITHUB_TOKEN` で REST / GraphQL API を直接呼ぶ実装（`gh` 不要）
+///
+/// GitHub Enterprise では検出したホストの `/api/v3` を使う。`GITHUB_API_URL` で上書きできる。
+pub struct RestBackend {
+    client: reqwest::Client,
+    api_url: String,
+    token: String,
+    offline: bool,
+}
+
+impl RestBackend {
+    pub fn new(api_url: &str, token: &str, offline: bool) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(concat!("octorus/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            offline,
+        })
+    }
+
+    pub fn from_env(offline: bool) -> Result<Self> {
+        let token = std::env::var("GITHUB_TOKEN")
+            .ok()
+            .filter(|t| !t.trim().is_empty())
+            .context("GITHUB_TOKEN must be set when [github] backend = \"rest\"")?;
+        let api_url = std::env::var("GITHUB_API_URL")
+            .unwrap_or_else(|_| default_api_url(&super::client::github_host()));
+        Self::new(&api_url, &token, offline)
+    }
+
+    /// GraphQL API の URL（Enterprise の `/api/v3` は `/api/graphql`）
+    fn graphql_url(&self) -> String {
+        let base = self.api_url.strip_suffix("/v3").unwrap_or(&self.api_url);
+        format!("{}/graphql", base)
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        if endpoint == "graphql" {
+            self.graphql_url()
+        } else {
+            format!("{}/{}", self.api_url, endpoint)
+        }
+    }
+}
+
+/// API エラーのメッセージ（`{"message": ...}` があればそれを使う）
+fn format_api_error(status: reqwest::StatusCode, body: &str) -> String {
+    let message = serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|json| json.get("message")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| body.chars().take(200).collect());
+    format!("GitHub API request failed ({}): {}", status, message)
+}
+
+#[async_trait]
+impl GitHubBackend for RestBackend {
+    async fn api(&self, request: ApiRequest<'_>) -> Result<String> {
+        ensure_online(self.offline)?;
+        let method = match request.method {
+            ApiMethod::Get => reqwest::Method::GET,
+            ApiMethod::Post => reqwest::Method::POST,
+            ApiMethod::Delete => reqwest::Method::DELETE,
+        };
+        let mut builder = self
+            .client
+            .request(method, self.url(request.endpoint))
+            .bearer_auth(&self.token)
+            .header(
+                reqwest::header::ACCEPT,
+                request.accept.unwrap_or("application/vnd.github+json"),
+            )
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(body) = &request.body {
+            builder = builder.json(body);
+        }
+
+        let response = builder.send().await.context("GitHub API request failed")?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .context("Failed to read GitHub API response")?;
+        if !status.is_success() {
+            anyhow::bail!("{}", format_api_error(status, &text));
+        }
+        Ok(text)
+    }
+}
+
+/// 呼び出しを記録するテスト用バックエンド
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::collections::{HashMap, VecDeque};
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::{ExitStatus, Output};
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// 事前に積んだ (exit code, stdout, stderr) を順に返す `gh` ランナー
+    ///
+    /// 積んだ分を使い切った後の呼び出しは panic する（`gh` を呼ばないことの検証にも使う）。
+    #[derive(Default)]
+    pub(crate) struct ScriptedGhRunner {
+        outputs: Mutex<VecDeque<(i32, String, String)>>,
+        calls: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl ScriptedGhRunner {
+        pub fn new(outputs: &[(i32, &str, &str)]) -> Self {
+            Self {
+                outputs: Mutex::new(
+                    outputs
+                        .iter()
+                        .map(|(code, out, err)| (*code, out.to_string(), err.to_string()))
+                        .collect(),
+                ),
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+
+        pub fn calls(&self) -> Vec<Vec<String>> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        fn next(&self, args: &[String]) -> Output {
+            self.calls.lock().unwrap().push(args.to_vec());
+            let (code, stdout, stderr) = self
+                .outputs
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| panic!("unexpected gh call: {:?}", args));
+            Output {
+                status: ExitStatus::from_raw(code << 8),
+                stdout: stdout.into_bytes(),
+                stderr: stderr.into_bytes(),
+            }
+        }
+    }
+
+    impl GhRunner for ScriptedGhRunner {
+        fn run(&self, args: &[String], _input: Option<&[u8]>) -> std::io::Result<Output> {
+            Ok(self.next(args))
+        }
+
+        fn run_lines(
+            &self,
+            args: &[String],
+            on_line: &mut dyn FnMut(&str),
+        ) -> std::io::Result<Output> {
+            let mut output = self.next(args);
+            String::from_utf8_lossy(&output.stdout)
+                .split_inclusive('\n')
+                .for_each(on_line);
+            output.stdout.clear();
+            Ok(output)
+        }
+    }
+
+    #[derive(Default)]
+    pub(crate) struct RecordingBackend {
+        pub calls: Mutex<Vec<String>>,
+        pub pr: Option<PullRequest>,
+        /// ページ番号 → ファイル一覧 JSON
+        pub files_pages: HashMap<usize, String>,
+        pub diff: String,
+        /// `api` のエンドポイント → レスポンス本文（無ければエラー）
+        pub responses: HashMap<String, String>,
+    }
+
+    impl RecordingBackend {
+        fn record(&self, call: String) {
+            self.calls.lock().unwrap().push(call);
+        }
+
+        pub fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl GitHubBackend for RecordingBackend {
+        async fn api(&self, request: ApiRequest<'_>) -> Result<String> {
+            self.record(format!(
+                "api {} {}",
+                request.method.as_str(),
+                request.endpoint
+            ));
+            self.responses
+                .get(request.endpoint)
+                .cloned()
+                .with_context(|| format!("no response for {}", request.endpoint))
+        }
+
+        async fn fetch_pr(&self, repo: &str, pr_number: u32) -> Result<PullRequest> {
+            self.record(format!("fetch_pr {} #{}", repo, pr_number));
+            self.pr.clone().context("no PR")
+        }
+
+        async fn fetch_changed_files_page(
+            &self,
+            repo: &str,
+            pr_number: u32,
+            page: usize,
+        ) -> Result<String> {
+            self.record(format!(
+                "fetch_changed_files_page {} #{} page={}",
+                repo, pr_number, page
+            ));
+            Ok(self
+                .files_pages
+                .get(&page)
+                .cloned()
+                .unwrap_or_else(|| "[]".to_string()))
+        }
+
+        async fn fetch_pr_diff(&self, repo: &str, pr_number: u32) -> Result<String> {
+            self.record(format!("fetch_pr_diff {} #{}", repo, pr_number));
+            Ok(self.diff.clone())
+        }
+
+        async fn fetch_review_comments(
+            &self,
+            repo: &str,
+            pr_number: u32,
+        ) -> Result<Vec<ReviewComment>> {
+            self.record(format!("fetch_review_comments {} #{}", repo, pr_number));
+            Ok(Vec::new())
+        }
+
+        async fn submit_review(
+            &self,
+            repo: &str,
+            pr_number: u32,
+            action: ReviewAction,
+            body: &str,
+            comments: &[DraftReviewComment],
+        ) -> Result<()> {
+            let mut call = format!(
+                "submit_review {} #{} {} {:?}",
+                repo,
+                pr_number,
+                review_event(action),
+                body
+            );
+            for comment in comments {
+                call.push_str(&format!(
+                    " [{}:{} {:?}]",
+                    comment.path, comment.line, comment.body
+                ));
+            }
+            self.record(call);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_api_url_for_host() {
+        assert_eq!(default_api_url("github.com"), "https://api.github.com");
+        assert_eq!(
+            default_api_url("ghe.example.com"),
+            "https://ghe.example.com/api/v3"
+        );
+    }
+
+    #[test]
+    fn test_review_event() {
+        assert_eq!(review_event(ReviewAction::Approve), "APPROVE");
+        assert_eq!(
+            review_event(ReviewAction::RequestChanges),
+            "REQUEST_CHANGES"
+        );
+        assert_eq!(review_event(ReviewAction::Comment), "COMMENT");
+    }
+
+    #[test]
+    fn test_format_api_error_prefers_message_field() {
+        let status = reqwest::StatusCode::UNPROCESSABLE_ENTITY;
+        assert_eq!(
+            format_api_error(status, r#"{"message":"Validation Failed","errors":[]}"#),
+            "GitHub API request failed (422 Unprocessable Entity): Validation Failed"
+        );
+        assert_eq!(
+            format_api_error(reqwest::StatusCode::BAD_GATEWAY, "upstream error"),
+            "GitHub API request failed (502 Bad Gateway): upstream error"
+        );
+    }
+
+    #[test]
+    fn test_rest_backend_urls() {
+        let backend = RestBackend::new("https://ghe.example.com/api/v3/", "token", false).unwrap();
+        assert_eq!(backend.api_url, "https://ghe.example.com/api/v3");
+        assert_eq!(
+            backend.url("repos/o/r/pulls/1"),
+            "https://ghe.example.com/api/v3/repos/o/r/pulls/1"
+        );
+        assert_eq!(
+            backend.url("graphql"),
+            "https://ghe.example.com/api/graphql"
+        );
+
+        let backend = RestBackend::new(DEFAULT_API_URL, "token", false).unwrap();
+        assert_eq!(backend.url("graphql"), "https://api.github.com/graphql");
+    }
+
+    #[test]
+    fn test_api_request_is_read() {
+        assert!(ApiRequest::get("repos/o/r/pulls/1").is_read());
+        assert!(!ApiRequest::post("repos/o/r/issues/1/comments", json!({})).is_read());
+        assert!(!ApiRequest::delete("repos/o/r/pulls/comments/1/reactions/2").is_read());
+
+        let graphql = |query: &str| ApiRequest::post("graphql", json!({ "query": query }));
+        assert!(graphql("\nquery($id: ID!) { node(id: $id) { id } }").is_read());
+        assert!(
+            !graphql("\nmutation($id: ID!) { resolveReviewThread { clientMutationId } }").is_read()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gh_backend_retries_reads_but_not_writes_on_timeout() {
+        let policy = GhRetryPolicy {
+            max_retries: 2,
+            base_delay: std::time::Duration::ZERO,
+        };
+        let runner = Arc::new(mock::ScriptedGhRunner::new(&[
+            (1, "", "net/http: request timeout"),
+            (0, "{}", ""),
+        ]));
+        let backend = GhCliBackend::with_runner(runner.clone(), policy, false);
+        backend.api(ApiRequest::get("user")).await.unwrap();
+        assert_eq!(runner.calls().len(), 2);
+
+        // 書き込みはタイムアウトしても GitHub 側で処理済みかもしれないので再送しない
+        let runner = Arc::new(mock::ScriptedGhRunner::new(&[(
+            1,
+            "",
+            "net/http: request timeout",
+        )]));
+        let backend = GhCliBackend::with_runner(runner.clone(), policy, false);
+        let err = backend
+            .api(ApiRequest::post("repos/o/r/issues/1/comments", json!({})))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("timeout"));
+        assert_eq!(runner.calls().len(), 1);
+
+        // レート制限は処理前に拒否されるので書き込みでも再試行する
+        let runner = Arc::new(mock::ScriptedGhRunner::new(&[
+            (1, "", "API rate limit exceeded"),
+            (0, "{}", ""),
+        ]));
+        let backend = GhCliBackend::with_runner(runner.clone(), policy, false);
+        backend
+            .api(ApiRequest::post("repos/o/r/issues/1/comments", json!({})))
+            .await
+            .unwrap();
+        assert_eq!(runner.calls().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gh_backend_streams_diff_through_runner() {
+        let policy = GhRetryPolicy {
+            max_retries: 2,
+            base_delay: std::time::Duration::ZERO,
+        };
+        let lines = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = |lines: &Arc<std::sync::Mutex<Vec<String>>>| -> LineSink {
+            let lines = lines.clone();
+            Box::new(move |line| lines.lock().unwrap().push(line.to_string()))
+        };
+
+        // 行を渡す前の失敗は読み取りとして再試行する
+        let runner = Arc::new(mock::ScriptedGhRunner::new(&[
+            (1, "", "API rate limit exceeded"),
+            (0, "a\nb\n", ""),
+        ]));
+        let backend = GhCliBackend::with_runner(runner.clone(), policy, false);
+        backend
+            .fold_pr_diff_lines("o/r", 1, sink(&lines))
+            .await
+            .unwrap();
+        assert_eq!(runner.calls().len(), 2);
+        assert_eq!(*lines.lock().unwrap(), vec!["a\n", "b\n"]);
+
+        // 途中まで渡した後の失敗は、同じ行を二重に渡さないよう再試行せず stderr を返す
+        lines.lock().unwrap().clear();
+        let runner = Arc::new(mock::ScriptedGhRunner::new(&[(
+            1,
+            "a\n",
+            "API rate limit exceeded",
+        )]));
+        let backend = GhCliBackend::with_runner(runner.clone(), policy, false);
+        let err = backend
+            .fold_pr_diff_lines("o/r", 1, sink(&lines))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("API rate limit"));
+        assert_eq!(runner.calls().len(), 1);
+        assert_eq!(*lines.lock().unwrap(), vec!["a\n"]);
+    }
+}
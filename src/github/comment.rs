@@ -1,16 +1,19 @@
 use anyhow::{Context, Result};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-use super::client::{gh_api_paginate, gh_api_post, FieldValue};
+use super::backend::{api_json, ApiRequest, GitHubBackend};
 use super::pr::User;
+use crate::diff::CommentSide;
 
 /// ジェネリックなfetch & parse関数（ページネーション対応）
 async fn fetch_and_parse<T: DeserializeOwned>(
+    backend: &dyn GitHubBackend,
     endpoint: &str,
     error_context: &'static str,
 ) -> Result<T> {
-    let json = gh_api_paginate(endpoint).await?;
+    let json = backend.api_paginate(endpoint).await?;
     serde_json::from_value(json).context(error_context)
 }
 
@@ -26,14 +29,142 @@ pub struct ReviewComment {
     pub created_at: String,
     #[serde(default)]
     pub in_reply_to_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Reactions::is_empty")]
+    pub reactions: Reactions,
 }
 
-pub async fn fetch_review_comments(repo: &str, pr_number: u32) -> Result<Vec<ReviewComment>> {
-    fetch_and_parse(
-        &format!("repos/{}/pulls/{}/comments?per_page=100", repo, pr_number),
-        "Failed to parse review comments response",
-    )
-    .await
+/// コメントに付けられるリアクションの種類（ピッカーの表示順）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReactionKind {
+    PlusOne,
+    MinusOne,
+    Laugh,
+    Hooray,
+    Confused,
+    Heart,
+    Rocket,
+    Eyes,
+}
+
+impl ReactionKind {
+    pub const ALL: [ReactionKind; 8] = [
+        Self::PlusOne,
+        Self::MinusOne,
+        Self::Laugh,
+        Self::Hooray,
+        Self::Confused,
+        Self::Heart,
+        Self::Rocket,
+        Self::Eyes,
+    ];
+
+    /// REST API の `content` 値
+    pub fn content(self) -> &'static str {
+        match self {
+            Self::PlusOne => "+1",
+            Self::MinusOne => "-1",
+            Self::Laugh => "laugh",
+            Self::Hooray => "hooray",
+            Self::Confused => "confused",
+            Self::Heart => "heart",
+            Self::Rocket => "rocket",
+            Self::Eyes => "eyes",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::PlusOne => "👍",
+            Self::MinusOne => "👎",
+            Self::Laugh => "😄",
+            Self::Hooray => "🎉",
+            Self::Confused => "😕",
+            Self::Heart => "❤️",
+            Self::Rocket => "🚀",
+            Self::Eyes => "👀",
+        }
+    }
+}
+
+/// コメントのリアクション数（REST API の `reactions` オブジェクト）
+///
+/// `url` / `total_count` は使わないため読み捨てる。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Reactions {
+    #[serde(rename = "+1")]
+    pub plus_one: u32,
+    #[serde(rename = "-1")]
+    pub minus_one: u32,
+    pub laugh: u32,
+    pub hooray: u32,
+    pub confused: u32,
+    pub heart: u32,
+    pub rocket: u32,
+    pub eyes: u32,
+}
+
+impl Reactions {
+    pub fn is_empty(&self) -> bool {
+        ReactionKind::ALL.iter().all(|&kind| self.count(kind) == 0)
+    }
+
+    pub fn count(&self, kind: ReactionKind) -> u32 {
+        *self.slot(kind)
+    }
+
+    pub fn increment(&mut self, kind: ReactionKind) {
+        *self.slot_mut(kind) += 1;
+    }
+
+    pub fn decrement(&mut self, kind: ReactionKind) {
+        let slot = self.slot_mut(kind);
+        *slot = slot.saturating_sub(1);
+    }
+
+    /// 表示用の要約（例: `👍 2  🚀 1`）。リアクションがなければ空文字。
+    pub fn summary(&self) -> String {
+        ReactionKind::ALL
+            .iter()
+            .filter(|&&kind| self.count(kind) > 0)
+            .map(|&kind| format!("{} {}", kind.emoji(), self.count(kind)))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
+    fn slot(&self, kind: ReactionKind) -> &u32 {
+        match kind {
+            ReactionKind::PlusOne => &self.plus_one,
+            ReactionKind::MinusOne => &self.minus_one,
+            ReactionKind::Laugh => &self.laugh,
+            ReactionKind::Hooray => &self.hooray,
+            ReactionKind::Confused => &self.confused,
+            ReactionKind::Heart => &self.heart,
+            ReactionKind::Rocket => &self.rocket,
+            ReactionKind::Eyes => &self.eyes,
+        }
+    }
+
+    fn slot_mut(&mut self, kind: ReactionKind) -> &mut u32 {
+        match kind {
+            ReactionKind::PlusOne => &mut self.plus_one,
+            ReactionKind::MinusOne => &mut self.minus_one,
+            ReactionKind::Laugh => &mut self.laugh,
+            ReactionKind::Hooray => &mut self.hooray,
+            ReactionKind::Confused => &mut self.confused,
+            ReactionKind::Heart => &mut self.heart,
+            ReactionKind::Rocket => &mut self.rocket,
+            ReactionKind::Eyes => &mut self.eyes,
+        }
+    }
+}
+
+pub async fn fetch_review_comments(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+) -> Result<Vec<ReviewComment>> {
+    backend.fetch_review_comments(repo, pr_number).await
 }
 
 /// ディスカッションコメント（PRの会話タブのコメント）
@@ -46,11 +177,13 @@ pub struct DiscussionComment {
 }
 
 pub async fn fetch_discussion_comments(
+    backend: &dyn GitHubBackend,
     repo: &str,
     pr_number: u32,
 ) -> Result<Vec<DiscussionComment>> {
     fetch_and_parse(
-        &format!("repos/{}/issues/{}/comments?per_page=100", repo, pr_number),
+        backend,
+        &format!("repos/{}/issues/{}/comments", repo, pr_number),
         "Failed to parse discussion comments response",
     )
     .await
@@ -66,38 +199,91 @@ pub struct Review {
     pub submitted_at: Option<String>,
 }
 
-pub async fn fetch_reviews(repo: &str, pr_number: u32) -> Result<Vec<Review>> {
+/// レビュー作成時に一緒に送るインラインコメント（`comments[]` の 1 件）
+///
+/// `line` / `side`（複数行なら `start_line` も同じ側）で位置を指定する。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DraftReviewComment {
+    pub path: String,
+    pub line: u32,
+    pub start_line: Option<u32>,
+    pub side: CommentSide,
+    pub body: String,
+}
+
+impl DraftReviewComment {
+    pub(super) fn to_json(&self) -> Value {
+        let mut fields = json!({
+            "path": self.path,
+            "body": self.body,
+            "line": self.line,
+            "side": self.side.as_str(),
+        });
+        if let Some(start_line) = self.start_line {
+            fields["start_line"] = json!(start_line);
+            fields["start_side"] = json!(self.side.as_str());
+        }
+        fields
+    }
+}
+
+pub async fn fetch_reviews(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+) -> Result<Vec<Review>> {
     fetch_and_parse(
-        &format!("repos/{}/pulls/{}/reviews?per_page=100", repo, pr_number),
+        backend,
+        &format!("repos/{}/pulls/{}/reviews", repo, pr_number),
         "Failed to parse reviews response",
     )
     .await
 }
 
+/// 単一行コメントの送信本文。
+///
+/// NOTE: line/subject_type は Pull Request Review の一部としてのみ有効。
+/// 単体コメント API (POST /pulls/{n}/comments) では oneOf スキーマに合致せず 422 になる。
+/// position パラメータ（patch 内オフセット）を使用し、削除行（旧ファイル側）への
+/// コメントのみ `side=LEFT` を添える。
+fn review_comment_body(
+    commit_id: &str,
+    path: &str,
+    position: u32,
+    side: CommentSide,
+    body: &str,
+) -> Value {
+    let mut fields = json!({
+        "body": body,
+        "commit_id": commit_id,
+        "path": path,
+        "position": position,
+    });
+    if side == CommentSide::Left {
+        fields["side"] = json!(side.as_str());
+    }
+    fields
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn create_review_comment(
+    backend: &dyn GitHubBackend,
     repo: &str,
     pr_number: u32,
     commit_id: &str,
     path: &str,
     position: u32,
+    side: CommentSide,
     body: &str,
 ) -> Result<ReviewComment> {
     let endpoint = format!("repos/{}/pulls/{}/comments", repo, pr_number);
-    let position_str = position.to_string();
-    // NOTE: line/side/subject_type は Pull Request Review の一部としてのみ有効。
-    // 単体コメント API (POST /pulls/{n}/comments) では oneOf スキーマに合致せず 422 になる。
-    // position パラメータ（patch 内オフセット）を使用する。
-    let json = gh_api_post(
-        &endpoint,
-        &[
-            ("body", FieldValue::String(body)),
-            ("commit_id", FieldValue::String(commit_id)),
-            ("path", FieldValue::String(path)),
-            ("position", FieldValue::Raw(&position_str)),
-        ],
+    let fields = review_comment_body(commit_id, path, position, side, body);
+    api_json(
+        backend,
+        ApiRequest::post(&endpoint, fields),
+        "Failed to parse created comment response",
     )
-    .await?;
-    serde_json::from_value(json).context("Failed to parse created comment response")
+    .await
 }
 
 /// 複数行レビューコメントを作成する。
@@ -110,6 +296,7 @@ pub async fn create_review_comment(
 /// API は自動的に line-level コメントとして扱う。
 #[allow(clippy::too_many_arguments)]
 pub async fn create_multiline_review_comment(
+    backend: &dyn GitHubBackend,
     repo: &str,
     pr_number: u32,
     commit_id: &str,
@@ -120,25 +307,25 @@ pub async fn create_multiline_review_comment(
     body: &str,
 ) -> Result<ReviewComment> {
     let endpoint = format!("repos/{}/pulls/{}/comments", repo, pr_number);
-    let start_line_str = start_line.to_string();
-    let end_line_str = end_line.to_string();
-    let json = gh_api_post(
-        &endpoint,
-        &[
-            ("body", FieldValue::String(body)),
-            ("commit_id", FieldValue::String(commit_id)),
-            ("path", FieldValue::String(path)),
-            ("start_line", FieldValue::Raw(&start_line_str)),
-            ("line", FieldValue::Raw(&end_line_str)),
-            ("start_side", FieldValue::String(side)),
-            ("side", FieldValue::String(side)),
-        ],
+    let fields = json!({
+        "body": body,
+        "commit_id": commit_id,
+        "path": path,
+        "start_line": start_line,
+        "line": end_line,
+        "start_side": side,
+        "side": side,
+    });
+    api_json(
+        backend,
+        ApiRequest::post(&endpoint, fields),
+        "Failed to parse created multiline comment response",
     )
-    .await?;
-    serde_json::from_value(json).context("Failed to parse created multiline comment response")
+    .await
 }
 
 pub async fn create_reply_comment(
+    backend: &dyn GitHubBackend,
     repo: &str,
     pr_number: u32,
     comment_id: u64,
@@ -148,8 +335,56 @@ pub async fn create_reply_comment(
         "repos/{}/pulls/{}/comments/{}/replies",
         repo, pr_number, comment_id
     );
-    let json = gh_api_post(&endpoint, &[("body", FieldValue::String(body))]).await?;
-    serde_json::from_value(json).context("Failed to parse reply comment response")
+    api_json(
+        backend,
+        ApiRequest::post(&endpoint, json!({ "body": body })),
+        "Failed to parse reply comment response",
+    )
+    .await
+}
+
+fn reaction_endpoint(repo: &str, comment_id: u64) -> String {
+    format!("repos/{}/pulls/comments/{}/reactions", repo, comment_id)
+}
+
+fn reaction_body(kind: ReactionKind) -> Value {
+    json!({ "content": kind.content() })
+}
+
+#[derive(Deserialize)]
+struct CreatedReaction {
+    id: u64,
+}
+
+/// レビューコメントにリアクションを付け、作成されたリアクションの ID を返す。
+///
+/// 同じユーザーが同じ種類を付け済みの場合、GitHub は既存のリアクションを返す。
+pub async fn add_review_comment_reaction(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    comment_id: u64,
+    kind: ReactionKind,
+) -> Result<u64> {
+    let endpoint = reaction_endpoint(repo, comment_id);
+    let created: CreatedReaction = api_json(
+        backend,
+        ApiRequest::post(&endpoint, reaction_body(kind)),
+        "Failed to parse reaction response",
+    )
+    .await?;
+    Ok(created.id)
+}
+
+/// `add_review_comment_reaction` で付けたリアクションを取り消す
+pub async fn remove_review_comment_reaction(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    comment_id: u64,
+    reaction_id: u64,
+) -> Result<()> {
+    let endpoint = format!("{}/{}", reaction_endpoint(repo, comment_id), reaction_id);
+    backend.api(ApiRequest::delete(&endpoint)).await?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -204,6 +439,7 @@ mod tests {
             },
             created_at: "2025-03-01T12:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         };
         let serialized = serde_json::to_string(&original).unwrap();
         let deserialized: ReviewComment = serde_json::from_str(&serialized).unwrap();
@@ -213,6 +449,110 @@ mod tests {
         assert_eq!(deserialized.body, original.body);
     }
 
+    #[test]
+    fn test_review_comment_deserialize_reactions() {
+        let json = serde_json::json!({
+            "id": 1,
+            "path": "src/lib.rs",
+            "line": 3,
+            "body": "nice",
+            "user": { "login": "dev" },
+            "created_at": "2025-01-15T10:30:00Z",
+            "reactions": {
+                "url": "https://api.github.com/repos/o/r/pulls/comments/1/reactions",
+                "total_count": 4,
+                "+1": 2,
+                "-1": 0,
+                "laugh": 0,
+                "hooray": 1,
+                "confused": 0,
+                "heart": 0,
+                "rocket": 1,
+                "eyes": 0
+            }
+        });
+        let comment: ReviewComment = serde_json::from_value(json).unwrap();
+        assert_eq!(comment.reactions.count(ReactionKind::PlusOne), 2);
+        assert_eq!(comment.reactions.count(ReactionKind::Hooray), 1);
+        assert_eq!(comment.reactions.count(ReactionKind::Rocket), 1);
+        assert_eq!(comment.reactions.summary(), "👍 2  🎉 1  🚀 1");
+    }
+
+    #[test]
+    fn test_reactions_increment_decrement() {
+        let mut reactions = Reactions::default();
+        assert!(reactions.is_empty());
+        reactions.increment(ReactionKind::Eyes);
+        assert_eq!(reactions.summary(), "👀 1");
+        reactions.decrement(ReactionKind::Eyes);
+        reactions.decrement(ReactionKind::Eyes);
+        assert!(reactions.is_empty());
+    }
+
+    #[test]
+    fn test_reaction_request_payload() {
+        assert_eq!(
+            reaction_endpoint("owner/repo", 42),
+            "repos/owner/repo/pulls/comments/42/reactions"
+        );
+        let contents: Vec<String> = ReactionKind::ALL
+            .iter()
+            .map(|&kind| match reaction_body(kind) {
+                Value::Object(fields) if fields.len() == 1 => {
+                    fields["content"].as_str().unwrap().to_string()
+                }
+                other => panic!("unexpected fields: {}", other),
+            })
+            .collect();
+        assert_eq!(
+            contents,
+            ["+1", "-1", "laugh", "hooray", "confused", "heart", "rocket", "eyes"]
+        );
+    }
+
+    #[test]
+    fn test_draft_review_comment_json() {
+        let single = DraftReviewComment {
+            path: "src/lib.rs".to_string(),
+            line: 5,
+            start_line: None,
+            side: CommentSide::Left,
+            body: "note".to_string(),
+        };
+        assert_eq!(
+            single.to_json(),
+            json!({ "path": "src/lib.rs", "body": "note", "line": 5, "side": "LEFT" })
+        );
+        let multi = DraftReviewComment {
+            start_line: Some(3),
+            side: CommentSide::Right,
+            ..single
+        };
+        assert_eq!(
+            multi.to_json(),
+            json!({
+                "path": "src/lib.rs",
+                "body": "note",
+                "line": 5,
+                "side": "RIGHT",
+                "start_line": 3,
+                "start_side": "RIGHT",
+            })
+        );
+    }
+
+    #[test]
+    fn test_review_comment_payload_side() {
+        let right = review_comment_body("abc123", "src/lib.rs", 5, CommentSide::Right, "note");
+        assert_eq!(right.as_object().unwrap().len(), 4);
+        assert_eq!(right["position"], json!(5));
+        assert!(right.get("side").is_none());
+
+        let left = review_comment_body("abc123", "src/lib.rs", 5, CommentSide::Left, "note");
+        assert_eq!(left["position"], json!(5));
+        assert_eq!(left["side"], json!("LEFT"));
+    }
+
     #[test]
     fn test_discussion_comment_deserialize() {
         let json = serde_json::json!({
@@ -322,7 +662,7 @@ mod tests {
             "created_at": "2025-01-01T00:00:00Z"
         });
         let comment: ReviewComment = serde_json::from_value(json).unwrap();
-        assert_snapshot!(format!("{:?}", comment), @r#"ReviewComment { id: 555, path: "src/app.rs", line: Some(100), start_line: None, body: "Snapshot test body", user: User { login: "snapshot_user" }, created_at: "2025-01-01T00:00:00Z", in_reply_to_id: None }"#);
+        assert_snapshot!(format!("{:?}", comment), @r#"ReviewComment { id: 555, path: "src/app.rs", line: Some(100), start_line: None, body: "Snapshot test body", user: User { login: "snapshot_user" }, created_at: "2025-01-01T00:00:00Z", in_reply_to_id: None, reactions: Reactions { plus_one: 0, minus_one: 0, laugh: 0, hooray: 0, confused: 0, heart: 0, rocket: 0, eyes: 0 } }"#);
     }
 
     #[test]
@@ -338,7 +678,8 @@ mod tests {
             },
             created_at: "2026-03-25T00:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         };
-        assert_snapshot!(format!("{:?}", comment), @r#"ReviewComment { id: 10, path: "src/app.rs", line: Some(50), start_line: Some(45), body: "Multiline review", user: User { login: "dacuna" }, created_at: "2026-03-25T00:00:00Z", in_reply_to_id: None }"#);
+        assert_snapshot!(format!("{:?}", comment), @r#"ReviewComment { id: 10, path: "src/app.rs", line: Some(50), start_line: Some(45), body: "Multiline review", user: User { login: "dacuna" }, created_at: "2026-03-25T00:00:00Z", in_reply_to_id: None, reactions: Reactions { plus_one: 0, minus_one: 0, laugh: 0, hooray: 0, confused: 0, heart: 0, rocket: 0, eyes: 0 } }"#);
     }
 }
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
-use super::client::{gh_api, gh_command};
+use super::backend::{api_json, ApiRequest, GitHubBackend};
 
 /// GitHub API レスポンスの中間構造体（nested JSON を平坦化）
 #[derive(Debug, Clone, Deserialize)]
@@ -59,6 +59,7 @@ pub struct CommitListPage {
 /// `page` は 1-indexed。`per_page + 1` 件を要求して `has_more` を判定する。
 /// GitHub API は最大250コミットまで（API仕様制限）。
 pub async fn fetch_pr_commits(
+    backend: &dyn GitHubBackend,
     repo: &str,
     pr_number: u32,
     page: u32,
@@ -69,12 +70,13 @@ pub async fn fetch_pr_commits(
         "repos/{}/pulls/{}/commits?per_page={}&page={}",
         repo, pr_number, fetch_count, page
     );
-    let json = gh_api(&endpoint)
-        .await
-        .context("Failed to fetch PR commits")?;
-
-    let responses: Vec<CommitResponse> =
-        serde_json::from_value(json).context("Failed to parse PR commits response")?;
+    let responses: Vec<CommitResponse> = api_json(
+        backend,
+        ApiRequest::get(&endpoint),
+        "Failed to parse PR commits response",
+    )
+    .await
+    .context("Failed to fetch PR commits")?;
 
     let has_more = responses.len() > per_page as usize;
 
@@ -117,16 +119,16 @@ fn commit_response_to_pr_commit(r: CommitResponse) -> PrCommit {
 }
 
 /// 特定コミットの unified diff を取得
-pub async fn fetch_commit_diff(repo: &str, sha: &str) -> Result<String> {
+pub async fn fetch_commit_diff(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    sha: &str,
+) -> Result<String> {
     let endpoint = format!("repos/{}/commits/{}", repo, sha);
-    let diff = gh_command(&[
-        "api",
-        "-H",
-        "Accept: application/vnd.github.v3.diff",
-        &endpoint,
-    ])
-    .await
-    .context("Failed to fetch commit diff")?;
+    let diff = backend
+        .api(ApiRequest::get(&endpoint).accept("application/vnd.github.v3.diff"))
+        .await
+        .context("Failed to fetch commit diff")?;
     Ok(diff)
 }
 
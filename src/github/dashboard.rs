@@ -1,6 +1,6 @@
 use anyhow::Result;
 
-use super::client::gh_api;
+use super::backend::{api_json, ApiRequest, GitHubBackend};
 
 fn parse_total_count(json: &serde_json::Value) -> u32 {
     json.get("total_count")
@@ -9,21 +9,34 @@ fn parse_total_count(json: &serde_json::Value) -> u32 {
         .unwrap_or(0)
 }
 
-pub async fn fetch_mentioned_issues_count(repo: &str) -> Result<u32> {
+pub async fn fetch_mentioned_issues_count(backend: &dyn GitHubBackend, repo: &str) -> Result<u32> {
     let endpoint = format!(
         "search/issues?q=mentions:@me+is:issue+is:open+repo:{}&per_page=1",
         repo
     );
-    let json = gh_api(&endpoint).await?;
+    let json = api_json(
+        backend,
+        ApiRequest::get(&endpoint),
+        "Failed to parse search response",
+    )
+    .await?;
     Ok(parse_total_count(&json))
 }
 
-pub async fn fetch_review_requested_prs_count(repo: &str) -> Result<u32> {
+pub async fn fetch_review_requested_prs_count(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+) -> Result<u32> {
     let endpoint = format!(
         "search/issues?q=review-requested:@me+is:pr+is:open+repo:{}&per_page=1",
         repo
     );
-    let json = gh_api(&endpoint).await?;
+    let json = api_json(
+        backend,
+        ApiRequest::get(&endpoint),
+        "Failed to parse search response",
+    )
+    .await?;
     Ok(parse_total_count(&json))
 }
 
@@ -1,8 +1,10 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-use super::client::{gh_api_graphql, gh_api_post, gh_command, FieldValue};
-use super::pr::{Label, User};
+use super::backend::{api_json, ApiRequest, GitHubBackend, PER_PAGE};
+use super::client::check_graphql_errors;
+use super::pr::{graphql_author, Label, User};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IssueComment {
@@ -33,12 +35,18 @@ pub fn parse_issue_comments(raw: &[serde_json::Value]) -> Vec<IssueComment> {
 
 /// Issue にコメントを投稿する（REST API）
 pub async fn create_issue_comment(
+    backend: &dyn GitHubBackend,
     repo: &str,
     issue_number: u32,
     body: &str,
 ) -> Result<IssueComment> {
     let endpoint = format!("repos/{}/issues/{}/comments", repo, issue_number);
-    let json = gh_api_post(&endpoint, &[("body", FieldValue::String(body))]).await?;
+    let json: Value = api_json(
+        backend,
+        ApiRequest::post(&endpoint, json!({ "body": body })),
+        "Failed to parse gh api response as JSON",
+    )
+    .await?;
     parse_rest_issue_comment(&json)
 }
 
@@ -157,37 +165,24 @@ pub struct IssueListPage {
 }
 
 pub async fn fetch_issue_list(
+    backend: &dyn GitHubBackend,
     repo: &str,
     state: IssueStateFilter,
     limit: u32,
 ) -> Result<IssueListPage> {
-    fetch_issue_list_with_offset(repo, state, 0, limit).await
+    fetch_issue_list_with_offset(backend, repo, state, 0, limit).await
 }
 
 /// Issue一覧取得（オフセット付き、追加ロード用）
 pub async fn fetch_issue_list_with_offset(
+    backend: &dyn GitHubBackend,
     repo: &str,
     state: IssueStateFilter,
     offset: u32,
     limit: u32,
 ) -> Result<IssueListPage> {
     let fetch_count = offset + limit + 1;
-    let output = gh_command(&[
-        "issue",
-        "list",
-        "-R",
-        repo,
-        "-s",
-        state.as_gh_arg(),
-        "--json",
-        "number,title,state,author,labels,updatedAt,comments",
-        "--limit",
-        &fetch_count.to_string(),
-    ])
-    .await?;
-
-    let all_items: Vec<IssueSummary> =
-        serde_json::from_str(&output).context("Failed to parse issue list response")?;
+    let all_items = backend.fetch_issue_list(repo, state, fetch_count).await?;
 
     let has_more = all_items.len() > (offset + limit) as usize;
     let items: Vec<IssueSummary> = all_items
@@ -200,19 +195,169 @@ pub async fn fetch_issue_list_with_offset(
 }
 
 /// Issue詳細取得
-pub async fn fetch_issue_detail(repo: &str, issue_number: u32) -> Result<IssueDetail> {
-    let output = gh_command(&[
-        "issue",
-        "view",
-        &issue_number.to_string(),
-        "-R",
-        repo,
-        "--json",
-        "number,title,body,state,author,labels,createdAt,updatedAt,comments",
-    ])
-    .await?;
+pub async fn fetch_issue_detail(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    issue_number: u32,
+) -> Result<IssueDetail> {
+    backend.fetch_issue_detail(repo, issue_number).await
+}
 
-    serde_json::from_str(&output).context("Failed to parse issue detail response")
+fn split_repo(repo: &str) -> Result<(&str, &str)> {
+    repo.split_once('/')
+        .with_context(|| format!("Invalid repo format: expected 'owner/repo', got '{}'", repo))
+}
+
+const ISSUE_LIST_QUERY: &str = r#"
+query($owner: String!, $name: String!, $states: [IssueState!], $first: Int!, $after: String) {
+  repository(owner: $owner, name: $name) {
+    issues(states: $states, first: $first, after: $after, orderBy: { field: CREATED_AT, direction: DESC }) {
+      nodes {
+        number
+        title
+        state
+        updatedAt
+        author { login }
+        labels(first: 20) { nodes { name } }
+        comments(first: 100) { nodes { id } }
+      }
+      pageInfo {
+        hasNextPage
+        endCursor
+      }
+    }
+  }
+}
+"#;
+
+const ISSUE_DETAIL_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    issue(number: $number) {
+      number
+      title
+      body
+      state
+      createdAt
+      updatedAt
+      author { login }
+      labels(first: 20) { nodes { name } }
+      comments(first: 100) {
+        nodes {
+          id
+          body
+          createdAt
+          authorAssociation
+          url
+          author { login }
+        }
+      }
+    }
+  }
+}
+"#;
+
+fn issue_states(state: IssueStateFilter) -> Value {
+    match state {
+        IssueStateFilter::Open => json!(["OPEN"]),
+        IssueStateFilter::Closed => json!(["CLOSED"]),
+        IssueStateFilter::All => json!(["OPEN", "CLOSED"]),
+    }
+}
+
+/// GraphQL の Issue ノードを `gh issue list/view --json` と同じ形に直す
+/// （`labels` / `comments` の `nodes` を展開し、削除済みユーザーを `ghost` にする）
+fn issue_from_graphql(node: &Value) -> Value {
+    let mut issue = node.clone();
+    issue["author"] = graphql_author(node);
+    issue["labels"] = node
+        .pointer("/labels/nodes")
+        .cloned()
+        .unwrap_or_else(|| json!([]));
+    let comments: Vec<Value> = node
+        .pointer("/comments/nodes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|comment| {
+            let mut comment = comment.clone();
+            comment["author"] = graphql_author(&comment);
+            comment
+        })
+        .collect();
+    issue["comments"] = Value::Array(comments);
+    issue
+}
+
+/// 新しい順に最大 `count` 件の Issue を GraphQL で取得する（`gh issue list` を使わない実装）
+pub(super) async fn query_issue_list<B: GitHubBackend + ?Sized>(
+    backend: &B,
+    repo: &str,
+    state: IssueStateFilter,
+    count: u32,
+) -> Result<Vec<IssueSummary>> {
+    let (owner, name) = split_repo(repo)?;
+    let mut items = Vec::new();
+    let mut after: Option<String> = None;
+    while items.len() < count as usize {
+        let first = (count as usize - items.len()).min(PER_PAGE);
+        let response = backend
+            .graphql(
+                ISSUE_LIST_QUERY,
+                json!({
+                    "owner": owner,
+                    "name": name,
+                    "states": issue_states(state),
+                    "first": first,
+                    "after": after,
+                }),
+            )
+            .await?;
+        check_graphql_errors(&response)?;
+        let issues = response
+            .pointer("/data/repository/issues")
+            .context("GitHub GraphQL response missing data")?;
+        for node in issues["nodes"].as_array().into_iter().flatten() {
+            items.push(
+                serde_json::from_value(issue_from_graphql(node))
+                    .context("Failed to parse issue list response")?,
+            );
+        }
+        let has_next = issues
+            .pointer("/pageInfo/hasNextPage")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        match issues
+            .pointer("/pageInfo/endCursor")
+            .and_then(Value::as_str)
+        {
+            Some(cursor) if has_next => after = Some(cursor.to_string()),
+            _ => break,
+        }
+    }
+    Ok(items)
+}
+
+/// Issue 詳細を GraphQL で取得する（`gh issue view` を使わない実装）
+pub(super) async fn query_issue_detail<B: GitHubBackend + ?Sized>(
+    backend: &B,
+    repo: &str,
+    issue_number: u32,
+) -> Result<IssueDetail> {
+    let (owner, name) = split_repo(repo)?;
+    let response = backend
+        .graphql(
+            ISSUE_DETAIL_QUERY,
+            json!({ "owner": owner, "name": name, "number": issue_number }),
+        )
+        .await?;
+    check_graphql_errors(&response)?;
+    let node = response
+        .pointer("/data/repository/issue")
+        .filter(|node| !node.is_null())
+        .with_context(|| format!("Issue #{} not found", issue_number))?;
+    serde_json::from_value(issue_from_graphql(node))
+        .context("Failed to parse issue detail response")
 }
 
 /// GraphQL レスポンスからlinked PRを抽出する純粋関数
@@ -325,20 +470,19 @@ query($owner: String!, $name: String!, $number: Int!) {
 ///
 /// `ConnectedEvent`（Development sidebar リンク）と `CrossReferencedEvent`
 /// （PR body/コミットメッセージからの参照）の両方を取得する。
-pub async fn fetch_linked_prs(repo: &str, issue_number: u32) -> Result<Vec<LinkedPr>> {
-    let Some((owner, name)) = repo.split_once('/') else {
-        anyhow::bail!("Invalid repo format: expected 'owner/repo', got '{}'", repo);
-    };
+pub async fn fetch_linked_prs(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    issue_number: u32,
+) -> Result<Vec<LinkedPr>> {
+    let (owner, name) = split_repo(repo)?;
 
-    let data = gh_api_graphql(
-        LINKED_PRS_QUERY,
-        &[
-            ("owner", FieldValue::String(owner)),
-            ("name", FieldValue::String(name)),
-            ("number", FieldValue::Raw(&issue_number.to_string())),
-        ],
-    )
-    .await?;
+    let data = backend
+        .graphql(
+            LINKED_PRS_QUERY,
+            json!({ "owner": owner, "name": name, "number": issue_number }),
+        )
+        .await?;
 
     Ok(parse_linked_prs_from_graphql(&data, repo))
 }
@@ -849,6 +993,22 @@ mod tests {
         ");
     }
 
+    #[test]
+    fn test_issue_from_graphql_flattens_nodes() {
+        let node = json!({
+            "number": 5,
+            "title": "Bug",
+            "author": null,
+            "labels": { "nodes": [{ "name": "bug" }] },
+            "comments": { "nodes": [{ "body": "hi", "author": { "login": "alice" } }, { "body": "gone", "author": null }] },
+        });
+        let issue = issue_from_graphql(&node);
+        assert_eq!(issue["author"]["login"], "ghost");
+        assert_eq!(issue["labels"], json!([{ "name": "bug" }]));
+        assert_eq!(issue["comments"][0]["author"]["login"], "alice");
+        assert_eq!(issue["comments"][1]["author"]["login"], "ghost");
+    }
+
     #[test]
     fn snapshot_reply_template_japanese() {
         let template = build_reply_template(
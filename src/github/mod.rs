@@ -1,3 +1,4 @@
+pub(crate) mod backend;
 mod client;
 pub mod comment;
 mod commit;
@@ -43,9 +44,21 @@ macro_rules! define_state_filter {
 
 mod issue;
 mod pr;
+pub mod threads;
+mod url;
 
-pub use client::{detect_repo, gh_command, DetectRepoError};
-pub use comment::{create_multiline_review_comment, create_reply_comment, create_review_comment};
+pub use backend::{
+    backend_from_config, ApiMethod, ApiRequest, GhCliBackend, GitHubBackend, RestBackend,
+};
+pub use client::{
+    detect_repo, github_host, parse_remote_host, set_github_host, DetectRepoError, GhRetryPolicy,
+    DEFAULT_GITHUB_HOST,
+};
+pub use comment::{
+    add_review_comment_reaction, create_multiline_review_comment, create_reply_comment,
+    create_review_comment, remove_review_comment_reaction, DraftReviewComment, ReactionKind,
+    Reactions,
+};
 pub use commit::{
     fetch_commit_diff, fetch_local_commit_diff, fetch_local_commits, fetch_pr_commits,
     format_relative_time, CommitListPage, PrCommit,
@@ -59,8 +72,13 @@ pub use issue::{
 };
 
 pub use pr::{
-    fetch_changed_files, fetch_files_viewed_state, fetch_pr, fetch_pr_checks, fetch_pr_diff,
-    fetch_pr_list, fetch_pr_list_with_offset, set_file_viewed, submit_review, Branch, ChangedFile,
-    CheckItem, CiStatus, Label, PrListPage, PrStateFilter, PullRequest, PullRequestSummary,
-    StatusCheckRollupItem, User,
+    add_pr_people, fetch_changed_files, fetch_changed_files_with_progress, fetch_compare_files,
+    fetch_file_content, fetch_files_viewed_state, fetch_pr, fetch_pr_checks, fetch_pr_diff,
+    fetch_pr_diff_streaming, fetch_pr_file_patch, fetch_pr_list, fetch_pr_list_with_offset,
+    find_open_pr_by_head, is_file_list_truncated, parse_logins, set_file_viewed, submit_review,
+    Branch, ChangedFile, CheckCounts, CheckItem, CiStatus, Label, PrListFilters, PrListPage,
+    PrPeopleRole, PrStateFilter, PullRequest, PullRequestSummary, StatusCheckRollupItem, User,
+    MAX_PR_FILES,
 };
+pub use threads::{fetch_review_threads, set_review_thread_resolved, ReviewThread};
+pub use url::{file_page_url, issue_url, permalink_url, pr_url, GithubFilePage};
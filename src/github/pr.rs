@@ -1,12 +1,15 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 
-use super::client::{
-    check_graphql_errors, gh_api, gh_api_graphql, gh_api_paginate, gh_command,
-    gh_command_allow_exit_codes, FieldValue,
-};
+use super::backend::{api_json, ApiRequest, GitHubBackend, PER_PAGE};
+use super::client::check_graphql_errors;
+use super::comment::DraftReviewComment;
 use crate::app::ReviewAction;
+use crate::diff::UnifiedDiffStream;
 
 define_state_filter!(PrStateFilter);
 
@@ -31,34 +34,90 @@ pub enum CiStatus {
 
 impl CiStatus {
     pub fn from_rollup(items: &[StatusCheckRollupItem]) -> Self {
-        if items.is_empty() {
-            return Self::None;
+        CheckCounts::from_rollup(items).status()
+    }
+}
+
+/// Number of checks per state, shown as `checks: 5✓ 1✗ 2•` in the header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CheckCounts {
+    pub passed: usize,
+    pub failed: usize,
+    pub pending: usize,
+    /// Skipped checks count as passing for the overall status but are not shown.
+    pub skipped: usize,
+}
+
+impl CheckCounts {
+    /// Count `gh pr checks --json` items by their `bucket`.
+    pub fn from_checks(checks: &[CheckItem]) -> Self {
+        let mut counts = Self::default();
+        for check in checks {
+            match check.bucket.as_deref() {
+                Some("pass") => counts.passed += 1,
+                Some("fail") | Some("cancel") => counts.failed += 1,
+                Some("pending") => counts.pending += 1,
+                Some("skipping") => counts.skipped += 1,
+                _ => {}
+            }
         }
-        let mut has_pending = false;
+        counts
+    }
+
+    /// Count the `statusCheckRollup` items returned with the PR list.
+    pub fn from_rollup(items: &[StatusCheckRollupItem]) -> Self {
+        let mut counts = Self::default();
         for item in items {
             match item.type_name.as_str() {
                 "CheckRun" => match item.conclusion.as_deref() {
-                    Some("SUCCESS") | Some("NEUTRAL") | Some("SKIPPED") => {}
+                    Some("SUCCESS") | Some("NEUTRAL") => counts.passed += 1,
+                    Some("SKIPPED") => counts.skipped += 1,
                     // gh returns conclusion: "" for in-progress/queued checks
-                    None | Some("") => {
-                        has_pending = true;
-                    }
-                    Some(_) => return Self::Failure,
+                    None | Some("") => counts.pending += 1,
+                    Some(_) => counts.failed += 1,
                 },
                 "StatusContext" => match item.state.as_deref() {
-                    Some("SUCCESS") => {}
+                    Some("SUCCESS") => counts.passed += 1,
                     // gh returns state: "" for in-progress StatusContext entries
-                    Some("PENDING") | Some("EXPECTED") | Some("") => has_pending = true,
-                    Some(_) => return Self::Failure,
+                    Some("PENDING") | Some("EXPECTED") | Some("") => counts.pending += 1,
+                    Some(_) => counts.failed += 1,
                     None => {}
                 },
                 _ => {}
             }
         }
-        if has_pending {
-            Self::Pending
+        counts
+    }
+
+    pub fn total(&self) -> usize {
+        self.passed + self.failed + self.pending + self.skipped
+    }
+
+    /// Overall status: any failure fails, then any pending check is pending.
+    pub fn status(&self) -> CiStatus {
+        if self.total() == 0 {
+            CiStatus::None
+        } else if self.failed > 0 {
+            CiStatus::Failure
+        } else if self.pending > 0 {
+            CiStatus::Pending
+        } else {
+            CiStatus::Success
+        }
+    }
+
+    /// Compact summary such as `checks: 5✓ 1✗ 2•` (zero counts are omitted).
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [(self.passed, "✓"), (self.failed, "✗"), (self.pending, "•")]
+            .iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, mark)| format!("{}{}", n, mark))
+            .collect();
+        if parts.is_empty() {
+            // Only skipped checks
+            format!("checks: {} skipped", self.skipped)
         } else {
-            Self::Success
+            format!("checks: {}", parts.join(" "))
         }
     }
 }
@@ -110,6 +169,14 @@ pub struct PullRequest {
     pub base: Branch,
     pub user: User,
     pub updated_at: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    /// github.com 上の PR ページ（古いキャッシュには存在しない）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+    /// PR の変更ファイル総数（古いキャッシュには存在しない）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed_files: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,48 +202,265 @@ pub struct ChangedFile {
     pub viewed: bool,
 }
 
-pub async fn fetch_pr(repo: &str, pr_number: u32) -> Result<PullRequest> {
-    let endpoint = format!("repos/{}/pulls/{}", repo, pr_number);
-    let json = gh_api(&endpoint).await?;
-    serde_json::from_value(json).context("Failed to parse PR response")
+pub async fn fetch_pr(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+) -> Result<PullRequest> {
+    backend.fetch_pr(repo, pr_number).await
+}
+
+/// PR ファイル一覧 API の 1 ページあたりの件数（REST の上限）
+const CHANGED_FILES_PER_PAGE: usize = PER_PAGE;
+
+/// PR ファイル一覧 API が返すファイル数の上限。これを超えるファイルは一覧に現れない
+pub const MAX_PR_FILES: usize = 3000;
+
+/// 取得したファイル一覧が API の上限で打ち切られているか
+///
+/// 上限ちょうどの件数が返った場合と、PR の `changed_files` が取得件数を上回る場合に true。
+pub fn is_file_list_truncated(pr: &PullRequest, fetched: usize) -> bool {
+    fetched >= MAX_PR_FILES || pr.changed_files.is_some_and(|total| total > fetched)
+}
+
+pub async fn fetch_changed_files(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+) -> Result<Vec<ChangedFile>> {
+    fetch_changed_files_with_progress(backend, repo, pr_number, |_| {}).await
 }
 
-pub async fn fetch_changed_files(repo: &str, pr_number: u32) -> Result<Vec<ChangedFile>> {
-    let endpoint = format!("repos/{}/pulls/{}/files?per_page=100", repo, pr_number);
-    let json = gh_api_paginate(&endpoint).await?;
-    serde_json::from_value(json).context("Failed to parse changed files response")
+/// PR の変更ファイルを全ページ取得する。`on_progress` にはページ取得ごとに累計件数を渡す。
+pub async fn fetch_changed_files_with_progress(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+    on_progress: impl FnMut(usize),
+) -> Result<Vec<ChangedFile>> {
+    collect_paged_files(
+        |page| backend.fetch_changed_files_page(repo, pr_number, page),
+        on_progress,
+    )
+    .await
 }
 
+/// compare API のレスポンスのうち変更ファイル一覧だけを読む
+#[derive(Debug, Deserialize)]
+struct CompareResponse {
+    #[serde(default)]
+    files: Vec<ChangedFile>,
+}
+
+/// PR の 1 ファイル分の patch を取得する（`index` はファイル一覧上の位置）
+///
+/// ファイル一覧 API の該当ページだけを読む。GitHub が patch を省いた大きなファイルは
+/// diff を流し読みし、そのファイルの部分だけを残す。
+pub async fn fetch_pr_file_patch(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+    filename: &str,
+    index: usize,
+) -> Result<Option<String>> {
+    let page = index / CHANGED_FILES_PER_PAGE + 1;
+    let output = backend
+        .fetch_changed_files_page(repo, pr_number, page)
+        .await?;
+    let batch: Vec<ChangedFile> = serde_json::from_str(&output)
+        .with_context(|| format!("Failed to parse changed files response (page {})", page))?;
+    if let Some(patch) = batch
+        .into_iter()
+        .find(|file| file.filename == filename)
+        .and_then(|file| file.patch)
+    {
+        return Ok(Some(patch));
+    }
+
+    let state = Arc::new(Mutex::new((UnifiedDiffStream::default(), None)));
+    let line_state = state.clone();
+    let target = filename.to_string();
+    backend
+        .fold_pr_diff_lines(
+            repo,
+            pr_number,
+            Box::new(move |line| {
+                if let Ok(mut state) = line_state.lock() {
+                    let (stream, found) = &mut *state;
+                    if let Some((name, patch)) = stream.push_line(line) {
+                        if name == target {
+                            *found = Some(patch);
+                        }
+                    }
+                }
+            }),
+        )
+        .await?;
+    let (stream, found) = Arc::try_unwrap(state)
+        .ok()
+        .and_then(|state| state.into_inner().ok())
+        .context("diff stream state is still shared")?;
+    Ok(found.or_else(|| {
+        stream
+            .finish()
+            .filter(|(name, _)| name == filename)
+            .map(|(_, patch)| patch)
+    }))
+}
+
+/// `base...head` の変更ファイルを compare API（`gh api repos/{repo}/compare/...`）から取得する
+///
+/// PR の一部のコミット範囲だけをレビューするために使う。compare API が返すのは最大 300 ファイル。
+pub async fn fetch_compare_files(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    base: &str,
+    head: &str,
+) -> Result<Vec<ChangedFile>> {
+    let endpoint = format!("repos/{}/compare/{}...{}", repo, base, head);
+    let output = backend.api(ApiRequest::get(&endpoint)).await?;
+    parse_compare_files(&output)
+}
+
+/// `path` の `git_ref` 時点の内容を contents API（raw メディアタイプ）で取得する
+///
+/// diff の hunk 周辺の文脈行を広げるために使う。
+pub async fn fetch_file_content(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    path: &str,
+    git_ref: &str,
+) -> Result<String> {
+    let endpoint = format!(
+        "repos/{}/contents/{}?ref={}",
+        repo,
+        super::url::encode_path(path),
+        git_ref
+    );
+    backend
+        .api(ApiRequest::get(&endpoint).accept("application/vnd.github.raw"))
+        .await
+}
+
+fn parse_compare_files(json: &str) -> Result<Vec<ChangedFile>> {
+    let response: CompareResponse =
+        serde_json::from_str(json).context("Failed to parse compare response")?;
+    Ok(response.files)
+}
+
+/// `page=1, 2, ...` を件数が 1 ページ分に満たなくなるまで取得して連結する
+///
+/// 取得中に PR が更新されるとページ境界がずれて同じファイルが重複し得るため、
+/// ファイル名で重複を除く（先に取得したものを残す）。
+/// [`MAX_PR_FILES`] に達したら API はそれ以上返さないので、そこで打ち切る。
+async fn collect_paged_files<F, Fut>(
+    mut fetch_page: F,
+    mut on_progress: impl FnMut(usize),
+) -> Result<Vec<ChangedFile>>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let mut files = Vec::new();
+    let mut seen = HashSet::new();
+    for page in 1.. {
+        let output = fetch_page(page).await?;
+        let batch: Vec<ChangedFile> = serde_json::from_str(&output)
+            .with_context(|| format!("Failed to parse changed files response (page {})", page))?;
+        let batch_len = batch.len();
+        for file in batch {
+            if seen.insert(file.filename.clone()) {
+                files.push(file);
+            }
+        }
+        on_progress(files.len());
+        if batch_len < CHANGED_FILES_PER_PAGE || files.len() >= MAX_PR_FILES {
+            break;
+        }
+    }
+    Ok(files)
+}
+
+/// レビューを送信する（`comments` はインラインコメントとしてレビューと同時に作成される）
 pub async fn submit_review(
+    backend: &dyn GitHubBackend,
     repo: &str,
     pr_number: u32,
     action: ReviewAction,
     body: &str,
+    comments: &[DraftReviewComment],
 ) -> Result<()> {
-    let action_flag = match action {
-        ReviewAction::Approve => "--approve",
-        ReviewAction::RequestChanges => "--request-changes",
-        ReviewAction::Comment => "--comment",
-    };
+    backend
+        .submit_review(repo, pr_number, action, body, comments)
+        .await
+}
 
-    gh_command(&[
-        "pr",
-        "review",
-        &pr_number.to_string(),
-        action_flag,
-        "-b",
-        body,
-        "-R",
-        repo,
-    ])
-    .await?;
+/// Fetch the raw diff for a PR (`gh pr diff` or the REST diff media type)
+pub async fn fetch_pr_diff(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+) -> Result<String> {
+    backend.fetch_pr_diff(repo, pr_number).await
+}
 
-    Ok(())
+/// `gh pr diff` をストリーミングで取得する。ファイルの patch が揃うたびに `on_file` を
+/// diff 順に呼び、最後に全ファイルの filename -> patch を返す。
+///
+/// ストリーミングできないバックエンドでは diff 全体を取得してから同じ順に `on_file` を呼ぶ。
+pub async fn fetch_pr_diff_streaming(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+    on_file: impl FnMut(&str, &str) + Send + 'static,
+) -> Result<HashMap<String, String>> {
+    let state = Arc::new(Mutex::new((
+        UnifiedDiffStream::default(),
+        HashMap::new(),
+        on_file,
+    )));
+    let line_state = state.clone();
+    backend
+        .fold_pr_diff_lines(
+            repo,
+            pr_number,
+            Box::new(move |line| {
+                if let Ok(mut state) = line_state.lock() {
+                    push_diff_line(&mut state, line);
+                }
+            }),
+        )
+        .await?;
+    let state = Arc::try_unwrap(state)
+        .ok()
+        .and_then(|state| state.into_inner().ok())
+        .context("diff stream state is still shared")?;
+    // 最後のファイルも `on_file` を blocking スレッドから呼ぶ
+    tokio::task::spawn_blocking(move || finish_diff_stream(state))
+        .await
+        .context("diff splitting task panicked")
+}
+
+type DiffStreamState<F> = (UnifiedDiffStream, HashMap<String, String>, F);
+
+fn push_diff_line<F: FnMut(&str, &str)>(
+    (stream, patches, on_file): &mut DiffStreamState<F>,
+    line: &str,
+) {
+    if let Some((filename, patch)) = stream.push_line(line) {
+        on_file(&filename, &patch);
+        patches.insert(filename, patch);
+    }
 }
 
-/// Fetch the raw diff for a PR using `gh pr diff`
-pub async fn fetch_pr_diff(repo: &str, pr_number: u32) -> Result<String> {
-    gh_command(&["pr", "diff", &pr_number.to_string(), "-R", repo]).await
+fn finish_diff_stream<F: FnMut(&str, &str)>(
+    (stream, mut patches, mut on_file): DiffStreamState<F>,
+) -> HashMap<String, String> {
+    if let Some((filename, patch)) = stream.finish() {
+        on_file(&filename, &patch);
+        patches.insert(filename, patch);
+    }
+    patches
 }
 
 #[derive(Debug, Deserialize)]
@@ -217,7 +501,7 @@ struct GraphqlFilesViewedStateResponse {
 }
 
 pub async fn fetch_files_viewed_state(
-    _repo: &str,
+    backend: &dyn GitHubBackend,
     pr_node_id: &str,
 ) -> Result<HashMap<String, bool>> {
     let query = r#"
@@ -243,12 +527,8 @@ query($pullRequestId: ID!, $after: String) {
     let mut after: Option<String> = None;
 
     loop {
-        let mut fields = vec![("pullRequestId", FieldValue::String(pr_node_id))];
-        if let Some(cursor) = after.as_deref() {
-            fields.push(("after", FieldValue::String(cursor)));
-        }
-
-        let response = gh_api_graphql(query, &fields).await?;
+        let variables = json!({ "pullRequestId": pr_node_id, "after": after });
+        let response = backend.graphql(query, variables).await?;
 
         check_graphql_errors(&response)?;
 
@@ -298,7 +578,7 @@ mutation($pullRequestId: ID!, $path: String!) {
 "#;
 
 pub async fn set_file_viewed(
-    _repo: &str,
+    backend: &dyn GitHubBackend,
     pr_node_id: &str,
     path: &str,
     viewed: bool,
@@ -309,14 +589,9 @@ pub async fn set_file_viewed(
         UNMARK_VIEWED_QUERY
     };
 
-    let response = gh_api_graphql(
-        query,
-        &[
-            ("pullRequestId", FieldValue::String(pr_node_id)),
-            ("path", FieldValue::String(path)),
-        ],
-    )
-    .await?;
+    let response = backend
+        .graphql(query, json!({ "pullRequestId": pr_node_id, "path": path }))
+        .await?;
 
     check_graphql_errors(&response)?;
 
@@ -329,35 +604,237 @@ pub struct PrListPage {
     pub has_more: bool,
 }
 
-pub async fn fetch_pr_list(repo: &str, state: PrStateFilter, limit: u32) -> Result<PrListPage> {
-    fetch_pr_list_with_offset(repo, state, 0, limit).await
+/// PR 一覧の作成者・担当者・ラベルによる絞り込み条件
+///
+/// `author:<login> assignee:<login> label:<name>` 形式のクエリで入力し、
+/// `gh pr list` の `--author` / `--assignee` / `--label` に変換する。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrListFilters {
+    pub author: Option<String>,
+    pub assignee: Option<String>,
+    pub labels: Vec<String>,
+}
+
+impl PrListFilters {
+    /// `author:` / `assignee:` / `label:` をスペース区切りで並べたクエリを解釈する。
+    /// `label:` は複数指定でき、`author:` / `assignee:` は後の指定が優先される。
+    pub fn parse(query: &str) -> std::result::Result<Self, String> {
+        let mut filters = Self::default();
+        for token in query.split_whitespace() {
+            let (key, value) = token
+                .split_once(':')
+                .filter(|(_, value)| !value.is_empty())
+                .ok_or_else(|| format!("Expected key:value, got \"{}\"", token))?;
+            match key {
+                "author" => filters.author = Some(value.to_string()),
+                "assignee" => filters.assignee = Some(value.to_string()),
+                "label" => filters.labels.push(value.to_string()),
+                _ => return Err(format!("Unknown filter \"{}\"", key)),
+            }
+        }
+        Ok(filters)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.author.is_none() && self.assignee.is_none() && self.labels.is_empty()
+    }
+
+    /// `parse` で読み戻せるクエリ文字列（ヘッダ表示と再編集用）
+    pub fn query(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(author) = &self.author {
+            parts.push(format!("author:{}", author));
+        }
+        if let Some(assignee) = &self.assignee {
+            parts.push(format!("assignee:{}", assignee));
+        }
+        parts.extend(self.labels.iter().map(|label| format!("label:{}", label)));
+        parts.join(" ")
+    }
+}
+
+/// `gh pr list` の引数を組み立てる
+pub(super) fn pr_list_args(
+    repo: &str,
+    state: PrStateFilter,
+    filters: &PrListFilters,
+    fetch_count: u32,
+) -> Vec<String> {
+    let mut args: Vec<String> = ["pr", "list", "-R", repo, "-s", state.as_gh_arg()]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    if let Some(author) = &filters.author {
+        args.extend(["--author".to_string(), author.clone()]);
+    }
+    if let Some(assignee) = &filters.assignee {
+        args.extend(["--assignee".to_string(), assignee.clone()]);
+    }
+    for label in &filters.labels {
+        args.extend(["--label".to_string(), label.clone()]);
+    }
+    args.extend([
+        "--json".to_string(),
+        "number,title,state,author,isDraft,labels,updatedAt,statusCheckRollup".to_string(),
+        "--limit".to_string(),
+        fetch_count.to_string(),
+    ]);
+    args
+}
+
+/// GraphQL 検索クエリ（`gh pr list` の絞り込みと同じ条件）
+fn pr_search_query(repo: &str, state: PrStateFilter, filters: &PrListFilters) -> String {
+    let mut terms = vec![format!("repo:{}", repo), "is:pr".to_string()];
+    match state {
+        PrStateFilter::Open => terms.push("is:open".to_string()),
+        PrStateFilter::Closed => terms.push("is:closed".to_string()),
+        PrStateFilter::All => {}
+    }
+    if let Some(author) = &filters.author {
+        terms.push(format!("author:{}", author));
+    }
+    if let Some(assignee) = &filters.assignee {
+        terms.push(format!("assignee:{}", assignee));
+    }
+    terms.extend(
+        filters
+            .labels
+            .iter()
+            .map(|label| format!("label:\"{}\"", label)),
+    );
+    terms.push("sort:created-desc".to_string());
+    terms.join(" ")
+}
+
+const PR_SEARCH_QUERY: &str = r#"
+query($q: String!, $first: Int!, $after: String) {
+  search(query: $q, type: ISSUE, first: $first, after: $after) {
+    nodes {
+      ... on PullRequest {
+        number
+        title
+        state
+        isDraft
+        updatedAt
+        author { login }
+        labels(first: 20) { nodes { name } }
+        commits(last: 1) {
+          nodes {
+            commit {
+              statusCheckRollup {
+                contexts(first: 100) {
+                  nodes {
+                    __typename
+                    ... on CheckRun { name status conclusion }
+                    ... on StatusContext { context state }
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+    pageInfo {
+      hasNextPage
+      endCursor
+    }
+  }
+}
+"#;
+
+/// GraphQL の PR ノードを `gh pr list --json` と同じ形に直す
+fn pr_summary_from_graphql(node: &Value) -> Value {
+    let rollup = node
+        .pointer("/commits/nodes/0/commit/statusCheckRollup/contexts/nodes")
+        .cloned()
+        .unwrap_or_else(|| json!([]));
+    json!({
+        "number": node["number"],
+        "title": node["title"],
+        "state": node["state"],
+        "isDraft": node["isDraft"],
+        "updatedAt": node["updatedAt"],
+        "author": graphql_author(node),
+        "labels": node.pointer("/labels/nodes").cloned().unwrap_or_else(|| json!([])),
+        "statusCheckRollup": rollup,
+    })
+}
+
+/// 退会済みユーザーは `author: null` になるため `ghost` として扱う（gh と同じ）
+pub(super) fn graphql_author(node: &Value) -> Value {
+    match node.get("author") {
+        Some(author) if !author.is_null() => author.clone(),
+        _ => json!({ "login": "ghost" }),
+    }
+}
+
+/// 新しい順に最大 `count` 件の PR を GraphQL 検索で取得する（`gh pr list` を使わない実装）
+pub(super) async fn search_pr_list<B: GitHubBackend + ?Sized>(
+    backend: &B,
+    repo: &str,
+    state: PrStateFilter,
+    filters: &PrListFilters,
+    count: u32,
+) -> Result<Vec<PullRequestSummary>> {
+    let q = pr_search_query(repo, state, filters);
+    let mut items = Vec::new();
+    let mut after: Option<String> = None;
+    while items.len() < count as usize {
+        let first = (count as usize - items.len()).min(PER_PAGE);
+        let response = backend
+            .graphql(
+                PR_SEARCH_QUERY,
+                json!({ "q": q, "first": first, "after": after }),
+            )
+            .await?;
+        check_graphql_errors(&response)?;
+        let search = response
+            .pointer("/data/search")
+            .context("GitHub GraphQL response missing data")?;
+        for node in search["nodes"].as_array().into_iter().flatten() {
+            // PR 以外（Issue）は空オブジェクトで返る
+            if node.get("number").is_some() {
+                items.push(
+                    serde_json::from_value(pr_summary_from_graphql(node))
+                        .context("Failed to parse PR list response")?,
+                );
+            }
+        }
+        let page_info: GraphqlPageInfo = serde_json::from_value(search["pageInfo"].clone())
+            .context("Failed to parse PR list response")?;
+        match page_info.end_cursor.filter(|_| page_info.has_next_page) {
+            Some(cursor) => after = Some(cursor),
+            None => break,
+        }
+    }
+    Ok(items)
+}
+
+pub async fn fetch_pr_list(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    state: PrStateFilter,
+    filters: &PrListFilters,
+    limit: u32,
+) -> Result<PrListPage> {
+    fetch_pr_list_with_offset(backend, repo, state, filters, 0, limit).await
 }
 
 /// PR一覧取得（オフセット付き、追加ロード用）
 pub async fn fetch_pr_list_with_offset(
+    backend: &dyn GitHubBackend,
     repo: &str,
     state: PrStateFilter,
+    filters: &PrListFilters,
     offset: u32,
     limit: u32,
 ) -> Result<PrListPage> {
     // gh pr list doesn't support offset directly, so we fetch offset+limit+1 and skip
     let fetch_count = offset + limit + 1;
-    let output = gh_command(&[
-        "pr",
-        "list",
-        "-R",
-        repo,
-        "-s",
-        state.as_gh_arg(),
-        "--json",
-        "number,title,state,author,isDraft,labels,updatedAt,statusCheckRollup",
-        "--limit",
-        &fetch_count.to_string(),
-    ])
-    .await?;
-
-    let all_items: Vec<PullRequestSummary> =
-        serde_json::from_str(&output).context("Failed to parse PR list response")?;
+    let all_items = backend
+        .fetch_pr_list(repo, state, filters, fetch_count)
+        .await?;
 
     // Check if there are more items beyond what we're returning
     let has_more = all_items.len() > (offset + limit) as usize;
@@ -372,27 +849,427 @@ pub async fn fetch_pr_list_with_offset(
     Ok(PrListPage { items, has_more })
 }
 
-pub async fn fetch_pr_checks(repo: &str, pr_number: u32) -> Result<Vec<CheckItem>> {
-    let output = gh_command_allow_exit_codes(
-        &[
-            "pr",
-            "checks",
-            &pr_number.to_string(),
-            "-R",
-            repo,
-            "--json",
-            "name,state,bucket,link,workflow,description,startedAt,completedAt",
-        ],
-        &[8], // exit code 8 = checks pending
-    )
-    .await?;
-    serde_json::from_str(&output).context("Failed to parse PR checks response")
+/// `gh pr edit` で PR に追加する相手の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrPeopleRole {
+    Reviewer,
+    Assignee,
+}
+
+impl PrPeopleRole {
+    fn gh_flag(self) -> &'static str {
+        match self {
+            Self::Reviewer => "--add-reviewer",
+            Self::Assignee => "--add-assignee",
+        }
+    }
+
+    /// 入力欄のタイトル・結果メッセージ用
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Reviewer => "reviewers",
+            Self::Assignee => "assignees",
+        }
+    }
+}
+
+/// カンマ（または空白）区切りのログイン一覧を取り出す。先頭の `@` は取り除く。
+///
+/// `@me` はそのまま残す（gh が自分自身に解決する）。レビュアーには `org/team` も指定できる。
+pub fn parse_logins(input: &str, role: PrPeopleRole) -> Result<Vec<String>, String> {
+    let mut logins: Vec<String> = Vec::new();
+    for token in input.split([',', ' ', '\t']).filter(|t| !t.is_empty()) {
+        let login = if token == "@me" {
+            token
+        } else {
+            token.trim_start_matches('@')
+        };
+        let valid_char = |c: char| {
+            c.is_ascii_alphanumeric()
+                || c == '-'
+                || c == '_'
+                || (c == '/' && role == PrPeopleRole::Reviewer)
+        };
+        if login.is_empty() || (login != "@me" && !login.chars().all(valid_char)) {
+            return Err(format!("Invalid login \"{}\"", token));
+        }
+        if !logins.iter().any(|l| l == login) {
+            logins.push(login.to_string());
+        }
+    }
+    if logins.is_empty() {
+        return Err("No logins given".to_string());
+    }
+    Ok(logins)
+}
+
+/// `gh pr edit` の引数を組み立てる（ログインは 1 つのフラグにカンマ区切りで渡す）
+pub(super) fn pr_people_args(
+    repo: &str,
+    pr_number: u32,
+    role: PrPeopleRole,
+    logins: &[String],
+) -> Vec<String> {
+    vec![
+        "pr".to_string(),
+        "edit".to_string(),
+        pr_number.to_string(),
+        "-R".to_string(),
+        repo.to_string(),
+        role.gh_flag().to_string(),
+        logins.join(","),
+    ]
+}
+
+/// レビュアー / 担当者追加の REST リクエスト（エンドポイント, 本文）
+///
+/// `org/team` 形式のレビュアーはチームとして `team_reviewers` に slug を渡す。
+fn pr_people_request(
+    repo: &str,
+    pr_number: u32,
+    role: PrPeopleRole,
+    logins: &[String],
+) -> (String, Value) {
+    match role {
+        PrPeopleRole::Reviewer => {
+            let (teams, users): (Vec<&String>, Vec<&String>) =
+                logins.iter().partition(|login| login.contains('/'));
+            let team_slugs: Vec<&str> = teams
+                .iter()
+                .filter_map(|team| team.split_once('/').map(|(_, slug)| slug))
+                .collect();
+            (
+                format!("repos/{}/pulls/{}/requested_reviewers", repo, pr_number),
+                json!({ "reviewers": users, "team_reviewers": team_slugs }),
+            )
+        }
+        PrPeopleRole::Assignee => (
+            format!("repos/{}/issues/{}/assignees", repo, pr_number),
+            json!({ "assignees": logins }),
+        ),
+    }
+}
+
+/// REST API でレビュアー / 担当者を追加する（`@me` は認証ユーザーに解決する）
+pub(super) async fn request_pr_people<B: GitHubBackend + ?Sized>(
+    backend: &B,
+    repo: &str,
+    pr_number: u32,
+    role: PrPeopleRole,
+    logins: &[String],
+) -> Result<()> {
+    let mut resolved = Vec::with_capacity(logins.len());
+    for login in logins {
+        if login == "@me" {
+            let me: User = api_json(
+                backend,
+                ApiRequest::get("user"),
+                "Failed to parse user response",
+            )
+            .await?;
+            resolved.push(me.login);
+        } else {
+            resolved.push(login.clone());
+        }
+    }
+    let (endpoint, body) = pr_people_request(repo, pr_number, role, &resolved);
+    backend.api(ApiRequest::post(&endpoint, body)).await?;
+    Ok(())
+}
+
+/// PR にレビュアー / 担当者を追加する（`gh pr edit --add-reviewer` / `--add-assignee`）
+pub async fn add_pr_people(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+    role: PrPeopleRole,
+    logins: &[String],
+) -> Result<()> {
+    backend.add_pr_people(repo, pr_number, role, logins).await
+}
+
+/// 指定ブランチを head とする open PR の番号を取得（stacked PR の親解決用）
+///
+/// base が `main` 等の通常ブランチの場合は `None` を返す。
+pub async fn find_open_pr_by_head(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    head_ref: &str,
+) -> Result<Option<u32>> {
+    backend.find_open_pr_by_head(repo, head_ref).await
+}
+
+pub(super) fn parse_pr_numbers(json: &str) -> Result<Vec<u32>> {
+    #[derive(Deserialize)]
+    struct Item {
+        number: u32,
+    }
+    let items: Vec<Item> =
+        serde_json::from_str(json).context("Failed to parse PR list response")?;
+    Ok(items.into_iter().map(|i| i.number).collect())
+}
+
+pub async fn fetch_pr_checks(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+) -> Result<Vec<CheckItem>> {
+    backend.fetch_pr_checks(repo, pr_number).await
+}
+
+const PR_CHECKS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      commits(last: 1) {
+        nodes {
+          commit {
+            statusCheckRollup {
+              contexts(first: 100) {
+                nodes {
+                  __typename
+                  ... on CheckRun {
+                    name
+                    status
+                    conclusion
+                    detailsUrl
+                    startedAt
+                    completedAt
+                    checkSuite { workflowRun { workflow { name } } }
+                  }
+                  ... on StatusContext {
+                    context
+                    state
+                    targetUrl
+                    description
+                    createdAt
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// `gh pr checks` と同じ state → bucket の対応
+fn check_bucket(state: &str) -> &'static str {
+    match state {
+        "SUCCESS" => "pass",
+        "SKIPPED" | "NEUTRAL" => "skipping",
+        "ERROR" | "FAILURE" | "TIMED_OUT" | "ACTION_REQUIRED" | "STARTUP_FAILURE" => "fail",
+        "CANCELLED" => "cancel",
+        _ => "pending",
+    }
+}
+
+/// `statusCheckRollup` の 1 件を `gh pr checks --json` と同じ `CheckItem` に直す
+fn check_item_from_graphql(node: &Value) -> Option<CheckItem> {
+    let text = |pointer: &str| node.pointer(pointer).and_then(Value::as_str);
+    let item = match text("/__typename")? {
+        "CheckRun" => {
+            // 完了していれば conclusion、実行中なら status（QUEUED / IN_PROGRESS）
+            let state = text("/conclusion")
+                .filter(|c| !c.is_empty())
+                .or(text("/status"))
+                .unwrap_or("PENDING");
+            CheckItem {
+                name: text("/name")?.to_string(),
+                state: state.to_string(),
+                bucket: Some(check_bucket(state).to_string()),
+                link: text("/detailsUrl").map(str::to_string),
+                workflow: text("/checkSuite/workflowRun/workflow/name")
+                    .unwrap_or_default()
+                    .to_string(),
+                description: None,
+                started_at: text("/startedAt").map(str::to_string),
+                completed_at: text("/completedAt").map(str::to_string),
+            }
+        }
+        "StatusContext" => {
+            let state = text("/state").unwrap_or("PENDING");
+            CheckItem {
+                name: text("/context")?.to_string(),
+                state: state.to_string(),
+                bucket: Some(check_bucket(state).to_string()),
+                link: text("/targetUrl").map(str::to_string),
+                workflow: String::new(),
+                description: text("/description").map(str::to_string),
+                started_at: text("/createdAt").map(str::to_string),
+                completed_at: text("/createdAt").map(str::to_string),
+            }
+        }
+        _ => return None,
+    };
+    Some(item)
+}
+
+/// PR の最新コミットのチェックを GraphQL で取得する（`gh pr checks` を使わない実装）
+pub(super) async fn query_pr_checks<B: GitHubBackend + ?Sized>(
+    backend: &B,
+    repo: &str,
+    pr_number: u32,
+) -> Result<Vec<CheckItem>> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let response = backend
+        .graphql(
+            PR_CHECKS_QUERY,
+            json!({ "owner": owner, "name": name, "number": pr_number }),
+        )
+        .await?;
+    check_graphql_errors(&response)?;
+    let nodes = response
+        .pointer(
+            "/data/repository/pullRequest/commits/nodes/0/commit/statusCheckRollup/contexts/nodes",
+        )
+        .and_then(Value::as_array);
+    Ok(nodes
+        .into_iter()
+        .flatten()
+        .filter_map(check_item_from_graphql)
+        .collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pr_list_args_with_filters() {
+        let filters =
+            PrListFilters::parse("author:octocat label:bug assignee:@me label:ui").unwrap();
+        assert_eq!(
+            filters.query(),
+            "author:octocat assignee:@me label:bug label:ui"
+        );
+        let args = pr_list_args("owner/repo", PrStateFilter::Closed, &filters, 31);
+        assert_eq!(
+            args,
+            vec![
+                "pr",
+                "list",
+                "-R",
+                "owner/repo",
+                "-s",
+                "closed",
+                "--author",
+                "octocat",
+                "--assignee",
+                "@me",
+                "--label",
+                "bug",
+                "--label",
+                "ui",
+                "--json",
+                "number,title,state,author,isDraft,labels,updatedAt,statusCheckRollup",
+                "--limit",
+                "31",
+            ]
+        );
+
+        // 条件なしなら状態フィルタのみ
+        let args = pr_list_args(
+            "owner/repo",
+            PrStateFilter::Open,
+            &PrListFilters::default(),
+            31,
+        );
+        assert!(!args.iter().any(|a| a.starts_with("--a") || a == "--label"));
+    }
+
+    #[test]
+    fn test_pr_list_filters_parse_errors() {
+        assert!(PrListFilters::parse("  ").unwrap().is_empty());
+        assert_eq!(
+            PrListFilters::parse("reviewer:me").unwrap_err(),
+            "Unknown filter \"reviewer\""
+        );
+        assert!(PrListFilters::parse("author:").is_err());
+        assert!(PrListFilters::parse("octocat").is_err());
+    }
+
+    #[test]
+    fn test_pr_people_args_join_multiple_logins() {
+        let logins = parse_logins("@alice, bob  carol,alice", PrPeopleRole::Reviewer).unwrap();
+        assert_eq!(logins, vec!["alice", "bob", "carol"]);
+        assert_eq!(
+            pr_people_args("owner/repo", 7, PrPeopleRole::Reviewer, &logins),
+            vec![
+                "pr",
+                "edit",
+                "7",
+                "-R",
+                "owner/repo",
+                "--add-reviewer",
+                "alice,bob,carol"
+            ]
+        );
+
+        let logins = parse_logins("@me,octo-cat", PrPeopleRole::Assignee).unwrap();
+        assert_eq!(
+            pr_people_args("owner/repo", 7, PrPeopleRole::Assignee, &logins),
+            vec![
+                "pr",
+                "edit",
+                "7",
+                "-R",
+                "owner/repo",
+                "--add-assignee",
+                "@me,octo-cat"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_logins_rejects_empty_and_invalid() {
+        assert_eq!(
+            parse_logins(" , ", PrPeopleRole::Reviewer).unwrap_err(),
+            "No logins given"
+        );
+        assert_eq!(
+            parse_logins("alice;rm", PrPeopleRole::Reviewer).unwrap_err(),
+            "Invalid login \"alice;rm\""
+        );
+        // チームはレビュアーにのみ指定できる
+        assert!(parse_logins("org/team", PrPeopleRole::Reviewer).is_ok());
+        assert!(parse_logins("org/team", PrPeopleRole::Assignee).is_err());
+        assert!(parse_logins("@", PrPeopleRole::Assignee).is_err());
+    }
+
+    #[test]
+    fn test_parse_pr_numbers() {
+        assert_eq!(parse_pr_numbers(r#"[{"number":42}]"#).unwrap(), vec![42]);
+        assert!(parse_pr_numbers("[]").unwrap().is_empty());
+        assert!(parse_pr_numbers("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_compare_files() {
+        let json = r#"{
+            "status": "ahead",
+            "commits": [],
+            "files": [
+                {"filename": "src/a.rs", "status": "modified", "additions": 1, "deletions": 1,
+                 "patch": "@@ -1 +1 @@\n-a\n+b"},
+                {"filename": "assets/logo.png", "status": "added", "additions": 0, "deletions": 0}
+            ]
+        }"#;
+        let files = parse_compare_files(json).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].filename, "src/a.rs");
+        assert!(files[0].patch.is_some());
+        assert!(files[1].patch.is_none());
+
+        assert!(parse_compare_files(r#"{"status": "identical"}"#)
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_pr_state_filter_as_gh_arg() {
         assert_eq!(PrStateFilter::Open.as_gh_arg(), "open");
@@ -553,6 +1430,56 @@ mod tests {
         assert_eq!(CiStatus::from_rollup(&items), CiStatus::Pending);
     }
 
+    #[test]
+    fn test_check_counts_from_checks_json() {
+        let json = r#"[
+            {"name": "build", "state": "SUCCESS", "bucket": "pass"},
+            {"name": "lint", "state": "SUCCESS", "bucket": "pass"},
+            {"name": "test", "state": "FAILURE", "bucket": "fail"},
+            {"name": "deploy", "state": "CANCELLED", "bucket": "cancel"},
+            {"name": "e2e", "state": "IN_PROGRESS", "bucket": "pending"},
+            {"name": "docs", "state": "SKIPPED", "bucket": "skipping"}
+        ]"#;
+        let checks: Vec<CheckItem> = serde_json::from_str(json).unwrap();
+        let counts = CheckCounts::from_checks(&checks);
+        assert_eq!(
+            counts,
+            CheckCounts {
+                passed: 2,
+                failed: 2,
+                pending: 1,
+                skipped: 1,
+            }
+        );
+        assert_eq!(counts.status(), CiStatus::Failure);
+        assert_eq!(counts.summary(), "checks: 2✓ 2✗ 1•");
+    }
+
+    #[test]
+    fn test_check_counts_summary_and_status() {
+        assert_eq!(CheckCounts::default().status(), CiStatus::None);
+        let passing = CheckCounts {
+            passed: 5,
+            skipped: 1,
+            ..CheckCounts::default()
+        };
+        assert_eq!(passing.status(), CiStatus::Success);
+        assert_eq!(passing.summary(), "checks: 5✓");
+        let pending = CheckCounts {
+            passed: 1,
+            pending: 2,
+            ..CheckCounts::default()
+        };
+        assert_eq!(pending.status(), CiStatus::Pending);
+        assert_eq!(pending.summary(), "checks: 1✓ 2•");
+        let skipped = CheckCounts {
+            skipped: 3,
+            ..CheckCounts::default()
+        };
+        assert_eq!(skipped.status(), CiStatus::Success);
+        assert_eq!(skipped.summary(), "checks: 3 skipped");
+    }
+
     #[test]
     fn test_check_item_deserialize() {
         let json = r#"{
@@ -597,4 +1524,239 @@ mod tests {
         assert_eq!(item.name.as_deref(), Some("build"));
         assert_eq!(item.conclusion.as_deref(), Some("SUCCESS"));
     }
+
+    fn file_page(names: impl IntoIterator<Item = String>) -> String {
+        let files: Vec<serde_json::Value> = names
+            .into_iter()
+            .map(|name| {
+                serde_json::json!({
+                    "filename": name,
+                    "status": "modified",
+                    "additions": 1,
+                    "deletions": 0,
+                })
+            })
+            .collect();
+        serde_json::Value::Array(files).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_collect_paged_files_across_pages_dedups() {
+        // page 1: 100 件, page 2: 100 件（先頭 2 件は page 1 と重複）, page 3: 5 件で終了
+        let pages = [
+            file_page((0..100).map(|i| format!("f{}", i))),
+            file_page((98..198).map(|i| format!("f{}", i))),
+            file_page((198..203).map(|i| format!("f{}", i))),
+        ];
+        let mut requested = Vec::new();
+        let mut progress = Vec::new();
+
+        let files = collect_paged_files(
+            |page| {
+                requested.push(page);
+                let output = pages[page - 1].clone();
+                async move { Ok(output) }
+            },
+            |count| progress.push(count),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(requested, vec![1, 2, 3]);
+        assert_eq!(progress, vec![100, 198, 203]);
+        assert_eq!(files.len(), 203);
+        let unique: HashSet<&str> = files.iter().map(|f| f.filename.as_str()).collect();
+        assert_eq!(unique.len(), 203);
+        assert_eq!(files[0].filename, "f0");
+        assert_eq!(files[202].filename, "f202");
+    }
+
+    #[tokio::test]
+    async fn test_collect_paged_files_stops_at_file_cap() {
+        let mut requested = Vec::new();
+        let files = collect_paged_files(
+            |page| {
+                requested.push(page);
+                let start = (page - 1) * CHANGED_FILES_PER_PAGE;
+                let output =
+                    file_page((start..start + CHANGED_FILES_PER_PAGE).map(|i| format!("f{}", i)));
+                async move { Ok(output) }
+            },
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(files.len(), MAX_PR_FILES);
+        assert_eq!(requested.len(), MAX_PR_FILES / CHANGED_FILES_PER_PAGE);
+    }
+
+    #[test]
+    fn test_is_file_list_truncated() {
+        let mut pr: PullRequest = serde_json::from_value(serde_json::json!({
+            "number": 1,
+            "title": "t",
+            "body": null,
+            "state": "open",
+            "head": {"ref": "h", "sha": "a"},
+            "base": {"ref": "b", "sha": "b"},
+            "user": {"login": "u"},
+            "updated_at": "2024-01-01T00:00:00Z",
+            "changed_files": 3500,
+        }))
+        .unwrap();
+
+        assert!(is_file_list_truncated(&pr, MAX_PR_FILES));
+        assert!(is_file_list_truncated(&pr, 2990));
+        pr.changed_files = Some(120);
+        assert!(!is_file_list_truncated(&pr, 120));
+        // 古いキャッシュなど総数が分からない場合は上限到達だけで判定する
+        pr.changed_files = None;
+        assert!(!is_file_list_truncated(&pr, 2999));
+        assert!(is_file_list_truncated(&pr, MAX_PR_FILES));
+    }
+
+    #[tokio::test]
+    async fn test_collect_paged_files_stops_on_empty_page_and_propagates_errors() {
+        let full = file_page((0..100).map(|i| format!("f{}", i)));
+        let files = collect_paged_files(
+            |page| {
+                let output = if page == 1 {
+                    full.clone()
+                } else {
+                    "[]".to_string()
+                };
+                async move { Ok(output) }
+            },
+            |_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(files.len(), 100);
+
+        let err = collect_paged_files(
+            |page| async move {
+                if page == 1 {
+                    Ok("{}".to_string())
+                } else {
+                    unreachable!()
+                }
+            },
+            |_| {},
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("page 1"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_pr_file_patch_falls_back_to_diff() {
+        use crate::github::backend::mock::RecordingBackend;
+
+        // ファイル一覧に patch が無い（GitHub が省いた）→ diff から該当ファイルだけを取り出す
+        let backend = RecordingBackend {
+            files_pages: HashMap::from([(1, file_page(["big.rs".to_string()]))]),
+            diff: "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-x\n+y\n\
+                   diff --git a/big.rs b/big.rs\n--- a/big.rs\n+++ b/big.rs\n@@ -1 +1 @@\n-old\n+new\n"
+                .to_string(),
+            ..Default::default()
+        };
+        let patch = fetch_pr_file_patch(&backend, "owner/repo", 1, "big.rs", 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(patch.contains("+new"));
+        assert!(!patch.contains("+y"));
+        assert_eq!(
+            backend.calls(),
+            vec![
+                "fetch_changed_files_page owner/repo #1 page=1".to_string(),
+                "fetch_pr_diff owner/repo #1".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pr_search_query_matches_gh_filters() {
+        let filters = PrListFilters::parse("author:alice label:bug label:ui").unwrap();
+        assert_eq!(
+            pr_search_query("owner/repo", PrStateFilter::Open, &filters),
+            "repo:owner/repo is:pr is:open author:alice label:\"bug\" label:\"ui\" sort:created-desc"
+        );
+        assert_eq!(
+            pr_search_query("owner/repo", PrStateFilter::All, &PrListFilters::default()),
+            "repo:owner/repo is:pr sort:created-desc"
+        );
+    }
+
+    #[test]
+    fn test_pr_summary_from_graphql_flattens_nodes() {
+        let node = json!({
+            "number": 7,
+            "title": "Fix",
+            "state": "OPEN",
+            "isDraft": false,
+            "updatedAt": "2024-01-01T00:00:00Z",
+            "author": null,
+            "labels": { "nodes": [{ "name": "bug" }] },
+            "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "contexts": {
+                "nodes": [{ "__typename": "CheckRun", "name": "ci", "status": "COMPLETED", "conclusion": "SUCCESS" }]
+            } } } }] },
+        });
+        let summary: PullRequestSummary =
+            serde_json::from_value(pr_summary_from_graphql(&node)).unwrap();
+        assert_eq!(summary.number, 7);
+        assert_eq!(summary.author.login, "ghost");
+        assert_eq!(summary.labels.len(), 1);
+    }
+
+    #[test]
+    fn test_check_item_from_graphql() {
+        let run = check_item_from_graphql(&json!({
+            "__typename": "CheckRun",
+            "name": "build",
+            "status": "IN_PROGRESS",
+            "conclusion": null,
+            "checkSuite": { "workflowRun": { "workflow": { "name": "CI" } } },
+        }))
+        .unwrap();
+        assert_eq!(run.state, "IN_PROGRESS");
+        assert_eq!(run.bucket.as_deref(), Some("pending"));
+        assert_eq!(run.workflow, "CI");
+
+        let status = check_item_from_graphql(&json!({
+            "__typename": "StatusContext",
+            "context": "deploy",
+            "state": "FAILURE",
+        }))
+        .unwrap();
+        assert_eq!(status.name, "deploy");
+        assert_eq!(status.bucket.as_deref(), Some("fail"));
+
+        assert!(check_item_from_graphql(&json!({ "__typename": "Other" })).is_none());
+    }
+
+    #[test]
+    fn test_pr_people_request_splits_teams() {
+        let (endpoint, body) = pr_people_request(
+            "owner/repo",
+            3,
+            PrPeopleRole::Reviewer,
+            &["alice".to_string(), "org/core".to_string()],
+        );
+        assert_eq!(endpoint, "repos/owner/repo/pulls/3/requested_reviewers");
+        assert_eq!(
+            body,
+            json!({ "reviewers": ["alice"], "team_reviewers": ["core"] })
+        );
+
+        let (endpoint, body) = pr_people_request(
+            "owner/repo",
+            3,
+            PrPeopleRole::Assignee,
+            &["bob".to_string()],
+        );
+        assert_eq!(endpoint, "repos/owner/repo/issues/3/assignees");
+        assert_eq!(body, json!({ "assignees": ["bob"] }));
+    }
 }
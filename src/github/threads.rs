@@ -0,0 +1,305 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::backend::GitHubBackend;
+use super::client::check_graphql_errors;
+
+/// PR のレビュースレッド（GraphQL `PullRequestReviewThread`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewThread {
+    /// GraphQL node ID（resolve / unresolve mutation に渡す）
+    pub id: String,
+    pub is_resolved: bool,
+    pub path: String,
+    /// スレッド内コメントの REST ID（`ReviewComment::id`）。先頭がルートコメント
+    pub comment_ids: Vec<u64>,
+}
+
+impl ReviewThread {
+    /// REST のコメント ID がこのスレッドに属するか
+    pub fn contains_comment(&self, comment_id: u64) -> bool {
+        self.comment_ids.contains(&comment_id)
+    }
+}
+
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!, $after: String) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $after) {
+        nodes {
+          id
+          isResolved
+          path
+          comments(first: 100) {
+            nodes {
+              databaseId
+            }
+          }
+        }
+        pageInfo {
+          hasNextPage
+          endCursor
+        }
+      }
+    }
+  }
+}
+"#;
+
+const RESOLVE_THREAD_QUERY: &str = r#"
+mutation($threadId: ID!) {
+  resolveReviewThread(input: { threadId: $threadId }) {
+    thread {
+      isResolved
+    }
+  }
+}
+"#;
+
+const UNRESOLVE_THREAD_QUERY: &str = r#"
+mutation($threadId: ID!) {
+  unresolveReviewThread(input: { threadId: $threadId }) {
+    thread {
+      isResolved
+    }
+  }
+}
+"#;
+
+#[derive(Debug, Deserialize)]
+struct GraphqlReviewThreadsResponse {
+    data: Option<GraphqlReviewThreadsData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlReviewThreadsData {
+    repository: Option<GraphqlRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlRepository {
+    pull_request: Option<GraphqlPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlPullRequest {
+    review_threads: GraphqlReviewThreadConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlReviewThreadConnection {
+    nodes: Vec<GraphqlReviewThreadNode>,
+    page_info: GraphqlPageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlPageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlReviewThreadNode {
+    id: String,
+    is_resolved: bool,
+    path: String,
+    comments: GraphqlThreadCommentConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlThreadCommentConnection {
+    nodes: Vec<GraphqlThreadCommentNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlThreadCommentNode {
+    /// 削除済みユーザーなどで null になり得る
+    database_id: Option<u64>,
+}
+
+impl From<GraphqlReviewThreadNode> for ReviewThread {
+    fn from(node: GraphqlReviewThreadNode) -> Self {
+        Self {
+            id: node.id,
+            is_resolved: node.is_resolved,
+            path: node.path,
+            comment_ids: node
+                .comments
+                .nodes
+                .into_iter()
+                .filter_map(|c| c.database_id)
+                .collect(),
+        }
+    }
+}
+
+/// 1 ページ分のレスポンスをパースし、スレッドと次ページのカーソルを返す
+fn parse_review_threads_page(
+    response: serde_json::Value,
+) -> Result<(Vec<ReviewThread>, Option<String>)> {
+    check_graphql_errors(&response)?;
+    let parsed: GraphqlReviewThreadsResponse = serde_json::from_value(response)
+        .context("Failed to parse review threads GraphQL response")?;
+    let Some(pull_request) = parsed
+        .data
+        .and_then(|data| data.repository)
+        .and_then(|repo| repo.pull_request)
+    else {
+        anyhow::bail!("Pull request not found for review threads query");
+    };
+
+    let connection = pull_request.review_threads;
+    let next_cursor = if connection.page_info.has_next_page {
+        let Some(cursor) = connection.page_info.end_cursor else {
+            anyhow::bail!("GitHub GraphQL pageInfo missing endCursor");
+        };
+        Some(cursor)
+    } else {
+        None
+    };
+    let threads = connection
+        .nodes
+        .into_iter()
+        .map(ReviewThread::from)
+        .collect();
+    Ok((threads, next_cursor))
+}
+
+/// PR のレビュースレッドを resolved 状態付きで取得する
+pub async fn fetch_review_threads(
+    backend: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+) -> Result<Vec<ReviewThread>> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let mut threads = Vec::new();
+    let mut after: Option<String> = None;
+    loop {
+        let variables = json!({
+            "owner": owner,
+            "name": name,
+            "number": pr_number,
+            "after": after,
+        });
+        let response = backend.graphql(REVIEW_THREADS_QUERY, variables).await?;
+        let (page, next_cursor) = parse_review_threads_page(response)?;
+        threads.extend(page);
+
+        match next_cursor {
+            Some(cursor) => after = Some(cursor),
+            None => break,
+        }
+    }
+    Ok(threads)
+}
+
+/// レビュースレッドを resolve / unresolve する
+pub async fn set_review_thread_resolved(
+    backend: &dyn GitHubBackend,
+    thread_id: &str,
+    resolved: bool,
+) -> Result<()> {
+    let query = if resolved {
+        RESOLVE_THREAD_QUERY
+    } else {
+        UNRESOLVE_THREAD_QUERY
+    };
+    let response = backend
+        .graphql(query, json!({ "threadId": thread_id }))
+        .await?;
+    check_graphql_errors(&response)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_review_threads_page() {
+        let response = json!({
+            "data": {
+                "repository": {
+                    "pullRequest": {
+                        "reviewThreads": {
+                            "nodes": [
+                                {
+                                    "id": "PRRT_1",
+                                    "isResolved": true,
+                                    "path": "src/main.rs",
+                                    "comments": {
+                                        "nodes": [
+                                            { "databaseId": 10 },
+                                            { "databaseId": null },
+                                            { "databaseId": 12 }
+                                        ]
+                                    }
+                                },
+                                {
+                                    "id": "PRRT_2",
+                                    "isResolved": false,
+                                    "path": "README.md",
+                                    "comments": { "nodes": [{ "databaseId": 20 }] }
+                                }
+                            ],
+                            "pageInfo": { "hasNextPage": true, "endCursor": "Y3Vyc29y" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let (threads, cursor) = parse_review_threads_page(response).unwrap();
+        assert_eq!(cursor.as_deref(), Some("Y3Vyc29y"));
+        assert_eq!(
+            threads,
+            vec![
+                ReviewThread {
+                    id: "PRRT_1".to_string(),
+                    is_resolved: true,
+                    path: "src/main.rs".to_string(),
+                    comment_ids: vec![10, 12],
+                },
+                ReviewThread {
+                    id: "PRRT_2".to_string(),
+                    is_resolved: false,
+                    path: "README.md".to_string(),
+                    comment_ids: vec![20],
+                },
+            ]
+        );
+        assert!(threads[0].contains_comment(12));
+        assert!(!threads[0].contains_comment(20));
+    }
+
+    #[test]
+    fn test_parse_review_threads_page_errors() {
+        let last_page = json!({
+            "data": { "repository": { "pullRequest": { "reviewThreads": {
+                "nodes": [],
+                "pageInfo": { "hasNextPage": false, "endCursor": null }
+            } } } }
+        });
+        let (threads, cursor) = parse_review_threads_page(last_page).unwrap();
+        assert!(threads.is_empty());
+        assert!(cursor.is_none());
+
+        let missing_pr = json!({ "data": { "repository": { "pullRequest": null } } });
+        assert!(parse_review_threads_page(missing_pr).is_err());
+
+        let errors = json!({ "errors": [{ "message": "Could not resolve to a Repository" }] });
+        let err = parse_review_threads_page(errors).unwrap_err();
+        assert!(err.to_string().contains("Could not resolve"));
+    }
+}
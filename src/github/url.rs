@@ -0,0 +1,168 @@
+/// GitHub（github.com / Enterprise）上のファイル単位ページ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GithubFilePage {
+    /// `/blame/<ref>/<path>`
+    Blame,
+    /// `/commits/<ref>/<path>`
+    History,
+}
+
+impl GithubFilePage {
+    fn path_segment(self) -> &'static str {
+        match self {
+            Self::Blame => "blame",
+            Self::History => "commits",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Blame => "blame",
+            Self::History => "history",
+        }
+    }
+}
+
+/// Build the URL for a file's blame or history page on `host`.
+///
+/// `git_ref` may be a branch name or a commit SHA. Path segments are
+/// percent-encoded for characters that would otherwise break the URL.
+pub fn file_page_url(
+    host: &str,
+    repo: &str,
+    git_ref: &str,
+    path: &str,
+    page: GithubFilePage,
+) -> String {
+    format!(
+        "https://{}/{}/{}/{}/{}",
+        host,
+        repo,
+        page.path_segment(),
+        encode_path(git_ref),
+        encode_path(path)
+    )
+}
+
+/// Build the URL of pull request `number` on `host`.
+pub fn pr_url(host: &str, repo: &str, number: u32) -> String {
+    format!("https://{}/{}/pull/{}", host, repo, number)
+}
+
+/// Build the URL of issue `number` on `host`.
+pub fn issue_url(host: &str, repo: &str, number: u32) -> String {
+    format!("https://{}/{}/issues/{}", host, repo, number)
+}
+
+/// Build a permalink on `host` to lines `start..=end` of a file at `sha`.
+///
+/// A single line links as `#L10`, a range as `#L10-L12`.
+pub fn permalink_url(
+    host: &str,
+    repo: &str,
+    sha: &str,
+    path: &str,
+    start: u32,
+    end: u32,
+) -> String {
+    let anchor = if start == end {
+        format!("L{}", start)
+    } else {
+        format!("L{}-L{}", start, end)
+    };
+    format!(
+        "https://{}/{}/blob/{}/{}#{}",
+        host,
+        repo,
+        sha,
+        encode_path(path),
+        anchor
+    )
+}
+
+pub(super) fn encode_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            '%' => out.push_str("%25"),
+            ' ' => out.push_str("%20"),
+            '#' => out.push_str("%23"),
+            '?' => out.push_str("%3F"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_page_url_blame_and_history() {
+        assert_eq!(
+            file_page_url(
+                "github.com",
+                "owner/repo",
+                "abc123",
+                "src/main.rs",
+                GithubFilePage::Blame
+            ),
+            "https://github.com/owner/repo/blame/abc123/src/main.rs"
+        );
+        assert_eq!(
+            file_page_url(
+                "github.com",
+                "owner/repo",
+                "feature/x",
+                "src/main.rs",
+                GithubFilePage::History
+            ),
+            "https://github.com/owner/repo/commits/feature/x/src/main.rs"
+        );
+    }
+
+    #[test]
+    fn test_permalink_url_single_line_and_range() {
+        assert_eq!(
+            permalink_url("github.com", "owner/repo", "abc123", "src/main.rs", 42, 42),
+            "https://github.com/owner/repo/blob/abc123/src/main.rs#L42"
+        );
+        assert_eq!(
+            permalink_url("github.com", "owner/repo", "abc123", "docs/a b.md", 3, 7),
+            "https://github.com/owner/repo/blob/abc123/docs/a%20b.md#L3-L7"
+        );
+    }
+
+    #[test]
+    fn test_file_page_url_encodes_special_chars() {
+        assert_eq!(
+            file_page_url(
+                "github.com",
+                "o/r",
+                "main",
+                "docs/a b#1?.md",
+                GithubFilePage::Blame
+            ),
+            "https://github.com/o/r/blame/main/docs/a%20b%231%3F.md"
+        );
+    }
+
+    #[test]
+    fn test_urls_use_enterprise_host() {
+        assert_eq!(
+            permalink_url("ghe.example.com", "org/repo", "abc123", "src/main.rs", 1, 2),
+            "https://ghe.example.com/org/repo/blob/abc123/src/main.rs#L1-L2"
+        );
+        assert_eq!(
+            file_page_url(
+                "ghe.example.com",
+                "org/repo",
+                "main",
+                "a.rs",
+                GithubFilePage::History
+            ),
+            "https://ghe.example.com/org/repo/commits/main/a.rs"
+        );
+    }
+}
@@ -3,14 +3,15 @@ use serde::Serialize;
 use tokio::sync::mpsc;
 
 use std::borrow::Cow;
+use std::sync::Arc;
 
 use crate::ai::adapter::{
     CommentSeverity, Context, ReviewAction, RevieweeOutput, RevieweeStatus, ReviewerOutput,
 };
 use crate::ai::orchestrator::{Orchestrator, OrchestratorCommand, RallyEvent, RallyState};
 use crate::ai::prompt_loader::{PromptLoader, PromptSource};
-use crate::config::Config;
-use crate::github;
+use crate::config::{AiConfig, Config, RallyMode};
+use crate::github::{self, GitHubBackend};
 
 use crate::config::SENSITIVE_AI_KEYS;
 use crate::ui::common::truncate_with_width;
@@ -23,6 +24,7 @@ use crate::ui::common::truncate_with_width;
 pub async fn run_headless_rally(
     repo: &str,
     pr_number: u32,
+    github: Arc<dyn GitHubBackend>,
     config: &Config,
     working_dir: Option<&str>,
     accept_local_overrides: bool,
@@ -30,8 +32,8 @@ pub async fn run_headless_rally(
 ) -> Result<bool> {
     eprintln!("[Headless] Fetching PR #{} from {}...", pr_number, repo);
 
-    let pr = github::fetch_pr(repo, pr_number).await?;
-    let files = github::fetch_changed_files(repo, pr_number).await?;
+    let pr = github::fetch_pr(&*github, repo, pr_number).await?;
+    let files = github::fetch_changed_files(&*github, repo, pr_number).await?;
 
     let mut file_patches: Vec<(String, String)> = files
         .iter()
@@ -42,7 +44,7 @@ pub async fn run_headless_rally(
     let has_missing_patches = files.iter().any(|f| f.patch.is_none());
     if has_missing_patches {
         eprintln!("[Headless] Some files missing patches, fetching full PR diff...");
-        if let Ok(full_diff) = github::fetch_pr_diff(repo, pr_number).await {
+        if let Ok(full_diff) = github::fetch_pr_diff(&*github, repo, pr_number).await {
             let parsed = crate::diff::parse_unified_diff(&full_diff);
             for (filename, patch) in &parsed {
                 if !file_patches.iter().any(|(f, _)| f == filename) {
@@ -75,6 +77,7 @@ pub async fn run_headless_rally(
     run_headless_with_context(
         repo,
         pr_number,
+        github,
         config,
         context,
         accept_local_overrides,
@@ -90,6 +93,7 @@ pub async fn run_headless_rally(
 /// Returns `true` if approved, `false` otherwise.
 pub async fn run_headless_rally_local(
     repo: &str,
+    github: Arc<dyn GitHubBackend>,
     config: &Config,
     working_dir: Option<&str>,
     accept_local_overrides: bool,
@@ -188,6 +192,7 @@ pub async fn run_headless_rally_local(
     run_headless_with_context(
         repo,
         0,
+        github,
         config,
         context,
         accept_local_overrides,
@@ -225,6 +230,7 @@ fn collect_sensitive_overrides(config: &Config) -> Vec<Cow<'static, str>> {
 async fn run_headless_with_context(
     repo: &str,
     pr_number: u32,
+    github: Arc<dyn GitHubBackend>,
     config: &Config,
     context: Context,
     accept_local_overrides: bool,
@@ -259,10 +265,17 @@ async fn run_headless_with_context(
     let local_mode = context.local_mode;
     let auto_post = config.ai.auto_post;
 
+    // Nobody can commit held-back comments without the TUI, so post them directly
+    let ai_config = AiConfig {
+        rally_mode: RallyMode::Post,
+        ..config.ai.clone()
+    };
+
     let mut orchestrator = Orchestrator::new(
         repo,
         pr_number,
-        config.ai.clone(),
+        github,
+        ai_config,
         event_tx,
         Some(cmd_rx),
         prompt_loader,
@@ -476,11 +489,15 @@ async fn run_headless_event_loop(
                 let truncated = truncate_with_width(&result, 200);
                 eprintln!("  < {}: {}", name, truncated);
             }
-            // Suppress AgentThinking, AgentText, and pause events (headless can't pause)
+            // Suppress AgentThinking, AgentText, and pause events (headless can't pause).
+            // PendingComments is never sent: headless always runs in `RallyMode::Post`.
             RallyEvent::AgentThinking(_)
             | RallyEvent::AgentText(_)
             | RallyEvent::Paused
-            | RallyEvent::Resumed => {}
+            | RallyEvent::Resumed
+            | RallyEvent::PendingComments(_)
+            | RallyEvent::StepStarted(_)
+            | RallyEvent::StepReplayed { .. } => {}
             // Auto-skip clarification (headless can't interact)
             RallyEvent::ClarificationNeeded(question) => {
                 eprintln!("  [Clarification needed] {}", question);
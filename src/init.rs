@@ -18,10 +18,82 @@ pub(crate) const DEFAULT_CONFIG: &str = r#"# Editor for writing review body.
 # Supports arguments: editor = "code --wait"
 # editor = "vim"
 
+# Browse without write actions (comments, suggestions, reviews, mark viewed, AI Rally,
+# git ops). Same as the --read-only flag.
+# read_only = false
+
+# Warn in the comment composer when the body exceeds this many characters
+# (not blocking; 0 disables).
+# comment_warn_length = 2000
+
+# Seed the external comment editor with the commented line as a fenced code block.
+# Left unchanged, the quote alone still counts as an empty (cancelled) comment.
+# quote_line_in_comment_editor = false
+
+# Re-fetch an open remote PR (diff and comments) every N seconds (minimum 10).
+# 0 disables.
+# auto_refresh_secs = 0
+
+# Mouse support: click a file to select it, scroll or click the diff.
+# While enabled, hold Shift (most terminals) to select text.
+# mouse = false
+
+# Formatters for `suggest_format` (local mode), keyed by file extension.
+# The selected lines are piped to stdin; stdout pre-fills the suggestion.
+# [formatters]
+# rs = "rustfmt --emit stdout"
+
 [diff]
 theme = "base16-ocean.dark"
-# Number of spaces per tab character in diff view (minimum: 1)
+# Themes for light / dark terminal backgrounds, chosen from $COLORFGBG at startup.
+# `theme` is used when the background cannot be detected.
+# theme_light = "base16-ocean.light"
+# theme_dark = "base16-ocean.dark"
+# Tab stop width in diff view (minimum: 1)
 tab_width = 4
+# Highlight TODO/FIXME-style markers in added lines (default: false)
+# highlight_markers = false
+# marker_words = ["TODO", "FIXME", "HACK", "XXX"]
+# marker_color = "yellow"
+# marker_bold = true
+# Visualize whitespace: "none", "trailing" or "all" (default: "none")
+# show_whitespace = "none"
+# Flag trailing whitespace / tabs in indentation on added lines (default: false)
+# highlight_trailing_whitespace = false
+# highlight_tab_indent = false
+# Collapse hunks that only change line endings (CRLF<->LF) into a summary line (default: true)
+# collapse_line_endings = true
+# Collapse runs of unchanged lines between changes into a placeholder (default: false)
+# collapse_unchanged = false
+# Unchanged lines kept around each change when collapsing (default: 3)
+# context_lines = 3
+# Wrap next/prev comment navigation around the end of the file (default: false)
+# wrap_comment_navigation = false
+# Wrap next/prev change navigation around the end of the PR (default: false)
+# wrap_change_navigation = false
+# Render ANSI color/bold escapes found in diff lines (e.g. terminal-output fixtures) (default: false)
+# render_ansi_content = false
+# Show the exact line (number, LEFT/RIGHT side, content) a comment attaches to in the composer (default: true)
+# comment_anchor_preview = true
+# Match case in the diff search (`/`) (default: false, ASCII case-insensitive)
+# search_case_sensitive = false
+# Line-number gutter: "none", "new" (new-file numbers) or "both" (old and new) (default: "none")
+# gutter = "none"
+# How the gutter numbers lines: "absolute", "relative" (distance from the cursor line)
+# or "hybrid" (absolute on the cursor line, relative elsewhere) (default: "absolute")
+# line_numbers = "absolute"
+# Fold these files into a collapsed "Generated (N)" group at the end of the file list,
+# in addition to files marked `linguist-generated` in .gitattributes (default: [])
+# generated_globs = ["*.lock", "*.snap"]
+
+# Line-level diff colors (markers, line backgrounds, hunk headers); syntax colors are unchanged.
+# Color names or '#rrggbb'.
+# [diff_colors]
+# added_fg = "green"
+# added_bg = '#002800'
+# removed_fg = "red"
+# removed_bg = '#280000'
+# header_fg = "cyan"
 
 [layout]
 # Left panel width percentage in split view (10-90, right panel fills the rest)
@@ -40,6 +112,7 @@ reviewer = "claude"
 reviewee = "claude"
 max_iterations = 10
 timeout_secs = 600
+# stall_warning_secs = 120  # Warn in the AI Rally status when an agent call runs longer
 # prompt_dir = "/custom/path/to/prompts"  # Optional: custom prompt directory
 
 # Additional tools for reviewer agent (Claude only)
@@ -469,6 +469,10 @@ impl<'de> Visitor<'de> for KeySequenceVisitor {
         // Support alternatives separated by "/"
         // e.g., "j/Down" → primary: j, alt: [Down]
         // e.g., "q/Esc" → primary: q, alt: [Esc]
+        // "/" 単体はスラッシュキーそのもの
+        if v.trim() == "/" {
+            return Ok(KeySequence::single(KeyBinding::char('/')));
+        }
         let parts: Vec<&str> = v.split('/').map(|s| s.trim()).collect();
         if parts.len() == 1 {
             let key = parse_key_string(v).map_err(de::Error::custom)?;
@@ -694,6 +698,173 @@ pub fn event_to_keybinding(event: &KeyEvent) -> Option<KeyBinding> {
     Some(KeyBinding { code, modifiers })
 }
 
+/// Category an action is grouped under in the help screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionCategory {
+    Navigation,
+    Review,
+    Ai,
+    View,
+}
+
+impl ActionCategory {
+    /// All categories, in the order the help screen lists them
+    pub const ALL: [ActionCategory; 4] = [
+        ActionCategory::Navigation,
+        ActionCategory::Review,
+        ActionCategory::Ai,
+        ActionCategory::View,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ActionCategory::Navigation => "Navigation",
+            ActionCategory::Review => "Review",
+            ActionCategory::Ai => "AI",
+            ActionCategory::View => "View",
+        }
+    }
+}
+
+/// Help metadata for a configurable action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActionMeta {
+    pub category: ActionCategory,
+    /// Human readable description shown in the help screen
+    pub description: &'static str,
+}
+
+/// Look up the help metadata for an action by its `[keybindings]` name
+pub fn action_meta(name: &str) -> Option<ActionMeta> {
+    use ActionCategory::*;
+
+    let (category, description) = match name {
+        "move_down" => (Navigation, "Move down"),
+        "move_up" => (Navigation, "Move up"),
+        "move_left" => (Navigation, "Move left / back"),
+        "move_right" => (Navigation, "Move right / select"),
+        "page_down" => (Navigation, "Page down"),
+        "page_up" => (Navigation, "Page up"),
+        "diff_page_down" => (Navigation, "Scroll diff page down from any pane"),
+        "diff_page_up" => (Navigation, "Scroll diff page up from any pane"),
+        "jump_to_first" => (Navigation, "Jump to first line"),
+        "jump_to_last" => (Navigation, "Jump to last line"),
+        "jump_back" => (Navigation, "Jump to previous position"),
+        "next_comment" => (Navigation, "Jump to next comment"),
+        "prev_comment" => (Navigation, "Jump to previous comment"),
+        "first_comment" => (Navigation, "Jump to first comment in the file"),
+        "last_comment" => (Navigation, "Jump to last comment in the file"),
+        "next_change" => (Navigation, "Jump to next hunk (across files)"),
+        "prev_change" => (Navigation, "Jump to previous hunk (across files)"),
+        "next_change_block" => (Navigation, "Jump to next change block in the file"),
+        "prev_change_block" => (Navigation, "Jump to previous change block in the file"),
+        "next_file_with_comments" => (Navigation, "Select next file with comments"),
+        "prev_file_with_comments" => (Navigation, "Select previous file with comments"),
+        "next_conflict" => (Navigation, "Jump to next conflict marker in the file"),
+        "go_to_definition" => (Navigation, "Go to definition"),
+        "go_to_file" => (Navigation, "Open file in $EDITOR"),
+        "open_all_files_in_editor" => (Navigation, "Open all changed files in $EDITOR"),
+        "open_panel" => (Navigation, "Open panel / select"),
+        "open_in_browser" => (Navigation, "Open PR in browser"),
+        "open_blame_in_browser" => (Navigation, "Open file blame on GitHub"),
+        "open_history_in_browser" => (Navigation, "Open file history on GitHub"),
+        "jump_to_base_pr" => (Navigation, "Switch to base PR (stacked PRs)"),
+        "issue_list" => (Navigation, "Open issue list"),
+        "tab_switch" => (Navigation, "Switch focused pane"),
+        "tab_prev" => (Navigation, "Previous tab"),
+        "tab_next" => (Navigation, "Next tab"),
+        "scroll_left" => (Navigation, "Scroll side-by-side column left"),
+        "scroll_right" => (Navigation, "Scroll side-by-side column right"),
+        "search_in_diff" => (Navigation, "Search in the diff"),
+        "quit" => (Navigation, "Quit / Back"),
+        "help" => (Navigation, "Toggle help"),
+        "approve" => (Review, "Approve PR"),
+        "request_changes" => (Review, "Request changes"),
+        "comment" => (Review, "Add comment (file list: comment-only review)"),
+        "suggestion" => (Review, "Add suggestion"),
+        "suggestion_in_editor" => (Review, "Write suggestion in external editor"),
+        "suggest_format" => (Review, "Suggest formatter output for the selection"),
+        "reply" => (Review, "Reply to comment"),
+        "submit" => (Review, "Submit input"),
+        "review_submit" => (Review, "Submit review: pick the verdict in a popup"),
+        "toggle_pending_review" => (Review, "Toggle pending review"),
+        "comment_list" => (Review, "Open comment list"),
+        "multiline_select" => (Review, "Enter multiline selection mode"),
+        "mark_viewed" => (Review, "Mark selected file as viewed"),
+        "mark_viewed_dir" => (Review, "Mark selected directory as viewed"),
+        "toggle_resolved" => (Review, "Resolve / reopen review thread"),
+        "add_reaction" => (Review, "React to the selected review comment"),
+        "request_reviewer" => (Review, "Request reviewers"),
+        "add_assignee" => (Review, "Add assignees"),
+        "yank_line" => (Review, "Copy current diff line"),
+        "yank_hunk" => (Review, "Copy current hunk"),
+        "yank_permalink" => (Review, "Copy GitHub permalink"),
+        "confirm_yes" => (Review, "Confirm"),
+        "confirm_no" => (Review, "Cancel confirmation"),
+        "git_ops" => (Review, "Open git ops view"),
+        "git_ops_stage" => (Review, "Git ops: stage / unstage"),
+        "git_ops_stage_all" => (Review, "Git ops: stage all files"),
+        "git_ops_discard" => (Review, "Git ops: discard changes"),
+        "git_ops_commit" => (Review, "Git ops: commit"),
+        "git_ops_undo" => (Review, "Git ops: undo last operation"),
+        "git_ops_reset" => (Review, "Git ops: reset --soft to selected commit"),
+        "git_ops_push" => (Review, "Git ops: push to origin"),
+        "ai_rally" => (Ai, "Start AI Rally"),
+        "rally_background" => (Ai, "Run AI Rally in the background"),
+        "rally_pause" => (Ai, "Pause / resume AI Rally"),
+        "commit_rally" => (Ai, "Post pending AI Rally comments"),
+        "rally_drop_comment" => (Ai, "Drop selected pending AI Rally comment"),
+        "retry" => (Ai, "Retry after an error"),
+        "retry_iteration" => (Ai, "Re-run the latest step of a finished rally"),
+        "ai_explain_hunk" => (Ai, "Explain the current hunk with AI"),
+        "refresh" => (View, "Refresh (clear cache and reload)"),
+        "reload_config" => (View, "Reload config"),
+        "reload_current_file" => (View, "Refetch the current file's diff"),
+        "shell_command" => (View, "Execute shell command"),
+        "pr_description" => (View, "View PR description"),
+        "ci_checks" => (View, "View CI checks"),
+        "symbol_digest" => (View, "Symbol digest (changed symbols per file)"),
+        "show_blame" => (View, "Show git blame around the cursor"),
+        "toggle_local_mode" => (View, "Toggle local diff mode"),
+        "toggle_auto_focus" => (View, "Toggle auto-focus (local mode)"),
+        "toggle_zen_mode" => (View, "Toggle zen mode"),
+        "toggle_markdown_rich" => (View, "Toggle markdown rich display"),
+        "toggle_highlighting" => (View, "Toggle syntax highlighting"),
+        "choose_theme" => (View, "Choose syntax theme"),
+        "toggle_diff_layout" => (View, "Toggle unified / side-by-side layout"),
+        "toggle_wrap" => (View, "Toggle line wrapping"),
+        "toggle_whitespace" => (View, "Hide / show whitespace-only changes"),
+        "toggle_unchanged" => (View, "Collapse / expand unchanged lines"),
+        "toggle_line_endings" => (View, "Expand / collapse line-ending-only hunks"),
+        "toggle_hunk" => (View, "Collapse / expand hunk"),
+        "toggle_hunk_filter" => (View, "Toggle all hunks / filtered hunks"),
+        "peek_split_hunk" => (View, "Peek hunk side-by-side"),
+        "expand_context" => (View, "Expand context around hunk"),
+        "reset_context" => (View, "Reset expanded context"),
+        "collapse_all_threads" => (View, "Collapse all comment threads"),
+        "expand_all_threads" => (View, "Expand all comment threads"),
+        "toggle_thread" => (View, "Collapse / expand comment thread"),
+        "tree_toggle" => (View, "Toggle file tree view"),
+        "sort_files" => (View, "Cycle file sort order"),
+        "set_diff_base" => (View, "Review changes since a commit / branch"),
+        "filter" => (View, "Filter list"),
+        "filter_files" => (View, "Filter files by glob"),
+        "filter_added_files" => (View, "Only added files"),
+        "filter_deleted_files" => (View, "Only deleted files"),
+        "filter_modified_files" => (View, "Only modified files"),
+        "filter_renamed_files" => (View, "Only renamed files"),
+        "filter_open" => (View, "Show open PRs / issues"),
+        "filter_closed" => (View, "Show closed PRs / issues"),
+        "filter_all" => (View, "Show all PRs / issues"),
+        "pr_list_filters" => (View, "Edit PR list filters"),
+        _ => return None,
+    };
+    Some(ActionMeta {
+        category,
+        description,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,7 +33,7 @@ pub enum SupportedLanguage {
     Bash,
     Php,
     Swift,
-    // Kotlin is excluded: highlights.scm uses #lua-match? which is not tree-sitter compatible
+    Kotlin,
     Haskell,
     // Svelte is excluded: tree-sitter-svelte-ng requires injection for <script>/<style> content
     // which octorus doesn't support. Svelte falls back to syntect which provides better highlighting.
@@ -63,6 +63,28 @@ static TYPESCRIPT_COMBINED_QUERY: LazyLock<String> = LazyLock::new(|| {
     )
 });
 
+/// Combined JSX highlights query (JavaScript base + JSX tags/attributes).
+///
+/// tree-sitter-javascript parses JSX natively but ships the tag/attribute
+/// patterns as a separate query, so `.jsx` needs both.
+static JSX_COMBINED_QUERY: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "{}\n{}",
+        tree_sitter_javascript::HIGHLIGHT_QUERY,
+        tree_sitter_javascript::JSX_HIGHLIGHT_QUERY
+    )
+});
+
+/// Combined TSX highlights query (JavaScript base + JSX + TypeScript-specific).
+static TSX_COMBINED_QUERY: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "{}\n{}\n{}",
+        tree_sitter_javascript::HIGHLIGHT_QUERY,
+        tree_sitter_javascript::JSX_HIGHLIGHT_QUERY,
+        tree_sitter_typescript::HIGHLIGHTS_QUERY
+    )
+});
+
 /// Combined C++ highlights query (C base + C++-specific).
 ///
 /// C++'s HIGHLIGHT_QUERY only contains C++-specific patterns (class, virtual, etc.)
@@ -108,6 +130,10 @@ static VUE_COMBINED_QUERY: LazyLock<String> = LazyLock::new(|| {
 /// C# highlights query (bundled as tree-sitter-c-sharp doesn't export it).
 const CSHARP_HIGHLIGHTS_QUERY: &str = include_str!("queries/c_sharp/highlights.scm");
 
+/// Kotlin highlights query (bundled as tree-sitter-kotlin-ng doesn't export one,
+/// and the upstream query relies on the non-standard `#lua-match?` predicate).
+const KOTLIN_HIGHLIGHTS_QUERY: &str = include_str!("queries/kotlin/highlights.scm");
+
 /// All definition prefixes from all supported languages, deduplicated.
 static ALL_DEFINITION_PREFIXES: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
     let mut prefixes = Vec::new();
@@ -164,6 +190,7 @@ impl SupportedLanguage {
             "sh" | "bash" | "zsh" => Some(Self::Bash),
             "php" => Some(Self::Php),
             "swift" => Some(Self::Swift),
+            "kt" | "kts" => Some(Self::Kotlin),
             "hs" | "lhs" => Some(Self::Haskell),
             // Phase 2: MoonBit
             "mbt" => Some(Self::MoonBit),
@@ -206,6 +233,7 @@ impl SupportedLanguage {
             Self::Bash => Some("sh"),
             Self::Php => Some("php"),
             Self::Swift => Some("swift"),
+            Self::Kotlin => Some("kotlin"),
             Self::Haskell => Some("hs"),
             Self::Css => Some("css"),
             Self::Markdown | Self::MarkdownInline => Some("md"),
@@ -235,6 +263,7 @@ impl SupportedLanguage {
             Self::Bash => "sh",
             Self::Php => "php",
             Self::Swift => "swift",
+            Self::Kotlin => "kt",
             Self::Haskell => "hs",
             Self::MoonBit => "mbt",
             Self::Svelte => "svelte",
@@ -266,6 +295,7 @@ impl SupportedLanguage {
             Self::Bash => tree_sitter_bash::LANGUAGE.into(),
             Self::Php => tree_sitter_php::LANGUAGE_PHP.into(),
             Self::Swift => tree_sitter_swift::LANGUAGE.into(),
+            Self::Kotlin => tree_sitter_kotlin_ng::LANGUAGE.into(),
             Self::Haskell => tree_sitter_haskell::LANGUAGE.into(),
             // Phase 2: MoonBit
             Self::MoonBit => tree_sitter_moonbit::LANGUAGE.into(),
@@ -285,22 +315,24 @@ impl SupportedLanguage {
     ///
     /// For TypeScript/TSX, this returns a combined query including JavaScript
     /// patterns, since tree-sitter-typescript's query only contains TS-specific
-    /// additions.
+    /// additions. JSX/TSX additionally include the JSX tag/attribute patterns.
     ///
     /// For C++, this returns a combined query including C patterns, since
     /// tree-sitter-cpp's query only contains C++-specific additions.
     ///
-    /// For C#, we bundle our own highlights query as the upstream crate
-    /// doesn't export it.
+    /// For C# and Kotlin, we bundle our own highlights query as the upstream
+    /// crates don't export one.
     pub fn highlights_query(&self) -> &'static str {
         match self {
             Self::Rust => tree_sitter_rust::HIGHLIGHTS_QUERY,
-            Self::TypeScript | Self::TypeScriptReact => {
+            Self::TypeScript => {
                 // SAFETY: LazyLock ensures the string is initialized once and lives
                 // for the duration of the program
                 TYPESCRIPT_COMBINED_QUERY.as_str()
             }
-            Self::JavaScript | Self::JavaScriptReact => tree_sitter_javascript::HIGHLIGHT_QUERY,
+            Self::TypeScriptReact => TSX_COMBINED_QUERY.as_str(),
+            Self::JavaScript => tree_sitter_javascript::HIGHLIGHT_QUERY,
+            Self::JavaScriptReact => JSX_COMBINED_QUERY.as_str(),
             Self::Go => tree_sitter_go::HIGHLIGHTS_QUERY,
             Self::Python => tree_sitter_python::HIGHLIGHTS_QUERY,
             Self::Ruby => tree_sitter_ruby::HIGHLIGHTS_QUERY,
@@ -318,6 +350,7 @@ impl SupportedLanguage {
             Self::Bash => tree_sitter_bash::HIGHLIGHT_QUERY, // Note: singular HIGHLIGHT_QUERY
             Self::Php => tree_sitter_php::HIGHLIGHTS_QUERY,
             Self::Swift => tree_sitter_swift::HIGHLIGHTS_QUERY,
+            Self::Kotlin => KOTLIN_HIGHLIGHTS_QUERY,
             Self::Haskell => tree_sitter_haskell::HIGHLIGHTS_QUERY,
             // Phase 2: MoonBit
             Self::MoonBit => tree_sitter_moonbit::HIGHLIGHTS_QUERY,
@@ -441,6 +474,17 @@ impl SupportedLanguage {
                 "var ",
                 "let ",
             ],
+            Self::Kotlin => &[
+                "fun ",
+                "class ",
+                "interface ",
+                "object ",
+                "enum class ",
+                "data class ",
+                "typealias ",
+                "val ",
+                "var ",
+            ],
             Self::Haskell => &["data ", "newtype ", "type ", "class ", "instance "],
             // Phase 2: MoonBit
             Self::MoonBit => &[
@@ -1029,6 +1073,68 @@ impl SupportedLanguage {
                 "init",
                 "value",
             ],
+            Self::Kotlin => &[
+                "abstract",
+                "annotation",
+                "as",
+                "break",
+                "by",
+                "catch",
+                "class",
+                "companion",
+                "const",
+                "constructor",
+                "continue",
+                "data",
+                "do",
+                "else",
+                "enum",
+                "external",
+                "false",
+                "final",
+                "finally",
+                "for",
+                "fun",
+                "get",
+                "if",
+                "import",
+                "in",
+                "infix",
+                "init",
+                "inline",
+                "inner",
+                "interface",
+                "internal",
+                "is",
+                "lateinit",
+                "null",
+                "object",
+                "open",
+                "operator",
+                "out",
+                "override",
+                "package",
+                "private",
+                "protected",
+                "public",
+                "reified",
+                "return",
+                "sealed",
+                "set",
+                "super",
+                "suspend",
+                "this",
+                "throw",
+                "true",
+                "try",
+                "typealias",
+                "val",
+                "var",
+                "vararg",
+                "when",
+                "where",
+                "while",
+            ],
             Self::Haskell => &[
                 "case",
                 "class",
@@ -1185,6 +1291,7 @@ impl SupportedLanguage {
             Self::Bash,
             Self::Php,
             Self::Swift,
+            Self::Kotlin,
             Self::Haskell,
             // Phase 2: MoonBit
             Self::MoonBit,
@@ -1341,6 +1448,14 @@ mod tests {
             SupportedLanguage::from_extension("swift"),
             Some(SupportedLanguage::Swift)
         );
+        assert_eq!(
+            SupportedLanguage::from_extension("kt"),
+            Some(SupportedLanguage::Kotlin)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("kts"),
+            Some(SupportedLanguage::Kotlin)
+        );
         assert_eq!(
             SupportedLanguage::from_extension("hs"),
             Some(SupportedLanguage::Haskell)
@@ -1389,6 +1504,8 @@ mod tests {
         assert!(SupportedLanguage::is_supported("sh"));
         assert!(SupportedLanguage::is_supported("php"));
         assert!(SupportedLanguage::is_supported("swift"));
+        assert!(SupportedLanguage::is_supported("kt"));
+        assert!(SupportedLanguage::is_supported("kts"));
         assert!(SupportedLanguage::is_supported("hs"));
 
         // Phase 3: Svelte is now supported
@@ -1584,7 +1701,7 @@ mod tests {
     #[test]
     fn test_all_iterator() {
         let langs: Vec<_> = SupportedLanguage::all().collect();
-        assert_eq!(langs.len(), 24); // +2 for Markdown/MarkdownInline
+        assert_eq!(langs.len(), 25); // +2 for Markdown/MarkdownInline
         assert!(langs.contains(&SupportedLanguage::Rust));
         assert!(langs.contains(&SupportedLanguage::TypeScript));
         assert!(langs.contains(&SupportedLanguage::TypeScriptReact));
@@ -1599,6 +1716,7 @@ mod tests {
         assert!(langs.contains(&SupportedLanguage::Cpp));
         assert!(langs.contains(&SupportedLanguage::Java));
         assert!(langs.contains(&SupportedLanguage::CSharp));
+        assert!(langs.contains(&SupportedLanguage::Kotlin));
     }
 
     #[test]
@@ -4,7 +4,9 @@
 
 pub mod ai;
 pub mod app;
+pub mod blame;
 pub mod cache;
+pub mod clipboard;
 pub mod config;
 pub mod diff;
 pub mod diff_store;
@@ -24,4 +26,4 @@ pub mod ui;
 pub use app::{CachedDiffLine, DiffCache, InternedSpan};
 pub use diff::{classify_line, get_line_info, LineType, PatchIndex, PatchLineInfo};
 pub use syntax::ParserPool;
-pub use ui::diff_view::{build_diff_cache, render_cached_lines};
+pub use ui::diff_view::{build_diff_cache, render_cached_lines, LineRenderOptions};
@@ -1,27 +1,55 @@
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use chrono::Utc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tokio::process::Command;
 use tokio::sync::mpsc;
 use tracing::warn;
 
+use crate::cache;
 use crate::diff;
-use crate::github::{self, ChangedFile, PullRequest};
+use crate::github::{self, ChangedFile, GitHubBackend, PullRequest};
 
 pub enum DataLoadResult {
     /// APIからデータ取得成功
     Success {
         pr: Box<PullRequest>,
         files: Vec<ChangedFile>,
+        revision: DataRevision,
     },
     /// エラー
     Error(String),
+    /// 変更ファイル一覧の取得途中経過（ページ取得ごとの累計件数）
+    Progress { pr_number: u32, files_loaded: usize },
+    /// patch 欠落があり `gh pr diff` をストリーミング取得する前のファイル一覧
+    Partial {
+        pr: Box<PullRequest>,
+        files: Vec<ChangedFile>,
+    },
+    /// ストリーミング中に確定した 1 ファイル分の patch（最後に Success が続く）
+    FilePatch {
+        pr_number: u32,
+        filename: String,
+        patch: String,
+    },
+}
+
+/// `Success` が同じ PR の何番目のデータか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataRevision {
+    /// ディスクキャッシュから即時表示する古いかもしれないデータ。後から `Fresh` が届く
+    Stale,
+    /// 確定したデータ（オフライン時はキャッシュの内容）
+    Fresh,
 }
 
 /// 単一ファイルの diff 結果（バッチ/オンデマンド共通）
 pub struct SingleFileDiffResult {
     pub filename: String,
     pub patch: Option<String>,
+    /// 取得が失敗・中断した（`patch` が None でも「差分なし」とは限らない）
+    pub incomplete: bool,
 }
 
 /// コメント送信結果
@@ -38,37 +66,183 @@ pub enum FetchMode {
     Fresh,
     /// 更新チェックのみ（キャッシュヒット時）
     CheckUpdate(String), // cached updated_at
+    /// ディスクキャッシュのみから読み込む（`--offline`、`gh` は一切呼ばない）
+    CacheOnly,
+    /// キャッシュがあれば `Stale` として即座に送り、続けて新規取得した `Fresh` を送る
+    StaleWhileRevalidate,
 }
 
 /// バックグラウンドでPRデータを取得
+///
+/// `base` を指定すると PR 全体ではなく `base...head` のコミット範囲の差分を取得する。
 pub async fn fetch_pr_data(
+    github: Arc<dyn GitHubBackend>,
     repo: String,
     pr_number: u32,
     mode: FetchMode,
+    base: Option<String>,
     tx: mpsc::Sender<DataLoadResult>,
 ) {
+    if let Some(base) = base {
+        let result = match mode {
+            FetchMode::CacheOnly => DataLoadResult::Error(format!(
+                "Reviewing a commit range (since {}) is not available offline.",
+                base
+            )),
+            _ => fetch_range(&*github, &repo, pr_number, &base).await,
+        };
+        let _ = tx.send(result).await;
+        return;
+    }
+
     match mode {
         FetchMode::Fresh => {
-            fetch_and_send(&repo, pr_number, tx).await;
+            fetch_and_send(&*github, &repo, pr_number, tx).await;
         }
         FetchMode::CheckUpdate(cached_updated_at) => {
-            check_for_updates(&repo, pr_number, &cached_updated_at, tx).await;
+            check_for_updates(&*github, &repo, pr_number, &cached_updated_at, tx).await;
+        }
+        FetchMode::CacheOnly => {
+            let result = load_cached_pr_data(&repo, pr_number, &cache::cache_dir());
+            let _ = tx.send(result).await;
+        }
+        FetchMode::StaleWhileRevalidate => {
+            let fetcher = GithubPrDataFetcher {
+                github: &*github,
+                repo: &repo,
+                pr_number,
+            };
+            stale_while_revalidate(&fetcher, tx).await;
         }
     }
 }
 
+/// stale-while-revalidate のデータ源（テストではモックに差し替える）
+#[async_trait]
+trait PrDataFetcher: Send + Sync {
+    /// ディスクキャッシュのスナップショット（無ければ None）
+    fn cached(&self) -> Option<(PullRequest, Vec<ChangedFile>)>;
+    /// 最新データを取得して `tx` に送る（`Fresh` の `Success` か `Error` で終わる）
+    async fn fetch(&self, tx: mpsc::Sender<DataLoadResult>);
+}
+
+struct GithubPrDataFetcher<'a> {
+    github: &'a dyn GitHubBackend,
+    repo: &'a str,
+    pr_number: u32,
+}
+
+#[async_trait]
+impl PrDataFetcher for GithubPrDataFetcher<'_> {
+    fn cached(&self) -> Option<(PullRequest, Vec<ChangedFile>)> {
+        match cache::load_pr_data_snapshot(self.repo, self.pr_number) {
+            Ok(snapshot) => snapshot.map(|s| (s.pr, s.files)),
+            Err(e) => {
+                warn!("Failed to read cached PR data: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn fetch(&self, tx: mpsc::Sender<DataLoadResult>) {
+        fetch_and_send(self.github, self.repo, self.pr_number, tx).await;
+    }
+}
+
+async fn stale_while_revalidate(fetcher: &dyn PrDataFetcher, tx: mpsc::Sender<DataLoadResult>) {
+    if let Some((pr, files)) = fetcher.cached() {
+        let _ = tx
+            .send(DataLoadResult::Success {
+                pr: Box::new(pr),
+                files,
+                revision: DataRevision::Stale,
+            })
+            .await;
+    }
+    fetcher.fetch(tx).await;
+}
+
+/// ディスクキャッシュに保存済みの PR データを読み込む（ネットワークアクセスなし）
+fn load_cached_pr_data(repo: &str, pr_number: u32, base: &std::path::Path) -> DataLoadResult {
+    match cache::load_pr_data_snapshot_with_base(repo, pr_number, base) {
+        Ok(Some(snapshot)) => DataLoadResult::Success {
+            pr: Box::new(snapshot.pr),
+            files: snapshot.files,
+            revision: DataRevision::Fresh,
+        },
+        Ok(None) => DataLoadResult::Error(format!(
+            "PR #{} is not cached (offline). Open it once while online to browse it offline.",
+            pr_number
+        )),
+        Err(e) => DataLoadResult::Error(format!("Failed to read cached PR data (offline): {}", e)),
+    }
+}
+
+/// PR の head と `base` の間の差分だけを compare API から取得する
+///
+/// 範囲指定の表示は PR 全体のスナップショットと食い違うため、オフライン用キャッシュには保存しない。
+async fn fetch_range(
+    github: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+    base: &str,
+) -> DataLoadResult {
+    let pr = match github::fetch_pr(github, repo, pr_number).await {
+        Ok(pr) => pr,
+        Err(e) => return DataLoadResult::Error(e.to_string()),
+    };
+    match github::fetch_compare_files(github, repo, base, &pr.head.sha).await {
+        Ok(files) => DataLoadResult::Success {
+            pr: Box::new(pr),
+            files,
+            revision: DataRevision::Fresh,
+        },
+        Err(e) => DataLoadResult::Error(format!(
+            "Failed to fetch diff between {} and the PR head: {}",
+            base, e
+        )),
+    }
+}
+
+/// 比較元のリビジョンとして受け付けられる文字列か
+///
+/// `git diff` のオプションと誤認される `-` 始まりや空白を含むものは拒否する。
+pub fn is_valid_diff_base(rev: &str) -> bool {
+    !rev.is_empty() && !rev.starts_with('-') && !rev.contains(char::is_whitespace)
+}
+
+/// ローカル diff 用の `git diff` 引数を組み立てる（比較元は `base`、未指定なら HEAD）
+fn git_diff_args<'a>(
+    base: Option<&'a str>,
+    options: &[&'a str],
+    paths: &[&'a str],
+) -> Vec<&'a str> {
+    let mut args = vec!["diff"];
+    args.extend_from_slice(options);
+    args.push(base.unwrap_or("HEAD"));
+    if !paths.is_empty() {
+        args.push("--");
+        args.extend_from_slice(paths);
+    }
+    args
+}
+
 /// ローカル `git diff` から PR データを再構築して読み込み（2段階ロード版）
 ///
 /// Phase 1: name-status + numstat のみ → ファイル一覧（patch: None）を即座に送信
 /// Phase 2: バッチ diff ロードは app.rs 側で start_batch_diff_loading() 経由で行う
+///
+/// `base` を指定すると HEAD ではなくそのリビジョンとワーキングツリーの差分を表示する。
 pub async fn fetch_local_diff(
     _repo: String,
     working_dir: Option<String>,
+    base: Option<String>,
     tx: mpsc::Sender<DataLoadResult>,
 ) {
     let current_workdir = working_dir.as_deref();
+    let base = base.as_deref();
 
-    let name_status_output = match run_git_name_status(current_workdir).await {
+    let name_status_output = match run_git_name_status(current_workdir, base).await {
         Ok(output) => output,
         Err(e) => {
             let _ = tx.send(DataLoadResult::Error(e.to_string())).await;
@@ -77,18 +251,29 @@ pub async fn fetch_local_diff(
     };
     let file_statuses = parse_name_status_output(&name_status_output);
 
-    let numstat_output = run_git_numstat(current_workdir).await.ok();
+    let numstat_output = run_git_numstat(current_workdir, base).await.ok();
     let file_changes = parse_numstat_output(numstat_output.as_deref());
 
     let mut files = build_changed_files_lazy(&file_statuses, &file_changes);
 
     merge_untracked_files_lazy(current_workdir, &mut files).await;
 
+    let (title, body) = match base {
+        Some(rev) => (
+            format!("Local diff from {}", rev),
+            format!("Current working tree diff from {}", rev),
+        ),
+        None => (
+            "Local HEAD diff".to_string(),
+            "Current working tree diff from HEAD".to_string(),
+        ),
+    };
+
     let pr = PullRequest {
         number: 0,
         node_id: None,
-        title: "Local HEAD diff".to_string(),
-        body: Some("Current working tree diff from HEAD".to_string()),
+        title,
+        body: Some(body),
         state: "local".to_string(),
         head: github::Branch {
             ref_name: "HEAD".to_string(),
@@ -104,12 +289,16 @@ pub async fn fetch_local_diff(
             login: "local".to_string(),
         },
         updated_at: Utc::now().to_rfc3339(),
+        labels: vec![],
+        html_url: None,
+        changed_files: None,
     };
 
     let _ = tx
         .send(DataLoadResult::Success {
             pr: Box::new(pr),
             files,
+            revision: DataRevision::Fresh,
         })
         .await;
 }
@@ -117,6 +306,7 @@ pub async fn fetch_local_diff(
 /// バッチ diff ロード: ファイルリスト順にバッチで diff を取得し、チャネルに送信
 pub async fn fetch_local_diffs_batched(
     working_dir: Option<String>,
+    base: Option<String>,
     filenames: Vec<String>,
     untracked_filenames: Vec<String>,
     batch_size: usize,
@@ -126,11 +316,11 @@ pub async fn fetch_local_diffs_batched(
 
     // tracked ファイルをバッチで処理
     for batch in filenames.chunks(batch_size) {
-        let mut args = vec!["diff", "HEAD", "--"];
         let batch_strs: Vec<&str> = batch.iter().map(|s| s.as_str()).collect();
-        args.extend(&batch_strs);
+        let args = git_diff_args(base.as_deref(), &[], &batch_strs);
 
         let output = run_git_command(wd, &args).await;
+        let incomplete = output.is_err();
         let mut patches = match output {
             Ok(diff_output) => diff::parse_unified_diff(&diff_output),
             Err(_) => HashMap::new(),
@@ -141,6 +331,7 @@ pub async fn fetch_local_diffs_batched(
             .map(|filename| SingleFileDiffResult {
                 filename: filename.clone(),
                 patch: patches.remove(filename),
+                incomplete,
             })
             .collect();
 
@@ -153,11 +344,11 @@ pub async fn fetch_local_diffs_batched(
     for batch in untracked_filenames.chunks(batch_size) {
         let mut results = Vec::with_capacity(batch.len());
         for filename in batch {
-            let patch = run_git_no_index_diff(wd, filename).await.ok();
-            let patch = patch.filter(|p| !p.is_empty());
+            let output = run_git_no_index_diff(wd, filename).await;
             results.push(SingleFileDiffResult {
                 filename: filename.clone(),
-                patch,
+                incomplete: output.is_err(),
+                patch: output.ok().filter(|p| !p.is_empty()),
             });
         }
         if tx.send(results).await.is_err() {
@@ -169,35 +360,77 @@ pub async fn fetch_local_diffs_batched(
 /// 単一ファイルの diff をオンデマンド取得（tracked + untracked 自動判別）
 pub async fn fetch_single_file_diff(
     working_dir: Option<String>,
+    base: Option<String>,
     filename: String,
     is_untracked: bool,
     tx: mpsc::Sender<SingleFileDiffResult>,
 ) {
     let wd = working_dir.as_deref();
 
-    let patch = if is_untracked {
-        run_git_no_index_diff(wd, &filename)
-            .await
-            .ok()
-            .filter(|p| !p.is_empty())
+    let output = if is_untracked {
+        run_git_no_index_diff(wd, &filename).await
     } else {
-        run_git_diff_file(wd, &filename)
-            .await
-            .ok()
-            .filter(|p| !p.is_empty())
+        run_git_diff_file(wd, base.as_deref(), &filename).await
     };
 
-    let _ = tx.send(SingleFileDiffResult { filename, patch }).await;
+    let _ = tx
+        .send(SingleFileDiffResult {
+            filename,
+            incomplete: output.is_err(),
+            patch: output.ok().filter(|p| !p.is_empty()),
+        })
+        .await;
+}
+
+/// PR の 1 ファイル分の diff を取り直す（`index` はファイル一覧上の位置）
+pub async fn fetch_pr_file_diff(
+    github: Arc<dyn GitHubBackend>,
+    repo: String,
+    pr_number: u32,
+    filename: String,
+    index: usize,
+    tx: mpsc::Sender<SingleFileDiffResult>,
+) {
+    let result =
+        match github::fetch_pr_file_patch(&*github, &repo, pr_number, &filename, index).await {
+            Ok(patch) => SingleFileDiffResult {
+                filename,
+                patch,
+                incomplete: false,
+            },
+            Err(e) => {
+                warn!("Failed to refetch diff for {}: {}", filename, e);
+                SingleFileDiffResult {
+                    filename,
+                    patch: None,
+                    incomplete: true,
+                }
+            }
+        };
+    let _ = tx.send(result).await;
 }
 
-async fn fetch_and_send(repo: &str, pr_number: u32, tx: mpsc::Sender<DataLoadResult>) {
+async fn fetch_and_send(
+    github: &dyn GitHubBackend,
+    repo: &str,
+    pr_number: u32,
+    tx: mpsc::Sender<DataLoadResult>,
+) {
+    // 進捗は best-effort（チャンネルが詰まっていれば捨てる）
+    let progress_tx = tx.clone();
+    let on_progress = move |files_loaded| {
+        let _ = progress_tx.try_send(DataLoadResult::Progress {
+            pr_number,
+            files_loaded,
+        });
+    };
     match tokio::try_join!(
-        github::fetch_pr(repo, pr_number),
-        github::fetch_changed_files(repo, pr_number)
+        github::fetch_pr(github, repo, pr_number),
+        github::fetch_changed_files_with_progress(github, repo, pr_number, on_progress)
     ) {
         Ok((pr, mut files)) => {
             if let Some(pr_node_id) = pr.node_id.as_deref() {
-                match github::fetch_files_viewed_state(repo, pr_node_id).await {
+                match github::fetch_files_viewed_state(github, pr_node_id).await {
                     Ok(viewed_state) => {
                         for file in files.iter_mut() {
                             file.viewed =
@@ -214,11 +447,32 @@ async fn fetch_and_send(repo: &str, pr_number: u32, tx: mpsc::Sender<DataLoadRes
             let has_missing_patches = files.iter().any(|f| f.patch.is_none());
 
             if has_missing_patches {
+                // 巨大 PR の diff 全体を待たずに一覧を表示し、patch は届いた順に流す
+                let _ = tx
+                    .send(DataLoadResult::Partial {
+                        pr: Box::new(pr.clone()),
+                        files: files.clone(),
+                    })
+                    .await;
+                let missing: HashSet<String> = files
+                    .iter()
+                    .filter(|f| f.patch.is_none())
+                    .map(|f| f.filename.clone())
+                    .collect();
+                let patch_tx = tx.clone();
+                // spawn_blocking 上で呼ばれるため blocking_send で UI 側の消費を待つ
+                let on_file = move |filename: &str, patch: &str| {
+                    if missing.contains(filename) {
+                        let _ = patch_tx.blocking_send(DataLoadResult::FilePatch {
+                            pr_number,
+                            filename: filename.to_string(),
+                            patch: patch.to_string(),
+                        });
+                    }
+                };
                 // Fetch full diff using gh pr diff as fallback
-                match github::fetch_pr_diff(repo, pr_number).await {
-                    Ok(full_diff) => {
-                        let mut patch_map = diff::parse_unified_diff(&full_diff);
-
+                match github::fetch_pr_diff_streaming(github, repo, pr_number, on_file).await {
+                    Ok(mut patch_map) => {
                         // Apply patches only to files that are missing them
                         for file in files.iter_mut() {
                             if file.patch.is_none() {
@@ -235,10 +489,15 @@ async fn fetch_and_send(repo: &str, pr_number: u32, tx: mpsc::Sender<DataLoadRes
                 }
             }
 
+            if let Err(e) = cache::save_pr_data_snapshot(repo, pr_number, &pr, &files) {
+                warn!("Failed to save PR data for offline use: {}", e);
+            }
+
             let _ = tx
                 .send(DataLoadResult::Success {
                     pr: Box::new(pr),
                     files,
+                    revision: DataRevision::Fresh,
                 })
                 .await;
         }
@@ -249,27 +508,28 @@ async fn fetch_and_send(repo: &str, pr_number: u32, tx: mpsc::Sender<DataLoadRes
 }
 
 async fn check_for_updates(
+    github: &dyn GitHubBackend,
     repo: &str,
     pr_number: u32,
     cached_updated_at: &str,
     tx: mpsc::Sender<DataLoadResult>,
 ) {
     // PRの基本情報だけ取得してupdated_atを比較
-    if let Ok(fresh_pr) = github::fetch_pr(repo, pr_number).await {
+    if let Ok(fresh_pr) = github::fetch_pr(github, repo, pr_number).await {
         if fresh_pr.updated_at != cached_updated_at {
             // 更新あり → 全データ再取得
-            fetch_and_send(repo, pr_number, tx).await;
+            fetch_and_send(github, repo, pr_number, tx).await;
         }
         // 更新なし → 何もしない（キャッシュデータをそのまま使用）
     }
 }
 
-async fn run_git_numstat(working_dir: Option<&str>) -> Result<String> {
-    run_git_command(working_dir, &["diff", "--numstat", "HEAD"]).await
+async fn run_git_numstat(working_dir: Option<&str>, base: Option<&str>) -> Result<String> {
+    run_git_command(working_dir, &git_diff_args(base, &["--numstat"], &[])).await
 }
 
-async fn run_git_name_status(working_dir: Option<&str>) -> Result<String> {
-    run_git_command(working_dir, &["diff", "--name-status", "HEAD"]).await
+async fn run_git_name_status(working_dir: Option<&str>, base: Option<&str>) -> Result<String> {
+    run_git_command(working_dir, &git_diff_args(base, &["--name-status"], &[])).await
 }
 
 /// Git の C-quoted パス文字列をデコードする。
@@ -512,8 +772,12 @@ fn parse_path_list(output: &str) -> Vec<String> {
         .collect()
 }
 
-async fn run_git_diff_file(working_dir: Option<&str>, filename: &str) -> Result<String> {
-    run_git_command(working_dir, &["diff", "HEAD", "--", filename]).await
+async fn run_git_diff_file(
+    working_dir: Option<&str>,
+    base: Option<&str>,
+    filename: &str,
+) -> Result<String> {
+    run_git_command(working_dir, &git_diff_args(base, &[], &[filename])).await
 }
 
 async fn run_git_untracked(working_dir: Option<&str>) -> Result<String> {
@@ -583,6 +847,125 @@ mod tests {
         std::fs::write(path, content).unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    #[serial_test::serial]
+    async fn test_fetch_and_send_uses_github_backend() {
+        use crate::github::backend::mock::RecordingBackend;
+
+        let cache_home = tempdir().unwrap();
+        let old_cache_home = std::env::var_os("XDG_CACHE_HOME");
+        unsafe { std::env::set_var("XDG_CACHE_HOME", cache_home.path()) };
+
+        let pr: PullRequest = serde_json::from_value(serde_json::json!({
+            "number": 7,
+            "title": "Add foo",
+            "body": null,
+            "state": "open",
+            "head": { "ref": "foo", "sha": "abc" },
+            "base": { "ref": "main", "sha": "def" },
+            "user": { "login": "octocat" },
+            "updated_at": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        let files = r#"[{"filename":"src/foo.rs","status":"added","additions":1,"deletions":0}]"#;
+        let backend = std::sync::Arc::new(RecordingBackend {
+            pr: Some(pr),
+            files_pages: [(1, files.to_string())].into(),
+            diff: "diff --git a/src/foo.rs b/src/foo.rs\n--- /dev/null\n+++ b/src/foo.rs\n@@ -0,0 +1 @@\n+fn foo() {}\n"
+                .to_string(),
+            ..Default::default()
+        });
+        let (tx, mut rx) = mpsc::channel(16);
+        fetch_and_send(&*backend, "owner/repo", 7, tx).await;
+
+        let mut success = None;
+        while let Ok(result) = rx.try_recv() {
+            if let DataLoadResult::Success { files, .. } = result {
+                success = Some(files);
+            }
+        }
+        match old_cache_home {
+            Some(v) => unsafe { std::env::set_var("XDG_CACHE_HOME", v) },
+            None => unsafe { std::env::remove_var("XDG_CACHE_HOME") },
+        }
+
+        // patch の欠けたファイルは diff 全体から補う
+        let files = success.expect("Success result");
+        assert!(files[0]
+            .patch
+            .as_deref()
+            .is_some_and(|patch| patch.ends_with("@@ -0,0 +1 @@\n+fn foo() {}")));
+        let mut calls = backend.calls();
+        calls.sort();
+        assert_eq!(
+            calls,
+            vec![
+                "fetch_changed_files_page owner/repo #7 page=1",
+                "fetch_pr owner/repo #7",
+                "fetch_pr_diff owner/repo #7",
+            ]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[serial_test::serial]
+    async fn test_cache_only_fetch_never_calls_gh() {
+        use crate::github::backend::mock::ScriptedGhRunner;
+        use crate::github::{GhCliBackend, GhRetryPolicy};
+
+        let cache_home = tempdir().unwrap();
+        let old_cache_home = std::env::var_os("XDG_CACHE_HOME");
+        unsafe { std::env::set_var("XDG_CACHE_HOME", cache_home.path()) };
+
+        let pr: PullRequest = serde_json::from_value(serde_json::json!({
+            "number": 7,
+            "title": "Cached PR",
+            "body": null,
+            "state": "open",
+            "head": { "ref": "foo", "sha": "abc" },
+            "base": { "ref": "main", "sha": "def" },
+            "user": { "login": "octocat" },
+            "updated_at": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        cache::save_pr_data_snapshot("owner/repo", 7, &pr, &[]).unwrap();
+
+        // 呼ばれたら panic する（積んだ出力が無い）ランナー
+        let runner = Arc::new(ScriptedGhRunner::default());
+        let github: Arc<dyn GitHubBackend> = Arc::new(GhCliBackend::with_runner(
+            runner.clone(),
+            GhRetryPolicy::default(),
+            true,
+        ));
+        let (tx, mut rx) = mpsc::channel(4);
+        fetch_pr_data(
+            github.clone(),
+            "owner/repo".to_string(),
+            7,
+            FetchMode::CacheOnly,
+            None,
+            tx,
+        )
+        .await;
+        let result = rx.recv().await;
+
+        // バックエンド自体も offline なら GitHub に接続しない
+        let err = github::fetch_pr(&*github, "owner/repo", 7)
+            .await
+            .unwrap_err();
+
+        match old_cache_home {
+            Some(v) => unsafe { std::env::set_var("XDG_CACHE_HOME", v) },
+            None => unsafe { std::env::remove_var("XDG_CACHE_HOME") },
+        }
+
+        assert!(
+            matches!(result, Some(DataLoadResult::Success { ref pr, .. }) if pr.title == "Cached PR")
+        );
+        assert!(err.to_string().contains("offline"), "{err}");
+        assert!(runner.calls().is_empty());
+    }
+
     #[tokio::test]
     async fn test_fetch_local_diff_detects_subdir_changes_and_skips_ignored_files() {
         let tempdir = tempdir().unwrap();
@@ -626,6 +1009,7 @@ mod tests {
         fetch_local_diff(
             "local".to_string(),
             Some(workdir.to_string_lossy().to_string()),
+            None,
             tx,
         )
         .await;
@@ -634,6 +1018,7 @@ mod tests {
         let files = match result {
             DataLoadResult::Success { files, .. } => files,
             DataLoadResult::Error(err) => panic!("unexpected error: {err}"),
+            _ => panic!("unexpected intermediate result"),
         };
 
         let filenames: Vec<_> = files.iter().map(|file| file.filename.as_str()).collect();
@@ -675,6 +1060,7 @@ mod tests {
         fetch_local_diff(
             "local".to_string(),
             Some(workdir.to_string_lossy().to_string()),
+            None,
             tx,
         )
         .await;
@@ -683,6 +1069,7 @@ mod tests {
         let files = match result {
             DataLoadResult::Success { files, .. } => files,
             DataLoadResult::Error(err) => panic!("unexpected error: {err}"),
+            _ => panic!("unexpected intermediate result"),
         };
 
         assert!(files.is_empty());
@@ -722,6 +1109,7 @@ mod tests {
         fetch_local_diff(
             "local".to_string(),
             Some(workdir.to_string_lossy().to_string()),
+            None,
             tx,
         )
         .await;
@@ -730,6 +1118,7 @@ mod tests {
         let files = match result {
             DataLoadResult::Success { files, .. } => files,
             DataLoadResult::Error(err) => panic!("unexpected error: {err}"),
+            _ => panic!("unexpected intermediate result"),
         };
 
         let new_file = files
@@ -775,6 +1164,7 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<SingleFileDiffResult>(1);
         fetch_single_file_diff(
             Some(workdir.to_string_lossy().to_string()),
+            None,
             "src/main.rs".to_string(),
             false,
             tx,
@@ -820,6 +1210,7 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<SingleFileDiffResult>(1);
         fetch_single_file_diff(
             Some(workdir.to_string_lossy().to_string()),
+            None,
             "src/new_feature.rs".to_string(),
             true,
             tx,
@@ -956,6 +1347,7 @@ index 1234567..abcdefg 100644
         let (tx, mut rx) = mpsc::channel::<Vec<SingleFileDiffResult>>(2);
         fetch_local_diffs_batched(
             Some(workdir.to_string_lossy().to_string()),
+            None,
             vec!["src/new_name.rs".to_string()],
             vec![],
             20,
@@ -999,6 +1391,107 @@ index 1234567..abcdefg 100644
         assert!(files[1].patch.is_none());
     }
 
+    #[test]
+    fn test_git_diff_args_defaults_to_head() {
+        assert_eq!(
+            git_diff_args(None, &["--name-status"], &[]),
+            vec!["diff", "--name-status", "HEAD"]
+        );
+        assert_eq!(
+            git_diff_args(None, &[], &["src/a.rs", "src/b.rs"]),
+            vec!["diff", "HEAD", "--", "src/a.rs", "src/b.rs"]
+        );
+    }
+
+    #[test]
+    fn test_git_diff_args_uses_base_rev() {
+        assert_eq!(
+            git_diff_args(Some("main~3"), &["--numstat"], &[]),
+            vec!["diff", "--numstat", "main~3"]
+        );
+        assert_eq!(
+            git_diff_args(Some("abc1234"), &[], &["src/a.rs"]),
+            vec!["diff", "abc1234", "--", "src/a.rs"]
+        );
+    }
+
+    #[test]
+    fn test_is_valid_diff_base() {
+        assert!(is_valid_diff_base("HEAD~2"));
+        assert!(is_valid_diff_base("origin/main"));
+        assert!(!is_valid_diff_base(""));
+        assert!(!is_valid_diff_base("--output=/tmp/x"));
+        assert!(!is_valid_diff_base("main feature"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_local_diff_with_base_includes_committed_changes() {
+        let tempdir = tempdir().unwrap();
+        let workdir = tempdir.path();
+
+        run_git(
+            &mut Command::new("git"),
+            workdir,
+            &["init", "-b", "main"],
+            "failed to initialize temp git repo",
+        );
+        write_file(&workdir.join("src/main.rs"), "fn main() {}\n");
+        run_git(
+            &mut Command::new("git"),
+            workdir,
+            &["add", "."],
+            "failed to add initial files",
+        );
+        run_git(
+            &mut Command::new("git"),
+            workdir,
+            &["commit", "-m", "initial commit"],
+            "failed to create initial commit",
+        );
+        write_file(&workdir.join("src/lib.rs"), "pub fn lib() {}\n");
+        run_git(
+            &mut Command::new("git"),
+            workdir,
+            &["add", "."],
+            "failed to add second file",
+        );
+        run_git(
+            &mut Command::new("git"),
+            workdir,
+            &["commit", "-m", "second commit"],
+            "failed to create second commit",
+        );
+        write_file(
+            &workdir.join("src/main.rs"),
+            "fn main() { println!(\"hello\"); }\n",
+        );
+
+        let fetch = |base: Option<&str>| {
+            let workdir = workdir.to_string_lossy().to_string();
+            let base = base.map(str::to_string);
+            async move {
+                let (tx, mut rx) = mpsc::channel::<DataLoadResult>(1);
+                fetch_local_diff("local".to_string(), Some(workdir), base, tx).await;
+                match rx.recv().await.unwrap() {
+                    DataLoadResult::Success { pr, files, .. } => (
+                        pr.title,
+                        files.into_iter().map(|f| f.filename).collect::<Vec<_>>(),
+                    ),
+                    _ => panic!("unexpected result"),
+                }
+            }
+        };
+
+        let (title, files) = fetch(None).await;
+        assert_eq!(title, "Local HEAD diff");
+        assert_eq!(files, vec!["src/main.rs"]);
+
+        let (title, mut files) = fetch(Some("HEAD~1")).await;
+        files.sort();
+        assert_eq!(title, "Local diff from HEAD~1");
+        assert_eq!(files, vec!["src/lib.rs", "src/main.rs"]);
+    }
+
     #[test]
     fn test_unquote_git_path_plain() {
         assert_eq!(unquote_git_path("src/foo.rs"), "src/foo.rs");
@@ -1115,6 +1608,7 @@ index 1234567..abcdefg 100644
         fetch_local_diff(
             "local".to_string(),
             Some(workdir.to_string_lossy().to_string()),
+            None,
             tx,
         )
         .await;
@@ -1123,6 +1617,7 @@ index 1234567..abcdefg 100644
         let files = match result {
             DataLoadResult::Success { files, .. } => files,
             DataLoadResult::Error(err) => panic!("unexpected error: {err}"),
+            _ => panic!("unexpected intermediate result"),
         };
 
         let non_ascii_file = files
@@ -1135,6 +1630,7 @@ index 1234567..abcdefg 100644
         let (tx2, mut rx2) = mpsc::channel::<Vec<SingleFileDiffResult>>(2);
         fetch_local_diffs_batched(
             Some(workdir.to_string_lossy().to_string()),
+            None,
             vec!["src/日本語.rs".to_string()],
             vec![],
             20,
@@ -1197,6 +1693,7 @@ index 1234567..abcdefg 100644
         let (tx, mut rx) = mpsc::channel::<SingleFileDiffResult>(1);
         fetch_single_file_diff(
             Some(workdir.to_string_lossy().to_string()),
+            None,
             "src/テスト.rs".to_string(),
             false,
             tx,
@@ -1212,4 +1709,167 @@ index 1234567..abcdefg 100644
         let patch = result.patch.unwrap();
         assert!(patch.contains("+fn modified()"));
     }
+
+    fn swr_pr(title: &str) -> PullRequest {
+        PullRequest {
+            number: 7,
+            node_id: None,
+            title: title.to_string(),
+            body: None,
+            state: "OPEN".to_string(),
+            head: github::Branch {
+                ref_name: "feature".to_string(),
+                sha: "abc".to_string(),
+            },
+            base: github::Branch {
+                ref_name: "main".to_string(),
+                sha: "def".to_string(),
+            },
+            user: github::User {
+                login: "octocat".to_string(),
+            },
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
+        }
+    }
+
+    /// キャッシュの有無と取得結果を差し替えられる fetcher
+    struct MockFetcher {
+        cached: Option<PullRequest>,
+        fresh: Result<PullRequest, String>,
+    }
+
+    #[async_trait]
+    impl PrDataFetcher for MockFetcher {
+        fn cached(&self) -> Option<(PullRequest, Vec<ChangedFile>)> {
+            self.cached.clone().map(|pr| (pr, vec![]))
+        }
+
+        async fn fetch(&self, tx: mpsc::Sender<DataLoadResult>) {
+            let result = match &self.fresh {
+                Ok(pr) => DataLoadResult::Success {
+                    pr: Box::new(pr.clone()),
+                    files: vec![],
+                    revision: DataRevision::Fresh,
+                },
+                Err(e) => DataLoadResult::Error(e.clone()),
+            };
+            let _ = tx.send(result).await;
+        }
+    }
+
+    /// 受信した結果を (revision, タイトル) / エラー文言の列にする
+    async fn collect_swr(fetcher: MockFetcher) -> Vec<Result<(DataRevision, String), String>> {
+        let (tx, mut rx) = mpsc::channel(4);
+        stale_while_revalidate(&fetcher, tx).await;
+        let mut received = Vec::new();
+        while let Some(result) = rx.recv().await {
+            received.push(match result {
+                DataLoadResult::Success { pr, revision, .. } => Ok((revision, pr.title)),
+                DataLoadResult::Error(e) => Err(e),
+                _ => Err("unexpected intermediate result".to_string()),
+            });
+        }
+        received
+    }
+
+    #[tokio::test]
+    async fn test_stale_while_revalidate_sends_cache_then_fresh() {
+        let received = collect_swr(MockFetcher {
+            cached: Some(swr_pr("Cached")),
+            fresh: Ok(swr_pr("Fresh")),
+        })
+        .await;
+        assert_eq!(
+            received,
+            vec![
+                Ok((DataRevision::Stale, "Cached".to_string())),
+                Ok((DataRevision::Fresh, "Fresh".to_string())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_while_revalidate_without_cache_sends_only_fresh() {
+        let received = collect_swr(MockFetcher {
+            cached: None,
+            fresh: Ok(swr_pr("Fresh")),
+        })
+        .await;
+        assert_eq!(
+            received,
+            vec![Ok((DataRevision::Fresh, "Fresh".to_string()))]
+        );
+
+        // 取得失敗時もキャッシュは先に届いている
+        let received = collect_swr(MockFetcher {
+            cached: Some(swr_pr("Cached")),
+            fresh: Err("boom".to_string()),
+        })
+        .await;
+        assert_eq!(
+            received,
+            vec![
+                Ok((DataRevision::Stale, "Cached".to_string())),
+                Err("boom".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cache_only_serves_snapshot_or_offline_error() {
+        let tempdir = tempdir().unwrap();
+        let base = tempdir.path().join("cache");
+
+        // 未キャッシュ: gh を呼ばずにオフラインエラー
+        match load_cached_pr_data("owner/repo", 7, &base) {
+            DataLoadResult::Error(msg) => assert!(msg.contains("not cached (offline)"), "{msg}"),
+            _ => panic!("expected offline error"),
+        }
+
+        let pr = PullRequest {
+            number: 7,
+            node_id: None,
+            title: "Cached PR".to_string(),
+            body: None,
+            state: "OPEN".to_string(),
+            head: github::Branch {
+                ref_name: "feature".to_string(),
+                sha: "abc".to_string(),
+            },
+            base: github::Branch {
+                ref_name: "main".to_string(),
+                sha: "def".to_string(),
+            },
+            user: github::User {
+                login: "octocat".to_string(),
+            },
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
+        };
+        let files = vec![ChangedFile {
+            filename: "src/lib.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some("@@ -1 +1 @@\n+x".to_string()),
+            viewed: true,
+        }];
+        cache::save_pr_data_snapshot_with_base("owner/repo", 7, &pr, &files, &base).unwrap();
+
+        match load_cached_pr_data("owner/repo", 7, &base) {
+            DataLoadResult::Success { pr, files, .. } => {
+                assert_eq!(pr.title, "Cached PR");
+                assert_eq!(files.len(), 1);
+                assert_eq!(files[0].patch.as_deref(), Some("@@ -1 +1 @@\n+x"));
+                assert!(files[0].viewed);
+            }
+            DataLoadResult::Error(msg) => panic!("unexpected error: {msg}"),
+            _ => panic!("unexpected intermediate result"),
+        }
+    }
 }
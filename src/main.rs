@@ -23,6 +23,7 @@ use octorus::{app, cache, config, github, headless, loader, syntax};
 mod init;
 mod local_comments;
 mod migrate;
+mod pr_json;
 mod update;
 
 #[derive(Parser, Debug)]
@@ -63,10 +64,30 @@ struct Args {
     #[arg(long, default_value = "false")]
     git_ops: bool,
 
+    /// Open the diff of this changed file directly (with --pr <number> or --local)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["issue", "git_ops", "ai_rally"])]
+    file: Option<String>,
+
     /// Auto-focus changed file when local diff updates (for local mode)
     #[arg(long, default_value = "false")]
     auto_focus: bool,
 
+    /// Review only the changes since this revision (with --pr <number>: REV...PR head; with --local: REV instead of HEAD)
+    #[arg(long, value_name = "REV", value_parser = parse_diff_base, conflicts_with_all = ["issue", "offline"])]
+    base: Option<String>,
+
+    /// Browse a previously opened PR from the disk cache only (no network access; with --pr <number>)
+    #[arg(long, default_value = "false", conflicts_with_all = ["local", "issue", "ai_rally", "git_ops"])]
+    offline: bool,
+
+    /// Browse without write actions (comments, suggestions, reviews, mark viewed, AI Rally, git ops)
+    #[arg(long, default_value = "false")]
+    read_only: bool,
+
+    /// Syntax highlighting theme for this session (overrides diff.theme in config)
+    #[arg(long, value_name = "NAME")]
+    theme: Option<String>,
+
     /// Working directory for AI agents (default: current directory)
     #[arg(long)]
     working_dir: Option<String>,
@@ -148,6 +169,16 @@ enum Commands {
         #[arg(required = true, num_args = 1.., value_name = "ID")]
         ids: Vec<u64>,
     },
+    /// Print a PR's metadata and changed files as JSON (no TUI)
+    Json {
+        /// Repository name (e.g., "owner/repo"). Auto-detected if omitted.
+        #[arg(short, long)]
+        repo: Option<String>,
+
+        /// Pull request number
+        #[arg(short, long)]
+        pr: u32,
+    },
     /// Update to the latest version from GitHub Releases
     Update,
     /// Migrate configuration files and prompts after an update
@@ -217,14 +248,17 @@ fn is_root_help(raw_args: &[OsString]) -> bool {
 /// Restore terminal to normal state
 fn restore_terminal() {
     octorus::ui::cleanup_keyboard_enhancement();
+    octorus::ui::cleanup_mouse_capture();
     let _ = disable_raw_mode();
     let _ = execute!(io::stdout(), LeaveAlternateScreen);
 }
 
-/// Set up panic hook to restore terminal on panic
+/// Set up panic hook to save the review session and restore terminal on panic
 fn setup_panic_hook() {
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
+        // クラッシュ復旧用にレビューセッションを best-effort で保存
+        cache::flush_crash_session_snapshot();
         restore_terminal();
         original_hook(panic_info);
     }));
@@ -314,6 +348,7 @@ async fn main() -> Result<()> {
                 )
                 .await
             }
+            Commands::Json { repo, pr } => pr_json::pr_json_command(repo, pr).await,
             Commands::Update => {
                 update::run_update()?;
                 Ok(())
@@ -326,6 +361,16 @@ async fn main() -> Result<()> {
         };
     }
 
+    if args.file.is_some() && !args.local && !matches!(args.pr, Some(pr) if pr > 0) {
+        eprintln!("Error: --file requires --pr <number> or --local");
+        std::process::exit(1);
+    }
+
+    if args.offline && !matches!(args.pr, Some(pr) if pr > 0) {
+        eprintln!("Error: --offline requires --pr <number>");
+        std::process::exit(1);
+    }
+
     let is_no_args =
         args.pr.is_none() && !args.local && args.issue.is_none() && !args.git_ops && !args.ai_rally;
 
@@ -349,14 +394,21 @@ async fn main() -> Result<()> {
         }
     };
 
+    let config_repo = repo_available.then_some(repo.as_str());
+
     if is_no_args {
         let mut config = if let Some(ref dir) = args.working_dir {
-            config::Config::load_for_dir(Path::new(dir))?
+            config::Config::load_for_dir(Path::new(dir), config_repo)?
         } else {
-            config::Config::load()?
+            config::Config::load(config_repo)?
         };
         apply_cli_config_overrides(&mut config, &args);
-        return run_with_cockpit(&repo, config, &args, repo_available).await;
+        config
+            .diff
+            .apply_terminal_background(syntax::detect_terminal_background());
+        apply_cli_theme(&mut config, &args);
+        let github = github::backend_from_config(&config.github, false)?;
+        return run_with_cockpit(&repo, config, github, &args, repo_available).await;
     }
 
     // Pre-initialize syntax highlighting in background to avoid delay on first diff view
@@ -366,11 +418,20 @@ async fn main() -> Result<()> {
     });
 
     let mut config = if let Some(ref dir) = args.working_dir {
-        config::Config::load_for_dir(Path::new(dir))?
+        config::Config::load_for_dir(Path::new(dir), config_repo)?
     } else {
-        config::Config::load()?
+        config::Config::load(config_repo)?
     };
     apply_cli_config_overrides(&mut config, &args);
+    if args.ai_rally && config.read_only {
+        eprintln!("Error: --ai-rally is not available in read-only mode");
+        std::process::exit(1);
+    }
+    config
+        .diff
+        .apply_terminal_background(syntax::detect_terminal_background());
+    apply_cli_theme(&mut config, &args);
+    let github = github::backend_from_config(&config.github, args.offline)?;
 
     // Headless mode: --ai-rally with --pr <number> or --local bypasses TUI entirely
     if args.ai_rally && matches!(args.pr, Some(pr) if pr > 0) {
@@ -379,6 +440,7 @@ async fn main() -> Result<()> {
         match headless::run_headless_rally(
             &repo,
             pr,
+            github,
             &config,
             working_dir.as_deref(),
             args.accept_local_overrides,
@@ -398,6 +460,7 @@ async fn main() -> Result<()> {
         let working_dir = resolve_working_dir(&args);
         match headless::run_headless_rally_local(
             &repo,
+            github,
             &config,
             working_dir.as_deref(),
             args.accept_local_overrides,
@@ -415,17 +478,23 @@ async fn main() -> Result<()> {
     }
 
     if args.local {
-        run_with_local_diff(&repo, &config, &args).await
+        run_with_local_diff(&repo, &config, github, &args).await
     } else if let Some(pr) = args.pr.filter(|&n| n > 0) {
-        run_with_pr(&repo, pr, &config, &args).await
+        run_with_pr(&repo, pr, &config, github, &args).await
     } else {
-        run_with_pr_list(&repo, config, &args, args.issue).await
+        run_with_pr_list(&repo, config, github, &args, args.issue).await
     }
 }
 
-async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -> Result<()> {
+async fn run_with_local_diff(
+    repo: &str,
+    config: &config::Config,
+    github: Arc<dyn github::GitHubBackend>,
+    args: &Args,
+) -> Result<()> {
     let (retry_tx, mut retry_rx) = mpsc::channel::<RefreshRequest>(1);
     let (mut app, tx) = app::App::new_loading(repo, 0, config.clone());
+    app.set_github_backend(github);
     let working_dir = args.working_dir.clone();
     let refresh_pending = Arc::new(AtomicBool::new(false));
 
@@ -434,7 +503,11 @@ async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -
     setup_local_watch(retry_tx, working_dir.clone(), refresh_pending.clone());
     app.set_local_mode(true);
     app.set_local_auto_focus(args.auto_focus);
+    app.set_diff_base(args.base.clone());
     setup_working_dir(&mut app, args);
+    if let Some(path) = args.file.clone() {
+        app.set_open_file_on_load(path);
+    }
 
     if args.ai_rally {
         app.set_start_ai_rally_on_load(true);
@@ -446,8 +519,15 @@ async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -
     let cancel_token = CancellationToken::new();
     let token_clone = cancel_token.clone();
     let repo = repo.to_string();
+    let diff_base = app.diff_base_handle();
 
-    loader::fetch_local_diff(repo.clone(), working_dir.clone(), tx.clone()).await;
+    loader::fetch_local_diff(
+        repo.clone(),
+        working_dir.clone(),
+        current_diff_base(&diff_base),
+        tx.clone(),
+    )
+    .await;
 
     tokio::spawn(async move {
         tokio::select! {
@@ -460,7 +540,7 @@ async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -
 
                             loop {
                                 let tx_retry = tx.clone();
-                                loader::fetch_local_diff(repo.clone(), working_dir.clone(), tx_retry).await;
+                                loader::fetch_local_diff(repo.clone(), working_dir.clone(), current_diff_base(&diff_base), tx_retry).await;
 
                                 if !refresh_pending.swap(false, Ordering::AcqRel) {
                                     break;
@@ -471,7 +551,7 @@ async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -
                             // In local mode, pr_number == 0 is a dummy value that would
                             // produce invalid API calls, so treat PrRefresh as LocalRefresh.
                             let tx_retry = tx.clone();
-                            loader::fetch_local_diff(repo.clone(), working_dir.clone(), tx_retry).await;
+                            loader::fetch_local_diff(repo.clone(), working_dir.clone(), current_diff_base(&diff_base), tx_retry).await;
                         }
                     }
                 }
@@ -491,6 +571,20 @@ async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -
     std::process::exit(exit_code);
 }
 
+/// retry ループから見た現在の比較元リビジョン
+fn current_diff_base(diff_base: &app::SharedDiffBase) -> Option<String> {
+    diff_base.lock().ok().and_then(|base| base.clone())
+}
+
+/// `--base` の値を検証する（`git diff` のオプションと誤認される値を拒否）
+fn parse_diff_base(rev: &str) -> std::result::Result<String, String> {
+    if loader::is_valid_diff_base(rev) {
+        Ok(rev.to_string())
+    } else {
+        Err(format!("invalid revision: {:?}", rev))
+    }
+}
+
 fn setup_local_watch(
     refresh_tx: mpsc::Sender<RefreshRequest>,
     working_dir: Option<String>,
@@ -548,15 +642,30 @@ fn is_octorus_config_file(path: &Path) -> bool {
 }
 
 /// Run the app with a specific PR number (existing flow)
-async fn run_with_pr(repo: &str, pr: u32, config: &config::Config, args: &Args) -> Result<()> {
+async fn run_with_pr(
+    repo: &str,
+    pr: u32,
+    config: &config::Config,
+    github: Arc<dyn github::GitHubBackend>,
+    args: &Args,
+) -> Result<()> {
     let (retry_tx, mut retry_rx) = mpsc::channel::<RefreshRequest>(1);
     let refresh_pending = Arc::new(AtomicBool::new(false));
 
     let (mut app, tx) = app::App::new_loading(repo, pr, config.clone());
+    app.set_github_backend(github.clone());
 
-    app.set_retry_sender(retry_tx);
-    start_update_check(&mut app);
+    app.set_retry_sender(retry_tx.clone());
+    app.set_diff_base(args.base.clone());
+    if args.offline {
+        app.set_offline(true);
+    } else {
+        start_update_check(&mut app);
+    }
     setup_working_dir(&mut app, args);
+    if let Some(path) = args.file.clone() {
+        app.set_open_file_on_load(path);
+    }
 
     if args.ai_rally {
         app.set_start_ai_rally_on_load(true);
@@ -567,29 +676,45 @@ async fn run_with_pr(repo: &str, pr: u32, config: &config::Config, args: &Args)
 
     let cancel_token = CancellationToken::new();
     let token_clone = cancel_token.clone();
+    start_auto_refresh(&app, retry_tx, &cancel_token);
 
     let repo_clone = repo.to_string();
     let pr_number = pr;
     let working_dir = args.working_dir.clone();
+    let offline = args.offline;
+    let diff_base = app.diff_base_handle();
+    let fetch_mode = move || {
+        if offline {
+            loader::FetchMode::CacheOnly
+        } else {
+            loader::FetchMode::Fresh
+        }
+    };
 
     tokio::spawn(async move {
         tokio::select! {
             _ = token_clone.cancelled() => {}
             _ = async {
-                loader::fetch_pr_data(repo_clone.clone(), pr_number, loader::FetchMode::Fresh, tx.clone()).await;
+                // 初回はキャッシュを即座に表示してから最新データに差し替える
+                let initial_mode = if offline {
+                    loader::FetchMode::CacheOnly
+                } else {
+                    loader::FetchMode::StaleWhileRevalidate
+                };
+                loader::fetch_pr_data(github.clone(), repo_clone.clone(), pr_number, initial_mode, current_diff_base(&diff_base), tx.clone()).await;
 
                 while let Some(request) = retry_rx.recv().await {
                     match request {
                         RefreshRequest::PrRefresh { pr_number } => {
                             let tx_retry = tx.clone();
-                            loader::fetch_pr_data(repo_clone.clone(), pr_number, loader::FetchMode::Fresh, tx_retry)
+                            loader::fetch_pr_data(github.clone(), repo_clone.clone(), pr_number, fetch_mode(), current_diff_base(&diff_base), tx_retry)
                                 .await;
                         }
                         RefreshRequest::LocalRefresh => {
                             refresh_pending.store(false, Ordering::Release);
                             loop {
                                 let tx_retry = tx.clone();
-                                loader::fetch_local_diff(repo_clone.clone(), working_dir.clone(), tx_retry).await;
+                                loader::fetch_local_diff(repo_clone.clone(), working_dir.clone(), current_diff_base(&diff_base), tx_retry).await;
                                 if !refresh_pending.swap(false, Ordering::AcqRel) {
                                     break;
                                 }
@@ -619,6 +744,7 @@ async fn run_with_pr(repo: &str, pr: u32, config: &config::Config, args: &Args)
 async fn run_with_pr_list(
     repo: &str,
     config: config::Config,
+    github: Arc<dyn github::GitHubBackend>,
     args: &Args,
     issue_arg: Option<u32>,
 ) -> Result<()> {
@@ -627,7 +753,8 @@ async fn run_with_pr_list(
     let refresh_pending = Arc::new(AtomicBool::new(false));
 
     let mut app = app::App::new_pr_list(repo, config);
-    app.set_retry_sender(retry_tx);
+    app.set_github_backend(github.clone());
+    app.set_retry_sender(retry_tx.clone());
     start_update_check(&mut app);
     setup_working_dir(&mut app, args);
 
@@ -654,9 +781,12 @@ async fn run_with_pr_list(
 
     let repo_clone = repo.to_string();
     let state_filter = app.prs.pr_list_state_filter;
+    let filters = app.prs.pr_list_filters.clone();
+    let github_for_list = github.clone();
 
     tokio::spawn(async move {
-        let result = github::fetch_pr_list(&repo_clone, state_filter, 30).await;
+        let result =
+            github::fetch_pr_list(&*github_for_list, &repo_clone, state_filter, &filters, 30).await;
         let _ = pr_list_tx.send(result.map_err(|e| e.to_string())).await;
     });
 
@@ -666,8 +796,10 @@ async fn run_with_pr_list(
 
     let cancel_token = CancellationToken::new();
     let token_clone = cancel_token.clone();
+    start_auto_refresh(&app, retry_tx, &cancel_token);
     let repo_for_retry = repo.to_string();
     let working_dir = args.working_dir.clone();
+    let diff_base = app.diff_base_handle();
 
     tokio::spawn(async move {
         tokio::select! {
@@ -677,14 +809,14 @@ async fn run_with_pr_list(
                     match request {
                         RefreshRequest::PrRefresh { pr_number } => {
                             let tx_retry = data_tx.clone();
-                            loader::fetch_pr_data(repo_for_retry.clone(), pr_number, loader::FetchMode::Fresh, tx_retry)
+                            loader::fetch_pr_data(github.clone(), repo_for_retry.clone(), pr_number, loader::FetchMode::Fresh, current_diff_base(&diff_base), tx_retry)
                                 .await;
                         }
                         RefreshRequest::LocalRefresh => {
                             refresh_pending.store(false, Ordering::Release);
                             loop {
                                 let tx_retry = data_tx.clone();
-                                loader::fetch_local_diff(repo_for_retry.clone(), working_dir.clone(), tx_retry).await;
+                                loader::fetch_local_diff(repo_for_retry.clone(), working_dir.clone(), current_diff_base(&diff_base), tx_retry).await;
                                 if !refresh_pending.swap(false, Ordering::AcqRel) {
                                     break;
                                 }
@@ -713,6 +845,7 @@ async fn run_with_pr_list(
 async fn run_with_cockpit(
     repo: &str,
     config: config::Config,
+    github: Arc<dyn github::GitHubBackend>,
     args: &Args,
     repo_available: bool,
 ) -> Result<()> {
@@ -720,7 +853,8 @@ async fn run_with_cockpit(
     let refresh_pending = Arc::new(AtomicBool::new(false));
 
     let mut app = app::App::new_cockpit(repo, config, repo_available);
-    app.set_retry_sender(retry_tx);
+    app.set_github_backend(github.clone());
+    app.set_retry_sender(retry_tx.clone());
     start_update_check(&mut app);
     setup_working_dir(&mut app, args);
 
@@ -731,9 +865,11 @@ async fn run_with_cockpit(
 
     let cancel_token = CancellationToken::new();
     let token_clone = cancel_token.clone();
+    start_auto_refresh(&app, retry_tx, &cancel_token);
 
     let repo_for_retry = repo.to_string();
     let working_dir = args.working_dir.clone();
+    let diff_base = app.diff_base_handle();
 
     tokio::spawn(async move {
         tokio::select! {
@@ -743,14 +879,14 @@ async fn run_with_cockpit(
                     match request {
                         app::RefreshRequest::PrRefresh { pr_number } => {
                             let tx_retry = data_tx.clone();
-                            loader::fetch_pr_data(repo_for_retry.clone(), pr_number, loader::FetchMode::Fresh, tx_retry)
+                            loader::fetch_pr_data(github.clone(), repo_for_retry.clone(), pr_number, loader::FetchMode::Fresh, current_diff_base(&diff_base), tx_retry)
                                 .await;
                         }
                         app::RefreshRequest::LocalRefresh => {
                             refresh_pending.store(false, Ordering::Release);
                             loop {
                                 let tx_retry = data_tx.clone();
-                                loader::fetch_local_diff(repo_for_retry.clone(), working_dir.clone(), tx_retry).await;
+                                loader::fetch_local_diff(repo_for_retry.clone(), working_dir.clone(), current_diff_base(&diff_base), tx_retry).await;
                                 if !refresh_pending.swap(false, Ordering::AcqRel) {
                                     break;
                                 }
@@ -774,6 +910,27 @@ async fn run_with_cockpit(
     std::process::exit(exit_code);
 }
 
+/// `auto_refresh_secs` が設定されていれば、表示中のリモート PR を定期的に
+/// 再取得させるタイマーを起動する（対象の PR は App が毎フレーム更新する）
+fn start_auto_refresh(
+    app: &app::App,
+    retry_tx: mpsc::Sender<RefreshRequest>,
+    cancel_token: &CancellationToken,
+) {
+    if app.config.auto_refresh_secs == 0 {
+        return;
+    }
+    let period = Duration::from_secs(app.config.auto_refresh_secs);
+    let target = app.auto_refresh_handle();
+    let token = cancel_token.clone();
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = token.cancelled() => {}
+            _ = app::run_auto_refresh(period, target, retry_tx) => {}
+        }
+    });
+}
+
 /// Spawn a background version check and set the receiver on the App.
 fn start_update_check(app: &mut app::App) {
     let (tx, rx) = mpsc::channel(1);
@@ -803,6 +960,24 @@ fn apply_cli_config_overrides(config: &mut config::Config, args: &Args) {
         // project-local key for headless/TUI local-override warnings.
         config.local_overrides.remove("ai.review_only");
     }
+    if args.read_only {
+        config.read_only = true;
+    }
+}
+
+/// `--theme` を適用する。端末背景によるテーマ選択（`theme_light` / `theme_dark`）より
+/// 後に呼び、指定を優先する。見つからないテーマ名なら警告して設定のテーマを使う。
+fn apply_cli_theme(config: &mut config::Config, args: &Args) {
+    let Some(requested) = args.theme.as_deref() else {
+        return;
+    };
+    let (theme, warning) = syntax::resolve_theme_override(&config.diff.theme, Some(requested));
+    if let Some(warning) = warning {
+        eprintln!("Warning: {}", warning);
+        return;
+    }
+    config.diff.theme = theme.clone();
+    config.theme_override = Some(theme);
 }
 
 /// Set up working directory for AI agents
@@ -918,6 +1093,38 @@ mod tests {
         assert!(!config.local_overrides.contains("ai.review_only"));
     }
 
+    #[test]
+    fn test_read_only_cli_flag_enables_config() {
+        let args = Args::parse_from(["or", "--pr", "123", "--read-only"]);
+        let mut config = config::Config::default();
+
+        apply_cli_config_overrides(&mut config, &args);
+
+        assert!(config.read_only);
+    }
+
+    #[test]
+    fn test_theme_cli_flag_overrides_config_theme() {
+        let args = Args::parse_from(["or", "--local", "--theme", "dracula"]);
+        let mut config = config::Config::default();
+        config.diff.theme = "base16-ocean.dark".to_string();
+
+        apply_cli_theme(&mut config, &args);
+
+        assert_eq!(config.diff.theme, "Dracula");
+        assert_eq!(config.theme_override.as_deref(), Some("Dracula"));
+
+        // 見つからないテーマ名は設定のテーマのまま
+        let args = Args::parse_from(["or", "--local", "--theme", "no-such-theme"]);
+        let mut config = config::Config::default();
+        config.diff.theme = "base16-ocean.dark".to_string();
+
+        apply_cli_theme(&mut config, &args);
+
+        assert_eq!(config.diff.theme, "base16-ocean.dark");
+        assert!(config.theme_override.is_none());
+    }
+
     #[test]
     fn test_root_help_snapshot_includes_review_only_flag() {
         use clap::CommandFactory;
@@ -935,6 +1142,7 @@ mod tests {
           clean                 Remove AI Rally session data
           local-comments        Show saved local comments for the current worktree
           update-local-comment  Update saved local comments for the current worktree
+          json                  Print a PR's metadata and changed files as JSON (no TUI)
           update                Update to the latest version from GitHub Releases
           migrate               Migrate configuration files and prompts after an update
           help                  Print this message or the help of the given subcommand(s)
@@ -947,7 +1155,12 @@ mod tests {
               --local                      Show local git diff against current HEAD (no GitHub PR fetch)
           -i, --issue [<ISSUE>]            Issue number. Shows issue detail directly if provided, issue list if flag only
               --git-ops                    Start in Git Ops view directly
+              --file <PATH>                Open the diff of this changed file directly (with --pr <number> or --local)
               --auto-focus                 Auto-focus changed file when local diff updates (for local mode)
+              --base <REV>                 Review only the changes since this revision (with --pr <number>: REV...PR head; with --local: REV instead of HEAD)
+              --offline                    Browse a previously opened PR from the disk cache only (no network access; with --pr <number>)
+              --read-only                  Browse without write actions (comments, suggestions, reviews, mark viewed, AI Rally, git ops)
+              --theme <NAME>               Syntax highlighting theme for this session (overrides diff.theme in config)
               --working-dir <WORKING_DIR>  Working directory for AI agents (default: current directory)
               --accept-local-overrides     Accept local .octorus/ overrides for AI settings in headless mode. Without this flag, headless AI Rally will refuse to run if the local config overrides security-sensitive AI keys or local prompt files are detected in .octorus/prompts/
               --output <OUTPUT>            Write JSON result to a file (in addition to stdout). Useful when running as a background task where stdout may not be captured
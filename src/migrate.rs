@@ -179,6 +179,55 @@ const DEFAULT_HASHES: &[DefaultFileHash] = &[
         filename: "rereview.md",
         sha256: HASH_REREVIEW_0_5_6,
     },
+    // 0.6.7 — global config.toml is the 0.6.7 default; local config and prompts unchanged
+    DefaultFileHash {
+        scope: FileScope::Global,
+        version: "0.6.7",
+        filename: "config.toml",
+        sha256: HASH_GLOBAL_CONFIG_0_6_7,
+    },
+    DefaultFileHash {
+        scope: FileScope::Local,
+        version: "0.6.7",
+        filename: "config.toml",
+        sha256: HASH_LOCAL_CONFIG_0_5_8,
+    },
+    DefaultFileHash {
+        scope: FileScope::Global,
+        version: "0.6.7",
+        filename: "reviewer.md",
+        sha256: HASH_REVIEWER_0_5_6,
+    },
+    DefaultFileHash {
+        scope: FileScope::Local,
+        version: "0.6.7",
+        filename: "reviewer.md",
+        sha256: HASH_REVIEWER_0_5_6,
+    },
+    DefaultFileHash {
+        scope: FileScope::Global,
+        version: "0.6.7",
+        filename: "reviewee.md",
+        sha256: HASH_REVIEWEE_0_5_6,
+    },
+    DefaultFileHash {
+        scope: FileScope::Local,
+        version: "0.6.7",
+        filename: "reviewee.md",
+        sha256: HASH_REVIEWEE_0_5_6,
+    },
+    DefaultFileHash {
+        scope: FileScope::Global,
+        version: "0.6.7",
+        filename: "rereview.md",
+        sha256: HASH_REREVIEW_0_5_6,
+    },
+    DefaultFileHash {
+        scope: FileScope::Local,
+        version: "0.6.7",
+        filename: "rereview.md",
+        sha256: HASH_REREVIEW_0_5_6,
+    },
     // SKILL.md
     DefaultFileHash {
         scope: FileScope::Skill,
@@ -217,6 +266,8 @@ const HASH_GLOBAL_CONFIG_0_5_8: &str =
     "432c5a06806123c2a3a944353c826d004ab775759e158728541f87039240560e";
 const HASH_LOCAL_CONFIG_0_5_8: &str =
     "db391a2874904add6f193366aaa0d5eb7b35689f5fae04cf4a2d8d740c1c353e";
+const HASH_GLOBAL_CONFIG_0_6_7: &str =
+    "192d00c12bbbb9cefa7ed4553369f0df54007b8c95746d82063dc94853f7eb87";
 const HASH_REVIEWER_0_5_6: &str =
     "d9dfdd90d4041ef424edbab3754ab94bafbdad9d69e7297db195cf6194701e58";
 const HASH_REVIEWEE_0_5_6: &str =
@@ -1193,7 +1244,7 @@ mod tests {
                 FileScope::Global,
                 "config.toml",
                 DEFAULT_CONFIG,
-                HASH_GLOBAL_CONFIG_0_5_8,
+                HASH_GLOBAL_CONFIG_0_6_7,
             ),
             (
                 FileScope::Local,
@@ -1296,13 +1347,13 @@ mod tests {
         .unwrap();
 
         let manifest = Some(VersionManifest {
-            binary_version: "0.5.8".to_string(),
+            binary_version: "0.6.7".to_string(),
             initialized_at: "2024-01-01T00:00:00Z".to_string(),
             last_migrated_at: None,
             files: HashMap::new(),
         });
 
-        let actions = build_migration_plan(&config_dir, None, &manifest, "0.5.8", false, false);
+        let actions = build_migration_plan(&config_dir, None, &manifest, "0.6.7", false, false);
 
         // Should only have skips + WriteManifest
         assert!(
@@ -1558,7 +1609,7 @@ mod tests {
         )
         .unwrap();
 
-        let version = "0.5.8";
+        let version = "0.6.7";
 
         // First migration — should be "all up to date" but write manifest
         let actions = build_migration_plan(&config_dir, None, &None, version, false, false);
@@ -1596,7 +1647,7 @@ mod tests {
         )
         .unwrap();
 
-        let version = "0.5.8";
+        let version = "0.6.7";
         let manifest_path = config_dir.join(".version");
 
         // Run 1
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use octorus::config::Config;
+use octorus::github::{self, ChangedFile, PullRequest};
+
+/// Schema of `or json` output (version 1).
+///
+/// Field names and order are stable; new fields are only ever appended.
+///
+/// ```json
+/// {
+///   "schema_version": 1,
+///   "repo": "owner/repo",
+///   "number": 42,
+///   "title": "Add feature",
+///   "state": "open",
+///   "author": "alice",
+///   "head": "feature",
+///   "base": "main",
+///   "labels": ["enhancement"],
+///   "updated_at": "2024-01-01T00:00:00Z",
+///   "files": [
+///     { "filename": "src/lib.rs", "status": "modified", "additions": 3, "deletions": 1 }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Serialize)]
+struct PrJsonOutput {
+    schema_version: u32,
+    repo: String,
+    number: u32,
+    title: String,
+    state: String,
+    author: String,
+    head: String,
+    base: String,
+    labels: Vec<String>,
+    updated_at: String,
+    files: Vec<PrJsonFile>,
+}
+
+#[derive(Debug, Serialize)]
+struct PrJsonFile {
+    filename: String,
+    status: String,
+    additions: u32,
+    deletions: u32,
+}
+
+const SCHEMA_VERSION: u32 = 1;
+
+impl PrJsonOutput {
+    fn new(repo: &str, pr: &PullRequest, files: &[ChangedFile]) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            repo: repo.to_string(),
+            number: pr.number,
+            title: pr.title.clone(),
+            state: pr.state.clone(),
+            author: pr.user.login.clone(),
+            head: pr.head.ref_name.clone(),
+            base: pr.base.ref_name.clone(),
+            labels: pr.labels.iter().map(|l| l.name.clone()).collect(),
+            updated_at: pr.updated_at.clone(),
+            files: files
+                .iter()
+                .map(|f| PrJsonFile {
+                    filename: f.filename.clone(),
+                    status: f.status.clone(),
+                    additions: f.additions,
+                    deletions: f.deletions,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// `or json --pr <number>`: PR のメタデータと変更ファイルを JSON で標準出力に書き出す
+///
+/// TUI を起動しないため raw mode / alternate screen には入らない。
+pub async fn pr_json_command(repo: Option<String>, pr_number: u32) -> Result<()> {
+    let repo = match repo {
+        Some(repo) => repo,
+        None => github::detect_repo().await?,
+    };
+
+    let config = Config::load(Some(&repo))?;
+    let github = github::backend_from_config(&config.github, false)?;
+
+    let (pr, files) = tokio::try_join!(
+        github::fetch_pr(&*github, &repo, pr_number),
+        github::fetch_changed_files(&*github, &repo, pr_number),
+    )?;
+
+    let payload = PrJsonOutput::new(&repo, &pr, &files);
+    println!("{}", serde_json::to_string_pretty(&payload)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use octorus::github::{Branch, Label, User};
+
+    #[test]
+    fn test_pr_json_output_shape() {
+        let pr = PullRequest {
+            number: 42,
+            node_id: Some("PR_node".to_string()),
+            title: "Add feature".to_string(),
+            body: Some("body is not part of the output".to_string()),
+            state: "open".to_string(),
+            head: Branch {
+                ref_name: "feature".to_string(),
+                sha: "abc".to_string(),
+            },
+            base: Branch {
+                ref_name: "main".to_string(),
+                sha: "def".to_string(),
+            },
+            user: User {
+                login: "alice".to_string(),
+            },
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: vec![Label {
+                name: "enhancement".to_string(),
+            }],
+            html_url: None,
+            changed_files: None,
+        };
+        let files = vec![ChangedFile {
+            filename: "src/lib.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 3,
+            deletions: 1,
+            patch: Some("@@ -1 +1 @@\n-a\n+b".to_string()),
+            viewed: true,
+        }];
+
+        let json =
+            serde_json::to_string_pretty(&PrJsonOutput::new("owner/repo", &pr, &files)).unwrap();
+        assert_snapshot!(json, @r#"
+        {
+          "schema_version": 1,
+          "repo": "owner/repo",
+          "number": 42,
+          "title": "Add feature",
+          "state": "open",
+          "author": "alice",
+          "head": "feature",
+          "base": "main",
+          "labels": [
+            "enhancement"
+          ],
+          "updated_at": "2024-01-01T00:00:00Z",
+          "files": [
+            {
+              "filename": "src/lib.rs",
+              "status": "modified",
+              "additions": 3,
+              "deletions": 1
+            }
+          ]
+        }
+        "#);
+    }
+}
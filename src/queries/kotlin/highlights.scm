@@ -0,0 +1,203 @@
+;; Kotlin Syntax Highlighting Query
+;; tree-sitter-kotlin-ng doesn't ship queries in its crate, and the upstream
+;; highlights.scm relies on #lua-match?, so this is a tree-sitter compatible subset.
+
+(identifier) @variable
+
+;; Declarations
+
+(function_declaration name: (identifier) @function)
+(class_declaration name: (identifier) @type.definition)
+(object_declaration name: (identifier) @type.definition)
+(companion_object name: (identifier) @type.definition)
+(type_alias type: (identifier) @type.definition)
+(enum_entry (identifier) @constant)
+
+(parameter (identifier) @variable.parameter)
+(class_parameter (identifier) @variable.parameter)
+(type_parameter (identifier) @type)
+
+(user_type (identifier) @type)
+
+(package_header (qualified_identifier (identifier) @module))
+(import (qualified_identifier (identifier) @module))
+
+(annotation (user_type (identifier) @attribute))
+(annotation (constructor_invocation (user_type (identifier) @attribute)))
+"@" @attribute
+
+(label) @label
+
+;; Calls
+
+(call_expression (identifier) @function.call)
+(call_expression
+  (navigation_expression (identifier) @function.method.call .))
+
+(navigation_expression (identifier) @property .)
+
+((identifier) @boolean
+  (#any-of? @boolean "true" "false"))
+
+((identifier) @constant.builtin
+  (#eq? @constant.builtin "null"))
+
+(this_expression) @variable.builtin
+(super_expression) @variable.builtin
+
+;; Literals
+
+[
+  (number_literal)
+] @number
+
+(float_literal) @number.float
+
+[
+  (string_literal)
+  (multiline_string_literal)
+] @string
+
+(character_literal) @character
+
+(escape_sequence) @string.escape
+
+(interpolation
+  [
+    "$"
+    "${"
+    "}"
+  ] @punctuation.special)
+
+[
+  (line_comment)
+  (block_comment)
+] @comment
+
+(shebang) @comment
+
+;; Keywords
+
+[
+  "package"
+  "import"
+] @keyword.import
+
+[
+  "fun"
+] @keyword.function
+
+[
+  "val"
+  "var"
+  "class"
+  "interface"
+  "object"
+  "typealias"
+  "constructor"
+  "init"
+  "companion"
+  "enum"
+  "by"
+  "where"
+  "get"
+  "set"
+] @keyword
+
+[
+  "return"
+  "return@"
+] @keyword.return
+
+[
+  "if"
+  "else"
+  "when"
+] @keyword.conditional
+
+[
+  "for"
+  "while"
+  "do"
+] @keyword.repeat
+
+[
+  "try"
+  "catch"
+  "finally"
+  "throw"
+] @keyword.exception
+
+[
+  "as"
+  "as?"
+  "in"
+  "!in"
+  "is"
+  "!is"
+] @keyword.operator
+
+[
+  (visibility_modifier)
+  (inheritance_modifier)
+  (class_modifier)
+  (member_modifier)
+  (function_modifier)
+  (property_modifier)
+  (parameter_modifier)
+  (platform_modifier)
+  (reification_modifier)
+  (variance_modifier)
+] @keyword.modifier
+
+;; Operators and punctuation
+
+[
+  "="
+  "+="
+  "-="
+  "*="
+  "/="
+  "%="
+  "=="
+  "!="
+  "==="
+  "!=="
+  "<"
+  ">"
+  "<="
+  ">="
+  "+"
+  "-"
+  "*"
+  "/"
+  "%"
+  "++"
+  "--"
+  "!"
+  "!!"
+  "&&"
+  "||"
+  "?:"
+  ".."
+  "..<"
+  "->"
+  "::"
+  "?."
+] @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  "."
+  ","
+  ";"
+  ":"
+] @punctuation.delimiter
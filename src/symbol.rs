@@ -3,13 +3,14 @@
 //! Pure functions for extracting identifiers from source lines
 //! and searching for definitions within diff patches and repositories.
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::OnceLock;
 
 use anyhow::Result;
 use tokio::process::Command;
 
-use crate::diff::{classify_line, LineType};
+use crate::diff::{classify_hunk_line, LineType};
 use crate::github::ChangedFile;
 use crate::language::SupportedLanguage;
 
@@ -261,17 +262,203 @@ pub fn is_definition_line(content: &str, symbol: &str) -> bool {
     false
 }
 
+/// Kind of change reported by [`changed_symbols`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl SymbolChange {
+    pub fn marker(self) -> char {
+        match self {
+            Self::Added => '+',
+            Self::Removed => '-',
+            Self::Modified => '~',
+        }
+    }
+}
+
+/// A symbol touched by a patch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedSymbol {
+    pub name: String,
+    pub change: SymbolChange,
+    /// Diff line index to jump to (the definition line, or the first changed line of the hunk).
+    pub line_index: usize,
+}
+
+/// Extract the defined name from a definition line (`pub fn foo(` → `foo`).
+fn definition_name<'a>(content: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    let trimmed = content.trim_start();
+    prefixes.iter().find_map(|prefix| {
+        let rest = trimmed.strip_prefix(prefix)?.trim_start();
+        let end = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+        let name = &rest[..end];
+        name.starts_with(is_ident_start).then_some(name)
+    })
+}
+
+fn record_symbol(
+    symbols: &mut Vec<ChangedSymbol>,
+    name: &str,
+    change: SymbolChange,
+    line_index: usize,
+) {
+    match symbols.iter_mut().find(|s| s.name == name) {
+        Some(existing) if existing.change != change => existing.change = SymbolChange::Modified,
+        Some(_) => {}
+        None => symbols.push(ChangedSymbol {
+            name: name.to_string(),
+            change,
+            line_index,
+        }),
+    }
+}
+
+/// List the symbols a file's patch adds, removes or modifies, in patch order.
+///
+/// Definition lines (`fn`, `class`, `def`, ...) on added/removed lines are
+/// reported as Added/Removed, or Modified when the name appears on both sides.
+/// A hunk that changes no definition line is attributed to its enclosing
+/// symbol: the last context definition line before the first change, or the
+/// hunk header's context (`@@ -1,2 +1,3 @@ fn foo()`). Files without
+/// language support only use this hunk-context fallback.
+pub fn changed_symbols(filename: &str, patch: &str) -> Vec<ChangedSymbol> {
+    let prefixes = Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(SupportedLanguage::from_extension)
+        .map(|lang| lang.definition_prefixes())
+        .unwrap_or(&[]);
+
+    let mut symbols = Vec::new();
+    let mut enclosing: Option<String> = None;
+    let mut first_change: Option<usize> = None;
+    let mut hunk_has_definition = false;
+    let mut combined = false;
+
+    for (line_idx, line) in patch.lines().enumerate() {
+        let (line_type, content) = classify_hunk_line(line, &mut combined);
+        // 結合 diff の行は結果から見た追加/削除/文脈として扱う
+        let line_type = line_type.two_way();
+        match line_type {
+            LineType::Header => {
+                if !hunk_has_definition {
+                    if let (Some(name), Some(line)) = (&enclosing, first_change) {
+                        record_symbol(&mut symbols, name, SymbolChange::Modified, line);
+                    }
+                }
+                first_change = None;
+                hunk_has_definition = false;
+                let fence = if combined { "@@@" } else { "@@" };
+                let context = line.splitn(3, fence).nth(2).map(str::trim).unwrap_or("");
+                enclosing = definition_name(context, SupportedLanguage::all_definition_prefixes())
+                    .or((!context.is_empty()).then_some(context))
+                    .map(String::from);
+            }
+            LineType::Context if first_change.is_none() => {
+                if let Some(name) = definition_name(content, prefixes) {
+                    enclosing = Some(name.to_string());
+                }
+            }
+            LineType::Added | LineType::Removed => {
+                first_change.get_or_insert(line_idx);
+                if let Some(name) = definition_name(content, prefixes) {
+                    hunk_has_definition = true;
+                    let change = if line_type == LineType::Added {
+                        SymbolChange::Added
+                    } else {
+                        SymbolChange::Removed
+                    };
+                    record_symbol(&mut symbols, name, change, line_idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    if !hunk_has_definition {
+        if let (Some(name), Some(line)) = (&enclosing, first_change) {
+            record_symbol(&mut symbols, name, SymbolChange::Modified, line);
+        }
+    }
+
+    symbols
+}
+
+/// Definitions found in the new-file side (Added and Context lines) of the PR patches.
+///
+/// Maps a symbol name to every `(file_index, diff_line_index)` defining it,
+/// in file order then patch order.
+#[derive(Debug, Default)]
+pub struct PatchSymbolTable {
+    definitions: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl PatchSymbolTable {
+    pub fn build(files: &[ChangedFile]) -> Self {
+        let mut definitions: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        let prefixes = SupportedLanguage::all_definition_prefixes();
+
+        for (file_idx, file) in files.iter().enumerate() {
+            let Some(ref patch) = file.patch else {
+                continue;
+            };
+            let mut combined = false;
+            for (line_idx, line) in patch.lines().enumerate() {
+                let (line_type, content) = classify_hunk_line(line, &mut combined);
+                // 削除行は新ファイルに存在しない
+                if !matches!(line_type.two_way(), LineType::Added | LineType::Context) {
+                    continue;
+                }
+                let trimmed = content.trim_start();
+                if !prefixes.iter().any(|p| trimmed.starts_with(p)) {
+                    continue;
+                }
+                for (name, _, _) in extract_all_identifiers(content) {
+                    if is_definition_line(content, &name) {
+                        definitions
+                            .entry(name)
+                            .or_default()
+                            .push((file_idx, line_idx));
+                    }
+                }
+            }
+        }
+
+        Self { definitions }
+    }
+
+    /// Definition of `symbol`, preferring the current file over the others.
+    pub fn lookup(&self, symbol: &str, current_file_index: usize) -> Option<(usize, usize)> {
+        let locations = self.definitions.get(symbol)?;
+        locations
+            .iter()
+            .find(|(file_idx, _)| *file_idx == current_file_index)
+            .or_else(|| locations.first())
+            .copied()
+    }
+}
+
 /// Search for a symbol definition within the PR diff patches.
 ///
 /// Returns `(file_index, diff_line_index)` if found.
 /// Search order: current file first, then other files.
-/// Two-pass strategy: first search for actual definitions (fn, struct, etc.),
-/// then fall back to import/use statements.
+/// Two-pass strategy: first look the symbol up in `table`, the [`PatchSymbolTable`]
+/// of actual definitions (fn, struct, etc.) built from `files`, then fall back
+/// to import/use statements.
 pub fn find_definition_in_patches(
     symbol: &str,
     files: &[ChangedFile],
+    table: &PatchSymbolTable,
     current_file_index: usize,
 ) -> Option<(usize, usize)> {
+    // Pass 1: Search for actual definitions (fn, struct, class, etc.)
+    if let Some(location) = table.lookup(symbol, current_file_index) {
+        return Some(location);
+    }
+
     // Build search order: current file first, then others
     let mut search_order: Vec<usize> = Vec::with_capacity(files.len());
     if current_file_index < files.len() {
@@ -283,27 +470,6 @@ pub fn find_definition_in_patches(
         }
     }
 
-    // Pass 1: Search for actual definitions (fn, struct, class, etc.)
-    for &file_idx in &search_order {
-        let file = &files[file_idx];
-        let Some(ref patch) = file.patch else {
-            continue;
-        };
-
-        for (line_idx, line) in patch.lines().enumerate() {
-            let (line_type, content) = classify_line(line);
-
-            // Only search in Added and Context lines
-            if !matches!(line_type, LineType::Added | LineType::Context) {
-                continue;
-            }
-
-            if is_definition_line(content, symbol) {
-                return Some((file_idx, line_idx));
-            }
-        }
-    }
-
     // Pass 2: Fall back to import/use statements
     for &file_idx in &search_order {
         let file = &files[file_idx];
@@ -311,10 +477,11 @@ pub fn find_definition_in_patches(
             continue;
         };
 
+        let mut combined = false;
         for (line_idx, line) in patch.lines().enumerate() {
-            let (line_type, content) = classify_line(line);
+            let (line_type, content) = classify_hunk_line(line, &mut combined);
 
-            if !matches!(line_type, LineType::Added | LineType::Context) {
+            if !matches!(line_type.two_way(), LineType::Added | LineType::Context) {
                 continue;
             }
 
@@ -597,6 +764,45 @@ pub fn prev_word_boundary(content: &str, column: usize) -> usize {
 mod tests {
     use super::*;
 
+    // ===== changed_symbols tests =====
+
+    #[test]
+    fn test_changed_symbols_added_removed_modified() {
+        let patch = "@@ -1,6 +1,6 @@\n-fn old_name() {}\n+fn new_name() {}\n-pub fn render(a: u8) {\n+pub fn render(a: u16) {\n     body();\n }";
+        let symbols = changed_symbols("src/lib.rs", patch);
+        let summary: Vec<(&str, SymbolChange, usize)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.change, s.line_index))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("old_name", SymbolChange::Removed, 1),
+                ("new_name", SymbolChange::Added, 2),
+                ("render", SymbolChange::Modified, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_changed_symbols_falls_back_to_enclosing_context() {
+        // 定義行を含まない hunk は、直前の context 定義 → hunk ヘッダの順で帰属させる
+        let patch = "@@ -1,4 +1,4 @@\n fn outer() {\n-    a();\n+    b();\n }\n@@ -20,2 +20,2 @@ fn tail() {\n-    c();\n+    d();";
+        let symbols = changed_symbols("src/lib.rs", patch);
+        let names: Vec<(&str, usize)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.line_index))
+            .collect();
+        assert_eq!(names, vec![("outer", 2), ("tail", 6)]);
+        assert!(symbols.iter().all(|s| s.change == SymbolChange::Modified));
+
+        // 言語サポートのないファイルはヘッダの context のみ
+        let patch = "@@ -3,1 +3,1 @@ section: deploy\n-a: 1\n+a: 2";
+        let symbols = changed_symbols("ci.yaml", patch);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "section: deploy");
+    }
+
     // ===== extract_word_at tests =====
 
     #[test]
@@ -800,7 +1006,8 @@ mod tests {
             viewed: false,
         }];
 
-        let result = find_definition_in_patches("helper", &files, 0);
+        let result =
+            find_definition_in_patches("helper", &files, &PatchSymbolTable::build(&files), 0);
         assert_eq!(result, Some((0, 4)));
     }
 
@@ -829,7 +1036,8 @@ mod tests {
             },
         ];
 
-        let result = find_definition_in_patches("helper", &files, 0);
+        let result =
+            find_definition_in_patches("helper", &files, &PatchSymbolTable::build(&files), 0);
         assert_eq!(result, Some((1, 1)));
     }
 
@@ -846,7 +1054,8 @@ mod tests {
             viewed: false,
         }];
 
-        let result = find_definition_in_patches("nonexistent", &files, 0);
+        let result =
+            find_definition_in_patches("nonexistent", &files, &PatchSymbolTable::build(&files), 0);
         assert_eq!(result, None);
     }
 
@@ -865,7 +1074,8 @@ mod tests {
         }];
 
         // Should NOT find old_helper (removed line)
-        let result = find_definition_in_patches("old_helper", &files, 0);
+        let result =
+            find_definition_in_patches("old_helper", &files, &PatchSymbolTable::build(&files), 0);
         assert_eq!(result, None);
     }
 
@@ -890,10 +1100,64 @@ mod tests {
             },
         ];
 
-        let result = find_definition_in_patches("target", &files, 0);
+        let result =
+            find_definition_in_patches("target", &files, &PatchSymbolTable::build(&files), 0);
         assert_eq!(result, Some((1, 1)));
     }
 
+    #[test]
+    fn test_patch_symbol_table_cross_file_lookup() {
+        let files = vec![
+            ChangedFile {
+                filename: "src/caller.rs".to_string(),
+                status: "modified".to_string(),
+                additions: 1,
+                deletions: 0,
+                patch: Some("@@ -1,2 +1,3 @@\n fn run() {\n+    foo();\n }\n".to_string()),
+                viewed: false,
+            },
+            ChangedFile {
+                filename: "src/foo.rs".to_string(),
+                status: "modified".to_string(),
+                additions: 1,
+                deletions: 1,
+                patch: Some(
+                    "@@ -1,3 +1,3 @@\n use std::fmt;\n-fn old_foo() {}\n+pub fn foo() {}\n"
+                        .to_string(),
+                ),
+                viewed: false,
+            },
+        ];
+
+        let table = PatchSymbolTable::build(&files);
+        // 参照元 (caller.rs) から別ファイルの定義を引ける
+        assert_eq!(table.lookup("foo", 0), Some((1, 3)));
+        assert_eq!(table.lookup("run", 1), Some((0, 1)));
+        // 削除行の定義は新ファイルに存在しない
+        assert_eq!(table.lookup("old_foo", 0), None);
+        assert_eq!(
+            find_definition_in_patches("foo", &files, &table, 0),
+            Some((1, 3))
+        );
+    }
+
+    #[test]
+    fn test_patch_symbol_table_prefers_current_file() {
+        let file = |name: &str| ChangedFile {
+            filename: name.to_string(),
+            status: "added".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some("@@ -0,0 +1 @@\n+fn foo() {}\n".to_string()),
+            viewed: false,
+        };
+        let files = vec![file("a.rs"), file("b.rs")];
+
+        let table = PatchSymbolTable::build(&files);
+        assert_eq!(table.lookup("foo", 1), Some((1, 1)));
+        assert_eq!(table.lookup("foo", 5), Some((0, 1)));
+    }
+
     // ===== next_word_boundary tests =====
 
     #[test]
@@ -1059,7 +1323,8 @@ mod tests {
         }];
 
         // Command is not defined in the patch, but it is imported
-        let result = find_definition_in_patches("Command", &files, 0);
+        let result =
+            find_definition_in_patches("Command", &files, &PatchSymbolTable::build(&files), 0);
         assert_eq!(result, Some((0, 1))); // line 1 = "use std::process::Command;"
     }
 
@@ -0,0 +1,55 @@
+//! Terminal background detection for choosing a light or dark theme.
+//!
+//! Only the `COLORFGBG` environment variable (set by rxvt, Konsole, iTerm2 and
+//! others) is consulted. Querying the terminal with OSC 11 would require reading
+//! the reply from the tty before the TUI starts, which cannot be done with a
+//! timeout without risking swallowing the user's first keystrokes.
+
+/// Brightness of the terminal background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalBackground {
+    Light,
+    Dark,
+}
+
+/// Classify a `COLORFGBG` value (`"fg;bg"` or `"fg;default;bg"`).
+///
+/// The last field is the background's ANSI color index. White (7) and the
+/// bright colors except bright black (9-15) are light; the others are dark.
+/// Returns `None` when the background is `default` or not an ANSI index.
+pub fn classify_colorfgbg(value: &str) -> Option<TerminalBackground> {
+    let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+    match bg {
+        7 | 9..=15 => Some(TerminalBackground::Light),
+        0..=6 | 8 => Some(TerminalBackground::Dark),
+        _ => None,
+    }
+}
+
+/// Detect the terminal background from the environment.
+pub fn detect_terminal_background() -> Option<TerminalBackground> {
+    std::env::var("COLORFGBG")
+        .ok()
+        .and_then(|value| classify_colorfgbg(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_colorfgbg() {
+        assert_eq!(classify_colorfgbg("15;0"), Some(TerminalBackground::Dark));
+        assert_eq!(classify_colorfgbg("0;15"), Some(TerminalBackground::Light));
+        assert_eq!(classify_colorfgbg("7;8"), Some(TerminalBackground::Dark));
+        assert_eq!(classify_colorfgbg("0;7"), Some(TerminalBackground::Light));
+        // rxvt 形式: fg;default;bg
+        assert_eq!(
+            classify_colorfgbg("0;default;15"),
+            Some(TerminalBackground::Light)
+        );
+        assert_eq!(classify_colorfgbg("15;default"), None);
+        assert_eq!(classify_colorfgbg("15;255"), None);
+        assert_eq!(classify_colorfgbg(""), None);
+    }
+}
@@ -3,11 +3,12 @@
 //! Tree-sitter is used for supported languages:
 //! - Rust, TypeScript/TSX, JavaScript/JSX, Go, Python (original)
 //! - Ruby, Zig, C, C++, Java, C# (added)
-//! - Lua, Bash/Shell, PHP, Swift, Haskell (Phase 1)
+//! - Lua, Bash/Shell, PHP, Swift, Kotlin, Haskell (Phase 1)
 //! - MoonBit (Phase 2)
 //!
 //! Syntect is used as a fallback for other languages (Vue, Svelte, YAML, Markdown, etc.).
 
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
 use lasso::Rodeo;
@@ -146,6 +147,26 @@ impl Highlighter {
         }
     }
 
+    /// Like [`Self::parse_source`], but lets the pool reuse the previous tree
+    /// parsed for `path` (see [`ParserPool::with_tree_cache`]).
+    pub fn parse_file_source(
+        &self,
+        path: &str,
+        source: &str,
+        parser_pool: &mut ParserPool,
+    ) -> Option<CstParseResult> {
+        match self {
+            Highlighter::Cst { supported_lang, .. } => {
+                let (tree, _reused) = parser_pool.parse_file(path, *supported_lang, source)?;
+                Some(CstParseResult {
+                    tree,
+                    lang: *supported_lang,
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Get a reference to the style cache for CST highlighting.
     ///
     /// Returns `None` for Syntect or None variants.
@@ -202,6 +223,8 @@ pub fn collect_line_highlights(
 ) -> LineHighlights {
     let mut cursor = QueryCursor::new();
     let mut captures_by_line: HashMap<usize, Vec<LineCapture>> = HashMap::new();
+    // (line, local_start, local_end) -> (pattern_index, index in captures_by_line[line])
+    let mut same_range: HashMap<(usize, usize, usize), (usize, usize)> = HashMap::new();
 
     let line_offsets: Vec<usize> =
         std::iter::once(0)
@@ -249,14 +272,27 @@ pub fn collect_line_highlights(
                     .min(line_end - line_start);
 
                 if local_start < local_end {
-                    captures_by_line
-                        .entry(line_idx)
-                        .or_default()
-                        .push(LineCapture {
-                            local_start,
-                            local_end,
-                            style,
-                        });
+                    let captures = captures_by_line.entry(line_idx).or_default();
+                    // 同一範囲を複数パターンが捕捉した場合は後に定義されたパターンを優先する。
+                    // マッチの列挙順はパターン順と一致しない（親ノード起点のパターン、
+                    // 例: JSX のタグ名はより早く列挙される）ため、ここで明示的に解決する。
+                    match same_range.entry((line_idx, local_start, local_end)) {
+                        Entry::Occupied(mut e) => {
+                            let (prev_pattern, pos) = *e.get();
+                            if mat.pattern_index >= prev_pattern {
+                                captures[pos].style = style;
+                                e.insert((mat.pattern_index, pos));
+                            }
+                        }
+                        Entry::Vacant(e) => {
+                            e.insert((mat.pattern_index, captures.len()));
+                            captures.push(LineCapture {
+                                local_start,
+                                local_end,
+                                style,
+                            });
+                        }
+                    }
                 }
             }
         }
@@ -271,6 +307,9 @@ pub fn collect_line_highlights(
 
 /// Collect highlights with injection support for languages like Svelte.
 ///
+/// Also used for JSX/TSX, whose tagged template literals (e.g. styled-components
+/// `css\`...\``) are highlighted with the language named by the tag.
+///
 /// This extends `collect_line_highlights` to handle embedded languages:
 /// - Parses the parent language (e.g., Svelte)
 /// - Extracts injection ranges using the language's injection query
@@ -316,6 +355,8 @@ pub fn collect_line_highlights_with_injections(
         "svelte" => tree_sitter_svelte_ng::INJECTIONS_QUERY,
         "vue" => tree_sitter_vue3::INJECTIONS_QUERY,
         "md" | "markdown" => tree_sitter_md::INJECTION_QUERY_BLOCK,
+        // Tagged template literals (css`...`, html`...` etc.) inside JSX/TSX
+        "jsx" | "tsx" => tree_sitter_javascript::INJECTIONS_QUERY,
         _ => return result, // No injection support for other languages yet
     };
 
@@ -371,6 +412,7 @@ pub fn collect_line_highlights_with_injections(
             "bash" => "sh",
             "php" => "php",
             "swift" => "swift",
+            "kotlin" => "kt",
             "haskell" => "hs",
             "zig" => "zig",
             "moonbit" => "mbt",
@@ -796,6 +838,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_swift_keyword_spans_via_tree_sitter() {
+        use crate::syntax::get_theme;
+        use crate::syntax::themes::ThemeStyleCache;
+        use ratatui::style::Color;
+
+        let mut pool = ParserPool::new();
+        let highlighter = Highlighter::for_file("main.swift", "Dracula");
+
+        let source = "let x = 1\nprint(x)";
+        let result = highlighter
+            .parse_source(source, &mut pool)
+            .expect("Should parse Swift source");
+
+        let query = pool.get_or_create_query(result.lang).unwrap();
+        let capture_names: Vec<String> = query
+            .capture_names()
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let style_cache = ThemeStyleCache::new(get_theme("Dracula"));
+        let line_highlights =
+            collect_line_highlights(source, &result.tree, query, &capture_names, &style_cache);
+
+        let mut interner = Rodeo::default();
+        let spans = apply_line_highlights("let x = 1", line_highlights.get(0), &mut interner);
+        let let_span = spans
+            .iter()
+            .find(|s| interner.resolve(&s.content) == "let")
+            .expect("Should have 'let' span");
+        assert_eq!(let_span.style.fg, Some(Color::Rgb(255, 121, 198)));
+    }
+
+    #[test]
+    fn test_kotlin_keyword_spans_via_tree_sitter() {
+        use crate::syntax::get_theme;
+        use crate::syntax::themes::ThemeStyleCache;
+        use ratatui::style::Color;
+
+        let mut pool = ParserPool::new();
+        let style_cache = ThemeStyleCache::new(get_theme("Dracula"));
+
+        for filename in ["Main.kt", "build.gradle.kts"] {
+            let highlighter = Highlighter::for_file(filename, "Dracula");
+
+            let source = "fun main() {\n    val x = 1\n}";
+            let result = highlighter
+                .parse_source(source, &mut pool)
+                .expect("Should parse Kotlin source");
+
+            let query = pool.get_or_create_query(result.lang).unwrap();
+            let capture_names: Vec<String> = query
+                .capture_names()
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            let line_highlights =
+                collect_line_highlights(source, &result.tree, query, &capture_names, &style_cache);
+
+            let mut interner = Rodeo::default();
+            let spans =
+                apply_line_highlights("fun main() {", line_highlights.get(0), &mut interner);
+            let fun_span = spans
+                .iter()
+                .find(|s| interner.resolve(&s.content) == "fun")
+                .expect("Should have 'fun' span");
+            assert_eq!(fun_span.style.fg, Some(Color::Rgb(255, 121, 198)));
+
+            let spans =
+                apply_line_highlights("    val x = 1", line_highlights.get(1), &mut interner);
+            let val_span = spans
+                .iter()
+                .find(|s| interner.resolve(&s.content) == "val")
+                .expect("Should have 'val' span");
+            assert_eq!(val_span.style.fg, Some(Color::Rgb(255, 121, 198)));
+        }
+    }
+
     #[test]
     fn test_vue_primed_highlighting() {
         use syntect::easy::HighlightLines;
@@ -1278,6 +1398,97 @@ mod tests {
             "'color' should have syntax highlighting as CSS property in Vue"
         );
     }
+
+    /// Highlight `source` as `filename` and return the style of each named token
+    /// on line `line_idx`.
+    fn token_styles(
+        filename: &str,
+        source: &str,
+        line_idx: usize,
+        tokens: &[&str],
+    ) -> Vec<Option<Style>> {
+        use crate::syntax::get_theme;
+        use crate::syntax::themes::ThemeStyleCache;
+
+        let mut pool = ParserPool::new();
+        let highlighter = Highlighter::for_file(filename, "Dracula");
+        let result = highlighter
+            .parse_source(source, &mut pool)
+            .expect("Should parse source");
+        let style_cache = ThemeStyleCache::new(get_theme("Dracula"));
+        let ext = filename.rsplit('.').next().unwrap();
+        let line_highlights = collect_line_highlights_with_injections(
+            source,
+            &result.tree,
+            result.lang,
+            &style_cache,
+            &mut pool,
+            ext,
+        );
+
+        let mut interner = Rodeo::default();
+        let line = source.lines().nth(line_idx).unwrap();
+        let spans = apply_line_highlights(line, line_highlights.get(line_idx), &mut interner);
+        tokens
+            .iter()
+            .map(|token| {
+                spans
+                    .iter()
+                    .find(|s| interner.resolve(&s.content) == *token)
+                    .map(|s| s.style)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_jsx_tag_and_attribute_styled_apart_from_expression() {
+        for filename in ["App.jsx", "App.tsx"] {
+            let styles = token_styles(
+                filename,
+                "const el = <Foo bar={x} />;",
+                0,
+                &["Foo", "bar", "x"],
+            );
+            let [tag, attr, expr] = [styles[0], styles[1], styles[2]];
+            let (Some(tag), Some(attr), Some(expr)) = (tag, attr, expr) else {
+                panic!("{filename}: expected separate spans for tag/attribute/expression");
+            };
+            assert_ne!(
+                tag, expr,
+                "{filename}: tag name should differ from expression"
+            );
+            assert_ne!(
+                attr, expr,
+                "{filename}: attribute should differ from expression"
+            );
+            assert!(
+                attr.fg.is_some(),
+                "{filename}: attribute should be highlighted"
+            );
+        }
+    }
+
+    #[test]
+    fn test_jsx_intrinsic_element_uses_tag_style() {
+        use crate::syntax::get_theme;
+        use crate::syntax::themes::ThemeStyleCache;
+
+        let tag_style = ThemeStyleCache::new(get_theme("Dracula")).get("tag");
+        for filename in ["App.jsx", "App.tsx"] {
+            let styles = token_styles(filename, "const el = <div className={cls} />;", 0, &["div"]);
+            assert_eq!(styles[0], Some(tag_style), "{filename}");
+        }
+    }
+
+    #[test]
+    fn test_tsx_tagged_template_css_injection() {
+        let source = "const styles = css`\n  .box { color: red; }\n`;";
+        let styles = token_styles("Box.tsx", source, 1, &["color"]);
+        assert!(
+            styles[0].is_some_and(|s| s.fg.is_some()),
+            "'color' should be highlighted by the CSS injection"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -1502,7 +1713,7 @@ const count = ref(0)
         L3: "fn main() {" => "fn main() {"[fg:Green], "fn"[fg:Rgb(180, 142, 173)], "main"[fg:Rgb(143, 161, 179)], "("[fg:Gray], ")"[fg:Gray], "{"[fg:Gray]
         L4: "    println!(\"hello\");" => "    println!(\"hello\");"[fg:Green], "println"[fg:Rgb(143, 161, 179)], "!"[fg:Rgb(143, 161, 179)], "("[fg:Gray], "\"hello\""[fg:Rgb(163, 190, 140)], ")"[fg:Gray], ";"[fg:Gray]
         L5: "}" => "}"[fg:Green], "}"[fg:Gray]
-        L6: "```" => "```"[fg:Green], "```"[fg:Gray]
+        L6: "```" => "```"[fg:Gray]
         "##);
     }
 }
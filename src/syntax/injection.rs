@@ -164,6 +164,7 @@ pub fn normalize_language_name(name: &str) -> &str {
         "bash" | "sh" | "shell" => "bash",
         "php" => "php",
         "swift" => "swift",
+        "kotlin" | "kt" => "kotlin",
         "haskell" | "hs" => "haskell",
         "moonbit" | "mbt" => "moonbit",
         "markdown_inline" | "markdown-inline" => "markdown_inline",
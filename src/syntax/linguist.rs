@@ -0,0 +1,247 @@
+//! `.gitattributes` の linguist 属性によるハイライト言語の上書き。
+//!
+//! - `linguist-language=<name>`: 拡張子ではなく言語名で syntax を選ぶ
+//! - `linguist-generated`: 生成ファイルとしてハイライトを省略する
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use super::syntax_for_file_with_override;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    pattern: String,
+    language: Option<String>,
+    /// `linguist-generated` / `-linguist-generated` / `linguist-generated=false`
+    generated: Option<bool>,
+}
+
+/// `.gitattributes` から読み込んだ linguist 属性（後に書かれた行が優先）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinguistOverrides {
+    rules: Vec<Rule>,
+}
+
+impl LinguistOverrides {
+    /// `dir/.gitattributes` を読み込む。存在しない・読めない場合は空。
+    pub fn load(dir: &Path) -> Self {
+        std::fs::read_to_string(dir.join(".gitattributes"))
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut fields = line.split_whitespace();
+                let pattern = fields.next()?.to_string();
+                let mut rule = Rule {
+                    pattern,
+                    language: None,
+                    generated: None,
+                };
+                for attr in fields {
+                    match attr {
+                        "linguist-generated" | "linguist-generated=true" => {
+                            rule.generated = Some(true)
+                        }
+                        "-linguist-generated"
+                        | "!linguist-generated"
+                        | "linguist-generated=false" => rule.generated = Some(false),
+                        _ => {
+                            if let Some(name) = attr.strip_prefix("linguist-language=") {
+                                rule.language = Some(name.to_string());
+                            }
+                        }
+                    }
+                }
+                (rule.language.is_some() || rule.generated.is_some()).then_some(rule)
+            })
+            .collect();
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// `linguist-language=` で指定された言語名
+    pub fn language_for<'a>(&'a self, path: &'a str) -> Option<&'a str> {
+        self.matching_rules(path)
+            .find_map(|rule| rule.language.as_deref())
+    }
+
+    /// `linguist-generated` が付いているか
+    pub fn is_generated(&self, path: &str) -> bool {
+        self.generated_attr(path).unwrap_or(false)
+    }
+
+    /// `linguist-generated` の指定（`-linguist-generated` なら `Some(false)`、指定なしは `None`）
+    pub fn generated_attr(&self, path: &str) -> Option<bool> {
+        self.matching_rules(path).find_map(|rule| rule.generated)
+    }
+
+    /// ハイライトに使うファイル名。生成ファイルはハイライトしないので `None`。
+    ///
+    /// 言語指定があれば、その言語の拡張子を付けたファイル名を返す
+    /// （tree-sitter / syntect の拡張子ベースの選択にそのまま乗せるため）。
+    pub fn highlight_filename<'a>(&self, path: &'a str) -> Option<Cow<'a, str>> {
+        if self.is_generated(path) {
+            return None;
+        }
+        let ext = self
+            .language_for(path)
+            .and_then(|language| syntax_for_file_with_override(path, Some(language)))
+            .and_then(|syntax| syntax.file_extensions.first());
+        Some(match ext {
+            Some(ext) => Cow::Owned(format!("{}.{}", path, ext)),
+            None => Cow::Borrowed(path),
+        })
+    }
+
+    /// 後勝ちなので末尾から走査する
+    fn matching_rules<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a Rule> + 'a {
+        self.rules
+            .iter()
+            .rev()
+            .filter(move |rule| pattern_matches(&rule.pattern, path))
+    }
+}
+
+/// gitattributes のパターン照合
+///
+/// `/` を含まないパターンは任意の階層のファイル名に、含むパターンはリポジトリルート
+/// からのパスに一致する。`*` / `?` は `/` を跨がず、`**` は跨ぐ。
+pub(crate) fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let path = path.trim_start_matches("./");
+    if let Some(anchored) = pattern.strip_prefix('/') {
+        return wildcard_match(anchored.as_bytes(), path.as_bytes());
+    }
+    if pattern.contains('/') {
+        return wildcard_match(pattern.as_bytes(), path.as_bytes());
+    }
+    let basename = path.rsplit('/').next().unwrap_or(path);
+    wildcard_match(pattern.as_bytes(), basename.as_bytes())
+}
+
+/// ワイルドカードを 1 文字ずつ読み、一致し得るテキスト位置の集合を更新していく
+///
+/// バックトラックしないため `*` が多いパターンでも O(パターン長 × パス長) で済む。
+fn wildcard_match(pattern: &[u8], text: &[u8]) -> bool {
+    // reachable[i]: ここまでのパターンが text[..i] に一致するか
+    let mut reachable = vec![false; text.len() + 1];
+    reachable[0] = true;
+    let mut next = vec![false; text.len() + 1];
+    let mut p = 0;
+    while p < pattern.len() {
+        next.fill(false);
+        match pattern[p] {
+            b'*' if pattern.get(p + 1) == Some(&b'*') => {
+                p += 1;
+                if pattern.get(p + 1) == Some(&b'/') {
+                    // `**/` は 0 個以上のディレクトリ（ディレクトリ境界の直後にのみ進める）
+                    p += 1;
+                    let mut seen = false;
+                    for i in 0..=text.len() {
+                        next[i] = reachable[i] || (seen && text[i - 1] == b'/');
+                        seen |= reachable[i];
+                    }
+                } else {
+                    // `**` は `/` を跨いで任意長
+                    for i in 0..=text.len() {
+                        next[i] = reachable[i] || (i > 0 && next[i - 1]);
+                    }
+                }
+            }
+            b'*' => {
+                for i in 0..=text.len() {
+                    next[i] = reachable[i] || (i > 0 && next[i - 1] && text[i - 1] != b'/');
+                }
+            }
+            b'?' => {
+                for i in 0..text.len() {
+                    next[i + 1] = reachable[i] && text[i] != b'/';
+                }
+            }
+            c => {
+                for i in 0..text.len() {
+                    next[i + 1] = reachable[i] && text[i] == c;
+                }
+            }
+        }
+        std::mem::swap(&mut reachable, &mut next);
+        if !reachable.contains(&true) {
+            return false;
+        }
+        p += 1;
+    }
+    reachable[text.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitattributes_language_override_maps_inc_to_php() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".gitattributes"),
+            "# comment\n*.inc linguist-language=PHP\nvendor/** linguist-generated\n*.min.js linguist-generated=true\nvendor/keep.js -linguist-generated\n* text=auto\n",
+        )
+        .unwrap();
+        let overrides = LinguistOverrides::load(dir.path());
+
+        assert_eq!(overrides.language_for("lib/config.inc"), Some("PHP"));
+        assert_eq!(overrides.language_for("src/main.rs"), None);
+        let syntax = syntax_for_file_with_override("lib/config.inc", Some("PHP")).unwrap();
+        assert_eq!(syntax.name, "PHP");
+        assert_eq!(
+            overrides.highlight_filename("lib/config.inc").as_deref(),
+            Some("lib/config.inc.php")
+        );
+        assert_eq!(
+            overrides.highlight_filename("src/main.rs").as_deref(),
+            Some("src/main.rs")
+        );
+
+        // 生成ファイルはハイライトしない（後勝ちで打ち消し可能）
+        assert!(overrides.is_generated("vendor/a/b.js"));
+        assert!(overrides.is_generated("assets/app.min.js"));
+        assert!(!overrides.is_generated("vendor/keep.js"));
+        assert!(overrides.highlight_filename("vendor/a/b.js").is_none());
+
+        assert!(LinguistOverrides::load(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("*.inc", "a/b/c.inc"));
+        assert!(pattern_matches("/docs/*.md", "docs/a.md"));
+        assert!(!pattern_matches("/docs/*.md", "x/docs/a.md"));
+        assert!(!pattern_matches("docs/*.md", "docs/sub/a.md"));
+        assert!(pattern_matches("docs/**/*.md", "docs/a.md"));
+        assert!(pattern_matches("docs/**/*.md", "docs/sub/deep/a.md"));
+        assert!(pattern_matches("file?.h", "include/file1.h"));
+        assert!(!pattern_matches("*.h", "main.hpp"));
+        assert!(!pattern_matches("docs/**/a.md", "docs/xa.md"));
+        assert!(pattern_matches("vendor/**", "vendor/a/b.js"));
+    }
+
+    #[test]
+    fn test_pattern_matches_many_wildcards_does_not_backtrack() {
+        // 再帰的なバックトラックでは指数時間になるパターン
+        let pattern = format!("{}b", "*a".repeat(30));
+        let path = "a".repeat(200);
+        assert!(!pattern_matches(&pattern, &path));
+        let pattern = format!("{}z", "**/".repeat(30));
+        let path = "a/".repeat(200);
+        assert!(!pattern_matches(&pattern, &path));
+        assert!(pattern_matches(&pattern, &format!("{}z", path)));
+    }
+}
@@ -9,6 +9,9 @@
 //! - Rust (.rs)
 //! - TypeScript (.ts, .tsx)
 //! - JavaScript (.js, .jsx)
+//!
+//! `.jsx` / `.tsx` additionally get JSX tag/attribute highlighting and
+//! tagged-template injections (e.g. `css\`...\``).
 //! - Go (.go)
 //! - Python (.py)
 //!
@@ -30,16 +33,20 @@
 //!
 //! User themes override bundled themes if they have the same name.
 
+pub mod background;
 pub mod highlighter;
 pub mod injection;
+pub mod linguist;
 pub mod parser_pool;
 pub mod themes;
 
+pub use background::{detect_terminal_background, TerminalBackground};
 pub use highlighter::{
     apply_line_highlights, collect_line_highlights, collect_line_highlights_with_injections,
     CstParseResult, Highlighter, LineHighlights,
 };
-pub use parser_pool::ParserPool;
+pub use linguist::LinguistOverrides;
+pub use parser_pool::{ParserPool, SharedTreeCache};
 pub use themes::ThemeStyleCache;
 
 use std::io::Cursor;
@@ -136,6 +143,29 @@ pub fn syntax_for_file(filename: &str) -> Option<&'static syntect::parsing::Synt
     syntax_set().find_syntax_by_extension(ext)
 }
 
+/// Get the SyntaxReference for a file, preferring an explicit language name.
+///
+/// `language` is a linguist language name from `.gitattributes`
+/// (`linguist-language=PHP`). It is looked up by syntax name (case-insensitive),
+/// then as a file extension token; when it does not resolve, the file extension
+/// is used as in [`syntax_for_file`].
+pub fn syntax_for_file_with_override(
+    filename: &str,
+    language: Option<&str>,
+) -> Option<&'static syntect::parsing::SyntaxReference> {
+    let by_language = language.and_then(|name| {
+        let set = syntax_set();
+        set.find_syntax_by_name(name)
+            .or_else(|| {
+                set.syntaxes()
+                    .iter()
+                    .find(|s| s.name.eq_ignore_ascii_case(name))
+            })
+            .or_else(|| set.find_syntax_by_token(name))
+    });
+    by_language.or_else(|| syntax_for_file(filename))
+}
+
 /// Get a theme by name with fallback to default themes.
 ///
 /// Theme matching is case-insensitive. Falls back to "base16-ocean.dark"
@@ -148,34 +178,67 @@ pub fn syntax_for_file(filename: &str) -> Option<&'static syntect::parsing::Synt
 /// # Returns
 /// A reference to the theme
 pub fn get_theme(name: &str) -> &'static syntect::highlighting::Theme {
-    let themes = &theme_set().themes;
-
-    // Try exact match first
-    if let Some(theme) = themes.get(name) {
+    if let Some((_, theme)) = find_theme(name) {
         return theme;
     }
 
-    // Try case-insensitive match
-    let name_lower = name.to_lowercase();
-    for (key, theme) in themes.iter() {
-        if key.to_lowercase() == name_lower {
-            return theme;
-        }
-    }
-
     // Fallback to default themes
+    let themes = &theme_set().themes;
     themes
         .get("base16-ocean.dark")
         .or_else(|| themes.values().next())
         .expect("syntect default themes should never be empty")
 }
 
+/// Look up a theme by exact name, then case-insensitively. No fallback.
+fn find_theme(name: &str) -> Option<(&'static str, &'static syntect::highlighting::Theme)> {
+    let themes = &theme_set().themes;
+
+    // Try exact match first
+    if let Some((key, theme)) = themes.get_key_value(name) {
+        return Some((key.as_str(), theme));
+    }
+
+    // Try case-insensitive match
+    let name_lower = name.to_lowercase();
+    themes
+        .iter()
+        .find(|(key, _)| key.to_lowercase() == name_lower)
+        .map(|(key, theme)| (key.as_str(), theme))
+}
+
+/// Pick the theme name for this session.
+///
+/// A `requested` name (the `--theme` flag) takes precedence over the
+/// `configured` one and is matched like [`get_theme`] (case-insensitive).
+/// An unknown requested name is reported as a warning and the configured
+/// theme is kept.
+pub fn resolve_theme_override(
+    configured: &str,
+    requested: Option<&str>,
+) -> (String, Option<String>) {
+    let Some(requested) = requested else {
+        return (configured.to_string(), None);
+    };
+    match find_theme(requested) {
+        Some((name, _)) => (name.to_string(), None),
+        None => (
+            configured.to_string(),
+            Some(format!(
+                "Unknown theme \"{}\"; using \"{}\" from config",
+                requested, configured
+            )),
+        ),
+    }
+}
+
 /// Highlight a code line and return a vector of InternedSpans.
 ///
 /// # Arguments
 /// * `code` - The code line to highlight
 /// * `highlighter` - A mutable reference to the HighlightLines instance
 /// * `interner` - A mutable reference to the string interner
+/// * `style_cache` - Memoizes syntect → ratatui style conversion across tokens
 ///
 /// # Returns
 /// A vector of `InternedSpan` with syntax highlighting applied.
@@ -184,6 +247,7 @@ pub fn highlight_code_line(
     code: &str,
     highlighter: &mut HighlightLines<'_>,
     interner: &mut Rodeo,
+    style_cache: &mut ThemeStyleCache,
 ) -> SpanVec {
     match highlighter.highlight_line(code, syntax_set()) {
         Ok(ranges) => ranges
@@ -192,7 +256,7 @@ pub fn highlight_code_line(
                 // Intern the text to avoid allocations for duplicate tokens
                 InternedSpan {
                     content: interner.get_or_intern(text),
-                    style: convert_syntect_style(&style),
+                    style: style_cache.syntect_style(&style),
                 }
             })
             .collect(),
@@ -416,6 +480,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_theme_override_takes_precedence() {
+        // No override: the configured theme is used
+        assert_eq!(
+            resolve_theme_override("base16-ocean.dark", None),
+            ("base16-ocean.dark".to_string(), None)
+        );
+        // The override wins and resolves case-insensitively to the canonical name
+        assert_eq!(
+            resolve_theme_override("base16-ocean.dark", Some("dracula")),
+            ("Dracula".to_string(), None)
+        );
+        assert_eq!(
+            resolve_theme_override("Dracula", Some("InspiredGitHub")),
+            ("InspiredGitHub".to_string(), None)
+        );
+        // Unknown names warn and fall back to the configured theme
+        let (theme, warning) = resolve_theme_override("Dracula", Some("no-such-theme"));
+        assert_eq!(theme, "Dracula");
+        assert!(warning.unwrap().contains("no-such-theme"));
+    }
+
     #[test]
     fn test_get_theme_case_insensitive() {
         // Theme names should match case-insensitively
@@ -467,8 +553,14 @@ mod tests {
         let theme = get_theme("base16-ocean.dark");
         let mut highlighter = HighlightLines::new(syntax, theme);
         let mut interner = Rodeo::default();
+        let mut style_cache = ThemeStyleCache::default();
 
-        let spans = highlight_code_line("let app = Self {", &mut highlighter, &mut interner);
+        let spans = highlight_code_line(
+            "let app = Self {",
+            &mut highlighter,
+            &mut interner,
+            &mut style_cache,
+        );
         assert!(!spans.is_empty());
 
         // Verify that the interner contains the expected tokens
@@ -484,10 +576,21 @@ mod tests {
         let theme = get_theme("base16-ocean.dark");
         let mut highlighter = HighlightLines::new(syntax, theme);
         let mut interner = Rodeo::default();
+        let mut style_cache = ThemeStyleCache::default();
 
         // Highlight two lines with the same keyword
-        let spans1 = highlight_code_line("let x = 1;", &mut highlighter, &mut interner);
-        let spans2 = highlight_code_line("let y = 2;", &mut highlighter, &mut interner);
+        let spans1 = highlight_code_line(
+            "let x = 1;",
+            &mut highlighter,
+            &mut interner,
+            &mut style_cache,
+        );
+        let spans2 = highlight_code_line(
+            "let y = 2;",
+            &mut highlighter,
+            &mut interner,
+            &mut style_cache,
+        );
 
         // Find "let" in both spans - they should have the same Spur
         let let_spur1 = spans1
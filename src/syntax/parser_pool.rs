@@ -5,10 +5,14 @@
 //!
 //! Also caches compiled queries, which are expensive to create (they require
 //! parsing the query string and building internal data structures).
+//!
+//! Optionally, a [`SharedTreeCache`] can be attached so that re-parsing the same
+//! file (e.g. on local-mode refreshes) reuses the previous `Tree` incrementally.
 
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use tree_sitter::{Parser, Query};
+use std::sync::{Arc, Mutex};
+use tree_sitter::{InputEdit, Parser, Point, Query, Tree};
 
 use crate::language::SupportedLanguage;
 
@@ -20,8 +24,34 @@ pub struct ParserPool {
     parsers: HashMap<SupportedLanguage, Parser>,
     /// Cached compiled queries for highlight queries
     queries: HashMap<SupportedLanguage, Query>,
+    /// Previous trees per file for incremental re-parsing (None = disabled)
+    tree_cache: Option<SharedTreeCache>,
+}
+
+/// Minimum fraction of the new source that must be shared (common prefix +
+/// common suffix) with the cached source for the old tree to be reused.
+const MIN_SHARED_RATIO: f64 = 0.5;
+
+/// Last parsed source and tree for a file.
+struct CachedTree {
+    lang: SupportedLanguage,
+    source: String,
+    tree: Tree,
+}
+
+/// Per-file cache of previously parsed trees.
+///
+/// Diffs do not carry exact byte edits, so the edit is approximated from the
+/// common prefix/suffix of the old and new source. When too little is shared
+/// the old tree is discarded and the file is parsed from scratch.
+#[derive(Default)]
+pub struct TreeCache {
+    entries: HashMap<String, CachedTree>,
 }
 
+/// Tree cache shared between the UI thread and background highlight tasks.
+pub type SharedTreeCache = Arc<Mutex<TreeCache>>;
+
 impl Default for ParserPool {
     fn default() -> Self {
         Self::new()
@@ -34,9 +64,60 @@ impl ParserPool {
         Self {
             parsers: HashMap::new(),
             queries: HashMap::new(),
+            tree_cache: None,
+        }
+    }
+
+    /// Create a pool that reuses previous trees from `tree_cache`.
+    pub fn with_tree_cache(tree_cache: SharedTreeCache) -> Self {
+        Self {
+            tree_cache: Some(tree_cache),
+            ..Self::new()
         }
     }
 
+    /// Parse `source` for the file at `path`, reusing the previous tree when possible.
+    ///
+    /// Returns the tree and whether a cached tree was reused. Without an attached
+    /// tree cache this is a plain full parse.
+    pub fn parse_file(
+        &mut self,
+        path: &str,
+        lang: SupportedLanguage,
+        source: &str,
+    ) -> Option<(Tree, bool)> {
+        let tree_cache = self.tree_cache.clone();
+        let old_tree = tree_cache.as_ref().and_then(|cache| {
+            let cache = cache.lock().ok()?;
+            let cached = cache.entries.get(path)?;
+            if cached.lang != lang {
+                return None;
+            }
+            let edit = approximate_edit(&cached.source, source)?;
+            let mut tree = cached.tree.clone();
+            tree.edit(&edit);
+            Some(tree)
+        });
+
+        let parser = self.get_or_create(lang.default_extension())?;
+        let reused = old_tree.is_some();
+        let tree = parser.parse(source, old_tree.as_ref())?;
+
+        if let Some(cache) = tree_cache {
+            if let Ok(mut cache) = cache.lock() {
+                cache.entries.insert(
+                    path.to_string(),
+                    CachedTree {
+                        lang,
+                        source: source.to_string(),
+                        tree: tree.clone(),
+                    },
+                );
+            }
+        }
+        Some((tree, reused))
+    }
+
     /// Get or create a compiled highlight query for the given language.
     ///
     /// Queries are cached to avoid recompilation overhead on each use.
@@ -77,10 +158,132 @@ impl ParserPool {
     }
 }
 
+/// Approximate the edit from `old` to `new` as a single replaced region between
+/// their common prefix and common suffix.
+///
+/// Returns `None` when the shared portion is too small for reuse to pay off.
+fn approximate_edit(old: &str, new: &str) -> Option<InputEdit> {
+    let (old_bytes, new_bytes) = (old.as_bytes(), new.as_bytes());
+    let prefix = old_bytes
+        .iter()
+        .zip(new_bytes)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let max_suffix = old_bytes.len().min(new_bytes.len()) - prefix;
+    let suffix = old_bytes
+        .iter()
+        .rev()
+        .zip(new_bytes.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let shared = (prefix + suffix) as f64;
+    if !new.is_empty() && shared < new.len() as f64 * MIN_SHARED_RATIO {
+        return None;
+    }
+
+    let old_end_byte = old_bytes.len() - suffix;
+    let new_end_byte = new_bytes.len() - suffix;
+    Some(InputEdit {
+        start_byte: prefix,
+        old_end_byte,
+        new_end_byte,
+        start_position: point_at(new_bytes, prefix),
+        old_end_position: point_at(old_bytes, old_end_byte),
+        new_end_position: point_at(new_bytes, new_end_byte),
+    })
+}
+
+/// Row/column of byte offset `byte` in `text`.
+fn point_at(text: &[u8], byte: usize) -> Point {
+    let before = &text[..byte];
+    let row = before.iter().filter(|&&b| b == b'\n').count();
+    let column = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(byte, |nl| byte - nl - 1);
+    Point { row, column }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_file_reuses_tree_for_identical_content() {
+        let cache = SharedTreeCache::default();
+        let mut pool = ParserPool::with_tree_cache(cache.clone());
+        let source = "fn main() {\n    let x = 1;\n}\n";
+
+        let (_, reused) = pool
+            .parse_file("src/main.rs", SupportedLanguage::Rust, source)
+            .unwrap();
+        assert!(!reused, "first parse has nothing to reuse");
+
+        // 別のプール（バックグラウンドタスク相当）でも共有キャッシュから再利用される
+        let mut pool = ParserPool::with_tree_cache(cache);
+        let (tree, reused) = pool
+            .parse_file("src/main.rs", SupportedLanguage::Rust, source)
+            .unwrap();
+        assert!(reused);
+        assert!(!tree.root_node().has_error());
+    }
+
+    #[test]
+    fn test_parse_file_reuses_tree_for_small_edit() {
+        let mut pool = ParserPool::with_tree_cache(SharedTreeCache::default());
+        let old = "fn main() {\n    let x = 1;\n    let y = 2;\n}\n";
+        let new = "fn main() {\n    let x = 10;\n    let y = 2;\n}\n";
+        pool.parse_file("a.rs", SupportedLanguage::Rust, old)
+            .unwrap();
+
+        let (tree, reused) = pool
+            .parse_file("a.rs", SupportedLanguage::Rust, new)
+            .unwrap();
+        assert!(reused);
+        // 再利用したツリーがフルパースと同じ構造になること
+        let mut fresh = ParserPool::new();
+        let (expected, _) = fresh
+            .parse_file("a.rs", SupportedLanguage::Rust, new)
+            .unwrap();
+        assert_eq!(tree.root_node().to_sexp(), expected.root_node().to_sexp());
+    }
+
+    #[test]
+    fn test_parse_file_discards_tree_when_content_diverges() {
+        let mut pool = ParserPool::with_tree_cache(SharedTreeCache::default());
+        pool.parse_file("a.rs", SupportedLanguage::Rust, "fn a() {}\n")
+            .unwrap();
+
+        let (_, reused) = pool
+            .parse_file(
+                "a.rs",
+                SupportedLanguage::Rust,
+                "struct Completely { different: u8 }\n",
+            )
+            .unwrap();
+        assert!(!reused);
+
+        // 別ファイルのツリーは使わない
+        let (_, reused) = pool
+            .parse_file("b.rs", SupportedLanguage::Rust, "fn a() {}\n")
+            .unwrap();
+        assert!(!reused);
+    }
+
+    #[test]
+    fn test_parse_file_without_tree_cache_never_reuses() {
+        let mut pool = ParserPool::new();
+        let source = "fn main() {}\n";
+        pool.parse_file("a.rs", SupportedLanguage::Rust, source)
+            .unwrap();
+        let (_, reused) = pool
+            .parse_file("a.rs", SupportedLanguage::Rust, source)
+            .unwrap();
+        assert!(!reused);
+    }
+
     #[test]
     fn test_parser_pool_rust() {
         let mut pool = ParserPool::new();
@@ -188,6 +391,13 @@ mod tests {
         assert!(pool.get_or_create("swift").is_some());
     }
 
+    #[test]
+    fn test_parser_pool_kotlin() {
+        let mut pool = ParserPool::new();
+        assert!(pool.get_or_create("kt").is_some());
+        assert!(pool.get_or_create("kts").is_some());
+    }
+
     #[test]
     fn test_parser_pool_haskell() {
         let mut pool = ParserPool::new();
@@ -252,6 +462,7 @@ mod tests {
         assert!(ParserPool::supports_extension("sh"));
         assert!(ParserPool::supports_extension("php"));
         assert!(ParserPool::supports_extension("swift"));
+        assert!(ParserPool::supports_extension("kt"));
         assert!(ParserPool::supports_extension("hs"));
 
         // Phase 3: Svelte is now supported
@@ -104,10 +104,16 @@ static CAPTURE_TO_SCOPES: phf::Map<&'static str, &'static [&'static str]> = phf_
 
 /// Cache of styles for each capture name, pre-computed from a theme.
 ///
-/// This avoids repeated scope lookups during highlighting.
-#[derive(Clone)]
+/// This avoids repeated scope lookups during highlighting. It also memoizes
+/// syntect token styles converted to ratatui styles (see [`Self::syntect_style`]).
+/// `Default` yields an empty capture map, which is enough for the syntect path.
+#[derive(Clone, Default)]
 pub struct ThemeStyleCache {
     cache: HashMap<&'static str, Style>,
+    /// Converted syntect styles keyed by foreground RGBA + font style bits.
+    syntect_styles: HashMap<(u32, u8), Style>,
+    /// Number of `syntect_style` calls served from `syntect_styles`.
+    syntect_hits: usize,
 }
 
 impl ThemeStyleCache {
@@ -123,7 +129,10 @@ impl ThemeStyleCache {
             }
         }
 
-        Self { cache }
+        Self {
+            cache,
+            ..Self::default()
+        }
     }
 
     /// Get the style for a capture name.
@@ -137,6 +146,31 @@ impl ThemeStyleCache {
             .unwrap_or_else(|| style_for_capture(capture))
     }
 
+    /// Convert a syntect token style, reusing the result for identical styles.
+    ///
+    /// Only the foreground color and font style affect the converted style
+    /// (background is ignored by [`super::convert_syntect_style`]), so they form the key.
+    #[inline]
+    pub fn syntect_style(&mut self, style: &syntect::highlighting::Style) -> Style {
+        let fg = style.foreground;
+        let key = (
+            u32::from_be_bytes([fg.r, fg.g, fg.b, fg.a]),
+            style.font_style.bits(),
+        );
+        if let Some(&cached) = self.syntect_styles.get(&key) {
+            self.syntect_hits += 1;
+            return cached;
+        }
+        let converted = super::convert_syntect_style(style);
+        self.syntect_styles.insert(key, converted);
+        converted
+    }
+
+    /// Number of [`Self::syntect_style`] calls answered from the cache.
+    pub fn syntect_hits(&self) -> usize {
+        self.syntect_hits
+    }
+
     /// Apply markdown rich display overrides to this cache.
     ///
     /// Overrides styles for markdown-specific captures (headings, emphasis, etc.)
@@ -456,6 +490,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_syntect_style_conversion_is_memoized() {
+        use syntect::highlighting::{Color as SyntectColor, Style as SyntectStyle};
+
+        let token = |r: u8, font_style: FontStyle| SyntectStyle {
+            foreground: SyntectColor {
+                r,
+                g: 10,
+                b: 20,
+                a: 0xFF,
+            },
+            background: SyntectColor::BLACK,
+            font_style,
+        };
+        let mut cache = ThemeStyleCache::default();
+
+        let first = cache.syntect_style(&token(1, FontStyle::BOLD));
+        assert_eq!(cache.syntect_hits(), 0);
+        for _ in 0..100 {
+            assert_eq!(cache.syntect_style(&token(1, FontStyle::BOLD)), first);
+        }
+        assert_eq!(cache.syntect_hits(), 100);
+        assert_eq!(
+            first,
+            crate::syntax::convert_syntect_style(&token(1, FontStyle::BOLD))
+        );
+
+        // 色違い・フォント違いは別エントリになる（衝突しない）
+        let other_color = cache.syntect_style(&token(2, FontStyle::BOLD));
+        let other_font = cache.syntect_style(&token(1, FontStyle::ITALIC));
+        assert_eq!(cache.syntect_hits(), 100);
+        assert_eq!(other_color.fg, Some(Color::Rgb(2, 10, 20)));
+        assert!(other_font.add_modifier.contains(Modifier::ITALIC));
+        assert!(!other_font.add_modifier.contains(Modifier::BOLD));
+    }
+
     #[test]
     fn test_theme_style_cache_unknown_capture_fallback() {
         let theme = get_theme("Dracula");
@@ -11,8 +11,10 @@ use ratatui::{
 
 use super::common::{build_pr_info, truncate_with_width};
 use crate::ai::{RallyState, ReviewAction, RevieweeStatus};
-use crate::app::{AiRallyState, App, LogEntry, LogEventType, PauseState};
+use crate::app::{AiRallyState, App, LogEntry, LogEventType, PauseState, SPINNER_FRAMES};
 use crate::config::KeybindingsConfig;
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     let pr_info = build_pr_info(app);
@@ -31,7 +33,8 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         .split(frame.area());
 
     let kb = &app.config.keybindings;
-    render_header(frame, chunks[0], rally_state, &pr_info);
+    let stall_after = Duration::from_secs(app.config.ai.stall_warning_secs);
+    render_header(frame, chunks[0], rally_state, &pr_info, stall_after);
     render_main_content(frame, chunks[1], rally_state, kb);
     render_status_bar(frame, chunks[2], rally_state, kb);
 
@@ -40,7 +43,44 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     }
 }
 
-fn render_header(frame: &mut Frame, area: Rect, state: &AiRallyState, pr_info: &str) {
+/// エージェント呼び出しの経過時間（`42s` / `2m05s` / `1h02m`）
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// 実行中のエージェント呼び出しのスピナーと経過時間。
+/// `stall_after` を超えたら応答が止まっている可能性を警告色で示す。
+fn agent_activity_spans(state: &AiRallyState, stall_after: Duration) -> Vec<Span<'static>> {
+    let Some(started) = state.agent_started_at.filter(|_| state.is_agent_running()) else {
+        return Vec::new();
+    };
+    let elapsed = started.elapsed();
+    let spinner = if state.pause_state == PauseState::Paused {
+        "⏸"
+    } else {
+        SPINNER_FRAMES[state.spinner_frame % SPINNER_FRAMES.len()]
+    };
+    let stalled = elapsed >= stall_after;
+    let mut text = format!("  {} {}", spinner, format_elapsed(elapsed));
+    if stalled {
+        text.push_str(" (no response yet)");
+    }
+    let color = if stalled { Color::Red } else { Color::DarkGray };
+    vec![Span::styled(text, Style::default().fg(color))]
+}
+
+fn render_header(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AiRallyState,
+    pr_info: &str,
+    stall_after: Duration,
+) {
     let base_state_text = match state.state {
         RallyState::Initializing => "Initializing...",
         RallyState::ReviewerReviewing => "Reviewer reviewing...",
@@ -90,17 +130,20 @@ fn render_header(frame: &mut Frame, area: Rect, state: &AiRallyState, pr_info: &
         )
     };
 
+    let mut status_spans = vec![
+        Span::styled("Status: ", Style::default().fg(Color::Gray)),
+        Span::styled(
+            state_text,
+            Style::default()
+                .fg(state_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    status_spans.extend(agent_activity_spans(state, stall_after));
+
     let header = Paragraph::new(vec![
         Line::from(Span::styled(pr_info, Style::default().fg(Color::White))),
-        Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Color::Gray)),
-            Span::styled(
-                state_text,
-                Style::default()
-                    .fg(state_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-        ]),
+        Line::from(status_spans),
     ])
     .block(
         Block::default()
@@ -130,11 +173,19 @@ fn render_main_content(
             | RallyState::WaitingForPostConfirmation
     );
 
+    // 保留コメントがあれば履歴とログの間に一覧を出す（最大 8 件）
+    let pending_height = if state.pending_comments.is_empty() {
+        0
+    } else {
+        (state.pending_comments.len() as u16 + 2).min(10)
+    };
+
     let chunks = if is_waiting {
         Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Percentage(35),
+                Constraint::Length(pending_height),
                 Constraint::Length(6),
                 Constraint::Min(10),
             ])
@@ -142,20 +193,88 @@ fn render_main_content(
     } else {
         Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .constraints([
+                Constraint::Percentage(50),
+                Constraint::Length(pending_height),
+                Constraint::Min(5),
+            ])
             .split(area)
     };
 
     render_history(frame, chunks[0], state);
+    if pending_height > 0 {
+        render_pending_comments(frame, chunks[1], state, kb);
+    }
 
     if is_waiting {
-        render_waiting_prompt(frame, chunks[1], state, kb);
-        render_logs(frame, chunks[2], state);
+        render_waiting_prompt(frame, chunks[2], state, kb);
+        render_logs(frame, chunks[3], state);
     } else {
-        render_logs(frame, chunks[1], state);
+        render_logs(frame, chunks[2], state);
     }
 }
 
+/// dry run で保留中のインラインコメント一覧
+fn render_pending_comments(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AiRallyState,
+    kb: &KeybindingsConfig,
+) {
+    let visible_height = area.height.saturating_sub(2) as usize;
+    let selected = state
+        .selected_pending_comment
+        .min(state.pending_comments.len().saturating_sub(1));
+    let offset = (selected + 1).saturating_sub(visible_height);
+    let body_width = area.width.saturating_sub(4) as usize;
+
+    let items: Vec<ListItem> = state
+        .pending_comments
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible_height)
+        .map(|(i, comment)| {
+            let is_selected = state.pending_comments_focused && i == selected;
+            let location = format!("{}:{} ", comment.path, comment.line);
+            let first_line = comment.body.lines().next().unwrap_or_default();
+            let body = truncate_with_width(first_line, body_width.saturating_sub(location.width()));
+            let mut line = Line::from(vec![
+                Span::styled(location, Style::default().fg(Color::Cyan)),
+                Span::raw(body),
+            ]);
+            if is_selected {
+                line = line.style(
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                );
+            }
+            ListItem::new(line)
+        })
+        .collect();
+
+    let border_color = if state.pending_comments_focused {
+        Color::Yellow
+    } else {
+        Color::DarkGray
+    };
+    let title = format!(
+        " Pending comments ({}) - {}: post all | {}: drop | {}: focus ",
+        state.pending_comments.len(),
+        kb.commit_rally.display(),
+        kb.rally_drop_comment.display(),
+        kb.tab_switch.display()
+    );
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border_color)),
+    );
+    frame.render_widget(list, area);
+}
+
 fn render_config_warning(
     frame: &mut Frame,
     area: Rect,
@@ -634,6 +753,7 @@ fn render_status_bar(frame: &mut Frame, area: Rect, state: &AiRallyState, kb: &K
     let bg = kb.rally_background.display();
     let pause = kb.rally_pause.display();
     let retry = kb.retry.display();
+    let rerun = kb.retry_iteration.display();
 
     let help_text = if state.pending_config_warning.is_some() {
         format!("{yes}: Accept and continue | {no}/{quit}: Cancel and return")
@@ -656,10 +776,11 @@ fn render_status_bar(frame: &mut Frame, area: Rect, state: &AiRallyState, kb: &K
             RallyState::WaitingForPostConfirmation => {
                 format!("{yes}: Post to PR | {no}: Skip | {mv}: select | {enter}: detail | {quit}: Abort")
             }
-            RallyState::Completed => format!("{mv}: select | {enter}: detail | {bg}: Background | {quit}: Close"),
-            RallyState::Aborted => format!("{mv}: select | {enter}: detail | {bg}: Background | {quit}: Close"),
+            RallyState::Completed | RallyState::Aborted => format!(
+                "{rerun}: Re-run step | {mv}: select | {enter}: detail | {bg}: Background | {quit}: Close"
+            ),
             RallyState::Error => {
-                format!("{retry}: Retry | {mv}: select | {enter}: detail | {bg}: Background | {quit}: Close")
+                format!("{retry}: Retry | {rerun}: Re-run step | {mv}: select | {enter}: detail | {bg}: Background | {quit}: Close")
             }
             _ => format!("{pause}: Pause | {mv}: select | {enter}: detail | {bg}: Background | {quit}: Abort"),
         }
@@ -708,6 +829,12 @@ mod tests {
             last_visible_log_height: 0,
             pending_config_warning: None,
             pause_state: PauseState::Running,
+            agent_started_at: None,
+            spinner_frame: 0,
+            pending_comments: Vec::new(),
+            selected_pending_comment: 0,
+            pending_comments_focused: false,
+            step_inputs: Vec::new(),
         }
     }
 
@@ -905,4 +1032,47 @@ mod tests {
             "header should not show [Review Only] badge when review_only is false"
         );
     }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(0)), "0s");
+        assert_eq!(format_elapsed(Duration::from_secs(59)), "59s");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2m05s");
+        assert_eq!(format_elapsed(Duration::from_secs(3720)), "1h02m");
+    }
+
+    #[test]
+    fn test_spinner_advances_only_while_agent_runs() {
+        let mut state = make_rally_state();
+        state.tick();
+        assert_eq!(state.spinner_frame, 0);
+
+        state.set_state(RallyState::ReviewerReviewing);
+        assert!(state.agent_started_at.is_some());
+        state.tick();
+        state.tick();
+        assert_eq!(state.spinner_frame, 2);
+
+        state.pause_state = PauseState::Paused;
+        state.tick();
+        assert_eq!(state.spinner_frame, 2);
+
+        state.set_state(RallyState::WaitingForPermission);
+        assert!(state.agent_started_at.is_none());
+    }
+
+    #[test]
+    fn test_stalled_agent_shows_warning() {
+        let mut state = make_rally_state();
+        state.set_state(RallyState::RevieweeFix);
+        let spans = agent_activity_spans(&state, Duration::from_secs(120));
+        assert_eq!(spans[0].style.fg, Some(Color::DarkGray));
+        assert!(spans[0].content.ends_with("0s"));
+
+        state.agent_started_at = std::time::Instant::now().checked_sub(Duration::from_secs(200));
+        let spans = agent_activity_spans(&state, Duration::from_secs(120));
+        assert_eq!(spans[0].style.fg, Some(Color::Red));
+        assert!(spans[0].content.contains("3m2"));
+        assert!(spans[0].content.ends_with("(no response yet)"));
+    }
 }
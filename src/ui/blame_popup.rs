@@ -0,0 +1,95 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, BlamePopupState, LoadState};
+use crate::blame::BlameLine;
+
+/// `show_blame`: カーソル行周辺の `git blame` を author / commit / 日付付きで表示する
+pub fn render(frame: &mut Frame, app: &App, popup: &BlamePopupState) {
+    let area = frame.area();
+    let width = (area.width * 90 / 100).max(40).min(area.width);
+    let line_count = match &popup.lines {
+        LoadState::Loaded(lines) => lines.len(),
+        _ => 1,
+    };
+    let height = (line_count as u16 + 2).max(3).min(area.height);
+    let popup_area = super::centered_rect(width, height, area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Blame: {}:{} ", popup.filename, popup.line))
+        .title_bottom(" Esc: close ");
+
+    let lines = match &popup.lines {
+        LoadState::Loaded(lines) if lines.is_empty() => vec![Line::from(Span::styled(
+            "(no blame output)",
+            Style::default().fg(Color::DarkGray),
+        ))],
+        LoadState::Loaded(lines) => {
+            let author_width = lines
+                .iter()
+                .map(|l| l.author.chars().count())
+                .max()
+                .unwrap_or(0)
+                .min(20);
+            let tab = " ".repeat(app.config.diff.tab_width as usize);
+            lines
+                .iter()
+                .map(|line| blame_line(line, popup.line, author_width, &tab))
+                .collect()
+        }
+        LoadState::Error(e) => vec![Line::from(Span::styled(
+            e.clone(),
+            Style::default().fg(Color::Red),
+        ))],
+        _ => vec![Line::from(Span::styled(
+            format!("{} Running git blame... (Esc: cancel)", app.spinner_char()),
+            Style::default().fg(Color::Yellow),
+        ))],
+    };
+
+    frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn blame_line(line: &BlameLine, cursor: u32, author_width: usize, tab: &str) -> Line<'static> {
+    let meta_style = Style::default().fg(Color::DarkGray);
+    let (commit, author, date) = if line.is_uncommitted() {
+        (
+            Span::styled("--------", Style::default().fg(Color::Yellow)),
+            "Not committed".to_string(),
+            String::new(),
+        )
+    } else {
+        (
+            Span::styled(
+                line.short_commit().to_string(),
+                Style::default().fg(Color::Yellow),
+            ),
+            line.author.chars().take(author_width).collect(),
+            line.date(),
+        )
+    };
+    let result = Line::from(vec![
+        commit,
+        Span::raw(" "),
+        Span::styled(
+            format!("{:<w$}", author, w = author_width),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(format!(" {:<10} ", date), meta_style),
+        Span::styled(format!("{:>5} ", line.line_number), meta_style),
+        Span::raw(line.content.replace('\t', tab)),
+    ]);
+    if line.line_number == cursor {
+        result.style(Style::default().add_modifier(Modifier::REVERSED))
+    } else {
+        result
+    }
+}
@@ -0,0 +1,271 @@
+//! コメント本文の描画
+//!
+//! フェンスコードブロック（```` ```lang ```` / `~~~lang`）はフェンスの言語タグで
+//! シンタックスハイライトし、それ以外の行はプレーンテキストのまま折り返す。
+//! 言語タグがない、または対応する構文が見つからないブロックもプレーンのまま表示する。
+
+use lasso::Rodeo;
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use syntect::easy::HighlightLines;
+
+use super::common::wrap_text;
+use crate::syntax::{
+    get_theme, highlight_code_line, syntax_for_file_with_override, ThemeStyleCache,
+};
+
+/// フェンスコードブロック 1 つ分（フェンス行を除いた中身）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FencedBlock<'a> {
+    /// 開始フェンスの情報文字列の先頭語（```` ```rust title ```` なら `rust`）
+    pub lang: Option<&'a str>,
+    pub lines: Vec<&'a str>,
+}
+
+/// コメント本文を行単位で分けたもの
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BodySegment<'a> {
+    Text(&'a str),
+    /// 開始・終了のフェンス行
+    Fence(&'a str),
+    Code(FencedBlock<'a>),
+}
+
+/// 開始フェンスなら（フェンス文字, 長さ, 言語タグ）を返す
+fn opening_fence(line: &str) -> Option<(char, usize, Option<&str>)> {
+    let trimmed = line.trim_start();
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = trimmed.chars().take_while(|&c| c == marker).count();
+    if len < 3 {
+        return None;
+    }
+    let info = trimmed[len..].trim();
+    // バッククォートのフェンスは情報文字列にバッククォートを含められない
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+    Some((marker, len, info.split_whitespace().next()))
+}
+
+fn is_closing_fence(line: &str, marker: char, len: usize) -> bool {
+    let trimmed = line.trim();
+    let run = trimmed.chars().take_while(|&c| c == marker).count();
+    run >= len && run == trimmed.chars().count()
+}
+
+/// 本文をテキスト行・フェンス行・コードブロックに分ける。
+/// 閉じられていないフェンスは本文の末尾までをコードとして扱う。
+pub(crate) fn split_comment_body(body: &str) -> Vec<BodySegment<'_>> {
+    let mut segments = Vec::new();
+    let mut lines = body.lines();
+    while let Some(line) = lines.next() {
+        let Some((marker, len, lang)) = opening_fence(line) else {
+            segments.push(BodySegment::Text(line));
+            continue;
+        };
+        segments.push(BodySegment::Fence(line));
+        let mut block = FencedBlock {
+            lang,
+            lines: Vec::new(),
+        };
+        let mut closing = None;
+        for line in lines.by_ref() {
+            if is_closing_fence(line, marker, len) {
+                closing = Some(line);
+                break;
+            }
+            block.lines.push(line);
+        }
+        segments.push(BodySegment::Code(block));
+        segments.extend(closing.map(BodySegment::Fence));
+    }
+    segments
+}
+
+/// コードブロックを言語タグの構文でハイライトする（構文が見つからなければ None）
+pub(crate) fn highlight_fenced_block(
+    block: &FencedBlock<'_>,
+    theme_name: &str,
+) -> Option<Vec<Vec<Span<'static>>>> {
+    let syntax = syntax_for_file_with_override("", Some(block.lang?))?;
+    let theme = get_theme(theme_name);
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut interner = Rodeo::default();
+    let mut style_cache = ThemeStyleCache::new(theme);
+    Some(
+        block
+            .lines
+            .iter()
+            .map(|line| {
+                highlight_code_line(line, &mut highlighter, &mut interner, &mut style_cache)
+                    .into_iter()
+                    .map(|span| {
+                        Span::styled(interner.resolve(&span.content).to_string(), span.style)
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// コメント本文を `indent` 付きの行に変換する。
+/// テキスト行は `width` で折り返し、コード行はハイライトのみで折り返さない。
+pub(crate) fn comment_body_lines(
+    body: &str,
+    width: usize,
+    indent: &'static str,
+    theme_name: &str,
+) -> Vec<Line<'static>> {
+    let fence_style = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    for segment in split_comment_body(body) {
+        match segment {
+            BodySegment::Text(text) => {
+                lines.extend(
+                    wrap_text(text, width)
+                        .into_iter()
+                        .map(|wrapped| Line::from(vec![Span::raw(indent), Span::raw(wrapped)])),
+                );
+            }
+            BodySegment::Fence(fence) => {
+                lines.push(Line::from(vec![
+                    Span::raw(indent),
+                    Span::styled(fence.to_string(), fence_style),
+                ]));
+            }
+            BodySegment::Code(block) => match highlight_fenced_block(&block, theme_name) {
+                Some(highlighted) => {
+                    lines.extend(highlighted.into_iter().map(|spans| {
+                        Line::from(
+                            std::iter::once(Span::raw(indent))
+                                .chain(spans)
+                                .collect::<Vec<_>>(),
+                        )
+                    }));
+                }
+                None => {
+                    lines.extend(block.lines.iter().map(|code| {
+                        Line::from(vec![Span::raw(indent), Span::raw(code.to_string())])
+                    }));
+                }
+            },
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = "\
+Consider this instead:
+```rust
+fn main() {
+    println!(\"hi\");
+}
+```
+and maybe
+~~~
+plain
+~~~";
+
+    fn blocks(body: &str) -> Vec<FencedBlock<'_>> {
+        split_comment_body(body)
+            .into_iter()
+            .filter_map(|segment| match segment {
+                BodySegment::Code(block) => Some(block),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_comment_body_extracts_fenced_blocks() {
+        let blocks = blocks(BODY);
+        assert_eq!(
+            blocks,
+            vec![
+                FencedBlock {
+                    lang: Some("rust"),
+                    lines: vec!["fn main() {", "    println!(\"hi\");", "}"],
+                },
+                FencedBlock {
+                    lang: None,
+                    lines: vec!["plain"],
+                },
+            ]
+        );
+
+        let segments = split_comment_body(BODY);
+        assert_eq!(segments[0], BodySegment::Text("Consider this instead:"));
+        assert_eq!(segments[1], BodySegment::Fence("```rust"));
+        assert_eq!(segments[3], BodySegment::Fence("```"));
+        assert_eq!(segments[4], BodySegment::Text("and maybe"));
+    }
+
+    #[test]
+    fn test_split_comment_body_fence_edge_cases() {
+        // 短いフェンスでは閉じない / 閉じられていなければ末尾までコード
+        let body = "````suggestion title\nlet a = 1;\n```\n````\nafter\n```py\nx = 1";
+        assert_eq!(
+            blocks(body),
+            vec![
+                FencedBlock {
+                    lang: Some("suggestion"),
+                    lines: vec!["let a = 1;", "```"],
+                },
+                FencedBlock {
+                    lang: Some("py"),
+                    lines: vec!["x = 1"],
+                },
+            ]
+        );
+        // 情報文字列にバッククォートを含むものはインラインコード
+        assert!(blocks("```a``` inline").is_empty());
+    }
+
+    #[test]
+    fn test_highlight_fenced_block_uses_language_tag() {
+        let rust = FencedBlock {
+            lang: Some("rust"),
+            lines: vec!["fn main() {}"],
+        };
+        let highlighted = highlight_fenced_block(&rust, "base16-ocean.dark").unwrap();
+        assert_eq!(highlighted.len(), 1);
+        let text: String = highlighted[0].iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "fn main() {}");
+        assert!(
+            highlighted[0].len() > 1 && highlighted[0].iter().any(|s| s.style.fg.is_some()),
+            "code should be split into styled tokens: {:?}",
+            highlighted[0]
+        );
+
+        let unknown = FencedBlock {
+            lang: Some("no-such-language"),
+            lines: vec!["x"],
+        };
+        assert!(highlight_fenced_block(&unknown, "base16-ocean.dark").is_none());
+        let untagged = FencedBlock {
+            lang: None,
+            lines: vec!["x"],
+        };
+        assert!(highlight_fenced_block(&untagged, "base16-ocean.dark").is_none());
+    }
+
+    #[test]
+    fn test_comment_body_lines_keeps_text_plain() {
+        let lines = comment_body_lines(BODY, 80, "    ", "base16-ocean.dark");
+        assert_eq!(lines.len(), 10);
+        let text =
+            |line: &Line<'_>| -> String { line.spans.iter().map(|s| s.content.as_ref()).collect() };
+        assert_eq!(text(&lines[0]), "    Consider this instead:");
+        assert!(lines[0].spans.iter().all(|s| s.style == Style::default()));
+        assert_eq!(text(&lines[2]), "    fn main() {");
+        assert!(lines[2].spans.iter().any(|s| s.style.fg.is_some()));
+        assert_eq!(text(&lines[8]), "    plain");
+        assert!(lines[8].spans.iter().all(|s| s.style == Style::default()));
+    }
+}
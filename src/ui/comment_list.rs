@@ -1,12 +1,14 @@
-use super::common::{render_rally_status_bar, wrap_text};
-use crate::app::{App, CommentTab};
+use super::comment_body::comment_body_lines;
+use super::common::render_rally_status_bar;
+use crate::app::{App, CommentTab, ReactionPickerState};
+use crate::github::{ReactionKind, Reactions};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Margin},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{
-        Block, Borders, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
-        ScrollbarState, Wrap,
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Wrap,
     },
     Frame,
 };
@@ -58,6 +60,60 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     let help_text = super::footer::footer_hint_back(&app.config.keybindings);
     let footer = Paragraph::new(help_text).block(Block::default().borders(Borders::ALL));
     frame.render_widget(footer, chunks[footer_chunk_idx]);
+
+    if let Some(ref picker) = app.reaction_picker {
+        render_reaction_picker(frame, app, picker);
+    }
+}
+
+/// `add_reaction`: 付ける絵文字を選ぶポップアップ
+fn render_reaction_picker(frame: &mut Frame, app: &App, picker: &ReactionPickerState) {
+    let popup_area = super::centered_rect(44.min(frame.area().width), 4, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let spans: Vec<Span> = ReactionKind::ALL
+        .iter()
+        .enumerate()
+        .flat_map(|(i, kind)| {
+            let style = if i == picker.selected {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let own = app
+                .cmt
+                .own_reactions
+                .contains_key(&(picker.comment_id, *kind));
+            let marker = if own { "*" } else { " " };
+            [
+                Span::raw(" "),
+                Span::styled(format!("{}{}", kind.emoji(), marker), style),
+            ]
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" React ")
+        .title_bottom(" 1-8/Enter: toggle | Esc: cancel ");
+    frame.render_widget(
+        Paragraph::new(vec![Line::from(spans), Line::from("")]).block(block),
+        popup_area,
+    );
+}
+
+/// コメント本文の下に出すリアクション件数の行（リアクションがなければ None）
+fn reactions_line(reactions: &Reactions, indent: &'static str) -> Option<Line<'static>> {
+    if reactions.is_empty() {
+        return None;
+    }
+    Some(Line::from(vec![
+        Span::raw(indent),
+        Span::styled(reactions.summary(), Style::default().fg(Color::DarkGray)),
+    ]))
 }
 
 fn render_local_comment_list(frame: &mut Frame, app: &mut App) {
@@ -271,6 +327,35 @@ fn render_tab_header(frame: &mut Frame, app: &App, area: ratatui::layout::Rect)
     frame.render_widget(header, area);
 }
 
+/// スレッドの resolved 状態バッジ
+///
+/// local mode はローカルの resolved メタデータ、PR mode は GraphQL で取得した
+/// レビュースレッドの状態を使う。PR mode ではスレッド未取得なら何も表示しない。
+fn resolution_badge(app: &App, comment_id: u64, locally_resolved: bool) -> Span<'static> {
+    let resolved = if app.is_local_mode() {
+        locally_resolved.then_some(true)
+    } else {
+        app.review_thread_state(comment_id).map(|t| t.is_resolved)
+    };
+    match resolved {
+        Some(true) => Span::styled(" [resolved]", Style::default().fg(Color::DarkGray)),
+        Some(false) => Span::styled(" [open]", Style::default().fg(Color::Yellow)),
+        None => Span::raw(""),
+    }
+}
+
+/// 未送信（pending）コメントのバッジ
+fn pending_badge(pending: bool) -> Span<'static> {
+    if pending {
+        Span::styled(
+            " [pending (not submitted)]",
+            Style::default().fg(Color::Yellow),
+        )
+    } else {
+        Span::raw("")
+    }
+}
+
 fn render_review_comments(frame: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
     if app.cmt.expanded_thread.is_some() {
         render_expanded_thread(frame, app, area);
@@ -280,8 +365,9 @@ fn render_review_comments(frame: &mut Frame, app: &mut App, area: ratatui::layou
     use crate::app::CommentThread;
     use std::collections::HashSet;
 
-    // Resolved-state badges only apply in local mode; ignore any stale meta
-    // that might persist from a prior mode switch.
+    // Local resolved meta only applies in local mode; ignore any stale meta
+    // that might persist from a prior mode switch. PR mode uses the GraphQL
+    // review threads instead (see `resolution_badge`).
     let resolved_ids: HashSet<u64> = if app.is_local_mode() {
         app.cmt
             .local_comment_meta
@@ -328,6 +414,7 @@ fn render_review_comments(frame: &mut Frame, app: &mut App, area: ratatui::layou
             let prefix = if is_selected { "> " } else { "  " };
             let line_info = comment.line.map(|l| format!(":{}", l)).unwrap_or_default();
             let resolved = resolved_ids.contains(&comment.id);
+            let pending = app.is_pending_comment(comment.id);
 
             let reply_count = thread.replies.len();
             let reply_info = if reply_count > 0 {
@@ -346,16 +433,8 @@ fn render_review_comments(frame: &mut Frame, app: &mut App, area: ratatui::layou
                     format!("@{}", comment.user.login),
                     Style::default().fg(Color::Cyan),
                 ),
-                if resolved {
-                    Span::raw(" ")
-                } else {
-                    Span::raw("")
-                },
-                if resolved {
-                    Span::styled("[resolved]", Style::default().fg(Color::DarkGray))
-                } else {
-                    Span::raw("")
-                },
+                resolution_badge(app, comment.id, resolved),
+                pending_badge(pending),
                 Span::raw(" on "),
                 Span::styled(
                     format!("{}{}", comment.path, line_info),
@@ -365,12 +444,13 @@ fn render_review_comments(frame: &mut Frame, app: &mut App, area: ratatui::layou
             ]);
 
             let mut lines = vec![header_line];
-            for body_line in comment.body.lines() {
-                let wrapped = wrap_text(body_line, body_width);
-                for wrapped_line in wrapped {
-                    lines.push(Line::from(vec![Span::raw("    "), Span::raw(wrapped_line)]));
-                }
-            }
+            lines.extend(comment_body_lines(
+                &comment.body,
+                body_width,
+                "    ",
+                &app.config.diff.theme,
+            ));
+            lines.extend(reactions_line(&comment.reactions, "    "));
             lines.push(Line::from(""));
 
             ListItem::new(lines)
@@ -424,9 +504,9 @@ fn render_expanded_thread(frame: &mut Frame, app: &mut App, area: ratatui::layou
         return;
     };
 
-    // Mirror the collapsed view: only display resolved-state badges in
-    // local mode, and key them on each comment's id (so a resolved reply
-    // is also flagged in the expanded conversation).
+    // Mirror the collapsed view: local resolved meta is keyed on each
+    // comment's id (so a resolved reply is also flagged in the expanded
+    // conversation).
     let resolved_ids: HashSet<u64> = if app.is_local_mode() {
         app.cmt
             .local_comment_meta
@@ -454,12 +534,13 @@ fn render_expanded_thread(frame: &mut Frame, app: &mut App, area: ratatui::layou
             let is_selected = i == app.cmt.expanded_selected;
             let prefix = if is_selected { "> " } else { "  " };
             let is_root = i == 0;
-            let resolved = resolved_ids.contains(&comment.id);
-            let resolved_badge = if resolved {
-                Span::styled(" [resolved]", Style::default().fg(Color::DarkGray))
+            // PR mode の resolved 状態はスレッド単位なのでルートにだけ表示する
+            let resolved_badge = if is_root || app.is_local_mode() {
+                resolution_badge(app, comment.id, resolved_ids.contains(&comment.id))
             } else {
                 Span::raw("")
             };
+            let pending = pending_badge(app.is_pending_comment(comment.id));
 
             let header_line = if is_root {
                 let line_info = comment.line.map(|l| format!(":{}", l)).unwrap_or_default();
@@ -470,6 +551,7 @@ fn render_expanded_thread(frame: &mut Frame, app: &mut App, area: ratatui::layou
                         Style::default().fg(Color::Cyan),
                     ),
                     resolved_badge,
+                    pending,
                     Span::raw(" on "),
                     Span::styled(
                         format!("{}{}", comment.path, line_info),
@@ -497,12 +579,13 @@ fn render_expanded_thread(frame: &mut Frame, app: &mut App, area: ratatui::layou
 
             let indent = if is_root { "    " } else { "      " };
             let mut lines = vec![header_line];
-            for body_line in comment.body.lines() {
-                let wrapped = wrap_text(body_line, body_width);
-                for wrapped_line in wrapped {
-                    lines.push(Line::from(vec![Span::raw(indent), Span::raw(wrapped_line)]));
-                }
-            }
+            lines.extend(comment_body_lines(
+                &comment.body,
+                body_width,
+                indent,
+                &app.config.diff.theme,
+            ));
+            lines.extend(reactions_line(&comment.reactions, indent));
             lines.push(Line::from(""));
 
             ListItem::new(lines)
@@ -584,21 +667,16 @@ fn render_discussion_comments(frame: &mut Frame, app: &mut App, area: ratatui::l
             ]);
 
             let mut lines = vec![header_line];
-            let mut line_count = 0;
             let max_preview_lines = 3;
-            'outer: for body_line in comment.body.lines() {
-                let wrapped = wrap_text(body_line, body_width);
-                for wrapped_line in wrapped {
-                    if line_count >= max_preview_lines {
-                        lines.push(Line::from(vec![
-                            Span::raw("    "),
-                            Span::styled("...", Style::default().fg(Color::DarkGray)),
-                        ]));
-                        break 'outer;
-                    }
-                    lines.push(Line::from(vec![Span::raw("    "), Span::raw(wrapped_line)]));
-                    line_count += 1;
-                }
+            let body_lines =
+                comment_body_lines(&comment.body, body_width, "    ", &app.config.diff.theme);
+            let truncated = body_lines.len() > max_preview_lines;
+            lines.extend(body_lines.into_iter().take(max_preview_lines));
+            if truncated {
+                lines.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled("...", Style::default().fg(Color::DarkGray)),
+                ]));
             }
             lines.push(Line::from(""));
 
@@ -657,12 +735,11 @@ fn render_discussion_detail(frame: &mut Frame, app: &App) {
     frame.render_widget(header, chunks[0]);
 
     let content_height = chunks[1].height.saturating_sub(2) as usize;
-    let body_lines: Vec<Line> = comment
-        .body
-        .lines()
+    // 折り返しは Paragraph に任せる（width 0 は折り返さない）
+    let body_lines: Vec<Line> = comment_body_lines(&comment.body, 0, "", &app.config.diff.theme)
+        .into_iter()
         .skip(app.cmt.discussion_comment_detail_scroll)
         .take(content_height)
-        .map(|line| Line::from(line.to_string()))
         .collect();
 
     let total_lines = comment.body.lines().count();
@@ -812,6 +889,7 @@ mod tests {
             },
             created_at: "2025-01-01T00:00:00Z".to_string(),
             in_reply_to_id: None,
+            reactions: Default::default(),
         }]);
         app.build_review_threads();
 
@@ -938,6 +1016,7 @@ mod tests {
                 },
                 created_at: "2026-03-25T02:00:00+00:00".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 2,
@@ -950,6 +1029,7 @@ mod tests {
                 },
                 created_at: "2026-03-25T03:00:00+00:00".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
         ]);
         app.cmt.local_comment_meta.insert(
@@ -1041,6 +1121,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T00:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 101,
@@ -1053,6 +1134,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T01:00:00Z".to_string(),
                 in_reply_to_id: Some(100),
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 102,
@@ -1065,6 +1147,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T02:00:00Z".to_string(),
                 in_reply_to_id: Some(100),
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 200,
@@ -1077,6 +1160,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T03:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
         ]);
         app.build_review_threads();
@@ -1109,6 +1193,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T00:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 101,
@@ -1121,6 +1206,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T01:00:00Z".to_string(),
                 in_reply_to_id: Some(100),
+                reactions: Default::default(),
             },
         ]);
         app.build_review_threads();
@@ -1153,6 +1239,54 @@ mod tests {
         ");
     }
 
+    #[test]
+    fn test_pr_review_thread_resolution_badges() {
+        use crate::github::ReviewThread;
+
+        let mut app = App::new_for_test();
+        app.state = crate::app::AppState::CommentList;
+        app.cmt.comment_tab = CommentTab::Review;
+        let comment = |id: u64, path: &str, login: &str| ReviewComment {
+            id,
+            path: path.to_string(),
+            line: Some(1),
+            start_line: None,
+            body: "body".to_string(),
+            user: User {
+                login: login.to_string(),
+            },
+            created_at: format!("2025-01-01T00:00:{:02}Z", id),
+            in_reply_to_id: None,
+            reactions: Default::default(),
+        };
+        app.cmt.review_comments = Some(vec![
+            comment(1, "a.rs", "alice"),
+            comment(2, "b.rs", "bob"),
+            comment(3, "[PR Review]", "carol"),
+        ]);
+        app.cmt.pr_review_threads = vec![
+            ReviewThread {
+                id: "PRRT_a".to_string(),
+                is_resolved: true,
+                path: "a.rs".to_string(),
+                comment_ids: vec![1],
+            },
+            ReviewThread {
+                id: "PRRT_b".to_string(),
+                is_resolved: false,
+                path: "b.rs".to_string(),
+                comment_ids: vec![2],
+            },
+        ];
+        app.build_review_threads();
+
+        let output = render_full(&mut app);
+        assert!(output.contains("@alice [resolved] on a.rs:1"));
+        assert!(output.contains("@bob [open] on b.rs:1"));
+        // レビュー本文はスレッドを持たないのでバッジなし
+        assert!(output.contains("@carol on [PR Review]:1"));
+    }
+
     #[test]
     fn test_expanded_thread_rendering() {
         let mut app = App::new_for_test();
@@ -1170,6 +1304,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T00:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 101,
@@ -1182,6 +1317,7 @@ mod tests {
                 },
                 created_at: "2025-01-02T00:00:00Z".to_string(),
                 in_reply_to_id: Some(100),
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 102,
@@ -1194,6 +1330,7 @@ mod tests {
                 },
                 created_at: "2025-01-03T00:00:00Z".to_string(),
                 in_reply_to_id: Some(100),
+                reactions: Default::default(),
             },
         ]);
         app.build_review_threads();
@@ -1251,6 +1388,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T00:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 401,
@@ -1263,6 +1401,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T01:00:00Z".to_string(),
                 in_reply_to_id: Some(400),
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 402,
@@ -1275,6 +1414,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T02:00:00Z".to_string(),
                 in_reply_to_id: Some(400),
+                reactions: Default::default(),
             },
         ]);
         // Root resolved, first reply resolved, second reply open.
@@ -1327,6 +1467,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T00:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 300,
@@ -1339,6 +1480,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T01:00:00Z".to_string(),
                 in_reply_to_id: None,
+                reactions: Default::default(),
             },
             ReviewComment {
                 id: 301,
@@ -1351,6 +1493,7 @@ mod tests {
                 },
                 created_at: "2025-01-01T02:00:00Z".to_string(),
                 in_reply_to_id: Some(300),
+                reactions: Default::default(),
             },
         ]);
         // Root of thread 1 is resolved; only the reply of thread 2 is resolved.
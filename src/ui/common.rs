@@ -11,18 +11,32 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::ai::RallyState;
 use crate::app::{App, DataState};
-use crate::github::CiStatus;
+use crate::github::{self, CiStatus};
 
 /// Build PR info string for header display (shared between file_list and ai_rally)
 pub fn build_pr_info(app: &App) -> String {
     if app.is_local_mode() {
         let af = if app.is_local_auto_focus() { " AF" } else { "" };
-        format!("[LOCAL{}] Local HEAD diff", af)
+        match app.diff_base() {
+            Some(rev) => format!("[LOCAL{}] Local diff from {}", af, rev),
+            None => format!("[LOCAL{}] Local HEAD diff", af),
+        }
     } else {
         match &app.data_state {
-            DataState::Loaded { pr, .. } => {
-                format!("PR #{}: {} by @{}", pr.number, pr.title, pr.user.login)
-            }
+            DataState::Loaded { pr, .. } => match app.diff_base() {
+                Some(rev) => format!(
+                    "PR #{}: {} by @{}  [since {}]",
+                    pr.number, pr.title, pr.user.login, rev
+                ),
+                None => format!("PR #{}: {} by @{}", pr.number, pr.title, pr.user.login),
+            },
+            DataState::PartiallyLoaded { pr, .. } => format!(
+                "PR #{}: {} by @{}  {} Loading diff...",
+                pr.number,
+                pr.title,
+                pr.user.login,
+                app.spinner_char()
+            ),
             _ => match app.pr_number {
                 Some(n) => format!("PR #{}", n),
                 None => "PR".to_string(),
@@ -31,16 +45,99 @@ pub fn build_pr_info(app: &App) -> String {
     }
 }
 
-/// Build CI status span with color for header display
-pub fn build_ci_status_span(app: &App) -> Span<'static> {
-    match app.chk.ci_status {
-        Some(CiStatus::Success) => Span::styled("  ✓ CI passed", Style::default().fg(Color::Green)),
-        Some(CiStatus::Failure) => Span::styled("  ✕ CI failed", Style::default().fg(Color::Red)),
-        Some(CiStatus::Pending) => {
-            Span::styled("  ○ CI pending", Style::default().fg(Color::Yellow))
+/// Build read-only mode indicator span for header display
+pub fn build_read_only_span(app: &App) -> Span<'static> {
+    if app.is_read_only() {
+        Span::styled("  [READ-ONLY]", Style::default().fg(Color::Magenta))
+    } else {
+        Span::raw("")
+    }
+}
+
+/// Build file-list truncation warning span (the PR files API stops at 3000 files)
+pub fn build_truncated_files_span(app: &App) -> Span<'static> {
+    if app.is_local_mode() || app.diff_base().is_some() {
+        return Span::raw("");
+    }
+    let (DataState::Loaded { pr, files } | DataState::PartiallyLoaded { pr, files }) =
+        &app.data_state
+    else {
+        return Span::raw("");
+    };
+    if !github::is_file_list_truncated(pr, files.len()) {
+        return Span::raw("");
+    }
+    let label = match pr.changed_files {
+        Some(total) if total > files.len() => {
+            format!(
+                "  [file list truncated: {} of {} files]",
+                files.len(),
+                total
+            )
         }
-        Some(CiStatus::None) | None => Span::raw(""),
+        _ => format!("  [file list truncated at {} files]", files.len()),
+    };
+    Span::styled(label, Style::default().fg(Color::Red))
+}
+
+/// Build pending review indicator span (mode on, or unsent comments kept) for header display
+pub fn build_pending_review_span(app: &App) -> Span<'static> {
+    let count = app.cmt.pending_comments.len();
+    if !app.cmt.pending_review && count == 0 {
+        return Span::raw("");
+    }
+    let label = if app.cmt.pending_review {
+        format!("  [PENDING REVIEW: {}]", count)
+    } else {
+        format!("  [{} pending]", count)
+    };
+    Span::styled(label, Style::default().fg(Color::Yellow))
+}
+
+/// Build CI check summary span (`checks: 5✓ 1✗ 2•`) with color for header display
+pub fn build_ci_status_span(app: &App) -> Span<'static> {
+    let Some(counts) = app
+        .chk
+        .check_counts
+        .filter(|_| app.config.github.show_checks)
+    else {
+        return Span::raw("");
+    };
+    let color = match counts.status() {
+        CiStatus::Success => Color::Green,
+        CiStatus::Failure => Color::Red,
+        CiStatus::Pending => Color::Yellow,
+        CiStatus::None => return Span::raw(""),
+    };
+    Span::styled(
+        format!("  {}", counts.summary()),
+        Style::default().fg(color),
+    )
+}
+
+/// Build review marker count span (TODO/FIXME etc. added by the PR) for header display
+pub fn build_marker_count_span(app: &App) -> Span<'static> {
+    let count = app.review_marker_count;
+    if count == 0 {
+        return Span::raw("");
     }
+    let color = app
+        .config
+        .diff
+        .marker_color
+        .parse()
+        .unwrap_or(Color::Yellow);
+    let plural = if count == 1 { "" } else { "s" };
+    // patch のロード途中は下限値であることを示す
+    let bound = if app.review_marker_count_partial {
+        "≥"
+    } else {
+        ""
+    };
+    Span::styled(
+        format!("  ⚑ {}{} marker{}", bound, count, plural),
+        Style::default().fg(color),
+    )
 }
 
 /// Render rally status bar for background rally indication
@@ -147,6 +244,30 @@ pub fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
     lines
 }
 
+/// ファイル一覧上部の diffstat（例: `12 files, +340 −91`）
+pub fn build_diff_stat_line(app: &App) -> Line<'static> {
+    let stat = app.diff_stat;
+    if stat.files == 0 {
+        return Line::from(Span::styled(
+            " No changes ",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let plural = if stat.files == 1 { "" } else { "s" };
+    Line::from(vec![
+        Span::raw(format!(" {} file{}, ", stat.files, plural)),
+        Span::styled(
+            format!("+{}", stat.additions),
+            Style::default().fg(Color::Green),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("−{} ", stat.deletions),
+            Style::default().fg(Color::Red),
+        ),
+    ])
+}
+
 pub fn render_filter_bar(frame: &mut Frame, area: Rect, filter: &crate::filter::ListFilter) {
     let cursor_display = format!("/{}", filter.query);
     let filter_bar = Paragraph::new(Line::from(vec![
@@ -162,11 +283,48 @@ pub fn render_filter_bar(frame: &mut Frame, area: Rect, filter: &crate::filter::
     frame.render_widget(filter_bar, area);
 }
 
+/// ファイル一覧の glob フィルタ入力バー（`filter_files`）
+pub fn render_file_glob_bar(frame: &mut Frame, area: Rect, pattern: &str) {
+    let glob_bar = Paragraph::new(Line::from(vec![
+        Span::styled("Glob: ", Style::default().fg(Color::Cyan)),
+        Span::styled(pattern.to_string(), Style::default().fg(Color::White)),
+        Span::styled("│", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            "  (e.g. src/**/*.rs, !*.lock; empty clears)",
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(glob_bar, area);
+}
+
+pub fn render_diff_base_bar(frame: &mut Frame, area: Rect, rev: &str) {
+    let base_bar = Paragraph::new(Line::from(vec![
+        Span::styled("Base: ", Style::default().fg(Color::Cyan)),
+        Span::styled(rev.to_string(), Style::default().fg(Color::White)),
+        Span::styled("│", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            "  (review changes since this commit/branch; empty shows the whole diff)",
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(base_bar, area);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::app::App;
-    use crate::github::{Branch, PullRequest, User};
+    use crate::github::{Branch, CheckCounts, PullRequest, User};
 
     #[test]
     fn test_build_pr_info_loaded() {
@@ -190,6 +348,9 @@ mod tests {
                     login: "alice".to_string(),
                 },
                 updated_at: "2024-01-01T00:00:00Z".to_string(),
+                labels: vec![],
+                html_url: None,
+                changed_files: None,
             }),
             files: vec![],
         };
@@ -226,4 +387,164 @@ mod tests {
         app.set_local_auto_focus(true);
         assert_eq!(build_pr_info(&app), "[LOCAL AF] Local HEAD diff");
     }
+
+    #[test]
+    fn test_build_diff_stat_line() {
+        let mut app = App::new_for_test();
+        let file = |filename: &str, additions, deletions| crate::github::ChangedFile {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions,
+            deletions,
+            patch: None,
+            viewed: false,
+        };
+        app.refresh_diff_stat();
+        assert_eq!(build_diff_stat_line(&app).to_string(), " No changes ");
+
+        app.data_state = DataState::Loaded {
+            pr: Box::new(PullRequest {
+                number: 1,
+                node_id: None,
+                title: "t".to_string(),
+                body: None,
+                state: "open".to_string(),
+                head: Branch {
+                    ref_name: "feature".to_string(),
+                    sha: "abc".to_string(),
+                },
+                base: Branch {
+                    ref_name: "main".to_string(),
+                    sha: "def".to_string(),
+                },
+                user: User {
+                    login: "alice".to_string(),
+                },
+                updated_at: "2024-01-01T00:00:00Z".to_string(),
+                labels: vec![],
+                html_url: None,
+                changed_files: None,
+            }),
+            files: vec![file("src/a.rs", 300, 90), file("src/b.rs", 40, 1)],
+        };
+        app.refresh_diff_stat();
+        assert_eq!(
+            build_diff_stat_line(&app).to_string(),
+            " 2 files, +340 −91 "
+        );
+
+        // local mode はパッチの行から数える（patch 未取得のファイルは numstat の値）
+        app.set_local_mode(true);
+        if let DataState::Loaded { files, .. } = &mut app.data_state {
+            files[0].patch = Some("@@ -1,2 +1,3 @@\n-a\n+b\n+c\n ctx".to_string());
+        }
+        app.refresh_diff_stat();
+        assert_eq!(build_diff_stat_line(&app).to_string(), " 2 files, +42 −2 ");
+    }
+
+    #[test]
+    fn test_build_truncated_files_span() {
+        let mut app = App::new_for_test();
+        let file = |i: usize| crate::github::ChangedFile {
+            filename: format!("f{}", i),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: None,
+            viewed: false,
+        };
+        let pr = |changed_files| {
+            Box::new(PullRequest {
+                number: 1,
+                node_id: None,
+                title: "t".to_string(),
+                body: None,
+                state: "open".to_string(),
+                head: Branch {
+                    ref_name: "feature".to_string(),
+                    sha: "abc".to_string(),
+                },
+                base: Branch {
+                    ref_name: "main".to_string(),
+                    sha: "def".to_string(),
+                },
+                user: User {
+                    login: "alice".to_string(),
+                },
+                updated_at: "2024-01-01T00:00:00Z".to_string(),
+                labels: vec![],
+                html_url: None,
+                changed_files,
+            })
+        };
+
+        app.data_state = DataState::Loaded {
+            pr: pr(Some(2)),
+            files: (0..2).map(file).collect(),
+        };
+        assert_eq!(build_truncated_files_span(&app).content, "");
+
+        app.data_state = DataState::Loaded {
+            pr: pr(Some(3200)),
+            files: (0..github::MAX_PR_FILES).map(file).collect(),
+        };
+        assert_eq!(
+            build_truncated_files_span(&app).content,
+            "  [file list truncated: 3000 of 3200 files]"
+        );
+
+        app.data_state = DataState::PartiallyLoaded {
+            pr: pr(None),
+            files: (0..github::MAX_PR_FILES).map(file).collect(),
+        };
+        assert_eq!(
+            build_truncated_files_span(&app).content,
+            "  [file list truncated at 3000 files]"
+        );
+
+        // local mode のファイル一覧は API を通らないので上限は無い
+        app.set_local_mode(true);
+        assert_eq!(build_truncated_files_span(&app).content, "");
+    }
+
+    #[test]
+    fn test_build_ci_status_span_shows_counts() {
+        let mut app = App::new_for_test();
+        assert_eq!(build_ci_status_span(&app).content, "");
+
+        app.chk.check_counts = Some(CheckCounts {
+            passed: 5,
+            failed: 1,
+            pending: 2,
+            skipped: 0,
+        });
+        let span = build_ci_status_span(&app);
+        assert_eq!(span.content, "  checks: 5✓ 1✗ 2•");
+        assert_eq!(span.style.fg, Some(Color::Red));
+
+        app.chk.check_counts = Some(CheckCounts {
+            passed: 3,
+            pending: 1,
+            ..CheckCounts::default()
+        });
+        let span = build_ci_status_span(&app);
+        assert_eq!(span.content, "  checks: 3✓ 1•");
+        assert_eq!(span.style.fg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn test_build_ci_status_span_hidden() {
+        let mut app = App::new_for_test();
+        // チェックが 1 件もない PR
+        app.chk.check_counts = Some(CheckCounts::default());
+        assert_eq!(build_ci_status_span(&app).content, "");
+
+        // show_checks = false
+        app.chk.check_counts = Some(CheckCounts {
+            passed: 2,
+            ..CheckCounts::default()
+        });
+        app.config.github.show_checks = false;
+        assert_eq!(build_ci_status_span(&app).content, "");
+    }
 }
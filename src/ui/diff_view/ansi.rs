@@ -0,0 +1,264 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+
+const ESC: char = '\x1b';
+
+/// Interpret ANSI escape sequences embedded in a diff line as styles.
+///
+/// Only SGR (`ESC [ ... m`) colors and text attributes are applied; every
+/// other CSI sequence (cursor movement, erase, ...) and OSC string is
+/// dropped. Text before `content_start` (the diff `+`/`-` marker) is kept
+/// as-is. Lines without `ESC` are returned untouched.
+///
+/// Syntax highlighting of the line is replaced by the style of its first
+/// content span, on top of which the SGR attributes are layered.
+pub(super) fn split_ansi_spans<'a>(spans: Vec<Span<'a>>, content_start: usize) -> Vec<Span<'a>> {
+    let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+    if !text.contains(ESC) || content_start > text.len() {
+        return spans;
+    }
+
+    let style_at = |pos: usize| {
+        let mut offset = 0;
+        for span in &spans {
+            offset += span.content.len();
+            if offset > pos {
+                return span.style;
+            }
+        }
+        spans.last().map(|s| s.style).unwrap_or_default()
+    };
+    let base = style_at(content_start);
+
+    let mut out = Vec::new();
+    if content_start > 0 {
+        out.push(Span::styled(text[..content_start].to_owned(), style_at(0)));
+    }
+
+    let mut style = base;
+    let mut segment = String::new();
+    let mut pos = content_start;
+    for escape in escapes(&text, content_start) {
+        segment.push_str(&text[pos..escape.start]);
+        pos = escape.end;
+        if let Some(params) = escape.sgr {
+            if !segment.is_empty() {
+                out.push(Span::styled(std::mem::take(&mut segment), style));
+            }
+            style = apply_sgr(style, base, params);
+        }
+    }
+    segment.push_str(&text[pos..]);
+    if !segment.is_empty() {
+        out.push(Span::styled(segment, style));
+    }
+    out
+}
+
+/// Map byte ranges of a raw line (such as expanded tab cells) onto the text
+/// left after [`split_ansi_spans`] has removed the escape sequences.
+pub(super) fn strip_ansi_ranges(
+    text: &str,
+    content_start: usize,
+    ranges: &[(u32, u32)],
+) -> Vec<(u32, u32)> {
+    if !text.contains(ESC) || content_start > text.len() {
+        return ranges.to_vec();
+    }
+    let escapes = escapes(text, content_start);
+    let map = |pos: u32| {
+        let pos = pos as usize;
+        let removed: usize = escapes
+            .iter()
+            .take_while(|escape| escape.start < pos)
+            .map(|escape| escape.end.min(pos) - escape.start)
+            .sum();
+        (pos - removed) as u32
+    };
+    ranges
+        .iter()
+        .map(|&(start, end)| (map(start), map(end)))
+        .filter(|(start, end)| start < end)
+        .collect()
+}
+
+/// An escape sequence in a line: its byte range, plus the parameters when it
+/// is an SGR sequence.
+struct Escape<'t> {
+    start: usize,
+    end: usize,
+    sgr: Option<&'t str>,
+}
+
+fn escapes(text: &str, from: usize) -> Vec<Escape<'_>> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::new();
+    let mut pos = from;
+    while let Some(offset) = text[pos..].find(ESC) {
+        let start = pos + offset;
+        let mut end = start + 1;
+        let mut sgr = None;
+        match bytes.get(end) {
+            Some(b'[') => {
+                end += 1;
+                let params_start = end;
+                while end < bytes.len() && !(0x40..=0x7e).contains(&bytes[end]) {
+                    end += 1;
+                }
+                if end < bytes.len() {
+                    if bytes[end] == b'm' {
+                        sgr = Some(&text[params_start..end]);
+                    }
+                    end += 1;
+                }
+            }
+            // OSC: BEL か ST (ESC \) まで読み飛ばす
+            Some(b']') => {
+                end += 1;
+                while end < bytes.len() {
+                    end += 1;
+                    match bytes[end - 1] {
+                        0x07 => break,
+                        0x1b if bytes.get(end) == Some(&b'\\') => {
+                            end += 1;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // その他の 2 文字エスケープは無視
+            Some(_) => end += text[end..].chars().next().map_or(0, char::len_utf8),
+            None => {}
+        }
+        out.push(Escape { start, end, sgr });
+        pos = end;
+    }
+    out
+}
+
+fn apply_sgr(mut style: Style, base: Style, params: &str) -> Style {
+    let codes: Vec<u16> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+    let mut iter = codes.into_iter();
+    while let Some(code) = iter.next() {
+        style = match code {
+            0 => base,
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(indexed_color(code - 30)),
+            90..=97 => style.fg(indexed_color(code - 90 + 8)),
+            40..=47 => style.bg(indexed_color(code - 40)),
+            100..=107 => style.bg(indexed_color(code - 100 + 8)),
+            38 => match extended_color(&mut iter) {
+                Some(color) => style.fg(color),
+                None => style,
+            },
+            48 => match extended_color(&mut iter) {
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            39 => Style {
+                fg: base.fg,
+                ..style
+            },
+            49 => Style {
+                bg: base.bg,
+                ..style
+            },
+            _ => style,
+        };
+    }
+    style
+}
+
+/// `38;5;n` / `38;2;r;g;b` の後続パラメータを読む
+fn extended_color(iter: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    match iter.next()? {
+        5 => Some(Color::Indexed(iter.next()?.min(255) as u8)),
+        2 => {
+            let mut channel = || iter.next().map(|v| v.min(255) as u8);
+            Some(Color::Rgb(channel()?, channel()?, channel()?))
+        }
+        _ => None,
+    }
+}
+
+fn indexed_color(index: u16) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        7 => Color::Gray,
+        8 => Color::DarkGray,
+        9 => Color::LightRed,
+        10 => Color::LightGreen,
+        11 => Color::LightYellow,
+        12 => Color::LightBlue,
+        13 => Color::LightMagenta,
+        14 => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(spans: &[Span]) -> Vec<String> {
+        spans.iter().map(|s| s.content.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_ansi_spans_applies_sgr_and_drops_cursor_moves() {
+        let green = Style::default().fg(Color::Green);
+        let spans = vec![
+            Span::styled("+", green),
+            Span::styled(
+                "\x1b[2K\x1b[1;34mok\x1b[22m \x1b[38;5;208mwarn\x1b[39m done",
+                green,
+            ),
+        ];
+        let out = split_ansi_spans(spans, 1);
+
+        assert_eq!(texts(&out), vec!["+", "ok", " ", "warn", " done"]);
+        assert_eq!(
+            out[1].style,
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(out[2].style.fg, Some(Color::Blue));
+        assert!(!out[2].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(out[3].style.fg, Some(Color::Indexed(208)));
+        // 39 で行本来の前景色に戻る
+        assert_eq!(out[4].style.fg, Some(Color::Green));
+    }
+
+    #[test]
+    fn test_strip_ansi_ranges_follows_removed_escapes() {
+        // タブを展開したセルの範囲は、エスケープを含む生テキスト上の位置
+        let text = "+\x1b[31m    x\x1b[0m  y";
+        let ranges = [(6, 10), (15, 17)];
+        let stripped = strip_ansi_ranges(text, 1, &ranges);
+        assert_eq!(stripped, vec![(1, 5), (6, 8)]);
+
+        let spans = split_ansi_spans(vec![Span::raw(text)], 1);
+        let plain: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(&plain[1..5], "    ");
+        assert_eq!(&plain[6..8], "  ");
+
+        // エスケープの無い行はそのまま
+        assert_eq!(strip_ansi_ranges("+\tx", 1, &[(1, 5)]), vec![(1, 5)]);
+    }
+}
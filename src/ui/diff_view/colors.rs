@@ -0,0 +1,141 @@
+use ratatui::style::{Color, Style};
+
+use crate::config::DiffColorsConfig;
+use crate::diff::LineType;
+
+/// diff 行の記号・背景色と hunk ヘッダの色（`[diff_colors]` を解釈したもの）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffColors {
+    pub added_fg: Color,
+    pub added_bg: Color,
+    pub removed_fg: Color,
+    pub removed_bg: Color,
+    pub header_fg: Color,
+}
+
+impl Default for DiffColors {
+    fn default() -> Self {
+        Self {
+            added_fg: default_fg(LineType::Added),
+            added_bg: default_bg(LineType::Added),
+            removed_fg: default_fg(LineType::Removed),
+            removed_bg: default_bg(LineType::Removed),
+            header_fg: default_fg(LineType::Header),
+        }
+    }
+}
+
+fn default_fg(line_type: LineType) -> Color {
+    line_type.fg_color().unwrap_or(Color::Reset)
+}
+
+fn default_bg(line_type: LineType) -> Color {
+    line_type.bg_color().unwrap_or(Color::Reset)
+}
+
+/// 色名か `#rrggbb` を解釈する。未設定・不正な値は `fallback`。
+pub fn parse_color(value: Option<&str>, fallback: Color) -> Color {
+    value
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(fallback)
+}
+
+impl DiffColors {
+    pub fn from_config(config: &DiffColorsConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            added_fg: parse_color(config.added_fg.as_deref(), defaults.added_fg),
+            added_bg: parse_color(config.added_bg.as_deref(), defaults.added_bg),
+            removed_fg: parse_color(config.removed_fg.as_deref(), defaults.removed_fg),
+            removed_bg: parse_color(config.removed_bg.as_deref(), defaults.removed_bg),
+            header_fg: parse_color(config.header_fg.as_deref(), defaults.header_fg),
+        }
+    }
+
+    fn fg(&self, line_type: LineType) -> Option<Color> {
+        match line_type.two_way() {
+            LineType::Added => Some(self.added_fg),
+            LineType::Removed => Some(self.removed_fg),
+            LineType::Header => Some(self.header_fg),
+            _ => None,
+        }
+    }
+
+    /// 追加・削除行の背景色（それ以外の行は None）
+    pub fn bg(&self, line_type: LineType) -> Option<Color> {
+        match line_type.two_way() {
+            LineType::Added => Some(self.added_bg),
+            LineType::Removed => Some(self.removed_bg),
+            _ => None,
+        }
+    }
+
+    /// キャッシュ済み span の行レベルの色（記号・ハイライト無しの本文・ヘッダ）を差し替える。
+    /// シンタックスハイライトのトークン色は既定の行色と一致しないのでそのまま残る。
+    pub fn recolor(&self, style: Style, line_type: LineType) -> Style {
+        match (style.fg, line_type.fg_color(), self.fg(line_type)) {
+            (Some(fg), Some(default), Some(color)) if fg == default => style.fg(color),
+            _ => style,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color_named_and_hex() {
+        assert_eq!(parse_color(Some("blue"), Color::Reset), Color::Blue);
+        assert_eq!(parse_color(Some("LightRed"), Color::Reset), Color::LightRed);
+        assert_eq!(
+            parse_color(Some("#0055ff"), Color::Reset),
+            Color::Rgb(0x00, 0x55, 0xff)
+        );
+        assert_eq!(
+            parse_color(Some(" #112233 "), Color::Reset),
+            Color::Rgb(0x11, 0x22, 0x33)
+        );
+    }
+
+    #[test]
+    fn test_parse_color_falls_back_on_invalid() {
+        assert_eq!(parse_color(None, Color::Green), Color::Green);
+        assert_eq!(parse_color(Some("not-a-color"), Color::Green), Color::Green);
+        assert_eq!(parse_color(Some("#12345"), Color::Red), Color::Red);
+        assert_eq!(parse_color(Some(""), Color::Red), Color::Red);
+    }
+
+    #[test]
+    fn test_from_config_overrides_only_set_values() {
+        let config = DiffColorsConfig {
+            added_fg: Some("blue".to_string()),
+            removed_bg: Some("#2a1a00".to_string()),
+            header_fg: Some("bogus".to_string()),
+            ..Default::default()
+        };
+        let colors = DiffColors::from_config(&config);
+        assert_eq!(colors.added_fg, Color::Blue);
+        assert_eq!(colors.removed_bg, Color::Rgb(0x2a, 0x1a, 0x00));
+        assert_eq!(colors.added_bg, DiffColors::default().added_bg);
+        assert_eq!(colors.removed_fg, Color::Red);
+        assert_eq!(colors.header_fg, Color::Cyan);
+    }
+
+    #[test]
+    fn test_recolor_keeps_syntax_colors() {
+        let colors = DiffColors {
+            added_fg: Color::Blue,
+            ..Default::default()
+        };
+        let marker = Style::default().fg(Color::Green);
+        assert_eq!(
+            colors.recolor(marker, LineType::Added).fg,
+            Some(Color::Blue)
+        );
+        let token = Style::default().fg(Color::Rgb(255, 121, 198));
+        assert_eq!(colors.recolor(token, LineType::Added), token);
+        // 文脈行には行レベルの色が無い
+        assert_eq!(colors.recolor(marker, LineType::Context), marker);
+    }
+}
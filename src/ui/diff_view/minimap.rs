@@ -0,0 +1,167 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+};
+
+use crate::diff::{ChangeColumn, LineType};
+
+/// ミニマップ 1 セル分（= diff の一区間）の変更の有無
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ChangeDensity {
+    pub added: bool,
+    pub removed: bool,
+}
+
+impl ChangeDensity {
+    fn color(self) -> Option<Color> {
+        match (self.added, self.removed) {
+            (true, true) => Some(Color::Yellow),
+            (true, false) => Some(Color::Green),
+            (false, true) => Some(Color::Red),
+            (false, false) => None,
+        }
+    }
+}
+
+/// 行種別の列を `buckets` 個の区間に縮約する
+///
+/// 行 `i` は区間 `i * buckets / total` に入る。行数が区間数より少ない場合は
+/// 空の区間（変更なし）が混ざる。
+pub(crate) fn change_density(
+    line_types: impl ExactSizeIterator<Item = LineType>,
+    buckets: usize,
+) -> Vec<ChangeDensity> {
+    let total = line_types.len();
+    let mut density = vec![ChangeDensity::default(); buckets];
+    if buckets == 0 || total == 0 {
+        return density;
+    }
+    for (i, line_type) in line_types.enumerate() {
+        let cell = &mut density[i * buckets / total];
+        match line_type {
+            LineType::Added => cell.added = true,
+            LineType::Removed => cell.removed = true,
+            LineType::Combined { first, second } => {
+                for column in [first, second] {
+                    match column {
+                        ChangeColumn::Added => cell.added = true,
+                        ChangeColumn::Removed => cell.removed = true,
+                        ChangeColumn::Context => {}
+                    }
+                }
+            }
+            LineType::Context | LineType::Header | LineType::Meta => {}
+        }
+    }
+    density
+}
+
+/// 表示中の行範囲 `[start, start + visible)` にかかる区間
+fn viewport_buckets(
+    start: usize,
+    visible: usize,
+    total: usize,
+    buckets: usize,
+) -> Option<std::ops::RangeInclusive<usize>> {
+    if total == 0 || buckets == 0 {
+        return None;
+    }
+    let end = (start + visible).min(total).max(start + 1);
+    Some((start * buckets / total)..=((end - 1) * buckets / total).min(buckets - 1))
+}
+
+/// diff 枠の右端に変更密度のミニマップと表示位置を描画する
+///
+/// 変更区間は `▐` を追加=緑・削除=赤・両方=黄で塗り、表示中の区間は背景を
+/// 灰色にする。
+pub(super) fn render_minimap(
+    buf: &mut Buffer,
+    area: Rect,
+    line_types: impl ExactSizeIterator<Item = LineType>,
+    scroll_offset: usize,
+    visible_height: usize,
+) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let total = line_types.len();
+    let buckets = area.height as usize;
+    let density = change_density(line_types, buckets);
+    let viewport = viewport_buckets(scroll_offset, visible_height, total, buckets);
+    let x = area.right() - 1;
+
+    for (i, cell) in density.iter().enumerate() {
+        let mut style = Style::default();
+        let symbol = match cell.color() {
+            Some(color) => {
+                style = style.fg(color);
+                "▐"
+            }
+            None => " ",
+        };
+        if viewport.as_ref().is_some_and(|v| v.contains(&i)) {
+            style = style.bg(Color::DarkGray);
+        }
+        if let Some(target) = buf.cell_mut((x, area.y + i as u16)) {
+            target.set_symbol(symbol).set_style(style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_density_buckets() {
+        use LineType::*;
+        // 12 行を 4 区間へ: [0..3) 追加, [3..6) 文脈のみ, [6..9) 削除, [9..12) 両方
+        let lines = [
+            Header, Added, Context, Context, Context, Context, Context, Removed, Removed, Added,
+            Context, Removed,
+        ];
+        let density = change_density(lines.into_iter(), 4);
+        let colors: Vec<_> = density.iter().map(|d| d.color()).collect();
+        assert_eq!(
+            colors,
+            vec![
+                Some(Color::Green),
+                None,
+                Some(Color::Red),
+                Some(Color::Yellow)
+            ]
+        );
+
+        // 行数が区間数より少ない場合は空区間が混ざる
+        let density = change_density([Added, Removed].into_iter(), 4);
+        assert_eq!(
+            density,
+            vec![
+                ChangeDensity {
+                    added: true,
+                    removed: false
+                },
+                ChangeDensity::default(),
+                ChangeDensity {
+                    added: false,
+                    removed: true
+                },
+                ChangeDensity::default(),
+            ]
+        );
+
+        assert!(change_density(std::iter::empty(), 4)
+            .iter()
+            .all(|d| *d == ChangeDensity::default()));
+        assert!(change_density([Added].into_iter(), 0).is_empty());
+    }
+
+    #[test]
+    fn test_viewport_buckets() {
+        assert_eq!(viewport_buckets(0, 10, 100, 10), Some(0..=0));
+        assert_eq!(viewport_buckets(50, 20, 100, 10), Some(5..=6));
+        assert_eq!(viewport_buckets(95, 20, 100, 10), Some(9..=9));
+        assert_eq!(viewport_buckets(0, 10, 0, 10), None);
+    }
+}
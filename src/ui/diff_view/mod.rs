@@ -1,3 +1,8 @@
+mod ansi;
+mod colors;
+mod minimap;
+mod split;
+
 use std::borrow::Cow;
 use std::collections::HashSet;
 
@@ -11,29 +16,83 @@ use ratatui::{
 };
 use syntect::easy::HighlightLines;
 
-use smallvec::smallvec;
+use smallvec::{smallvec, SmallVec};
 
 use super::common::render_rally_status_bar;
 use crate::app::{
-    hash_string, App, CachedDiffLine, DiffCache, InputMode, InternedSpan, LineInputContext, SpanVec,
+    hash_string, App, CachedDiffLine, CollapsedLines, DiffCache, DiffLayout, InputMode,
+    InternedSpan, LineInputContext, SpanVec,
+};
+use crate::config::{DiffColorsConfig, DiffConfig, Gutter, LineNumbers, ShowWhitespace};
+use crate::diff::{
+    classify_hunk_line, conflict_marker_lines, context_runs, find_marker_ranges,
+    line_ending_only_hunks, moved_block_at, moved_blocks, LineEnding, LineType, MovedSide,
+    PatchIndex, SearchMatch,
 };
-use crate::diff::{classify_line, LineType};
 use crate::syntax::{
     apply_line_highlights, collect_line_highlights, collect_line_highlights_with_injections,
-    get_theme, highlight_code_line, syntax_for_file, Highlighter, ParserPool,
+    get_theme, highlight_code_line, syntax_for_file, Highlighter, ParserPool, ThemeStyleCache,
 };
+use ansi::{split_ansi_spans, strip_ansi_ranges};
+use colors::DiffColors;
 
-/// Expand tab characters to spaces with fixed width.
+/// Expand tab characters to spaces, advancing each tab to the next tab stop.
+///
+/// Tab stops are counted in display columns from the start of each line's
+/// content, i.e. after the diff prefix (`+`/`-`/space). The prefix and the
+/// line-number gutter are drawn in front of the content, so indentation that
+/// mixes tabs and spaces lines up the same as in an editor.
 ///
 /// Returns `Cow::Borrowed` when the input contains no tabs (zero allocation).
-/// Returns `Cow::Owned` when tabs are found, replacing each `\t` with `tab_width` spaces.
 pub fn expand_tabs(s: &str, tab_width: u8) -> Cow<'_, str> {
     if !s.contains('\t') {
-        Cow::Borrowed(s)
-    } else {
-        let spaces = " ".repeat(tab_width as usize);
-        Cow::Owned(s.replace('\t', &spaces))
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len());
+    for line in s.split_inclusive('\n') {
+        expand_line_tabs(line, tab_width, &mut out);
     }
+    Cow::Owned(out)
+}
+
+/// Append `line` to `out` with its tabs expanded, returning the byte ranges
+/// (relative to the appended text) each tab expanded to.
+fn expand_line_tabs(line: &str, tab_width: u8, out: &mut String) -> Vec<(u32, u32)> {
+    use unicode_width::UnicodeWidthChar;
+
+    let width = tab_width.max(1) as usize;
+    let start = out.len();
+    let mut chars = line.chars();
+    // diff 記号はタブ位置の起点に含めない
+    if line.starts_with(['+', '-', ' ']) {
+        out.extend(chars.next());
+    }
+    let mut ranges = Vec::new();
+    let mut column = 0;
+    for c in chars {
+        if c == '\t' {
+            let spaces = width - column % width;
+            let offset = (out.len() - start) as u32;
+            ranges.push((offset, offset + spaces as u32));
+            out.extend(std::iter::repeat_n(' ', spaces));
+            column += spaces;
+        } else {
+            out.push(c);
+            column += c.width().unwrap_or(0);
+        }
+    }
+    ranges
+}
+
+/// Byte ranges that each tab occupies after [`expand_tabs`].
+///
+/// Offsets are relative to the expanded line, so they line up with the
+/// concatenated spans of a [`CachedDiffLine`].
+fn tab_ranges(line: &str, tab_width: u8) -> Vec<(u32, u32)> {
+    if !line.contains('\t') {
+        return Vec::new();
+    }
+    expand_line_tabs(line, tab_width, &mut String::new())
 }
 
 /// Build a plain DiffCache without syntax highlighting (diff coloring only).
@@ -50,10 +109,12 @@ pub fn build_plain_diff_cache(patch: &str, tab_width: u8) -> DiffCache {
     let patch_hash = hash_string(patch);
     let expanded = expand_tabs(patch, tab_width);
     let mut interner = Rodeo::default();
-    let lines: Vec<CachedDiffLine> = expanded
+    let mut combined = false;
+    let mut lines: Vec<CachedDiffLine> = expanded
         .lines()
-        .map(|line| {
-            let (line_type, content) = classify_line(line);
+        .zip(patch.lines())
+        .map(|(line, original)| {
+            let (line_type, content) = classify_hunk_line(line, &mut combined);
 
             let fg_style = match line_type.fg_color() {
                 Some(c) => Style::default().fg(c),
@@ -77,9 +138,16 @@ pub fn build_plain_diff_cache(patch: &str, tab_width: u8) -> DiffCache {
                 }]
             };
 
-            CachedDiffLine { spans, line_type }
+            CachedDiffLine {
+                spans,
+                line_type,
+                tabs: tab_ranges(original, tab_width),
+                old_line_number: None,
+                new_line_number: None,
+            }
         })
         .collect();
+    assign_line_numbers(&mut lines, patch);
 
     DiffCache {
         file_index: 0,
@@ -88,6 +156,22 @@ pub fn build_plain_diff_cache(patch: &str, tab_width: u8) -> DiffCache {
         interner,
         highlighted: false,
         markdown_rich: false,
+        line_ending_hunks: line_ending_only_hunks(patch),
+        line_ending: LineEnding::detect(patch),
+        context_runs: context_runs(patch),
+        conflict_lines: conflict_marker_lines(patch),
+        moved_blocks: moved_blocks(patch),
+    }
+}
+
+/// 各行に旧/新ファイルの行番号（行番号ガター用）を設定する
+fn assign_line_numbers(lines: &mut [CachedDiffLine], patch: &str) {
+    let index = PatchIndex::build(patch);
+    for (i, line) in lines.iter_mut().enumerate() {
+        if let Some(info) = index.get(i) {
+            line.old_line_number = info.old_line_number;
+            line.new_line_number = info.new_line_number;
+        }
     }
 }
 
@@ -113,6 +197,7 @@ pub fn build_diff_cache(
     tab_width: u8,
 ) -> DiffCache {
     let patch_hash = hash_string(patch);
+    let original = patch;
     let expanded = expand_tabs(patch, tab_width);
     let patch = expanded.as_ref();
     let mut interner = Rodeo::default();
@@ -134,7 +219,7 @@ pub fn build_diff_cache(
     let highlighter = Highlighter::for_file(filename, theme_name);
 
     // Parse source (borrows parser_pool only for this call)
-    let cst_result = highlighter.parse_source(&combined_source, parser_pool);
+    let cst_result = highlighter.parse_file_source(filename, &combined_source, parser_pool);
 
     // Check if tree-sitter parsing succeeded
     // Note: We no longer fall back based on error count, since tree-sitter's
@@ -143,24 +228,20 @@ pub fn build_diff_cache(
     // between hunks), not because the code is actually invalid.
     let use_cst = cst_result.is_some();
 
+    // Owned so the syntect paths can memoize converted token styles while building
+    let mut style_cache = highlighter.style_cache().cloned().unwrap_or_default();
+
     let lines: Vec<CachedDiffLine> = if use_cst {
         let result = cst_result.as_ref().unwrap();
-        let base_style_cache = highlighter
-            .style_cache()
-            .expect("CST highlighter should have style_cache");
 
         // Apply markdown rich overrides when enabled for markdown files
-        let rich_cache;
-        let style_cache = if markdown_rich && (ext == "md" || ext == "markdown") {
-            rich_cache = base_style_cache.clone().with_markdown_rich_overrides();
-            &rich_cache
-        } else {
-            base_style_cache
-        };
+        if markdown_rich && (ext == "md" || ext == "markdown") {
+            style_cache = style_cache.with_markdown_rich_overrides();
+        }
 
         // CST path: use tree-sitter with full AST context
-        // Use injection-aware highlighting for SFC languages (Svelte, Vue)
-        let line_highlights = if ext == "svelte" || ext == "vue" || ext == "md" || ext == "markdown"
+        // Use injection-aware highlighting for SFC languages (Svelte, Vue) and JSX/TSX
+        let line_highlights = if matches!(ext, "svelte" | "vue" | "md" | "markdown" | "jsx" | "tsx")
         {
             // Injection path: query is obtained inside the function to avoid borrow conflicts
             // Note: priming_lines offset is handled when applying highlights to diff lines
@@ -168,7 +249,7 @@ pub fn build_diff_cache(
                 &combined_source,
                 &result.tree,
                 result.lang,
-                style_cache,
+                &style_cache,
                 parser_pool,
                 ext,
             )
@@ -187,7 +268,7 @@ pub fn build_diff_cache(
                 &result.tree,
                 query,
                 &capture_names,
-                style_cache,
+                &style_cache,
             )
         };
         build_lines_with_cst(
@@ -198,10 +279,11 @@ pub fn build_diff_cache(
             &line_mapping,
             priming_lines,
             &mut interner,
+            &mut style_cache,
         )
     } else {
         // Syntect fallback path (no CST support for this file type)
-        build_lines_with_syntect(patch, filename, theme_name, &mut interner)
+        build_lines_with_syntect(patch, filename, theme_name, &mut interner, &mut style_cache)
     };
 
     // Post-process: hide/replace markdown syntax in rich mode
@@ -209,7 +291,13 @@ pub fn build_diff_cache(
     if markdown_rich && (ext == "md" || ext == "markdown") {
         apply_markdown_rich_transforms(&mut lines, &mut interner);
         apply_markdown_table_transforms(&mut lines, &mut interner);
+    } else if expanded.as_ref() != original {
+        // rich 変換はスパンを削るためタブ位置がずれる → 変換しない場合のみ記録
+        for (line, original) in lines.iter_mut().zip(original.lines()) {
+            line.tabs = tab_ranges(original, tab_width);
+        }
     }
+    assign_line_numbers(&mut lines, original);
 
     DiffCache {
         file_index: 0, // Caller should update this
@@ -218,6 +306,11 @@ pub fn build_diff_cache(
         interner,
         highlighted: true,
         markdown_rich,
+        line_ending_hunks: line_ending_only_hunks(patch),
+        line_ending: LineEnding::detect(patch),
+        context_runs: context_runs(patch),
+        conflict_lines: conflict_marker_lines(patch),
+        moved_blocks: moved_blocks(patch),
     }
 }
 
@@ -267,6 +360,9 @@ pub fn build_commit_diff_cache(
             combined_lines.push(CachedDiffLine {
                 spans,
                 line_type: line.line_type,
+                tabs: line.tabs.clone(),
+                old_line_number: line.old_line_number,
+                new_line_number: line.new_line_number,
             });
         }
     }
@@ -278,6 +374,11 @@ pub fn build_commit_diff_cache(
         interner: combined_interner,
         highlighted: true,
         markdown_rich: false,
+        line_ending_hunks: Vec::new(),
+        line_ending: LineEnding::Lf,
+        context_runs: Vec::new(),
+        conflict_lines: Vec::new(),
+        moved_blocks: Vec::new(),
     }
 }
 
@@ -786,9 +887,11 @@ fn build_combined_source_for_highlight(patch: &str) -> (String, Vec<(usize, Line
     // Maps source line index -> (diff line index, line type)
     let mut line_mapping: Vec<(usize, LineType)> = Vec::new();
 
+    let mut combined = false;
     for (diff_line_idx, line) in patch.lines().enumerate() {
-        let (line_type, content) = classify_line(line);
-        match line_type {
+        let (line_type, content) = classify_hunk_line(line, &mut combined);
+        // Combined (merge) lines count as the result-side line they collapse to
+        match line_type.two_way() {
             // Only include added and context lines (post-change version)
             // This ensures the source is syntactically valid for tree-sitter
             LineType::Added | LineType::Context => {
@@ -798,7 +901,7 @@ fn build_combined_source_for_highlight(patch: &str) -> (String, Vec<(usize, Line
             }
             // Skip removed lines to maintain valid syntax (especially for Python)
             // Skip headers and meta lines
-            LineType::Removed | LineType::Header | LineType::Meta => {}
+            LineType::Removed | LineType::Header | LineType::Meta | LineType::Combined { .. } => {}
         }
     }
     (source, line_mapping)
@@ -943,6 +1046,7 @@ fn build_lines_with_cst(
     line_mapping: &[(usize, LineType)],
     priming_lines: usize,
     interner: &mut Rodeo,
+    style_cache: &mut ThemeStyleCache,
 ) -> Vec<CachedDiffLine> {
     // Build a reverse mapping: diff_line_index -> source_line_index
     // Only Added and Context lines are in the source (Removed lines are excluded)
@@ -960,20 +1064,21 @@ fn build_lines_with_cst(
     let theme = get_theme(theme_name);
     let mut syntect_highlighter = syntax.map(|s| HighlightLines::new(s, theme));
 
+    let mut combined = false;
     patch
         .lines()
         .enumerate()
         .map(|(i, line)| {
-            let (line_type, content) = classify_line(line);
+            let (line_type, content) = classify_hunk_line(line, &mut combined);
 
-            let spans = match line_type {
+            let spans = match line_type.two_way() {
                 LineType::Header => {
                     smallvec![InternedSpan {
                         content: interner.get_or_intern(line),
                         style: Style::default().fg(Color::Cyan),
                     }]
                 }
-                LineType::Meta => {
+                LineType::Meta | LineType::Combined { .. } => {
                     smallvec![InternedSpan {
                         content: interner.get_or_intern(line),
                         style: Style::default().fg(Color::Yellow),
@@ -983,19 +1088,13 @@ fn build_lines_with_cst(
                     // These lines are in the CST source, look up their highlights
                     let source_line_index = diff_to_source.get(&i).copied();
 
-                    let marker_style = match line_type {
-                        LineType::Added => Style::default().fg(Color::Green),
-                        _ => Style::default(),
-                    };
-
-                    let marker = match line_type {
-                        LineType::Added => "+",
-                        LineType::Context => " ",
-                        _ => "",
+                    let marker_style = match line_type.fg_color() {
+                        Some(c) => Style::default().fg(c),
+                        None => Style::default(),
                     };
 
                     let mut spans: SpanVec = smallvec![InternedSpan {
-                        content: interner.get_or_intern(marker),
+                        content: interner.get_or_intern(line_type.marker().unwrap_or("")),
                         style: marker_style,
                     }];
 
@@ -1009,7 +1108,7 @@ fn build_lines_with_cst(
                     // Removed lines are NOT in the CST source (to preserve valid syntax)
                     // Use syntect to apply syntax highlighting (fallback)
                     let marker = InternedSpan {
-                        content: interner.get_or_intern("-"),
+                        content: interner.get_or_intern(line_type.marker().unwrap_or("-")),
                         style: Style::default().fg(Color::Red),
                     };
                     let code_spans = highlight_or_fallback(
@@ -1017,6 +1116,7 @@ fn build_lines_with_cst(
                         &mut syntect_highlighter,
                         Color::Red,
                         interner,
+                        style_cache,
                     );
                     let mut spans: SpanVec = smallvec![marker];
                     spans.extend(code_spans);
@@ -1024,7 +1124,13 @@ fn build_lines_with_cst(
                 }
             };
 
-            CachedDiffLine { spans, line_type }
+            CachedDiffLine {
+                spans,
+                line_type,
+                tabs: Vec::new(),
+                old_line_number: None,
+                new_line_number: None,
+            }
         })
         .collect()
 }
@@ -1035,6 +1141,7 @@ fn build_lines_with_syntect(
     filename: &str,
     theme_name: &str,
     interner: &mut Rodeo,
+    style_cache: &mut ThemeStyleCache,
 ) -> Vec<CachedDiffLine> {
     let syntax = syntax_for_file(filename);
     let theme = get_theme(theme_name);
@@ -1051,35 +1158,405 @@ fn build_lines_with_syntect(
         }
     }
 
+    let mut combined = false;
     patch
         .lines()
         .map(|line| {
-            let (line_type, content) = classify_line(line);
+            let (line_type, content) = classify_hunk_line(line, &mut combined);
 
-            let spans = build_line_spans(line_type, line, content, &mut highlighter, interner);
+            let spans = build_line_spans(
+                line_type,
+                line,
+                content,
+                &mut highlighter,
+                interner,
+                style_cache,
+            );
 
-            CachedDiffLine { spans, line_type }
+            CachedDiffLine {
+                spans,
+                line_type,
+                tabs: Vec::new(),
+                old_line_number: None,
+                new_line_number: None,
+            }
         })
         .collect()
 }
 
+/// Marker-word highlighting applied to added lines by [`render_cached_lines`].
+#[derive(Debug, Clone, Copy)]
+pub struct MarkerHighlight<'a> {
+    pub words: &'a [String],
+    pub style: Style,
+}
+
+/// Per-line styling options for [`render_cached_lines`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineRenderOptions<'a> {
+    /// 追加/削除行に背景色を付けるか
+    pub bg_color: bool,
+    /// 行レベルの記号・背景・ヘッダの色（`[diff_colors]`）
+    pub colors: DiffColors,
+    /// 追加行のレビューマーカー強調（None なら無効）
+    pub markers: Option<MarkerHighlight<'a>>,
+    /// 空白文字の可視化モード
+    pub whitespace: ShowWhitespace,
+    /// 追加行の行末の空白を強調するか
+    pub trailing_whitespace: bool,
+    /// 追加行のインデント中のタブを強調するか
+    pub tab_indent: bool,
+    /// 行内の ANSI エスケープ（SGR）をスタイルとして解釈するか
+    pub ansi: bool,
+    /// diff 内検索の一致箇所（行順）。None なら強調しない
+    pub search: Option<&'a [SearchMatch]>,
+    /// 行番号ガターの表示モード
+    pub gutter: Gutter,
+    /// ガターの行番号を絶対値・カーソルからの相対値のどちらで出すか
+    pub line_numbers: LineNumbers,
+    /// 長い行を表示幅で折り返し、継続行を本文の開始桁に揃えるか（`zw`）
+    pub wrap: bool,
+    /// コメント行のうちスレッドがすべて折りたたまれている行（`●` の代わりに `▸`）
+    pub collapsed_comments: Option<&'a HashSet<usize>>,
+}
+
+impl<'a> LineRenderOptions<'a> {
+    pub fn from_config(config: &'a DiffConfig, colors: &DiffColorsConfig) -> Self {
+        let markers = (config.highlight_markers && !config.marker_words.is_empty()).then(|| {
+            let color = config.marker_color.parse().unwrap_or(Color::Yellow);
+            let mut style = Style::default().fg(color);
+            if config.marker_bold {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            MarkerHighlight {
+                words: &config.marker_words,
+                style,
+            }
+        });
+        Self {
+            bg_color: config.bg_color,
+            colors: DiffColors::from_config(colors),
+            markers,
+            whitespace: config.show_whitespace,
+            trailing_whitespace: config.highlight_trailing_whitespace,
+            tab_indent: config.highlight_tab_indent,
+            ansi: config.render_ansi_content,
+            search: None,
+            gutter: config.gutter,
+            line_numbers: config.line_numbers,
+            wrap: false,
+            collapsed_comments: None,
+        }
+    }
+}
+
+/// diff 行のコメント記号。スレッドがすべて折りたたまれている行は `▸` で控えめに出す
+pub(super) fn comment_marker(
+    line: usize,
+    comment_lines: &HashSet<usize>,
+    collapsed: Option<&HashSet<usize>>,
+) -> Option<Span<'static>> {
+    if !comment_lines.contains(&line) {
+        return None;
+    }
+    if collapsed.is_some_and(|c| c.contains(&line)) {
+        Some(Span::styled("▸ ", Style::default().fg(Color::DarkGray)))
+    } else {
+        Some(Span::styled("● ", Style::default().fg(Color::Yellow)))
+    }
+}
+
+/// 行番号ガターの 1 列分の幅（キャッシュ内の最大行番号の桁数）
+///
+/// 行番号はハンク順に単調増加するので、末尾から最初に見つかった番号が最大値になる。
+/// 相対表示ではカーソルからの距離（最大で行数 - 1）も収まる幅にする。
+fn gutter_number_width(cache: &DiffCache, line_numbers: LineNumbers) -> usize {
+    let last = |f: fn(&CachedDiffLine) -> Option<u32>| cache.lines.iter().rev().find_map(f);
+    let max = last(|l| l.old_line_number)
+        .max(last(|l| l.new_line_number))
+        .unwrap_or(0) as usize;
+    let max_distance = match line_numbers {
+        LineNumbers::Absolute => 0,
+        LineNumbers::Relative | LineNumbers::Hybrid => cache.lines.len().saturating_sub(1),
+    };
+    max.max(max_distance).max(1).to_string().len()
+}
+
+/// 行番号ガターの文字列（`Gutter::None` なら None）
+///
+/// 相対表示ではカーソル行からの距離 `distance` をガター全体の右端に揃えて 1 つだけ出す。
+/// ヘッダ行も `5j` などの移動で 1 行に数えるため、行番号の無い行にも距離を出す。
+fn gutter_text(
+    cached: &CachedDiffLine,
+    gutter: Gutter,
+    line_numbers: LineNumbers,
+    width: usize,
+    distance: usize,
+) -> Option<String> {
+    let column = |n: Option<u32>| match n {
+        Some(n) => format!("{:>width$} ", n),
+        None => " ".repeat(width + 1),
+    };
+    let columns = match gutter {
+        Gutter::None => return None,
+        Gutter::New => 1,
+        Gutter::Both => 2,
+    };
+    let relative = match line_numbers {
+        LineNumbers::Absolute => false,
+        LineNumbers::Relative => true,
+        LineNumbers::Hybrid => distance != 0,
+    };
+    if relative {
+        let total = columns * (width + 1) - 1;
+        return Some(format!("{:>total$} ", distance));
+    }
+    Some(match gutter {
+        Gutter::Both => column(cached.old_line_number) + &column(cached.new_line_number),
+        _ => column(cached.new_line_number),
+    })
+}
+
+/// 行番号ガターの span（`Gutter::None` なら None）
+fn gutter_span(
+    cached: &CachedDiffLine,
+    opts: &LineRenderOptions<'_>,
+    width: usize,
+    distance: usize,
+) -> Option<Span<'static>> {
+    gutter_text(cached, opts.gutter, opts.line_numbers, width, distance)
+        .map(|text| Span::styled(text, Style::default().fg(Color::DarkGray)))
+}
+
+/// 検索一致の強調スタイル
+const SEARCH_MATCH_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+/// 行末の空白・インデント中のタブの強調スタイル
+const WHITESPACE_ERROR_STYLE: Style = Style::new().bg(Color::Red);
+/// 衝突マーカー行の文字スタイル（行の背景は `CONFLICT_LINE_BG`）
+const CONFLICT_MARKER_STYLE: Style = Style::new()
+    .fg(Color::Black)
+    .bg(CONFLICT_LINE_BG)
+    .add_modifier(Modifier::BOLD);
+const CONFLICT_LINE_BG: Color = Color::LightRed;
+/// 移動したコードの文字色（`git diff --color-moved` と同じく移動元はマゼンタ、移動先はシアン）
+const MOVED_FROM_STYLE: Style = Style::new().fg(Color::Magenta);
+const MOVED_TO_STYLE: Style = Style::new().fg(Color::Cyan);
+
+/// 移動ブロック先頭行の末尾に付ける、対応する側の行番号の案内（`↳ moved to line 42` など）
+fn moved_link_span(cache: &DiffCache, line: usize) -> Option<Span<'static>> {
+    let (block, side) = moved_block_at(&cache.moved_blocks, line)?;
+    let (first, text, counterpart) = match side {
+        MovedSide::From => (
+            block.removed.start,
+            "moved to",
+            cache.lines.get(block.added.start)?.new_line_number,
+        ),
+        MovedSide::To => (
+            block.added.start,
+            "moved from",
+            cache.lines.get(block.removed.start)?.old_line_number,
+        ),
+    };
+    if line != first {
+        return None;
+    }
+    let text = match counterpart {
+        Some(n) => format!("  ↳ {} line {}", text, n),
+        None => format!("  ↳ {}", text),
+    };
+    Some(Span::styled(
+        text,
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC),
+    ))
+}
+
+/// 追加行の空白の誤り（行末の空白・インデント中のタブ）の行内バイト範囲（昇順）
+///
+/// タブは展開済みなので、位置は `tabs`（展開後のタブの範囲）から求める。
+/// 空白だけの行は全体が行末の空白になるため、タブの範囲はその手前までに限る。
+fn whitespace_error_ranges(
+    text: &str,
+    tabs: &[(u32, u32)],
+    content_start: usize,
+    opts: &LineRenderOptions<'_>,
+) -> SmallVec<[(usize, usize); 2]> {
+    let mut ranges = SmallVec::new();
+    let Some(content) = text.get(content_start..) else {
+        return ranges;
+    };
+    let trailing_start = content_start + content.trim_end_matches(' ').len();
+    if opts.tab_indent {
+        let indent_end = content_start + content.len() - content.trim_start_matches(' ').len();
+        let limit = if opts.trailing_whitespace {
+            indent_end.min(trailing_start)
+        } else {
+            indent_end
+        };
+        ranges.extend(
+            tabs.iter()
+                .map(|&(start, end)| (start as usize, end as usize))
+                .filter(|&(start, end)| start >= content_start && end <= limit),
+        );
+    }
+    if opts.trailing_whitespace && trailing_start < text.len() {
+        ranges.push((trailing_start, text.len()));
+    }
+    ranges
+}
+
+/// `line` 上の検索一致（`matches` は行順）
+fn search_matches_on_line(matches: &[SearchMatch], line: usize) -> &[SearchMatch] {
+    let start = matches.partition_point(|m| m.line < line);
+    let end = start + matches[start..].partition_point(|m| m.line == line);
+    &matches[start..end]
+}
+
+/// Split spans at marker-word boundaries and apply the marker style.
+///
+/// Matching runs on the whole line so that word boundaries spanning syntax
+/// tokens (e.g. `FOO_` + `TODO`) agree with [`count_added_markers`].
+/// Borrowed slices keep pointing into the interner, so no string is copied.
+///
+/// [`count_added_markers`]: crate::diff::count_added_markers
+fn split_marker_spans<'a>(spans: Vec<Span<'a>>, markers: MarkerHighlight<'_>) -> Vec<Span<'a>> {
+    let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+    let ranges = find_marker_ranges(&text, markers.words);
+    if ranges.is_empty() {
+        return spans;
+    }
+    split_styled_ranges(spans, &ranges, markers.style)
+}
+
+/// 行全体のバイト範囲 `ranges` にかかる部分をスパンごとに切り出し、`style` を重ねる
+fn split_styled_ranges<'a>(
+    spans: Vec<Span<'a>>,
+    ranges: &[(usize, usize)],
+    style: Style,
+) -> Vec<Span<'a>> {
+    let mut out = Vec::with_capacity(spans.len() + ranges.len() * 2);
+    let mut offset = 0;
+    for span in spans {
+        let len = span.content.len();
+        let (span_start, span_end) = (offset, offset + len);
+        offset = span_end;
+
+        let overlaps = |&&(start, end): &&(usize, usize)| start < span_end && end > span_start;
+        if !ranges.iter().any(|r| overlaps(&r)) {
+            out.push(span);
+            continue;
+        }
+
+        // 行全体のバイト範囲をスパン内の相対位置に変換して分割
+        let mut pos = 0;
+        for &(start, end) in ranges.iter().filter(overlaps) {
+            let start = start.max(span_start) - span_start;
+            let end = end.min(span_end) - span_start;
+            if start > pos {
+                out.push(slice_span(&span, pos, start, span.style));
+            }
+            out.push(slice_span(&span, start, end, span.style.patch(style)));
+            pos = end;
+        }
+        if pos < len {
+            out.push(slice_span(&span, pos, len, span.style));
+        }
+    }
+    out
+}
+
+/// Replace visible whitespace in the line content with dim glyphs.
+///
+/// Spaces become `·`; an expanded tab shows `→` in its first cell and keeps
+/// the remaining cells as padding. Each glyph replaces exactly one space, so
+/// display width (and therefore wrapping and comment anchoring) is unchanged.
+fn split_whitespace_spans<'a>(
+    spans: Vec<Span<'a>>,
+    tabs: &[(u32, u32)],
+    mode: ShowWhitespace,
+    content_start: usize,
+) -> Vec<Span<'a>> {
+    let region_start = match mode {
+        ShowWhitespace::None => return spans,
+        ShowWhitespace::All => content_start,
+        ShowWhitespace::Trailing => {
+            let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+            match text.get(content_start..) {
+                Some(content) => content_start + content.trim_end_matches(' ').len(),
+                None => return spans,
+            }
+        }
+    };
+    let glyph_style = Style::default().fg(Color::DarkGray);
+
+    let mut out = Vec::with_capacity(spans.len());
+    let mut offset = 0;
+    for span in spans {
+        let len = span.content.len();
+        let span_start = offset;
+        offset += len;
+        if offset <= region_start {
+            out.push(span);
+            continue;
+        }
+
+        // ' ' は 1 バイトなので UTF-8 境界を気にせずスライスできる
+        let mut pos = 0;
+        for (i, b) in span.content.bytes().enumerate() {
+            let abs = (span_start + i) as u32;
+            if b != b' ' || (span_start + i) < region_start {
+                continue;
+            }
+            let glyph = match tabs.iter().find(|&&(start, end)| abs >= start && abs < end) {
+                Some(&(start, _)) if start == abs => "→",
+                Some(_) => continue,
+                None => "·",
+            };
+            if i > pos {
+                out.push(slice_span(&span, pos, i, span.style));
+            }
+            out.push(Span::styled(glyph, span.style.patch(glyph_style)));
+            pos = i + 1;
+        }
+        if pos == 0 {
+            out.push(span);
+        } else if pos < len {
+            out.push(slice_span(&span, pos, len, span.style));
+        }
+    }
+    out
+}
+
+fn slice_span<'a>(span: &Span<'a>, start: usize, end: usize, style: Style) -> Span<'a> {
+    let content = match &span.content {
+        Cow::Borrowed(s) => Cow::Borrowed(&s[start..end]),
+        Cow::Owned(s) => Cow::Owned(s[start..end].to_owned()),
+    };
+    Span::styled(content, style)
+}
+
 /// Convert cached diff lines to renderable [`Line`]s using zero-copy borrowing.
 ///
 /// Resolves interned strings from the DiffCache's interner, avoiding heap
-/// allocations entirely. Comment markers (`● `) are injected at render time
-/// via iterator composition (no `Vec::insert`).
+/// allocations entirely. Comment markers (`● `, or `▸ ` for folded threads) are
+/// injected at render time via iterator composition (no `Vec::insert`).
 ///
 /// * `cache` – the DiffCache containing both lines and the interner.
 /// * `range` – the range of lines to render (may be a sub-range).
 /// * `selected_line` – absolute index of the currently selected line.
 /// * `comment_lines` – set of diff line indices that have comments (for `●` marker).
+/// * `opts` – line background, marker-word and whitespace rendering options.
 /// * `multiline_range` – 複数行選択範囲 (start, end) の inclusive range。None なら通常選択。
+///
+/// `opts.wrap` が有効なら 1 論理行が複数の [`Line`] になる（カーソルは論理行単位のまま）。
 pub fn render_cached_lines<'a>(
     cache: &'a DiffCache,
     range: std::ops::Range<usize>,
     selected_line: usize,
     comment_lines: &HashSet<usize>,
-    bg_color: bool,
+    opts: LineRenderOptions<'_>,
     multiline_range: Option<(usize, usize)>,
     content_width: u16,
 ) -> Vec<Line<'a>> {
@@ -1092,71 +1569,238 @@ pub fn render_cached_lines<'a>(
     // Handle case where start > end after clamping (produces empty slice)
     let safe_range = safe_start..safe_start.max(safe_end);
     let cw = content_width as usize;
+    let gutter_width = if opts.gutter == Gutter::None {
+        0
+    } else {
+        gutter_number_width(cache, opts.line_numbers)
+    };
 
     cache.lines[safe_range.clone()]
         .iter()
         .enumerate()
-        .map(|(rel_idx, cached)| {
+        .flat_map(|(rel_idx, cached)| {
             let abs_idx = safe_range.start + rel_idx;
             let is_selected = abs_idx == selected_line;
+            let distance = abs_idx.abs_diff(selected_line);
             let is_in_multiline = multiline_range
                 .map(|(start, end)| abs_idx >= start && abs_idx <= end)
                 .unwrap_or(false);
 
-            let marker = if comment_lines.contains(&abs_idx) {
-                Some(Span::styled("● ", Style::default().fg(Color::Yellow)))
-            } else {
-                None
-            };
-            let base = cached
-                .spans
+            let marker = comment_marker(abs_idx, comment_lines, opts.collapsed_comments);
+            // 折り返した継続行の字下げ: ガター・コメント記号・diff 記号の後ろに揃える
+            let wrap_indent = UnicodeWidthStr::width(
+                gutter_span(cached, &opts, gutter_width, distance)
+                    .map(|s| s.content)
+                    .unwrap_or_default()
+                    .as_ref(),
+            ) + marker.as_ref().map_or(0, |m| m.width())
+                + cached.line_type.marker().map_or(0, str::len);
+            let base = cached.spans.iter().map(|s| {
+                Span::styled(
+                    cache.resolve(s.content),
+                    opts.colors.recolor(s.style, cached.line_type),
+                )
+            });
+            // 検索一致はキャッシュ行テキスト基準なので、空白記号等の置換より先に分割する
+            let search_ranges: SmallVec<[(usize, usize); 2]> = opts
+                .search
+                .map(|matches| search_matches_on_line(matches, abs_idx))
+                .unwrap_or_default()
                 .iter()
-                .map(|s| Span::styled(cache.resolve(s.content), s.style));
-            let mut all_spans: Vec<Span<'_>> = marker.into_iter().chain(base).collect();
+                .map(|m| (m.range.start, m.range.end))
+                .collect();
+            let error_ranges = match cached.line_type.marker() {
+                Some(prefix)
+                    if cached.line_type == LineType::Added
+                        && (opts.trailing_whitespace || opts.tab_indent) =>
+                {
+                    let text: String = cached
+                        .spans
+                        .iter()
+                        .map(|s| cache.resolve(s.content))
+                        .collect();
+                    whitespace_error_ranges(&text, &cached.tabs, prefix.len(), &opts)
+                }
+                _ => SmallVec::new(),
+            };
+            let with_search = |mut spans: Vec<Span<'a>>| {
+                if !search_ranges.is_empty() {
+                    spans = split_styled_ranges(spans, &search_ranges, SEARCH_MATCH_STYLE);
+                }
+                if !error_ranges.is_empty() {
+                    spans = split_styled_ranges(spans, &error_ranges, WHITESPACE_ERROR_STYLE);
+                }
+                spans
+            };
+            let mut all_spans: Vec<Span<'_>> = match cached.line_type.marker() {
+                // diff 記号 (+/-/空白) の後ろだけを対象にする
+                Some(prefix) if opts.ansi || opts.whitespace != ShowWhitespace::None => {
+                    let mut spans: Vec<Span<'_>> = with_search(base.collect());
+                    let mut tabs = Cow::Borrowed(cached.tabs.as_slice());
+                    if opts.ansi {
+                        // エスケープを取り除くとバイト位置がずれるので、タブの範囲も合わせる
+                        if !tabs.is_empty() {
+                            let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+                            tabs = Cow::Owned(strip_ansi_ranges(&text, prefix.len(), &tabs));
+                        }
+                        spans = split_ansi_spans(spans, prefix.len());
+                    }
+                    marker
+                        .into_iter()
+                        .chain(split_whitespace_spans(
+                            spans,
+                            &tabs,
+                            opts.whitespace,
+                            prefix.len(),
+                        ))
+                        .collect()
+                }
+                _ if !search_ranges.is_empty() || !error_ranges.is_empty() => marker
+                    .into_iter()
+                    .chain(with_search(base.collect()))
+                    .collect(),
+                _ => marker.into_iter().chain(base).collect(),
+            };
+
+            if let Some(markers) = opts.markers {
+                if cached.line_type == LineType::Added {
+                    all_spans = split_marker_spans(all_spans, markers);
+                }
+            }
+            let is_conflict = cache.conflict_lines.binary_search(&abs_idx).is_ok();
+            if is_conflict && !is_selected && !is_in_multiline {
+                all_spans = all_spans
+                    .into_iter()
+                    .map(|span| span.patch_style(CONFLICT_MARKER_STYLE))
+                    .collect();
+            }
+            // 移動したコードは追加・削除の色ではなく移動の色で表示する
+            let moved = moved_block_at(&cache.moved_blocks, abs_idx).map(|(_, side)| side);
+            if let Some(side) = moved.filter(|_| !is_selected && !is_in_multiline) {
+                let style = match side {
+                    MovedSide::From => MOVED_FROM_STYLE,
+                    MovedSide::To => MOVED_TO_STYLE,
+                };
+                all_spans = all_spans
+                    .into_iter()
+                    .map(|span| span.patch_style(style))
+                    .collect();
+            }
+            if moved.is_some() {
+                all_spans.extend(moved_link_span(cache, abs_idx));
+            }
+            if let Some(gutter) = gutter_span(cached, &opts, gutter_width, distance) {
+                all_spans.insert(0, gutter);
+            }
 
             // Pad trailing spaces so a line-level background color extends to the
             // visible width. Only pad when a bg will actually be applied — the
             // padded spaces inherit the line's bg via Style merging, so they
             // become the colored fill.
             let line_bg_will_be_set = is_in_multiline
-                || (!is_selected && bg_color && cached.line_type.bg_color().is_some());
-            if line_bg_will_be_set && cw > 0 {
-                let display_width: usize = all_spans
-                    .iter()
-                    .map(|s| UnicodeWidthStr::width(s.content.as_ref()))
-                    .sum();
-                let pad_len = (cw - (display_width % cw)) % cw;
-                if pad_len > 0 {
-                    all_spans.push(Span::raw(" ".repeat(pad_len)));
+                || (!is_selected
+                    && (is_conflict
+                        || (opts.bg_color
+                            && moved.is_none()
+                            && opts.colors.bg(cached.line_type).is_some())));
+            let pad = move |mut spans: Vec<Span<'a>>| {
+                if line_bg_will_be_set && cw > 0 {
+                    let display_width: usize = spans
+                        .iter()
+                        .map(|s| UnicodeWidthStr::width(s.content.as_ref()))
+                        .sum();
+                    let pad_len = (cw - (display_width % cw)) % cw;
+                    if pad_len > 0 {
+                        spans.push(Span::raw(" ".repeat(pad_len)));
+                    }
                 }
-            }
+                spans
+            };
 
-            let line = Line::from(all_spans);
-            if is_in_multiline {
+            let line_style = if is_in_multiline {
                 if is_selected {
-                    line.style(
+                    Some(
                         Style::default()
                             .bg(Color::Rgb(0, 40, 80))
                             .add_modifier(Modifier::REVERSED),
                     )
                 } else {
-                    line.style(Style::default().bg(Color::Rgb(0, 40, 80)))
+                    Some(Style::default().bg(Color::Rgb(0, 40, 80)))
                 }
             } else if is_selected {
-                line.style(Style::default().add_modifier(Modifier::REVERSED))
-            } else if bg_color {
-                if let Some(bg) = cached.line_type.bg_color() {
-                    line.style(Style::default().bg(bg))
-                } else {
-                    line
-                }
+                Some(Style::default().add_modifier(Modifier::REVERSED))
+            } else if is_conflict {
+                Some(Style::default().bg(CONFLICT_LINE_BG))
+            } else if opts.bg_color && moved.is_none() {
+                opts.colors
+                    .bg(cached.line_type)
+                    .map(|bg| Style::default().bg(bg))
             } else {
-                line
-            }
+                None
+            };
+
+            let rows: SmallVec<[Vec<Span<'a>>; 1]> = if opts.wrap && cw > 0 {
+                wrap_spans(all_spans, cw, wrap_indent).into_iter().collect()
+            } else {
+                smallvec![all_spans]
+            };
+            rows.into_iter().map(move |spans| {
+                let line = Line::from(pad(spans));
+                match line_style {
+                    Some(style) => line.style(style),
+                    None => line,
+                }
+            })
         })
         .collect()
 }
 
+/// スタイル付きの 1 行を表示幅 `width` ごとの行に分割する
+///
+/// 文字境界で切るため、span のスタイルは分割後もそのまま引き継がれる。2 行目以降は
+/// 先頭に `indent` 桁の空白を置き、その分だけ本文の幅を狭める（`indent` が幅に収まらない場合は 0）。
+pub(crate) fn wrap_spans<'a>(
+    spans: Vec<Span<'a>>,
+    width: usize,
+    indent: usize,
+) -> Vec<Vec<Span<'a>>> {
+    use unicode_width::UnicodeWidthChar;
+
+    let indent = if indent < width { indent } else { 0 };
+    let mut rows: Vec<Vec<Span<'a>>> = vec![Vec::new()];
+    let mut used = 0;
+    // 幅に収まらない全角文字でも 1 行に最低 1 文字は置く（無限に改行しない）
+    let mut row_has_text = false;
+    for span in spans {
+        let mut start = 0;
+        for (i, c) in span.content.char_indices() {
+            let w = c.width().unwrap_or(0);
+            if used + w > width && row_has_text {
+                if i > start {
+                    let piece = slice_span(&span, start, i, span.style);
+                    rows.last_mut().unwrap().push(piece);
+                }
+                let mut row = Vec::new();
+                if indent > 0 {
+                    row.push(Span::raw(" ".repeat(indent)));
+                }
+                rows.push(row);
+                used = indent;
+                start = i;
+            }
+            used += w;
+            row_has_text = true;
+        }
+        if start == 0 {
+            rows.last_mut().unwrap().push(span);
+        } else if start < span.content.len() {
+            let piece = slice_span(&span, start, span.content.len(), span.style);
+            rows.last_mut().unwrap().push(piece);
+        }
+    }
+    rows
+}
+
 pub fn render(frame: &mut Frame, app: &App) {
     if app.cmt.comment_panel_open {
         render_with_inline_comment(frame, app);
@@ -1233,14 +1877,235 @@ fn render_with_inline_comment(frame: &mut Frame, app: &App) {
     }
 }
 
+/// Header suffix for the hunk filter: the query being typed, or the applied match count.
+pub(crate) fn hunk_filter_header_suffix(app: &App) -> String {
+    let Some(filter) = app.current_hunk_filter() else {
+        return String::new();
+    };
+    let total = filter.hunks.len();
+    let matched = filter.matched_count();
+    if filter.input_active {
+        format!("  /{}│ ({}/{} hunks)", filter.query, matched, total)
+    } else if filter.is_hiding() {
+        format!("  [{}/{} hunks match \"{}\"]", matched, total, filter.query)
+    } else {
+        String::new()
+    }
+}
+
+/// Header suffix showing the file's line ending when it is not LF.
+fn line_ending_header_suffix(app: &App) -> String {
+    let line_ending = app
+        .diff_store
+        .current
+        .as_ref()
+        .filter(|cache| cache.file_index == app.selected_file)
+        .map_or(LineEnding::Lf, |cache| cache.line_ending);
+    match line_ending {
+        LineEnding::Lf => String::new(),
+        LineEnding::Crlf => format!("  [{}]", line_ending.label()),
+    }
+}
+
+/// Header badge shown while the file has unresolved conflict markers.
+fn conflict_header_suffix(app: &App) -> &'static str {
+    let has_conflicts = app
+        .diff_store
+        .current
+        .as_ref()
+        .filter(|cache| cache.file_index == app.selected_file)
+        .is_some_and(|cache| !cache.conflict_lines.is_empty());
+    if has_conflicts {
+        "  (conflicts present)"
+    } else {
+        ""
+    }
+}
+
+/// Header suffix for the in-diff search: the query being typed, or the match count.
+pub(crate) fn diff_search_header_suffix(app: &App) -> String {
+    let Some(search) = app.current_diff_search() else {
+        return String::new();
+    };
+    if search.input_active {
+        format!(
+            "  Search: {}│ ({} matches)",
+            search.query,
+            search.matches.len()
+        )
+    } else if !search.query.is_empty() {
+        format!(
+            "  [\"{}\": {} matches on {} lines]",
+            search.query,
+            search.matches.len(),
+            search.matched_line_count()
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// diff の取得が中断したファイルの表示（「差分なし」と区別し、再取得キーを案内する）
+pub(crate) fn incomplete_patch_lines(app: &App) -> Vec<Line<'static>> {
+    vec![
+        Line::from(Span::styled(
+            "Diff fetch was interrupted (incomplete)",
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "Press {} to retry this file",
+                app.config.keybindings.reload_current_file.display()
+            ),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ]
+}
+
+/// `start` から表示行を組み立てる。hunk フィルタで隠れた hunk と
+/// 改行コードのみの hunk 本体は要約 1 行に畳み、行インデックス（= 実ファイル
+/// 行番号との対応）は変えない。
+pub(crate) fn render_diff_window<'a>(
+    app: &App,
+    cache: &'a DiffCache,
+    start: usize,
+    visible_height: usize,
+    content_width: u16,
+) -> Vec<Line<'a>> {
+    let line_count = cache.lines.len();
+    let budget = visible_height + 10;
+    let multiline_range = app
+        .multiline_selection
+        .as_ref()
+        .map(|s| (s.start(), s.end()));
+    let opts = LineRenderOptions {
+        search: app
+            .current_diff_search()
+            .map(|search| search.matches.as_slice()),
+        wrap: app.diff_wrap,
+        collapsed_comments: Some(&app.cmt.file_collapsed_comment_lines),
+        ..LineRenderOptions::from_config(&app.config.diff, &app.config.diff_colors)
+    };
+    let render = |range: std::ops::Range<usize>| {
+        render_cached_lines(
+            cache,
+            range,
+            app.diff_scroll.selected_line,
+            &app.cmt.file_comment_lines,
+            opts,
+            multiline_range,
+            content_width,
+        )
+    };
+
+    let filter = app.current_hunk_filter().filter(|f| f.is_hiding());
+    if let Some(filter) = filter.filter(|f| f.matched_count() == 0) {
+        return vec![Line::from(Span::styled(
+            format!("No hunks match \"{}\"", filter.query),
+            Style::default().fg(Color::DarkGray),
+        ))];
+    }
+    if filter.is_none()
+        && (!app.line_endings_collapsed() || cache.line_ending_hunks.is_empty())
+        && app.current_folded_hunks().is_empty()
+        && (!app.unchanged_collapsed() || cache.context_runs.is_empty())
+    {
+        return render(start..(start + budget).min(line_count));
+    }
+
+    let summary_style = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::with_capacity(budget);
+    let mut i = start;
+    while i < line_count && lines.len() < budget {
+        if let Some(hidden) = app.unchanged_placeholder_at(i) {
+            let style = if i == app.diff_scroll.selected_line {
+                summary_style.add_modifier(Modifier::REVERSED)
+            } else {
+                summary_style
+            };
+            lines.push(Line::from(Span::styled(
+                format!("┈┈ {} unchanged lines ┈┈ Enter to expand", hidden),
+                style,
+            )));
+            i += 1;
+            continue;
+        }
+        match app.collapsed_lines_at(i) {
+            Some(CollapsedLines::FilteredHunk(_)) => {
+                let mut hidden = 0;
+                while let Some(CollapsedLines::FilteredHunk(h)) = app.collapsed_lines_at(i) {
+                    hidden += 1;
+                    i = h.range.end;
+                }
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "┈┈ {} hunk{} hidden ┈┈",
+                        hidden,
+                        if hidden == 1 { "" } else { "s" }
+                    ),
+                    summary_style,
+                )));
+            }
+            Some(CollapsedLines::LineEndings(h)) => {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "┈┈ line endings changed ({}), {} line{} ┈┈ {} to expand",
+                        h.change.label(),
+                        h.pairs,
+                        if h.pairs == 1 { "" } else { "s" },
+                        app.config.keybindings.toggle_line_endings.display()
+                    ),
+                    summary_style,
+                )));
+                i = h.body.end;
+            }
+            // 隠した行数はヘッダ行側 / プレースホルダ行に表示済み
+            Some(CollapsedLines::FoldedHunk(body)) => i = body.end,
+            Some(CollapsedLines::Unchanged(range)) => i = range.end,
+            None => {
+                let mut end = i + 1;
+                while end < line_count
+                    && end - i < budget - lines.len()
+                    && app.collapsed_lines_at(end).is_none()
+                    && app.unchanged_placeholder_at(end).is_none()
+                {
+                    end += 1;
+                }
+                lines.extend(render(i..end));
+                if let Some(CollapsedLines::FoldedHunk(body)) = app.collapsed_lines_at(end) {
+                    if let Some(header) = lines.last_mut() {
+                        header.spans.push(Span::styled(
+                            format!(" ({} lines hidden)", body.len()),
+                            summary_style,
+                        ));
+                    }
+                }
+                i = end;
+            }
+        }
+    }
+    lines
+}
+
 pub(crate) fn render_header(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let header_text = app
         .files()
         .get(app.selected_file)
         .map(|file| {
             format!(
-                "{} (+{} -{})",
-                file.filename, file.additions, file.deletions
+                "{} (+{} -{}){}{}{}{}{}",
+                file.filename,
+                file.additions,
+                file.deletions,
+                line_ending_header_suffix(app),
+                conflict_header_suffix(app),
+                if app.hide_whitespace {
+                    "  [whitespace hidden]"
+                } else {
+                    ""
+                },
+                hunk_filter_header_suffix(app),
+                diff_search_header_suffix(app)
             )
         })
         .unwrap_or_else(|| "No file selected".to_string());
@@ -1251,6 +2116,18 @@ pub(crate) fn render_header(frame: &mut Frame, app: &App, area: ratatui::layout:
 }
 
 pub(crate) fn render_diff_content(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    app.mouse_layout.diff.set(Some(area));
+    if app.diff_layout == DiffLayout::Split {
+        if let Some(patch) = app
+            .files()
+            .get(app.selected_file)
+            .and_then(|f| f.patch.as_deref())
+        {
+            split::render_split_diff(frame, app, area, patch);
+            return;
+        }
+    }
+
     let visible_height = area.height.saturating_sub(2) as usize;
 
     // Try to use cached lines if available
@@ -1261,18 +2138,11 @@ pub(crate) fn render_diff_content(frame: &mut Frame, app: &App, area: ratatui::l
         // Bound to visible viewport + buffer for wrap handling to avoid O(n) per frame.
         let max_scroll = line_count.saturating_sub(visible_height);
         let start = app.diff_scroll.scroll_offset.min(max_scroll);
-        let end = (start + visible_height + 10).min(line_count);
-        let multiline_range = app
-            .multiline_selection
-            .as_ref()
-            .map(|s| (s.start(), s.end()));
-        let rendered = render_cached_lines(
+        let rendered = render_diff_window(
+            app,
             cache,
-            start..end,
-            app.diff_scroll.selected_line,
-            &app.cmt.file_comment_lines,
-            app.config.diff.bg_color,
-            multiline_range,
+            start,
+            visible_height,
             area.width.saturating_sub(2),
         );
         (rendered, 0u16)
@@ -1289,11 +2159,14 @@ pub(crate) fn render_diff_content(frame: &mut Frame, app: &App, area: ratatui::l
                     &f.filename,
                     theme_name,
                     &app.cmt.file_comment_lines,
+                    &app.cmt.file_collapsed_comment_lines,
                     app.config.diff.tab_width,
                 ),
                 None => {
                     if app.is_lazy_diff_loading() {
                         vec![Line::from("Loading diff...")]
+                    } else if app.is_current_patch_incomplete() {
+                        incomplete_patch_lines(app)
                     } else {
                         vec![Line::from("No diff available")]
                     }
@@ -1304,6 +2177,7 @@ pub(crate) fn render_diff_content(frame: &mut Frame, app: &App, area: ratatui::l
         (rendered, app.diff_scroll.scroll_offset as u16)
     };
 
+    let line_count = lines.len() as u16;
     let diff_block = Paragraph::new(lines)
         .block(Block::default().borders(Borders::ALL))
         .wrap(Wrap { trim: false })
@@ -1311,25 +2185,23 @@ pub(crate) fn render_diff_content(frame: &mut Frame, app: &App, area: ratatui::l
 
     frame.render_widget(diff_block, area);
 
+    crate::ui::image_preview::request_below_text(app, area, line_count);
+
     if let Some(ref cache) = app.diff_store.current {
         let total_lines = cache.lines.len();
         let visible_height = area.height.saturating_sub(2) as usize;
         let max_scroll = total_lines.saturating_sub(visible_height);
         if max_scroll > 0 {
-            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .begin_symbol(Some("▲"))
-                .end_symbol(Some("▼"));
-
-            let clamped_position = app.diff_scroll.scroll_offset.min(max_scroll);
-            let mut scrollbar_state = ScrollbarState::new(max_scroll).position(clamped_position);
-
-            frame.render_stateful_widget(
-                scrollbar,
+            // スクロールバーの代わりに変更密度のミニマップを右枠に重ねる
+            minimap::render_minimap(
+                frame.buffer_mut(),
                 area.inner(Margin {
                     vertical: 1,
                     horizontal: 0,
                 }),
-                &mut scrollbar_state,
+                cache.lines.iter().map(|line| line.line_type),
+                app.diff_scroll.scroll_offset.min(max_scroll),
+                visible_height,
             );
         }
     }
@@ -1352,6 +2224,7 @@ fn parse_patch_to_lines(
     filename: &str,
     theme_name: &str,
     comment_lines: &HashSet<usize>,
+    collapsed_comments: &HashSet<usize>,
     tab_width: u8,
 ) -> Vec<Line<'static>> {
     // Build DiffCache and then convert to Lines
@@ -1373,20 +2246,13 @@ fn parse_patch_to_lines(
         .enumerate()
         .map(|(i, cached)| {
             let is_selected = i == selected_line;
-            let marker = if comment_lines.contains(&i) {
-                Some(Span::styled(
-                    "● ".to_string(),
-                    if is_selected {
-                        Style::default()
-                            .fg(Color::Yellow)
-                            .add_modifier(Modifier::REVERSED)
-                    } else {
-                        Style::default().fg(Color::Yellow)
-                    },
-                ))
-            } else {
-                None
-            };
+            let marker = comment_marker(i, comment_lines, Some(collapsed_comments)).map(|m| {
+                if is_selected {
+                    m.patch_style(Modifier::REVERSED)
+                } else {
+                    m
+                }
+            });
             let base = cached.spans.iter().map(|s| {
                 let text = cache.resolve(s.content).to_string();
                 let mut style = s.style;
@@ -1407,6 +2273,7 @@ fn build_line_spans(
     content: &str,
     highlighter: &mut Option<HighlightLines<'_>>,
     interner: &mut Rodeo,
+    style_cache: &mut ThemeStyleCache,
 ) -> SpanVec {
     let fg_style = match line_type.fg_color() {
         Some(c) => Style::default().fg(c),
@@ -1418,7 +2285,8 @@ fn build_line_spans(
             style: fg_style,
         };
         let fallback_color = line_type.fg_color().unwrap_or(Color::Reset);
-        let code_spans = highlight_or_fallback(content, highlighter, fallback_color, interner);
+        let code_spans =
+            highlight_or_fallback(content, highlighter, fallback_color, interner, style_cache);
         let mut spans: SpanVec = smallvec![marker];
         spans.extend(code_spans);
         spans
@@ -1435,10 +2303,11 @@ fn highlight_or_fallback(
     highlighter: &mut Option<HighlightLines<'_>>,
     fallback_color: Color,
     interner: &mut Rodeo,
+    style_cache: &mut ThemeStyleCache,
 ) -> SpanVec {
     match highlighter {
         Some(h) => {
-            let spans = highlight_code_line(content, h, interner);
+            let spans = highlight_code_line(content, h, interner, style_cache);
             if spans.is_empty() {
                 smallvec![InternedSpan {
                     content: interner.get_or_intern(content),
@@ -1471,7 +2340,7 @@ fn render_footer(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
 
 /// Render inline comments panel for current line
 fn render_inline_comments(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let indices = app.get_comment_indices_at_current_line();
+    let indices = app.visible_inline_comment_indices();
 
     let mut lines: Vec<Line> = vec![];
 
@@ -1505,6 +2374,17 @@ fn render_inline_comments(frame: &mut Frame, app: &App, area: ratatui::layout::R
                 Span::raw("")
             };
 
+            if app.is_thread_collapsed(idx) {
+                lines.push(Line::from(vec![
+                    indicator,
+                    Span::styled(
+                        app.collapsed_thread_summary(idx),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]));
+                continue;
+            }
+
             lines.push(Line::from(vec![
                 indicator,
                 Span::styled(
@@ -1515,6 +2395,14 @@ fn render_inline_comments(frame: &mut Frame, app: &App, area: ratatui::layout::R
                     format!(" (line {})", comment.line.unwrap_or(0)),
                     Style::default().fg(Color::DarkGray),
                 ),
+                if app.is_pending_comment(comment.id) {
+                    Span::styled(
+                        " pending (not submitted)",
+                        Style::default().fg(Color::Yellow),
+                    )
+                } else {
+                    Span::raw("")
+                },
             ]));
 
             for line in comment.body.lines() {
@@ -1524,7 +2412,7 @@ fn render_inline_comments(frame: &mut Frame, app: &App, area: ratatui::layout::R
         }
     }
 
-    let title = "Comments (j/k/↑↓: scroll, c: comment, s: suggest, r: reply)";
+    let title = "Comments (j/k/↑↓: scroll, c: comment, s: suggest, r: reply, za/zM/zR: fold)";
     let total_lines = lines.len();
 
     let block = Block::default()
@@ -1608,36 +2496,97 @@ pub fn render_text_input(frame: &mut Frame, app: &App) {
                 "Type your comment here...",
             );
         }
+        Some(InputMode::PrListFilters) => {
+            let hint = Paragraph::new(vec![
+                Line::from("author:<login>  assignee:<login>  label:<name>"),
+                Line::from(Span::styled(
+                    "Separate filters with spaces; repeat label: for more labels. \
+                     Submit an empty query to clear.",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("PR filters ({})", app.repo)),
+            );
+            frame.render_widget(hint, chunks[1]);
+            render_text_input_area(frame, app, chunks[2], "PR Filters", "author:@me label:bug");
+        }
+        Some(InputMode::PrPeople(role)) => {
+            let teams = if *role == crate::github::PrPeopleRole::Reviewer {
+                "; org/team requests a team"
+            } else {
+                ""
+            };
+            let hint = Paragraph::new(vec![
+                Line::from("alice, bob  @me"),
+                Line::from(Span::styled(
+                    format!("Separate logins with commas or spaces{}.", teams),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "Add {} to #{}",
+                role.label(),
+                app.pr_number.unwrap_or_default()
+            )));
+            frame.render_widget(hint, chunks[1]);
+            render_text_input_area(frame, app, chunks[2], "Logins", "alice, bob");
+        }
         None => {}
     }
 }
 
 /// Render context info for comment input
-fn render_comment_context(
-    frame: &mut Frame,
-    app: &App,
-    area: ratatui::layout::Rect,
-    ctx: &LineInputContext,
-) {
-    let filename = app
-        .files()
-        .get(ctx.file_index)
-        .map(|f| f.filename.as_str())
-        .unwrap_or("Unknown file");
-
-    let lines = vec![
-        Line::from(vec![
-            Span::styled("File: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(filename, Style::default().fg(Color::Cyan)),
-        ]),
-        Line::from(vec![
+fn render_comment_context(
+    frame: &mut Frame,
+    app: &App,
+    area: ratatui::layout::Rect,
+    ctx: &LineInputContext,
+) {
+    let file = app.files().get(ctx.file_index);
+    let filename = file.map(|f| f.filename.as_str()).unwrap_or("Unknown file");
+    let anchor = app
+        .config
+        .diff
+        .comment_anchor_preview
+        .then(|| app.comment_anchor_preview(ctx))
+        .flatten();
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled("File: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(filename, Style::default().fg(Color::Cyan)),
+    ])];
+    match anchor {
+        Some(anchor) => {
+            let (label, range) = match ctx.start_line_number {
+                Some(start) if start != anchor.line => {
+                    ("Lines: ", format!("{}-{}", start, anchor.line))
+                }
+                _ => ("Line: ", anchor.line.to_string()),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::DarkGray)),
+                Span::styled(range, Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!(" ({})", anchor.side.as_str()),
+                    Style::default().fg(Color::Magenta),
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Code: ", Style::default().fg(Color::DarkGray)),
+                Span::raw(anchor.content),
+            ]));
+        }
+        None => lines.push(Line::from(vec![
             Span::styled("Line: ", Style::default().fg(Color::DarkGray)),
             Span::styled(
                 ctx.line_number.to_string(),
                 Style::default().fg(Color::Yellow),
             ),
-        ]),
-    ];
+        ])),
+    }
 
     let paragraph = Paragraph::new(lines)
         .block(
@@ -1649,6 +2598,14 @@ fn render_comment_context(
     frame.render_widget(paragraph, area);
 }
 
+/// 本文がソフト上限を超えたときにタイトルへ付ける警告
+fn comment_length_warning_suffix(app: &App) -> String {
+    match app.comment_length_warning() {
+        Some((len, limit)) => format!("  ⚠ {} chars (over {})", len, limit),
+        None => String::new(),
+    }
+}
+
 /// Render TextArea with dynamic title and placeholder
 fn render_text_input_area(
     frame: &mut Frame,
@@ -1658,7 +2615,12 @@ fn render_text_input_area(
     placeholder: &str,
 ) {
     let submit_key = app.input_text_area.submit_key_display();
-    let title = format!("{} ({}: submit, Esc: cancel)", label, submit_key);
+    let title = format!(
+        "{} ({}: submit, Esc: cancel){}",
+        label,
+        submit_key,
+        comment_length_warning_suffix(app)
+    );
     app.input_text_area
         .render_with_title(frame, area, &title, placeholder);
 }
@@ -1666,7 +2628,11 @@ fn render_text_input_area(
 /// Render suggestion input TextArea with syntax highlighting
 fn render_suggestion_input(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let submit_key = app.input_text_area.submit_key_display();
-    let title = format!("Suggested code ({}: submit, Esc: cancel)", submit_key);
+    let title = format!(
+        "Suggested code ({}: submit, Esc: cancel){}",
+        submit_key,
+        comment_length_warning_suffix(app)
+    );
 
     if let Some(ref cache) = app.suggestion_highlight_cache {
         app.input_text_area.render_highlighted(
@@ -2319,7 +3285,7 @@ mod tests {
             0..plain.lines.len(),
             0,
             &comment_lines,
-            false,
+            LineRenderOptions::default(),
             None,
             0,
         );
@@ -2328,7 +3294,7 @@ mod tests {
             0..highlighted.lines.len(),
             0,
             &comment_lines,
-            false,
+            LineRenderOptions::default(),
             None,
             0,
         );
@@ -2368,6 +3334,306 @@ mod tests {
             !no_comment_text.contains('●'),
             "non-comment line should not have marker"
         );
+
+        // スレッドがすべて折りたたまれた行は ▸ で表示する
+        let collapsed: HashSet<usize> = [6].into();
+        let folded = render_cached_lines(
+            &plain,
+            0..plain.lines.len(),
+            0,
+            &comment_lines,
+            LineRenderOptions {
+                collapsed_comments: Some(&collapsed),
+                ..Default::default()
+            },
+            None,
+            0,
+        );
+        assert_eq!(folded[4].spans[0].content, "● ");
+        assert_eq!(folded[6].spans[0].content, "▸ ");
+    }
+
+    const SAMPLE_PATCH: &str =
+        "@@ -1,4 +1,5 @@\n line 1\n-old line 2\n+new line 2\n+added line\n line 3";
+
+    #[test]
+    fn test_diff_cache_tracks_old_and_new_line_numbers() {
+        let expected = vec![
+            (None, None),
+            (Some(1), Some(1)),
+            (Some(2), None),
+            (None, Some(2)),
+            (None, Some(3)),
+            (Some(3), Some(4)),
+        ];
+        let numbers = |cache: &DiffCache| -> Vec<(Option<u32>, Option<u32>)> {
+            cache
+                .lines
+                .iter()
+                .map(|l| (l.old_line_number, l.new_line_number))
+                .collect()
+        };
+
+        assert_eq!(numbers(&build_plain_diff_cache(SAMPLE_PATCH, 4)), expected);
+        let mut parser_pool = ParserPool::new();
+        let highlighted = build_diff_cache(
+            SAMPLE_PATCH,
+            "sample.txt",
+            "base16-ocean.dark",
+            &mut parser_pool,
+            false,
+            4,
+        );
+        assert_eq!(numbers(&highlighted), expected);
+    }
+
+    #[test]
+    fn test_render_cached_lines_gutter_columns() {
+        let cache = build_plain_diff_cache(SAMPLE_PATCH, 4);
+        let render = |gutter| -> Vec<String> {
+            let opts = LineRenderOptions {
+                gutter,
+                ..LineRenderOptions::default()
+            };
+            render_cached_lines(&cache, 0..6, 99, &HashSet::new(), opts, None, 0)
+                .iter()
+                .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+
+        assert_eq!(render(Gutter::None)[2], "-old line 2");
+        assert_eq!(
+            render(Gutter::New),
+            vec![
+                "  @@ -1,4 +1,5 @@",
+                "1  line 1",
+                "  -old line 2",
+                "2 +new line 2",
+                "3 +added line",
+                "4  line 3",
+            ]
+        );
+        assert_eq!(
+            render(Gutter::Both),
+            vec![
+                "    @@ -1,4 +1,5 @@",
+                "1 1  line 1",
+                "2   -old line 2",
+                "  2 +new line 2",
+                "  3 +added line",
+                "3 4  line 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_combined_diff_strips_both_prefix_columns() {
+        let patch = "diff --cc src/lib.rs\n\
+                     index 1111111,2222222..3333333\n\
+                     --- a/src/lib.rs\n\
+                     +++ b/src/lib.rs\n\
+                     @@@ -1,2 -1,2 +1,3 @@@\n  \
+                     fn main() {\n\
+                     - let a = 1;\n \
+                     +let b = 2;\n\
+                     ++    let c = 3;";
+        let render = |cache: &DiffCache| -> Vec<String> {
+            let opts = LineRenderOptions {
+                gutter: Gutter::Both,
+                ..LineRenderOptions::default()
+            };
+            render_cached_lines(cache, 4..9, 99, &HashSet::new(), opts, None, 0)
+                .iter()
+                .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+        // 左の行番号は 1 つ目の親の行（" +" は 2 つ目の親にだけ無かった行）
+        let expected = vec![
+            "    @@@ -1,2 -1,2 +1,3 @@@",
+            "1 1   fn main() {",
+            "2   - let a = 1;",
+            "3 2  +let b = 2;",
+            "  3 ++    let c = 3;",
+        ];
+
+        let plain = build_plain_diff_cache(patch, 4);
+        assert_eq!(render(&plain), expected);
+        assert_eq!(
+            plain.lines[8].line_type,
+            LineType::Combined {
+                first: crate::diff::ChangeColumn::Added,
+                second: crate::diff::ChangeColumn::Added,
+            }
+        );
+        assert_eq!(plain.lines[8].spans[0].style.fg, Some(Color::Green));
+
+        // シンタックスハイライト経路（tree-sitter）でも接頭辞 2 列を外してから解析する
+        let mut parser_pool = ParserPool::new();
+        let highlighted =
+            build_diff_cache(patch, "src/lib.rs", "Dracula", &mut parser_pool, false, 4);
+        assert_eq!(render(&highlighted), expected);
+        let let_span = highlighted.lines[8]
+            .spans
+            .iter()
+            .find(|s| highlighted.resolve(s.content) == "let");
+        assert!(
+            let_span.is_some(),
+            "combined added line should be highlighted"
+        );
+    }
+
+    #[test]
+    fn test_gutter_text_line_number_modes_with_cursor_at_line_10() {
+        let patch: String = std::iter::once("@@ -1,14 +1,14 @@".to_string())
+            .chain((1..=14).map(|i| format!(" line {}", i)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let cache = build_plain_diff_cache(&patch, 4);
+        let cursor = 10;
+        let text = |mode, gutter, idx: usize| {
+            let width = gutter_number_width(&cache, mode);
+            gutter_text(&cache.lines[idx], gutter, mode, width, idx.abs_diff(cursor)).unwrap()
+        };
+
+        let rows = |mode| -> Vec<String> {
+            [0, 7, 10, 12]
+                .into_iter()
+                .map(|idx| text(mode, Gutter::New, idx))
+                .collect()
+        };
+        assert_eq!(
+            rows(LineNumbers::Absolute),
+            vec!["   ", " 7 ", "10 ", "12 "]
+        );
+        assert_eq!(
+            rows(LineNumbers::Relative),
+            vec!["10 ", " 3 ", " 0 ", " 2 "]
+        );
+        assert_eq!(rows(LineNumbers::Hybrid), vec!["10 ", " 3 ", "10 ", " 2 "]);
+
+        // 2 列表示でも距離はガター全体の右端に 1 つだけ出す
+        assert_eq!(text(LineNumbers::Relative, Gutter::Both, 7), "    3 ");
+        assert_eq!(text(LineNumbers::Hybrid, Gutter::Both, 10), "10 10 ");
+        assert_eq!(
+            gutter_text(&cache.lines[7], Gutter::None, LineNumbers::Relative, 2, 3),
+            None
+        );
+    }
+
+    #[test]
+    fn test_wrap_spans_splits_at_width_and_keeps_styles() {
+        let red = Style::default().fg(Color::Red);
+        let green = Style::default().fg(Color::Green);
+        let spans = vec![Span::styled("abcdef", red), Span::styled("ghij", green)];
+
+        let rows = wrap_spans(spans, 4, 2);
+        let texts: Vec<Vec<(&str, Style)>> = rows
+            .iter()
+            .map(|row| row.iter().map(|s| (s.content.as_ref(), s.style)).collect())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                vec![("abcd", red)],
+                vec![("  ", Style::default()), ("ef", red)],
+                vec![("  ", Style::default()), ("gh", green)],
+                vec![("  ", Style::default()), ("ij", green)],
+            ]
+        );
+        assert!(rows.iter().all(|row| Line::from(row.clone()).width() <= 4));
+    }
+
+    #[test]
+    fn test_wrap_spans_short_line_and_wide_chars() {
+        let rows = wrap_spans(vec![Span::raw("abc")], 10, 2);
+        assert_eq!(rows.len(), 1);
+
+        // 全角文字は途中で割らず、幅を超える前で折り返す
+        let rows = wrap_spans(vec![Span::raw("日本語です")], 5, 0);
+        let texts: Vec<String> = rows
+            .iter()
+            .map(|row| row.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(texts, vec!["日本", "語で", "す"]);
+
+        // 字下げが幅以上なら字下げしない
+        let rows = wrap_spans(vec![Span::raw("abcdef")], 3, 3);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1][0].content, "def");
+    }
+
+    #[test]
+    fn test_render_cached_lines_wrap_indents_continuation_under_gutter() {
+        let patch = "@@ -1,1 +1,1 @@\n-short\n+0123456789abcdef";
+        let cache = build_plain_diff_cache(patch, 4);
+        let opts = LineRenderOptions {
+            gutter: Gutter::New,
+            wrap: true,
+            ..LineRenderOptions::default()
+        };
+        let lines = render_cached_lines(&cache, 0..3, 99, &HashSet::new(), opts, None, 10);
+        let texts: Vec<String> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                "  @@ -1,1 ",
+                "  +1,1 @@",
+                "  -short",
+                "1 +0123456",
+                "   789abcd",
+                "   ef",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_diff_cache_marks_moved_block() {
+        // 4 行のブロックを先頭から末尾へそのまま移動
+        let patch = "@@ -1,8 +1,9 @@\n-fn helper(x: u32) -> u32 {\n-    let y = x * 2;\n-    y + 1\n-}\n \n fn main() {\n     run();\n }\n+\n+fn helper(x: u32) -> u32 {\n+    let y = x * 2;\n+    y + 1\n+}";
+        let mut parser_pool = ParserPool::new();
+        let cache = build_diff_cache(
+            patch,
+            "foo.rs",
+            "base16-ocean.dark",
+            &mut parser_pool,
+            false,
+            4,
+        );
+        assert_eq!(
+            cache.moved_blocks,
+            vec![crate::diff::MovedBlock {
+                removed: 1..5,
+                added: 10..14,
+            }]
+        );
+
+        let opts = LineRenderOptions {
+            bg_color: true,
+            ..LineRenderOptions::default()
+        };
+        let lines = render_cached_lines(&cache, 0..14, 99, &HashSet::new(), opts, None, 0);
+        let text =
+            |i: usize| -> String { lines[i].spans.iter().map(|s| s.content.as_ref()).collect() };
+
+        // ブロック先頭行に対応する側の行番号を案内する
+        assert_eq!(text(1), "-fn helper(x: u32) -> u32 {  ↳ moved to line 6");
+        assert_eq!(text(10), "+fn helper(x: u32) -> u32 {  ↳ moved from line 1");
+        assert_eq!(text(11), "+    let y = x * 2;");
+        // 移動行は移動の色で、追加・削除の背景は付けない
+        assert!(lines[2]
+            .spans
+            .iter()
+            .all(|s| s.style.fg == Some(Color::Magenta)));
+        assert!(lines[12]
+            .spans
+            .iter()
+            .all(|s| s.style.fg == Some(Color::Cyan)));
+        assert_eq!(lines[12].style.bg, None);
+        // 移動ではない追加行（空行）は通常どおり
+        assert!(lines[9].style.bg.is_some());
     }
 
     #[test]
@@ -2422,8 +3688,15 @@ mod tests {
 +added line"#;
 
         let comment_lines = HashSet::new();
-        let lines =
-            parse_patch_to_lines(patch, 0, "test.rs", "base16-ocean.dark", &comment_lines, 4);
+        let lines = parse_patch_to_lines(
+            patch,
+            0,
+            "test.rs",
+            "base16-ocean.dark",
+            &comment_lines,
+            &HashSet::new(),
+            4,
+        );
 
         // 4行: header, context, removed, added
         assert_eq!(lines.len(), 4);
@@ -2439,8 +3712,15 @@ mod tests {
         let mut comment_lines = HashSet::new();
         comment_lines.insert(2); // added 行にコメント
 
-        let lines =
-            parse_patch_to_lines(patch, 2, "test.rs", "base16-ocean.dark", &comment_lines, 4);
+        let lines = parse_patch_to_lines(
+            patch,
+            2,
+            "test.rs",
+            "base16-ocean.dark",
+            &comment_lines,
+            &HashSet::new(),
+            4,
+        );
 
         assert_eq!(lines.len(), 4);
 
@@ -2471,8 +3751,15 @@ mod tests {
         let comment_lines = HashSet::new();
 
         // tab_width = 2: tab should expand to 2 spaces
-        let lines =
-            parse_patch_to_lines(patch, 0, "test.rs", "base16-ocean.dark", &comment_lines, 2);
+        let lines = parse_patch_to_lines(
+            patch,
+            0,
+            "test.rs",
+            "base16-ocean.dark",
+            &comment_lines,
+            &HashSet::new(),
+            2,
+        );
         let line_text: String = lines[1].spans.iter().map(|s| s.content.as_ref()).collect();
         assert!(
             line_text.contains("  indented"),
@@ -2481,8 +3768,15 @@ mod tests {
         );
 
         // tab_width = 8: tab should expand to 8 spaces
-        let lines =
-            parse_patch_to_lines(patch, 0, "test.rs", "base16-ocean.dark", &comment_lines, 8);
+        let lines = parse_patch_to_lines(
+            patch,
+            0,
+            "test.rs",
+            "base16-ocean.dark",
+            &comment_lines,
+            &HashSet::new(),
+            8,
+        );
         let line_text: String = lines[1].spans.iter().map(|s| s.content.as_ref()).collect();
         assert!(
             line_text.contains("        indented"),
@@ -2505,7 +3799,15 @@ mod tests {
         assert_eq!(cache.lines.len(), 4);
 
         // range が完全に範囲外 → 空の Vec
-        let result = render_cached_lines(&cache, 100..200, 0, &HashSet::new(), false, None, 0);
+        let result = render_cached_lines(
+            &cache,
+            100..200,
+            0,
+            &HashSet::new(),
+            LineRenderOptions::default(),
+            None,
+            0,
+        );
         assert!(
             result.is_empty(),
             "Out-of-bounds range should return empty Vec"
@@ -2517,9 +3819,215 @@ mod tests {
         let cache = build_plain_diff_cache("", 4);
         assert!(cache.lines.is_empty());
 
-        let result = render_cached_lines(&cache, 0..10, 0, &HashSet::new(), false, None, 0);
+        let result = render_cached_lines(
+            &cache,
+            0..10,
+            0,
+            &HashSet::new(),
+            LineRenderOptions::default(),
+            None,
+            0,
+        );
         assert!(result.is_empty(), "Empty cache should return empty Vec");
     }
+
+    #[test]
+    fn test_render_cached_lines_highlights_markers_on_added_lines_only() {
+        let patch = "@@ -1,3 +1,3 @@\n // TODO: context\n-// FIXME: removed\n+// TODO: fix HACK";
+        let cache = build_plain_diff_cache(patch, 4);
+        let config = DiffConfig {
+            highlight_markers: true,
+            ..DiffConfig::default()
+        };
+        let opts = LineRenderOptions::from_config(&config, &DiffColorsConfig::default());
+        let result = render_cached_lines(
+            &cache,
+            0..cache.lines.len(),
+            99,
+            &HashSet::new(),
+            opts,
+            None,
+            0,
+        );
+
+        let marker_spans = |line: &Line| -> Vec<String> {
+            line.spans
+                .iter()
+                .filter(|s| s.style.add_modifier.contains(Modifier::BOLD))
+                .map(|s| s.content.to_string())
+                .collect()
+        };
+        assert!(marker_spans(&result[1]).is_empty());
+        assert!(marker_spans(&result[2]).is_empty());
+        assert_eq!(marker_spans(&result[3]), vec!["TODO", "HACK"]);
+        assert_eq!(result[3].spans[2].style.fg, Some(Color::Yellow));
+        // 分割後も行の内容は変わらない
+        let text: String = result[3].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "+// TODO: fix HACK");
+
+        // 無効時は分割しない
+        let plain = render_cached_lines(
+            &cache,
+            3..4,
+            99,
+            &HashSet::new(),
+            LineRenderOptions::default(),
+            None,
+            0,
+        );
+        assert_eq!(plain[0].spans.len(), 2);
+    }
+
+    #[test]
+    fn test_render_cached_lines_show_whitespace_modes() {
+        // 行末のタブは次のタブ位置（2 桁先）まで
+        let patch = "@@ -1,2 +1,2 @@\n \tx = 1;\t \n+\tlet y = 2;  ";
+        let render = |cache: &DiffCache, whitespace: ShowWhitespace| -> Vec<String> {
+            let opts = LineRenderOptions {
+                whitespace,
+                ..LineRenderOptions::default()
+            };
+            render_cached_lines(cache, 0..3, 99, &HashSet::new(), opts, None, 0)
+                .iter()
+                .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+
+        let mut pool = ParserPool::new();
+        let plain = build_plain_diff_cache(patch, 4);
+        let highlighted =
+            build_diff_cache(patch, "test.rs", "base16-ocean.dark", &mut pool, false, 4);
+        for cache in [&plain, &highlighted] {
+            assert_eq!(
+                render(cache, ShowWhitespace::None),
+                vec!["@@ -1,2 +1,2 @@", "     x = 1;   ", "+    let y = 2;  "]
+            );
+            assert_eq!(
+                render(cache, ShowWhitespace::Trailing),
+                vec!["@@ -1,2 +1,2 @@", "     x = 1;→ ·", "+    let y = 2;··"]
+            );
+            assert_eq!(
+                render(cache, ShowWhitespace::All),
+                vec!["@@ -1,2 +1,2 @@", " →   x·=·1;→ ·", "+→   let·y·=·2;··"]
+            );
+        }
+
+        // グリフは dim 表示、表示幅は変わらない
+        let opts = LineRenderOptions {
+            whitespace: ShowWhitespace::All,
+            ..LineRenderOptions::default()
+        };
+        let lines = render_cached_lines(&plain, 2..3, 99, &HashSet::new(), opts, None, 0);
+        let arrow = lines[0].spans.iter().find(|s| s.content == "→").unwrap();
+        assert_eq!(arrow.style.fg, Some(Color::DarkGray));
+        assert_eq!(lines[0].width(), "+    let y = 2;  ".len());
+    }
+
+    #[test]
+    fn test_render_cached_lines_flags_whitespace_errors_on_added_lines_only() {
+        let patch = "@@ -1,2 +1,3 @@\n keep  \n+\t x = 1;  \n+ \t\n-gone  ";
+        let cache = build_plain_diff_cache(patch, 4);
+        let opts = LineRenderOptions {
+            trailing_whitespace: true,
+            tab_indent: true,
+            ..LineRenderOptions::default()
+        };
+        let result = render_cached_lines(&cache, 0..5, 99, &HashSet::new(), opts, None, 0);
+        let flagged = |line: &Line| -> Vec<String> {
+            line.spans
+                .iter()
+                .filter(|s| s.style.bg == Some(Color::Red))
+                .map(|s| s.content.to_string())
+                .collect()
+        };
+
+        // 文脈行・削除行は対象外
+        assert!(flagged(&result[1]).is_empty());
+        assert!(flagged(&result[4]).is_empty());
+        // インデント中のタブ（展開後の 4 桁）と行末の空白
+        assert_eq!(flagged(&result[2]), vec!["    ", "  "]);
+        // 空白だけの行は全体が行末の空白
+        assert_eq!(flagged(&result[3]), vec!["    "]);
+
+        // 行末の空白だけを有効にした場合
+        let opts = LineRenderOptions {
+            trailing_whitespace: true,
+            ..LineRenderOptions::default()
+        };
+        let result = render_cached_lines(&cache, 2..3, 99, &HashSet::new(), opts, None, 0);
+        assert_eq!(flagged(&result[0]), vec!["  "]);
+        let text: String = result[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "+     x = 1;  ");
+    }
+
+    #[test]
+    fn test_render_cached_lines_interprets_ansi_when_enabled() {
+        let patch = "@@ -1 +1 @@\n+\x1b[31mred\x1b[0m plain";
+        let cache = build_plain_diff_cache(patch, 4);
+        let render = |ansi: bool| {
+            let opts = LineRenderOptions {
+                ansi,
+                ..LineRenderOptions::default()
+            };
+            render_cached_lines(&cache, 1..2, 99, &HashSet::new(), opts, None, 0).remove(0)
+        };
+
+        let line = render(true);
+        let texts: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["+", "red", " plain"]);
+        assert_eq!(line.spans[1].style.fg, Some(Color::Red));
+        assert_eq!(line.spans[2].style.fg, LineType::Added.fg_color());
+
+        // 既定では生のエスケープをそのまま残す
+        let raw: String = render(false)
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(raw, "+\x1b[31mred\x1b[0m plain");
+    }
+
+    #[test]
+    fn test_render_cached_lines_ansi_keeps_tab_glyphs_aligned() {
+        let patch = "@@ -1 +1 @@\n+\x1b[31mred\x1b[0m\tx y";
+        let cache = build_plain_diff_cache(patch, 4);
+        let opts = LineRenderOptions {
+            ansi: true,
+            whitespace: ShowWhitespace::All,
+            ..LineRenderOptions::default()
+        };
+        let line = render_cached_lines(&cache, 1..2, 99, &HashSet::new(), opts, None, 0).remove(0);
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        // タブの → はエスケープを除いた位置（red の直後）に出る
+        assert_eq!(text, "+red→ x·y");
+    }
+
+    #[test]
+    fn test_split_marker_spans_respects_boundaries_across_spans() {
+        let words = vec!["TODO".to_string()];
+        let markers = MarkerHighlight {
+            words: &words,
+            style: Style::default().add_modifier(Modifier::BOLD),
+        };
+        let bold = |spans: &[Span]| -> Vec<String> {
+            spans
+                .iter()
+                .filter(|s| s.style.add_modifier.contains(Modifier::BOLD))
+                .map(|s| s.content.to_string())
+                .collect()
+        };
+
+        // ハイライタが `FOO_` と `TODO` を別トークンにしても単語の一部として扱う
+        let spans = vec![Span::raw("+"), Span::raw("FOO_"), Span::raw("TODO")];
+        assert!(bold(&split_marker_spans(spans, markers)).is_empty());
+
+        // 1 語が複数スパンに分かれていても強調される
+        let spans = vec![Span::raw("+// TO"), Span::raw("DO: x")];
+        let out = split_marker_spans(spans, markers);
+        assert_eq!(bold(&out), vec!["TO", "DO"]);
+        let text: String = out.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "+// TODO: x");
+    }
 }
 
 #[cfg(test)]
@@ -2758,8 +4266,14 @@ mod priming_diff_tests {
 +const y = 2;"#;
 
         let mut interner = Rodeo::default();
-        let lines =
-            build_lines_with_syntect(patch, "Component.vue", "base16-ocean.dark", &mut interner);
+        let mut style_cache = ThemeStyleCache::default();
+        let lines = build_lines_with_syntect(
+            patch,
+            "Component.vue",
+            "base16-ocean.dark",
+            &mut interner,
+            &mut style_cache,
+        );
 
         assert_eq!(lines.len(), 3);
 
@@ -2776,6 +4290,27 @@ mod priming_diff_tests {
         assert_eq!(added_marker, "+");
     }
 
+    #[test]
+    fn test_build_lines_with_syntect_reuses_converted_styles() {
+        // 同じトークン種別が繰り返されるので、2 行目以降の変換はキャッシュから返る
+        let patch = "@@ -1,3 +1,3 @@\n-let a = 1;\n+let b = 2;\n+let c = 3;";
+        let mut interner = Rodeo::default();
+        let mut style_cache = ThemeStyleCache::default();
+        let lines = build_lines_with_syntect(
+            patch,
+            "main.rs",
+            "base16-ocean.dark",
+            &mut interner,
+            &mut style_cache,
+        );
+
+        assert_eq!(lines.len(), 4);
+        assert!(
+            style_cache.syntect_hits() > 0,
+            "repeated token styles should be served from the cache"
+        );
+    }
+
     #[test]
     fn test_looks_like_script_content_mixed() {
         // Mixed content should still be detected as script
@@ -3165,11 +4700,11 @@ mod priming_diff_tests {
         assert!(matches!(result, std::borrow::Cow::Borrowed(_)));
         assert_eq!(result, "hello world");
 
-        // With tabs → Cow::Owned
+        // With tabs → Cow::Owned（タブは次のタブ位置まで）
         let with_tabs = "\thello\tworld";
         let result = expand_tabs(with_tabs, 4);
         assert!(matches!(result, std::borrow::Cow::Owned(_)));
-        assert_eq!(result, "    hello    world");
+        assert_eq!(result, "    hello   world");
 
         // Custom tab width
         let result = expand_tabs("\tx", 2);
@@ -3180,6 +4715,27 @@ mod priming_diff_tests {
         assert!(matches!(result, std::borrow::Cow::Borrowed(_)));
     }
 
+    #[test]
+    fn test_expand_tabs_uses_tab_stops_after_diff_prefix() {
+        assert_eq!(expand_tabs("\tfoo\tbar", 4), "    foo bar");
+        assert_eq!(expand_tabs("\tfoo\tbar", 8), "        foo     bar");
+
+        // diff 記号の後ろから数える
+        assert_eq!(expand_tabs("+\tfoo\tbar", 4), "+    foo bar");
+        assert_eq!(expand_tabs("-\tfoo\tbar", 8), "-        foo     bar");
+
+        // スペースとタブの混在インデントはエディタと同じ位置に揃う
+        let patch = "@@ -1,2 +1,2 @@\n \tx\n+  \ty\n+ \t\tz";
+        assert_eq!(
+            expand_tabs(patch, 4),
+            "@@ -1,2 +1,2 @@\n     x\n+    y\n+        z"
+        );
+        // 全角文字は 2 桁
+        assert_eq!(expand_tabs("+日本\tx", 8), "+日本    x");
+
+        assert_eq!(tab_ranges("+ab\tc\td", 4), vec![(3, 5), (6, 9)]);
+    }
+
     #[test]
     fn test_build_plain_diff_cache_with_tabs() {
         let patch = "@@ -1 +1 @@\n+\tindented\n+\t\tdouble";
@@ -0,0 +1,198 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::diff::{side_by_side_rows, SideLine, SplitDiffRow};
+
+use super::expand_tabs;
+
+/// `DiffLayout::Split`: old を左列、new を右列に並べて描画する
+///
+/// 縦方向のカーソル・スクロールは unified と同じ patch 行を基準にし、
+/// 各列は `split_scroll` で独立に横スクロールする。
+pub(super) fn render_split_diff(frame: &mut Frame, app: &App, area: Rect, patch: &str) {
+    let block = Block::default().borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = side_by_side_rows(patch);
+    let visible_height = inner.height.saturating_sub(1) as usize;
+    let selected = app.diff_scroll.selected_line;
+    let window = visible_rows(
+        &rows,
+        app.diff_scroll.scroll_offset,
+        selected,
+        visible_height,
+    );
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+
+    let scroll = app.split_scroll;
+    let focus_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let title = |label: &str, offset: usize, focused: bool| {
+        let text = if offset > 0 {
+            format!(" {} (+{}) ", label, offset)
+        } else {
+            format!(" {} ", label)
+        };
+        if focused {
+            Span::styled(text, focus_style)
+        } else {
+            Span::styled(text, Style::default().fg(Color::DarkGray))
+        }
+    };
+
+    let tab_width = app.config.diff.tab_width;
+    let comment_lines = &app.cmt.file_comment_lines;
+    let collapsed_comments = &app.cmt.file_collapsed_comment_lines;
+    let column = |new_side: bool| -> Vec<Line<'static>> {
+        let offset = if new_side { scroll.new } else { scroll.old };
+        rows[window.clone()]
+            .iter()
+            .map(|row| {
+                let line = match row {
+                    SplitDiffRow::Header { text, .. } => Line::from(Span::styled(
+                        skip_columns(text, offset),
+                        Style::default().fg(Color::Cyan),
+                    )),
+                    SplitDiffRow::Pair(pair) => {
+                        let side = if new_side { &pair.new } else { &pair.old };
+                        side_line(
+                            side.as_ref(),
+                            new_side,
+                            offset,
+                            tab_width,
+                            comment_lines,
+                            collapsed_comments,
+                        )
+                    }
+                };
+                if row.contains_patch_line(selected) {
+                    line.style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    line
+                }
+            })
+            .collect()
+    };
+
+    frame.render_widget(
+        Paragraph::new(column(false)).block(Block::default().title(title(
+            "Old",
+            scroll.old,
+            !scroll.focus_new,
+        ))),
+        columns[0],
+    );
+    frame.render_widget(
+        Paragraph::new(column(true)).block(
+            Block::default()
+                .borders(Borders::LEFT)
+                .border_style(Style::default().fg(Color::DarkGray))
+                .title(title("New", scroll.new, scroll.focus_new)),
+        ),
+        columns[1],
+    );
+}
+
+/// 描画する行の範囲。先頭は `scroll_offset` の patch 行を含む行で、
+/// カーソル行が窓に収まらなければ下へずらす。
+fn visible_rows(
+    rows: &[SplitDiffRow],
+    scroll_offset: usize,
+    selected: usize,
+    height: usize,
+) -> std::ops::Range<usize> {
+    // 変更行の対応付けで行数は patch より少ないので、scroll_offset 基準で通常はカーソルも収まる
+    let mut start = rows
+        .iter()
+        .position(|r| r.contains_patch_line(scroll_offset) || r.first_patch_line() > scroll_offset)
+        .unwrap_or(rows.len());
+    if let Some(selected_row) = rows.iter().position(|r| r.contains_patch_line(selected)) {
+        if selected_row < start {
+            start = selected_row;
+        } else if height > 0 && selected_row >= start + height {
+            start = selected_row + 1 - height;
+        }
+    }
+    start..(start + height).min(rows.len()).max(start)
+}
+
+fn side_line(
+    side: Option<&SideLine>,
+    new_side: bool,
+    offset: usize,
+    tab_width: u8,
+    comment_lines: &std::collections::HashSet<usize>,
+    collapsed_comments: &std::collections::HashSet<usize>,
+) -> Line<'static> {
+    let Some(side) = side else {
+        return Line::from("");
+    };
+    let (marker, color) = if new_side {
+        ('+', Color::Green)
+    } else {
+        ('-', Color::Red)
+    };
+    let content_style = if side.changed {
+        Style::default().fg(color)
+    } else {
+        Style::default()
+    };
+    let gutter = super::comment_marker(side.patch_line, comment_lines, Some(collapsed_comments))
+        .unwrap_or_else(|| Span::raw("  "));
+    Line::from(vec![
+        gutter,
+        Span::styled(
+            format!("{:>5} ", side.line_number),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            if side.changed {
+                marker.to_string()
+            } else {
+                " ".to_string()
+            },
+            content_style,
+        ),
+        Span::styled(
+            skip_columns(&expand_tabs(&side.content, tab_width), offset),
+            content_style,
+        ),
+    ])
+}
+
+/// 先頭 `n` 文字を読み飛ばす（横スクロール）
+fn skip_columns(s: &str, n: usize) -> String {
+    s.chars().skip(n).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_rows_keeps_cursor_in_window() {
+        // 0: header, 1: ctx, 2/3: 変更ペア, 4..: 追加のみ
+        let patch = "@@ -1,2 +1,6 @@\n a\n-b\n+B\n+c\n+d\n+e\n+f";
+        let rows = side_by_side_rows(patch);
+        assert_eq!(rows.len(), 7);
+
+        assert_eq!(visible_rows(&rows, 0, 0, 3), 0..3);
+        // 2 行目（-b）と 3 行目（+B）は同じ行
+        assert_eq!(visible_rows(&rows, 3, 3, 3), 2..5);
+        // カーソルが窓の下にはみ出したらずらす
+        assert_eq!(visible_rows(&rows, 0, 7, 3), 4..7);
+        assert_eq!(visible_rows(&rows, 0, 0, 0), 0..0);
+    }
+}
@@ -10,10 +10,13 @@ use ratatui::{
 };
 
 use super::common::{
-    build_ci_status_span, build_pr_info, render_rally_status_bar, render_update_bar,
+    build_ci_status_span, build_diff_stat_line, build_marker_count_span, build_pending_review_span,
+    build_pr_info, build_read_only_span, build_truncated_files_span, render_rally_status_bar,
+    render_update_bar,
 };
-use crate::app::App;
 use crate::app::TreeRow;
+use crate::app::{App, FileListRow, FileSort};
+use crate::filter::ListFilter;
 use crate::github::ChangedFile;
 use std::collections::HashMap;
 
@@ -23,7 +26,9 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     let has_filter_bar = app
         .file_list_filter
         .as_ref()
-        .is_some_and(|f| f.input_active);
+        .is_some_and(|f| f.input_active)
+        || app.file_glob_input.is_some()
+        || app.diff_base_input.is_some();
 
     let mut constraints = vec![Constraint::Length(3), Constraint::Min(0)];
     if has_filter_bar {
@@ -41,12 +46,24 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         .direction(Direction::Vertical)
         .constraints(constraints)
         .split(frame.area());
+    app.mouse_layout.file_list.set(Some(chunks[1]));
 
     let pr_info = build_pr_info(app);
     let ci_span = build_ci_status_span(app);
-
-    let header = Paragraph::new(Line::from(vec![Span::raw(pr_info), ci_span]))
-        .block(Block::default().borders(Borders::ALL).title("octorus"));
+    let marker_span = build_marker_count_span(app);
+    let read_only_span = build_read_only_span(app);
+    let pending_span = build_pending_review_span(app);
+    let truncated_span = build_truncated_files_span(app);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::raw(pr_info),
+        read_only_span,
+        pending_span,
+        truncated_span,
+        ci_span,
+        marker_span,
+    ]))
+    .block(Block::default().borders(Borders::ALL).title("octorus"));
     frame.render_widget(header, chunks[0]);
 
     let files = app.files();
@@ -54,13 +71,14 @@ pub fn render(frame: &mut Frame, app: &mut App) {
 
     if let Some(ref filter) = app.file_list_filter {
         if filter.matched_indices.is_empty() {
-            let empty_msg = format!("No matches for '{}'", filter.query);
+            let empty_msg = filtered_files_empty_message(app, filter);
             let empty = Paragraph::new(empty_msg)
                 .style(Style::default().fg(Color::DarkGray))
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title(format!("Changed Files (0/{})", total_files)),
+                        .title(filtered_files_title("Changed Files", app, 0, total_files))
+                        .title_top(build_diff_stat_line(app).right_aligned()),
                 );
             frame.render_widget(empty, chunks[1]);
         } else {
@@ -79,7 +97,13 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title(format!("Changed Files ({}/{})", display_count, total_files)),
+                        .title(filtered_files_title(
+                            "Changed Files",
+                            app,
+                            display_count,
+                            total_files,
+                        ))
+                        .title_top(build_diff_stat_line(app).right_aligned()),
                 )
                 .highlight_style(Style::default().bg(Color::DarkGray));
 
@@ -135,7 +159,12 @@ pub fn render(frame: &mut Frame, app: &mut App) {
 
         let title = format!("Changed Files ({}) [tree]", total_files);
         let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title(title))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_top(build_diff_stat_line(app).right_aligned()),
+            )
             .highlight_style(Style::default().bg(Color::DarkGray));
 
         let mut list_state = ListState::default()
@@ -165,34 +194,48 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             );
         }
     } else {
-        let items = build_file_list_items(files, app.selected_file, &app.cmt.file_comment_counts);
+        let rows = app.file_list_rows();
+        let display_selected = app.selected_file_list_row(&rows);
+        let items = build_file_list_row_items(
+            files,
+            &rows,
+            display_selected,
+            app.generated_files_expanded,
+            &app.cmt.file_comment_counts,
+        );
+        let row_count = rows.len();
 
         let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(format!("Changed Files ({})", total_files)),
+                    .title(format!(
+                        "Changed Files ({}){}",
+                        total_files,
+                        file_sort_title_suffix(app)
+                    ))
+                    .title_top(build_diff_stat_line(app).right_aligned()),
             )
             .highlight_style(Style::default().bg(Color::DarkGray));
 
         let mut list_state = ListState::default()
             .with_offset(app.file_list_scroll_offset)
-            .with_selected(Some(app.selected_file));
+            .with_selected(Some(display_selected));
 
         frame.render_stateful_widget(list, chunks[1], &mut list_state);
 
         app.file_list_scroll_offset = list_state.offset();
-        if let Some(sel) = list_state.selected() {
-            app.selected_file = sel;
+        if let Some(&row) = list_state.selected().and_then(|sel| rows.get(sel)) {
+            app.select_file_list_row(row);
         }
 
-        if total_files > 1 {
+        if row_count > 1 {
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(Some("▲"))
                 .end_symbol(Some("▼"));
 
             let mut scrollbar_state =
-                ScrollbarState::new(total_files.saturating_sub(1)).position(app.selected_file);
+                ScrollbarState::new(row_count.saturating_sub(1)).position(display_selected);
 
             frame.render_stateful_widget(
                 scrollbar,
@@ -208,7 +251,11 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     let mut next_chunk = 2;
 
     if has_filter_bar {
-        if let Some(ref filter) = app.file_list_filter {
+        if let Some(ref pattern) = app.file_glob_input {
+            super::common::render_file_glob_bar(frame, chunks[next_chunk], pattern);
+        } else if let Some(ref rev) = app.diff_base_input {
+            super::common::render_diff_base_bar(frame, chunks[next_chunk], rev);
+        } else if let Some(ref filter) = app.file_list_filter {
             super::common::render_filter_bar(frame, chunks[next_chunk], filter);
         }
         next_chunk += 1;
@@ -255,6 +302,12 @@ pub fn render_loading(frame: &mut Frame, app: &App) {
 
     let loading_msg = if app.is_local_mode() {
         format!("{} Loading local diff...", app.spinner_char())
+    } else if let Some(count) = app.files_load_progress {
+        format!(
+            "{} Loading PR data... ({} files)",
+            app.spinner_char(),
+            count
+        )
     } else {
         format!("{} Loading PR data...", app.spinner_char())
     };
@@ -304,9 +357,41 @@ pub fn render_error(frame: &mut Frame, app: &App, error_msg: &str) {
     frame.render_widget(footer, chunks[2]);
 }
 
-pub(crate) fn build_file_list_items<'a>(
-    files: &'a [ChangedFile],
-    selected_file: usize,
+/// フィルタ中のファイル一覧タイトル（変更種別フィルタ中はその種別を併記）
+///
+/// glob フィルタ中は `(filtered: <pattern>)` を併記する。
+pub(crate) fn filtered_files_title(label: &str, app: &App, count: usize, total: usize) -> String {
+    let mut title = match app.file_category_filter {
+        Some(category) => format!("{} ({}/{}) [{}]", label, count, total, category.label()),
+        None => format!("{} ({}/{})", label, count, total),
+    };
+    if let Some(ref pattern) = app.file_glob_filter {
+        title.push_str(&format!(" (filtered: {})", pattern));
+    }
+    title.push_str(&file_sort_title_suffix(app));
+    title
+}
+
+/// ファイル一覧タイトルに付ける並び順（既定の順なら空）
+pub(crate) fn file_sort_title_suffix(app: &App) -> String {
+    match app.file_sort {
+        FileSort::Default => String::new(),
+        sort => format!(" [sort: {}]", sort.label()),
+    }
+}
+
+pub(crate) fn filtered_files_empty_message(app: &App, filter: &ListFilter) -> String {
+    match (app.file_category_filter, &app.file_glob_filter) {
+        _ if !filter.query.is_empty() => format!("No matches for '{}'", filter.query),
+        (Some(category), _) => format!("No {} files", category.label()),
+        (None, Some(pattern)) => format!("No files match '{}'", pattern),
+        (None, None) => format!("No matches for '{}'", filter.query),
+    }
+}
+
+pub(crate) fn build_file_list_items_ref<'a>(
+    files: &[&'a ChangedFile],
+    selected: usize,
     comment_counts: &HashMap<String, usize>,
 ) -> Vec<ListItem<'a>> {
     let max_count = files
@@ -320,32 +405,60 @@ pub(crate) fn build_file_list_items<'a>(
         .enumerate()
         .map(|(i, file)| {
             let count = comment_counts.get(&file.filename).copied().unwrap_or(0);
-            build_file_list_item(file, i == selected_file, count, col_width)
+            build_file_list_item(file, i == selected, count, col_width)
         })
         .collect()
 }
 
-pub(crate) fn build_file_list_items_ref<'a>(
-    files: &[&'a ChangedFile],
+/// フラットなファイル一覧の行。生成ファイルは末尾の「Generated (N)」見出しの下にまとめる。
+pub(crate) fn build_file_list_row_items<'a>(
+    files: &'a [ChangedFile],
+    rows: &[FileListRow],
     selected: usize,
+    generated_expanded: bool,
     comment_counts: &HashMap<String, usize>,
 ) -> Vec<ListItem<'a>> {
-    let max_count = files
+    let max_count = rows
         .iter()
-        .filter_map(|f| comment_counts.get(&f.filename).copied())
+        .filter_map(|row| row.file_index())
+        .filter_map(|idx| comment_counts.get(&files[idx].filename).copied())
         .max()
         .unwrap_or(0);
     let col_width = comment_col_width(max_count);
-    files
-        .iter()
+    rows.iter()
         .enumerate()
-        .map(|(i, file)| {
-            let count = comment_counts.get(&file.filename).copied().unwrap_or(0);
-            build_file_list_item(file, i == selected, count, col_width)
+        .map(|(i, row)| match *row {
+            FileListRow::File(idx) => {
+                let file = &files[idx];
+                let count = comment_counts.get(&file.filename).copied().unwrap_or(0);
+                build_file_list_item(file, i == selected, count, col_width)
+            }
+            FileListRow::GeneratedHeader { count } => {
+                build_generated_header_item(count, generated_expanded, i == selected)
+            }
         })
         .collect()
 }
 
+fn build_generated_header_item(
+    count: usize,
+    expanded: bool,
+    is_selected: bool,
+) -> ListItem<'static> {
+    let style = if is_selected {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let icon = if expanded { "▾" } else { "▸" };
+    ListItem::new(Line::from(Span::styled(
+        format!("{} Generated ({})", icon, count),
+        style,
+    )))
+}
+
 fn comment_label(count: usize) -> String {
     if count > 999 {
         "[1k+]".to_string()
@@ -384,6 +497,8 @@ fn build_file_list_item<'a>(
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD)
+    } else if file.viewed {
+        Style::default().fg(Color::DarkGray)
     } else {
         Style::default()
     };
@@ -471,6 +586,8 @@ pub(crate) fn build_tree_row_item<'a>(
                 Style::default()
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD)
+            } else if file.viewed {
+                Style::default().fg(Color::DarkGray)
             } else {
                 Style::default()
             };
@@ -525,6 +642,57 @@ mod tests {
         span.content.to_string()
     }
 
+    fn changed_file(filename: &str) -> ChangedFile {
+        ChangedFile {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: None,
+            viewed: false,
+        }
+    }
+
+    #[test]
+    fn generated_header_rows_collapsed_and_expanded() {
+        let files = vec![
+            changed_file("src/main.rs"),
+            changed_file("Cargo.lock"),
+            changed_file("a.snap"),
+        ];
+        let counts = HashMap::new();
+        let header = FileListRow::GeneratedHeader { count: 2 };
+
+        let collapsed = [FileListRow::File(0), header];
+        let items = build_file_list_row_items(&files, &collapsed, 0, false, &counts);
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            build_generated_header_item(2, false, false),
+            ListItem::new(Line::from(Span::styled(
+                "▸ Generated (2)",
+                Style::default().fg(Color::DarkGray)
+            )))
+        );
+
+        let expanded = [
+            FileListRow::File(0),
+            header,
+            FileListRow::File(1),
+            FileListRow::File(2),
+        ];
+        let items = build_file_list_row_items(&files, &expanded, 1, true, &counts);
+        assert_eq!(items.len(), 4);
+        assert_eq!(
+            items[1],
+            ListItem::new(Line::from(Span::styled(
+                "▾ Generated (2)",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            )))
+        );
+    }
+
     #[test]
     fn comment_column_no_comments_collapses() {
         let col = comment_col_width(0);
@@ -28,10 +28,11 @@ pub fn footer_hint_quit(kb: &KeybindingsConfig) -> String {
 /// Priority cascade:
 /// 1. Shell input overlay (highest, focused pane only)
 /// 2. Approve confirmation
-/// 3. PR comment submitting
-/// 4. Issue comment submitting
-/// 5. Submission result
-/// 6. Default help text + loading indicators
+/// 3. Session restore confirmation
+/// 4. PR comment submitting
+/// 5. Issue comment submitting
+/// 6. Submission result
+/// 7. Default help text + loading indicators
 pub fn build_footer_line<'a>(app: &'a App, help_text: &'a str) -> Line<'a> {
     build_footer_line_with_focus(app, help_text, true)
 }
@@ -53,6 +54,11 @@ pub fn build_footer_line_with_focus<'a>(
             app.approve_confirmation_footer_text(),
             Style::default().fg(Color::Yellow),
         ))
+    } else if app.is_pending_session_restore() {
+        Line::from(Span::styled(
+            app.session_restore_footer_text(),
+            Style::default().fg(Color::Yellow),
+        ))
     } else if app.is_submitting_comment() || app.is_issue_comment_submitting() {
         Line::from(Span::styled(
             format!("{} Submitting...", app.spinner_char()),
@@ -108,12 +114,16 @@ pub fn build_footer_block_with_focus(
             .is_some_and(|s| matches!(s.phase, ShellPhase::Input));
     let style = if shell_input_active {
         Style::default().fg(Color::Cyan)
-    } else if app.is_pending_approve_confirmation() {
+    } else if app.is_pending_approve_confirmation() || app.is_pending_session_restore() {
         Style::default().fg(Color::Yellow)
     } else {
         base_style
     };
-    Block::default().borders(Borders::ALL).border_style(style)
+    let block = Block::default().borders(Borders::ALL).border_style(style);
+    match app.review_timer_display() {
+        Some(elapsed) => block.title_top(Line::from(format!(" ⏱ {} ", elapsed)).right_aligned()),
+        None => block,
+    }
 }
 
 fn render_shell_input_line(input: &str, cursor: usize) -> Line<'static> {
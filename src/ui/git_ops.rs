@@ -249,7 +249,11 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: ratatui::layout::Rect, i
         ])
         .split(area);
 
-    let bg_color = app.config.diff.bg_color;
+    // git ops のプレビューではレビューマーカー強調は行わない
+    let line_opts = diff_view::LineRenderOptions {
+        markers: None,
+        ..diff_view::LineRenderOptions::from_config(&app.config.diff, &app.config.diff_colors)
+    };
 
     let Some(ref ops) = app.git_ops_state else {
         return;
@@ -278,9 +282,9 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: ratatui::layout::Rect, i
     frame.render_widget(header, chunks[0]);
 
     if is_commit_diff {
-        render_commit_diff_body(frame, &ops.commit_log, chunks[1], border_color, bg_color);
+        render_commit_diff_body(frame, &ops.commit_log, chunks[1], border_color, line_opts);
     } else {
-        render_diff_body(frame, ops, chunks[1], border_color, bg_color);
+        render_diff_body(frame, ops, chunks[1], border_color, line_opts);
     }
 
     let footer_text_owned;
@@ -304,7 +308,7 @@ fn render_diff_body(
     ops: &GitOpsState,
     area: ratatui::layout::Rect,
     border_color: Color,
-    bg_color: bool,
+    line_opts: diff_view::LineRenderOptions<'_>,
 ) {
     let lines: Vec<Line> = if let Some(ref cache) = ops.diff_store.current {
         let visible_height = area.height.saturating_sub(2) as usize;
@@ -322,7 +326,7 @@ fn render_diff_body(
             visible_start..visible_end,
             ops.diff_scroll.selected_line,
             &empty_comments,
-            bg_color,
+            line_opts,
             None,
             area.width.saturating_sub(2),
         )
@@ -379,7 +383,7 @@ fn render_commit_diff_body(
     cl: &CommitLogState,
     area: ratatui::layout::Rect,
     border_color: Color,
-    bg_color: bool,
+    line_opts: diff_view::LineRenderOptions<'_>,
 ) {
     let lines: Vec<Line> = if cl.diff_loading {
         vec![Line::from(Span::styled(
@@ -407,7 +411,7 @@ fn render_commit_diff_body(
             visible_start..visible_end,
             cl.diff_scroll.selected_line,
             &empty_comments,
-            bg_color,
+            line_opts,
             None,
             area.width.saturating_sub(2),
         )
@@ -1346,6 +1350,11 @@ mod tests {
             interner: lasso::Rodeo::new(),
             highlighted: false,
             markdown_rich: false,
+            line_ending_hunks: Vec::new(),
+            line_ending: crate::diff::LineEnding::Lf,
+            context_runs: Vec::new(),
+            conflict_lines: Vec::new(),
+            moved_blocks: Vec::new(),
         };
         ops.diff_store.set_current("a.rs".to_string(), cache);
 
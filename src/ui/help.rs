@@ -9,8 +9,11 @@ use ratatui::{
 use crate::ai::{PromptLoader, PromptSource};
 use crate::app::{App, HelpTab};
 use crate::config::{Config, KeybindingsConfig};
+use crate::keybinding::{action_meta, ActionCategory};
 use crate::syntax::available_themes;
 
+use super::common::render_filter_bar;
+
 /// Format a key display with padding for alignment
 fn fmt_key(key: &str, width: usize) -> String {
     format!("  {:<width$}", key, width = width)
@@ -34,7 +37,19 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     render_tab_header(frame, app, chunks[0]);
 
     match app.help_tab {
-        HelpTab::Keybindings => render_keybindings_tab(frame, app, chunks[1]),
+        HelpTab::Keybindings => {
+            let content_area = if let Some(filter) = app.help_filter.as_ref() {
+                let areas = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(3), Constraint::Min(0)])
+                    .split(chunks[1]);
+                render_filter_bar(frame, areas[0], filter);
+                areas[1]
+            } else {
+                chunks[1]
+            };
+            render_keybindings_tab(frame, app, content_area);
+        }
         HelpTab::Config => render_config_tab(frame, app, chunks[1]),
     }
 
@@ -84,9 +99,62 @@ fn render_tab_header(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(tabs, area);
 }
 
+/// 読み取り専用モードで help から隠す書き込み操作の説明（先頭一致）
+const WRITE_ACTION_HELP_PREFIXES: &[&str] = &[
+    "Add comment",
+    "Add suggestion",
+    "Approve PR",
+    "Request changes",
+    "Comment only",
+    "Submit review",
+    "Comment on selection",
+    "Suggest on selection",
+    "Reply to comment",
+    "Mark selected",
+    "Start AI Rally",
+    "Stage/unstage",
+    "Stage all",
+    "Discard changes",
+    "Commit (opens editor)",
+    "Undo last operation",
+    "Push to origin",
+    "Reset --soft",
+    "Git ops: ",
+];
+
+fn is_write_action_help_line(line: &Line<'_>) -> bool {
+    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    text.trim_start()
+        .split_once("  ")
+        .map(|(_, desc)| desc.trim_start())
+        .is_some_and(|desc| {
+            WRITE_ACTION_HELP_PREFIXES
+                .iter()
+                .any(|prefix| desc.starts_with(prefix))
+        })
+}
+
 fn render_keybindings_tab(frame: &mut Frame, app: &mut App, area: Rect) {
     let kb = &app.config.keybindings;
-    let help_lines = build_help_lines(kb);
+    let query = app
+        .help_filter
+        .as_ref()
+        .map(|f| f.query.as_str())
+        .unwrap_or("");
+    let bindings = help_bindings(kb);
+    let matched = filter_bindings(&bindings, query);
+    let mut help_lines = build_category_lines(&group_by_category(&matched));
+    if query.is_empty() {
+        help_lines.extend(build_help_lines(kb));
+    } else if matched.is_empty() {
+        help_lines.push(Line::from(Span::styled(
+            "  No matching keybindings",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    if app.is_read_only() {
+        help_lines.retain(|line| !is_write_action_help_line(line));
+    }
     let total_lines = help_lines.len();
     let content_height = area.height.saturating_sub(2) as usize;
 
@@ -172,9 +240,10 @@ fn render_config_tab(frame: &mut Frame, app: &mut App, area: Rect) {
 fn render_help_footer(frame: &mut Frame, app: &App, area: Rect) {
     let kb = &app.config.keybindings;
     let footer_text = format!(
-        " {}/{}: close | [/]: switch tab | j/k: scroll | g/G: top/bottom",
+        " {}/{}: close | [/]: switch tab | j/k: scroll | g/G: top/bottom | {}: filter",
         kb.quit.display(),
-        kb.help.display()
+        kb.help.display(),
+        kb.search_in_diff.display()
     );
     let footer = Paragraph::new(Line::from(Span::styled(
         footer_text,
@@ -258,6 +327,15 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
             fmt_label("Local", label_width),
             local_status
         )),
+        Line::from(format!(
+            "{}{}",
+            fmt_label("Repo section", label_width),
+            config
+                .loaded_repo_section
+                .as_deref()
+                .map(|repo| format!("[repos.\"{}\"] [applied]", repo))
+                .unwrap_or_else(|| "(none)".to_string())
+        )),
         Line::from(format!(
             "{}{}",
             fmt_label("Project root", label_width),
@@ -283,6 +361,101 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
             "diff.bg_color",
             overrides,
         ),
+        config_value_line(
+            "Highlight markers",
+            &config.diff.highlight_markers.to_string(),
+            "diff.highlight_markers",
+            overrides,
+        ),
+        config_value_line(
+            "Marker words",
+            &config.diff.marker_words.join(", "),
+            "diff.marker_words",
+            overrides,
+        ),
+        config_value_line(
+            "Marker color",
+            &config.diff.marker_color,
+            "diff.marker_color",
+            overrides,
+        ),
+        config_value_line(
+            "Marker bold",
+            &config.diff.marker_bold.to_string(),
+            "diff.marker_bold",
+            overrides,
+        ),
+        config_value_line(
+            "Show whitespace",
+            match config.diff.show_whitespace {
+                crate::config::ShowWhitespace::None => "none",
+                crate::config::ShowWhitespace::Trailing => "trailing",
+                crate::config::ShowWhitespace::All => "all",
+            },
+            "diff.show_whitespace",
+            overrides,
+        ),
+        config_value_line(
+            "Trailing whitespace",
+            &config.diff.highlight_trailing_whitespace.to_string(),
+            "diff.highlight_trailing_whitespace",
+            overrides,
+        ),
+        config_value_line(
+            "Tab indent",
+            &config.diff.highlight_tab_indent.to_string(),
+            "diff.highlight_tab_indent",
+            overrides,
+        ),
+        config_value_line(
+            "Collapse EOL-only",
+            &config.diff.collapse_line_endings.to_string(),
+            "diff.collapse_line_endings",
+            overrides,
+        ),
+        config_value_line(
+            "Collapse unchanged",
+            &format!(
+                "{} (context {})",
+                config.diff.collapse_unchanged, config.diff.context_lines
+            ),
+            "diff.collapse_unchanged",
+            overrides,
+        ),
+        config_value_line(
+            "Wrap comment nav",
+            &config.diff.wrap_comment_navigation.to_string(),
+            "diff.wrap_comment_navigation",
+            overrides,
+        ),
+        config_value_line(
+            "Wrap change nav",
+            &config.diff.wrap_change_navigation.to_string(),
+            "diff.wrap_change_navigation",
+            overrides,
+        ),
+        config_value_line(
+            "Render ANSI",
+            &config.diff.render_ansi_content.to_string(),
+            "diff.render_ansi_content",
+            overrides,
+        ),
+        config_value_line(
+            "Anchor preview",
+            &config.diff.comment_anchor_preview.to_string(),
+            "diff.comment_anchor_preview",
+            overrides,
+        ),
+        config_value_line(
+            "Generated globs",
+            &if config.diff.generated_globs.is_empty() {
+                "(none)".to_string()
+            } else {
+                config.diff.generated_globs.join(", ")
+            },
+            "diff.generated_globs",
+            overrides,
+        ),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Layout Settings",
@@ -310,6 +483,42 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
                 .add_modifier(Modifier::BOLD),
         )]),
         config_value_line("Editor", &editor_display, "editor", overrides),
+        config_value_line(
+            "Read-only",
+            &config.read_only.to_string(),
+            "read_only",
+            overrides,
+        ),
+        config_value_line(
+            "Comment warn length",
+            &config
+                .comment_warn_length
+                .unwrap_or(crate::config::DEFAULT_COMMENT_WARN_LENGTH)
+                .to_string(),
+            "comment_warn_length",
+            overrides,
+        ),
+        config_value_line(
+            "Quote line in editor",
+            &config.quote_line_in_comment_editor.to_string(),
+            "quote_line_in_comment_editor",
+            overrides,
+        ),
+        config_value_line(
+            "Auto refresh",
+            &match config.auto_refresh_secs {
+                0 => "off".to_string(),
+                secs => format!("{}s", secs),
+            },
+            "auto_refresh_secs",
+            overrides,
+        ),
+        config_value_line(
+            "Mouse",
+            if config.mouse { "on" } else { "off" },
+            "mouse",
+            overrides,
+        ),
         Line::from(""),
         Line::from(vec![Span::styled(
             "AI Rally Settings",
@@ -331,6 +540,12 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
             "ai.timeout_secs",
             overrides,
         ),
+        config_value_line(
+            "Stall warning (secs)",
+            &config.ai.stall_warning_secs.to_string(),
+            "ai.stall_warning_secs",
+            overrides,
+        ),
         config_value_line(
             "Auto post",
             &config.ai.auto_post.to_string(),
@@ -402,6 +617,12 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
         "ai.post_reviewee_proposals",
         overrides,
     ));
+    lines.push(config_value_line(
+        "Rally mode",
+        config.ai.rally_mode.label(),
+        "ai.rally_mode",
+        overrides,
+    ));
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
@@ -417,6 +638,26 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
         overrides,
     ));
 
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        "Session Settings",
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    lines.push(config_value_line(
+        "Auto-save (secs)",
+        &config.session.auto_save_secs.to_string(),
+        "session.auto_save_secs",
+        overrides,
+    ));
+    lines.push(config_value_line(
+        "Review timer",
+        &config.session.review_timer.to_string(),
+        "session.review_timer",
+        overrides,
+    ));
+
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "Prompt Resolution",
@@ -444,10 +685,100 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
     lines
 }
 
+/// ヘルプのカテゴリ別一覧に並べる 1 アクション
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HelpBinding {
+    name: &'static str,
+    key: String,
+    category: ActionCategory,
+    description: &'static str,
+}
+
+/// 設定中のキーバインドをメタデータ付きで定義順に並べる
+fn help_bindings(kb: &KeybindingsConfig) -> Vec<HelpBinding> {
+    kb.bindings()
+        .into_iter()
+        .filter_map(|(name, seq)| {
+            let meta = action_meta(name)?;
+            Some(HelpBinding {
+                name,
+                key: seq.display(),
+                category: meta.category,
+                description: meta.description,
+            })
+        })
+        .collect()
+}
+
+/// アクション名か説明に `query` を含むもの（大文字小文字を無視）。空クエリは全件。
+fn filter_bindings<'a>(bindings: &'a [HelpBinding], query: &str) -> Vec<&'a HelpBinding> {
+    let query = query.trim().to_lowercase();
+    bindings
+        .iter()
+        .filter(|b| {
+            query.is_empty()
+                || b.name.contains(&query)
+                || b.description.to_lowercase().contains(&query)
+        })
+        .collect()
+}
+
+/// `ActionCategory::ALL` の順にまとめる。該当の無いカテゴリは含めない。
+fn group_by_category<'a>(
+    bindings: &[&'a HelpBinding],
+) -> Vec<(ActionCategory, Vec<&'a HelpBinding>)> {
+    ActionCategory::ALL
+        .iter()
+        .filter_map(|&category| {
+            let items: Vec<&HelpBinding> = bindings
+                .iter()
+                .copied()
+                .filter(|b| b.category == category)
+                .collect();
+            (!items.is_empty()).then_some((category, items))
+        })
+        .collect()
+}
+
+fn build_category_lines(groups: &[(ActionCategory, Vec<&HelpBinding>)]) -> Vec<Line<'static>> {
+    let key_width = 14;
+    let mut lines = Vec::new();
+    for (category, items) in groups {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            category.label(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        for item in items {
+            lines.push(Line::from(vec![
+                Span::raw(format!(
+                    "{}  {}",
+                    fmt_key(&item.key, key_width),
+                    item.description
+                )),
+                Span::styled(
+                    format!("  ({})", item.name),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+    lines
+}
+
 fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
     let key_width = 14; // Width for key column
 
     vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Per-View Reference",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Global",
@@ -463,6 +794,10 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Execute shell command",
             fmt_key(&kb.shell_command.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Reload config",
+            fmt_key(&kb.reload_config.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Quit / Back",
             fmt_key(&kb.quit.display(), key_width)
@@ -513,6 +848,18 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Comment only",
             fmt_key(&kb.comment.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Submit review: pick Approve / Request changes / Comment",
+            fmt_key(&kb.review_submit.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Request reviewers (comma-separated logins)",
+            fmt_key(&kb.request_reviewer.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Add assignees (comma-separated logins)",
+            fmt_key(&kb.add_assignee.display(), key_width)
+        )),
         Line::from(format!(
             "{}  View review comments",
             fmt_key(&kb.comment_list.display(), key_width)
@@ -525,6 +872,14 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  View CI checks",
             fmt_key(&kb.ci_checks.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Symbol digest (changed symbols per file)",
+            fmt_key(&kb.symbol_digest.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Toggle pending review (hold comments until review submit)",
+            fmt_key(&kb.toggle_pending_review.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Start AI Rally (local mode seeds from local comments)",
             fmt_key(&kb.ai_rally.display(), key_width)
@@ -533,6 +888,10 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Open PR in browser",
             fmt_key(&kb.open_in_browser.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Switch to base PR (stacked PRs)",
+            fmt_key(&kb.jump_to_base_pr.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Refresh (clear cache and reload)",
             fmt_key(&kb.refresh.display(), key_width)
@@ -557,6 +916,46 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Filter list",
             fmt_key(&kb.filter.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Only added / deleted / modified / renamed files (again: off)",
+            fmt_key(
+                &format!(
+                    "{}/{}/{}/{}",
+                    kb.filter_added_files.display(),
+                    kb.filter_deleted_files.display(),
+                    kb.filter_modified_files.display(),
+                    kb.filter_renamed_files.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Filter files by glob (e.g. src/**/*.rs, !*.lock)",
+            fmt_key(&kb.filter_files.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Sort files: default / churn / name / status",
+            fmt_key(&kb.sort_files.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Next / previous file with comments",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.next_file_with_comments.display(),
+                    kb.prev_file_with_comments.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Review changes since a commit/branch (empty: whole diff)",
+            fmt_key(&kb.set_diff_base.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Open all changed files in $EDITOR (local mode)",
+            fmt_key(&kb.open_all_files_in_editor.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Open issue list",
             fmt_key(&kb.issue_list.display(), key_width)
@@ -650,6 +1049,78 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Open file in $EDITOR",
             fmt_key(&kb.go_to_file.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Open all changed files in $EDITOR (local mode)",
+            fmt_key(&kb.open_all_files_in_editor.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Open blame/history on GitHub",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.open_blame_in_browser.display(),
+                    kb.open_history_in_browser.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Show git blame around cursor (local mode)",
+            fmt_key(&kb.show_blame.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Filter hunks by search term",
+            fmt_key(&kb.filter.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Toggle hunk filter (all hunks / matches)",
+            fmt_key(&kb.toggle_hunk_filter.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Search in diff ({}/{} cycle matches)",
+            fmt_key(&kb.search_in_diff.display(), key_width),
+            kb.next_comment.display(),
+            kb.prev_comment.display()
+        )),
+        Line::from(format!(
+            "{}  Expand/collapse line-ending-only hunks",
+            fmt_key(&kb.toggle_line_endings.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Collapse/expand the hunk under the cursor",
+            fmt_key(&kb.toggle_hunk.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Wrap long lines (continuation rows align after the gutter)",
+            fmt_key(&kb.toggle_wrap.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Collapse/expand long unchanged runs (Enter expands one in place)",
+            fmt_key(&kb.toggle_unchanged.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Hide/show whitespace-only changes (shown as context)",
+            fmt_key(&kb.toggle_whitespace.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Choose syntax theme (live preview, Enter applies)",
+            fmt_key(&kb.choose_theme.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Expand context around the hunk header / reset",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.expand_context.display(),
+                    kb.reset_context.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Toggle pending review (hold comments until review submit)",
+            fmt_key(&kb.toggle_pending_review.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Jump to first/last line",
             fmt_key(
@@ -676,10 +1147,58 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
                 key_width
             )
         )),
+        Line::from(format!(
+            "{}  First/last comment in file",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.first_comment.display(),
+                    kb.last_comment.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Next/prev change (hunks across files)",
+            fmt_key(
+                &format!("{}/{}", kb.next_change.display(), kb.prev_change.display()),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Next/prev change block in this file",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.next_change_block.display(),
+                    kb.prev_change_block.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Next conflict marker in this file",
+            fmt_key(&kb.next_conflict.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Open comment panel",
             fmt_key(&kb.open_panel.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Collapse/expand all threads (comment panel)",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.collapse_all_threads.display(),
+                    kb.expand_all_threads.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Toggle selected thread (comment panel)",
+            fmt_key(&kb.toggle_thread.display(), key_width)
+        )),
         Line::from(format!(
             "  Right, {}       Open fullscreen diff",
             kb.move_right.display()
@@ -718,6 +1237,78 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Open file in $EDITOR",
             fmt_key(&kb.go_to_file.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Open all changed files in $EDITOR (local mode)",
+            fmt_key(&kb.open_all_files_in_editor.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Open blame/history on GitHub",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.open_blame_in_browser.display(),
+                    kb.open_history_in_browser.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Show git blame around cursor (local mode)",
+            fmt_key(&kb.show_blame.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Filter hunks by search term",
+            fmt_key(&kb.filter.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Toggle hunk filter (all hunks / matches)",
+            fmt_key(&kb.toggle_hunk_filter.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Search in diff ({}/{} cycle matches)",
+            fmt_key(&kb.search_in_diff.display(), key_width),
+            kb.next_comment.display(),
+            kb.prev_comment.display()
+        )),
+        Line::from(format!(
+            "{}  Expand/collapse line-ending-only hunks",
+            fmt_key(&kb.toggle_line_endings.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Collapse/expand the hunk under the cursor",
+            fmt_key(&kb.toggle_hunk.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Wrap long lines (continuation rows align after the gutter)",
+            fmt_key(&kb.toggle_wrap.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Collapse/expand long unchanged runs (Enter expands one in place)",
+            fmt_key(&kb.toggle_unchanged.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Hide/show whitespace-only changes (shown as context)",
+            fmt_key(&kb.toggle_whitespace.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Choose syntax theme (live preview, Enter applies)",
+            fmt_key(&kb.choose_theme.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Expand context around the hunk header / reset",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.expand_context.display(),
+                    kb.reset_context.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Toggle pending review (hold comments until review submit)",
+            fmt_key(&kb.toggle_pending_review.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Jump to first/last line",
             fmt_key(
@@ -741,10 +1332,58 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Jump to previous comment",
             fmt_key(&kb.prev_comment.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Jump to first/last comment in file",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.first_comment.display(),
+                    kb.last_comment.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Next/prev change (hunks across files)",
+            fmt_key(
+                &format!("{}/{}", kb.next_change.display(), kb.prev_change.display()),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Next/prev change block in this file",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.next_change_block.display(),
+                    kb.prev_change_block.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Next conflict marker in this file",
+            fmt_key(&kb.next_conflict.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Open comment panel",
             fmt_key(&kb.open_panel.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Collapse/expand all threads (comment panel)",
+            fmt_key(
+                &format!(
+                    "{}/{}",
+                    kb.collapse_all_threads.display(),
+                    kb.expand_all_threads.display()
+                ),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Toggle selected thread (comment panel)",
+            fmt_key(&kb.toggle_thread.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Page down (also J)",
             fmt_key(&kb.page_down.display(), key_width)
@@ -795,11 +1434,58 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Suggest on selection",
             fmt_key(&kb.suggestion.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Suggest on selection in external editor",
+            fmt_key(&kb.suggestion_in_editor.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Suggest formatter output for selection (local mode)",
+            fmt_key(&kb.suggest_format.display(), key_width)
+        )),
         Line::from(format!("{}  Cancel selection", fmt_key("Esc", key_width))),
         Line::from(format!(
             "{}  Toggle markdown rich display",
             fmt_key(&kb.toggle_markdown_rich.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Toggle syntax highlighting",
+            fmt_key(&kb.toggle_highlighting.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Peek current hunk side-by-side",
+            fmt_key(&kb.peek_split_hunk.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Explain current hunk with AI (popup)",
+            fmt_key(&kb.ai_explain_hunk.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Copy line / hunk to clipboard",
+            fmt_key(
+                &format!("{}/{}", kb.yank_line.display(), kb.yank_hunk.display()),
+                key_width
+            )
+        )),
+        Line::from(format!(
+            "{}  Copy GitHub permalink to line / selection",
+            fmt_key(&kb.yank_permalink.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Toggle unified / side-by-side layout",
+            fmt_key(&kb.toggle_diff_layout.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Side-by-side: scroll column left/right ({} switches column)",
+            fmt_key(
+                &format!("{}/{}", kb.scroll_left.display(), kb.scroll_right.display()),
+                key_width
+            ),
+            kb.tab_switch.display()
+        )),
+        Line::from(format!(
+            "{}  Reload current file's diff",
+            fmt_key(&kb.reload_current_file.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Back to file list",
             fmt_key(&format!("{}, Esc", kb.quit.display()), key_width)
@@ -879,6 +1565,14 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Review: Jump to file | Discussion: View detail",
             fmt_key(&kb.open_panel.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Resolve / reopen review thread (PR mode)",
+            fmt_key(&kb.toggle_resolved.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Add / remove a reaction on the selected comment (PR mode)",
+            fmt_key(&kb.add_reaction.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Back to file list",
             fmt_key(&format!("{}, Esc", kb.quit.display()), key_width)
@@ -969,6 +1663,10 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Retry on error",
             fmt_key(&kb.retry.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Re-run the latest step (finished rally)",
+            fmt_key(&kb.retry_iteration.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Send to background",
             fmt_key(&kb.rally_background.display(), key_width)
@@ -977,6 +1675,18 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  Pause / Resume",
             fmt_key(&kb.rally_pause.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Post pending (dry run) comments",
+            fmt_key(&kb.commit_rally.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Drop selected pending comment",
+            fmt_key(&kb.rally_drop_comment.display(), key_width)
+        )),
+        Line::from(format!(
+            "{}  Focus pending comments / log",
+            fmt_key(&kb.tab_switch.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Abort rally",
             fmt_key(&kb.quit.display(), key_width)
@@ -1228,6 +1938,10 @@ fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
             "{}  View PR",
             fmt_key(&kb.open_panel.display(), key_width)
         )),
+        Line::from(format!(
+            "{}  Filter by author/assignee/label",
+            fmt_key(&kb.pr_list_filters.display(), key_width)
+        )),
         Line::from(format!(
             "{}  Open issue list",
             fmt_key(&kb.issue_list.display(), key_width)
@@ -1433,6 +2147,84 @@ mod tests {
         assert!(joined.contains("Prompt Resolution"));
     }
 
+    #[test]
+    fn test_write_action_help_lines_are_detected() {
+        let kb = KeybindingsConfig::default();
+        let lines = build_help_lines(&kb);
+        let write_lines: Vec<String> = lines
+            .iter()
+            .filter(|l| is_write_action_help_line(l))
+            .map(|l| l.to_string())
+            .collect();
+
+        assert!(write_lines.iter().any(|l| l.ends_with("Approve PR")));
+        assert!(write_lines.iter().any(|l| l.ends_with("Add suggestion")));
+        // 閲覧系の行は残す
+        assert!(!write_lines.iter().any(|l| l.contains("Go to definition")));
+        assert!(!write_lines
+            .iter()
+            .any(|l| l.contains("Jump to next comment")));
+    }
+
+    #[test]
+    fn test_filter_bindings_matches_name_and_description() {
+        let bindings = help_bindings(&KeybindingsConfig::default());
+        assert_eq!(filter_bindings(&bindings, "").len(), bindings.len());
+
+        // アクション名で一致
+        let names: Vec<&str> = filter_bindings(&bindings, "yank_")
+            .iter()
+            .map(|b| b.name)
+            .collect();
+        assert_eq!(names, ["yank_line", "yank_hunk", "yank_permalink"]);
+
+        // 説明で一致（大文字小文字・前後の空白は無視）
+        let names: Vec<&str> = filter_bindings(&bindings, "  APPROVE ")
+            .iter()
+            .map(|b| b.name)
+            .collect();
+        assert_eq!(names, ["approve"]);
+
+        assert!(filter_bindings(&bindings, "no such action").is_empty());
+    }
+
+    #[test]
+    fn test_group_by_category_orders_and_skips_empty() {
+        let bindings = help_bindings(&KeybindingsConfig::default());
+        let all = filter_bindings(&bindings, "");
+        let groups = group_by_category(&all);
+        let categories: Vec<ActionCategory> = groups.iter().map(|(c, _)| *c).collect();
+        assert_eq!(categories, ActionCategory::ALL);
+        let total: usize = groups.iter().map(|(_, items)| items.len()).sum();
+        assert_eq!(total, bindings.len());
+        for (category, items) in &groups {
+            assert!(items.iter().all(|b| b.category == *category));
+        }
+
+        let rally = filter_bindings(&bindings, "rally");
+        let groups = group_by_category(&rally);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].0, ActionCategory::Ai);
+        assert!(groups[0].1.iter().any(|b| b.name == "commit_rally"));
+    }
+
+    #[test]
+    fn test_category_lines_hide_write_actions_in_read_only() {
+        let bindings = help_bindings(&KeybindingsConfig::default());
+        let all = filter_bindings(&bindings, "");
+        let lines = build_category_lines(&group_by_category(&all));
+        let joined: String = lines
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(joined.contains("Navigation"));
+        assert!(joined.contains("Approve PR  (approve)"));
+        assert!(lines
+            .iter()
+            .any(|l| is_write_action_help_line(l) && l.to_string().contains("(approve)")));
+    }
+
     #[test]
     fn test_build_help_lines_lists_readline_editing_keys() {
         // The text-input section in the help screen documents the emacs-style
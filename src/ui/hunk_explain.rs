@@ -0,0 +1,57 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::{App, HunkExplainState, LoadState};
+
+/// `ai_explain_hunk`: カーソル位置の hunk に対する AI の解説を表示する
+pub fn render(frame: &mut Frame, app: &App, popup: &HunkExplainState) {
+    let area = frame.area();
+    let width = (area.width * 80 / 100).max(40).min(area.width);
+    let height = (area.height * 70 / 100).max(8).min(area.height);
+    let popup_area = super::centered_rect(width, height, area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let kb = &app.config.keybindings;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Explain: {}  {} ", popup.filename, popup.header))
+        .title_bottom(format!(
+            " {}/{}: scroll | Esc: close ",
+            kb.move_down.display(),
+            kb.move_up.display()
+        ));
+
+    let lines = match &popup.explanation {
+        LoadState::Loaded(text) if text.trim().is_empty() => vec![Line::from(Span::styled(
+            "(empty explanation)",
+            Style::default().fg(Color::DarkGray),
+        ))],
+        LoadState::Loaded(text) => text.lines().map(|l| Line::from(l.to_string())).collect(),
+        LoadState::Error(e) => vec![Line::from(Span::styled(
+            e.clone(),
+            Style::default().fg(Color::Red),
+        ))],
+        _ => vec![Line::from(Span::styled(
+            format!(
+                "{} Asking {} to explain this hunk... (Esc: cancel)",
+                app.spinner_char(),
+                app.config.ai.reviewer
+            ),
+            Style::default().fg(Color::Yellow),
+        ))],
+    };
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((popup.scroll as u16, 0)),
+        popup_area,
+    );
+}
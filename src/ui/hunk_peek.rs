@@ -0,0 +1,114 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, HunkPeekState};
+use crate::diff::SideLine;
+
+/// `peek_split_hunk`: カーソル位置の hunk を old（左）/ new（右）で並べたポップアップ
+pub fn render(frame: &mut Frame, app: &App, peek: &HunkPeekState) {
+    let area = frame.area();
+    let width = (area.width * 90 / 100).max(40).min(area.width);
+    let height = (area.height * 80 / 100).max(8).min(area.height);
+    let popup_area = super::centered_rect(width, height, area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let kb = &app.config.keybindings;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" {}  {} ", peek.filename, peek.hunk.header))
+        .title_bottom(format!(
+            " {}/{}: scroll | Esc, {}: close ",
+            kb.move_down.display(),
+            kb.move_up.display(),
+            kb.peek_split_hunk.display()
+        ));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+
+    let tab = " ".repeat(app.config.diff.tab_width as usize);
+    let rows = &peek.hunk.rows;
+    let old_lines = side_lines(
+        rows.iter().map(|r| r.old.as_ref()),
+        Color::Red,
+        "(no old lines: added hunk)",
+        &tab,
+    );
+    let new_lines = side_lines(
+        rows.iter().map(|r| r.new.as_ref()),
+        Color::Green,
+        "(no new lines: deleted hunk)",
+        &tab,
+    );
+
+    let scroll = (peek.scroll as u16, 0);
+    frame.render_widget(
+        Paragraph::new(old_lines)
+            .block(Block::default().title("Old"))
+            .scroll(scroll),
+        columns[0],
+    );
+    frame.render_widget(
+        Paragraph::new(new_lines)
+            .block(
+                Block::default()
+                    .borders(Borders::LEFT)
+                    .border_style(Style::default().fg(Color::DarkGray))
+                    .title("New"),
+            )
+            .scroll(scroll),
+        columns[1],
+    );
+}
+
+/// 片側の行を描画用に変換する。対応する行が無い位置は空行で揃え、
+/// 片側が丸ごと空（純粋な追加 / 削除 hunk）なら先頭に説明を出す。
+fn side_lines<'a>(
+    side: impl Iterator<Item = Option<&'a SideLine>>,
+    changed_color: Color,
+    empty_note: &'static str,
+    tab: &str,
+) -> Vec<Line<'static>> {
+    let side: Vec<Option<&SideLine>> = side.collect();
+    let mut lines: Vec<Line<'static>> = side
+        .iter()
+        .map(|line| match line {
+            Some(line) => {
+                let style = if line.changed {
+                    Style::default().fg(changed_color)
+                } else {
+                    Style::default()
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:>5} ", line.line_number),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(line.content.replace('\t', tab), style),
+                ])
+            }
+            None => Line::from(""),
+        })
+        .collect();
+
+    if side.iter().all(Option::is_none) {
+        if let Some(first) = lines.first_mut() {
+            *first = Line::from(Span::styled(
+                empty_note,
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+    }
+    lines
+}
@@ -0,0 +1,336 @@
+//! diff view でのバイナリ画像のプレビュー。
+//!
+//! local mode で追加・変更された画像は作業ツリーに新しい版があるので、端末の
+//! graphics protocol（kitty / iTerm2 inline images）で diff 枠内に描画する。
+//! 対応端末でない場合や形式を表示できない場合は従来どおり
+//! `Binary files ... differ` のテキストのみを表示する。
+//!
+//! sixel はデコード済みのピクセル列が必要で、画像デコーダを持たないため未対応
+//! （テキスト表示にフォールバックする）。
+
+use std::cell::{Cell, OnceCell};
+use std::io::{Stdout, Write};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use base64::Engine;
+use crossterm::{cursor::MoveTo, queue};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Margin, Rect},
+    Terminal,
+};
+
+use crate::app::App;
+
+/// これより大きい画像はプレビューしない
+const MAX_IMAGE_BYTES: u64 = 10 * 1024 * 1024;
+/// kitty の 1 エスケープシーケンスあたりの base64 ペイロード上限
+const KITTY_CHUNK_SIZE: usize = 4096;
+/// 画像の px → セル換算に使う 1 セルのおおよその大きさ
+const CELL_PIXELS: (u32, u32) = (8, 16);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+}
+
+impl GraphicsProtocol {
+    /// 環境変数から端末の graphics protocol 対応を判定する
+    pub fn detect() -> Option<Self> {
+        Self::detect_from(|key| std::env::var(key).ok())
+    }
+
+    fn detect_from(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        // tmux / screen はエスケープシーケンスを素通ししない
+        if env("TMUX").is_some() || env("STY").is_some() {
+            return None;
+        }
+        let term = env("TERM").unwrap_or_default();
+        let term_program = env("TERM_PROGRAM").unwrap_or_default();
+        if env("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || term_program == "ghostty"
+        {
+            return Some(Self::Kitty);
+        }
+        if term_program == "iTerm.app" || term_program == "WezTerm" {
+            return Some(Self::Iterm2);
+        }
+        None
+    }
+
+    /// 端末側でデコードできる形式か（kitty は PNG のみ直接転送できる）
+    pub fn supports(self, filename: &str) -> bool {
+        let ext = std::path::Path::new(filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .unwrap_or_default();
+        match self {
+            Self::Kitty => ext == "png",
+            Self::Iterm2 => matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "gif"),
+        }
+    }
+}
+
+/// 画像プレビューの描画状態
+///
+/// ratatui のバッファ外に描くので、render で確保した領域を受け取り、
+/// `terminal.draw` の後に [`sync`] で端末へ直接書き込む。
+#[derive(Default)]
+pub struct ImagePreviewState {
+    protocol: Option<GraphicsProtocol>,
+    /// local mode のリポジトリルート（初回のみ解決）
+    repo_root: OnceCell<Option<PathBuf>>,
+    /// 今フレームの render が要求したプレビュー
+    requested: Cell<Option<(PathBuf, Rect)>>,
+    /// 端末に描画済みのプレビュー
+    drawn: Option<(PathBuf, Rect)>,
+}
+
+impl ImagePreviewState {
+    pub fn new() -> Self {
+        Self {
+            protocol: GraphicsProtocol::detect(),
+            ..Self::default()
+        }
+    }
+}
+
+/// 選択中ファイルがプレビュー可能な画像なら、作業ツリー上のパスを返す
+pub(crate) fn preview_path(app: &App) -> Option<PathBuf> {
+    let protocol = app.image_preview.protocol?;
+    if !app.is_local_mode() {
+        return None;
+    }
+    let file = app.files().get(app.selected_file)?;
+    if file.status == "removed"
+        || !crate::diff::is_image(&file.filename)
+        || !protocol.supports(&file.filename)
+        || !file
+            .patch
+            .as_deref()
+            .is_some_and(crate::diff::is_binary_patch)
+    {
+        return None;
+    }
+    let root = app
+        .image_preview
+        .repo_root
+        .get_or_init(|| resolve_repo_root(app.working_dir.as_deref()))
+        .as_ref()?;
+    let path = root.join(&file.filename);
+    let size = std::fs::metadata(&path).ok()?.len();
+    (size <= MAX_IMAGE_BYTES).then_some(path)
+}
+
+fn resolve_repo_root(working_dir: Option<&str>) -> Option<PathBuf> {
+    let mut command = std::process::Command::new("git");
+    command.args(["rev-parse", "--show-toplevel"]);
+    if let Some(dir) = working_dir {
+        command.current_dir(dir);
+    }
+    let output = command.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+/// render 中に呼ぶ: 枠付きの diff 領域 `area` のうち、`text_lines` 行の
+/// `Binary files ... differ` の下に新しい版の画像を描くよう要求する
+pub(crate) fn request_below_text(app: &App, area: Rect, text_lines: u16) {
+    let Some(path) = preview_path(app) else {
+        return;
+    };
+    let inner = area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    let offset = text_lines.saturating_add(1).min(inner.height);
+    let image_area = Rect {
+        y: inner.y + offset,
+        height: inner.height - offset,
+        ..inner
+    };
+    if image_area.width >= 4 && image_area.height >= 2 {
+        app.image_preview.requested.set(Some((path, image_area)));
+    }
+}
+
+/// `terminal.draw` の後に呼び、要求に合わせて画像を描画・消去する
+pub fn sync(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) -> Result<()> {
+    let requested = app.image_preview.requested.take();
+    // ポップアップが重なる間は画像を消す
+    let target = requested.filter(|_| {
+        app.blame_popup.is_none() && app.hunk_peek.is_none() && app.hunk_explain.is_none()
+    });
+    let state = &mut app.image_preview;
+    let Some(protocol) = state.protocol else {
+        return Ok(());
+    };
+    if target == state.drawn {
+        return Ok(());
+    }
+
+    if state.drawn.take().is_some() {
+        match protocol {
+            GraphicsProtocol::Kitty => {
+                let backend = terminal.backend_mut();
+                backend.write_all(b"\x1b_Ga=d,q=2\x1b\\")?;
+                backend.flush()?;
+            }
+            GraphicsProtocol::Iterm2 => {
+                // セルに焼き付いた画像は全面再描画で消し、次のフレームで描き直す
+                terminal.clear()?;
+                return Ok(());
+            }
+        }
+    }
+
+    let Some((path, area)) = target else {
+        return Ok(());
+    };
+    // 読めない場合もテキスト表示のまま再試行しない
+    state.drawn = Some((path.clone(), area));
+    let Ok(bytes) = std::fs::read(&path) else {
+        return Ok(());
+    };
+    let sequence = match protocol {
+        GraphicsProtocol::Kitty => {
+            let (cols, rows) = png_dimensions(&bytes)
+                .map(|size| fit_cells(size, (area.width, area.height)))
+                .unwrap_or((area.width, area.height));
+            kitty_sequence(&bytes, cols, rows)
+        }
+        GraphicsProtocol::Iterm2 => iterm2_sequence(&bytes, area.width, area.height),
+    };
+    let backend = terminal.backend_mut();
+    queue!(backend, MoveTo(area.x, area.y))?;
+    backend.write_all(sequence.as_bytes())?;
+    backend.flush()?;
+    Ok(())
+}
+
+/// PNG の IHDR から (幅, 高さ) を読む
+fn png_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.len() < 24 || !bytes.starts_with(b"\x89PNG\r\n\x1a\n") || &bytes[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(bytes[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(bytes[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+/// 画像の px サイズを、縦横比を保ったまま `max` セルに収まるセル数へ換算する
+fn fit_cells((width, height): (u32, u32), (max_cols, max_rows): (u16, u16)) -> (u16, u16) {
+    let cols = width.div_ceil(CELL_PIXELS.0).max(1) as f64;
+    let rows = height.div_ceil(CELL_PIXELS.1).max(1) as f64;
+    let scale = (max_cols as f64 / cols)
+        .min(max_rows as f64 / rows)
+        .min(1.0);
+    (
+        ((cols * scale).round() as u16).clamp(1, max_cols.max(1)),
+        ((rows * scale).round() as u16).clamp(1, max_rows.max(1)),
+    )
+}
+
+/// kitty graphics protocol: PNG をそのまま転送して `cols` x `rows` セルに表示する
+fn kitty_sequence(png: &[u8], cols: u16, rows: u16) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+    let mut sequence = String::with_capacity(encoded.len() + chunks.len() * 32);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            // q=2: 応答を返させない（入力として読まれないように）、C=1: カーソルを動かさない
+            sequence.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,C=1,c={},r={},m={};{}\x1b\\",
+                cols, rows, more, chunk
+            ));
+        } else {
+            sequence.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    sequence
+}
+
+/// iTerm2 inline images: 縦横比は端末側で保つ
+fn iterm2_sequence(bytes: &[u8], cols: u16, rows: u16) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+        bytes.len(),
+        cols,
+        rows,
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_of<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn test_detect_graphics_protocol() {
+        let detect = |vars: &[(&str, &str)]| GraphicsProtocol::detect_from(env_of(vars));
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty")]),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            detect(&[("KITTY_WINDOW_ID", "1"), ("TERM", "xterm-256color")]),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "iTerm.app")]),
+            Some(GraphicsProtocol::Iterm2)
+        );
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+        // tmux 内では描画できないのでテキストにフォールバック
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux")]),
+            None
+        );
+
+        assert!(GraphicsProtocol::Kitty.supports("a/logo.PNG"));
+        assert!(!GraphicsProtocol::Kitty.supports("photo.jpg"));
+        assert!(GraphicsProtocol::Iterm2.supports("photo.jpg"));
+        assert!(!GraphicsProtocol::Iterm2.supports("icon.svg"));
+    }
+
+    #[test]
+    fn test_png_dimensions_and_fit_cells() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&640u32.to_be_bytes());
+        png.extend_from_slice(&320u32.to_be_bytes());
+        assert_eq!(png_dimensions(&png), Some((640, 320)));
+        assert_eq!(png_dimensions(b"GIF89a"), None);
+
+        // 640x320 px = 80x20 セル。40x40 に収めると縦横比を保って半分になる
+        assert_eq!(fit_cells((640, 320), (40, 40)), (40, 10));
+        // 小さい画像は拡大しない
+        assert_eq!(fit_cells((16, 16), (40, 40)), (2, 1));
+    }
+
+    #[test]
+    fn test_kitty_sequence_chunks_payload() {
+        let data = vec![0u8; KITTY_CHUNK_SIZE];
+        let sequence = kitty_sequence(&data, 10, 5);
+        assert!(sequence.starts_with("\x1b_Ga=T,f=100,q=2,C=1,c=10,r=5,m=1;"));
+        assert_eq!(sequence.matches("\x1b_G").count(), 2);
+        assert!(sequence.contains("\x1b_Gm=0;"));
+        assert!(sequence.ends_with("\x1b\\"));
+    }
+}
@@ -0,0 +1,385 @@
+//! 軽量な Markdown → ratatui `Line` 変換
+//!
+//! PR description のように「読むだけ」の Markdown を整形表示するための最小実装。
+//! CommonMark 完全準拠は目指さず、PR 本文で頻出する要素（見出し・強調・リスト・
+//! インラインコード・リンク・フェンスコードブロック・引用・水平線）のみを扱う。
+//! 解釈できない記法はそのまま literal として残す。
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+fn code_style() -> Style {
+    Style::default().fg(Color::Green)
+}
+
+fn link_style() -> Style {
+    Style::default()
+        .fg(Color::Blue)
+        .add_modifier(Modifier::UNDERLINED)
+}
+
+fn heading_style(level: usize) -> Style {
+    let style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    if level == 1 {
+        style.add_modifier(Modifier::UNDERLINED)
+    } else {
+        style
+    }
+}
+
+/// Markdown テキストをスタイル付きの `Line` 列に変換する。
+///
+/// フェンスコードブロック内はインライン解釈を行わず、コードスタイルでそのまま出力する。
+pub fn markdown_to_lines(text: &str) -> Vec<Line<'static>> {
+    let text = text.replace("\r\n", "\n");
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for raw in text.lines() {
+        let trimmed = raw.trim_start();
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            } else {
+                lines.push(Line::from(Span::styled(format!("  {}", raw), code_style())));
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            continue;
+        }
+
+        lines.push(block_line(raw));
+    }
+
+    lines
+}
+
+/// フェンス外の 1 行をブロック要素として解釈する
+fn block_line(raw: &str) -> Line<'static> {
+    let trimmed = raw.trim_start();
+    let indent = raw.len() - trimmed.len();
+
+    if trimmed.is_empty() {
+        return Line::default();
+    }
+
+    if let Some(level) = heading_level(trimmed) {
+        let content = trimmed[level..].trim().trim_end_matches('#').trim_end();
+        let mut spans = Vec::new();
+        parse_inline(content, heading_style(level), &mut spans);
+        return Line::from(spans);
+    }
+
+    if is_horizontal_rule(trimmed) {
+        return Line::from(Span::styled(
+            "─".repeat(40),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    if let Some(rest) = trimmed.strip_prefix('>') {
+        let mut spans = vec![Span::styled("│ ", Style::default().fg(Color::DarkGray))];
+        parse_inline(
+            rest.trim_start(),
+            Style::default().add_modifier(Modifier::ITALIC),
+            &mut spans,
+        );
+        return Line::from(spans);
+    }
+
+    if let Some((marker, rest)) = list_item(trimmed) {
+        let depth = indent / 2;
+        let marker = if marker.is_empty() {
+            BULLETS[depth.min(BULLETS.len() - 1)].to_string()
+        } else {
+            marker
+        };
+        let mut spans = vec![Span::raw(format!("{}{} ", "  ".repeat(depth), marker))];
+        parse_inline(rest, Style::default(), &mut spans);
+        return Line::from(spans);
+    }
+
+    let mut spans = Vec::new();
+    parse_inline(trimmed, Style::default(), &mut spans);
+    Line::from(spans)
+}
+
+/// `# ` ～ `###### ` の見出しレベルを返す
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+        Some(level)
+    } else {
+        None
+    }
+}
+
+fn is_horizontal_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|&m| compact.chars().all(|c| c == m))
+}
+
+/// リスト項目ならマーカー（表示用）と本文を返す。
+///
+/// 箇条書きの記号はネスト深さに応じて呼び出し側で決めるため、ここでは空文字を返す。
+/// タスクリスト（`- [ ]` / `- [x]`）はチェックボックス記号を返す。
+fn list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(bullet) {
+            if let Some(rest) = rest.strip_prefix("[ ] ") {
+                return Some(("☐".to_string(), rest));
+            }
+            if let Some(rest) = rest
+                .strip_prefix("[x] ")
+                .or_else(|| rest.strip_prefix("[X] "))
+            {
+                return Some(("☑".to_string(), rest));
+            }
+            return Some((String::new(), rest));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        let after = &line[digits..];
+        for delim in [". ", ") "] {
+            if let Some(rest) = after.strip_prefix(delim) {
+                return Some((format!("{}{}", &line[..digits], delim.trim_end()), rest));
+            }
+        }
+    }
+    None
+}
+
+/// インライン要素を解釈して `out` に span を追加する。
+///
+/// 強調の中身は再帰的に解釈するため、`**bold `code`**` のような入れ子も扱える。
+/// 閉じマーカーが見つからない場合はマーカーを literal として出力する。
+fn parse_inline(text: &str, base: Style, out: &mut Vec<Span<'static>>) {
+    let mut plain = String::new();
+    let mut rest = text;
+
+    let flush = |plain: &mut String, out: &mut Vec<Span<'static>>| {
+        if !plain.is_empty() {
+            out.push(Span::styled(std::mem::take(plain), base));
+        }
+    };
+
+    while let Some(c) = rest.chars().next() {
+        // インラインコード: 中身は解釈しない
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                flush(&mut plain, out);
+                out.push(Span::styled(rest[1..1 + end].to_string(), code_style()));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+
+        // 画像 / リンク: テキスト部分のみ下線付きで表示する
+        if c == '[' || rest.starts_with("![") {
+            let start = if c == '!' { 2 } else { 1 };
+            if let Some((label, consumed)) = parse_link(&rest[start..]) {
+                flush(&mut plain, out);
+                parse_inline(label, base.patch(link_style()), out);
+                rest = &rest[start + consumed..];
+                continue;
+            }
+        }
+
+        // autolink: <https://...>
+        if c == '<' {
+            if let Some(end) = rest.find('>') {
+                let inner = &rest[1..end];
+                if inner.starts_with("http://") || inner.starts_with("https://") {
+                    flush(&mut plain, out);
+                    out.push(Span::styled(inner.to_string(), base.patch(link_style())));
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        // 強調系: 長いマーカーを優先する
+        let emphasis = [
+            ("**", Modifier::BOLD),
+            ("__", Modifier::BOLD),
+            ("~~", Modifier::CROSSED_OUT),
+            ("*", Modifier::ITALIC),
+            ("_", Modifier::ITALIC),
+        ];
+        let mut matched = false;
+        for (marker, modifier) in emphasis {
+            if !rest.starts_with(marker) {
+                continue;
+            }
+            // snake_case の識別子を強調と誤認しないよう、`_` は単語境界でのみ開く
+            if marker.starts_with('_') && plain.chars().last().is_some_and(|p| p.is_alphanumeric())
+            {
+                break;
+            }
+            let body = &rest[marker.len()..];
+            if body.starts_with(' ') || body.is_empty() {
+                break;
+            }
+            if let Some(end) = find_closing(body, marker) {
+                flush(&mut plain, out);
+                parse_inline(&body[..end], base.add_modifier(modifier), out);
+                rest = &body[end + marker.len()..];
+                matched = true;
+            }
+            break;
+        }
+        if matched {
+            continue;
+        }
+
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    flush(&mut plain, out);
+}
+
+/// `label](url)` を解釈し、ラベルと消費したバイト数を返す
+fn parse_link(text: &str) -> Option<(&str, usize)> {
+    let close = text.find("](")?;
+    let label = &text[..close];
+    if label.contains('[') {
+        return None;
+    }
+    let url_len = text[close + 2..].find(')')?;
+    Some((label, close + 2 + url_len + 1))
+}
+
+/// 閉じマーカーの位置を探す。`_` 系は直後が英数字なら閉じとみなさない。
+fn find_closing(body: &str, marker: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(pos) = body[from..].find(marker) {
+        let idx = from + pos;
+        let before_ok = idx > 0 && !body[..idx].ends_with(' ');
+        let after = body[idx + marker.len()..].chars().next();
+        // `**` の内側にある単独 `*` を閉じと誤認しない
+        let doubled = marker.len() == 1 && after == marker.chars().next();
+        let boundary_ok = !marker.starts_with('_') || !after.is_some_and(|a| a.is_alphanumeric());
+        if before_ok && !doubled && boundary_ok {
+            return Some(idx);
+        }
+        from = idx + marker.len() + usize::from(doubled);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    /// span ごとのスタイルを読みやすい形に書き出す（スナップショット用）
+    fn dump(lines: &[Line<'_>]) -> String {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| {
+                        let mut tags = Vec::new();
+                        if let Some(fg) = span.style.fg {
+                            tags.push(format!("{:?}", fg));
+                        }
+                        let m = span.style.add_modifier;
+                        for (flag, name) in [
+                            (Modifier::BOLD, "b"),
+                            (Modifier::ITALIC, "i"),
+                            (Modifier::UNDERLINED, "u"),
+                            (Modifier::CROSSED_OUT, "s"),
+                        ] {
+                            if m.contains(flag) {
+                                tags.push(name.to_string());
+                            }
+                        }
+                        if tags.is_empty() {
+                            format!("[{}]", span.content)
+                        } else {
+                            format!("[{}|{}]", span.content, tags.join(","))
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_markdown_to_lines_sample_body() {
+        let body = "\
+# Summary
+
+This PR adds **bold** text, *italic* text and `inline code`.
+See [the docs](https://example.com) or <https://example.com/raw>.
+
+## Changes
+
+- top level item
+  - nested item with `code`
+    - deeper item
+1. first
+2. second
+
+> quoted _note_
+
+```rust
+fn main() {
+    // **not bold** inside a fence
+}
+```
+---";
+        assert_snapshot!(dump(&markdown_to_lines(body)), @r"
+        [Summary|Yellow,b,u]
+
+        [This PR adds ][bold|b][ text, ][italic|i][ text and ][inline code|Green][.]
+        [See ][the docs|Blue,u][ or ][https://example.com/raw|Blue,u][.]
+
+        [Changes|Yellow,b]
+
+        [• ][top level item]
+        [  ◦ ][nested item with ][code|Green]
+        [    ▪ ][deeper item]
+        [1. ][first]
+        [2. ][second]
+
+        [│ |DarkGray][quoted |i][note|i]
+
+        [  fn main() {|Green]
+        [      // **not bold** inside a fence|Green]
+        [  }|Green]
+        [────────────────────────────────────────|DarkGray]
+        ");
+    }
+
+    #[test]
+    fn test_unmatched_markers_and_snake_case_stay_literal() {
+        let lines = markdown_to_lines("use snake_case_name and 2 * 3 or **open");
+        assert_snapshot!(dump(&lines), @"[use snake_case_name and 2 * 3 or **open]");
+    }
+
+    #[test]
+    fn test_nested_emphasis_and_task_list() {
+        let lines = markdown_to_lines("- [x] done with **bold `code`**\n- [ ] ~~todo~~");
+        assert_snapshot!(dump(&lines), @r"
+        [☑ ][done with ][bold |b][code|Green]
+        [☐ ][todo|s]
+        ");
+    }
+}
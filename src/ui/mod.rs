@@ -1,6 +1,8 @@
 mod ai_rally;
+mod blame_popup;
 mod checks_list;
 mod cockpit;
+mod comment_body;
 mod comment_list;
 pub(crate) mod common;
 pub mod diff_view;
@@ -8,18 +10,29 @@ mod file_list;
 pub(super) mod footer;
 mod git_ops;
 mod help;
+mod hunk_explain;
+mod hunk_peek;
+pub mod image_preview;
 mod issue_comment_list;
 mod issue_detail;
 mod issue_list;
+pub mod markdown;
+pub mod mouse;
 mod pr_description;
 mod pr_list;
+mod review_picker;
 mod simulate_modal;
 mod split_view;
+mod symbol_digest;
 pub mod text_area;
+mod theme_picker;
 
 use anyhow::Result;
 use crossterm::{
-    event::{KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -40,6 +53,14 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use crate::app::{App, AppState, DataState, ShellCommandResult, ShellPhase};
 
 static KITTY_ENABLED: AtomicBool = AtomicBool::new(false);
+/// 設定 `mouse` で要求されたマウスキャプチャ
+static MOUSE_REQUESTED: AtomicBool = AtomicBool::new(false);
+static MOUSE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 以降の [`setup_terminal`] でマウスキャプチャを有効にするか（設定 `mouse`）
+pub fn set_mouse_capture(enabled: bool) {
+    MOUSE_REQUESTED.store(enabled, Ordering::SeqCst);
+}
 
 pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
     enable_raw_mode()?;
@@ -58,6 +79,9 @@ pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
     {
         KITTY_ENABLED.store(true, Ordering::SeqCst);
     }
+    if MOUSE_REQUESTED.load(Ordering::SeqCst) && execute!(stdout, EnableMouseCapture).is_ok() {
+        MOUSE_ENABLED.store(true, Ordering::SeqCst);
+    }
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
@@ -65,6 +89,7 @@ pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
 
 pub fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
     cleanup_keyboard_enhancement();
+    cleanup_mouse_capture();
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
@@ -82,7 +107,18 @@ pub fn cleanup_keyboard_enhancement() {
     }
 }
 
+/// Disable mouse capture if previously enabled. Safe to call multiple times.
+pub fn cleanup_mouse_capture() {
+    if MOUSE_ENABLED
+        .compare_exchange(true, false, Ordering::SeqCst, Ordering::Relaxed)
+        .is_ok()
+    {
+        let _ = execute!(io::stdout(), DisableMouseCapture);
+    }
+}
+
 pub fn render(frame: &mut Frame, app: &mut App) {
+    app.mouse_layout.clear();
     if !app.state.is_data_state_independent() {
         if matches!(app.data_state, DataState::Loading) {
             file_list::render_loading(frame, app);
@@ -105,6 +141,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         AppState::SplitViewFileList | AppState::SplitViewDiff => split_view::render(frame, app),
         AppState::PrDescription => pr_description::render(frame, app),
         AppState::ChecksList => checks_list::render(frame, app),
+        AppState::SymbolDigest => symbol_digest::render(frame, app),
         AppState::IssueList => issue_list::render(frame, app),
         AppState::IssueDetail => issue_detail::render(frame, app),
         AppState::IssueCommentList => issue_comment_list::render(frame, app),
@@ -134,6 +171,34 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         render_symbol_popup(frame, popup);
     }
 
+    if let Some(ref peek) = app.hunk_peek {
+        if matches!(app.state, AppState::DiffView | AppState::SplitViewDiff) {
+            hunk_peek::render(frame, app, peek);
+        }
+    }
+
+    if let Some(ref popup) = app.hunk_explain {
+        if matches!(app.state, AppState::DiffView | AppState::SplitViewDiff) {
+            hunk_explain::render(frame, app, popup);
+        }
+    }
+
+    if let Some(ref picker) = app.review_picker {
+        review_picker::render(frame, app, picker);
+    }
+
+    if let Some(ref popup) = app.blame_popup {
+        if matches!(app.state, AppState::DiffView | AppState::SplitViewDiff) {
+            blame_popup::render(frame, app, popup);
+        }
+    }
+
+    if let Some(ref picker) = app.theme_picker {
+        if matches!(app.state, AppState::DiffView | AppState::SplitViewDiff) {
+            theme_picker::render(frame, app, picker);
+        }
+    }
+
     if let Some(ref shell) = app.shell_state {
         match &shell.phase {
             ShellPhase::Input => {} // Handled by build_footer_line + build_footer_block_with_border
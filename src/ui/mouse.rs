@@ -0,0 +1,89 @@
+//! マウス操作の当たり判定
+//!
+//! render 中にファイル一覧・diff 本体の枠付き領域を記録し、入力処理では
+//! クリック位置をその領域内の表示行に変換する。
+
+use ratatui::layout::{Margin, Position, Rect};
+use std::cell::Cell;
+
+/// 今フレームで描画したクリック対象の領域（いずれも枠線込み）
+#[derive(Default)]
+pub struct MouseLayout {
+    pub(crate) file_list: Cell<Option<Rect>>,
+    pub(crate) diff: Cell<Option<Rect>>,
+}
+
+impl MouseLayout {
+    /// フレームの描画前に呼ぶ（描画されなかった領域をクリック対象から外す）
+    pub(crate) fn clear(&self) {
+        self.file_list.set(None);
+        self.diff.set(None);
+    }
+}
+
+/// 枠付き領域 `area` 内の `(column, row)` が枠の内側の何行目かを返す。
+/// 枠線上や領域外なら `None`。
+pub(crate) fn row_in(area: Rect, column: u16, row: u16) -> Option<usize> {
+    let inner = area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    inner
+        .contains(Position::new(column, row))
+        .then(|| (row - inner.y) as usize)
+}
+
+/// 先頭に `offset` 番目の項目を表示している一覧で、クリック位置の項目インデックスを返す。
+/// 最終項目より下の空き行は `None`。
+pub(crate) fn list_index_at(
+    area: Rect,
+    offset: usize,
+    len: usize,
+    column: u16,
+    row: u16,
+) -> Option<usize> {
+    let index = offset + row_in(area, column, row)?;
+    (index < len).then_some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // x=10..30, y=5..15（枠の内側は x=11..29, y=6..14）
+    const AREA: Rect = Rect {
+        x: 10,
+        y: 5,
+        width: 20,
+        height: 10,
+    };
+
+    #[test]
+    fn test_row_in_excludes_borders() {
+        assert_eq!(row_in(AREA, 11, 6), Some(0));
+        assert_eq!(row_in(AREA, 28, 13), Some(7));
+        // 上下左右の枠線
+        assert_eq!(row_in(AREA, 15, 5), None);
+        assert_eq!(row_in(AREA, 15, 14), None);
+        assert_eq!(row_in(AREA, 10, 8), None);
+        assert_eq!(row_in(AREA, 29, 8), None);
+        // 領域外
+        assert_eq!(row_in(AREA, 40, 8), None);
+        assert_eq!(row_in(AREA, 15, 2), None);
+    }
+
+    #[test]
+    fn test_list_index_at_applies_scroll_offset() {
+        assert_eq!(list_index_at(AREA, 0, 100, 15, 6), Some(0));
+        assert_eq!(list_index_at(AREA, 0, 100, 15, 9), Some(3));
+        assert_eq!(list_index_at(AREA, 40, 100, 15, 6), Some(40));
+        assert_eq!(list_index_at(AREA, 40, 100, 15, 13), Some(47));
+    }
+
+    #[test]
+    fn test_list_index_at_ignores_rows_past_the_end() {
+        assert_eq!(list_index_at(AREA, 0, 3, 15, 8), Some(2));
+        assert_eq!(list_index_at(AREA, 0, 3, 15, 9), None);
+        assert_eq!(list_index_at(AREA, 0, 0, 15, 6), None);
+    }
+}
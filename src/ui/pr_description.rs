@@ -8,7 +8,8 @@ use ratatui::{
 
 use crate::app::App;
 use crate::diff::LineType;
-use crate::ui::common::{build_ci_status_span, build_pr_info};
+use crate::ui::common::{build_ci_status_span, build_pr_info, build_read_only_span};
+use crate::ui::markdown::markdown_to_lines;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     let chunks = Layout::default()
@@ -37,6 +38,7 @@ fn render_header(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         ),
         Span::raw(" - "),
         Span::styled(pr_info, Style::default().fg(Color::Cyan)),
+        build_read_only_span(app),
         ci_span,
     ]))
     .block(Block::default().borders(Borders::ALL));
@@ -56,27 +58,33 @@ fn render_body(frame: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
         return;
     };
 
-    let lines: Vec<Line<'_>> = cache
-        .lines
-        .iter()
-        .filter(|cached| cached.line_type != LineType::Header)
-        .map(|cached| {
-            let spans: Vec<Span<'_>> = cached
-                .spans
-                .iter()
-                .enumerate()
-                .map(|(i, s)| {
-                    let text = cache.resolve(s.content);
-                    if i == 0 && text.starts_with(' ') {
-                        Span::styled(text[1..].to_string(), s.style)
-                    } else {
-                        Span::styled(text.to_string(), s.style)
-                    }
-                })
-                .collect();
-            Line::from(spans)
-        })
-        .collect();
+    // rich 表示時は Markdown を整形して表示する（見出し・リスト・コードブロック等）
+    let lines: Vec<Line<'_>> = if app.is_markdown_rich() {
+        let body = app.pr().and_then(|pr| pr.body.as_deref()).unwrap_or("");
+        markdown_to_lines(body)
+    } else {
+        cache
+            .lines
+            .iter()
+            .filter(|cached| cached.line_type != LineType::Header)
+            .map(|cached| {
+                let spans: Vec<Span<'_>> = cached
+                    .spans
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| {
+                        let text = cache.resolve(s.content);
+                        if i == 0 && text.starts_with(' ') {
+                            Span::styled(text[1..].to_string(), s.style)
+                        } else {
+                            Span::styled(text.to_string(), s.style)
+                        }
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect()
+    };
 
     // Scroll offset is measured in *wrapped* (visual) rows because
     // `Paragraph::scroll` applies after wrapping. Count wrapped rows at the inner
@@ -187,6 +195,9 @@ mod tests {
                 login: "user".to_string(),
             },
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: vec![],
+            html_url: None,
+            changed_files: None,
         });
         app.data_state = DataState::Loaded { pr, files: vec![] };
         app.open_pr_description();
@@ -218,6 +229,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_markdown_rich_renders_formatted_body() {
+        let mut app = app_with_pr_body("# Title\n\n- item\n  - nested\n\n```\nlet x = 1;\n```");
+        app.toggle_markdown_rich();
+        app.rebuild_pr_description_cache();
+        assert_snapshot!(render_at(&mut app, 40, 12), @"
+        ┌──────────────────────────────────────┐
+        │PR Description - PR #1: Test PR by @us│
+        └──────────────────────────────────────┘
+        ┌Description───────────────────────────┐
+        │Title                                 │
+        │                                      │
+        │• item                                │
+        │  ◦ nested                            │
+        │                                      │
+        │  let x = 1;                          │
+        └──────────────────────────────────────┘
+         ? Help | ! Shell | q/Esc Back
+        ");
+    }
+
     #[test]
     fn test_empty_no_description_cache() {
         let mut app = App::new_for_test();
@@ -38,7 +38,10 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         .split(frame.area());
 
     let filter_str = app.prs.pr_list_state_filter.display_name();
-    let header_text = format!("PR List: {} ({})", app.repo, filter_str);
+    let mut header_text = format!("PR List: {} ({})", app.repo, filter_str);
+    if !app.prs.pr_list_filters.is_empty() {
+        header_text.push_str(&format!(" [{}]", app.prs.pr_list_filters.query()));
+    }
     let header =
         Paragraph::new(header_text).block(Block::default().borders(Borders::ALL).title("octorus"));
     frame.render_widget(header, chunks[0]);
@@ -114,7 +117,13 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             };
 
             let inner_width = chunks[1].width.saturating_sub(3) as usize;
-            let items = build_pr_list_items_ref(&display_prs, display_selected, inner_width);
+            let mut items = build_pr_list_items_ref(&display_prs, display_selected, inner_width);
+            if matches!(app.prs.pr_list, crate::app::LoadState::LoadingMore(_)) {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    format!("{} Loading more...", app.spinner_char()),
+                    Style::default().fg(Color::Yellow),
+                ))));
+            }
 
             let mut list_state = ListState::default()
                 .with_offset(app.prs.pr_list_scroll_offset)
@@ -0,0 +1,69 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, ReviewAction, ReviewPickerState};
+
+/// `review_submit`: 送信するレビューの判定を選ぶモーダル
+pub fn render(frame: &mut Frame, app: &App, picker: &ReviewPickerState) {
+    let area = frame.area();
+    let height = if picker.confirming_approve { 10 } else { 8 };
+    let popup_area = super::centered_rect(56.min(area.width), height, area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let kb = &app.config.keybindings;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Submit review ")
+        .title_bottom(format!(
+            " {}/{}: move | Enter: submit | Esc: cancel ",
+            kb.move_down.display(),
+            kb.move_up.display()
+        ));
+
+    let mut lines: Vec<Line> = crate::app::REVIEW_PICKER_ACTIONS
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let (label, color) = match action {
+                ReviewAction::Approve => ("Approve", Color::Green),
+                ReviewAction::RequestChanges => ("Request changes", Color::Red),
+                ReviewAction::Comment => ("Comment", Color::Yellow),
+            };
+            if i == picker.selected {
+                Line::from(Span::styled(
+                    format!("> {}", label),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::from(format!("  {}", label))
+            }
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "Pending inline comments: {} ({} suggestions)",
+            picker.pending_comments, picker.pending_suggestions
+        ),
+        Style::default().fg(Color::DarkGray),
+    )));
+    if picker.confirming_approve {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} unresolved suggestion(s) pending. Enter again to approve",
+                picker.pending_suggestions
+            ),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
@@ -11,7 +11,10 @@ use ratatui::{
 
 use super::common::render_rally_status_bar;
 use super::diff_view;
-use super::file_list::{build_file_list_items, build_file_list_items_ref, build_tree_row_item};
+use super::file_list::{
+    build_file_list_items_ref, build_file_list_row_items, build_tree_row_item,
+    file_sort_title_suffix,
+};
 use crate::app::{App, AppState, DataState};
 use crate::github::ChangedFile;
 
@@ -63,7 +66,9 @@ fn render_file_list_pane(
     let has_filter_bar = app
         .file_list_filter
         .as_ref()
-        .is_some_and(|f| f.input_active);
+        .is_some_and(|f| f.input_active)
+        || app.file_glob_input.is_some()
+        || app.diff_base_input.is_some();
 
     let mut constraints = vec![Constraint::Length(3), Constraint::Min(0)];
     if has_filter_bar {
@@ -76,8 +81,10 @@ fn render_file_list_pane(
         .constraints(constraints)
         .split(area);
 
+    app.mouse_layout.file_list.set(Some(chunks[1]));
+
     let pr_info = match &app.data_state {
-        DataState::Loaded { pr, .. } => {
+        DataState::Loaded { pr, .. } | DataState::PartiallyLoaded { pr, .. } => {
             format!("PR #{}: {}", pr.number, pr.title)
         }
         _ => match app.pr_number {
@@ -99,14 +106,19 @@ fn render_file_list_pane(
 
     if let Some(ref filter) = app.file_list_filter {
         if filter.matched_indices.is_empty() {
-            let empty_msg = format!("No matches for '{}'", filter.query);
+            let empty_msg = super::file_list::filtered_files_empty_message(app, filter);
             let empty = Paragraph::new(empty_msg)
                 .style(Style::default().fg(Color::DarkGray))
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
                         .border_style(Style::default().fg(border_color))
-                        .title(format!("Files (0/{})", total_files)),
+                        .title(super::file_list::filtered_files_title(
+                            "Files",
+                            app,
+                            0,
+                            total_files,
+                        )),
                 );
             frame.render_widget(empty, chunks[1]);
         } else {
@@ -126,7 +138,12 @@ fn render_file_list_pane(
                     Block::default()
                         .borders(Borders::ALL)
                         .border_style(Style::default().fg(border_color))
-                        .title(format!("Files ({}/{})", display_count, total_files)),
+                        .title(super::file_list::filtered_files_title(
+                            "Files",
+                            app,
+                            display_count,
+                            total_files,
+                        )),
                 )
                 .highlight_style(Style::default().bg(Color::DarkGray));
 
@@ -217,35 +234,48 @@ fn render_file_list_pane(
             );
         }
     } else {
-        let items = build_file_list_items(files, app.selected_file, &app.cmt.file_comment_counts);
+        let rows = app.file_list_rows();
+        let display_selected = app.selected_file_list_row(&rows);
+        let items = build_file_list_row_items(
+            files,
+            &rows,
+            display_selected,
+            app.generated_files_expanded,
+            &app.cmt.file_comment_counts,
+        );
+        let row_count = rows.len();
 
         let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(border_color))
-                    .title(format!("Files ({})", total_files)),
+                    .title(format!(
+                        "Files ({}){}",
+                        total_files,
+                        file_sort_title_suffix(app)
+                    )),
             )
             .highlight_style(Style::default().bg(Color::DarkGray));
 
         let mut list_state = ListState::default()
             .with_offset(app.file_list_scroll_offset)
-            .with_selected(Some(app.selected_file));
+            .with_selected(Some(display_selected));
 
         frame.render_stateful_widget(list, chunks[1], &mut list_state);
 
         app.file_list_scroll_offset = list_state.offset();
-        if let Some(sel) = list_state.selected() {
-            app.selected_file = sel;
+        if let Some(&row) = list_state.selected().and_then(|sel| rows.get(sel)) {
+            app.select_file_list_row(row);
         }
 
-        if total_files > 1 {
+        if row_count > 1 {
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(Some("▲"))
                 .end_symbol(Some("▼"));
 
             let mut scrollbar_state =
-                ScrollbarState::new(total_files.saturating_sub(1)).position(app.selected_file);
+                ScrollbarState::new(row_count.saturating_sub(1)).position(display_selected);
 
             frame.render_stateful_widget(
                 scrollbar,
@@ -261,7 +291,11 @@ fn render_file_list_pane(
     let mut next_chunk = 2;
 
     if has_filter_bar {
-        if let Some(ref filter) = app.file_list_filter {
+        if let Some(ref pattern) = app.file_glob_input {
+            super::common::render_file_glob_bar(frame, chunks[next_chunk], pattern);
+        } else if let Some(ref rev) = app.diff_base_input {
+            super::common::render_diff_base_bar(frame, chunks[next_chunk], rev);
+        } else if let Some(ref filter) = app.file_list_filter {
             let cursor_display = format!("/{}", filter.query);
             let filter_bar = Paragraph::new(Line::from(vec![
                 Span::styled("Filter: ", Style::default().fg(Color::Cyan)),
@@ -376,7 +410,7 @@ fn render_diff_pane_with_comments(
     render_diff_header(frame, app, chunks[0], border_color);
     render_diff_body(frame, app, chunks[1], border_color);
 
-    let indices = app.get_comment_indices_at_current_line();
+    let indices = app.visible_inline_comment_indices();
     let mut lines: Vec<Line> = vec![];
 
     if indices.is_empty() {
@@ -397,6 +431,14 @@ fn render_diff_pane_with_comments(
                 )));
             }
 
+            if app.is_thread_collapsed(idx) {
+                lines.push(Line::from(Span::styled(
+                    app.collapsed_thread_summary(idx),
+                    Style::default().fg(Color::DarkGray),
+                )));
+                continue;
+            }
+
             lines.push(Line::from(vec![
                 Span::styled(
                     format!("@{}", comment.user.login),
@@ -415,7 +457,7 @@ fn render_diff_pane_with_comments(
         }
     }
 
-    let title = "Comments (j/k/↑↓: scroll, c: comment, s: suggest, r: reply)";
+    let title = "Comments (j/k/↑↓: scroll, c: comment, s: suggest, r: reply, za/zM/zR: fold)";
     let total_lines = lines.len();
 
     let paragraph = Paragraph::new(lines)
@@ -463,8 +505,12 @@ fn render_diff_header(
         .get(app.selected_file)
         .map(|file| {
             format!(
-                "{} (+{} -{})",
-                file.filename, file.additions, file.deletions
+                "{} (+{} -{}){}{}",
+                file.filename,
+                file.additions,
+                file.deletions,
+                diff_view::hunk_filter_header_suffix(app),
+                diff_view::diff_search_header_suffix(app)
             )
         })
         .unwrap_or_else(|| "No file selected".to_string());
@@ -484,24 +530,18 @@ fn render_diff_body(
     area: ratatui::layout::Rect,
     border_color: Color,
 ) {
+    app.mouse_layout.diff.set(Some(area));
     let visible_height = area.height.saturating_sub(2) as usize;
     let (lines, scroll_row) = if let Some(ref cache) = app.diff_store.current {
         let line_count = cache.lines.len();
         // Slice from scroll_offset, bounded to visible viewport + buffer for wrap handling.
         let max_scroll = line_count.saturating_sub(visible_height);
         let start = app.diff_scroll.scroll_offset.min(max_scroll);
-        let end = (start + visible_height + 10).min(line_count);
-        let multiline_range = app
-            .multiline_selection
-            .as_ref()
-            .map(|s| (s.start(), s.end()));
-        let rendered = diff_view::render_cached_lines(
+        let rendered = diff_view::render_diff_window(
+            app,
             cache,
-            start..end,
-            app.diff_scroll.selected_line,
-            &app.cmt.file_comment_lines,
-            app.config.diff.bg_color,
-            multiline_range,
+            start,
+            visible_height,
             area.width.saturating_sub(2),
         );
         (rendered, 0u16)
@@ -513,6 +553,8 @@ fn render_diff_body(
                 None => {
                     if app.is_lazy_diff_loading() {
                         vec![Line::from("Loading diff...")]
+                    } else if app.is_current_patch_incomplete() {
+                        diff_view::incomplete_patch_lines(app)
                     } else {
                         vec![Line::from("No diff available")]
                     }
@@ -523,6 +565,7 @@ fn render_diff_body(
         (rendered, app.diff_scroll.scroll_offset as u16)
     };
 
+    let line_count = lines.len() as u16;
     let diff_block = Paragraph::new(lines)
         .block(
             Block::default()
@@ -533,6 +576,7 @@ fn render_diff_body(
         .scroll((scroll_row, 0));
 
     frame.render_widget(diff_block, area);
+    super::image_preview::request_below_text(app, area, line_count);
 
     if let Some(ref cache) = app.diff_store.current {
         let total_lines = cache.lines.len();
@@ -0,0 +1,144 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Margin},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState,
+    },
+    Frame,
+};
+
+use super::common::build_pr_info;
+use crate::app::{App, SymbolDigestRow};
+use crate::github::ChangedFile;
+use crate::symbol::SymbolChange;
+
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(format!("Symbol digest: {}", build_pr_info(app)))
+        .block(Block::default().borders(Borders::ALL).title("octorus"));
+    frame.render_widget(header, chunks[0]);
+
+    let Some(ref digest) = app.symbol_digest else {
+        return;
+    };
+    let total = digest.rows.len();
+    let symbol_count = digest.rows.iter().filter(|r| r.symbol.is_some()).count();
+    let items = build_digest_items(&digest.rows, app.files(), digest.selected);
+
+    let mut list_state = ListState::default()
+        .with_offset(digest.scroll_offset)
+        .with_selected(Some(digest.selected));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Changed Symbols ({})", symbol_count)),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray));
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    if total > 1 {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("▲"))
+            .end_symbol(Some("▼"));
+        let mut scrollbar_state =
+            ScrollbarState::new(total.saturating_sub(1)).position(digest.selected);
+        frame.render_stateful_widget(
+            scrollbar,
+            chunks[1].inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+    }
+    if let Some(ref mut digest) = app.symbol_digest {
+        digest.scroll_offset = list_state.offset();
+    }
+
+    let kb = &app.config.keybindings;
+    let help_text = format!(
+        "{}/{}: move | {}: open diff | {}: toggle digest | {}",
+        kb.move_down.display(),
+        kb.move_up.display(),
+        kb.open_panel.display(),
+        kb.symbol_digest.display(),
+        super::footer::footer_hint_back(kb),
+    );
+    let footer = Paragraph::new(help_text).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, chunks[2]);
+}
+
+fn change_color(change: SymbolChange) -> Color {
+    match change {
+        SymbolChange::Added => Color::Green,
+        SymbolChange::Removed => Color::Red,
+        SymbolChange::Modified => Color::Yellow,
+    }
+}
+
+fn build_digest_items(
+    rows: &[SymbolDigestRow],
+    files: &[ChangedFile],
+    selected: usize,
+) -> Vec<ListItem<'static>> {
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let is_selected = i == selected;
+            let line = match (&row.symbol, files.get(row.file_index)) {
+                (Some(symbol), _) => Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(
+                        format!("{} ", symbol.change.marker()),
+                        Style::default().fg(change_color(symbol.change)),
+                    ),
+                    Span::styled(
+                        symbol.name.clone(),
+                        if is_selected {
+                            Style::default().fg(Color::Yellow)
+                        } else {
+                            Style::default()
+                        },
+                    ),
+                ]),
+                (None, Some(file)) => {
+                    let has_symbols = rows
+                        .get(i + 1)
+                        .is_some_and(|next| next.file_index == row.file_index);
+                    let mut spans = vec![
+                        Span::styled(
+                            file.filename.clone(),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!(" (+{} -{})", file.additions, file.deletions),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ];
+                    if !has_symbols {
+                        let note = if file.patch.is_some() {
+                            "  no symbol changes"
+                        } else {
+                            "  diff not loaded"
+                        };
+                        spans.push(Span::styled(note, Style::default().fg(Color::DarkGray)));
+                    }
+                    Line::from(spans)
+                }
+                (None, None) => Line::from(""),
+            };
+            ListItem::new(line)
+        })
+        .collect()
+}
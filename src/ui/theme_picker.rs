@@ -0,0 +1,51 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::app::{App, ThemePickerState};
+
+/// `choose_theme`: テーマ一覧。試し適用した diff が見えるよう右上に小さく出す。
+pub fn render(frame: &mut Frame, app: &App, picker: &ThemePickerState) {
+    let area = frame.area();
+    let name_width = picker.themes.iter().map(|t| t.len()).max().unwrap_or(0) as u16;
+    let width = (name_width + 6).max(30).min(area.width);
+    let height = (picker.themes.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.x + area.width.saturating_sub(width + 1),
+        area.y + 1.min(area.height),
+        width,
+        height,
+    );
+
+    frame.render_widget(Clear, popup_area);
+
+    let kb = &app.config.keybindings;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Theme ")
+        .title_bottom(format!(
+            " {}/{} Enter Esc ",
+            kb.move_down.display(),
+            kb.move_up.display()
+        ));
+
+    let items: Vec<ListItem> = picker
+        .themes
+        .iter()
+        .map(|theme| {
+            let marker = if *theme == picker.original { "*" } else { " " };
+            ListItem::new(format!("{} {}", marker, theme))
+        })
+        .collect();
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+    );
+    let mut state = ListState::default().with_selected(Some(picker.selected));
+    frame.render_stateful_widget(list, popup_area, &mut state);
+}